@@ -273,6 +273,7 @@ fn export_fb_inner(
     let blocks = fireball.analyze_all().map_err(|err| err.to_string())?;
     let mut ast = generate_ast_with_pre_defined_symbols(blocks, fireball.get_defined())
         .map_err(|err| err.to_string())?;
+    ast.set_calling_convention(fireball.get_calling_convention());
     ast = ast.optimize(None).map_err(|err| err.to_string())?;
 
     let source_path = absolute_path(&request.input_path)?;
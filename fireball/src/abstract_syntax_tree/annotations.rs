@@ -0,0 +1,313 @@
+//! Module holding the names, types, parameter counts and comments given by the user while
+//! reading the decompiled code.
+//!
+//! Annotations are kept apart from the AST, keyed by what stays the same when the code is
+//! decompiled again (function addresses, stack slots), and applied by AST generation and
+//! optimization.
+
+use crate::abstract_syntax_tree::{AstValueType, AstVariable, PrintWithConfig};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{Read, Write},
+    path::Path,
+};
+
+const ANNOTATIONS_MAGIC: &str = "fba";
+const ANNOTATIONS_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AnnotationsPayload {
+    magic: String,
+    version: u32,
+    functions: BTreeMap<u64, AstFunctionAnnotation>,
+}
+
+/// Annotations of every function, by entry address
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AstAnnotations {
+    functions: BTreeMap<u64, AstFunctionAnnotation>,
+}
+
+/// Annotations of a function
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AstFunctionAnnotation {
+    pub name: Option<String>,
+    /// Return type, as printed (`uint32_t`)
+    pub return_type: Option<String>,
+    /// Amount of parameters, overriding the detected ones
+    pub parameter_count: Option<usize>,
+    /// Comments, by virtual address of the instruction they are put on
+    pub comments: BTreeMap<u64, String>,
+    pub variables: BTreeMap<AstVariableKey, AstVariableAnnotation>,
+}
+
+/// Annotations of a variable
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AstVariableAnnotation {
+    pub name: Option<String>,
+    /// Type, as printed (`char*`)
+    pub var_type: Option<String>,
+}
+
+/// Identifies a variable of a function across decompilations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AstVariableKey {
+    /// Stack slot the variable is stored in, by offset from the frame base pointer
+    FrameOffset(i64),
+    /// Index of the variable in its function (`12` of `v12`)
+    Index(u32),
+}
+
+impl AstVariableKey {
+    /// Returns the key of a variable: its stack slot if known, its index otherwise.
+    ///
+    /// ### Note
+    /// - Variables living in registers are told apart by index, since many of them share a
+    ///   register. Indexes stay the same as long as the same blocks are decompiled with the same
+    ///   optimizations.
+    pub fn of(variable: &AstVariable) -> Self {
+        match variable.frame_offset() {
+            Some(offset) => Self::FrameOffset(offset),
+            None => Self::Index(variable.id.index()),
+        }
+    }
+}
+
+impl AstFunctionAnnotation {
+    fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.return_type.is_none()
+            && self.parameter_count.is_none()
+            && self.comments.is_empty()
+            && self.variables.is_empty()
+    }
+}
+
+impl AstAnnotations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Returns the annotations of the function starting at the address.
+    pub fn function(&self, address: u64) -> Option<&AstFunctionAnnotation> {
+        self.functions.get(&address)
+    }
+
+    /// Returns the annotated functions, by entry address.
+    pub fn functions(&self) -> impl Iterator<Item = (u64, &AstFunctionAnnotation)> {
+        self.functions.iter().map(|(address, x)| (*address, x))
+    }
+
+    /// Names the function starting at the address, or gives it back its default name.
+    pub fn set_function_name(&mut self, address: u64, name: Option<String>) {
+        self.edit(address, |function| function.name = non_empty(name));
+    }
+
+    /// Sets the return type of the function starting at the address, or lets it be detected.
+    pub fn set_return_type(&mut self, address: u64, return_type: Option<&AstValueType>) {
+        let return_type = return_type.map(|x| x.to_string_with_config(None));
+        self.edit(address, |function| function.return_type = return_type);
+    }
+
+    /// Sets the amount of parameters of the function starting at the address, or lets it be
+    /// detected.
+    pub fn set_parameter_count(&mut self, address: u64, count: Option<usize>) {
+        self.edit(address, |function| function.parameter_count = count);
+    }
+
+    /// Puts a comment on an instruction of a function, or removes it.
+    ///
+    /// ### Arguments
+    /// - `function: u64` - entry address of the function
+    /// - `address: u64` - virtual address of the instruction
+    /// - `comment: Option<String>` - comment, `None` or empty to remove it
+    pub fn set_comment(&mut self, function: u64, address: u64, comment: Option<String>) {
+        self.edit(function, |function| match non_empty(comment) {
+            Some(comment) => {
+                function.comments.insert(address, comment);
+            }
+            None => {
+                function.comments.remove(&address);
+            }
+        });
+    }
+
+    /// Names a variable of a function, or gives it back its default name.
+    pub fn set_variable_name(&mut self, function: u64, key: AstVariableKey, name: Option<String>) {
+        self.edit_variable(function, key, |variable| variable.name = non_empty(name));
+    }
+
+    /// Sets the type of a variable of a function, or lets it be detected.
+    pub fn set_variable_type(
+        &mut self,
+        function: u64,
+        key: AstVariableKey,
+        var_type: Option<&AstValueType>,
+    ) {
+        let var_type = var_type.map(|x| x.to_string_with_config(None));
+        self.edit_variable(function, key, |variable| variable.var_type = var_type);
+    }
+
+    fn edit(&mut self, address: u64, f: impl FnOnce(&mut AstFunctionAnnotation)) {
+        let function = self.functions.entry(address).or_default();
+        f(function);
+        if function.is_empty() {
+            self.functions.remove(&address);
+        }
+    }
+
+    fn edit_variable(
+        &mut self,
+        function: u64,
+        key: AstVariableKey,
+        f: impl FnOnce(&mut AstVariableAnnotation),
+    ) {
+        self.edit(function, |function| {
+            let variable = function.variables.entry(key).or_default();
+            f(variable);
+            if variable.name.is_none() && variable.var_type.is_none() {
+                function.variables.remove(&key);
+            }
+        });
+    }
+
+    /// Encodes the annotations into bytes (bitcode + gzip).
+    pub fn encode(&self) -> Result<Vec<u8>, String> {
+        let payload = AnnotationsPayload {
+            magic: ANNOTATIONS_MAGIC.to_string(),
+            version: ANNOTATIONS_VERSION,
+            functions: self.functions.clone(),
+        };
+        let encoded = bitcode::serialize(&payload)
+            .map_err(|err| format!("failed to encode annotations: {err}"))?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&encoded)
+            .map_err(|err| format!("failed to compress annotations: {err}"))?;
+        encoder
+            .finish()
+            .map_err(|err| format!("failed to finish annotations: {err}"))
+    }
+
+    /// Decodes annotations encoded by [`AstAnnotations::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, String> {
+        let mut decoder = GzDecoder::new(bytes);
+        let mut decoded = Vec::new();
+        decoder
+            .read_to_end(&mut decoded)
+            .map_err(|err| format!("failed to decompress annotations: {err}"))?;
+        let payload = bitcode::deserialize::<AnnotationsPayload>(&decoded)
+            .map_err(|err| format!("failed to decode annotations: {err}"))?;
+        if payload.magic != ANNOTATIONS_MAGIC {
+            return Err(format!("invalid annotations magic `{}`", payload.magic));
+        }
+        if payload.version != ANNOTATIONS_VERSION {
+            return Err(format!(
+                "unsupported annotations version `{}` (expected `{ANNOTATIONS_VERSION}`)",
+                payload.version
+            ));
+        }
+        Ok(Self {
+            functions: payload.functions,
+        })
+    }
+
+    /// Saves the annotations to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        fs::write(path, self.encode()?)
+            .map_err(|err| format!("failed to write {}: {err}", path.display()))
+    }
+
+    /// Loads annotations saved by [`AstAnnotations::save`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let bytes =
+            fs::read(path).map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        Self::decode(&bytes)
+    }
+}
+
+fn non_empty(text: Option<String>) -> Option<String> {
+    text.map(|x| x.trim().to_owned()).filter(|x| !x.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotated_ast_is_shareable() {
+        // the ast and its annotations are shared through `Arc`, which must be able to cross threads
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<AstAnnotations>();
+        assert_send_sync::<crate::abstract_syntax_tree::Ast>();
+    }
+
+    #[test]
+    fn test_annotations_round_trip() {
+        let mut annotations = AstAnnotations::new();
+        annotations.set_function_name(0x401000, Some("parse_header".to_owned()));
+        annotations.set_return_type(0x401000, Some(&AstValueType::UInt32));
+        annotations.set_parameter_count(0x401000, Some(2));
+        annotations.set_comment(0x401000, 0x401010, Some("checks the magic".to_owned()));
+        annotations.set_variable_name(
+            0x401000,
+            AstVariableKey::FrameOffset(-0x28),
+            Some("header".to_owned()),
+        );
+        annotations.set_variable_type(
+            0x401000,
+            AstVariableKey::Index(12),
+            Some(&AstValueType::Pointer(Box::new(AstValueType::Char))),
+        );
+
+        let decoded = AstAnnotations::decode(&annotations.encode().unwrap()).unwrap();
+        assert_eq!(decoded, annotations);
+        let function = decoded.function(0x401000).unwrap();
+        assert_eq!(function.return_type.as_deref(), Some("uint32_t"));
+        assert_eq!(
+            function.variables[&AstVariableKey::Index(12)]
+                .var_type
+                .as_deref(),
+            Some("char*")
+        );
+    }
+
+    #[test]
+    fn test_annotations_removal() {
+        let mut annotations = AstAnnotations::new();
+        annotations.set_function_name(0x401000, Some("main".to_owned()));
+        annotations.set_variable_name(0x401000, AstVariableKey::Index(1), Some("x".to_owned()));
+        annotations.set_variable_name(0x401000, AstVariableKey::Index(1), Some(" ".to_owned()));
+        assert!(annotations.function(0x401000).unwrap().variables.is_empty());
+        annotations.set_function_name(0x401000, None);
+        assert!(annotations.is_empty());
+    }
+
+    #[test]
+    fn test_value_type_parse() {
+        for ty in [
+            AstValueType::Int32,
+            AstValueType::Pointer(Box::new(AstValueType::Char)),
+            AstValueType::Array(Box::new(AstValueType::UInt8), 16),
+            AstValueType::Array(
+                Box::new(AstValueType::Pointer(Box::new(AstValueType::Void))),
+                4,
+            ),
+            AstValueType::Struct("header".to_owned(), Vec::new()),
+        ] {
+            let printed = ty.to_string_with_config(None);
+            assert_eq!(printed.parse::<AstValueType>(), Ok(ty), "{printed}");
+        }
+        assert!("int33_t".parse::<AstValueType>().is_err());
+        assert!("int[x]".parse::<AstValueType>().is_err());
+    }
+}
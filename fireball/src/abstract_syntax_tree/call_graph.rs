@@ -0,0 +1,392 @@
+//! Module building the call graph between the functions of an AST.
+
+use super::*;
+use hashbrown::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Destination of a call in a [`CallGraph`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CallTarget {
+    /// Function of the AST
+    Function(AstFunctionId),
+    /// Address outside the functions of the AST (e.g., imports, functions not analyzed)
+    Address(u64),
+    /// Destination only known at runtime
+    Dynamic,
+}
+
+/// Calls between the functions of an AST
+///
+/// Built from the call relations of the blocks each function is made of.
+#[derive(Debug, Clone, Default)]
+pub struct CallGraph {
+    /// Destinations of the calls each function makes
+    calls: BTreeMap<AstFunctionId, BTreeSet<CallTarget>>,
+    /// Functions calling each function
+    callers: BTreeMap<AstFunctionId, BTreeSet<AstFunctionId>>,
+    /// Names of the functions and called addresses
+    names: HashMap<u64, String>,
+    /// Strongly connected components, callees before callers
+    components: Vec<Vec<AstFunctionId>>,
+    /// Functions calling themselves, directly or through other functions
+    recursive: BTreeSet<AstFunctionId>,
+}
+
+impl CallGraph {
+    /// Builds the call graph of the current version of every function.
+    ///
+    /// ### Arguments
+    /// - `ast: &Ast` - AST to build the graph of
+    ///
+    /// ### Returns
+    /// - `Self` - the graph
+    pub fn new(ast: &Ast) -> Self {
+        let functions = ast.functions.read().unwrap();
+        let mut edges = Vec::new();
+        let mut names = HashMap::new();
+        for (id, version) in ast.function_versions.iter() {
+            let Some(function) = functions.get(id).and_then(|x| x.get(version)) else {
+                continue;
+            };
+            names.insert(id.address, function.name());
+            let blocks = function.origin_ir.get_control_flow().get_blocks();
+            for call in blocks.iter().flat_map(|x| x.get_calls()) {
+                let target = match call {
+                    Some(address) => {
+                        let address = address.get_virtual_address();
+                        let target = AstFunctionId { address };
+                        if ast.function_versions.contains_key(&target) {
+                            CallTarget::Function(target)
+                        } else {
+                            if let Some(name) = ast.pre_defined_symbols.get(&address) {
+                                names.insert(address, name.clone());
+                            }
+                            CallTarget::Address(address)
+                        }
+                    }
+                    None => CallTarget::Dynamic,
+                };
+                edges.push((*id, target));
+            }
+        }
+        let mut graph = Self::from_edges(ast.function_versions.keys().copied(), edges);
+        graph.names.extend(names);
+        graph
+    }
+
+    /// Builds the graph from raw edges.
+    ///
+    /// ### Arguments
+    /// - `functions: impl IntoIterator<Item = AstFunctionId>` - every function of the graph
+    /// - `edges: impl IntoIterator<Item = (AstFunctionId, CallTarget)>` - (caller, callee) of each call
+    pub(crate) fn from_edges(
+        functions: impl IntoIterator<Item = AstFunctionId>,
+        edges: impl IntoIterator<Item = (AstFunctionId, CallTarget)>,
+    ) -> Self {
+        let mut calls: BTreeMap<_, BTreeSet<_>> = functions
+            .into_iter()
+            .map(|x| (x, BTreeSet::new()))
+            .collect();
+        let mut callers: BTreeMap<_, BTreeSet<_>> =
+            calls.keys().map(|x| (*x, BTreeSet::new())).collect();
+        for (caller, target) in edges {
+            calls.entry(caller).or_default().insert(target);
+            if let CallTarget::Function(callee) = target {
+                callers.entry(callee).or_default().insert(caller);
+                calls.entry(callee).or_default();
+            }
+            callers.entry(caller).or_default();
+        }
+        let mut graph = Self {
+            calls,
+            callers,
+            names: HashMap::new(),
+            components: Vec::new(),
+            recursive: BTreeSet::new(),
+        };
+        graph.components = graph.find_components();
+        graph.recursive = graph
+            .components
+            .iter()
+            .filter(|x| x.len() > 1 || graph.callees(x[0]).any(|callee| callee == x[0]))
+            .flatten()
+            .copied()
+            .collect();
+        graph
+    }
+
+    /// Returns every function of the graph, by address.
+    pub fn functions(&self) -> impl Iterator<Item = AstFunctionId> + '_ {
+        self.calls.keys().copied()
+    }
+
+    /// Returns the destinations of the calls a function makes.
+    pub fn call_targets(&self, function: AstFunctionId) -> impl Iterator<Item = CallTarget> + '_ {
+        self.calls.get(&function).into_iter().flatten().copied()
+    }
+
+    /// Returns the functions of the AST a function calls.
+    pub fn callees(&self, function: AstFunctionId) -> impl Iterator<Item = AstFunctionId> + '_ {
+        self.call_targets(function).filter_map(|x| match x {
+            CallTarget::Function(x) => Some(x),
+            _ => None,
+        })
+    }
+
+    /// Returns the functions calling a function.
+    pub fn callers(&self, function: AstFunctionId) -> impl Iterator<Item = AstFunctionId> + '_ {
+        self.callers.get(&function).into_iter().flatten().copied()
+    }
+
+    /// Returns the name of a function or called address.
+    pub fn name(&self, address: u64) -> String {
+        self.names
+            .get(&address)
+            .cloned()
+            .unwrap_or_else(|| AstFunctionId { address }.get_default_name())
+    }
+
+    /// Returns the groups of functions calling each other.
+    ///
+    /// ### Returns
+    /// - `&[Vec<AstFunctionId>]` - strongly connected components, callees before callers
+    pub fn strongly_connected_components(&self) -> &[Vec<AstFunctionId>] {
+        &self.components
+    }
+
+    /// Finds the groups of functions calling each other (Tarjan's algorithm).
+    fn find_components(&self) -> Vec<Vec<AstFunctionId>> {
+        struct Tarjan<'a> {
+            graph: &'a CallGraph,
+            index: HashMap<AstFunctionId, usize>,
+            low_link: HashMap<AstFunctionId, usize>,
+            stack: Vec<AstFunctionId>,
+            on_stack: BTreeSet<AstFunctionId>,
+            components: Vec<Vec<AstFunctionId>>,
+        }
+        impl Tarjan<'_> {
+            fn visit(&mut self, function: AstFunctionId) {
+                let index = self.index.len();
+                self.index.insert(function, index);
+                self.low_link.insert(function, index);
+                self.stack.push(function);
+                self.on_stack.insert(function);
+
+                for callee in self.graph.callees(function) {
+                    if !self.index.contains_key(&callee) {
+                        self.visit(callee);
+                        let low_link = self.low_link[&function].min(self.low_link[&callee]);
+                        self.low_link.insert(function, low_link);
+                    } else if self.on_stack.contains(&callee) {
+                        let low_link = self.low_link[&function].min(self.index[&callee]);
+                        self.low_link.insert(function, low_link);
+                    }
+                }
+
+                if self.low_link[&function] == self.index[&function] {
+                    let mut component = Vec::new();
+                    loop {
+                        let member = self.stack.pop().unwrap();
+                        self.on_stack.remove(&member);
+                        component.push(member);
+                        if member == function {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    self.components.push(component);
+                }
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: HashMap::new(),
+            low_link: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            components: Vec::new(),
+        };
+        for function in self.functions() {
+            if !tarjan.index.contains_key(&function) {
+                tarjan.visit(function);
+            }
+        }
+        tarjan.components
+    }
+
+    /// Returns whether a function can call itself, directly or through other functions.
+    pub fn is_recursive(&self, function: AstFunctionId) -> bool {
+        self.recursive.contains(&function)
+    }
+
+    /// Orders the functions so callees come before their callers.
+    ///
+    /// ### Note
+    /// - Functions calling each other are ordered by address.
+    pub fn bottom_up(&self) -> Vec<AstFunctionId> {
+        self.components.iter().flatten().copied().collect()
+    }
+
+    /// Exports the graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph call_graph {\n");
+        for function in self.functions() {
+            output.push_str(&format!(
+                "  \"{:#x}\" [label=\"{}\"];\n",
+                function.address,
+                escape(&self.name(function.address))
+            ));
+        }
+        let mut externals = BTreeSet::new();
+        for (caller, targets) in self.calls.iter() {
+            for target in targets {
+                let target = match target {
+                    CallTarget::Function(callee) => format!("{:#x}", callee.address),
+                    CallTarget::Address(address) => {
+                        externals.insert(*address);
+                        format!("{:#x}", address)
+                    }
+                    CallTarget::Dynamic => "dynamic".to_string(),
+                };
+                output.push_str(&format!("  \"{:#x}\" -> \"{}\";\n", caller.address, target));
+            }
+        }
+        for address in externals {
+            output.push_str(&format!(
+                "  \"{:#x}\" [label=\"{}\", shape=box];\n",
+                address,
+                escape(&self.name(address))
+            ));
+        }
+        if self
+            .calls
+            .values()
+            .flatten()
+            .any(|x| *x == CallTarget::Dynamic)
+        {
+            output.push_str("  \"dynamic\" [label=\"<dynamic>\", shape=box];\n");
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Exports the graph as JSON.
+    ///
+    /// ### Returns
+    /// - `String` - `{"functions": [{"address", "name", "recursive"}], "calls": [{"caller", "callee"}]}`,
+    ///   with `null` callees for dynamic calls
+    pub fn to_json(&self) -> String {
+        let functions: Vec<String> = self
+            .functions()
+            .map(|function| {
+                format!(
+                    "{{\"address\":{},\"name\":\"{}\",\"recursive\":{}}}",
+                    function.address,
+                    escape(&self.name(function.address)),
+                    self.is_recursive(function)
+                )
+            })
+            .collect();
+        let calls: Vec<String> = self
+            .calls
+            .iter()
+            .flat_map(|(caller, targets)| targets.iter().map(move |x| (caller, x)))
+            .map(|(caller, target)| {
+                let callee = match target {
+                    CallTarget::Function(callee) => callee.address.to_string(),
+                    CallTarget::Address(address) => address.to_string(),
+                    CallTarget::Dynamic => "null".to_string(),
+                };
+                format!("{{\"caller\":{},\"callee\":{}}}", caller.address, callee)
+            })
+            .collect();
+        format!(
+            "{{\"functions\":[{}],\"calls\":[{}]}}",
+            functions.join(","),
+            calls.join(",")
+        )
+    }
+}
+
+impl Ast {
+    /// Builds the call graph between the current versions of the functions.
+    pub fn call_graph(&self) -> CallGraph {
+        CallGraph::new(self)
+    }
+}
+
+/// Escapes a name for a quoted DOT or JSON string.
+fn escape(name: &str) -> String {
+    let mut output = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            c if c.is_control() => output.push_str(&format!("\\u{:04x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f(address: u64) -> AstFunctionId {
+        AstFunctionId { address }
+    }
+
+    /// 1 -> 2 -> 3 -> 2, 3 -> 4, 4 -> 4, 1 -> external
+    fn graph() -> CallGraph {
+        CallGraph::from_edges(
+            [f(1), f(2), f(3), f(4)],
+            [
+                (f(1), CallTarget::Function(f(2))),
+                (f(2), CallTarget::Function(f(3))),
+                (f(3), CallTarget::Function(f(2))),
+                (f(3), CallTarget::Function(f(4))),
+                (f(4), CallTarget::Function(f(4))),
+                (f(1), CallTarget::Address(0x100)),
+                (f(1), CallTarget::Dynamic),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_callers_and_callees() {
+        let graph = graph();
+        assert_eq!(graph.callees(f(1)).collect::<Vec<_>>(), vec![f(2)]);
+        assert_eq!(graph.call_targets(f(1)).count(), 3);
+        assert_eq!(graph.callers(f(2)).collect::<Vec<_>>(), vec![f(1), f(3)]);
+        assert_eq!(graph.callers(f(1)).count(), 0);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let graph = graph();
+        assert_eq!(
+            graph.strongly_connected_components(),
+            vec![vec![f(4)], vec![f(2), f(3)], vec![f(1)]]
+        );
+        assert_eq!(graph.bottom_up(), vec![f(4), f(2), f(3), f(1)]);
+        assert!(!graph.is_recursive(f(1)));
+        assert!(graph.is_recursive(f(2)));
+        assert!(graph.is_recursive(f(4)));
+    }
+
+    #[test]
+    fn test_export() {
+        let graph = graph();
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"0x3\" -> \"0x2\";"));
+        assert!(dot.contains("\"0x100\" [label=\"f100\", shape=box];"));
+        assert!(dot.contains("\"0x1\" -> \"dynamic\";"));
+        let json = graph.to_json();
+        assert!(json.contains("{\"address\":4,\"name\":\"f4\",\"recursive\":true}"));
+        assert!(json.contains("{\"address\":1,\"name\":\"f1\",\"recursive\":false}"));
+        assert!(json.contains("{\"caller\":1,\"callee\":null}"));
+    }
+}
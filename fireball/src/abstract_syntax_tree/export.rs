@@ -0,0 +1,361 @@
+//! Module building a serializable view of an AST, for tools reading the decompilation result.
+
+use super::*;
+use crate::prelude::DecompileError;
+use serde::Serialize;
+
+/// Decompilation result of every function, along with the printed code
+#[derive(Debug, Clone, Serialize)]
+pub struct AstExport {
+    pub functions: Vec<AstFunctionExport>,
+    /// Calls between the functions and to the addresses outside of them
+    pub calls: Vec<AstCallExport>,
+    /// Code printed by `Ast::print` with the same configuration
+    pub source: String,
+    /// Instructions each line of `source` was decompiled from, for the lines having some
+    pub lines: Vec<AstLineExport>,
+}
+
+/// Current version of a function
+#[derive(Debug, Clone, Serialize)]
+pub struct AstFunctionExport {
+    /// Entry address
+    pub address: u64,
+    pub name: String,
+    /// Declaration of the function, as printed (`int32_t main(int32_t argc, char** argv)`)
+    pub signature: String,
+    pub return_type: String,
+    pub calling_convention: Option<String>,
+    pub parameters: Vec<AstParameterExport>,
+    pub variables: Vec<AstVariableExport>,
+    pub body: Vec<AstStatementExport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AstParameterExport {
+    pub name: String,
+    pub var_type: String,
+    pub location: AstParameterLocationExport,
+}
+
+/// Where a parameter is passed
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AstParameterLocationExport {
+    Register {
+        register: String,
+    },
+    /// Offset from the stack pointer at the entry of the function
+    Stack {
+        offset: isize,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AstVariableExport {
+    /// Index of the variable in its function (`12` of `v12`)
+    pub index: u32,
+    pub name: String,
+    pub var_type: String,
+    /// Whether the variable lives outside of the function
+    pub global: bool,
+    /// Stack slot the variable is stored in, by offset from the frame base pointer
+    pub frame_offset: Option<i64>,
+    /// Register or memory the variable is read from, as printed
+    pub location: Option<String>,
+    /// Value of the variable, if it never changes
+    pub constant: Option<String>,
+}
+
+/// Statement, with the statements nested in it
+#[derive(Debug, Clone, Serialize)]
+pub struct AstStatementExport {
+    /// Variant of the statement, in snake case (`if`, `do_while`, `assignment`)
+    pub kind: &'static str,
+    /// Whole statement for simple ones, the condition or value for the ones holding bodies
+    pub code: Option<String>,
+    /// Instructions the statement was decompiled from
+    pub origin: Option<AstRangeExport>,
+    /// Bodies nested in the statement, in printed order
+    pub bodies: Vec<AstBodyExport>,
+}
+
+/// Statements nested in another one
+#[derive(Debug, Clone, Serialize)]
+pub struct AstBodyExport {
+    /// Role of the body (`then`, `else`, `case 1`, `default`, `catch (...)`)
+    pub label: String,
+    pub statements: Vec<AstStatementExport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AstCallExport {
+    /// Entry address of the calling function
+    pub caller: u64,
+    /// Called address, `None` for calls only known at runtime
+    pub callee: Option<u64>,
+    /// Name of the called function, `None` for calls only known at runtime
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AstLineExport {
+    /// One based line number in the printed code
+    pub line: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Virtual addresses of the instructions, end excluded
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct AstRangeExport {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Ast {
+    /// Builds a serializable view of the current version of every function.
+    ///
+    /// ### Arguments
+    /// - `config: Option<AstPrintConfig>` - configuration the code is printed with
+    ///
+    /// ### Returns
+    /// - `Result<AstExport, DecompileError>` - functions by address, calls, printed code and
+    ///   its source map, or an error if a parameter refers to a missing variable
+    pub fn export(&self, config: Option<AstPrintConfig>) -> Result<AstExport, DecompileError> {
+        let config = config.unwrap_or_default();
+        let names = self.function_names();
+        let mut functions: Vec<AstFunctionExport> = {
+            let functions = self.functions.read().unwrap();
+            with_function_names(names, || {
+                self.function_versions
+                    .iter()
+                    .filter_map(|(id, version)| functions.get(id).and_then(|x| x.get(version)))
+                    .map(|function| export_function(function, config))
+                    .collect::<Result<_, _>>()
+            })?
+        };
+        functions.sort_unstable_by_key(|x| x.address);
+
+        let graph = self.call_graph();
+        let calls = graph
+            .functions()
+            .flat_map(|caller| graph.call_targets(caller).map(move |x| (caller, x)))
+            .map(|(caller, target)| {
+                let callee = match target {
+                    CallTarget::Function(x) => Some(x.address),
+                    CallTarget::Address(x) => Some(x),
+                    CallTarget::Dynamic => None,
+                };
+                AstCallExport {
+                    caller: caller.address,
+                    callee,
+                    name: callee.map(|x| graph.name(x)),
+                }
+            })
+            .collect();
+
+        let (source, source_map) = self.print_with_source_map(Some(config));
+        let lines = (0..source_map.line_count())
+            .filter_map(|line| {
+                source_map
+                    .address_of_line(line)
+                    .map(|origin| AstLineExport {
+                        line: line + 1,
+                        start: origin.start,
+                        end: origin.end,
+                    })
+            })
+            .collect();
+
+        Ok(AstExport {
+            functions,
+            calls,
+            source,
+            lines,
+        })
+    }
+}
+
+fn export_function(
+    function: &AstFunction,
+    config: AstPrintConfig,
+) -> Result<AstFunctionExport, DecompileError> {
+    let return_type = function.return_type.to_string_with_config(Some(config));
+    let parameters: Vec<AstParameterExport> = function
+        .parameters
+        .iter()
+        .map(|param| {
+            let missing = |_| {
+                DecompileError::CASTGenerationFailed(Some(format!(
+                    "parameter of {} refers to a missing variable",
+                    function_name(&function.id)
+                )))
+            };
+            Ok(AstParameterExport {
+                name: param.name(&function.variables).map_err(missing)?,
+                var_type: param
+                    .read_type(&function.variables)
+                    .map_err(missing)?
+                    .to_string_with_config(Some(config)),
+                location: match &param.location {
+                    AstParameterLocation::Register(x) => AstParameterLocationExport::Register {
+                        register: x.to_string(),
+                    },
+                    AstParameterLocation::Stack(x) => {
+                        AstParameterLocationExport::Stack { offset: *x }
+                    }
+                },
+            })
+        })
+        .collect::<Result<_, DecompileError>>()?;
+    let signature = format!(
+        "{} {}({})",
+        return_type,
+        function_name(&function.id),
+        parameters
+            .iter()
+            .map(|x| format!("{} {}", x.var_type, x.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let var_map = function.variables.read().unwrap();
+    let mut variables: Vec<&AstVariable> = var_map.values().collect();
+    variables.sort_unstable_by_key(|x| (x.id.parent.is_none(), x.id.index));
+    let variables = variables
+        .into_iter()
+        .map(|var| AstVariableExport {
+            index: var.id.index,
+            name: var.name(),
+            var_type: var.var_type.to_string_with_config(Some(config)),
+            global: var.id.parent.is_none(),
+            frame_offset: var.frame_offset(),
+            location: var.access_location().map(|x| x.to_string()),
+            constant: var
+                .const_value
+                .as_ref()
+                .map(|x| x.to_string_with_config(Some(config))),
+        })
+        .collect();
+
+    Ok(AstFunctionExport {
+        address: function.id.address,
+        name: function_name(&function.id),
+        signature,
+        return_type,
+        calling_convention: function.calling_convention.map(|x| format!("{x:?}")),
+        parameters,
+        variables,
+        body: export_body(&function.body, config),
+    })
+}
+
+fn export_body(body: &[Wrapped<AstStatement>], config: AstPrintConfig) -> Vec<AstStatementExport> {
+    body.iter()
+        .filter(|x| config.print_empty_statement || !matches!(x.item, AstStatement::Empty))
+        .map(|x| export_statement(x, config))
+        .collect()
+}
+
+fn export_statement(stmt: &Wrapped<AstStatement>, config: AstPrintConfig) -> AstStatementExport {
+    let expression = |x: &Wrapped<AstExpression>| Some(x.to_string_with_config(Some(config)));
+    let body = |label: &str, statements: &[Wrapped<AstStatement>]| AstBodyExport {
+        label: label.to_owned(),
+        statements: export_body(statements, config),
+    };
+    let (kind, code, bodies) = match &stmt.item {
+        AstStatement::If(cond, then_body, else_body) => {
+            let mut bodies = vec![body("then", then_body)];
+            bodies.extend(else_body.as_ref().map(|x| body("else", x)));
+            ("if", expression(cond), bodies)
+        }
+        AstStatement::While(cond, x) => ("while", expression(cond), vec![body("body", x)]),
+        AstStatement::DoWhile(cond, x) => ("do_while", expression(cond), vec![body("body", x)]),
+        AstStatement::For(init, cond, update, x) => (
+            "for",
+            expression(cond),
+            vec![
+                body("init", std::slice::from_ref(&**init)),
+                body("update", std::slice::from_ref(&**update)),
+                body("body", x),
+            ],
+        ),
+        AstStatement::Switch(value, cases, default) => {
+            let mut bodies: Vec<_> = cases
+                .iter()
+                .map(|(literal, x)| {
+                    body(
+                        &format!("case {}", literal.to_string_with_config(Some(config))),
+                        x,
+                    )
+                })
+                .collect();
+            bodies.extend(default.as_ref().map(|x| body("default", x)));
+            ("switch", expression(value), bodies)
+        }
+        AstStatement::Try(x, handlers) => {
+            let mut bodies = vec![body("try", x)];
+            bodies.extend(handlers.iter().map(|(handler, x)| {
+                let label = match handler {
+                    AstExceptionHandler::Catch(types) => format!(
+                        "catch ({})",
+                        types
+                            .iter()
+                            .map(|x| x.as_deref().unwrap_or("..."))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    AstExceptionHandler::Except(filter) => {
+                        format!("except ({})", filter.to_string_with_config(Some(config)))
+                    }
+                    AstExceptionHandler::Finally => "finally".to_owned(),
+                };
+                body(&label, x)
+            }));
+            ("try", None, bodies)
+        }
+        AstStatement::Block(x) => ("block", None, vec![body("body", x)]),
+        item => (
+            statement_kind(item),
+            Some(item.to_string_with_config(Some(config))),
+            Vec::new(),
+        ),
+    };
+    AstStatementExport {
+        kind,
+        code,
+        origin: stmt.origin.map(|x| AstRangeExport {
+            start: x.start,
+            end: x.end,
+        }),
+        bodies,
+    }
+}
+
+/// Returns the variant of a statement not holding bodies, in snake case.
+fn statement_kind(stmt: &AstStatement) -> &'static str {
+    match stmt {
+        AstStatement::Declaration(..) => "declaration",
+        AstStatement::Assignment(..) => "assignment",
+        AstStatement::Return(..) => "return",
+        AstStatement::Call(..) => "call",
+        AstStatement::Label(..) => "label",
+        AstStatement::Goto(..) => "goto",
+        AstStatement::Assembly(..) => "assembly",
+        AstStatement::Undefined => "undefined",
+        AstStatement::Exception(..) => "exception",
+        AstStatement::Comment(..) => "comment",
+        AstStatement::Ir(..) => "ir",
+        AstStatement::Empty => "empty",
+        AstStatement::Break => "break",
+        AstStatement::Continue => "continue",
+        AstStatement::If(..) => "if",
+        AstStatement::While(..) => "while",
+        AstStatement::DoWhile(..) => "do_while",
+        AstStatement::For(..) => "for",
+        AstStatement::Switch(..) => "switch",
+        AstStatement::Try(..) => "try",
+        AstStatement::Block(..) => "block",
+    }
+}
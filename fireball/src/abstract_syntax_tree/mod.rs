@@ -1,3 +1,6 @@
+mod annotations;
+mod call_graph;
+mod export;
 mod objects;
 mod optimize;
 mod print;
@@ -5,6 +8,9 @@ mod traits;
 pub mod utils;
 
 use crate::prelude::*;
+pub use annotations::*;
+pub use call_graph::*;
+pub use export::*;
 use num_bigint::Sign;
 pub use objects::*;
 pub use optimize::pattern_matching;
@@ -1,5 +1,9 @@
 use crate::{
-    abstract_syntax_tree::objects::*, core::PreDefinedOffsets, ir::analyze::IrFunction, prelude::*,
+    abstract_syntax_tree::{AstAnnotations, objects::*},
+    core::PreDefinedOffsets,
+    debug_info::DebugInfo,
+    ir::analyze::IrFunction,
+    prelude::*,
     utils::version_map::VersionMap,
 };
 use hashbrown::HashMap;
@@ -11,6 +15,14 @@ pub struct Ast {
     pub functions: ArcAstFunctionMap,
     pub last_variable_id: HashMap<AstFunctionId, u32>,
     pub pre_defined_symbols: HashMap<u64, String>,
+    /// Calling convention of the platform the binary targets
+    pub calling_convention: Option<AstCallingConvention>,
+    /// Architecture of the instructions the functions are decompiled from
+    pub architecture: Option<iceball::MachineArchitecture>,
+    /// Debug information of the binary, applied while optimizing
+    pub debug_info: Arc<DebugInfo>,
+    /// Names, types and comments given by the user, applied while generating and optimizing
+    pub annotations: Arc<AstAnnotations>,
 }
 
 impl Ast {
@@ -20,6 +32,10 @@ impl Ast {
             functions: Arc::new(RwLock::new(HashMap::new())),
             last_variable_id: HashMap::new(),
             pre_defined_symbols: HashMap::new(),
+            calling_convention: None,
+            architecture: None,
+            debug_info: Arc::new(DebugInfo::default()),
+            annotations: Arc::new(AstAnnotations::default()),
         }
     }
 
@@ -38,6 +54,87 @@ impl Ast {
         self.pre_defined_symbols = map;
     }
 
+    /// Sets the calling convention of the platform the binary targets.
+    pub fn set_calling_convention(&mut self, calling_convention: AstCallingConvention) {
+        self.calling_convention = Some(calling_convention);
+    }
+
+    /// Sets the architecture of the instructions the functions are decompiled from.
+    pub fn set_architecture(&mut self, architecture: iceball::MachineArchitecture) {
+        self.architecture = Some(architecture);
+    }
+
+    /// Sets the debug information names, types and source lines are taken from.
+    pub fn set_debug_info(&mut self, debug_info: Arc<DebugInfo>) {
+        self.debug_info = debug_info;
+    }
+
+    /// Sets the annotations given by the user, and names the generated functions after them.
+    pub fn set_annotations(&mut self, annotations: Arc<AstAnnotations>) {
+        let mut functions = self.functions.write().unwrap();
+        for (id, version) in self.function_versions.iter() {
+            let Some(function) = functions.get_mut(id).and_then(|x| x.get_mut(version)) else {
+                continue;
+            };
+            if let Some(name) = annotations
+                .function(id.address)
+                .and_then(|x| x.name.clone())
+            {
+                function.name = Some(name);
+            }
+        }
+        drop(functions);
+        self.annotations = annotations;
+    }
+
+    /// Returns the function decompiled from the instruction at the address.
+    pub fn function_at(&self, address: u64) -> Option<AstFunctionId> {
+        let functions = self.functions.read().unwrap();
+        self.function_versions
+            .iter()
+            .filter_map(|(id, version)| functions.get(id)?.get(version))
+            .find(|function| {
+                function
+                    .origin_ir
+                    .get_ir()
+                    .iter()
+                    .any(|ir| ir.address.get_virtual_address() == address)
+            })
+            .map(|function| function.id)
+    }
+
+    /// Returns the variable of a function printed with the name.
+    pub fn variable_by_name(&self, id: &AstFunctionId, name: &str) -> Option<AstVariable> {
+        let version = self.function_versions.get(id)?;
+        let variables = self.get_variables(id, version).ok()?;
+        let variables = variables.read().unwrap();
+        variables.values().find(|x| x.name() == name).cloned()
+    }
+
+    /// Overrides the calling convention parameters of a function are recovered with.
+    ///
+    /// ### Arguments
+    /// - `id: &AstFunctionId` - function to override
+    /// - `calling_convention: Option<AstCallingConvention>` - convention to use, or `None` to detect it
+    ///
+    /// ### Returns
+    /// - `bool` - whether the function exists
+    pub fn set_function_calling_convention(
+        &mut self,
+        id: &AstFunctionId,
+        calling_convention: Option<AstCallingConvention>,
+    ) -> bool {
+        let Some(version) = self.function_versions.get(id) else {
+            return false;
+        };
+        let mut functions = self.functions.write().unwrap();
+        let Some(function) = functions.get_mut(id).and_then(|x| x.get_mut(version)) else {
+            return false;
+        };
+        function.calling_convention = calling_convention;
+        true
+    }
+
     /// 1. generate default function
     /// 2. set ast to pointing that version
     pub fn generate_default_function(&mut self, data: Arc<IrFunction>) -> AstFunctionId {
@@ -53,26 +150,43 @@ impl Ast {
             .enumerate()
         {
             let ir_index = ir_index as u32;
+            let start = ir.address.get_virtual_address();
+            let size = instruction
+                .inner
+                .bytes
+                .as_ref()
+                .map(|x| x.len())
+                .unwrap_or(0);
+            let origin = Some(AstAddressRange {
+                start,
+                end: start + size as u64,
+            });
             if let Some(stmts) = ir.statements {
                 for stmt in stmts.iter() {
                     body.push(Wrapped {
                         item: AstStatement::Ir(Box::new((Some(ir_index), stmt.clone()))),
                         comment: None,
+                        origin,
                     });
                 }
             } else {
                 body.push(Wrapped {
                     item: AstStatement::Assembly(instruction.inner.to_string()),
                     comment: None,
+                    origin,
                 });
             }
         }
         let func = AstFunction {
-            name: None,
+            name: self
+                .annotations
+                .function(id.address)
+                .and_then(|x| x.name.clone()),
             id,
             origin_ir: data,
             return_type: AstValueType::Void,
             parameters: Vec::new(),
+            calling_convention: None,
             variables: Arc::new(RwLock::new(HashMap::new())),
             body,
 
@@ -136,3 +250,33 @@ impl Ast {
         self.pre_defined_symbols.shrink_to_fit();
     }
 }
+
+#[cfg(test)]
+impl Ast {
+    /// Adds a function without instructions, for the tests building an ast by hand.
+    pub(crate) fn insert_test_function(
+        &mut self,
+        address: u64,
+        body: Vec<Wrapped<AstStatement>>,
+    ) -> AstFunctionId {
+        let id = AstFunctionId { address };
+        let func = AstFunction {
+            name: None,
+            id,
+            origin_ir: Arc::new(IrFunction::new(Arc::from([]), Vec::new(), Vec::new())),
+            return_type: AstValueType::Void,
+            parameters: Vec::new(),
+            calling_convention: None,
+            variables: Arc::new(RwLock::new(HashMap::new())),
+            body,
+
+            processed_optimizations: Vec::new(),
+        };
+        self.functions
+            .write()
+            .unwrap()
+            .insert(id, VersionMap::new(AstFunctionVersion(1), func));
+        self.function_versions.insert(id, AstFunctionVersion(1));
+        id
+    }
+}
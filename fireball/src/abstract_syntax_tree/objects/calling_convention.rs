@@ -0,0 +1,55 @@
+use iceball::MachineArchitecture;
+
+/// Convention a function receives its parameters with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum AstCallingConvention {
+    /// x86 - arguments on the stack, cleaned up by the caller
+    X86Cdecl,
+    /// x86 - arguments on the stack, cleaned up by the callee
+    X86Stdcall,
+    /// x86 - ecx, edx, then the stack, cleaned up by the callee
+    X86Fastcall,
+    /// x86 - `this` in ecx, then the stack, cleaned up by the callee
+    X86Thiscall,
+    /// x86 - xmm0 ~ xmm3, then the stack, cleaned up by the callee
+    X86Vectorcall,
+    /// x86-64 Windows - rcx(xmm0), rdx(xmm1), r8(xmm2), r9(xmm3), then the stack
+    X64Microsoft,
+    /// x86-64 System V (Linux, macOS, BSD) - rdi, rsi, rdx, rcx, r8, r9, xmm0 ~ xmm7, then the stack
+    X64SystemV,
+    /// AArch64 AAPCS64 - x0 ~ x7, v0 ~ v7, then the stack
+    Arm64Aapcs,
+    Unknown,
+}
+
+impl AstCallingConvention {
+    /// Returns the convention functions of a binary use unless told otherwise.
+    ///
+    /// ### Arguments
+    /// - `architecture: MachineArchitecture` - architecture of the binary
+    /// - `windows: bool` - whether the binary targets Windows (PE/COFF)
+    ///
+    /// ### Returns
+    /// - `Self` - default calling convention of the platform
+    pub fn platform_default(architecture: MachineArchitecture, windows: bool) -> Self {
+        match architecture {
+            MachineArchitecture::X86 => Self::X86Cdecl,
+            MachineArchitecture::X64 if windows => Self::X64Microsoft,
+            MachineArchitecture::X64 => Self::X64SystemV,
+            MachineArchitecture::Arm64 => Self::Arm64Aapcs,
+            MachineArchitecture::Arm => Self::Unknown,
+        }
+    }
+
+    /// Whether the convention passes the arguments of a 32-bit x86 function
+    pub fn is_x86(&self) -> bool {
+        matches!(
+            self,
+            Self::X86Cdecl
+                | Self::X86Stdcall
+                | Self::X86Fastcall
+                | Self::X86Thiscall
+                | Self::X86Vectorcall
+        )
+    }
+}
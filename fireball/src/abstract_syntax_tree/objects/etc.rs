@@ -6,10 +6,11 @@ pub type ArcAstFunctionMap =
     Arc<RwLock<HashMap<AstFunctionId, VersionMap<AstFunctionVersion, AstFunction>>>>;
 pub type ArcAstVariableMap = Arc<RwLock<HashMap<AstVariableId, AstVariable>>>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstJumpTarget {
     Variable {
         scope: AstFunctionId,
+        #[serde(with = "crate::project::shared::variable_map")]
         var_map: ArcAstVariableMap,
         var_id: AstVariableId,
     },
@@ -19,10 +20,11 @@ pub enum AstJumpTarget {
     Unknown(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstCall {
     Variable {
         scope: AstFunctionId,
+        #[serde(with = "crate::project::shared::variable_map")]
         var_map: ArcAstVariableMap,
         var_id: AstVariableId,
         args: Vec<Wrapped<AstExpression>>,
@@ -35,7 +37,7 @@ pub enum AstCall {
     Unknown(String, Vec<Wrapped<AstExpression>>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstBuiltinFunctionArgument {
     None,
 
@@ -54,7 +56,7 @@ pub enum AstBuiltinFunctionArgument {
     BitZeros(Wrapped<AstExpression>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstBuiltinFunction {
     ArchBitPerByte,
     InstructionByteSize,
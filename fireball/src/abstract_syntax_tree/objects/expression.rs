@@ -1,13 +1,16 @@
 use crate::abstract_syntax_tree::objects::*;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstExpression {
     Unknown,
     Undefined,
     ArchitectureBitSize,
     ArchitectureByteSize,
     Literal(AstLiteral),
-    Variable(ArcAstVariableMap, AstVariableId),
+    Variable(
+        #[serde(with = "crate::project::shared::variable_map")] ArcAstVariableMap,
+        AstVariableId,
+    ),
     UnaryOp(AstUnaryOperator, Box<Wrapped<AstExpression>>),
     BinaryOp(
         AstBinaryOperator,
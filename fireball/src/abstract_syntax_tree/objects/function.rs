@@ -1,13 +1,17 @@
 use crate::{abstract_syntax_tree::objects::*, ir::analyze::IrFunction};
 use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AstFunction {
     pub name: Option<String>,
     pub id: AstFunctionId,
+    #[serde(with = "crate::project::shared::origin")]
     pub origin_ir: Arc<IrFunction>,
     pub return_type: AstValueType,
     pub parameters: Vec<AstParameter>,
+    /// Calling convention the parameters are recovered with, detected when `None`
+    pub calling_convention: Option<AstCallingConvention>,
+    #[serde(with = "crate::project::shared::variable_map")]
     pub variables: ArcAstVariableMap,
     pub body: Vec<Wrapped<AstStatement>>,
 
@@ -1,4 +1,5 @@
 mod ast;
+mod calling_convention;
 mod etc;
 mod expression;
 mod function;
@@ -6,6 +7,7 @@ mod operator;
 mod optimization;
 mod parameter;
 mod print_config;
+mod source_map;
 mod statement;
 mod tags;
 mod value;
@@ -13,6 +15,7 @@ mod variable;
 mod wrapper;
 
 pub use ast::*;
+pub use calling_convention::*;
 pub use etc::*;
 pub use expression::*;
 pub use function::*;
@@ -20,6 +23,7 @@ pub use operator::*;
 pub use optimization::*;
 pub use parameter::*;
 pub use print_config::*;
+pub use source_map::*;
 pub use statement::*;
 pub use tags::*;
 pub use value::*;
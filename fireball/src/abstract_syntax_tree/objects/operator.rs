@@ -1,4 +1,4 @@
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstUnaryOperator {
     Negate,  // -
     Not,     // !
@@ -11,7 +11,7 @@ pub enum AstUnaryOperator {
     CastUnsigned,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstBinaryOperator {
     Add,
     Sub,
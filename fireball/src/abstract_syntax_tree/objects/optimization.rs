@@ -6,18 +6,22 @@ pub struct AstOptimizationConfig {
     pub parameter_analyzation: bool,
     pub constant_folding: bool,
     pub collapse_unused_variable: bool,
+    pub struct_recovery: bool,
+    pub stack_frame_analyzation: bool,
     pub pattern_matching: Vec<AstPattern>,
     pub max_pass_iterations: usize,
     /// When true, use the original embedded Rust implementations instead of
     /// `.fb` pattern files for migrated passes
     pub use_embedded_passes: bool,
 }
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum AstOptimizationKind {
     IrAnalyzation,
     ParameterAnalyzation,
     ConstantFolding,
     CollapseUnusedVariables,
+    StructRecovery,
+    StackFrameAnalyzation,
     PatternMatching(Box<AstPattern>),
 }
 impl AstOptimizationConfig {
@@ -27,6 +31,8 @@ impl AstOptimizationConfig {
             parameter_analyzation: true,
             constant_folding: true,
             collapse_unused_variable: true,
+            struct_recovery: true,
+            stack_frame_analyzation: true,
             pattern_matching: AstPattern::predefined_patterns(),
             max_pass_iterations: 3,
             use_embedded_passes: false,
@@ -38,6 +44,8 @@ impl AstOptimizationConfig {
             parameter_analyzation: false,
             constant_folding: false,
             collapse_unused_variable: false,
+            struct_recovery: false,
+            stack_frame_analyzation: false,
             pattern_matching: Vec::new(),
             max_pass_iterations: 1,
             use_embedded_passes: false,
@@ -60,6 +68,14 @@ impl AstOptimizationConfig {
         self.collapse_unused_variable = value;
         self
     }
+    pub fn struct_recovery(mut self, value: bool) -> Self {
+        self.struct_recovery = value;
+        self
+    }
+    pub fn stack_frame_analyzation(mut self, value: bool) -> Self {
+        self.stack_frame_analyzation = value;
+        self
+    }
     pub fn pattern_matching(mut self, value: Vec<AstPattern>) -> Self {
         self.pattern_matching = value;
         self
@@ -80,6 +96,8 @@ impl Default for AstOptimizationConfig {
             parameter_analyzation: true,
             constant_folding: true,
             collapse_unused_variable: true,
+            struct_recovery: true,
+            stack_frame_analyzation: true,
             pattern_matching: AstPattern::predefined_patterns(),
             max_pass_iterations: 3,
             use_embedded_passes: false,
@@ -103,6 +121,12 @@ impl From<AstOptimizationKind> for AstOptimizationConfig {
             AstOptimizationKind::CollapseUnusedVariables => {
                 n.collapse_unused_variable = true;
             }
+            AstOptimizationKind::StructRecovery => {
+                n.struct_recovery = true;
+            }
+            AstOptimizationKind::StackFrameAnalyzation => {
+                n.stack_frame_analyzation = true;
+            }
             AstOptimizationKind::PatternMatching(p) => {
                 n.pattern_matching = Vec::from([*p]);
             }
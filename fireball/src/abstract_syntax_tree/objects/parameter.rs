@@ -5,7 +5,7 @@ use crate::{
 };
 use either::Either;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AstParameter {
     pub location: AstParameterLocation,
     /// Either a related AST variable id (Left) or a temporary name when no related variable exists (Right)
@@ -42,7 +42,7 @@ impl AstParameter {
         }
     }
 }
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstParameterLocation {
     Register(Aos<IrData>),
     Stack(isize),
@@ -0,0 +1,59 @@
+use super::AstAddressRange;
+
+/// Instructions each line of printed code was decompiled from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AstSourceMap {
+    /// Origin of each line, by zero based line number
+    lines: Vec<Option<AstAddressRange>>,
+}
+
+impl AstSourceMap {
+    pub(crate) fn new(lines: Vec<Option<AstAddressRange>>) -> Self {
+        Self { lines }
+    }
+
+    /// Returns the instructions a printed line was decompiled from.
+    ///
+    /// ### Arguments
+    /// - `line: usize` - zero based line number
+    ///
+    /// ### Returns
+    /// - `Option<AstAddressRange>` - `None` for lines not decompiled from instructions
+    pub fn address_of_line(&self, line: usize) -> Option<AstAddressRange> {
+        self.lines.get(line).copied().flatten()
+    }
+
+    /// Returns every printed line decompiled from an instruction.
+    ///
+    /// ### Arguments
+    /// - `address: u64` - virtual address of the instruction
+    ///
+    /// ### Returns
+    /// - `Vec<usize>` - zero based line numbers, in order
+    pub fn lines_of_address(&self, address: u64) -> Vec<usize> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter(|(_, origin)| origin.is_some_and(|x| x.contains(address)))
+            .map(|(line, _)| line)
+            .collect()
+    }
+
+    /// Returns the printed line that most closely corresponds to an instruction.
+    ///
+    /// ### Note
+    /// - Lines decompiled from fewer instructions are preferred, so the header of a loop
+    ///   does not hide the statement in it.
+    pub fn line_of_address(&self, address: u64) -> Option<usize> {
+        self.lines_of_address(address)
+            .into_iter()
+            .min_by_key(|line| {
+                let origin = self.lines[*line].unwrap();
+                origin.end - origin.start
+            })
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+}
@@ -1,6 +1,8 @@
-use crate::{abstract_syntax_tree::objects::*, ir::statements::IrStatement};
+use crate::{
+    abstract_syntax_tree::objects::*, ir::statements::IrStatement, utils::static_str::StaticStr,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AstStatement {
     Declaration(AstVariable, Option<Wrapped<AstExpression>>),
     Assignment(Wrapped<AstExpression>, Wrapped<AstExpression>),
@@ -23,7 +25,7 @@ pub enum AstStatement {
     Block(Vec<Wrapped<AstStatement>>),
     Assembly(String),
     Undefined,
-    Exception(&'static str),
+    Exception(#[serde(with = "crate::utils::static_str")] StaticStr),
     Comment(String),
     Ir(
         Box<(
@@ -44,4 +46,20 @@ pub enum AstStatement {
     Continue,
     /// do { body } while (condition)
     DoWhile(Wrapped<AstExpression>, Vec<Wrapped<AstStatement>>),
+    /// try { body } catch (type) { handler } ...
+    Try(
+        Vec<Wrapped<AstStatement>>,
+        Vec<(AstExceptionHandler, Vec<Wrapped<AstStatement>>)>,
+    ),
+}
+
+/// Clause of a `try` statement
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum AstExceptionHandler {
+    /// catch (type) with the name of each caught type, `None` for `...`
+    Catch(Vec<Option<String>>),
+    /// __except (filter)
+    Except(Wrapped<AstExpression>),
+    /// __finally, also used for cleanups run while an exception passes through
+    Finally,
 }
@@ -1,10 +1,14 @@
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy, Hash)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy, Hash, serde::Serialize, serde::Deserialize,
+)]
 pub struct AstVariableId {
     /// nth variable
     pub(crate) index: u32,
     pub(crate) parent: Option<AstFunctionId>,
 }
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy, Hash)]
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Copy, Hash, serde::Serialize, serde::Deserialize,
+)]
 pub struct AstFunctionId {
     pub(crate) address: u64,
 }
@@ -19,6 +23,10 @@ impl AstVariableId {
             format!("g{}", self.index)
         }
     }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
 }
 impl AstFunctionId {
     pub fn get_default_name(&self) -> String {
@@ -1,7 +1,7 @@
 use crate::abstract_syntax_tree::objects::*;
 use num_bigint::BigInt;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum AstValueType {
     Void,
     Unknown,
@@ -25,7 +25,7 @@ pub enum AstValueType {
     Union(String, Vec<AstVariable>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum AstValue {
     Void,
     Unknown,
@@ -40,7 +40,7 @@ pub enum AstValue {
     Array(Vec<Wrapped<AstValue>>),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum AstLiteral {
     Int(i64),
     UInt(u64),
@@ -50,6 +50,62 @@ pub enum AstLiteral {
     Bool(bool),
 }
 
+impl std::str::FromStr for AstValueType {
+    type Err = String;
+
+    /// Parses a type as it is printed (`uint32_t`, `char*`, `int[4]`, `struct foo`).
+    ///
+    /// ### Note
+    /// - Structs and unions are parsed without their fields.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if let Some(inner) = text.strip_suffix('*') {
+            return Ok(AstValueType::Pointer(Box::new(inner.parse()?)));
+        }
+        if let Some(rest) = text.strip_suffix(']')
+            && let Some((inner, size)) = rest.rsplit_once('[')
+        {
+            let size = size
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid array size `{size}`"))?;
+            return Ok(AstValueType::Array(Box::new(inner.parse()?), size));
+        }
+        for (keyword, union) in [("struct ", false), ("union ", true)] {
+            let Some(name) = text.strip_prefix(keyword).map(str::trim) else {
+                continue;
+            };
+            if name.is_empty() {
+                return Err(format!("missing name of `{text}`"));
+            }
+            return Ok(if union {
+                AstValueType::Union(name.to_owned(), Vec::new())
+            } else {
+                AstValueType::Struct(name.to_owned(), Vec::new())
+            });
+        }
+        Ok(match text {
+            "void" => AstValueType::Void,
+            "unknown_t" => AstValueType::Unknown,
+            "int" => AstValueType::Int,
+            "int8_t" => AstValueType::Int8,
+            "int16_t" => AstValueType::Int16,
+            "int32_t" => AstValueType::Int32,
+            "int64_t" => AstValueType::Int64,
+            "uint" => AstValueType::UInt,
+            "uint8_t" => AstValueType::UInt8,
+            "uint16_t" => AstValueType::UInt16,
+            "uint32_t" => AstValueType::UInt32,
+            "uint64_t" => AstValueType::UInt64,
+            "char" => AstValueType::Char,
+            "float" => AstValueType::Float,
+            "double" => AstValueType::Double,
+            "bool" => AstValueType::Bool,
+            _ => return Err(format!("unknown type `{text}`")),
+        })
+    }
+}
+
 impl AstValue {
     pub fn num(&self) -> Option<&BigInt> {
         match self {
@@ -1,10 +1,15 @@
 use crate::{
     abstract_syntax_tree::objects::*,
-    ir::{data::IrDataAccess, utils::IrStatementDescriptorMap},
+    ir::{
+        data::{IrData, IrDataAccess, IrDataOperation},
+        operator::IrUnaryOperator,
+        utils::IrStatementDescriptorMap,
+    },
+    utils::Aos,
 };
 use std::fmt::Formatter;
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct AstVariable {
     pub name: Option<String>,
     pub id: AstVariableId,
@@ -12,6 +17,8 @@ pub struct AstVariable {
     pub const_value: Option<Wrapped<AstValue>>,
     /// None if origin is not ir
     pub data_access_ir: Option<IrStatementDescriptorMap<Vec<IrDataAccess>>>,
+    /// Offset in bytes of the field in its struct or union, None if not a field
+    pub offset: Option<u64>,
 }
 
 impl std::fmt::Debug for AstVariable {
@@ -21,6 +28,7 @@ impl std::fmt::Debug for AstVariable {
             .field("id", &self.id)
             .field("type", &self.var_type)
             .field("const_value", &self.const_value)
+            .field("offset", &self.offset)
             .finish()
     }
 }
@@ -31,4 +39,31 @@ impl AstVariable {
             .clone()
             .unwrap_or_else(|| self.id.get_default_name())
     }
+
+    /// Returns where the variable is first accessed, `None` if it doesn't come from ir.
+    pub fn access_location(&self) -> Option<&Aos<IrData>> {
+        let access = self
+            .data_access_ir
+            .as_ref()?
+            .values()
+            .flat_map(|x| x.iter())
+            .next()?;
+        Some(access.location())
+    }
+
+    /// Returns the offset from the frame base pointer of the stack slot the variable is stored
+    /// in, `None` if it is not stored in the stack frame.
+    pub fn frame_offset(&self) -> Option<i64> {
+        let offset = self
+            .access_location()?
+            .dereference()
+            .and_then(|inner| inner.get_offset_from_base_pointer())?;
+        match offset.as_ref() {
+            IrData::Operation(IrDataOperation::Unary {
+                operator: IrUnaryOperator::Negation,
+                arg,
+            }) => Some(-(arg.constant()? as i64)),
+            _ => Some(offset.constant()? as i64),
+        }
+    }
 }
@@ -1,9 +1,18 @@
 use std::ops::Deref;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Wrapped<T> {
     pub item: T,
     pub comment: Option<String>,
+    /// Instructions the item was decompiled from, `None` for generated items
+    pub origin: Option<AstAddressRange>,
+}
+
+/// Items are equal regardless of where they were decompiled from.
+impl<T: PartialEq> PartialEq for Wrapped<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.item == other.item && self.comment == other.comment
+    }
 }
 
 impl<T> AsRef<T> for Wrapped<T> {
@@ -18,3 +27,38 @@ impl<T> Deref for Wrapped<T> {
         &self.item
     }
 }
+
+/// Range of instruction addresses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct AstAddressRange {
+    /// Virtual address of the first instruction
+    pub start: u64,
+    /// Virtual address right after the last instruction
+    pub end: u64,
+}
+
+impl AstAddressRange {
+    /// Returns whether an address is in the range.
+    pub fn contains(&self, address: u64) -> bool {
+        self.start <= address && address < self.end
+    }
+
+    /// Returns the smallest range covering both ranges.
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// Merges the origins of items combined into one.
+    ///
+    /// ### Returns
+    /// - `Option<AstAddressRange>` - range covering both origins, or the known one
+    pub fn merge(a: Option<Self>, b: Option<Self>) -> Option<Self> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a.union(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
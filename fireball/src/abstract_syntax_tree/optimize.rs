@@ -1,12 +1,18 @@
+mod annotations;
 mod collapse_unused_variable;
 mod constant_folding;
+mod debug_info;
 mod ir_analyzation;
 pub(crate) mod opt_utils;
 mod parameter_analyzation;
 pub mod pattern_matching;
+mod return_analyzation;
+mod stack_frame_analyzation;
+mod struct_recovery;
 
 use super::*;
 use crate::pattern_matching::{AstPattern, AstPatternApplyPhase};
+use hashbrown::HashMap;
 use std::hash::Hash;
 
 impl Ast {
@@ -41,6 +47,13 @@ impl Ast {
             versions.push((function_id, to_version));
         }
 
+        for (function_id, to_version) in versions.iter().copied() {
+            if !has_function_version(&ast, function_id, to_version) {
+                continue;
+            }
+            debug_info::annotate_source_lines(&mut ast, function_id, to_version)?;
+        }
+
         apply_custom_patterns(
             &mut ast,
             &versions,
@@ -55,6 +68,9 @@ impl Ast {
                 }
                 ir_analyzation::analyze_ir_function(&mut ast, function_id, to_version)?;
             }
+            if config.stack_frame_analyzation {
+                stack_frame_analyzation::analyze_stack_frames(&mut ast, &versions)?;
+            }
             apply_custom_patterns(
                 &mut ast,
                 &versions,
@@ -63,12 +79,25 @@ impl Ast {
             )?;
         }
         if config.parameter_analyzation {
-            for (function_id, to_version) in versions.iter().copied() {
+            // callees first, so calls are given the parameters of the functions they call
+            let graph = CallGraph::new(&ast);
+            let components = graph.strongly_connected_components();
+            let bottom_up: HashMap<AstFunctionId, usize> = components
+                .iter()
+                .flatten()
+                .enumerate()
+                .map(|(i, function_id)| (*function_id, i))
+                .collect();
+            let mut ordered_versions = versions.clone();
+            ordered_versions.sort_by_key(|(function_id, _)| bottom_up.get(function_id).copied());
+            for (function_id, to_version) in ordered_versions {
                 if !has_function_version(&ast, function_id, to_version) {
                     continue;
                 }
                 parameter_analyzation::analyze_parameters(&mut ast, function_id, to_version)?;
+                parameter_analyzation::apply_callee_signatures(&mut ast, function_id, to_version)?;
             }
+            return_analyzation::analyze_returns(&mut ast, &versions, components)?;
             apply_custom_patterns(
                 &mut ast,
                 &versions,
@@ -76,6 +105,15 @@ impl Ast {
                 AstPatternApplyPhase::AfterParameterAnalyzation,
             )?;
         }
+        // parameters are matched by position, so names are applied once they are ordered
+        if config.ir_analyzation {
+            for (function_id, to_version) in versions.iter().copied() {
+                if !has_function_version(&ast, function_id, to_version) {
+                    continue;
+                }
+                debug_info::apply_debug_names(&mut ast, function_id, to_version)?;
+            }
+        }
 
         let max_pass_iterations = config.max_pass_iterations.max(1);
         for _ in 0..max_pass_iterations {
@@ -115,6 +153,10 @@ impl Ast {
             }
         }
 
+        if config.struct_recovery {
+            struct_recovery::recover_structs(&mut ast, &versions)?;
+        }
+
         apply_custom_patterns(
             &mut ast,
             &versions,
@@ -122,6 +164,14 @@ impl Ast {
             AstPatternApplyPhase::AfterOptimization,
         )?;
 
+        // names given by the user win over everything found while optimizing
+        for (function_id, to_version) in versions.iter().copied() {
+            if !has_function_version(&ast, function_id, to_version) {
+                continue;
+            }
+            annotations::apply_annotations(&mut ast, function_id, to_version)?;
+        }
+
         ast.shrink();
         Ok(ast)
     }
@@ -0,0 +1,112 @@
+//! Applies the names, types, parameter counts and comments given by the user.
+
+use crate::{
+    abstract_syntax_tree::{
+        Ast, AstAddressRange, AstFunctionId, AstFunctionVersion, AstStatement, AstValueType,
+        AstVariable, AstVariableAnnotation, AstVariableKey, Wrapped,
+        optimize::opt_utils::{for_each_declaration, nested_bodies},
+    },
+    prelude::*,
+};
+use hashbrown::HashMap;
+
+/// Names and types the function and its variables after its annotations, and puts the comments
+/// on the statements decompiled from their instructions.
+///
+/// ### Note
+/// - Runs after every other pass, so the annotations win over debug information and patterns.
+/// - Types which don't parse are left as detected.
+/// - A comment goes to the first statement starting at its instruction, or else to the first
+///   one covering it. Comments whose instruction was optimized away are dropped.
+pub(super) fn apply_annotations(
+    ast: &mut Ast,
+    function_id: AstFunctionId,
+    function_version: AstFunctionVersion,
+) -> Result<(), DecompileError> {
+    let annotation = ast.annotations.function(function_id.address).cloned();
+
+    let mut functions = ast.functions.write().unwrap();
+    let function = functions
+        .get_mut(&function_id)
+        .and_then(|x| x.get_mut(&function_version))
+        .unwrap();
+    let Some(annotation) = annotation else {
+        return Ok(());
+    };
+
+    if let Some(name) = &annotation.name {
+        function.name = Some(name.clone());
+    }
+    if let Some(return_type) = annotation
+        .return_type
+        .as_deref()
+        .and_then(|x| x.parse().ok())
+    {
+        function.return_type = return_type;
+    }
+    if let Some(count) = annotation.parameter_count {
+        function.parameters.truncate(count);
+    }
+
+    if !annotation.variables.is_empty() {
+        let mut annotated = HashMap::new();
+        for (var_id, variable) in function.variables.write().unwrap().iter_mut() {
+            if let Some(x) = annotation.variables.get(&AstVariableKey::of(variable)) {
+                annotate_variable(variable, x);
+                annotated.insert(*var_id, x);
+            }
+        }
+        for_each_declaration(&mut function.body, &mut |variable| {
+            if let Some(x) = annotated.get(&variable.id) {
+                annotate_variable(variable, x);
+            }
+        });
+    }
+
+    for (address, comment) in annotation.comments.iter() {
+        if !comment_on(&mut function.body, &|x| x.start == *address, comment) {
+            comment_on(&mut function.body, &|x| x.contains(*address), comment);
+        }
+    }
+    Ok(())
+}
+
+fn annotate_variable(variable: &mut AstVariable, annotation: &AstVariableAnnotation) {
+    if let Some(name) = &annotation.name {
+        variable.name = Some(name.clone());
+    }
+    if let Some(var_type) = annotation
+        .var_type
+        .as_deref()
+        .and_then(|x| x.parse::<AstValueType>().ok())
+    {
+        variable.var_type = var_type;
+    }
+}
+
+/// Puts the comment on the first statement whose origin matches, after the comment it has.
+///
+/// ### Returns
+/// - `bool` - whether a statement matched
+fn comment_on(
+    stmts: &mut [Wrapped<AstStatement>],
+    matches: &impl Fn(&AstAddressRange) -> bool,
+    comment: &str,
+) -> bool {
+    for stmt in stmts.iter_mut() {
+        if stmt.origin.as_ref().is_some_and(matches) {
+            stmt.comment = Some(match stmt.comment.take() {
+                Some(existing) => format!("{existing}; {comment}"),
+                None => comment.to_owned(),
+            });
+            return true;
+        }
+        if nested_bodies(&mut stmt.item)
+            .into_iter()
+            .any(|body| comment_on(body, matches, comment))
+        {
+            return true;
+        }
+    }
+    false
+}
@@ -141,6 +141,19 @@ pub(super) fn collapse_unused_variables(
                 new_body.push(stmt);
                 continue;
             }
+            AstStatement::Try(body, handlers) => {
+                // A handler may observe the state at any point of the body, and may not
+                // return to the statements after it
+                let mut body_overwritten_locations: HashSet<Aos<IrData>> = HashSet::new();
+                collapse(&variables, &mut body_overwritten_locations, body);
+                for (_handler, handler_body) in handlers.iter_mut() {
+                    let mut handler_overwritten_locations: HashSet<Aos<IrData>> = HashSet::new();
+                    collapse(&variables, &mut handler_overwritten_locations, handler_body);
+                }
+                overwritten_locations.clear();
+                new_body.push(stmt);
+                continue;
+            }
             AstStatement::Switch(_discrim, cases, default) => {
                 if cases.is_empty() && default.is_none() {
                     new_body.push(stmt);
@@ -176,11 +189,7 @@ pub(super) fn collapse_unused_variables(
             }
 
             /* etc */
-            AstStatement::Label(_)
-            | AstStatement::Comment(_)
-            | AstStatement::Break
-            | AstStatement::Continue
-            | AstStatement::Empty => {
+            AstStatement::Label(_) | AstStatement::Comment(_) | AstStatement::Empty => {
                 new_body.push(stmt);
                 continue;
             }
@@ -191,6 +200,8 @@ pub(super) fn collapse_unused_variables(
 
             /* next statements undetectable */
             AstStatement::Goto(_)
+            | AstStatement::Break
+            | AstStatement::Continue
             | AstStatement::Assembly(_)
             | AstStatement::Ir(_)
             | AstStatement::Return(_)
@@ -325,6 +336,16 @@ fn collapse(
                     collapse(variables, &mut loop_overwritten_locations, stmts);
                     overwritten_locations.clear();
                 }
+                AstStatement::Try(body, handlers) => {
+                    let mut body_overwritten_locations: HashSet<Aos<IrData>> = HashSet::new();
+                    collapse(variables, &mut body_overwritten_locations, body);
+                    for (_handler, handler_body) in handlers.iter_mut() {
+                        let mut handler_overwritten_locations: HashSet<Aos<IrData>> =
+                            HashSet::new();
+                        collapse(variables, &mut handler_overwritten_locations, handler_body);
+                    }
+                    overwritten_locations.clear();
+                }
                 AstStatement::Switch(_discrim, cases, default) => {
                     if cases.is_empty() && default.is_none() {
                         // nothing to do
@@ -359,17 +380,15 @@ fn collapse(
                 }
 
                 /* etc */
-                AstStatement::Label(_)
-                | AstStatement::Comment(_)
-                | AstStatement::Break
-                | AstStatement::Continue
-                | AstStatement::Empty => {}
+                AstStatement::Label(_) | AstStatement::Comment(_) | AstStatement::Empty => {}
                 AstStatement::Call(_) => {
                     // handled above
                 }
 
                 /* next statements undetectable */
                 AstStatement::Goto(_)
+                | AstStatement::Break
+                | AstStatement::Continue
                 | AstStatement::Assembly(_)
                 | AstStatement::Ir(_)
                 | AstStatement::Return(_)
@@ -2,8 +2,9 @@
 
 use crate::{
     abstract_syntax_tree::{
-        Ast, AstBuiltinFunctionArgument, AstCall, AstExpression, AstFunctionId, AstFunctionVersion,
-        AstLiteral, AstOptimizationKind, AstStatement, AstValue, AstVariableId, Wrapped,
+        Ast, AstBuiltinFunctionArgument, AstCall, AstExceptionHandler, AstExpression,
+        AstFunctionId, AstFunctionVersion, AstLiteral, AstOptimizationKind, AstStatement, AstValue,
+        AstVariableAccessType, AstVariableId, GetRelatedVariables, Wrapped,
     },
     prelude::DecompileError,
 };
@@ -133,22 +134,29 @@ fn fold_statement(
             }
         }
         AstStatement::While(cond, body) => {
+            // values written in the loop are not constant from the second iteration on
+            forget_written_variables(body.iter(), const_env);
             fold_expression(cond, const_env, true);
             let mut env_loop = const_env.clone();
             fold_statement_list(body, &mut env_loop);
+            *const_env = intersect_envs(const_env, &env_loop);
         }
         AstStatement::DoWhile(cond, body) => {
             // do-while evaluates body before condition, so fold body first.
+            forget_written_variables(body.iter(), const_env);
             let mut env_loop = const_env.clone();
             fold_statement_list(body, &mut env_loop);
             fold_expression(cond, &mut env_loop, true);
+            *const_env = intersect_envs(const_env, &env_loop);
         }
         AstStatement::For(init, cond, update, body) => {
             fold_statement(init, const_env);
+            forget_written_variables(body.iter().chain([&**update]), const_env);
             fold_expression(cond, const_env, true);
             let mut env_loop = const_env.clone();
             fold_statement_list(body, &mut env_loop);
             fold_statement(update, &mut env_loop);
+            *const_env = intersect_envs(const_env, &env_loop);
         }
         AstStatement::Switch(discrim, cases, default) => {
             fold_expression(discrim, const_env, true);
@@ -174,6 +182,22 @@ fn fold_statement(
                 *const_env = result;
             }
         }
+        AstStatement::Try(body, handlers) => {
+            let mut env_body = const_env.clone();
+            fold_statement_list(body, &mut env_body);
+            // handlers may be entered from any point of the body
+            forget_written_variables(body.iter(), const_env);
+            let mut result = env_body;
+            for (handler, handler_body) in handlers.iter_mut() {
+                let mut env_handler = const_env.clone();
+                if let AstExceptionHandler::Except(filter) = handler {
+                    fold_expression(filter, &env_handler, true);
+                }
+                fold_statement_list(handler_body, &mut env_handler);
+                result = intersect_envs(&result, &env_handler);
+            }
+            *const_env = result;
+        }
         AstStatement::Block(body) => {
             let mut env_block = const_env.clone();
             fold_statement_list(body, &mut env_block);
@@ -190,20 +214,31 @@ fn fold_statement(
             const_env.clear();
         }
         AstStatement::Goto(_)
+        | AstStatement::Label(_)
         | AstStatement::Assembly(_)
         | AstStatement::Ir(_)
         | AstStatement::Undefined
         | AstStatement::Exception(_) => {
             const_env.clear();
         }
-        AstStatement::Label(_)
-        | AstStatement::Comment(_)
+        AstStatement::Comment(_)
         | AstStatement::Break
         | AstStatement::Continue
         | AstStatement::Empty => {}
     }
 }
 
+fn forget_written_variables<'a>(
+    stmts: impl Iterator<Item = &'a Wrapped<AstStatement>>,
+    const_env: &mut HashMap<AstVariableId, AstLiteral>,
+) {
+    for (access_type, var_id) in stmts.flat_map(|x| x.get_related_variables()) {
+        if matches!(access_type, AstVariableAccessType::Write) {
+            const_env.remove(&var_id);
+        }
+    }
+}
+
 fn fold_call(call: &mut AstCall, const_env: &HashMap<AstVariableId, AstLiteral>) {
     match call {
         AstCall::Variable { args, .. }
@@ -357,5 +392,6 @@ fn wrap_with_source(
     Wrapped {
         item,
         comment: source.comment.clone(),
+        origin: source.origin,
     }
 }
@@ -0,0 +1,161 @@
+//! Apply the names, types and source lines of the binary's debug information.
+
+use crate::{
+    abstract_syntax_tree::{
+        Ast, AstFunctionId, AstFunctionVersion, AstStatement, AstVariable, AstVariableId,
+    },
+    debug_info::{DebugFunction, DebugLocation},
+    ir::data::IrData,
+    prelude::*,
+};
+use either::Either;
+use hashbrown::{HashMap, HashSet};
+
+/// Comments the first statement compiled from each source line with the line (`main.c:12`).
+///
+/// ### Note
+/// - Runs on `Ir` statements, before ir analyzation carries their comments over.
+pub(super) fn annotate_source_lines(
+    ast: &mut Ast,
+    function_id: AstFunctionId,
+    function_version: AstFunctionVersion,
+) -> Result<(), DecompileError> {
+    if ast.debug_info.is_empty() {
+        return Ok(());
+    }
+    let mut functions = ast.functions.write().unwrap();
+    let function = functions
+        .get_mut(&function_id)
+        .and_then(|x| x.get_mut(&function_version))
+        .unwrap();
+    let ir = function.origin_ir.get_ir();
+    let mut last_line = None;
+    for stmt in function.body.iter_mut() {
+        let AstStatement::Ir(ir_stmt) = &stmt.item else {
+            continue;
+        };
+        let Some(ir_index) = ir_stmt.0 else {
+            continue;
+        };
+        let address = ir[ir_index as usize].address.get_virtual_address();
+        let line = ast.debug_info.line_at(address);
+        if line.is_none() || line == last_line {
+            continue;
+        }
+        last_line = line;
+        if stmt.comment.is_none() {
+            stmt.comment = line.map(ToString::to_string);
+        }
+    }
+    Ok(())
+}
+
+/// Names the function, its parameters and its local variables after the debug information,
+/// and gives them their declared types.
+///
+/// ### Note
+/// - Local variables are matched by register or by offset from the frame base pointer.
+/// - Parameters are matched by position, after parameter analyzation ordered them.
+pub(super) fn apply_debug_names(
+    ast: &mut Ast,
+    function_id: AstFunctionId,
+    function_version: AstFunctionVersion,
+) -> Result<(), DecompileError> {
+    let Some(debug_function) = ast.debug_info.function_at(function_id.address) else {
+        return Ok(());
+    };
+    let mut functions = ast.functions.write().unwrap();
+    let function = functions
+        .get_mut(&function_id)
+        .and_then(|x| x.get_mut(&function_version))
+        .unwrap();
+    function.name = Some(debug_function.name.clone());
+    function.return_type = debug_function.return_type.clone();
+
+    let mut renamed: HashMap<AstVariableId, AstVariable> = HashMap::new();
+    {
+        let mut variables = function.variables.write().unwrap();
+        let named = name_by_location(debug_function, &mut variables);
+        let taken: HashSet<String> = named.values().cloned().collect();
+
+        for (parameter, debug_parameter) in function
+            .parameters
+            .iter_mut()
+            .zip(debug_function.parameters.iter())
+        {
+            let var_id = match &mut parameter.id {
+                Either::Left(var_id) => *var_id,
+                Either::Right(name) => {
+                    *name = debug_parameter.name.clone();
+                    continue;
+                }
+            };
+            let Some(variable) = variables.get_mut(&var_id) else {
+                continue;
+            };
+            // the parameter register is usually spilled to the slot the debug information names
+            let name = match named.get(&var_id) {
+                Some(name) if *name == debug_parameter.name => continue,
+                _ if taken.contains(&debug_parameter.name) => {
+                    format!("{}_arg", debug_parameter.name)
+                }
+                _ => debug_parameter.name.clone(),
+            };
+            variable.name = Some(name);
+            variable.var_type = debug_parameter.var_type.clone();
+        }
+        for var_id in named.keys().chain(
+            function
+                .parameters
+                .iter()
+                .filter_map(|x| x.id.as_ref().left()),
+        ) {
+            if let Some(variable) = variables.get(var_id) {
+                renamed.insert(*var_id, variable.clone());
+            }
+        }
+    }
+    super::opt_utils::for_each_declaration(&mut function.body, &mut |variable| {
+        if let Some(renamed) = renamed.get(&variable.id) {
+            variable.name = renamed.name.clone();
+            variable.var_type = renamed.var_type.clone();
+        }
+    });
+    Ok(())
+}
+
+/// Names the variables stored where a parameter or local variable of the debug information is.
+///
+/// ### Returns
+/// - `HashMap<AstVariableId, String>` - the names given, by variable
+fn name_by_location(
+    debug_function: &DebugFunction,
+    variables: &mut HashMap<AstVariableId, AstVariable>,
+) -> HashMap<AstVariableId, String> {
+    let mut named = HashMap::new();
+    for (var_id, variable) in variables.iter_mut() {
+        let Some(location) = variable_location(variable) else {
+            continue;
+        };
+        let debug_variable = debug_function
+            .parameters
+            .iter()
+            .chain(debug_function.variables.iter())
+            .find(|x| x.location.as_ref() == Some(&location));
+        let Some(debug_variable) = debug_variable else {
+            continue;
+        };
+        variable.name = Some(debug_variable.name.clone());
+        variable.var_type = debug_variable.var_type.clone();
+        named.insert(*var_id, debug_variable.name.clone());
+    }
+    named
+}
+
+/// Returns where a variable is stored, in the terms of the debug information.
+fn variable_location(variable: &AstVariable) -> Option<DebugLocation> {
+    if let IrData::Register(register) = variable.access_location()?.as_ref() {
+        return Some(DebugLocation::Register(register.name().to_string()));
+    }
+    variable.frame_offset().map(DebugLocation::FrameOffset)
+}
@@ -1,12 +1,13 @@
 //! Convert IR statements into high-level AST representation.
 
 mod convert;
+mod structuring;
 
 use crate::{
     abstract_syntax_tree::{
         Ast, AstFunctionId, AstFunctionVersion, AstOptimizationKind, AstStatement, AstValue,
         AstValueType, AstVariable, AstVariableId, PrintWithConfig, Wrapped,
-        optimize::ir_analyzation::convert::{convert_stmt, resolve_constant},
+        optimize::ir_analyzation::convert::{convert_expr, convert_stmt, resolve_constant},
     },
     ir::{analyze::DataType, data::IrData},
     prelude::{DecompileError, *},
@@ -44,6 +45,8 @@ pub(super) fn analyze_ir_function(
 
     let mut locals = HashMap::new();
     let mut var_map: HashMap<Aos<IrData>, AstVariableId> = HashMap::new();
+    // A location can hold different variables, so each instruction maps its own accesses
+    let mut instruction_vars: HashMap<u32, Vec<(Aos<IrData>, AstVariableId)>> = HashMap::new();
     for var in ir_function.get_variables().iter() {
         let var_id = ast.new_variable_id(&function_id);
         let mut c_type = match var.data_type {
@@ -60,6 +63,7 @@ pub(super) fn analyze_ir_function(
         let mut accesses_by_position: Vec<_> = var.get_data_accesses().iter().collect();
         accesses_by_position.sort_unstable_by_key(|(position, _)| position.to_u64());
         for (position, accesses) in accesses_by_position {
+            let ir_index = position.ir_index();
             let instruction_arg_size = ir_function.get_instructions()[position.ir_index() as usize]
                 .inner
                 .arguments
@@ -74,6 +78,10 @@ pub(super) fn analyze_ir_function(
             let position = &ir_function.get_ir()[position.ir_index() as usize].address;
             for da in accesses.iter() {
                 var_map.insert(da.location().clone(), var_id);
+                instruction_vars
+                    .entry(ir_index)
+                    .or_default()
+                    .push((da.location().clone(), var_id));
                 // Resolve constant value
                 if let Some(c) = resolve_constant(
                     position,
@@ -136,6 +144,7 @@ pub(super) fn analyze_ir_function(
                 var_type: c_type,
                 const_value,
                 data_access_ir: Some(var.get_data_accesses().clone()),
+                offset: None,
             },
         );
     }
@@ -147,6 +156,13 @@ pub(super) fn analyze_ir_function(
         .unwrap()
         .variables = Arc::new(RwLock::new(locals));
 
+    let positions: Vec<Option<u32>> = body
+        .iter()
+        .map(|ws| match &ws.item {
+            AstStatement::Ir(stmt) => stmt.0,
+            _ => None,
+        })
+        .collect();
     let map = ir_function.get_instructions().as_ref();
     for ws in &mut body {
         // skip if not analyzable
@@ -160,6 +176,9 @@ pub(super) fn analyze_ir_function(
 
         let instruction = &map[usize::try_from(*ir_index).unwrap()];
         let instruction_args = &instruction.inner.arguments;
+        if let Some(vars) = instruction_vars.get(ir_index) {
+            var_map.extend(vars.iter().cloned());
+        }
         /* analyze and turn into ast */
         let mut stmt = convert_stmt(
             ast,
@@ -170,8 +189,12 @@ pub(super) fn analyze_ir_function(
             instruction_args,
         )?;
         stmt.comment = ws.comment.clone();
+        super::opt_utils::inherit_origin(&mut stmt, ws.origin);
         *ws = stmt;
     }
+    let body = structuring::structure_function_body(&ir_function, body, &positions, |data| {
+        convert_expr(ast, function_id, function_version, data, &var_map).ok()
+    });
 
     {
         let mut functions = ast.functions.write().unwrap();
@@ -23,6 +23,7 @@ pub(super) fn w<T>(item: T) -> Wrapped<T> {
     Wrapped {
         item,
         comment: None,
+        origin: None,
     }
 }
 
@@ -58,6 +59,16 @@ fn ast_value_constant_address(value: &AstValue) -> Option<u64> {
     }
 }
 
+/// Returns the pre-defined symbol (e.g., an import reached through a PLT stub or GOT slot)
+/// a call or jump target refers to.
+fn resolve_symbol_name(ast: &Ast, expr: &AstExpression) -> Option<String> {
+    let address = match expr {
+        AstExpression::Deref(inner) => expr_constant_address(&inner.item),
+        _ => expr_constant_address(expr),
+    }?;
+    ast.pre_defined_symbols.get(&address).cloned()
+}
+
 fn resolve_function_id_by_address(ast: &Ast, addr: u64) -> Option<AstFunctionId> {
     let exact = AstFunctionId { address: addr };
     if ast.functions.read().ok()?.contains_key(&exact) {
@@ -316,12 +327,18 @@ pub(super) fn convert_stmt(
             let e = convert_expr(ast, function_id, function_version, target, var_map)?;
             let exact_target = expr_constant_address(e.as_ref())
                 .and_then(|addr| resolve_function_id_by_address(ast, addr));
-            match (e.as_ref(), exact_target) {
-                (_, Some(target)) => AstStatement::Call(AstCall::Function {
+            // imports and plt stubs are only named, functions of the ast keep their id
+            let symbol = exact_target
+                .is_none()
+                .then(|| resolve_symbol_name(ast, e.as_ref()))
+                .flatten();
+            match (e.as_ref(), exact_target, symbol) {
+                (_, Some(target), _) => AstStatement::Call(AstCall::Function {
                     target,
                     args: Vec::new(),
                 }),
-                (AstExpression::Variable(vars, id), None) => {
+                (_, None, Some(name)) => AstStatement::Call(AstCall::Unknown(name, Vec::new())),
+                (AstExpression::Variable(vars, id), None, None) => {
                     AstStatement::Call(AstCall::Variable {
                         scope: function_id,
                         var_map: vars.clone(),
@@ -329,7 +346,7 @@ pub(super) fn convert_stmt(
                         args: Vec::new(),
                     })
                 }
-                (_, None) => {
+                (_, None, None) => {
                     warn!("Uncovered call target");
                     let name = e.to_string_with_config(None);
                     AstStatement::Call(AstCall::Unknown(name, Vec::new()))
@@ -341,16 +358,22 @@ pub(super) fn convert_stmt(
             let e = convert_expr(ast, function_id, function_version, target, var_map)?;
             let exact_target = expr_constant_address(e.as_ref())
                 .and_then(|addr| resolve_function_id_by_address(ast, addr));
-            match (e.as_ref(), exact_target) {
-                (_, Some(target)) => AstStatement::Goto(AstJumpTarget::Function { target }),
-                (AstExpression::Variable(vars, id), None) => {
+            // jumping to another function's symbol is a tail call
+            let symbol = exact_target
+                .is_none()
+                .then(|| resolve_symbol_name(ast, e.as_ref()))
+                .flatten();
+            match (e.as_ref(), exact_target, symbol) {
+                (_, Some(target), _) => AstStatement::Goto(AstJumpTarget::Function { target }),
+                (_, None, Some(name)) => AstStatement::Goto(AstJumpTarget::Unknown(name)),
+                (AstExpression::Variable(vars, id), None, None) => {
                     AstStatement::Goto(AstJumpTarget::Variable {
                         scope: function_id,
                         var_map: vars.clone(),
                         var_id: *id,
                     })
                 }
-                (_, None) => {
+                (_, None, None) => {
                     warn!("Uncovered jump target");
                     let label = e.to_string_with_config(None);
                     AstStatement::Goto(AstJumpTarget::Unknown(label))
@@ -1047,3 +1070,41 @@ fn refine_extend_cast(
         rhs.item = AstExpression::Cast(ty, inner.clone());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(ast: &mut Ast, caller: AstFunctionId, address: usize) -> AstStatement {
+        let target = IrData::Constant(address).into();
+        convert_stmt(
+            ast,
+            caller,
+            AstFunctionVersion(1),
+            &IrStatement::JumpByCall { target },
+            &HashMap::new(),
+            &[],
+        )
+        .unwrap()
+        .item
+    }
+
+    #[test]
+    fn test_named_callee_stays_function() {
+        let mut ast = Ast::new();
+        let caller = ast.insert_test_function(0x1000, Vec::new());
+        let callee = ast.insert_test_function(0x2000, Vec::new());
+        ast.pre_defined_symbols.insert(0x2000, "helper".to_owned());
+        ast.pre_defined_symbols.insert(0x3000, "puts".to_owned());
+
+        assert!(matches!(
+            call(&mut ast, caller, 0x2000),
+            AstStatement::Call(AstCall::Function { target, .. }) if target == callee
+        ));
+        // addresses outside of the ast are only known by their symbol
+        assert!(matches!(
+            call(&mut ast, caller, 0x3000),
+            AstStatement::Call(AstCall::Unknown(name, _)) if name == "puts"
+        ));
+    }
+}
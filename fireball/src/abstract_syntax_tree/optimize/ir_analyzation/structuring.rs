@@ -0,0 +1,1214 @@
+//! Restore structured control flow (if / while / do-while / for / try) from the block graph of
+//! the function, so the converted body no longer depends on raw jumps.
+
+use crate::{
+    abstract_syntax_tree::{
+        AstAddressRange, AstBinaryOperator, AstCall, AstExceptionHandler, AstExpression,
+        AstFunctionId, AstJumpTarget, AstLiteral, AstStatement, AstUnaryOperator, Wrapped,
+        optimize::{
+            ir_analyzation::convert::w,
+            opt_utils::{inherit_expression_origin, nested_bodies},
+        },
+    },
+    exception_handling::{ExceptionFilter, ExceptionHandlerKind},
+    ir::{
+        analyze::{ControlFlowGraph, IrFunction, NaturalLoop, control_flow::ControlFlowEdgeKind},
+        data::IrData,
+    },
+    utils::Aos,
+};
+use hashbrown::{HashMap, HashSet};
+
+/// How the control leaves a block, with the jump statements already taken out of its body
+#[derive(Debug, Clone)]
+enum Terminator {
+    /// Leaves the function (or jumps somewhere unknown)
+    None,
+    Jump(usize),
+    Branch {
+        condition: Wrapped<AstExpression>,
+        taken: usize,
+        not_taken: usize,
+    },
+    /// Jump table dispatch, with the index value and destination of each case
+    Switch {
+        discriminant: Wrapped<AstExpression>,
+        cases: Vec<(u64, usize)>,
+        /// Destination of the values out of the table, once its bounds check is folded in
+        default: Option<usize>,
+    },
+}
+
+/// Where the statements being emitted are placed
+#[derive(Debug, Clone, Default)]
+struct Context {
+    /// Block the current sequence falls into when it is done
+    follow: Option<usize>,
+    /// Header of the innermost loop, reached by `continue`
+    loop_header: Option<usize>,
+    /// Block after the innermost loop, reached by `break`
+    loop_follow: Option<usize>,
+    /// Blocks emitted later by the enclosing statements, reached only by `goto`
+    outer: Vec<usize>,
+}
+
+impl Context {
+    fn nested(&self) -> Self {
+        let mut outer = self.outer.clone();
+        outer.extend(self.follow);
+        Self {
+            follow: None,
+            loop_header: self.loop_header,
+            loop_follow: self.loop_follow,
+            outer,
+        }
+    }
+}
+
+struct Structurer<'a> {
+    cfg: &'a ControlFlowGraph,
+    bodies: Vec<Vec<Wrapped<AstStatement>>>,
+    terminators: Vec<Terminator>,
+    ipdom: Vec<Option<usize>>,
+    loops: HashMap<usize, NaturalLoop>,
+    emitted: Vec<bool>,
+    used_labels: HashSet<String>,
+}
+
+/// Rebuild the body of the function into structured statements
+///
+/// ### Arguments
+/// - `ir_function: &IrFunction` - origin of the body
+/// - `body: Vec<Wrapped<AstStatement>>` - converted flat body
+/// - `positions: &[Option<u32>]` - ir index each statement of the body was generated from
+/// - `convert_index: impl FnMut(&Aos<IrData>) -> Option<Wrapped<AstExpression>>` - converts
+///   the index of a jump table into the expression switched on
+///
+/// ### Returns
+/// - `Vec<Wrapped<AstStatement>>` - structured body. Edges that can't be structured are kept
+///   as `goto`.
+pub(super) fn structure_function_body(
+    ir_function: &IrFunction,
+    body: Vec<Wrapped<AstStatement>>,
+    positions: &[Option<u32>],
+    convert_index: impl FnMut(&Aos<IrData>) -> Option<Wrapped<AstExpression>>,
+) -> Vec<Wrapped<AstStatement>> {
+    let cfg = ir_function.get_control_flow();
+    if cfg.is_empty() || body.len() != positions.len() {
+        return body;
+    }
+    let block_count = cfg.get_blocks().len();
+    let positions = resolve_positions(ir_function, positions);
+
+    let mut statements: Vec<BlockStatements> = vec![Vec::new(); block_count];
+    for (stmt, position) in body.into_iter().zip(positions) {
+        let block = cfg.block_of_ir_index(position).unwrap_or(cfg.get_entry());
+        statements[block].push((position, stmt));
+    }
+    structure(cfg, statements, convert_index)
+}
+
+/// Statements of a block, with the ir index each one was generated from
+type BlockStatements = Vec<(u32, Wrapped<AstStatement>)>;
+
+/// Rebuild the statements of the blocks of the graph into structured statements
+///
+/// ### Arguments
+/// - `cfg: &ControlFlowGraph` - block graph of the function
+/// - `statements: Vec<BlockStatements>` - statements of each block, in block order
+/// - `convert_index: impl FnMut(&Aos<IrData>) -> Option<Wrapped<AstExpression>>` - converts
+///   the index of a jump table into the expression switched on
+///
+/// ### Returns
+/// - `Vec<Wrapped<AstStatement>>` - structured body
+fn structure(
+    cfg: &ControlFlowGraph,
+    statements: Vec<BlockStatements>,
+    mut convert_index: impl FnMut(&Aos<IrData>) -> Option<Wrapped<AstExpression>>,
+) -> Vec<Wrapped<AstStatement>> {
+    let block_count = cfg.get_blocks().len();
+    let mut bodies = Vec::with_capacity(block_count);
+    let mut terminators = Vec::with_capacity(block_count);
+    for (block, mut stmts) in statements.into_iter().enumerate() {
+        terminators.push(take_terminator(cfg, block, &mut stmts, &mut convert_index));
+        bodies.push(stmts.into_iter().map(|(_, stmt)| stmt).collect());
+    }
+    fold_switch_bounds_checks(cfg, &mut terminators);
+
+    let idom = cfg.immediate_dominators();
+    let loops = cfg
+        .natural_loops(&idom)
+        .into_iter()
+        .map(|x| (x.header, x))
+        .collect();
+    let mut structurer = Structurer {
+        cfg,
+        bodies,
+        terminators,
+        ipdom: cfg.immediate_post_dominators(),
+        loops,
+        emitted: vec![false; block_count],
+        used_labels: HashSet::new(),
+    };
+
+    let mut result = Vec::new();
+    let root = Context::default();
+    structurer.emit_sequence(cfg.get_entry(), &root, &mut result);
+    // handlers are entered by the unwinder, not by a jump
+    let mut handler_bodies: HashMap<usize, Vec<Wrapped<AstStatement>>> = HashMap::new();
+    for handler in cfg.get_handlers() {
+        if !handler_bodies.contains_key(&handler.handler) {
+            let mut body = Vec::new();
+            structurer.emit_sequence(handler.handler, &root, &mut body);
+            handler_bodies.insert(handler.handler, body);
+        }
+    }
+    // blocks not reachable from the entry
+    for block in 0..block_count {
+        if !structurer.emitted[block] {
+            structurer.emit_sequence(block, &root, &mut result);
+        }
+    }
+
+    refine(&mut result, &structurer.used_labels);
+    for body in handler_bodies.values_mut() {
+        refine(body, &structurer.used_labels);
+    }
+    wrap_protected_ranges(cfg, &mut result, handler_bodies);
+    result
+}
+
+/// Wrap the statements of each protected range into a `try` statement with its handlers
+///
+/// ### Arguments
+/// - `cfg: &ControlFlowGraph` - graph holding the protected ranges
+/// - `result: &mut Vec<Wrapped<AstStatement>>` - structured body
+/// - `handler_bodies: HashMap<usize, Vec<Wrapped<AstStatement>>>` - structured body of each
+///   handler block
+///
+/// ### Note
+/// Handlers whose protected statements can't be found are appended to the end of the body.
+fn wrap_protected_ranges(
+    cfg: &ControlFlowGraph,
+    result: &mut Vec<Wrapped<AstStatement>>,
+    mut handler_bodies: HashMap<usize, Vec<Wrapped<AstStatement>>>,
+) {
+    // a handler guarding several call sites protects everything between them
+    let mut handlers: Vec<(AstAddressRange, usize, &ExceptionHandlerKind)> = Vec::new();
+    for handler in cfg.get_handlers() {
+        let range = AstAddressRange {
+            start: handler.start,
+            end: handler.end,
+        };
+        match handlers.iter_mut().find(|x| x.1 == handler.handler) {
+            Some(x) => x.0 = x.0.union(range),
+            None => handlers.push((range, handler.handler, &handler.kind)),
+        }
+    }
+    let mut ranges: Vec<(AstAddressRange, Vec<(usize, &ExceptionHandlerKind)>)> = Vec::new();
+    for (range, handler, kind) in handlers {
+        match ranges.iter_mut().find(|x| x.0 == range) {
+            Some(x) => x.1.push((handler, kind)),
+            None => ranges.push((range, vec![(handler, kind)])),
+        }
+    }
+    // inner ranges first, so the outer ones contain their `try`
+    ranges.sort_by_key(|(range, _)| (range.end - range.start, range.start));
+
+    let mut unplaced = Vec::new();
+    for (range, handlers) in ranges {
+        let mut clauses = Some(
+            handlers
+                .into_iter()
+                .map(|(handler, kind)| {
+                    let body = handler_bodies.remove(&handler).unwrap_or_default();
+                    (exception_handler(kind), body)
+                })
+                .collect::<Vec<_>>(),
+        );
+        if !wrap_try(result, range, &mut clauses) {
+            for body in handler_bodies.values_mut() {
+                if wrap_try(body, range, &mut clauses) {
+                    break;
+                }
+            }
+        }
+        if let Some(clauses) = clauses {
+            unplaced.extend(clauses.into_iter().flat_map(|(_, body)| body));
+        }
+    }
+    result.extend(handler_bodies.into_values().flatten());
+    result.extend(unplaced);
+}
+
+/// Handler of a `try` statement, with its body
+type TryClause = (AstExceptionHandler, Vec<Wrapped<AstStatement>>);
+
+/// Wrap the statements decompiled from the protected range into a `try` statement
+///
+/// ### Arguments
+/// - `stmts: &mut Vec<Wrapped<AstStatement>>` - statements to search the range in
+/// - `range: AstAddressRange` - protected range
+/// - `clauses: &mut Option<Vec<TryClause>>` - handlers of the range, taken when the statements
+///   are found
+///
+/// ### Returns
+/// - `bool` - whether the statements were found
+fn wrap_try(
+    stmts: &mut Vec<Wrapped<AstStatement>>,
+    range: AstAddressRange,
+    clauses: &mut Option<Vec<TryClause>>,
+) -> bool {
+    let inside = |x: &Wrapped<AstStatement>| {
+        x.origin
+            .is_some_and(|x| range.start <= x.start && x.end <= range.end)
+    };
+    if let (Some(first), Some(last)) = (
+        stmts.iter().position(inside),
+        stmts.iter().rposition(inside),
+    ) {
+        let Some(clauses) = clauses.take() else {
+            return false;
+        };
+        let body: Vec<_> = stmts.drain(first..=last).collect();
+        let origin = body
+            .iter()
+            .fold(None, |acc, x| AstAddressRange::merge(acc, x.origin));
+        stmts.insert(first, at(origin, AstStatement::Try(body, clauses)));
+        return true;
+    }
+    stmts.iter_mut().any(|stmt| {
+        nested_bodies(&mut stmt.item)
+            .into_iter()
+            .any(|body| wrap_try(body, range, clauses))
+    })
+}
+
+fn exception_handler(kind: &ExceptionHandlerKind) -> AstExceptionHandler {
+    match kind {
+        ExceptionHandlerKind::Catch(types) => AstExceptionHandler::Catch(types.clone()),
+        ExceptionHandlerKind::Except(ExceptionFilter::Constant(value)) => {
+            AstExceptionHandler::Except(w(AstExpression::Literal(AstLiteral::Int(i64::from(
+                *value,
+            )))))
+        }
+        ExceptionHandlerKind::Except(ExceptionFilter::Function(address)) => {
+            AstExceptionHandler::Except(w(AstExpression::Call(AstCall::Function {
+                target: AstFunctionId { address: *address },
+                args: Vec::new(),
+            })))
+        }
+        ExceptionHandlerKind::Cleanup => AstExceptionHandler::Finally,
+    }
+}
+
+/// Give every statement the ir index it belongs to.
+///
+/// Statements without an ir index come from instructions without ir (inline assembly), in order.
+fn resolve_positions(ir_function: &IrFunction, positions: &[Option<u32>]) -> Vec<u32> {
+    let mut no_ir = ir_function
+        .get_ir()
+        .iter()
+        .enumerate()
+        .filter(|(_, ir)| ir.statements.is_none())
+        .map(|(i, _)| i as u32)
+        .peekable();
+    let mut next_known = vec![None; positions.len()];
+    let mut known = None;
+    for (i, position) in positions.iter().enumerate().rev() {
+        next_known[i] = known;
+        if position.is_some() {
+            known = *position;
+        }
+    }
+
+    let mut result = Vec::with_capacity(positions.len());
+    let mut last: Option<u32> = None;
+    for (i, position) in positions.iter().enumerate() {
+        let position = match position {
+            Some(position) => *position,
+            None => {
+                let lower = last.map(|x| x + 1).unwrap_or(0);
+                while no_ir.next_if(|x| *x < lower).is_some() {}
+                match no_ir.next_if(|x| next_known[i].is_none_or(|next| *x < next)) {
+                    Some(position) => position,
+                    None => last.unwrap_or(0),
+                }
+            }
+        };
+        last = Some(position);
+        result.push(position);
+    }
+    result
+}
+
+/// Take the jump statements of the block's last instruction out of the body, returning how
+/// the block is left.
+fn take_terminator(
+    cfg: &ControlFlowGraph,
+    block: usize,
+    stmts: &mut BlockStatements,
+    convert_index: &mut impl FnMut(&Aos<IrData>) -> Option<Wrapped<AstExpression>>,
+) -> Terminator {
+    let block = &cfg.get_blocks()[block];
+    let last = block.get_ir_range().end.saturating_sub(1);
+    let successor = |kind: ControlFlowEdgeKind| {
+        block
+            .get_successors()
+            .iter()
+            .find(|x| x.kind == kind)
+            .map(|x| x.to)
+    };
+    let remove_last_goto = |stmts: &mut BlockStatements| {
+        let index = stmts
+            .iter()
+            .rposition(|(p, s)| *p == last && matches!(s.item, AstStatement::Goto(_)))?;
+        stmts.remove(index).1.origin
+    };
+
+    if let Some(switch) = block.get_switch()
+        && let Some(mut discriminant) = convert_index(&switch.index)
+    {
+        // the dispatch is traced back to the indirect jump
+        let origin = remove_last_goto(stmts);
+        inherit_expression_origin(&mut discriminant, origin);
+        return Terminator::Switch {
+            discriminant,
+            cases: switch.cases.clone(),
+            default: None,
+        };
+    }
+
+    if let (Some(taken), Some(not_taken)) = (
+        successor(ControlFlowEdgeKind::Taken),
+        successor(ControlFlowEdgeKind::NotTaken),
+    ) {
+        let index = stmts
+            .iter()
+            .rposition(|(p, s)| *p == last && matches!(s.item, AstStatement::If(..)));
+        let Some(index) = index else {
+            return Terminator::Jump(not_taken);
+        };
+        let AstStatement::If(condition, _, _) = stmts.remove(index).1.item else {
+            unreachable!()
+        };
+        return Terminator::Branch {
+            condition,
+            taken,
+            not_taken,
+        };
+    }
+    if let Some(to) = successor(ControlFlowEdgeKind::Jump) {
+        remove_last_goto(stmts);
+        return Terminator::Jump(to);
+    }
+    match successor(ControlFlowEdgeKind::Fallthrough)
+        .or_else(|| successor(ControlFlowEdgeKind::NotTaken))
+    {
+        Some(to) => Terminator::Jump(to),
+        None => Terminator::None,
+    }
+}
+
+/// Turns `if (index > max) goto default; switch (index) { ... }` into
+/// `switch (index) { ...; default: ... }`, when the bounds check only guards the switch.
+fn fold_switch_bounds_checks(cfg: &ControlFlowGraph, terminators: &mut [Terminator]) {
+    for guard in 0..terminators.len() {
+        let Terminator::Branch {
+            taken, not_taken, ..
+        } = terminators[guard]
+        else {
+            continue;
+        };
+        for (dispatch, default) in [(taken, not_taken), (not_taken, taken)] {
+            let block = &cfg.get_blocks()[dispatch];
+            let Some(switch) = block.get_switch() else {
+                continue;
+            };
+            if switch.default != Some(default) || block.get_predecessors() != [guard] {
+                continue;
+            }
+            let Terminator::Switch {
+                default: folded, ..
+            } = &mut terminators[dispatch]
+            else {
+                continue;
+            };
+            *folded = Some(default);
+            terminators[guard] = Terminator::Jump(dispatch);
+            break;
+        }
+    }
+}
+
+impl Structurer<'_> {
+    fn label(&self, block: usize) -> String {
+        format!(
+            "label_{:x}",
+            self.cfg.get_blocks()[block]
+                .get_start_address()
+                .get_virtual_address()
+        )
+    }
+
+    fn goto(&mut self, block: usize) -> Wrapped<AstStatement> {
+        let label = self.label(block);
+        self.used_labels.insert(label.clone());
+        w(AstStatement::Goto(AstJumpTarget::Unknown(label)))
+    }
+
+    fn in_loop(&self, ctx: &Context, block: usize) -> bool {
+        match ctx.loop_header {
+            Some(header) => self.loops[&header].nodes.contains(&block),
+            None => true,
+        }
+    }
+
+    /// Statement reaching the block, if it can't be emitted at the current place
+    fn exit_statement(&mut self, block: usize, ctx: &Context) -> Option<Wrapped<AstStatement>> {
+        if ctx.loop_header == Some(block) {
+            return Some(w(AstStatement::Continue));
+        }
+        if ctx.loop_follow == Some(block) {
+            return Some(w(AstStatement::Break));
+        }
+        if self.emitted[block] || ctx.outer.contains(&block) || !self.in_loop(ctx, block) {
+            return Some(self.goto(block));
+        }
+        None
+    }
+
+    /// Emit the blocks starting from `start` until the flow reaches `ctx.follow` or leaves
+    fn emit_sequence(&mut self, start: usize, ctx: &Context, out: &mut Vec<Wrapped<AstStatement>>) {
+        let mut current = Some(start);
+        while let Some(block) = current {
+            if ctx.follow == Some(block) {
+                return;
+            }
+            if let Some(stmt) = self.exit_statement(block, ctx) {
+                out.push(stmt);
+                return;
+            }
+            current = self.emit_block(block, ctx, out);
+        }
+    }
+
+    /// Emit the body of the loop, starting from its header
+    fn emit_loop_body(
+        &mut self,
+        header: usize,
+        ctx: &Context,
+        out: &mut Vec<Wrapped<AstStatement>>,
+    ) {
+        if let Some(next) = self.emit_block(header, ctx, out) {
+            self.emit_sequence(next, ctx, out);
+        }
+    }
+
+    /// ### Returns
+    /// - `Option<usize>` - block the flow continues with
+    fn emit_block(
+        &mut self,
+        block: usize,
+        ctx: &Context,
+        out: &mut Vec<Wrapped<AstStatement>>,
+    ) -> Option<usize> {
+        if ctx.loop_header != Some(block) {
+            if self.loops.contains_key(&block) {
+                return self.emit_loop(block, ctx, out);
+            }
+            out.push(w(AstStatement::Label(self.label(block))));
+        }
+        self.emitted[block] = true;
+        out.append(&mut self.bodies[block]);
+        match self.terminators[block].clone() {
+            Terminator::None => None,
+            Terminator::Jump(to) => Some(to),
+            Terminator::Branch {
+                condition,
+                taken,
+                not_taken,
+            } => self.emit_branch(block, condition, taken, not_taken, ctx, out),
+            Terminator::Switch {
+                discriminant,
+                cases,
+                default,
+            } => self.emit_switch(block, discriminant, cases, default, ctx, out),
+        }
+    }
+
+    /// Block the statements of a branch meet at, if it can be emitted after them
+    fn branch_follow(&self, block: usize, ctx: &Context) -> Option<usize> {
+        self.ipdom[block].filter(|&follow| {
+            [ctx.follow, ctx.loop_header, ctx.loop_follow].contains(&Some(follow))
+                || !(self.emitted[follow]
+                    || ctx.outer.contains(&follow)
+                    || !self.in_loop(ctx, follow))
+        })
+    }
+
+    fn emit_branch(
+        &mut self,
+        block: usize,
+        condition: Wrapped<AstExpression>,
+        taken: usize,
+        not_taken: usize,
+        ctx: &Context,
+        out: &mut Vec<Wrapped<AstStatement>>,
+    ) -> Option<usize> {
+        if taken == not_taken {
+            return Some(taken);
+        }
+        let follow = self.branch_follow(block, ctx);
+        let mut inner = ctx.nested();
+        inner.follow = follow;
+
+        let mut not_taken_branch = Vec::new();
+        self.emit_sequence(not_taken, &inner, &mut not_taken_branch);
+        let mut taken_branch = Vec::new();
+        self.emit_sequence(taken, &inner, &mut taken_branch);
+        out.extend(make_if(condition, taken_branch, not_taken_branch));
+        follow
+    }
+
+    fn emit_switch(
+        &mut self,
+        block: usize,
+        discriminant: Wrapped<AstExpression>,
+        cases: Vec<(u64, usize)>,
+        default: Option<usize>,
+        ctx: &Context,
+        out: &mut Vec<Wrapped<AstStatement>>,
+    ) -> Option<usize> {
+        let follow = self.branch_follow(block, ctx);
+        let mut inner = ctx.nested();
+        inner.follow = follow;
+        // `break` leaves the switch, so the loop can only be left by `goto`
+        inner.outer.extend(ctx.loop_follow);
+        inner.loop_follow = None;
+
+        // entries of the values the switch doesn't handle point to the default
+        let cases: Vec<_> = cases
+            .into_iter()
+            .filter(|(_, target)| Some(*target) != default)
+            .collect();
+        let mut targets: Vec<usize> = Vec::new();
+        for (_, target) in cases.iter() {
+            if !targets.contains(target) {
+                targets.push(*target);
+            }
+        }
+        let mut switch_cases = Vec::with_capacity(cases.len());
+        for target in targets {
+            let mut values: Vec<u64> = cases
+                .iter()
+                .filter(|(_, x)| *x == target)
+                .map(|(value, _)| *value)
+                .collect();
+            values.sort_unstable();
+            let mut body = Vec::new();
+            self.emit_sequence(target, &inner, &mut body);
+            if falls_through(&body) {
+                body.push(w(AstStatement::Break));
+            }
+            // cases sharing a destination fall through into the last of them
+            let (last, values) = values.split_last().unwrap();
+            for value in values {
+                switch_cases.push((AstLiteral::UInt(*value), Vec::new()));
+            }
+            switch_cases.push((AstLiteral::UInt(*last), body));
+        }
+        let default = default.filter(|x| Some(*x) != follow).map(|target| {
+            let mut body = Vec::new();
+            self.emit_sequence(target, &inner, &mut body);
+            if falls_through(&body) {
+                body.push(w(AstStatement::Break));
+            }
+            body
+        });
+        out.push(at(
+            discriminant.origin,
+            AstStatement::Switch(discriminant, switch_cases, default),
+        ));
+        follow
+    }
+
+    /// ### Returns
+    /// - `Option<usize>` - block the flow continues with after the loop
+    fn emit_loop(
+        &mut self,
+        header: usize,
+        ctx: &Context,
+        out: &mut Vec<Wrapped<AstStatement>>,
+    ) -> Option<usize> {
+        let natural = self.loops[&header].clone();
+        out.push(w(AstStatement::Label(self.label(header))));
+        let mut inner = ctx.nested();
+        inner.outer.extend(ctx.loop_follow);
+        inner.loop_header = Some(header);
+        let outside = |block: &usize| !natural.nodes.contains(block);
+
+        // do { ... } while (cond) of a single block
+        if natural.nodes.len() == 1 {
+            self.emitted[header] = true;
+            let body = std::mem::take(&mut self.bodies[header]);
+            return match self.terminators[header].clone() {
+                Terminator::Branch {
+                    condition,
+                    taken,
+                    not_taken,
+                } => {
+                    let (condition, exit) = if taken == header {
+                        (condition, not_taken)
+                    } else {
+                        (negate(condition), taken)
+                    };
+                    out.push(at(condition.origin, AstStatement::DoWhile(condition, body)));
+                    Some(exit)
+                }
+                _ => {
+                    out.push(w(AstStatement::While(true_expression(), body)));
+                    None
+                }
+            };
+        }
+
+        // while (cond) { ... }
+        if let Terminator::Branch {
+            condition,
+            taken,
+            not_taken,
+        } = self.terminators[header].clone()
+            && outside(&taken) != outside(&not_taken)
+        {
+            let (stay, exit, condition) = if outside(&not_taken) {
+                (taken, not_taken, condition)
+            } else {
+                (not_taken, taken, negate(condition))
+            };
+            inner.loop_follow = Some(exit);
+            self.emitted[header] = true;
+            let header_body = std::mem::take(&mut self.bodies[header]);
+            let mut body = Vec::new();
+            self.emit_sequence(stay, &inner, &mut body);
+            strip_trailing_continue(&mut body);
+            if header_body
+                .iter()
+                .all(|x| matches!(x.item, AstStatement::Empty))
+            {
+                out.push(at(condition.origin, AstStatement::While(condition, body)));
+            } else {
+                let mut stmts = header_body;
+                let condition = negate(condition);
+                stmts.push(at(
+                    condition.origin,
+                    AstStatement::If(condition, vec![w(AstStatement::Break)], None),
+                ));
+                stmts.extend(body);
+                out.push(w(AstStatement::While(true_expression(), stmts)));
+            }
+            return Some(exit);
+        }
+
+        // do { ... } while (cond)
+        if let [latch] = natural.latches[..]
+            && latch != header
+            && let Terminator::Branch {
+                condition,
+                taken,
+                not_taken,
+            } = self.terminators[latch].clone()
+            && ((taken == header && outside(&not_taken))
+                || (not_taken == header && outside(&taken)))
+        {
+            let (condition, exit) = if taken == header {
+                (condition, not_taken)
+            } else {
+                (negate(condition), taken)
+            };
+            inner.loop_follow = Some(exit);
+            inner.follow = Some(latch);
+            let mut body = Vec::new();
+            self.emit_loop_body(header, &inner, &mut body);
+            body.push(w(AstStatement::Label(self.label(latch))));
+            self.emitted[latch] = true;
+            body.append(&mut self.bodies[latch]);
+            out.push(at(condition.origin, AstStatement::DoWhile(condition, body)));
+            return Some(exit);
+        }
+
+        // while (true) { ... }
+        let exit = self.ipdom[header].filter(outside).or_else(|| {
+            natural
+                .nodes
+                .iter()
+                .flat_map(|x| self.cfg.get_blocks()[*x].get_successors())
+                .map(|x| x.to)
+                .find(outside)
+        });
+        inner.loop_follow = exit;
+        let mut body = Vec::new();
+        self.emit_loop_body(header, &inner, &mut body);
+        strip_trailing_continue(&mut body);
+        out.push(w(AstStatement::While(true_expression(), body)));
+        exit
+    }
+}
+
+/// Wraps a statement generated from the jump its condition was taken from.
+fn at(origin: Option<AstAddressRange>, stmt: AstStatement) -> Wrapped<AstStatement> {
+    Wrapped {
+        item: stmt,
+        comment: None,
+        origin,
+    }
+}
+
+fn true_expression() -> Wrapped<AstExpression> {
+    w(AstExpression::Literal(AstLiteral::Bool(true)))
+}
+
+/// Whether the control reaches the end of the statements
+fn falls_through(stmts: &[Wrapped<AstStatement>]) -> bool {
+    !matches!(
+        stmts.last().map(|x| &x.item),
+        Some(
+            AstStatement::Return(_)
+                | AstStatement::Goto(_)
+                | AstStatement::Break
+                | AstStatement::Continue
+        )
+    )
+}
+
+fn strip_trailing_continue(body: &mut Vec<Wrapped<AstStatement>>) {
+    if matches!(body.last().map(|x| &x.item), Some(AstStatement::Continue)) {
+        body.pop();
+    }
+}
+
+fn negate(condition: Wrapped<AstExpression>) -> Wrapped<AstExpression> {
+    let Wrapped {
+        item,
+        comment,
+        origin,
+    } = condition;
+    let item = match item {
+        AstExpression::UnaryOp(AstUnaryOperator::Not, inner) => return *inner,
+        AstExpression::Literal(AstLiteral::Bool(value)) => {
+            AstExpression::Literal(AstLiteral::Bool(!value))
+        }
+        AstExpression::BinaryOp(operator, lhs, rhs) => {
+            let operator = match operator {
+                AstBinaryOperator::Equal => AstBinaryOperator::NotEqual,
+                AstBinaryOperator::NotEqual => AstBinaryOperator::Equal,
+                AstBinaryOperator::Less => AstBinaryOperator::GreaterEqual,
+                AstBinaryOperator::LessEqual => AstBinaryOperator::Greater,
+                AstBinaryOperator::Greater => AstBinaryOperator::LessEqual,
+                AstBinaryOperator::GreaterEqual => AstBinaryOperator::Less,
+                operator => {
+                    let inner = Wrapped {
+                        item: AstExpression::BinaryOp(operator, lhs, rhs),
+                        comment,
+                        origin,
+                    };
+                    return Wrapped {
+                        item: AstExpression::UnaryOp(AstUnaryOperator::Not, Box::new(inner)),
+                        comment: None,
+                        origin,
+                    };
+                }
+            };
+            AstExpression::BinaryOp(operator, lhs, rhs)
+        }
+        item => AstExpression::UnaryOp(
+            AstUnaryOperator::Not,
+            Box::new(Wrapped {
+                item,
+                comment: None,
+                origin,
+            }),
+        ),
+    };
+    Wrapped {
+        item,
+        comment,
+        origin,
+    }
+}
+
+fn make_if(
+    condition: Wrapped<AstExpression>,
+    taken: Vec<Wrapped<AstStatement>>,
+    not_taken: Vec<Wrapped<AstStatement>>,
+) -> Option<Wrapped<AstStatement>> {
+    let origin = condition.origin;
+    let stmt = match (taken.is_empty(), not_taken.is_empty()) {
+        (true, true) => return None,
+        (true, false) => AstStatement::If(negate(condition), not_taken, None),
+        (false, true) => AstStatement::If(condition, taken, None),
+        (false, false) => AstStatement::If(negate(condition), not_taken, Some(taken)),
+    };
+    Some(at(origin, stmt))
+}
+
+/// Remove labels nobody jumps to and clean up what is left behind
+fn refine(stmts: &mut Vec<Wrapped<AstStatement>>, used_labels: &HashSet<String>) {
+    stmts.retain(|x| match &x.item {
+        AstStatement::Label(label) => used_labels.contains(label),
+        _ => true,
+    });
+    for stmt in stmts.iter_mut() {
+        match &mut stmt.item {
+            AstStatement::If(_, branch_true, branch_false) => {
+                refine(branch_true, used_labels);
+                if let Some(branch_false) = branch_false {
+                    refine(branch_false, used_labels);
+                }
+            }
+            AstStatement::While(_, body)
+            | AstStatement::DoWhile(_, body)
+            | AstStatement::For(_, _, _, body)
+            | AstStatement::Block(body) => refine(body, used_labels),
+            AstStatement::Switch(_, cases, default) => {
+                for (_, body) in cases.iter_mut() {
+                    refine(body, used_labels);
+                }
+                if let Some(default) = default {
+                    refine(default, used_labels);
+                }
+            }
+            AstStatement::Try(body, handlers) => {
+                refine(body, used_labels);
+                for (_, body) in handlers.iter_mut() {
+                    refine(body, used_labels);
+                }
+            }
+            _ => {}
+        }
+        // shapes left behind by removed labels
+        if let AstStatement::If(condition, branch_true, branch_false) = &mut stmt.item {
+            if branch_false.as_ref().is_some_and(|x| x.is_empty()) {
+                *branch_false = None;
+            }
+            if branch_true.is_empty()
+                && let Some(branch_false) = branch_false.take()
+            {
+                *condition = negate(std::mem::replace(condition, w(AstExpression::Unknown)));
+                *branch_true = branch_false;
+            }
+        }
+    }
+    stmts.retain(|x| !matches!(&x.item, AstStatement::If(_, t, None) if t.is_empty()));
+    merge_for_loops(stmts);
+}
+
+/// `init; while (cond) { ...; update; }` into `for (init; cond; update) { ... }`
+fn merge_for_loops(stmts: &mut Vec<Wrapped<AstStatement>>) {
+    let mut i = 1;
+    while i < stmts.len() {
+        if is_for_loop(&stmts[i - 1], &stmts[i]) {
+            let init = stmts.remove(i - 1);
+            let AstStatement::While(condition, mut body) =
+                std::mem::replace(&mut stmts[i - 1].item, AstStatement::Empty)
+            else {
+                unreachable!()
+            };
+            let update = body.pop().unwrap();
+            stmts[i - 1].origin = AstAddressRange::merge(init.origin, stmts[i - 1].origin);
+            stmts[i - 1].item =
+                AstStatement::For(Box::new(init), condition, Box::new(update), body);
+            continue;
+        }
+        i += 1;
+    }
+}
+
+fn is_for_loop(init: &Wrapped<AstStatement>, stmt: &Wrapped<AstStatement>) -> bool {
+    let AstStatement::Assignment(target, _) = &init.item else {
+        return false;
+    };
+    let AstExpression::Variable(_, var_id) = &target.item else {
+        return false;
+    };
+    let AstStatement::While(condition, body) = &stmt.item else {
+        return false;
+    };
+    let Some(AstStatement::Assignment(update_target, update)) = body.last().map(|x| &x.item) else {
+        return false;
+    };
+    matches!(&update_target.item, AstExpression::Variable(_, id) if id == var_id)
+        && update.get_related_variables().contains(var_id)
+        && condition.get_related_variables().contains(var_id)
+        && !has_continue(body)
+}
+
+/// Whether `continue` of the loop the statements belong to is used
+fn has_continue(stmts: &[Wrapped<AstStatement>]) -> bool {
+    stmts.iter().any(|x| match &x.item {
+        AstStatement::Continue => true,
+        AstStatement::If(_, branch_true, branch_false) => {
+            has_continue(branch_true) || branch_false.as_deref().is_some_and(has_continue)
+        }
+        AstStatement::Block(body) => has_continue(body),
+        AstStatement::Try(body, handlers) => {
+            has_continue(body) || handlers.iter().any(|(_, body)| has_continue(body))
+        }
+        AstStatement::Switch(_, cases, default) => {
+            cases.iter().any(|(_, body)| has_continue(body))
+                || default.as_deref().is_some_and(has_continue)
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::analyze::control_flow::ControlFlowSwitch;
+
+    /// Statements of a block: a comment naming it, then how it leaves
+    fn block(name: &str, exit: Option<AstStatement>) -> Vec<Wrapped<AstStatement>> {
+        let mut stmts = vec![w(AstStatement::Comment(name.to_string()))];
+        stmts.extend(exit.map(w));
+        stmts
+    }
+
+    fn branch() -> Option<AstStatement> {
+        let condition = w(AstExpression::Literal(AstLiteral::Bool(true)));
+        Some(AstStatement::If(condition, Vec::new(), None))
+    }
+
+    fn jump() -> Option<AstStatement> {
+        Some(AstStatement::Goto(AstJumpTarget::Unknown(String::new())))
+    }
+
+    fn run(cfg: &ControlFlowGraph, blocks: Vec<Vec<Wrapped<AstStatement>>>) -> String {
+        let statements = blocks
+            .into_iter()
+            .enumerate()
+            .map(|(i, stmts)| stmts.into_iter().map(|x| (i as u32, x)).collect())
+            .collect();
+        let result = structure(cfg, statements, |_| {
+            Some(w(AstExpression::Literal(AstLiteral::UInt(0))))
+        });
+        render(&result)
+    }
+
+    /// Compact rendering of the shape of the statements
+    fn render(stmts: &[Wrapped<AstStatement>]) -> String {
+        let body = |stmts: &[Wrapped<AstStatement>]| format!("{{{}}}", render(stmts));
+        // test branches are taken on `c`
+        let condition = |condition: &Wrapped<AstExpression>| match &condition.item {
+            AstExpression::Literal(AstLiteral::Bool(true)) => "c".to_string(),
+            AstExpression::Literal(AstLiteral::Bool(false)) => "!c".to_string(),
+            x => format!("{x:?}"),
+        };
+        let rendered: Vec<String> = stmts
+            .iter()
+            .map(|stmt| match &stmt.item {
+                AstStatement::Comment(name) => name.clone(),
+                AstStatement::If(c, taken, None) => format!("if {} {}", condition(c), body(taken)),
+                AstStatement::If(c, taken, Some(not_taken)) => {
+                    format!(
+                        "if {} {} else {}",
+                        condition(c),
+                        body(taken),
+                        body(not_taken)
+                    )
+                }
+                AstStatement::While(c, stmts)
+                    if matches!(c.item, AstExpression::Literal(AstLiteral::Bool(true))) =>
+                {
+                    format!("while {}", body(stmts))
+                }
+                AstStatement::While(c, stmts) => format!("while {} {}", condition(c), body(stmts)),
+                AstStatement::DoWhile(c, stmts) => {
+                    format!("do {} while {}", body(stmts), condition(c))
+                }
+                AstStatement::Switch(_, cases, default) => {
+                    let mut rendered: Vec<String> = cases
+                        .iter()
+                        .map(|(value, stmts)| match value {
+                            AstLiteral::UInt(value) => format!("{value}: {}", body(stmts)),
+                            x => format!("{x:?}: {}", body(stmts)),
+                        })
+                        .collect();
+                    if let Some(default) = default {
+                        rendered.push(format!("default: {}", body(default)));
+                    }
+                    format!("switch {{{}}}", rendered.join(" "))
+                }
+                AstStatement::Label(label) => format!("{label}:"),
+                AstStatement::Goto(AstJumpTarget::Unknown(label)) => format!("goto {label}"),
+                AstStatement::Break => "break".to_string(),
+                AstStatement::Continue => "continue".to_string(),
+                AstStatement::Return(_) => "return".to_string(),
+                x => format!("{x:?}"),
+            })
+            .collect();
+        rendered.join(" ")
+    }
+
+    #[test]
+    fn test_switch_default() {
+        use ControlFlowEdgeKind::*;
+        // 0: if (index > 2) goto 2; 1: switch (index) { 0 => 3, 1 => 4, 2 => 2 }
+        let mut cfg = ControlFlowGraph::from_edges(
+            6,
+            0,
+            &[
+                (0, 2, Taken),
+                (0, 1, NotTaken),
+                (1, 3, Case),
+                (1, 4, Case),
+                (1, 2, Case),
+                (2, 5, Jump),
+                (3, 5, Jump),
+                (4, 5, Jump),
+            ],
+        );
+        cfg.set_switch(
+            1,
+            ControlFlowSwitch {
+                index: IrData::Constant(0).into(),
+                cases: vec![(0, 3), (1, 4), (2, 2)],
+                default: Some(2),
+            },
+        );
+        let blocks = vec![
+            block("b0", branch()),
+            block("b1", jump()),
+            block("b2", jump()),
+            block("b3", jump()),
+            block("b4", jump()),
+            block("b5", Some(AstStatement::Return(None))),
+        ];
+        assert_eq!(
+            run(&cfg, blocks),
+            "b0 b1 switch {0: {b3 break} 1: {b4 break} default: {b2 break}} b5 return"
+        );
+    }
+
+    fn ret() -> Option<AstStatement> {
+        Some(AstStatement::Return(None))
+    }
+
+    #[test]
+    fn test_if_else() {
+        use ControlFlowEdgeKind::*;
+        // 0: if (..) goto 1 else goto 2; 1, 2: goto 3
+        let cfg = ControlFlowGraph::from_edges(
+            4,
+            0,
+            &[
+                (0, 1, Taken),
+                (0, 2, NotTaken),
+                (1, 3, Jump),
+                (2, 3, Fallthrough),
+            ],
+        );
+        let blocks = vec![
+            block("b0", branch()),
+            block("b1", jump()),
+            block("b2", None),
+            block("b3", ret()),
+        ];
+        assert_eq!(run(&cfg, blocks), "b0 if !c {b2} else {b1} b3 return");
+    }
+
+    #[test]
+    fn test_while() {
+        use ControlFlowEdgeKind::*;
+        // 1: if (..) goto 3; 2: goto 1
+        let cfg = ControlFlowGraph::from_edges(
+            4,
+            0,
+            &[
+                (0, 1, Fallthrough),
+                (1, 3, Taken),
+                (1, 2, NotTaken),
+                (2, 1, Jump),
+            ],
+        );
+        let blocks = vec![
+            block("b0", None),
+            block("b1", branch()),
+            block("b2", jump()),
+            block("b3", ret()),
+        ];
+        assert_eq!(run(&cfg, blocks), "b0 while {b1 if c {break} b2} b3 return");
+    }
+
+    #[test]
+    fn test_do_while() {
+        use ControlFlowEdgeKind::*;
+        // 1: if (..) goto 1
+        let cfg = ControlFlowGraph::from_edges(
+            3,
+            0,
+            &[(0, 1, Fallthrough), (1, 1, Taken), (1, 2, NotTaken)],
+        );
+        let blocks = vec![block("b0", None), block("b1", branch()), block("b2", ret())];
+        assert_eq!(run(&cfg, blocks), "b0 do {b1} while c b2 return");
+    }
+
+    #[test]
+    fn test_break_continue() {
+        use ControlFlowEdgeKind::*;
+        // 1: if (..) goto 5; 2: if (..) goto 5; 3: if (..) goto 1; 4: goto 1
+        let cfg = ControlFlowGraph::from_edges(
+            6,
+            0,
+            &[
+                (0, 1, Fallthrough),
+                (1, 5, Taken),
+                (1, 2, NotTaken),
+                (2, 5, Taken),
+                (2, 3, NotTaken),
+                (3, 1, Taken),
+                (3, 4, NotTaken),
+                (4, 1, Jump),
+            ],
+        );
+        let blocks = vec![
+            block("b0", None),
+            block("b1", branch()),
+            block("b2", branch()),
+            block("b3", branch()),
+            block("b4", jump()),
+            block("b5", ret()),
+        ];
+        assert_eq!(
+            run(&cfg, blocks),
+            "b0 while {b1 if c {break} b2 if !c {b3 if !c {b4} continue} break} b5 return"
+        );
+    }
+
+    #[test]
+    fn test_irreducible() {
+        use ControlFlowEdgeKind::*;
+        // a loop of 1 and 2 entered at both blocks falls back to a goto
+        let cfg = ControlFlowGraph::from_edges(
+            4,
+            0,
+            &[
+                (0, 1, Taken),
+                (0, 2, NotTaken),
+                (1, 2, Jump),
+                (2, 1, Taken),
+                (2, 3, NotTaken),
+            ],
+        );
+        let blocks = vec![
+            block("b0", branch()),
+            block("b1", jump()),
+            block("b2", branch()),
+            block("b3", ret()),
+        ];
+        assert_eq!(
+            run(&cfg, blocks),
+            "b0 if c {label_1: b1} b2 if c {goto label_1} b3 return"
+        );
+    }
+}
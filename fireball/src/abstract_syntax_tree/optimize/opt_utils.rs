@@ -1,6 +1,9 @@
 //! Shared utility functions for optimization passes (structural equality, purity, eval).
 
-use crate::abstract_syntax_tree::{AstExpression, AstVariableId};
+use crate::abstract_syntax_tree::{
+    AstAddressRange, AstBuiltinFunctionArgument, AstCall, AstExceptionHandler, AstExpression,
+    AstStatement, AstVariable, AstVariableId, Wrapped,
+};
 
 /// Recursive structural comparison of two expressions.
 /// Variables are compared by their `AstVariableId`, not by the variable map Arc.
@@ -112,8 +115,6 @@ pub fn has_write_side_effects(expr: &AstExpression) -> bool {
 
 /// Collect all variable IDs referenced (read) in an expression.
 pub fn collect_expr_variables(expr: &AstExpression, out: &mut hashbrown::HashSet<AstVariableId>) {
-    use crate::abstract_syntax_tree::{AstBuiltinFunctionArgument, AstCall};
-
     match expr {
         AstExpression::Variable(_, var_id) => {
             out.insert(*var_id);
@@ -386,3 +387,187 @@ fn eval_binary_str(operator: &AstBinaryOperator, a: &str, b: &str) -> Option<Ast
         _ => None,
     }
 }
+
+/// Calls `f` with the variable of every declaration in the statements, including nested ones.
+pub fn for_each_declaration(
+    stmts: &mut [Wrapped<AstStatement>],
+    f: &mut impl FnMut(&mut AstVariable),
+) {
+    for stmt in stmts.iter_mut() {
+        match &mut stmt.item {
+            AstStatement::Declaration(variable, _) => f(variable),
+            AstStatement::If(_, branch_true, branch_false) => {
+                for_each_declaration(branch_true, f);
+                if let Some(branch_false) = branch_false {
+                    for_each_declaration(branch_false, f);
+                }
+            }
+            AstStatement::While(_, body)
+            | AstStatement::DoWhile(_, body)
+            | AstStatement::Block(body) => for_each_declaration(body, f),
+            AstStatement::For(init, _, update, body) => {
+                for_each_declaration(std::slice::from_mut(&mut **init), f);
+                for_each_declaration(std::slice::from_mut(&mut **update), f);
+                for_each_declaration(body, f);
+            }
+            AstStatement::Switch(_, cases, default) => {
+                for (_, body) in cases.iter_mut() {
+                    for_each_declaration(body, f);
+                }
+                if let Some(default) = default {
+                    for_each_declaration(default, f);
+                }
+            }
+            AstStatement::Try(body, handlers) => {
+                for_each_declaration(body, f);
+                for (_, body) in handlers.iter_mut() {
+                    for_each_declaration(body, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Statement lists nested directly in the statement
+pub fn nested_bodies(stmt: &mut AstStatement) -> Vec<&mut Vec<Wrapped<AstStatement>>> {
+    match stmt {
+        AstStatement::If(_, branch_true, branch_false) => {
+            let mut result = vec![branch_true];
+            result.extend(branch_false.as_mut());
+            result
+        }
+        AstStatement::While(_, body)
+        | AstStatement::DoWhile(_, body)
+        | AstStatement::For(_, _, _, body)
+        | AstStatement::Block(body) => vec![body],
+        AstStatement::Switch(_, cases, default) => {
+            let mut result: Vec<_> = cases.iter_mut().map(|(_, body)| body).collect();
+            result.extend(default.as_mut());
+            result
+        }
+        AstStatement::Try(body, handlers) => {
+            let mut result = vec![body];
+            result.extend(handlers.iter_mut().map(|(_, body)| body));
+            result
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Gives the origin to the statement and everything nested in it that has none.
+///
+/// ### Note
+/// - Used where statements are generated from, or replace, other statements, so every
+///   expression can be traced back to its instructions.
+pub fn inherit_origin(stmt: &mut Wrapped<AstStatement>, origin: Option<AstAddressRange>) {
+    if stmt.origin.is_none() {
+        stmt.origin = origin;
+    }
+    let origin = stmt.origin;
+    let inherit_all = |stmts: &mut [Wrapped<AstStatement>]| {
+        for stmt in stmts.iter_mut() {
+            inherit_origin(stmt, origin);
+        }
+    };
+    match &mut stmt.item {
+        AstStatement::Declaration(_, Some(value)) => inherit_expression_origin(value, origin),
+        AstStatement::Assignment(lhs, rhs) => {
+            inherit_expression_origin(lhs, origin);
+            inherit_expression_origin(rhs, origin);
+        }
+        AstStatement::If(condition, branch_true, branch_false) => {
+            inherit_expression_origin(condition, origin);
+            inherit_all(branch_true);
+            if let Some(branch_false) = branch_false {
+                inherit_all(branch_false);
+            }
+        }
+        AstStatement::While(condition, body) | AstStatement::DoWhile(condition, body) => {
+            inherit_expression_origin(condition, origin);
+            inherit_all(body);
+        }
+        AstStatement::For(init, condition, update, body) => {
+            inherit_origin(init, origin);
+            inherit_expression_origin(condition, origin);
+            inherit_origin(update, origin);
+            inherit_all(body);
+        }
+        AstStatement::Return(Some(value)) => inherit_expression_origin(value, origin),
+        AstStatement::Call(call) => inherit_call_origin(call, origin),
+        AstStatement::Block(body) => inherit_all(body),
+        AstStatement::Switch(value, cases, default) => {
+            inherit_expression_origin(value, origin);
+            for (_, body) in cases.iter_mut() {
+                inherit_all(body);
+            }
+            if let Some(default) = default {
+                inherit_all(default);
+            }
+        }
+        AstStatement::Try(body, handlers) => {
+            inherit_all(body);
+            for (handler, body) in handlers.iter_mut() {
+                if let AstExceptionHandler::Except(filter) = handler {
+                    inherit_expression_origin(filter, origin);
+                }
+                inherit_all(body);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Gives the origin to the expression and its operands that have none.
+pub fn inherit_expression_origin(
+    expr: &mut Wrapped<AstExpression>,
+    origin: Option<AstAddressRange>,
+) {
+    if expr.origin.is_none() {
+        expr.origin = origin;
+    }
+    let origin = expr.origin;
+    match &mut expr.item {
+        AstExpression::UnaryOp(_, arg)
+        | AstExpression::Cast(_, arg)
+        | AstExpression::Deref(arg)
+        | AstExpression::AddressOf(arg)
+        | AstExpression::MemberAccess(arg, _) => inherit_expression_origin(arg, origin),
+        AstExpression::BinaryOp(_, lhs, rhs) | AstExpression::ArrayAccess(lhs, rhs) => {
+            inherit_expression_origin(lhs, origin);
+            inherit_expression_origin(rhs, origin);
+        }
+        AstExpression::Ternary(condition, true_expr, false_expr) => {
+            inherit_expression_origin(condition, origin);
+            inherit_expression_origin(true_expr, origin);
+            inherit_expression_origin(false_expr, origin);
+        }
+        AstExpression::Call(call) => inherit_call_origin(call, origin),
+        _ => {}
+    }
+}
+
+fn inherit_call_origin(call: &mut AstCall, origin: Option<AstAddressRange>) {
+    let args: Vec<&mut Wrapped<AstExpression>> = match call {
+        AstCall::Variable { args, .. }
+        | AstCall::Function { args, .. }
+        | AstCall::Unknown(_, args) => args.iter_mut().collect(),
+        AstCall::Builtin(_, arg) => match arg.as_mut() {
+            AstBuiltinFunctionArgument::None => Vec::new(),
+            AstBuiltinFunctionArgument::Print(args) => args.iter_mut().collect(),
+            AstBuiltinFunctionArgument::ByteSizeOf(arg)
+            | AstBuiltinFunctionArgument::BitSizeOf(arg)
+            | AstBuiltinFunctionArgument::OperandExists(arg)
+            | AstBuiltinFunctionArgument::SignedMax(arg)
+            | AstBuiltinFunctionArgument::SignedMin(arg)
+            | AstBuiltinFunctionArgument::UnsignedMax(arg)
+            | AstBuiltinFunctionArgument::UnsignedMin(arg)
+            | AstBuiltinFunctionArgument::BitOnes(arg)
+            | AstBuiltinFunctionArgument::BitZeros(arg) => vec![arg],
+            AstBuiltinFunctionArgument::Sized(a, b) => vec![a, b],
+        },
+    };
+    for arg in args {
+        inherit_expression_origin(arg, origin);
+    }
+}
@@ -2,13 +2,16 @@
 
 use crate::{
     abstract_syntax_tree::{
-        Ast, AstFunctionId, AstFunctionVersion, AstOptimizationKind, AstParameter,
-        AstVariableAccessType, AstVariableId, GetRelatedVariables,
+        ArcAstVariableMap, Ast, AstCall, AstCallingConvention, AstExpression, AstFunctionId,
+        AstFunctionVersion, AstOptimizationKind, AstParameter, AstParameterLocation, AstStatement,
+        AstVariableAccessType, AstVariableId, GetRelatedVariables, Wrapped,
     },
     ir::{
-        Register,
+        Architecture, Register, VirtualMachine,
+        arm::ArmRange,
         data::{IrData, IrDataOperation},
         operator::IrUnaryOperator,
+        x86_64::X64Range,
     },
     prelude::*,
 };
@@ -21,6 +24,8 @@ pub(super) fn analyze_parameters(
 ) -> Result<(), DecompileError> {
     let variables;
     let body;
+    let calling_convention;
+    let callee_cleanup;
     {
         let mut functions = ast.functions.write().unwrap();
         let function = functions
@@ -30,6 +35,8 @@ pub(super) fn analyze_parameters(
 
         body = std::mem::take(&mut function.body);
         variables = function.variables.clone();
+        calling_convention = function.calling_convention;
+        callee_cleanup = function.origin_ir.get_callee_cleanup();
     }
     let mut written_registers: HashSet<Register> = HashSet::new();
     let mut read_before_write_registers: HashSet<Register> = HashSet::new();
@@ -135,12 +142,48 @@ pub(super) fn analyze_parameters(
         }
     }
 
-    let parameters = used_locations_to_parameters(
-        read_before_write_registers,
-        read_before_write_offset_from_base_pointers,
+    let calling_convention = calling_convention.unwrap_or_else(|| {
+        detecting_calling_convention(
+            ast.calling_convention,
+            &read_before_write_registers,
+            &read_before_write_offset_from_base_pointers,
+            callee_cleanup,
+        )
+    });
+    let parameter_count = ast
+        .annotations
+        .function(function_id.address)
+        .and_then(|x| x.parameter_count);
+    let mut parameters = used_locations_to_parameters(
+        calling_convention,
+        read_before_write_registers.clone(),
+        read_before_write_offset_from_base_pointers.clone(),
         &reg_to_var,
         &offset_to_var,
     );
+    // an annotated count also brings in the parameters the body never reads
+    if let Some(count) = parameter_count {
+        if parameters.len() < count {
+            let (registers, offsets) = argument_locations(calling_convention, count);
+            for register in registers {
+                let read = read_before_write_registers.iter().any(|x| {
+                    x.architecture() == register.architecture() && is_in_family(x, &register)
+                });
+                if !read {
+                    read_before_write_registers.insert(register);
+                }
+            }
+            read_before_write_offset_from_base_pointers.extend(offsets);
+            parameters = used_locations_to_parameters(
+                calling_convention,
+                read_before_write_registers,
+                read_before_write_offset_from_base_pointers,
+                &reg_to_var,
+                &offset_to_var,
+            );
+        }
+        parameters.truncate(count);
+    }
 
     {
         let mut functions = ast.functions.write().unwrap();
@@ -157,14 +200,157 @@ pub(super) fn analyze_parameters(
     Ok(())
 }
 
-enum CallingConvention {
-    X86Cdecl,
-    X86Stdcall,
-    X86Fastcall,
-    X86Thiscall,
-    X86Vectorcall,
-    X64,
-    Unknown,
+/// Passes arguments at the calls to functions whose parameters are known.
+///
+/// ### Note
+/// - Runs bottom-up on the call graph, so callees are analyzed before their callers.
+/// - A parameter register is given the variable last written to it before the call.
+/// - Calls to functions with stack parameters, or with a register not written before the call,
+///   are left without arguments.
+pub(super) fn apply_callee_signatures(
+    ast: &mut Ast,
+    function_id: AstFunctionId,
+    function_version: AstFunctionVersion,
+) -> Result<(), DecompileError> {
+    let mut functions = ast.functions.write().unwrap();
+    let mut signatures: HashMap<AstFunctionId, Vec<Register>> = HashMap::new();
+    for (id, version) in ast.function_versions.iter() {
+        let Some(function) = functions.get(id).and_then(|x| x.get(version)) else {
+            continue;
+        };
+        let registers: Option<Vec<Register>> = function
+            .parameters
+            .iter()
+            .map(|parameter| match &parameter.location {
+                AstParameterLocation::Register(location) => match location.as_ref() {
+                    IrData::Register(register) => Some(*register),
+                    _ => None,
+                },
+                AstParameterLocation::Stack(_) => None,
+            })
+            .collect();
+        if let Some(registers) = registers.filter(|x| !x.is_empty()) {
+            signatures.insert(*id, registers);
+        }
+    }
+    let function = functions
+        .get_mut(&function_id)
+        .and_then(|x| x.get_mut(&function_version))
+        .unwrap();
+    let variables = function.variables.clone();
+    pass_arguments(&mut function.body, &signatures, &variables, &mut Vec::new());
+    Ok(())
+}
+
+/// ### Arguments
+/// - `written: &mut Vec<(Register, AstVariableId)>` - variables last written to each register
+fn pass_arguments(
+    stmts: &mut [Wrapped<AstStatement>],
+    signatures: &HashMap<AstFunctionId, Vec<Register>>,
+    variables: &ArcAstVariableMap,
+    written: &mut Vec<(Register, AstVariableId)>,
+) {
+    let write = |written: &mut Vec<(Register, AstVariableId)>, var_id: AstVariableId| {
+        let Some(register) = variable_register(variables, var_id) else {
+            return;
+        };
+        written.retain(|(x, _)| !is_in_family(x, &register) && !is_in_family(&register, x));
+        written.push((register, var_id));
+    };
+    for stmt in stmts.iter_mut() {
+        let origin = stmt.origin;
+        match &mut stmt.item {
+            AstStatement::Declaration(variable, Some(_)) => write(written, variable.id),
+            AstStatement::Assignment(lhs, _) => {
+                if let AstExpression::Variable(_, var_id) = &lhs.item {
+                    write(written, *var_id);
+                }
+            }
+            AstStatement::Call(call) => {
+                if let AstCall::Function { target, args } = call
+                    && args.is_empty()
+                    && let Some(registers) = signatures.get(target)
+                {
+                    let found: Option<Vec<AstVariableId>> = registers
+                        .iter()
+                        .map(|register| {
+                            written
+                                .iter()
+                                .rev()
+                                .find(|(x, _)| is_in_family(x, register))
+                                .map(|(_, var_id)| *var_id)
+                        })
+                        .collect();
+                    if let Some(found) = found {
+                        *args = found
+                            .into_iter()
+                            .map(|var_id| Wrapped {
+                                item: AstExpression::Variable(variables.clone(), var_id),
+                                comment: None,
+                                origin,
+                            })
+                            .collect();
+                    }
+                }
+                // the callee may overwrite every register
+                written.clear();
+            }
+            AstStatement::If(_, branch_true, branch_false) => {
+                pass_arguments(branch_true, signatures, variables, &mut written.clone());
+                if let Some(branch_false) = branch_false {
+                    pass_arguments(branch_false, signatures, variables, &mut written.clone());
+                }
+                written.clear();
+            }
+            AstStatement::While(_, body)
+            | AstStatement::DoWhile(_, body)
+            | AstStatement::For(_, _, _, body)
+            | AstStatement::Block(body) => {
+                pass_arguments(body, signatures, variables, &mut written.clone());
+                written.clear();
+            }
+            AstStatement::Switch(_, cases, default) => {
+                for (_, body) in cases.iter_mut() {
+                    pass_arguments(body, signatures, variables, &mut written.clone());
+                }
+                if let Some(default) = default {
+                    pass_arguments(default, signatures, variables, &mut written.clone());
+                }
+                written.clear();
+            }
+            AstStatement::Try(body, handlers) => {
+                pass_arguments(body, signatures, variables, &mut written.clone());
+                // handlers are entered with the registers of the raising instruction
+                for (_, body) in handlers.iter_mut() {
+                    pass_arguments(body, signatures, variables, &mut Default::default());
+                }
+                written.clear();
+            }
+            AstStatement::Goto(_) | AstStatement::Label(_) => written.clear(),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the register a variable is stored in.
+pub(super) fn variable_register(
+    variables: &ArcAstVariableMap,
+    var_id: AstVariableId,
+) -> Option<Register> {
+    let variables = variables.read().unwrap();
+    let location = variables
+        .get(&var_id)?
+        .data_access_ir
+        .as_ref()?
+        .values()
+        .flat_map(|x| x.iter())
+        .next()?
+        .location()
+        .clone();
+    match location.as_ref() {
+        IrData::Register(register) => Some(*register),
+        _ => None,
+    }
 }
 
 /// ordering parameters
@@ -174,55 +360,65 @@ enum CallingConvention {
 /// x86 thiscall - ecx, edx, stack..., sp cleaned by callee (?)
 /// x86 vectorcall - xmm0, xmm1, xmm2, xmm3, stack..., sp cleaned by callee (?)
 /// x64 - rcx(xmm0), rdx(xmm1), r8(xmm2), r9(xmm3), stack..., sp cleaned by callee
+/// x64 system v - rdi, rsi, rdx, rcx, r8, r9, xmm0 ~ xmm7, stack..., sp cleaned by caller
+/// aarch64 aapcs64 - x0 ~ x7, v0 ~ v7, stack..., sp cleaned by caller
 fn used_locations_to_parameters(
+    calling_convention: AstCallingConvention,
     read_before_write_registers: HashSet<Register>,
     read_before_write_offset_from_base_pointers: HashSet<isize>,
     reg_to_var: &HashMap<Register, AstVariableId>,
     offset_to_var: &HashMap<isize, AstVariableId>,
 ) -> Vec<AstParameter> {
-    let calling_convention = detecting_calling_convention(
-        &read_before_write_registers,
-        &read_before_write_offset_from_base_pointers,
-    );
-
     match calling_convention {
-        CallingConvention::X64 => parameter_ordering::order_params_x64(
+        AstCallingConvention::X64Microsoft => parameter_ordering::order_params_x64(
+            &read_before_write_registers,
+            &read_before_write_offset_from_base_pointers,
+            reg_to_var,
+            offset_to_var,
+        ),
+        AstCallingConvention::X86Cdecl => parameter_ordering::order_params_x86_cdecl(
             &read_before_write_registers,
             &read_before_write_offset_from_base_pointers,
             reg_to_var,
             offset_to_var,
         ),
-        CallingConvention::X86Cdecl => parameter_ordering::order_params_x86_cdecl(
+        AstCallingConvention::X86Stdcall => parameter_ordering::order_params_x86_stdcall(
             &read_before_write_registers,
             &read_before_write_offset_from_base_pointers,
             reg_to_var,
             offset_to_var,
         ),
-        CallingConvention::X86Stdcall => parameter_ordering::order_params_x86_stdcall(
+        AstCallingConvention::X86Fastcall => parameter_ordering::order_params_x86_fastcall(
             &read_before_write_registers,
             &read_before_write_offset_from_base_pointers,
             reg_to_var,
             offset_to_var,
         ),
-        CallingConvention::X86Fastcall => parameter_ordering::order_params_x86_fastcall(
+        AstCallingConvention::X86Thiscall => parameter_ordering::order_params_x86_thiscall(
             &read_before_write_registers,
             &read_before_write_offset_from_base_pointers,
             reg_to_var,
             offset_to_var,
         ),
-        CallingConvention::X86Thiscall => parameter_ordering::order_params_x86_thiscall(
+        AstCallingConvention::X86Vectorcall => parameter_ordering::order_params_x86_vectorcall(
             &read_before_write_registers,
             &read_before_write_offset_from_base_pointers,
             reg_to_var,
             offset_to_var,
         ),
-        CallingConvention::X86Vectorcall => parameter_ordering::order_params_x86_vectorcall(
+        AstCallingConvention::X64SystemV => parameter_ordering::order_params_x64_system_v(
             &read_before_write_registers,
             &read_before_write_offset_from_base_pointers,
             reg_to_var,
             offset_to_var,
         ),
-        CallingConvention::Unknown => parameter_ordering::order_params_unknown(
+        AstCallingConvention::Arm64Aapcs => parameter_ordering::order_params_arm64_aapcs(
+            &read_before_write_registers,
+            &read_before_write_offset_from_base_pointers,
+            reg_to_var,
+            offset_to_var,
+        ),
+        AstCallingConvention::Unknown => parameter_ordering::order_params_unknown(
             &read_before_write_registers,
             &read_before_write_offset_from_base_pointers,
             reg_to_var,
@@ -231,19 +427,153 @@ fn used_locations_to_parameters(
     }
 }
 
+/// Returns where the first integer arguments of a calling convention are passed.
+///
+/// ### Arguments
+/// - `calling_convention: AstCallingConvention` - convention of the function
+/// - `count: usize` - amount of arguments
+///
+/// ### Returns
+/// - `(Vec<Register>, Vec<isize>)` - argument registers, and stack offsets from the frame base
+///   pointer of the arguments passed on the stack
+fn argument_locations(
+    calling_convention: AstCallingConvention,
+    count: usize,
+) -> (Vec<Register>, Vec<isize>) {
+    let (registers, first_offset, slot_size) = match calling_convention {
+        AstCallingConvention::X64Microsoft => (
+            vec![
+                <VirtualMachine as X64Range>::rcx(),
+                <VirtualMachine as X64Range>::rdx(),
+                <VirtualMachine as X64Range>::r8(),
+                <VirtualMachine as X64Range>::r9(),
+            ],
+            // past the return address and the home space of the register arguments
+            0x30,
+            8,
+        ),
+        AstCallingConvention::X64SystemV => (
+            vec![
+                <VirtualMachine as X64Range>::rdi(),
+                <VirtualMachine as X64Range>::rsi(),
+                <VirtualMachine as X64Range>::rdx(),
+                <VirtualMachine as X64Range>::rcx(),
+                <VirtualMachine as X64Range>::r8(),
+                <VirtualMachine as X64Range>::r9(),
+            ],
+            0x10,
+            8,
+        ),
+        AstCallingConvention::X86Fastcall => (
+            vec![
+                <VirtualMachine as X64Range>::ecx(),
+                <VirtualMachine as X64Range>::edx(),
+            ],
+            8,
+            4,
+        ),
+        AstCallingConvention::X86Thiscall => (vec![<VirtualMachine as X64Range>::ecx()], 8, 4),
+        AstCallingConvention::X86Cdecl
+        | AstCallingConvention::X86Stdcall
+        | AstCallingConvention::X86Vectorcall => (Vec::new(), 8, 4),
+        AstCallingConvention::Arm64Aapcs => (
+            vec![
+                <VirtualMachine as ArmRange>::x0(),
+                <VirtualMachine as ArmRange>::x1(),
+                <VirtualMachine as ArmRange>::x2(),
+                <VirtualMachine as ArmRange>::x3(),
+                <VirtualMachine as ArmRange>::x4(),
+                <VirtualMachine as ArmRange>::x5(),
+                <VirtualMachine as ArmRange>::x6(),
+                <VirtualMachine as ArmRange>::x7(),
+            ],
+            0x10,
+            8,
+        ),
+        AstCallingConvention::Unknown => return (Vec::new(), Vec::new()),
+    };
+    let stack_count = count.saturating_sub(registers.len());
+    let offsets = (0..stack_count)
+        .map(|index| first_offset + index as isize * slot_size)
+        .collect();
+    (registers.into_iter().take(count).collect(), offsets)
+}
+
+/// Selects the calling convention of a function from the platform default and what it uses.
+///
+/// ### Arguments
+/// - `platform_default: Option<AstCallingConvention>` - convention of the binary's platform
+/// - `used_registers: &HashSet<Register>` - registers read before written
+/// - `used_offset_from_base_pointers: &HashSet<isize>` - stack offsets read before written
+/// - `callee_cleanup: Option<u64>` - bytes the function pops off the stack when returning
+///
+/// ### Returns
+/// - `AstCallingConvention` - the calling convention to order parameters with
 fn detecting_calling_convention(
-    _used_registers: &HashSet<Register>,
-    _used_offset_from_base_pointers: &HashSet<isize>,
-) -> CallingConvention {
-    // Fireball currently only supports x86_64 and our primary target is PE on Windows.
-    // Use the x64 parameter ordering so callsites and callee params line up deterministically.
-    CallingConvention::X64
+    platform_default: Option<AstCallingConvention>,
+    used_registers: &HashSet<Register>,
+    used_offset_from_base_pointers: &HashSet<isize>,
+    callee_cleanup: Option<u64>,
+) -> AstCallingConvention {
+    if used_registers
+        .iter()
+        .any(|x| x.architecture() == Architecture::Arm64)
+    {
+        return AstCallingConvention::Arm64Aapcs;
+    }
+    let reads = |family: Register| {
+        used_registers
+            .iter()
+            .any(|x| x.architecture() == family.architecture() && is_in_family(x, &family))
+    };
+    let platform_default = platform_default.unwrap_or(AstCallingConvention::Unknown);
+
+    if platform_default.is_x86() {
+        // register arguments only exist in callee cleaned conventions
+        let reads_ecx = reads(<VirtualMachine as X64Range>::rcx());
+        let reads_edx = reads(<VirtualMachine as X64Range>::rdx());
+        return match (reads_ecx, reads_edx) {
+            (true, true) => AstCallingConvention::X86Fastcall,
+            (true, false) => AstCallingConvention::X86Thiscall,
+            _ if callee_cleanup.is_some_and(|x| x != 0) => AstCallingConvention::X86Stdcall,
+            _ if used_offset_from_base_pointers.iter().any(|x| *x > 0) => {
+                AstCallingConvention::X86Cdecl
+            }
+            _ => platform_default,
+        };
+    }
+
+    // rdi and rsi are only arguments in System V, rcx, r8 and r9 are the first ones in Microsoft
+    let reads_system_v =
+        reads(<VirtualMachine as X64Range>::rdi()) || reads(<VirtualMachine as X64Range>::rsi());
+    let reads_microsoft = reads(<VirtualMachine as X64Range>::rcx())
+        || reads(<VirtualMachine as X64Range>::r8())
+        || reads(<VirtualMachine as X64Range>::r9());
+    match platform_default {
+        // `ms_abi` functions of System V binaries (e.g., Wine, UEFI loaders)
+        AstCallingConvention::X64SystemV if !reads_system_v && reads_microsoft => {
+            AstCallingConvention::X64Microsoft
+        }
+        AstCallingConvention::X64SystemV
+        | AstCallingConvention::X64Microsoft
+        | AstCallingConvention::Arm64Aapcs => platform_default,
+        _ if reads_system_v && !reads_microsoft => AstCallingConvention::X64SystemV,
+        // Fireball's primary target is PE on Windows, so unknown platforms default to it
+        _ => AstCallingConvention::X64Microsoft,
+    }
+}
+
+/// Whether the register is a part of the family register (e.g., `ecx` of `rcx`)
+pub(super) fn is_in_family(register: &Register, family: &Register) -> bool {
+    let range = register.bit_range();
+    let family = family.bit_range();
+    family.start <= range.start && range.end <= family.end
 }
 
 mod parameter_ordering {
     use crate::{
         abstract_syntax_tree::{AstParameter, AstParameterLocation, AstVariableId},
-        ir::{Register, VirtualMachine, x86_64::X64Range},
+        ir::{Register, VirtualMachine, arm::ArmRange, x86_64::X64Range},
     };
     use either::Either;
     use hashbrown::{HashMap, HashSet};
@@ -405,6 +735,155 @@ mod parameter_ordering {
         params
     }
 
+    /// Adds every used register aliasing the family register, the widest first
+    fn add_register_family_if_used(
+        params: &mut Vec<AstParameter>,
+        added_regs: &mut HashSet<Register>,
+        used_registers: &HashSet<Register>,
+        family: Register,
+        reg_to_var: &HashMap<Register, AstVariableId>,
+    ) {
+        let mut members: Vec<_> = used_registers
+            .iter()
+            .filter(|reg| {
+                reg.architecture() == family.architecture()
+                    && super::is_in_family(reg, &family)
+                    && !added_regs.contains(*reg)
+            })
+            .cloned()
+            .collect();
+        members.sort_by_key(|r| (usize::MAX - r.bit_len(), r.bit_range().start, r.name()));
+        for r in members {
+            push_reg_param(params, added_regs, r, reg_to_var);
+        }
+    }
+
+    /// Adds the remaining registers in deterministic order, then the stack arguments
+    fn add_remaining_and_stack_params(
+        params: &mut Vec<AstParameter>,
+        added_regs: &mut HashSet<Register>,
+        used_registers: &HashSet<Register>,
+        used_offset_from_base_pointers: &HashSet<isize>,
+        reg_to_var: &HashMap<Register, AstVariableId>,
+        offset_to_var: &HashMap<isize, AstVariableId>,
+    ) {
+        let mut remaining: Vec<_> = used_registers
+            .iter()
+            .filter(|reg| !added_regs.contains(*reg))
+            .cloned()
+            .collect();
+        remaining.sort_by_key(|r| r.name());
+        for r in remaining {
+            push_reg_param(params, added_regs, r, reg_to_var);
+        }
+
+        let mut stack_offsets: Vec<isize> = used_offset_from_base_pointers
+            .iter()
+            .copied()
+            .filter(|o| *o > 0)
+            .collect();
+        stack_offsets.sort();
+        for off in stack_offsets {
+            push_stack_param(params, off, offset_to_var);
+        }
+    }
+
+    pub(super) fn order_params_x64_system_v(
+        used_registers: &HashSet<Register>,
+        used_offset_from_base_pointers: &HashSet<isize>,
+        reg_to_var: &HashMap<Register, AstVariableId>,
+        offset_to_var: &HashMap<isize, AstVariableId>,
+    ) -> Vec<AstParameter> {
+        let mut params: Vec<AstParameter> = Vec::new();
+        let mut added_regs: HashSet<Register> = HashSet::new();
+
+        // integer arguments and floating point arguments are counted separately
+        let families = [
+            <VirtualMachine as X64Range>::rdi(),
+            <VirtualMachine as X64Range>::rsi(),
+            <VirtualMachine as X64Range>::rdx(),
+            <VirtualMachine as X64Range>::rcx(),
+            <VirtualMachine as X64Range>::r8(),
+            <VirtualMachine as X64Range>::r9(),
+            <VirtualMachine as X64Range>::xmm0(),
+            <VirtualMachine as X64Range>::xmm1(),
+            <VirtualMachine as X64Range>::xmm2(),
+            <VirtualMachine as X64Range>::xmm3(),
+            <VirtualMachine as X64Range>::xmm4(),
+            <VirtualMachine as X64Range>::xmm5(),
+            <VirtualMachine as X64Range>::xmm6(),
+            <VirtualMachine as X64Range>::xmm7(),
+        ];
+        for family in families {
+            add_register_family_if_used(
+                &mut params,
+                &mut added_regs,
+                used_registers,
+                family,
+                reg_to_var,
+            );
+        }
+        add_remaining_and_stack_params(
+            &mut params,
+            &mut added_regs,
+            used_registers,
+            used_offset_from_base_pointers,
+            reg_to_var,
+            offset_to_var,
+        );
+
+        params
+    }
+
+    pub(super) fn order_params_arm64_aapcs(
+        used_registers: &HashSet<Register>,
+        used_offset_from_base_pointers: &HashSet<isize>,
+        reg_to_var: &HashMap<Register, AstVariableId>,
+        offset_to_var: &HashMap<isize, AstVariableId>,
+    ) -> Vec<AstParameter> {
+        let mut params: Vec<AstParameter> = Vec::new();
+        let mut added_regs: HashSet<Register> = HashSet::new();
+
+        // x8 holds the address of an indirect result, so it is not an argument
+        let families = [
+            <VirtualMachine as ArmRange>::x0(),
+            <VirtualMachine as ArmRange>::x1(),
+            <VirtualMachine as ArmRange>::x2(),
+            <VirtualMachine as ArmRange>::x3(),
+            <VirtualMachine as ArmRange>::x4(),
+            <VirtualMachine as ArmRange>::x5(),
+            <VirtualMachine as ArmRange>::x6(),
+            <VirtualMachine as ArmRange>::x7(),
+            <VirtualMachine as ArmRange>::v0(),
+            <VirtualMachine as ArmRange>::v1(),
+            <VirtualMachine as ArmRange>::v2(),
+            <VirtualMachine as ArmRange>::v3(),
+            <VirtualMachine as ArmRange>::v4(),
+            <VirtualMachine as ArmRange>::v5(),
+            <VirtualMachine as ArmRange>::v6(),
+            <VirtualMachine as ArmRange>::v7(),
+        ];
+        for family in families {
+            add_register_family_if_used(
+                &mut params,
+                &mut added_regs,
+                used_registers,
+                family,
+                reg_to_var,
+            );
+        }
+        add_remaining_and_stack_params(
+            &mut params,
+            &mut added_regs,
+            used_registers,
+            used_offset_from_base_pointers,
+            reg_to_var,
+            offset_to_var,
+        );
+
+        params
+    }
+
     pub(super) fn order_params_x86_fastcall(
         used_registers: &HashSet<Register>,
         used_offset_from_base_pointers: &HashSet<isize>,
@@ -586,3 +1065,126 @@ mod parameter_ordering {
         params
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_syntax_tree::{AstLiteral, AstValueType, AstVariable},
+        ir::{
+            data::{IrAccessSize, IrDataAccess, IrDataAccessType},
+            utils::{IrStatementDescriptor, IrStatementDescriptorMap},
+        },
+    };
+    use either::Either;
+
+    fn w<T>(item: T) -> Wrapped<T> {
+        Wrapped {
+            item,
+            comment: None,
+            origin: None,
+        }
+    }
+
+    /// Builds a caller writing each register before calling a callee taking `rdi` and `rsi`,
+    /// and returns the arguments the call is given.
+    fn call_arguments(written: &[Register]) -> Vec<AstVariableId> {
+        let mut ast = Ast::new();
+        let callee = ast.insert_test_function(0x2000, Vec::new());
+        let caller = ast.insert_test_function(0x1000, Vec::new());
+        let mut functions = ast.functions.write().unwrap();
+        functions
+            .get_mut(&callee)
+            .unwrap()
+            .get_mut(&AstFunctionVersion(1))
+            .unwrap()
+            .parameters = [
+            <VirtualMachine as X64Range>::rdi(),
+            <VirtualMachine as X64Range>::rsi(),
+        ]
+        .iter()
+        .map(|register| AstParameter {
+            location: AstParameterLocation::Register(register.into()),
+            id: Either::Right(register.name().to_string()),
+        })
+        .collect();
+        let caller_function = functions
+            .get_mut(&caller)
+            .unwrap()
+            .get_mut(&AstFunctionVersion(1))
+            .unwrap();
+        let variables = caller_function.variables.clone();
+        for (index, register) in written.iter().enumerate() {
+            let mut data_access_ir = IrStatementDescriptorMap::new();
+            data_access_ir.insert(
+                IrStatementDescriptor::new(index as u32, 0),
+                vec![IrDataAccess::new(
+                    register.into(),
+                    IrDataAccessType::Write,
+                    IrAccessSize::ArchitectureSize,
+                )],
+            );
+            let id = AstVariableId {
+                index: index as u32,
+                parent: Some(caller),
+            };
+            variables.write().unwrap().insert(
+                id,
+                AstVariable {
+                    name: None,
+                    id,
+                    var_type: AstValueType::Int64,
+                    const_value: None,
+                    data_access_ir: Some(data_access_ir),
+                    offset: None,
+                },
+            );
+            caller_function.body.push(w(AstStatement::Assignment(
+                w(AstExpression::Variable(variables.clone(), id)),
+                w(AstExpression::Literal(AstLiteral::Int(index as i64))),
+            )));
+        }
+        caller_function
+            .body
+            .push(w(AstStatement::Call(AstCall::Function {
+                target: callee,
+                args: Vec::new(),
+            })));
+        drop(functions);
+
+        apply_callee_signatures(&mut ast, caller, AstFunctionVersion(1)).unwrap();
+
+        let functions = ast.functions.read().unwrap();
+        let caller_function = functions[&caller].get(&AstFunctionVersion(1)).unwrap();
+        let Some(AstStatement::Call(AstCall::Function { args, .. })) =
+            caller_function.body.last().map(|x| &x.item)
+        else {
+            panic!("the call is gone: {:?}", caller_function.body);
+        };
+        args.iter()
+            .map(|arg| match &arg.item {
+                AstExpression::Variable(_, var_id) => *var_id,
+                item => panic!("not a variable: {item:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_apply_callee_signatures() {
+        // written in the opposite order, and `edi` standing for `rdi`
+        let arguments = call_arguments(&[
+            <VirtualMachine as X64Range>::rsi(),
+            <VirtualMachine as X64Range>::edi(),
+        ]);
+        assert_eq!(
+            arguments.iter().map(|x| x.index).collect::<Vec<_>>(),
+            [1, 0]
+        );
+    }
+
+    #[test]
+    fn test_apply_callee_signatures_missing_argument() {
+        let arguments = call_arguments(&[<VirtualMachine as X64Range>::rdi()]);
+        assert!(arguments.is_empty());
+    }
+}
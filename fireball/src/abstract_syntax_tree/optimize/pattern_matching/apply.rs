@@ -13,7 +13,8 @@ use super::{
 };
 use crate::{
     abstract_syntax_tree::{
-        Ast, AstFunctionId, AstFunctionVersion, AstOptimizationKind, AstStatement, Wrapped,
+        Ast, AstAddressRange, AstFunctionId, AstFunctionVersion, AstOptimizationKind, AstStatement,
+        Wrapped, optimize::opt_utils::inherit_origin,
     },
     ir::statements::IrStatement,
     prelude::DecompileError,
@@ -278,6 +279,22 @@ fn apply_file_pattern_rules_recursive(
                         phase,
                     );
                 }
+                AstStatement::Try(body, handlers) => {
+                    pass_changed |= apply_file_pattern_rules_recursive(
+                        body,
+                        rules,
+                        function_ir_statements,
+                        phase,
+                    );
+                    for (_handler, handler_body) in handlers.iter_mut() {
+                        pass_changed |= apply_file_pattern_rules_recursive(
+                            handler_body,
+                            rules,
+                            function_ir_statements,
+                            phase,
+                        );
+                    }
+                }
                 AstStatement::Declaration(_, _)
                 | AstStatement::Assignment(_, _)
                 | AstStatement::Return(_)
@@ -475,8 +492,17 @@ fn apply_single_file_rule(
                                 // trailing statements that were part of the
                                 // sequence.
                                 if end > start + 1 {
-                                    stmts.drain((start + 1)..end);
+                                    let first = stmts[start].origin;
+                                    let origin = stmts
+                                        .drain((start + 1)..end)
+                                        .fold(first, |origin, stmt| {
+                                            AstAddressRange::merge(origin, stmt.origin)
+                                        });
+                                    stmts[start].origin = origin;
                                 }
+                                // the emitted statement is built from captures without origin
+                                let origin = stmts[start].origin;
+                                inherit_origin(&mut stmts[start], origin);
                             }
                         }
                     }
@@ -487,7 +513,9 @@ fn apply_single_file_rule(
                             if let Some(list) =
                                 stmt_pattern::construct_emit_after_list(emit_pat, caps)
                             {
-                                for (j, before_stmt) in list.into_iter().enumerate() {
+                                let origin = stmts.get(start).and_then(|x| x.origin);
+                                for (j, mut before_stmt) in list.into_iter().enumerate() {
+                                    inherit_origin(&mut before_stmt, origin);
                                     stmts.insert(start + j, before_stmt);
                                 }
                             }
@@ -957,16 +985,22 @@ fn apply_replace_asm(
     }
 
     if let Some((start, end)) = matched.asm_statement_range {
-        if let Some(first) = stmts.get_mut(start) {
-            first.item = replacement.statement.clone();
-        }
+        let mut origin = None;
         if end > start {
             for idx in (start + 1)..=end {
                 if let Some(stmt) = stmts.get_mut(idx) {
                     stmt.item = AstStatement::Empty;
+                    origin = AstAddressRange::merge(origin, stmt.origin);
                 }
             }
         }
+        if let Some(first) = stmts.get_mut(start) {
+            first.item = replacement.statement.clone();
+            // the replacement covers the instructions of the emptied statements
+            first.origin = AstAddressRange::merge(first.origin, origin);
+            let origin = first.origin;
+            inherit_origin(first, origin);
+        }
         return;
     }
 
@@ -1152,6 +1186,12 @@ fn prune_empty_else_statement_recursive(stmt: &mut Wrapped<AstStatement>) {
         AstStatement::Block(body) => {
             prune_empty_else_recursive(body);
         }
+        AstStatement::Try(body, handlers) => {
+            prune_empty_else_recursive(body);
+            for (_handler, handler_body) in handlers.iter_mut() {
+                prune_empty_else_recursive(handler_body);
+            }
+        }
         AstStatement::Declaration(_, _)
         | AstStatement::Assignment(_, _)
         | AstStatement::Return(_)
@@ -1,7 +1,7 @@
 use crate::{
     abstract_syntax_tree::{
-        AstBuiltinFunctionArgument, AstCall, AstExpression, AstJumpTarget, AstLiteral,
-        AstStatement, AstValue, AstValueType, AstVariable, Wrapped,
+        AstBuiltinFunctionArgument, AstCall, AstExceptionHandler, AstExpression, AstJumpTarget,
+        AstLiteral, AstStatement, AstValue, AstValueType, AstVariable, Wrapped,
     },
     ir::statements::{IrStatement, IrStatementSpecial},
 };
@@ -134,6 +134,19 @@ fn hash_statement(state: &mut Blake3StdHasher, stmt: &AstStatement) {
                 None => false.hash(state),
             }
         }
+        AstStatement::Try(body, handlers) => {
+            hash_statement_list(state, body);
+            handlers.len().hash(state);
+            for (handler, handler_body) in handlers {
+                std::mem::discriminant(handler).hash(state);
+                match handler {
+                    AstExceptionHandler::Catch(types) => types.hash(state),
+                    AstExceptionHandler::Except(filter) => hash_wrapped_expression(state, filter),
+                    AstExceptionHandler::Finally => {}
+                }
+                hash_statement_list(state, handler_body);
+            }
+        }
         AstStatement::Undefined
         | AstStatement::Break
         | AstStatement::Continue
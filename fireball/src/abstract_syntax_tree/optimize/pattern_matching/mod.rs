@@ -37,6 +37,18 @@ impl PartialEq for AstPattern {
     }
 }
 impl Eq for AstPattern {}
+/// Patterns are stored by their name and source, predefined ones are looked up again by name
+impl serde::Serialize for AstPattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&(&self.name, &self.pattern), serializer)
+    }
+}
+impl<'de> serde::Deserialize<'de> for AstPattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (name, pattern): (String, String) = serde::Deserialize::deserialize(deserializer)?;
+        Ok(Self::predefined_pattern(&name).unwrap_or_else(|| Self::new(name, pattern)))
+    }
+}
 impl AstPattern {
     fn from_parse_result(
         name: String,
@@ -37,6 +37,7 @@ impl RhaiAstStmt {
             AstStatement::Break => "break",
             AstStatement::Continue => "continue",
             AstStatement::DoWhile(_, _) => "dowhile",
+            AstStatement::Try(_, _) => "try",
         };
         Self {
             kind,
@@ -121,6 +122,10 @@ impl RhaiAstStmt {
         matches!(self.wrapped.item, AstStatement::DoWhile(_, _))
     }
 
+    fn is_try(&mut self) -> bool {
+        matches!(self.wrapped.item, AstStatement::Try(_, _))
+    }
+
     fn is_empty(&mut self) -> bool {
         matches!(self.wrapped.item, AstStatement::Empty)
     }
@@ -170,6 +175,7 @@ impl RhaiAstStmt {
             AstStatement::For(_, _, _, body) => body.len() as i64,
             AstStatement::Block(body) => body.len() as i64,
             AstStatement::DoWhile(_, body) => body.len() as i64,
+            AstStatement::Try(body, _) => body.len() as i64,
             _ => 0,
         }
     }
@@ -220,6 +226,7 @@ impl RhaiAstStmt {
         let wrapped = Wrapped {
             item: AstStatement::Comment(text),
             comment: self.wrapped.comment.clone(),
+            origin: self.wrapped.origin,
         };
         RhaiAstStmt::from_wrapped(&wrapped)
     }
@@ -246,6 +253,7 @@ impl RhaiAstStmt {
             | AstStatement::DoWhile(_, body) => body,
             AstStatement::Block(body) => body,
             AstStatement::For(_, _, _, body) => body,
+            AstStatement::Try(body, _) => body,
             _ => return Array::new(),
         };
         stmts
@@ -711,6 +719,12 @@ fn stmt_contains_call(stmt: &AstStatement, needle: &str) -> bool {
                 worklist.push(d);
             }
         }
+        AstStatement::Try(body, handlers) => {
+            worklist.push(body);
+            for (_, handler_body) in handlers {
+                worklist.push(handler_body);
+            }
+        }
         _ => return false,
     }
 
@@ -752,6 +766,12 @@ fn stmt_contains_call(stmt: &AstStatement, needle: &str) -> bool {
                         worklist.push(d);
                     }
                 }
+                AstStatement::Try(body, handlers) => {
+                    worklist.push(body);
+                    for (_, handler_body) in handlers {
+                        worklist.push(handler_body);
+                    }
+                }
                 _ => {}
             }
         }
@@ -922,6 +942,7 @@ pub(super) fn register_analysis_types(engine: &mut Engine) {
         .register_fn("is_break", RhaiAstStmt::is_break)
         .register_fn("is_continue", RhaiAstStmt::is_continue)
         .register_fn("is_dowhile", RhaiAstStmt::is_dowhile)
+        .register_fn("is_try", RhaiAstStmt::is_try)
         .register_fn("is_empty", RhaiAstStmt::is_empty)
         .register_fn("is_ir", RhaiAstStmt::is_ir)
         .register_fn("is_assembly", RhaiAstStmt::is_assembly)
@@ -146,6 +146,7 @@ pub(super) fn construct_wrapped_expr(
             Some(Wrapped {
                 item: expr,
                 comment: None,
+                origin: None,
             })
         }
         _ => None,
@@ -403,6 +404,7 @@ fn construct_stmt_list(pat: &PatTree, caps: &Captures) -> Option<Vec<Wrapped<Ast
             Captured::Statement(stmt) => Some(vec![Wrapped {
                 item: stmt.clone(),
                 comment: None,
+                origin: None,
             }]),
             _ => None,
         },
@@ -411,6 +413,7 @@ fn construct_stmt_list(pat: &PatTree, caps: &Captures) -> Option<Vec<Wrapped<Ast
             Some(vec![Wrapped {
                 item: stmt,
                 comment: None,
+                origin: None,
             }])
         }
         PatTree::List(pats) => {
@@ -420,6 +423,7 @@ fn construct_stmt_list(pat: &PatTree, caps: &Captures) -> Option<Vec<Wrapped<Ast
                 result.push(Wrapped {
                     item: stmt,
                     comment: None,
+                    origin: None,
                 });
             }
             Some(result)
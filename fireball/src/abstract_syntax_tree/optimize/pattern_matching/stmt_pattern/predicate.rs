@@ -883,6 +883,12 @@ fn stmt_contains_unsafe(stmt: &AstStatement) -> bool {
                     .as_ref()
                     .is_some_and(|d| branch_contains_unsafe_stmts(d))
         }
+        AstStatement::Try(body, handlers) => {
+            branch_contains_unsafe_stmts(body)
+                || handlers
+                    .iter()
+                    .any(|(_, body)| branch_contains_unsafe_stmts(body))
+        }
         _ => false,
     }
 }
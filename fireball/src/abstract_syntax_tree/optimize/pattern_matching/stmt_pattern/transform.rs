@@ -5,8 +5,8 @@ use super::{
     types::{Captured, Captures, PatTree, WherePredicate},
 };
 use crate::abstract_syntax_tree::{
-    AstBinaryOperator, AstBuiltinFunctionArgument, AstCall, AstExpression, AstLiteral,
-    AstStatement, Wrapped,
+    AstBinaryOperator, AstBuiltinFunctionArgument, AstCall, AstExceptionHandler, AstExpression,
+    AstLiteral, AstStatement, Wrapped,
 };
 
 // ---------------------------------------------------------------------------
@@ -89,6 +89,22 @@ fn transform_expressions_in_statement(
         AstStatement::Block(body) => {
             transform_expressions_in_stmts(body, match_pat, predicates, replace_pat)
         }
+        AstStatement::Try(body, handlers) => {
+            let mut changed =
+                transform_expressions_in_stmts(body, match_pat, predicates, replace_pat);
+            for (handler, handler_body) in handlers.iter_mut() {
+                if let AstExceptionHandler::Except(filter) = handler {
+                    changed |= transform_expression(filter, match_pat, predicates, replace_pat);
+                }
+                changed |= transform_expressions_in_stmts(
+                    handler_body,
+                    match_pat,
+                    predicates,
+                    replace_pat,
+                );
+            }
+            changed
+        }
         AstStatement::Return(expr) => {
             if let Some(expr) = expr {
                 transform_expression(expr, match_pat, predicates, replace_pat)
@@ -326,6 +342,26 @@ fn transform_expressions_in_statement_builtin(
         AstStatement::Block(body) => {
             transform_expressions_in_stmts_builtin(body, match_pat, predicates, func, arg_names)
         }
+        AstStatement::Try(body, handlers) => {
+            let mut changed = transform_expressions_in_stmts_builtin(
+                body, match_pat, predicates, func, arg_names,
+            );
+            for (handler, handler_body) in handlers.iter_mut() {
+                if let AstExceptionHandler::Except(filter) = handler {
+                    changed |= transform_expression_builtin(
+                        filter, match_pat, predicates, func, arg_names,
+                    );
+                }
+                changed |= transform_expressions_in_stmts_builtin(
+                    handler_body,
+                    match_pat,
+                    predicates,
+                    func,
+                    arg_names,
+                );
+            }
+            changed
+        }
         AstStatement::Return(expr) => {
             if let Some(expr) = expr {
                 transform_expression_builtin(expr, match_pat, predicates, func, arg_names)
@@ -497,6 +533,7 @@ fn eval_builtin_fn(
             Some(Wrapped {
                 item: AstExpression::Literal(result),
                 comment: source.comment.clone(),
+                origin: source.origin,
             })
         }
         "eval_unary" => {
@@ -515,6 +552,7 @@ fn eval_builtin_fn(
             Some(Wrapped {
                 item: AstExpression::Literal(result),
                 comment: source.comment.clone(),
+                origin: source.origin,
             })
         }
         _ => None,
@@ -955,6 +955,7 @@ fn rhai_ast_stmt_kind() {
     let wrapped = Wrapped {
         item: AstStatement::Comment("hello".to_string()),
         comment: None,
+        origin: None,
     };
     let rhai_stmt = rhai_types::RhaiAstStmt::from_wrapped(&wrapped);
     assert_eq!(rhai_stmt.kind, "comment");
@@ -967,11 +968,13 @@ fn rhai_ast_stmt_all_kinds() {
         Wrapped {
             item: s,
             comment: None,
+            origin: None,
         }
     };
     let dummy_expr = || Wrapped {
         item: AstExpression::Unknown,
         comment: None,
+        origin: None,
     };
 
     let cases: Vec<(AstStatement, &str)> = vec![
@@ -1002,6 +1005,7 @@ fn rhai_ast_stmt_all_kinds() {
                     var_type: crate::abstract_syntax_tree::AstValueType::Unknown,
                     const_value: None,
                     data_access_ir: None,
+                    offset: None,
                 },
                 None,
             ),
@@ -1020,6 +1024,7 @@ fn rhai_ast_stmt_all_kinds() {
         (AstStatement::DoWhile(dummy_expr(), vec![]), "dowhile"),
         (AstStatement::If(dummy_expr(), vec![], None), "if"),
         (AstStatement::Switch(dummy_expr(), vec![], None), "switch"),
+        (AstStatement::Try(vec![], vec![]), "try"),
     ];
 
     for (stmt, expected_kind) in cases {
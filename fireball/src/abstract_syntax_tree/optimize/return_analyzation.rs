@@ -0,0 +1,763 @@
+//! Infer whether functions return a value, and pass the value at their returns and calls.
+
+use super::parameter_analyzation::{is_in_family, variable_register};
+use crate::{
+    abstract_syntax_tree::{
+        ArcAstVariableMap, Ast, AstCall, AstExpression, AstFunctionId, AstFunctionVersion,
+        AstStatement, AstValueType, AstVariableAccessType, AstVariableId, GetRelatedVariables,
+        Wrapped,
+    },
+    ir::{Register, VirtualMachine, arm::ArmRange, x86_64::X64Range},
+    prelude::*,
+};
+use hashbrown::{HashMap, HashSet};
+
+/// Variables last written to each register
+type Written = Vec<(Register, AstVariableId)>;
+
+/// Registers values are returned in, integers first
+fn return_registers() -> [Register; 4] {
+    [
+        <VirtualMachine as X64Range>::rax(),
+        <VirtualMachine as ArmRange>::x0(),
+        <VirtualMachine as X64Range>::xmm0(),
+        <VirtualMachine as ArmRange>::v0(),
+    ]
+}
+
+fn in_family(register: &Register, family: &Register) -> bool {
+    register.architecture() == family.architecture() && is_in_family(register, family)
+}
+
+/// Decides which functions return a value, then rewrites their returns to carry it and the calls
+/// using it to assign it.
+///
+/// ### Arguments
+/// - `components: &[Vec<AstFunctionId>]` - functions calling each other, callees first
+///
+/// ### Note
+/// - A function returns a value when a return register is written before every `return`, and
+///   a caller reads it after the call before writing it. Functions without known callers only
+///   need the former.
+/// - Functions are decided callees first, so the value a caller returns may come from a call.
+/// - Calls to functions outside of the AST assign the value when a return register is read
+///   after them.
+/// - The return type is the type of the returned variables, or an integer the size of the
+///   register written. Functions already returning values keep their type.
+pub(super) fn analyze_returns(
+    ast: &mut Ast,
+    versions: &[(AstFunctionId, AstFunctionVersion)],
+    components: &[Vec<AstFunctionId>],
+) -> Result<(), DecompileError> {
+    let mut functions = ast.functions.write().unwrap();
+
+    // functions not in the call graph come last
+    let version_of: HashMap<AstFunctionId, AstFunctionVersion> = versions.iter().copied().collect();
+    let mut order: Vec<Vec<AstFunctionId>> = components
+        .iter()
+        .map(|component| {
+            component
+                .iter()
+                .copied()
+                .filter(|x| version_of.contains_key(x))
+                .collect::<Vec<_>>()
+        })
+        .filter(|component| !component.is_empty())
+        .collect();
+    let ordered: HashSet<AstFunctionId> = order.iter().flatten().copied().collect();
+    order.extend(
+        versions
+            .iter()
+            .filter(|(id, _)| !ordered.contains(id))
+            .map(|(id, _)| vec![*id]),
+    );
+
+    // return registers read after the calls to each function
+    let mut called: HashSet<AstFunctionId> = HashSet::new();
+    let mut read: HashMap<AstFunctionId, Vec<Register>> = HashMap::new();
+    for (function_id, function_version) in versions.iter() {
+        let Some(function) = functions
+            .get_mut(function_id)
+            .and_then(|x| x.get_mut(function_version))
+        else {
+            continue;
+        };
+        let variables = function.variables.clone();
+        for_each_call(&mut function.body, &mut |call, stmt, rest| {
+            let AstCall::Function { target, .. } = call else {
+                return;
+            };
+            called.insert(*target);
+            let read = read.entry(*target).or_default();
+            for family in return_registers() {
+                // calls assigned by an earlier run are used
+                let used = !matches!(stmt.item, AstStatement::Call(_))
+                    || read_after_call(rest, &family, &variables).is_some();
+                if used && !read.contains(&family) {
+                    read.push(family);
+                }
+            }
+        });
+    }
+
+    let mut returning: HashMap<AstFunctionId, Register> = HashMap::new();
+    for component in order.iter() {
+        let mut candidates: HashMap<AstFunctionId, Register> = HashMap::new();
+        let mut returns_without_value: HashSet<AstFunctionId> = HashSet::new();
+        for function_id in component.iter() {
+            let Some(function) = functions
+                .get_mut(function_id)
+                .and_then(|x| x.get_mut(&version_of[function_id]))
+            else {
+                continue;
+            };
+            let variables = function.variables.clone();
+            assign_returned_values(&mut function.body, &returning, &variables);
+            let mut returns: Vec<Written> = Vec::new();
+            walk(
+                &mut function.body,
+                Vec::new(),
+                &variables,
+                &mut |_, written| returns.push(written.clone()),
+            );
+            if returns.is_empty() {
+                continue;
+            }
+            returns_without_value.insert(*function_id);
+            let register = return_registers().into_iter().find(|family| {
+                returns
+                    .iter()
+                    .all(|written| written.iter().any(|(x, _)| in_family(x, family)))
+            });
+            let Some(register) = register else {
+                continue;
+            };
+            let used = read.get(function_id).is_some_and(|x| x.contains(&register));
+            if used || !called.contains(function_id) {
+                candidates.insert(*function_id, register);
+            }
+        }
+        returning.extend(candidates.iter().map(|(id, x)| (*id, *x)));
+
+        for function_id in component.iter() {
+            let Some(function) = functions
+                .get_mut(function_id)
+                .and_then(|x| x.get_mut(&version_of[function_id]))
+            else {
+                continue;
+            };
+            let Some(register) = candidates.get(function_id) else {
+                // without a `return` lacking its value, the function keeps its type
+                if returns_without_value.contains(function_id) {
+                    function.return_type = AstValueType::Void;
+                }
+                continue;
+            };
+            let variables = function.variables.clone();
+            let mut returned = Vec::new();
+            walk(
+                &mut function.body,
+                Vec::new(),
+                &variables,
+                &mut |stmt, written| {
+                    let Some((_, var_id)) =
+                        written.iter().rev().find(|(x, _)| in_family(x, register))
+                    else {
+                        return;
+                    };
+                    stmt.item = AstStatement::Return(Some(Wrapped {
+                        item: AstExpression::Variable(variables.clone(), *var_id),
+                        comment: None,
+                        origin: stmt.origin,
+                    }));
+                    returned.push(*var_id);
+                },
+            );
+            function.return_type = return_type(&returned, register, &variables);
+        }
+    }
+
+    // calls between functions calling each other are assigned once all of them are decided
+    for (function_id, function_version) in versions.iter() {
+        let Some(function) = functions
+            .get_mut(function_id)
+            .and_then(|x| x.get_mut(function_version))
+        else {
+            continue;
+        };
+        let variables = function.variables.clone();
+        assign_returned_values(&mut function.body, &returning, &variables);
+    }
+    Ok(())
+}
+
+/// Turns the calls whose value is read afterwards into assignments of the value.
+///
+/// ### Arguments
+/// - `returning: &HashMap<AstFunctionId, Register>` - register each function of the AST returns
+///   its value in, functions not in it return nothing
+fn assign_returned_values(
+    body: &mut [Wrapped<AstStatement>],
+    returning: &HashMap<AstFunctionId, Register>,
+    variables: &ArcAstVariableMap,
+) {
+    for_each_call(body, &mut |call, stmt, rest| {
+        let var_id = match call {
+            AstCall::Function { target, .. } => returning
+                .get(target)
+                .and_then(|register| read_after_call(rest, register, variables)),
+            // the callee is unknown, any return register read is its value
+            _ => return_registers()
+                .iter()
+                .find_map(|register| read_after_call(rest, register, variables)),
+        };
+        let Some(var_id) = var_id else {
+            return;
+        };
+        let AstStatement::Call(call) = &stmt.item else {
+            return;
+        };
+        let value = Wrapped {
+            item: AstExpression::Call(call.clone()),
+            comment: None,
+            origin: stmt.origin,
+        };
+        let target = Wrapped {
+            item: AstExpression::Variable(variables.clone(), var_id),
+            comment: None,
+            origin: stmt.origin,
+        };
+        stmt.item = AstStatement::Assignment(target, value);
+    });
+}
+
+/// Returns the type of the returned variables, or an integer the size of their register.
+fn return_type(
+    returned: &[AstVariableId],
+    family: &Register,
+    variables: &ArcAstVariableMap,
+) -> AstValueType {
+    let types: Vec<AstValueType> = {
+        let variables = variables.read().unwrap();
+        returned
+            .iter()
+            .filter_map(|x| variables.get(x))
+            .map(|x| x.var_type.clone())
+            .collect()
+    };
+    if let Some(first) = types.first()
+        && *first != AstValueType::Unknown
+        && types.iter().all(|x| x == first)
+    {
+        return first.clone();
+    }
+    if family.bit_len() > 64 {
+        return AstValueType::Double;
+    }
+    let bit_len = returned
+        .iter()
+        .filter_map(|x| variable_register(variables, *x))
+        .map(|x| x.bit_len())
+        .max()
+        .unwrap_or(family.bit_len());
+    match bit_len {
+        0..=8 => AstValueType::Int8,
+        9..=16 => AstValueType::Int16,
+        17..=32 => AstValueType::Int32,
+        _ => AstValueType::Int64,
+    }
+}
+
+/// Records a write to the register a variable is stored in.
+fn write(written: &mut Written, variables: &ArcAstVariableMap, var_id: AstVariableId) {
+    let Some(register) = variable_register(variables, var_id) else {
+        return;
+    };
+    written.retain(|(x, _)| !in_family(x, &register) && !in_family(&register, x));
+    written.push((register, var_id));
+}
+
+/// Keeps the writes both paths agree on.
+fn intersect(a: Written, b: &Written) -> Written {
+    a.into_iter().filter(|x| b.contains(x)).collect()
+}
+
+/// Keeps the writes the statements can not replace with another variable.
+fn unchanged_by(
+    mut written: Written,
+    stmts: &[Wrapped<AstStatement>],
+    variables: &ArcAstVariableMap,
+) -> Written {
+    for stmt in stmts {
+        if matches!(
+            &stmt.item,
+            AstStatement::Call(_)
+                | AstStatement::Assignment(
+                    _,
+                    Wrapped {
+                        item: AstExpression::Call(_),
+                        ..
+                    }
+                )
+        ) {
+            return Vec::new();
+        }
+        for (access_type, var_id) in stmt.get_related_variables() {
+            if access_type != AstVariableAccessType::Write {
+                continue;
+            }
+            let Some(register) = variable_register(variables, var_id) else {
+                continue;
+            };
+            written.retain(|(x, id)| {
+                *id == var_id || (!in_family(x, &register) && !in_family(&register, x))
+            });
+        }
+        let nested: Vec<&[Wrapped<AstStatement>]> = match &stmt.item {
+            AstStatement::If(_, branch_true, branch_false) => {
+                let mut nested = vec![branch_true.as_slice()];
+                nested.extend(branch_false.as_deref());
+                nested
+            }
+            AstStatement::While(_, body)
+            | AstStatement::DoWhile(_, body)
+            | AstStatement::For(_, _, _, body)
+            | AstStatement::Block(body) => vec![body.as_slice()],
+            AstStatement::Switch(_, cases, default) => cases
+                .iter()
+                .map(|(_, body)| body.as_slice())
+                .chain(default.as_deref())
+                .collect(),
+            AstStatement::Try(body, handlers) => [body.as_slice()]
+                .into_iter()
+                .chain(handlers.iter().map(|(_, body)| body.as_slice()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        for body in nested {
+            written = unchanged_by(written, body, variables);
+        }
+    }
+    written
+}
+
+/// Walks the statements in execution order, with the variables last written to each register.
+///
+/// ### Arguments
+/// - `on_return: &mut impl FnMut(&mut Wrapped<AstStatement>, &Written)` - called on every `return`
+///   without a value
+///
+/// ### Returns
+/// - `Option<Written>` - writes when the statements fall through, `None` when they never do
+fn walk(
+    stmts: &mut [Wrapped<AstStatement>],
+    mut written: Written,
+    variables: &ArcAstVariableMap,
+    on_return: &mut impl FnMut(&mut Wrapped<AstStatement>, &Written),
+) -> Option<Written> {
+    for stmt in stmts.iter_mut() {
+        match &mut stmt.item {
+            AstStatement::Declaration(variable, Some(_)) => {
+                write(&mut written, variables, variable.id)
+            }
+            AstStatement::Assignment(lhs, rhs) => {
+                // the callee may overwrite every register
+                if matches!(rhs.item, AstExpression::Call(_)) {
+                    written.clear();
+                }
+                if let AstExpression::Variable(_, var_id) = &lhs.item {
+                    write(&mut written, variables, *var_id);
+                }
+            }
+            // the callee may overwrite every register
+            AstStatement::Call(_) | AstStatement::Label(_) => written.clear(),
+            AstStatement::Return(None) => {
+                on_return(stmt, &written);
+                return None;
+            }
+            AstStatement::Return(Some(_))
+            | AstStatement::Break
+            | AstStatement::Continue
+            | AstStatement::Goto(_) => return None,
+            AstStatement::If(_, branch_true, branch_false) => {
+                let taken = walk(branch_true, written.clone(), variables, on_return);
+                let not_taken = match branch_false {
+                    Some(branch_false) => walk(branch_false, written.clone(), variables, on_return),
+                    None => Some(written.clone()),
+                };
+                written = match (taken, not_taken) {
+                    (Some(taken), Some(not_taken)) => intersect(taken, &not_taken),
+                    (Some(fallthrough), None) | (None, Some(fallthrough)) => fallthrough,
+                    (None, None) => return None,
+                };
+            }
+            AstStatement::For(init, _, _, body) => {
+                written = walk(
+                    std::slice::from_mut(init.as_mut()),
+                    written,
+                    variables,
+                    on_return,
+                )?;
+                walk(body, written.clone(), variables, on_return);
+                written = unchanged_by(written, body, variables);
+            }
+            AstStatement::While(_, body) | AstStatement::DoWhile(_, body) => {
+                walk(body, written.clone(), variables, on_return);
+                written = unchanged_by(written, body, variables);
+            }
+            AstStatement::Block(body) => {
+                written = walk(body, written, variables, on_return)?;
+            }
+            AstStatement::Switch(_, cases, default) => {
+                for (_, body) in cases.iter_mut() {
+                    walk(body, written.clone(), variables, on_return);
+                }
+                if let Some(default) = default {
+                    walk(default, written.clone(), variables, on_return);
+                }
+                for (_, body) in cases.iter() {
+                    written = unchanged_by(written, body, variables);
+                }
+                if let Some(default) = default {
+                    written = unchanged_by(written, default, variables);
+                }
+            }
+            AstStatement::Try(body, handlers) => {
+                walk(body, written.clone(), variables, on_return);
+                // handlers are entered from any point of the body
+                for (_, body) in handlers.iter_mut() {
+                    walk(body, Written::new(), variables, on_return);
+                }
+                written = unchanged_by(written, body, variables);
+                for (_, body) in handlers.iter() {
+                    written = unchanged_by(written, body, variables);
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(written)
+}
+
+/// Calls `f` with every call, the statement making it and the statements following it.
+///
+/// ### Note
+/// - Calls already assigned to a variable are visited too.
+fn for_each_call(
+    stmts: &mut [Wrapped<AstStatement>],
+    f: &mut impl FnMut(&AstCall, &mut Wrapped<AstStatement>, &[Wrapped<AstStatement>]),
+) {
+    for i in 0..stmts.len() {
+        let (stmt, rest) = stmts[i..].split_first_mut().unwrap();
+        match &mut stmt.item {
+            AstStatement::Call(call)
+            | AstStatement::Assignment(
+                _,
+                Wrapped {
+                    item: AstExpression::Call(call),
+                    ..
+                },
+            ) => {
+                if matches!(call, AstCall::Builtin(..)) {
+                    continue;
+                }
+                let call = call.clone();
+                f(&call, stmt, rest);
+            }
+            AstStatement::If(_, branch_true, branch_false) => {
+                for_each_call(branch_true, f);
+                if let Some(branch_false) = branch_false {
+                    for_each_call(branch_false, f);
+                }
+            }
+            AstStatement::While(_, body)
+            | AstStatement::DoWhile(_, body)
+            | AstStatement::For(_, _, _, body)
+            | AstStatement::Block(body) => for_each_call(body, f),
+            AstStatement::Switch(_, cases, default) => {
+                for (_, body) in cases.iter_mut() {
+                    for_each_call(body, f);
+                }
+                if let Some(default) = default {
+                    for_each_call(default, f);
+                }
+            }
+            AstStatement::Try(body, handlers) => {
+                for_each_call(body, f);
+                for (_, body) in handlers.iter_mut() {
+                    for_each_call(body, f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Finds the variable of the return register read after a call, before the register is
+/// written again.
+fn read_after_call(
+    rest: &[Wrapped<AstStatement>],
+    family: &Register,
+    variables: &ArcAstVariableMap,
+) -> Option<AstVariableId> {
+    let returned = |var_id: &AstVariableId| {
+        variable_register(variables, *var_id).is_some_and(|x| in_family(&x, family))
+    };
+    for stmt in rest {
+        let (reads, write) = match &stmt.item {
+            AstStatement::Assignment(_, rhs) if matches!(rhs.item, AstExpression::Call(_)) => {
+                // the next call overwrites the register
+                return rhs.get_related_variables().into_iter().find(returned);
+            }
+            AstStatement::Assignment(lhs, rhs) => match &lhs.item {
+                AstExpression::Variable(_, var_id) => (rhs.get_related_variables(), Some(*var_id)),
+                _ => {
+                    let mut reads = lhs.get_related_variables();
+                    reads.extend(rhs.get_related_variables());
+                    (reads, None)
+                }
+            },
+            AstStatement::Declaration(variable, init) => (
+                init.as_ref()
+                    .map(|x| x.get_related_variables())
+                    .unwrap_or_default(),
+                Some(variable.id),
+            ),
+            AstStatement::Call(call) => {
+                let call = AstExpression::Call(call.clone());
+                // the next call overwrites the register
+                return call.get_related_variables().into_iter().find(returned);
+            }
+            AstStatement::Return(Some(value)) => (value.get_related_variables(), None),
+            AstStatement::If(condition, _, _)
+            | AstStatement::While(condition, _)
+            | AstStatement::Switch(condition, _, _) => {
+                return condition.get_related_variables().into_iter().find(returned);
+            }
+            AstStatement::Comment(_) | AstStatement::Empty => continue,
+            _ => return None,
+        };
+        if let Some(var_id) = reads.into_iter().find(returned) {
+            return Some(var_id);
+        }
+        if write.is_some_and(|x| returned(&x)) {
+            return None;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_syntax_tree::{AstFunction, AstLiteral, AstVariable},
+        ir::{
+            data::{IrAccessSize, IrDataAccess, IrDataAccessType},
+            utils::{IrStatementDescriptor, IrStatementDescriptorMap},
+        },
+    };
+
+    fn w<T>(item: T) -> Wrapped<T> {
+        Wrapped {
+            item,
+            comment: None,
+            origin: None,
+        }
+    }
+
+    /// Adds a variable stored in `rax` to the function, and returns the variable map with its id.
+    fn rax_variable(
+        ast: &Ast,
+        function_id: AstFunctionId,
+        index: u32,
+        var_type: AstValueType,
+    ) -> (ArcAstVariableMap, AstVariableId) {
+        let variables = ast.functions.read().unwrap()[&function_id]
+            .get(&AstFunctionVersion(1))
+            .unwrap()
+            .variables
+            .clone();
+        let mut data_access_ir = IrStatementDescriptorMap::new();
+        data_access_ir.insert(
+            IrStatementDescriptor::new(0, 0),
+            vec![IrDataAccess::new(
+                (&<VirtualMachine as X64Range>::rax()).into(),
+                IrDataAccessType::Write,
+                IrAccessSize::ArchitectureSize,
+            )],
+        );
+        let id = AstVariableId {
+            index,
+            parent: Some(function_id),
+        };
+        variables.write().unwrap().insert(
+            id,
+            AstVariable {
+                name: None,
+                id,
+                var_type,
+                const_value: None,
+                data_access_ir: Some(data_access_ir),
+                offset: None,
+            },
+        );
+        (variables, id)
+    }
+
+    fn set_body(ast: &Ast, function_id: AstFunctionId, body: Vec<Wrapped<AstStatement>>) {
+        ast.functions
+            .write()
+            .unwrap()
+            .get_mut(&function_id)
+            .unwrap()
+            .get_mut(&AstFunctionVersion(1))
+            .unwrap()
+            .body = body;
+    }
+
+    fn function<T>(ast: &Ast, function_id: AstFunctionId, f: impl FnOnce(&AstFunction) -> T) -> T {
+        f(ast.functions.read().unwrap()[&function_id]
+            .get(&AstFunctionVersion(1))
+            .unwrap())
+    }
+
+    #[test]
+    fn test_analyze_returns_callee_first() {
+        let mut ast = Ast::new();
+        let callee = ast.insert_test_function(0x2000, Vec::new());
+        let caller = ast.insert_test_function(0x1000, Vec::new());
+        let (callee_variables, result) = rax_variable(&ast, callee, 1, AstValueType::Int32);
+        let (caller_variables, value) = rax_variable(&ast, caller, 2, AstValueType::Int32);
+        set_body(
+            &ast,
+            callee,
+            vec![
+                w(AstStatement::Assignment(
+                    w(AstExpression::Variable(callee_variables, result)),
+                    w(AstExpression::Literal(AstLiteral::Int(1))),
+                )),
+                w(AstStatement::Return(None)),
+            ],
+        );
+        set_body(
+            &ast,
+            caller,
+            vec![
+                w(AstStatement::Call(AstCall::Function {
+                    target: callee,
+                    args: Vec::new(),
+                })),
+                w(AstStatement::If(
+                    w(AstExpression::Variable(caller_variables.clone(), value)),
+                    Vec::new(),
+                    None,
+                )),
+                w(AstStatement::Return(None)),
+            ],
+        );
+        let versions = [
+            (caller, AstFunctionVersion(1)),
+            (callee, AstFunctionVersion(1)),
+        ];
+
+        analyze_returns(&mut ast, &versions, &[vec![callee], vec![caller]]).unwrap();
+
+        function(&ast, callee, |callee| {
+            assert_eq!(callee.return_type, AstValueType::Int32);
+            assert!(matches!(
+                &callee.body[1].item,
+                AstStatement::Return(Some(Wrapped { item: AstExpression::Variable(_, x), .. })) if *x == result
+            ));
+        });
+        function(&ast, caller, |caller| {
+            // the value of the call is returned, so the caller returns a value too
+            assert_eq!(caller.return_type, AstValueType::Int32);
+            assert!(matches!(
+                &caller.body[0].item,
+                AstStatement::Assignment(
+                    Wrapped { item: AstExpression::Variable(_, x), .. },
+                    Wrapped { item: AstExpression::Call(AstCall::Function { target, .. }), .. },
+                ) if *x == value && *target == callee
+            ));
+            assert!(matches!(
+                &caller.body[2].item,
+                AstStatement::Return(Some(Wrapped { item: AstExpression::Variable(_, x), .. })) if *x == value
+            ));
+        });
+    }
+
+    #[test]
+    fn test_analyze_returns_unknown_callee() {
+        let mut ast = Ast::new();
+        let caller = ast.insert_test_function(0x1000, Vec::new());
+        let (variables, value) = rax_variable(&ast, caller, 1, AstValueType::Int32);
+        set_body(
+            &ast,
+            caller,
+            vec![
+                w(AstStatement::Call(AstCall::Unknown(
+                    "puts".to_string(),
+                    Vec::new(),
+                ))),
+                w(AstStatement::If(
+                    w(AstExpression::Variable(variables, value)),
+                    Vec::new(),
+                    None,
+                )),
+            ],
+        );
+
+        analyze_returns(&mut ast, &[(caller, AstFunctionVersion(1))], &[]).unwrap();
+
+        function(&ast, caller, |caller| {
+            assert!(matches!(
+                &caller.body[0].item,
+                AstStatement::Assignment(
+                    Wrapped { item: AstExpression::Variable(_, x), .. },
+                    Wrapped { item: AstExpression::Call(AstCall::Unknown(name, _)), .. },
+                ) if *x == value && name == "puts"
+            ));
+        });
+    }
+
+    #[test]
+    fn test_analyze_returns_keeps_known_type() {
+        let mut ast = Ast::new();
+        let returning = ast.insert_test_function(0x1000, Vec::new());
+        let silent = ast.insert_test_function(0x2000, Vec::new());
+        let (variables, value) = rax_variable(&ast, returning, 1, AstValueType::Int32);
+        set_body(
+            &ast,
+            returning,
+            vec![w(AstStatement::Return(Some(w(AstExpression::Variable(
+                variables, value,
+            )))))],
+        );
+        set_body(&ast, silent, vec![w(AstStatement::Return(None))]);
+        for function_id in [returning, silent] {
+            ast.functions
+                .write()
+                .unwrap()
+                .get_mut(&function_id)
+                .unwrap()
+                .get_mut(&AstFunctionVersion(1))
+                .unwrap()
+                .return_type = AstValueType::Int32;
+        }
+        let versions = [
+            (returning, AstFunctionVersion(1)),
+            (silent, AstFunctionVersion(1)),
+        ];
+
+        analyze_returns(&mut ast, &versions, &[vec![returning], vec![silent]]).unwrap();
+
+        function(&ast, returning, |x| {
+            assert_eq!(x.return_type, AstValueType::Int32)
+        });
+        // nothing is written before the `return`
+        function(&ast, silent, |x| {
+            assert_eq!(x.return_type, AstValueType::Void)
+        });
+    }
+}
@@ -0,0 +1,600 @@
+//! Turn stack frame accesses into named local variables, arrays and stack arguments.
+//!
+//! Variables found at the same slot of the stack frame are merged into one, named by the slot
+//! (`local_28`, `arg_8`). Elements of local arrays become `local_40[2]`, and the remaining
+//! `*(rbp - 0x28)` and `rbp - 0x40` expressions are rewritten into the slots.
+
+use crate::{
+    abstract_syntax_tree::{
+        ArcAstVariableMap, Ast, AstBinaryOperator, AstCall, AstExceptionHandler, AstExpression,
+        AstFunctionId, AstFunctionVersion, AstLiteral, AstOptimizationKind, AstStatement,
+        AstValueType, AstVariable, AstVariableId, Wrapped,
+    },
+    ir::{
+        analyze::{IrFunction, StackFrame, StackPointerState, StackSlot, StackSlotKind},
+        data::IrData,
+    },
+    prelude::DecompileError,
+};
+use hashbrown::HashMap;
+use iceball::MachineArchitecture;
+
+pub(super) fn analyze_stack_frames(
+    ast: &mut Ast,
+    versions: &[(AstFunctionId, AstFunctionVersion)],
+) -> Result<(), DecompileError> {
+    // bytes each function pops off the stack when returning, by entry address
+    let callee_cleanup: HashMap<u64, u64> = {
+        let functions = ast.functions.read().unwrap();
+        ast.function_versions
+            .iter()
+            .filter_map(|(function_id, version)| {
+                let function = functions.get(function_id)?.get(version)?;
+                let ir_function = &function.origin_ir;
+                Some((
+                    ir_function.get_entry_address()?,
+                    ir_function.get_callee_cleanup()?,
+                ))
+            })
+            .collect()
+    };
+
+    for (function_id, function_version) in versions.iter() {
+        let ir_function;
+        let mut body;
+        let variables;
+        {
+            let mut functions = ast.functions.write().unwrap();
+            let Some(function) = functions
+                .get_mut(function_id)
+                .and_then(|x| x.get_mut(function_version))
+            else {
+                continue;
+            };
+            // stack accesses are found in variables and expressions generated from ir
+            if !function
+                .processed_optimizations
+                .contains(&AstOptimizationKind::IrAnalyzation)
+                || function
+                    .processed_optimizations
+                    .contains(&AstOptimizationKind::StackFrameAnalyzation)
+            {
+                continue;
+            }
+            body = std::mem::take(&mut function.body);
+            variables = function.variables.clone();
+            ir_function = function.origin_ir.clone();
+        }
+        let Some(architecture) = ast.architecture.or_else(|| architecture_of(&ir_function)) else {
+            let mut functions = ast.functions.write().unwrap();
+            if let Some(function) = functions
+                .get_mut(function_id)
+                .and_then(|x| x.get_mut(function_version))
+            {
+                function.body = body;
+            }
+            continue;
+        };
+        let frame = StackFrame::new(&ir_function, architecture, |address| {
+            callee_cleanup.get(&address).copied()
+        });
+        let ir_indices: HashMap<u64, u32> = ir_function
+            .get_ir()
+            .iter()
+            .enumerate()
+            .map(|(i, ir)| (ir.address.get_virtual_address(), i as u32))
+            .collect();
+
+        let mut rewriter = Rewriter {
+            ast: &mut *ast,
+            function_id: *function_id,
+            frame: &frame,
+            ir_indices: &ir_indices,
+            variables: variables.clone(),
+            frame_registers: HashMap::new(),
+            slot_variables: HashMap::new(),
+            replacements: HashMap::new(),
+        };
+        let members = rewriter.collect_variables();
+        rewriter.merge_variables(members);
+        rewriter.rewrite_statements(&mut body, None);
+        rewriter.add_created_variables();
+        let replacements = std::mem::take(&mut rewriter.replacements);
+        super::opt_utils::for_each_declaration(&mut body, &mut |variable| {
+            if let Some(Replacement::Variable(var_id)) = replacements.get(&variable.id)
+                && let Some(merged) = variables.read().unwrap().get(var_id)
+            {
+                *variable = merged.clone();
+            }
+        });
+
+        let mut functions = ast.functions.write().unwrap();
+        let function = functions
+            .get_mut(function_id)
+            .and_then(|x| x.get_mut(function_version))
+            .unwrap();
+        function.body = body;
+        function
+            .processed_optimizations
+            .push(AstOptimizationKind::StackFrameAnalyzation);
+    }
+    Ok(())
+}
+
+/// Guesses the architecture from the instructions when the ast doesn't know the binary.
+fn architecture_of(function: &IrFunction) -> Option<MachineArchitecture> {
+    function
+        .get_instructions()
+        .iter()
+        .find_map(|x| match x.inner.statement {
+            Ok(iceball::Statement::X64(_)) => Some(MachineArchitecture::X64),
+            Ok(iceball::Statement::Arm(_)) => Some(MachineArchitecture::Arm64),
+            _ => None,
+        })
+}
+
+/// What a variable at a stack slot is replaced with
+enum Replacement {
+    /// The variable of the slot
+    Variable(AstVariableId),
+    /// An element of the array of the slot
+    Element(AstVariableId, i64),
+}
+
+struct Rewriter<'a> {
+    ast: &'a mut Ast,
+    function_id: AstFunctionId,
+    frame: &'a StackFrame,
+    /// Ir index of each instruction address
+    ir_indices: &'a HashMap<u64, u32>,
+    variables: ArcAstVariableMap,
+    /// Variables of the stack and frame pointers, true for the stack pointer
+    frame_registers: HashMap<AstVariableId, bool>,
+    /// Variable of each slot, by slot index
+    slot_variables: HashMap<usize, AstVariableId>,
+    replacements: HashMap<AstVariableId, Replacement>,
+}
+
+impl Rewriter<'_> {
+    /// Finds the slot of each variable, and the variables of the stack and frame pointers.
+    ///
+    /// ### Returns
+    /// - `HashMap<usize, Vec<(AstVariableId, isize)>>` - variables and their offsets, by slot
+    fn collect_variables(&mut self) -> HashMap<usize, Vec<(AstVariableId, isize)>> {
+        let variables = self.variables.read().unwrap();
+        let mut members: HashMap<usize, Vec<(AstVariableId, isize)>> = HashMap::new();
+        for variable in variables.values() {
+            let Some(data_accesses) = variable.data_access_ir.as_ref() else {
+                continue;
+            };
+            let mut location = None;
+            for (position, accesses) in data_accesses.iter() {
+                let state = self.frame.state_at(position);
+                for access in accesses.iter() {
+                    let current = match access.location().as_ref() {
+                        IrData::Register(register) if register.is_sp() || register.is_bp() => {
+                            self.frame_registers.insert(variable.id, register.is_sp());
+                            None
+                        }
+                        IrData::Dereference(address) if !address.is_sp() => {
+                            match self.frame.frame_address(address, state) {
+                                Some((offset, None)) => {
+                                    self.frame.slot_of(offset).map(|slot| (slot, offset))
+                                }
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    };
+                    // every access has to be at the same offset
+                    location = match (location, current) {
+                        (None, Some(current)) => Some(Some(current)),
+                        (Some(Some(known)), Some(current)) if known == current => location,
+                        _ => Some(None),
+                    };
+                }
+            }
+            if let Some(Some((slot, offset))) = location {
+                members.entry(slot).or_default().push((variable.id, offset));
+            }
+        }
+        members
+    }
+
+    /// Merges the variables of each slot into the one with the smallest id, named and typed by
+    /// the slot. Variables of array elements are merged into the array.
+    fn merge_variables(&mut self, members: HashMap<usize, Vec<(AstVariableId, isize)>>) {
+        let mut variables = self.variables.write().unwrap();
+        let slot_list = self.frame.get_slots();
+        for (slot_index, found) in members {
+            let slot = &slot_list[slot_index];
+            let found: Vec<(AstVariableId, Option<i64>)> = found
+                .into_iter()
+                .filter_map(|(var_id, offset)| {
+                    let distance = offset - slot.offset;
+                    let index = match slot.kind {
+                        StackSlotKind::Array { element_size } => (distance % element_size as isize
+                            == 0)
+                            .then_some(Some((distance / element_size as isize) as i64))?,
+                        _ => (distance == 0).then_some(None)?,
+                    };
+                    Some((var_id, index))
+                })
+                .collect();
+            let Some(target) = found.iter().map(|(var_id, _)| *var_id).min() else {
+                continue;
+            };
+            self.slot_variables.insert(slot_index, target);
+            let mut merged = Vec::new();
+            for (var_id, index) in found {
+                let replacement = match index {
+                    Some(index) => Replacement::Element(target, index),
+                    None => Replacement::Variable(target),
+                };
+                self.replacements.insert(var_id, replacement);
+                if var_id != target
+                    && let Some(variable) = variables.remove(&var_id)
+                {
+                    merged.push(variable);
+                }
+            }
+            merged.sort_unstable_by_key(|x| x.id);
+
+            let variable = variables.get_mut(&target).unwrap();
+            for other in merged {
+                if variable.var_type == AstValueType::Unknown {
+                    variable.var_type = other.var_type;
+                }
+                let (Some(into), Some(from)) =
+                    (variable.data_access_ir.as_mut(), other.data_access_ir)
+                else {
+                    continue;
+                };
+                for (position, accesses) in from.iter() {
+                    match into.get_mut(position) {
+                        Some(known) => known.extend(accesses.iter().cloned()),
+                        None => {
+                            into.insert(position, accesses.clone());
+                        }
+                    }
+                }
+            }
+            name_variable(variable, slot);
+        }
+    }
+
+    /// Adds the variables created for slots only accessed through expressions.
+    fn add_created_variables(&mut self) {
+        let mut variables = self.variables.write().unwrap();
+        let slot_list = self.frame.get_slots();
+        for (slot_index, var_id) in self.slot_variables.iter() {
+            if variables.contains_key(var_id) {
+                continue;
+            }
+            let mut variable = AstVariable {
+                name: None,
+                id: *var_id,
+                var_type: AstValueType::Unknown,
+                const_value: None,
+                data_access_ir: None,
+                offset: None,
+            };
+            name_variable(&mut variable, &slot_list[*slot_index]);
+            variables.insert(*var_id, variable);
+        }
+    }
+
+    /// Returns the variable of the slot, creating it on the first access.
+    fn slot_variable(&mut self, slot_index: usize) -> AstVariableId {
+        if let Some(var_id) = self.slot_variables.get(&slot_index) {
+            return *var_id;
+        }
+        let var_id = self.ast.new_variable_id(&self.function_id);
+        self.slot_variables.insert(slot_index, var_id);
+        var_id
+    }
+
+    fn rewrite_statements(
+        &mut self,
+        stmts: &mut [Wrapped<AstStatement>],
+        state: Option<StackPointerState>,
+    ) {
+        for stmt in stmts.iter_mut() {
+            let state = self.state_of(stmt.origin.map(|x| x.start)).or(state);
+            match &mut stmt.item {
+                AstStatement::Declaration(_, Some(value)) => self.rewrite_expression(value, state),
+                AstStatement::Assignment(lhs, rhs) => {
+                    // moving the stack pointer is not an address of a variable
+                    let moves_frame = matches!(
+                        &lhs.item,
+                        AstExpression::Variable(_, var_id)
+                            if self.frame_registers.contains_key(var_id)
+                    );
+                    self.rewrite_expression(lhs, state);
+                    if !moves_frame {
+                        self.rewrite_expression(rhs, state);
+                    }
+                }
+                AstStatement::If(condition, branch_true, branch_false) => {
+                    self.rewrite_expression(condition, state);
+                    self.rewrite_statements(branch_true, state);
+                    if let Some(branch_false) = branch_false {
+                        self.rewrite_statements(branch_false, state);
+                    }
+                }
+                AstStatement::While(condition, body) => {
+                    self.rewrite_expression(condition, state);
+                    self.rewrite_statements(body, state);
+                }
+                AstStatement::For(init, condition, update, body) => {
+                    self.rewrite_statements(std::slice::from_mut(&mut **init), state);
+                    self.rewrite_expression(condition, state);
+                    self.rewrite_statements(std::slice::from_mut(&mut **update), state);
+                    self.rewrite_statements(body, state);
+                }
+                AstStatement::Return(Some(value)) => self.rewrite_expression(value, state),
+                AstStatement::Call(call) => self.rewrite_call(call, state),
+                AstStatement::Block(body) => self.rewrite_statements(body, state),
+                AstStatement::DoWhile(condition, body) => {
+                    self.rewrite_statements(body, state);
+                    self.rewrite_expression(condition, state);
+                }
+                AstStatement::Switch(value, cases, default) => {
+                    self.rewrite_expression(value, state);
+                    for (_, body) in cases.iter_mut() {
+                        self.rewrite_statements(body, state);
+                    }
+                    if let Some(default) = default {
+                        self.rewrite_statements(default, state);
+                    }
+                }
+                AstStatement::Try(body, handlers) => {
+                    self.rewrite_statements(body, state);
+                    for (handler, body) in handlers.iter_mut() {
+                        if let AstExceptionHandler::Except(filter) = handler {
+                            self.rewrite_expression(filter, state);
+                        }
+                        self.rewrite_statements(body, state);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn rewrite_call(&mut self, call: &mut AstCall, state: Option<StackPointerState>) {
+        match call {
+            AstCall::Variable { args, .. }
+            | AstCall::Function { args, .. }
+            | AstCall::Unknown(_, args) => {
+                for arg in args.iter_mut() {
+                    self.rewrite_expression(arg, state);
+                }
+            }
+            AstCall::Builtin(..) => {}
+        }
+    }
+
+    fn rewrite_expression(
+        &mut self,
+        expression: &mut Wrapped<AstExpression>,
+        state: Option<StackPointerState>,
+    ) {
+        let state = self.state_of(expression.origin.map(|x| x.start)).or(state);
+        let rewritten = match &expression.item {
+            AstExpression::Variable(_, var_id) => match self.replacements.get(var_id) {
+                Some(Replacement::Variable(target)) => Some(self.variable(*target)),
+                Some(Replacement::Element(array, index)) => Some(AstExpression::ArrayAccess(
+                    Box::new(self.wrap(self.variable(*array), expression)),
+                    Box::new(
+                        self.wrap(AstExpression::Literal(AstLiteral::Int(*index)), expression),
+                    ),
+                )),
+                None => None,
+            },
+            // pushes and pops save registers, not variables
+            AstExpression::Deref(address)
+                if matches!(address.item, AstExpression::Variable(..)) =>
+            {
+                None
+            }
+            AstExpression::Deref(address) => state.and_then(|state| {
+                let (offset, index) = self.split_address(address, state)?;
+                self.access(offset, index, expression)
+            }),
+            AstExpression::BinaryOp(AstBinaryOperator::Add | AstBinaryOperator::Sub, _, _) => state
+                .and_then(|state| {
+                    let (offset, index) = self.split_address(expression, state)?;
+                    let access = self.access(offset, index, expression)?;
+                    Some(match access {
+                        // arrays are used as the address of the first element
+                        AstExpression::ArrayAccess(array, index)
+                            if matches!(index.item, AstExpression::Literal(AstLiteral::Int(0))) =>
+                        {
+                            array.item
+                        }
+                        access => AstExpression::AddressOf(Box::new(self.wrap(access, expression))),
+                    })
+                }),
+            _ => None,
+        };
+        if let Some(rewritten) = rewritten {
+            expression.item = rewritten;
+            return;
+        }
+        match &mut expression.item {
+            AstExpression::UnaryOp(_, arg)
+            | AstExpression::Deref(arg)
+            | AstExpression::AddressOf(arg)
+            | AstExpression::Cast(_, arg)
+            | AstExpression::MemberAccess(arg, _) => self.rewrite_expression(arg, state),
+            AstExpression::BinaryOp(_, a, b) | AstExpression::ArrayAccess(a, b) => {
+                self.rewrite_expression(a, state);
+                self.rewrite_expression(b, state);
+            }
+            AstExpression::Ternary(condition, a, b) => {
+                self.rewrite_expression(condition, state);
+                self.rewrite_expression(a, state);
+                self.rewrite_expression(b, state);
+            }
+            AstExpression::Call(call) => self.rewrite_call(call, state),
+            _ => {}
+        }
+    }
+
+    /// Returns the access of the slot at the offset.
+    ///
+    /// ### Arguments
+    /// - `offset: isize` - offset from the stack pointer at the function entry
+    /// - `index: Option<Wrapped<AstExpression>>` - variable index of an array element
+    /// - `origin: &Wrapped<AstExpression>` - expression the access replaces
+    fn access(
+        &mut self,
+        offset: isize,
+        index: Option<Wrapped<AstExpression>>,
+        origin: &Wrapped<AstExpression>,
+    ) -> Option<AstExpression> {
+        let slot_index = self.frame.slot_of(offset)?;
+        let slot = self.frame.get_slots()[slot_index].clone();
+        let distance = offset - slot.offset;
+        match slot.kind {
+            StackSlotKind::Array { element_size } => {
+                if distance % element_size as isize != 0 {
+                    return None;
+                }
+                let element = AstExpression::Literal(AstLiteral::Int(
+                    (distance / element_size as isize) as i64,
+                ));
+                let index = match index {
+                    Some(index) if distance == 0 => index.item,
+                    Some(index) => AstExpression::BinaryOp(
+                        AstBinaryOperator::Add,
+                        Box::new(index),
+                        Box::new(self.wrap(element, origin)),
+                    ),
+                    None => element,
+                };
+                let array = self.slot_variable(slot_index);
+                Some(AstExpression::ArrayAccess(
+                    Box::new(self.wrap(self.variable(array), origin)),
+                    Box::new(self.wrap(index, origin)),
+                ))
+            }
+            _ if distance == 0 && index.is_none() => {
+                let var_id = self.slot_variable(slot_index);
+                Some(self.variable(var_id))
+            }
+            _ => None,
+        }
+    }
+
+    /// Splits `rbp - 0x40 + i * 4` into the offset from the entry stack pointer and the index.
+    fn split_address(
+        &self,
+        address: &Wrapped<AstExpression>,
+        state: StackPointerState,
+    ) -> Option<(isize, Option<Wrapped<AstExpression>>)> {
+        match &address.item {
+            AstExpression::Variable(_, var_id) => {
+                let is_sp = self.frame_registers.get(var_id)?;
+                Some((if *is_sp { state.sp? } else { state.bp? }, None))
+            }
+            AstExpression::Cast(_, inner) => self.split_address(inner, state),
+            AstExpression::BinaryOp(AstBinaryOperator::Sub, a, b) => {
+                let (offset, index) = self.split_address(a, state)?;
+                Some((offset.checked_sub(literal(b)?)?, index))
+            }
+            AstExpression::BinaryOp(AstBinaryOperator::Add, a, b) => {
+                for (base, other) in [(a, b), (b, a)] {
+                    let Some((offset, index)) = self.split_address(base, state) else {
+                        continue;
+                    };
+                    if let Some(value) = literal(other) {
+                        return Some((offset.checked_add(value)?, index));
+                    }
+                    if index.is_none() {
+                        return Some((offset, Some(scaled_index(other)?)));
+                    }
+                }
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn state_of(&self, address: Option<u64>) -> Option<StackPointerState> {
+        let ir_index = self.ir_indices.get(&address?)?;
+        Some(self.frame.instruction_state(*ir_index))
+    }
+
+    fn variable(&self, var_id: AstVariableId) -> AstExpression {
+        AstExpression::Variable(self.variables.clone(), var_id)
+    }
+
+    fn wrap(&self, item: AstExpression, origin: &Wrapped<AstExpression>) -> Wrapped<AstExpression> {
+        Wrapped {
+            item,
+            comment: None,
+            origin: origin.origin,
+        }
+    }
+}
+
+/// Names the variable by its slot, and gives it the size of the slot.
+fn name_variable(variable: &mut AstVariable, slot: &StackSlot) {
+    if variable.name.is_none() {
+        variable.name = Some(slot.name());
+    }
+    match slot.kind {
+        StackSlotKind::Array { element_size } => {
+            if !matches!(variable.var_type, AstValueType::Array(..)) {
+                let element = sized_int(element_size).unwrap_or(AstValueType::Int8);
+                let length = slot.size / element_size.max(1);
+                variable.var_type = AstValueType::Array(Box::new(element), length);
+            }
+        }
+        _ => {
+            if matches!(variable.var_type, AstValueType::Unknown | AstValueType::Int)
+                && let Some(sized) = sized_int(slot.size)
+            {
+                variable.var_type = sized;
+            }
+        }
+    }
+}
+
+fn sized_int(size: usize) -> Option<AstValueType> {
+    match size {
+        1 => Some(AstValueType::Int8),
+        2 => Some(AstValueType::Int16),
+        4 => Some(AstValueType::Int32),
+        8 => Some(AstValueType::Int64),
+        _ => None,
+    }
+}
+
+fn literal(expression: &Wrapped<AstExpression>) -> Option<isize> {
+    match &expression.item {
+        AstExpression::Literal(AstLiteral::Int(x)) => Some(*x as isize),
+        AstExpression::Literal(AstLiteral::UInt(x)) => Some(*x as i64 as isize),
+        AstExpression::Cast(_, inner) => literal(inner),
+        _ => None,
+    }
+}
+
+/// Turns `i * 4` into the index `i` of 4 byte elements.
+fn scaled_index(expression: &Wrapped<AstExpression>) -> Option<Wrapped<AstExpression>> {
+    match &expression.item {
+        AstExpression::BinaryOp(AstBinaryOperator::Mul, a, b) => {
+            if literal(b).is_some() {
+                Some((**a).clone())
+            } else if literal(a).is_some() {
+                Some((**b).clone())
+            } else {
+                None
+            }
+        }
+        AstExpression::Literal(_) => None,
+        _ => Some(expression.clone()),
+    }
+}
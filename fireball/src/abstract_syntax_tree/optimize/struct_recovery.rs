@@ -0,0 +1,646 @@
+//! Recover struct types from the constant offsets accessed through pointer variables.
+//!
+//! `*(p + 0x18)` and `*(p + 0x20)` make `p` a pointer to a struct with fields at `0x18` and
+//! `0x20`. Variables passed to a function as an argument share the layout with the parameter
+//! of the callee, so accesses in either function add fields to the same struct. Fields overlapping
+//! the next one are cut at its offset. The accesses are rewritten into `p->field_18`.
+
+use crate::{
+    abstract_syntax_tree::{
+        Ast, AstBinaryOperator, AstCall, AstExceptionHandler, AstExpression, AstFunctionId,
+        AstFunctionVersion, AstLiteral, AstOptimizationKind, AstParameter, AstStatement,
+        AstValueType, AstVariable, AstVariableId, Wrapped,
+    },
+    prelude::DecompileError,
+};
+use either::Either;
+use hashbrown::HashMap;
+use std::collections::BTreeMap;
+
+/// Offsets accessed through a variable larger than this are not treated as fields
+const MAX_FIELD_OFFSET: u64 = 0x10000;
+
+/// Fields accessed through a pointer, by offset
+type Fields = BTreeMap<u64, AstValueType>;
+
+pub(super) fn recover_structs(
+    ast: &mut Ast,
+    versions: &[(AstFunctionId, AstFunctionVersion)],
+) -> Result<(), DecompileError> {
+    /* Collect accessed offsets and argument passing */
+    let parameters: HashMap<AstFunctionId, Vec<AstParameter>> = {
+        let functions = ast.functions.read().unwrap();
+        functions
+            .iter()
+            .filter_map(|(function_id, version_map)| {
+                let version = versions
+                    .iter()
+                    .find(|(id, _)| id == function_id)
+                    .map(|(_, version)| version)
+                    .or_else(|| ast.function_versions.get(function_id))?;
+                let function = version_map.get(version)?;
+                Some((*function_id, function.parameters.clone()))
+            })
+            .collect()
+    };
+    let mut accesses: HashMap<AstVariableId, Fields> = HashMap::new();
+    let mut passed: Vec<(AstVariableId, AstVariableId)> = Vec::new();
+    for (function_id, function_version) in versions.iter() {
+        let mut body;
+        let variables;
+        {
+            let mut functions = ast.functions.write().unwrap();
+            let Some(function) = functions
+                .get_mut(function_id)
+                .and_then(|x| x.get_mut(function_version))
+            else {
+                continue;
+            };
+            body = std::mem::take(&mut function.body);
+            variables = function.variables.clone();
+        }
+        let variables = variables.read().unwrap();
+        let types = variable_types(&variables);
+        visit_statements(&mut body, &types, &mut |expression, hint| {
+            if let AstExpression::Call(AstCall::Function { target, args }) = &expression.item {
+                let Some(parameters) = parameters.get(target) else {
+                    return;
+                };
+                for (arg, parameter) in args.iter().zip(parameters.iter()) {
+                    if let (AstExpression::Variable(_, arg), Either::Left(parameter)) =
+                        (strip_casts(arg), &parameter.id)
+                    {
+                        passed.push((*arg, *parameter));
+                    }
+                }
+                return;
+            }
+            let AstExpression::Deref(address) = &expression.item else {
+                return;
+            };
+            let Some((var_id, offset)) = base_and_offset(address) else {
+                return;
+            };
+            let Some(variable) = variables.get(&var_id) else {
+                return;
+            };
+            if variable.const_value.is_some() || !may_be_pointer(&variable.var_type) {
+                return;
+            }
+            let pointee = match &address.item {
+                AstExpression::Cast(AstValueType::Pointer(pointee), _) => Some(pointee.as_ref()),
+                _ => None,
+            };
+            let field_type = hint
+                .or(pointee)
+                .filter(|x| type_size(x).is_some())
+                .cloned()
+                .unwrap_or(AstValueType::Unknown);
+            let field = accesses
+                .entry(var_id)
+                .or_default()
+                .entry(offset)
+                .or_insert(AstValueType::Unknown);
+            if *field == AstValueType::Unknown {
+                *field = field_type;
+            }
+        });
+        // layouts recovered by an earlier run are kept
+        for (var_id, variable) in variables.iter() {
+            if let AstValueType::Pointer(inner) = &variable.var_type
+                && let AstValueType::Struct(_, fields) = inner.as_ref()
+            {
+                let known = accesses.entry(*var_id).or_default();
+                for field in fields {
+                    let Some(offset) = field.offset else {
+                        continue;
+                    };
+                    known.entry(offset).or_insert(field.var_type.clone());
+                }
+            }
+        }
+        drop(variables);
+
+        let mut functions = ast.functions.write().unwrap();
+        let function = functions
+            .get_mut(function_id)
+            .and_then(|x| x.get_mut(function_version))
+            .unwrap();
+        function.body = body;
+    }
+
+    /* Share layouts between arguments and parameters */
+    let mut groups = VariableGroups::default();
+    for var_id in accesses.keys() {
+        groups.find(*var_id);
+    }
+    for (arg, parameter) in passed.iter() {
+        groups.union(*arg, *parameter);
+    }
+    let mut layouts: HashMap<AstVariableId, Fields> = HashMap::new();
+    for (var_id, fields) in accesses.iter() {
+        let layout = layouts.entry(groups.find(*var_id)).or_default();
+        for (offset, field_type) in fields {
+            let field = layout.entry(*offset).or_insert(AstValueType::Unknown);
+            if *field == AstValueType::Unknown {
+                *field = field_type.clone();
+            }
+        }
+    }
+    // a single access at offset 0 is a plain pointer dereference
+    layouts.retain(|_, fields| fields.keys().any(|offset| *offset != 0));
+    for fields in layouts.values_mut() {
+        reconcile_overlaps(fields);
+    }
+    let struct_types: HashMap<AstVariableId, AstValueType> = layouts
+        .iter()
+        .map(|(root, fields)| (*root, struct_type(root, fields)))
+        .collect();
+
+    /* Retype variables and rewrite accesses into member accesses */
+    for (function_id, function_version) in versions.iter() {
+        let mut body;
+        let variables;
+        {
+            let mut functions = ast.functions.write().unwrap();
+            let Some(function) = functions
+                .get_mut(function_id)
+                .and_then(|x| x.get_mut(function_version))
+            else {
+                continue;
+            };
+            body = std::mem::take(&mut function.body);
+            variables = function.variables.clone();
+        }
+        let mut retyped: HashMap<AstVariableId, AstValueType> = HashMap::new();
+        {
+            let mut variables = variables.write().unwrap();
+            for (var_id, variable) in variables.iter_mut() {
+                if !may_be_pointer(&variable.var_type) {
+                    continue;
+                }
+                let root = groups.find(*var_id);
+                if let Some(struct_type) = struct_types.get(&root) {
+                    variable.var_type = AstValueType::Pointer(Box::new(struct_type.clone()));
+                    retyped.insert(*var_id, variable.var_type.clone());
+                }
+            }
+        }
+        let types = variable_types(&variables.read().unwrap());
+        visit_statements(&mut body, &types, &mut |expression, _| {
+            let AstExpression::Deref(address) = &expression.item else {
+                return;
+            };
+            let Some((var_id, offset)) = base_and_offset(address) else {
+                return;
+            };
+            if !retyped.contains_key(&var_id) {
+                return;
+            }
+            let Some(mut base) = find_variable(address, var_id) else {
+                return;
+            };
+            base.origin = expression.origin;
+            expression.item = AstExpression::MemberAccess(
+                Box::new(Wrapped {
+                    item: AstExpression::Deref(Box::new(base)),
+                    comment: None,
+                    origin: expression.origin,
+                }),
+                field_name(offset),
+            );
+        });
+        super::opt_utils::for_each_declaration(&mut body, &mut |variable| {
+            if let Some(var_type) = retyped.get(&variable.id) {
+                variable.var_type = var_type.clone();
+            }
+        });
+
+        let mut functions = ast.functions.write().unwrap();
+        let function = functions
+            .get_mut(function_id)
+            .and_then(|x| x.get_mut(function_version))
+            .unwrap();
+        function.body = body;
+        function
+            .processed_optimizations
+            .push(AstOptimizationKind::StructRecovery);
+    }
+    Ok(())
+}
+
+/// Union-find over variables sharing a struct layout
+#[derive(Default)]
+struct VariableGroups {
+    parent: HashMap<AstVariableId, AstVariableId>,
+}
+
+impl VariableGroups {
+    fn find(&mut self, var_id: AstVariableId) -> AstVariableId {
+        let parent = *self.parent.entry(var_id).or_insert(var_id);
+        if parent == var_id {
+            return var_id;
+        }
+        let root = self.find(parent);
+        self.parent.insert(var_id, root);
+        root
+    }
+
+    /// Joins two groups, the smaller id (the earliest function) names the struct.
+    fn union(&mut self, a: AstVariableId, b: AstVariableId) {
+        let a = self.find(a);
+        let b = self.find(b);
+        if a != b {
+            self.parent.insert(a.max(b), a.min(b));
+        }
+    }
+}
+
+fn struct_type(root: &AstVariableId, fields: &Fields) -> AstValueType {
+    let name = match root.parent {
+        Some(function_id) => format!("struct_{:x}_{}", function_id.address, root.index),
+        None => format!("struct_{}", root.index),
+    };
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(index, (offset, field_type))| AstVariable {
+            name: Some(field_name(*offset)),
+            id: AstVariableId {
+                index: index as u32,
+                parent: None,
+            },
+            var_type: field_type.clone(),
+            const_value: None,
+            data_access_ir: None,
+            offset: Some(*offset),
+        })
+        .collect();
+    AstValueType::Struct(name, fields)
+}
+
+/// Cuts fields overlapping the next one at its offset.
+///
+/// `*(int64_t*)p` and `*(int32_t*)(p + 4)` share the bytes 4..8, the field at 0 keeps the bytes
+/// before 4 as an integer of that size, or as a byte array, so every accessed offset stays a field.
+fn reconcile_overlaps(fields: &mut Fields) {
+    let offsets: Vec<u64> = fields.keys().copied().collect();
+    for pair in offsets.windows(2) {
+        let (offset, next) = (pair[0], pair[1]);
+        let field = fields.get_mut(&offset).unwrap();
+        if type_size(field).is_none_or(|size| offset + size <= next) {
+            continue;
+        }
+        *field = match next - offset {
+            1 => AstValueType::UInt8,
+            2 => AstValueType::UInt16,
+            4 => AstValueType::UInt32,
+            8 => AstValueType::UInt64,
+            size => AstValueType::Array(Box::new(AstValueType::UInt8), size as usize),
+        };
+    }
+}
+
+fn field_name(offset: u64) -> String {
+    format!("field_{offset:x}")
+}
+
+/// Returns the size of a type in bytes, if it is known.
+fn type_size(value_type: &AstValueType) -> Option<u64> {
+    match value_type {
+        AstValueType::Int8 | AstValueType::UInt8 | AstValueType::Char | AstValueType::Bool => {
+            Some(1)
+        }
+        AstValueType::Int16 | AstValueType::UInt16 => Some(2),
+        AstValueType::Int32 | AstValueType::UInt32 | AstValueType::Float => Some(4),
+        AstValueType::Int64 | AstValueType::UInt64 | AstValueType::Double => Some(8),
+        AstValueType::Pointer(_) => Some(8),
+        _ => None,
+    }
+}
+
+/// Returns whether a variable of the type may hold a pointer.
+fn may_be_pointer(value_type: &AstValueType) -> bool {
+    match value_type {
+        AstValueType::Pointer(inner) => matches!(
+            inner.as_ref(),
+            AstValueType::Void | AstValueType::Unknown | AstValueType::Struct(..)
+        ),
+        AstValueType::Unknown
+        | AstValueType::Int
+        | AstValueType::Int64
+        | AstValueType::UInt
+        | AstValueType::UInt64 => true,
+        _ => false,
+    }
+}
+
+fn strip_casts(expression: &AstExpression) -> &AstExpression {
+    match expression {
+        AstExpression::Cast(_, inner) => strip_casts(inner),
+        _ => expression,
+    }
+}
+
+/// Splits `p`, `p + 0x18` and `0x18 + p` into the variable and the offset.
+fn base_and_offset(address: &AstExpression) -> Option<(AstVariableId, u64)> {
+    let offset = |literal: &AstExpression| match literal {
+        AstExpression::Literal(AstLiteral::Int(x)) if *x >= 0 => Some(*x as u64),
+        AstExpression::Literal(AstLiteral::UInt(x)) => Some(*x),
+        _ => None,
+    };
+    let (var_id, offset) = match strip_casts(address) {
+        AstExpression::Variable(_, var_id) => (*var_id, 0),
+        AstExpression::BinaryOp(AstBinaryOperator::Add, a, b) => {
+            match (strip_casts(a), strip_casts(b)) {
+                (AstExpression::Variable(_, var_id), x)
+                | (x, AstExpression::Variable(_, var_id)) => (*var_id, offset(x)?),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    (offset < MAX_FIELD_OFFSET).then_some((var_id, offset))
+}
+
+/// Returns the variable expression of an address, with its variable map.
+fn find_variable(address: &AstExpression, var_id: AstVariableId) -> Option<Wrapped<AstExpression>> {
+    match address {
+        AstExpression::Variable(_, id) if *id == var_id => Some(Wrapped {
+            item: address.clone(),
+            comment: None,
+            origin: None,
+        }),
+        AstExpression::Cast(_, inner) => find_variable(inner, var_id),
+        AstExpression::BinaryOp(_, a, b) => {
+            find_variable(a, var_id).or_else(|| find_variable(b, var_id))
+        }
+        _ => None,
+    }
+}
+
+fn variable_types(
+    variables: &HashMap<AstVariableId, AstVariable>,
+) -> HashMap<AstVariableId, AstValueType> {
+    variables
+        .iter()
+        .map(|(id, variable)| (*id, variable.var_type.clone()))
+        .collect()
+}
+
+/// Visits every expression of the statements, innermost first.
+///
+/// The visitor receives the type of the value the expression is assigned to or cast to, which
+/// gives the size of dereferences.
+fn visit_statements(
+    stmts: &mut [Wrapped<AstStatement>],
+    types: &HashMap<AstVariableId, AstValueType>,
+    visitor: &mut impl FnMut(&mut Wrapped<AstExpression>, Option<&AstValueType>),
+) {
+    for stmt in stmts.iter_mut() {
+        match &mut stmt.item {
+            AstStatement::Declaration(variable, Some(value)) => {
+                let hint = variable.var_type.clone();
+                visit_expression(value, Some(&hint), types, visitor);
+            }
+            AstStatement::Assignment(lhs, rhs) => {
+                let lhs_type = expression_type(lhs, types);
+                let rhs_type = expression_type(rhs, types);
+                visit_expression(lhs, rhs_type.as_ref(), types, visitor);
+                visit_expression(rhs, lhs_type.as_ref(), types, visitor);
+            }
+            AstStatement::If(condition, branch_true, branch_false) => {
+                visit_expression(condition, None, types, visitor);
+                visit_statements(branch_true, types, visitor);
+                if let Some(branch_false) = branch_false {
+                    visit_statements(branch_false, types, visitor);
+                }
+            }
+            AstStatement::While(condition, body) | AstStatement::DoWhile(condition, body) => {
+                visit_expression(condition, None, types, visitor);
+                visit_statements(body, types, visitor);
+            }
+            AstStatement::For(init, condition, update, body) => {
+                visit_statements(std::slice::from_mut(&mut **init), types, visitor);
+                visit_expression(condition, None, types, visitor);
+                visit_statements(std::slice::from_mut(&mut **update), types, visitor);
+                visit_statements(body, types, visitor);
+            }
+            AstStatement::Return(Some(value)) => visit_expression(value, None, types, visitor),
+            AstStatement::Call(call) => {
+                visit_call_args(call, types, visitor);
+                // the visitor sees calls as expressions, for argument passing
+                let mut expression = Wrapped {
+                    item: AstExpression::Call(std::mem::replace(
+                        call,
+                        AstCall::Unknown(String::new(), Vec::new()),
+                    )),
+                    comment: None,
+                    origin: stmt.origin,
+                };
+                visitor(&mut expression, None);
+                if let AstExpression::Call(visited) = expression.item {
+                    *call = visited;
+                }
+            }
+            AstStatement::Block(body) => visit_statements(body, types, visitor),
+            AstStatement::Switch(value, cases, default) => {
+                visit_expression(value, None, types, visitor);
+                for (_, body) in cases.iter_mut() {
+                    visit_statements(body, types, visitor);
+                }
+                if let Some(default) = default {
+                    visit_statements(default, types, visitor);
+                }
+            }
+            AstStatement::Try(body, handlers) => {
+                visit_statements(body, types, visitor);
+                for (handler, body) in handlers.iter_mut() {
+                    if let AstExceptionHandler::Except(filter) = handler {
+                        visit_expression(filter, None, types, visitor);
+                    }
+                    visit_statements(body, types, visitor);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn visit_call_args(
+    call: &mut AstCall,
+    types: &HashMap<AstVariableId, AstValueType>,
+    visitor: &mut impl FnMut(&mut Wrapped<AstExpression>, Option<&AstValueType>),
+) {
+    match call {
+        AstCall::Variable { args, .. }
+        | AstCall::Function { args, .. }
+        | AstCall::Unknown(_, args) => {
+            for arg in args.iter_mut() {
+                visit_expression(arg, None, types, visitor);
+            }
+        }
+        AstCall::Builtin(..) => {}
+    }
+}
+
+fn visit_expression(
+    expression: &mut Wrapped<AstExpression>,
+    hint: Option<&AstValueType>,
+    types: &HashMap<AstVariableId, AstValueType>,
+    visitor: &mut impl FnMut(&mut Wrapped<AstExpression>, Option<&AstValueType>),
+) {
+    match &mut expression.item {
+        AstExpression::UnaryOp(_, arg) | AstExpression::AddressOf(arg) => {
+            visit_expression(arg, None, types, visitor)
+        }
+        AstExpression::Deref(arg) => visit_expression(arg, None, types, visitor),
+        AstExpression::Cast(cast_type, arg) => {
+            let cast_type = cast_type.clone();
+            visit_expression(arg, Some(&cast_type), types, visitor);
+        }
+        AstExpression::BinaryOp(_, a, b) | AstExpression::ArrayAccess(a, b) => {
+            visit_expression(a, None, types, visitor);
+            visit_expression(b, None, types, visitor);
+        }
+        AstExpression::MemberAccess(arg, _) => match &mut arg.item {
+            // already recovered `p->field`, the dereference is not an access at offset 0
+            AstExpression::Deref(base) => visit_expression(base, None, types, visitor),
+            _ => visit_expression(arg, None, types, visitor),
+        },
+        AstExpression::Ternary(condition, a, b) => {
+            visit_expression(condition, None, types, visitor);
+            visit_expression(a, hint, types, visitor);
+            visit_expression(b, hint, types, visitor);
+        }
+        AstExpression::Call(call) => visit_call_args(call, types, visitor),
+        _ => {}
+    }
+    visitor(expression, hint);
+}
+
+/// Returns the type of an expression, if it is a variable or a cast.
+fn expression_type(
+    expression: &AstExpression,
+    types: &HashMap<AstVariableId, AstValueType>,
+) -> Option<AstValueType> {
+    match expression {
+        AstExpression::Variable(_, var_id) => types.get(var_id).cloned(),
+        AstExpression::Cast(cast_type, _) => Some(cast_type.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn w<T>(item: T) -> Wrapped<T> {
+        Wrapped {
+            item,
+            comment: None,
+            origin: None,
+        }
+    }
+
+    /// Recovers the struct `p` points to from `*(p + offset) = value` of each (offset, type),
+    /// and returns the type of `p` with the printed body.
+    fn recover(accesses: &[(u64, AstValueType)]) -> (AstValueType, String) {
+        let mut ast = Ast::new();
+        let function_id = ast.insert_test_function(0x1000, Vec::new());
+        let variables = ast.functions.read().unwrap()[&function_id]
+            .get(&AstFunctionVersion(1))
+            .unwrap()
+            .variables
+            .clone();
+        let variable = |index: u32, var_type: AstValueType| AstVariable {
+            name: None,
+            id: AstVariableId {
+                index,
+                parent: Some(function_id),
+            },
+            var_type,
+            const_value: None,
+            data_access_ir: None,
+            offset: None,
+        };
+        let pointer = variable(0, AstValueType::Unknown);
+        let pointer_id = pointer.id;
+        variables.write().unwrap().insert(pointer_id, pointer);
+        let mut body = Vec::new();
+        for (index, (offset, value_type)) in accesses.iter().enumerate() {
+            let value = variable(index as u32 + 1, value_type.clone());
+            let value_id = value.id;
+            variables.write().unwrap().insert(value_id, value);
+            let base = w(AstExpression::Variable(variables.clone(), pointer_id));
+            let address = match offset {
+                0 => base,
+                offset => w(AstExpression::BinaryOp(
+                    AstBinaryOperator::Add,
+                    Box::new(base),
+                    Box::new(w(AstExpression::Literal(AstLiteral::UInt(*offset)))),
+                )),
+            };
+            body.push(w(AstStatement::Assignment(
+                w(AstExpression::Deref(Box::new(address))),
+                w(AstExpression::Variable(variables.clone(), value_id)),
+            )));
+        }
+        ast.functions
+            .write()
+            .unwrap()
+            .get_mut(&function_id)
+            .unwrap()
+            .get_mut(&AstFunctionVersion(1))
+            .unwrap()
+            .body = body;
+
+        recover_structs(&mut ast, &[(function_id, AstFunctionVersion(1))]).unwrap();
+        let var_type = variables.read().unwrap()[&pointer_id].var_type.clone();
+        (var_type, ast.print(None))
+    }
+
+    fn fields(var_type: &AstValueType) -> Vec<(u64, AstValueType)> {
+        let AstValueType::Pointer(inner) = var_type else {
+            panic!("not a pointer: {var_type:?}");
+        };
+        let AstValueType::Struct(_, fields) = inner.as_ref() else {
+            panic!("not a struct: {inner:?}");
+        };
+        fields
+            .iter()
+            .map(|x| (x.offset.unwrap(), x.var_type.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_recover_struct() {
+        let (var_type, printed) = recover(&[(0, AstValueType::Int64), (8, AstValueType::Int32)]);
+        assert_eq!(
+            fields(&var_type),
+            [(0, AstValueType::Int64), (8, AstValueType::Int32)]
+        );
+        assert!(printed.contains("->field_0 = "));
+        assert!(printed.contains("->field_8 = "));
+        assert!(printed.contains("field_8; // +0x8"));
+    }
+
+    #[test]
+    fn test_recover_struct_overlapping_fields() {
+        let (var_type, _) = recover(&[
+            (0, AstValueType::Int64),
+            (4, AstValueType::Int32),
+            (8, AstValueType::Int64),
+            (0xb, AstValueType::Int8),
+        ]);
+        assert_eq!(
+            fields(&var_type),
+            [
+                (0, AstValueType::UInt32),
+                (4, AstValueType::Int32),
+                (8, AstValueType::Array(Box::new(AstValueType::UInt8), 3)),
+                (0xb, AstValueType::Int8),
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use super::*;
 use hashbrown::{HashMap, HashSet};
+use std::collections::BTreeMap;
 
 fn format_descriptor(ir_index: u32, statement_index: Option<u8>) -> String {
     match statement_index {
@@ -93,10 +94,158 @@ fn push_indented_lines(output: &mut String, indent: &str, content: &str) {
     }
 }
 
+/// Collects the definitions of the structs a type refers to, by name.
+fn collect_struct_types(
+    value_type: &AstValueType,
+    structs: &mut BTreeMap<String, Vec<AstVariable>>,
+) {
+    match value_type {
+        AstValueType::Pointer(inner) | AstValueType::Array(inner, _) => {
+            collect_struct_types(inner, structs)
+        }
+        AstValueType::Struct(name, fields) => {
+            // self-referencing structs from debug information refer to themselves without fields
+            if structs
+                .get(name)
+                .is_some_and(|known| !known.is_empty() || fields.is_empty())
+            {
+                return;
+            }
+            structs.insert(name.clone(), fields.clone());
+            for field in fields {
+                collect_struct_types(&field.var_type, structs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the origin of each line a statement prints to.
+///
+/// ### Note
+/// - Only statements without a body are printed to count their lines, the lines of the others
+///   follow from the ones of their bodies.
+fn statement_origins(
+    stmt: &Wrapped<AstStatement>,
+    config: AstPrintConfig,
+) -> Vec<Option<AstAddressRange>> {
+    let body_origins = |body: &[Wrapped<AstStatement>]| -> Vec<_> {
+        body.iter()
+            .flat_map(|stmt| statement_origins(stmt, config))
+            .collect()
+    };
+    let mut origins = vec![stmt.origin];
+    // non-empty blocks end with a line closing them, empty blocks stay on the opening line
+    let push_block = |origins: &mut Vec<_>, body: Vec<_>| {
+        if !body.is_empty() {
+            origins.extend(body);
+            origins.push(stmt.origin);
+        }
+    };
+    match &stmt.item {
+        AstStatement::If(_, then_body, else_body) => {
+            let then_origins = body_origins(then_body);
+            let else_origins = else_body.as_deref().map(body_origins);
+            if then_origins.is_empty()
+                && !config.print_empty_statement
+                && else_origins.as_ref().is_none_or(Vec::is_empty)
+            {
+                return Vec::new();
+            }
+            push_block(&mut origins, then_origins);
+            if let Some(else_origins) = else_origins {
+                push_block(&mut origins, else_origins);
+            }
+        }
+        AstStatement::While(_, body)
+        | AstStatement::For(_, _, _, body)
+        | AstStatement::Block(body)
+            if body.is_empty() && !config.print_empty_statement =>
+        {
+            return Vec::new();
+        }
+        AstStatement::While(_, body)
+        | AstStatement::For(_, _, _, body)
+        | AstStatement::DoWhile(_, body)
+        | AstStatement::Block(body) => push_block(&mut origins, body_origins(body)),
+        AstStatement::Switch(_, cases, default) => {
+            for body in cases.iter().map(|(_, body)| body).chain(default.iter()) {
+                origins.push(stmt.origin);
+                origins.extend(body_origins(body));
+            }
+            origins.push(stmt.origin);
+        }
+        AstStatement::Try(body, handlers) => {
+            push_block(&mut origins, body_origins(body));
+            for (_, body) in handlers {
+                push_block(&mut origins, body_origins(body));
+            }
+        }
+        _ => {
+            let line_count = stmt.to_string_with_config(Some(config)).lines().count();
+            return vec![stmt.origin; line_count];
+        }
+    }
+    // comments are printed after the closing line
+    if let Some(comment) = &stmt.comment {
+        origins.extend(std::iter::repeat_n(
+            stmt.origin,
+            comment.matches('\n').count(),
+        ));
+    }
+    origins
+}
+
 impl Ast {
     pub fn print(&self, config: Option<AstPrintConfig>) -> String {
+        self.print_with_source_map(config).0
+    }
+
+    /// Prints the code, along with the instructions each printed line was decompiled from.
+    ///
+    /// ### Arguments
+    /// - `config: Option<AstPrintConfig>` - print configuration
+    ///
+    /// ### Returns
+    /// - `String` - the printed code, same as `print`
+    /// - `AstSourceMap` - origin of each printed line
+    pub fn print_with_source_map(&self, config: Option<AstPrintConfig>) -> (String, AstSourceMap) {
         let config = config.unwrap_or_default();
+        with_function_names(self.function_names(), || self.print_functions(config))
+    }
+
+    /// Returns the names calls and jumps to the functions of the ast are printed with.
+    ///
+    /// ### Returns
+    /// - `HashMap<u64, String>` - name of each named function: its annotated name, or else the
+    ///   name of its current version, or else its symbol
+    pub(crate) fn function_names(&self) -> HashMap<u64, String> {
+        let functions = self.functions.read().unwrap();
+        let mut names: HashMap<u64, String> = self
+            .function_versions
+            .iter()
+            .filter_map(|(id, version)| {
+                let name = functions
+                    .get(id)
+                    .and_then(|x| x.get(version))
+                    .and_then(|x| x.name.as_ref())
+                    .or_else(|| self.pre_defined_symbols.get(&id.address))?;
+                Some((id.address, name.clone()))
+            })
+            .collect();
+        names.extend(
+            self.annotations
+                .functions()
+                .filter_map(|(address, x)| Some((address, x.name.clone()?))),
+        );
+        names
+    }
+
+    fn print_functions(&self, config: AstPrintConfig) -> (String, AstSourceMap) {
         let mut output = String::new();
+        let mut origins = Vec::new();
+        // length of the output whose lines are in `origins`
+        let mut mapped = 0;
         let function_versions = &self.function_versions;
 
         // Functions
@@ -110,6 +259,33 @@ impl Ast {
                 .is_some_and(|function| function.name() == "main");
             (if is_main { 0u8 } else { 1u8 }, key.address)
         });
+
+        // Structs
+        let mut structs = BTreeMap::new();
+        for func_id in function_keys_sorted.iter() {
+            let version = function_versions.get(*func_id).unwrap();
+            let func = functions.get(*func_id).unwrap().get(version).unwrap();
+            for var in func.variables.read().unwrap().values() {
+                collect_struct_types(&var.var_type, &mut structs);
+            }
+        }
+        for (name, fields) in structs {
+            if fields.is_empty() {
+                output.push_str(&format!("struct {};\n\n", name));
+                continue;
+            }
+            output.push_str(&format!("struct {} {{\n", name));
+            for field in fields {
+                output.push_str(&format!(
+                    "  {} {}; // +{:#x}\n",
+                    field.var_type.to_string_with_config(Some(config)),
+                    field.name(),
+                    field.offset.unwrap_or(0)
+                ));
+            }
+            output.push_str("};\n\n");
+        }
+
         for func_id in function_keys_sorted {
             let version_map = functions.get(func_id).unwrap();
             let version = function_versions.get(func_id).unwrap();
@@ -117,7 +293,7 @@ impl Ast {
             output.push_str(&format!(
                 "{} {}(",
                 func.return_type.to_string_with_config(Some(config)),
-                func.name()
+                function_name(&func.id)
             ));
 
             // Parameters
@@ -259,13 +435,23 @@ impl Ast {
                 if content.is_empty() {
                     continue;
                 }
+                origins.extend(output[mapped..].matches('\n').map(|_| None));
                 push_indented_lines(&mut output, "    ", &content);
+                let mut stmt_origins = statement_origins(stmt, config);
+                let line_count = content.lines().count();
+                if stmt_origins.len() != line_count {
+                    // lines broken inside an expression, the whole statement is the best guess
+                    stmt_origins = vec![stmt.origin; line_count];
+                }
+                origins.extend(stmt_origins);
+                mapped = output.len();
             }
 
             output.push_str("}\n\n");
         }
 
         output.pop();
-        output
+        origins.resize(output.lines().count(), None);
+        (output, AstSourceMap::new(origins))
     }
 }
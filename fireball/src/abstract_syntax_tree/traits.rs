@@ -2,6 +2,7 @@ mod get_related_variables;
 mod print_with_config;
 
 use super::*;
+pub(crate) use print_with_config::{function_name, with_function_names};
 
 pub trait PrintWithConfig {
     fn to_string_with_config(&self, option: Option<AstPrintConfig>) -> String;
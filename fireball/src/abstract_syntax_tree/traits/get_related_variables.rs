@@ -138,6 +138,23 @@ impl GetRelatedVariables for AstStatement {
                 }
                 ret
             }
+            AstStatement::Try(body, handlers) => {
+                let mut ret = Vec::new();
+                for stmt in body {
+                    ret.extend(stmt.get_related_variables());
+                }
+                for (handler, handler_body) in handlers {
+                    if let AstExceptionHandler::Except(filter) = handler {
+                        for var_id in filter.get_related_variables() {
+                            ret.push((AstVariableAccessType::Read, var_id));
+                        }
+                    }
+                    for stmt in handler_body {
+                        ret.extend(stmt.get_related_variables());
+                    }
+                }
+                ret
+            }
             AstStatement::Goto(target) => target.get_related_variables(),
             AstStatement::Block(stmts) => stmts
                 .iter()
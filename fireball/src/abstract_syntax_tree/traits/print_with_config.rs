@@ -1,4 +1,31 @@
 use super::*;
+use hashbrown::HashMap;
+use std::cell::RefCell;
+
+thread_local! {
+    /// Names the calls and jumps to the functions of the ast being printed are printed with
+    static FUNCTION_NAMES: RefCell<HashMap<u64, String>> = RefCell::new(HashMap::new());
+}
+
+/// Prints the calls and jumps to the functions by the given names while `print` runs, the other
+/// ones being printed by their default name.
+///
+/// ### Arguments
+/// - `names: HashMap<u64, String>` - names of the functions, by entry address
+/// - `print: impl FnOnce() -> T` - prints the code
+pub(crate) fn with_function_names<T>(names: HashMap<u64, String>, print: impl FnOnce() -> T) -> T {
+    let previous = FUNCTION_NAMES.replace(names);
+    let result = print();
+    FUNCTION_NAMES.set(previous);
+    result
+}
+
+/// Returns the name the function is printed with, see [`with_function_names`].
+pub(crate) fn function_name(target: &AstFunctionId) -> String {
+    FUNCTION_NAMES
+        .with_borrow(|names| names.get(&target.address).cloned())
+        .unwrap_or_else(|| target.get_default_name())
+}
 
 fn statement_body(stmts: &[Wrapped<AstStatement>], config: AstPrintConfig) -> Vec<String> {
     stmts
@@ -252,7 +279,7 @@ impl PrintWithConfig for AstStatement {
                     write!(f, ");")
                 }
                 AstCall::Function { target, args } => {
-                    write!(f, "{}(", target.get_default_name())?;
+                    write!(f, "{}(", function_name(target))?;
                     for (i, arg) in args.iter().enumerate() {
                         if i > 0 {
                             write!(f, ", ")?;
@@ -366,6 +393,32 @@ impl PrintWithConfig for AstStatement {
                 write_inline_block(f, body, config)?;
                 write!(f, " while ({});", cond.to_string_with_config(Some(config)))
             }
+            AstStatement::Try(body, handlers) => {
+                let structured = handlers
+                    .iter()
+                    .any(|(handler, _)| !matches!(handler, AstExceptionHandler::Catch(_)));
+                write!(f, "{} ", if structured { "__try" } else { "try" })?;
+                write_inline_block(f, body, config)?;
+                for (handler, handler_body) in handlers {
+                    match handler {
+                        AstExceptionHandler::Catch(types) => {
+                            let types: Vec<_> = types
+                                .iter()
+                                .map(|x| x.as_deref().unwrap_or("..."))
+                                .collect();
+                            write!(f, " catch ({}) ", types.join(" | "))?;
+                        }
+                        AstExceptionHandler::Except(filter) => write!(
+                            f,
+                            " __except ({}) ",
+                            filter.to_string_with_config(Some(config))
+                        )?,
+                        AstExceptionHandler::Finally => write!(f, " __finally ")?,
+                    }
+                    write_inline_block(f, handler_body, config)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -429,7 +482,7 @@ impl PrintWithConfig for AstExpression {
                     write!(f, ")")
                 }
                 AstCall::Function { target, args } => {
-                    write!(f, "{}(", target.get_default_name())?;
+                    write!(f, "{}(", function_name(target))?;
                     for (i, arg) in args.iter().enumerate() {
                         if i > 0 {
                             write!(f, ", ")?;
@@ -512,12 +565,20 @@ impl PrintWithConfig for AstExpression {
                     expression1.to_string_with_config(Some(config))
                 )
             }
-            AstExpression::MemberAccess(expression, member) => write!(
-                f,
-                "{}.{}",
-                render_prefixed_operand(expression, config),
-                member
-            ),
+            AstExpression::MemberAccess(expression, member) => match &expression.item {
+                AstExpression::Deref(pointer) => write!(
+                    f,
+                    "{}->{}",
+                    render_prefixed_operand(pointer, config),
+                    member
+                ),
+                _ => write!(
+                    f,
+                    "{}.{}",
+                    render_prefixed_operand(expression, config),
+                    member
+                ),
+            },
             AstExpression::Ternary(cond, true_expr, false_expr) => {
                 write!(
                     f,
@@ -680,7 +741,7 @@ impl PrintWithConfig for AstJumpTarget {
                 let var = var_map.get(var_id).unwrap();
                 write!(f, "{}", var.to_string_with_config(Some(config)))
             }
-            AstJumpTarget::Function { target } => write!(f, "{}", target.get_default_name()),
+            AstJumpTarget::Function { target } => write!(f, "{}", function_name(target)),
             AstJumpTarget::Unknown(name) => write!(f, "{}", name),
         }
     }
@@ -71,6 +71,18 @@ pub fn get_first_arg_undetectable_statement_index<'a>(
                     return Some(i);
                 }
             }
+            AstStatement::Try(body, handlers) => {
+                if get_first_arg_undetectable_statement_index(body.iter().rev()).is_some() {
+                    return Some(i);
+                }
+                for (_handler, handler_body) in handlers {
+                    if get_first_arg_undetectable_statement_index(handler_body.iter().rev())
+                        .is_some()
+                    {
+                        return Some(i);
+                    }
+                }
+            }
         }
     }
     None
@@ -0,0 +1,807 @@
+//! Module containing functions to convert ARM/AArch64 architecture instructions into IR
+#![allow(unused_imports)]
+
+mod a;
+mod a_generated;
+mod b;
+mod b_generated;
+mod c;
+mod c_generated;
+mod d_generated;
+mod e_generated;
+mod f_generated;
+mod g_generated;
+mod h_generated;
+mod i_generated;
+mod l;
+mod l_generated;
+mod m;
+mod m_generated;
+mod n;
+mod n_generated;
+mod o_generated;
+mod p_generated;
+mod pair;
+mod r;
+mod r_generated;
+mod s;
+mod s_generated;
+mod single;
+mod t;
+mod t_generated;
+mod u;
+mod u_generated;
+mod w_generated;
+mod x_generated;
+mod y_generated;
+mod z_generated;
+
+mod shortcuts {
+    #![allow(non_upper_case_globals, unused_imports)]
+
+    pub(in crate::arch) use crate::arch::x86_64::instruction_analyze::shortcuts::*;
+    use crate::utils::Aos;
+
+    /// sign extend `from` and store it into `to`
+    /// ```rust,ignore
+    /// let assignment = sign_extend(o2(), o1(), o1_size());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub(in crate::arch) fn sign_extend(
+        from: impl Into<Aos<IrData>>,
+        to: impl Into<Aos<IrData>>,
+        size: impl Into<IrAccessSize>,
+    ) -> IrStatement {
+        assign(u::sign_extend(from), to, size)
+    }
+}
+
+use crate::{core::Instruction, ir::statements::*};
+use iceball::Statement;
+
+/// Converts AArch64 assembly instructions into IR statements.
+///
+/// ### Arguments
+/// - `instruction: &Instruction` : AArch64 assembly instruction
+///
+/// ### Returns
+/// `Option<&'static [IrStatement]>` : IR statements corresponding to the AArch64 instruction
+/// or `None` if the instruction is not supported.
+pub fn create_ir_statement(instruction: &Instruction) -> Option<&'static [IrStatement]> {
+    let op = if let Ok(Statement::Arm(op)) = instruction.inner.statement {
+        op
+    } else {
+        return None;
+    };
+
+    use iceball::ArmStatement;
+    Some(match op {
+        ArmStatement::Abs => a_generated::abs(),
+        ArmStatement::Adc => a_generated::adc(),
+        ArmStatement::Adcs => a_generated::adcs(),
+        ArmStatement::Add => a_generated::add(),
+        ArmStatement::Addg => a_generated::addg(),
+        ArmStatement::Addhn => a_generated::addhn(),
+        ArmStatement::Addp => a_generated::addp(),
+        ArmStatement::Addpl => a_generated::addpl(),
+        ArmStatement::Adds => a_generated::adds(),
+        ArmStatement::Addv => a_generated::addv(),
+        ArmStatement::Addvl => a_generated::addvl(),
+        ArmStatement::Adr => a::adr(),
+        ArmStatement::Adrp => a::adrp(),
+        ArmStatement::Aesd => a_generated::aesd(),
+        ArmStatement::Aese => a_generated::aese(),
+        ArmStatement::Aesimc => a_generated::aesimc(),
+        ArmStatement::Aesmc => a_generated::aesmc(),
+        ArmStatement::And => a_generated::and(),
+        ArmStatement::Ands => a_generated::ands(),
+        ArmStatement::Andv => a_generated::andv(),
+        ArmStatement::Asr => a_generated::asr(),
+        ArmStatement::Asrd => a_generated::asrd(),
+        ArmStatement::Asrr => a_generated::asrr(),
+        ArmStatement::Asrv => a_generated::asrv(),
+        ArmStatement::Autda => a_generated::autda(),
+        ArmStatement::Autdb => a_generated::autdb(),
+        ArmStatement::Autia => a_generated::autia(),
+        ArmStatement::Autib => a_generated::autib(),
+        ArmStatement::Axflag => a_generated::axflag(),
+        ArmStatement::B => b_generated::b(),
+        ArmStatement::Bcax => b_generated::bcax(),
+        ArmStatement::Bcond => b::bcond(),
+        ArmStatement::Bfcvt => b_generated::bfcvt(),
+        ArmStatement::Bfcvtn => b_generated::bfcvtn(),
+        ArmStatement::Bfcvtnt => b_generated::bfcvtnt(),
+        ArmStatement::Bfdot => b_generated::bfdot(),
+        ArmStatement::Bfi => b::bfi(),
+        ArmStatement::Bfm => b_generated::bfm(),
+        ArmStatement::Bfmlal => b_generated::bfmlal(),
+        ArmStatement::Bfmlalb => b_generated::bfmlalb(),
+        ArmStatement::Bfmlalt => b_generated::bfmlalt(),
+        ArmStatement::Bfmmla => b_generated::bfmmla(),
+        ArmStatement::Bfxil => b::bfxil(),
+        ArmStatement::Bic => b_generated::bic(),
+        ArmStatement::Bics => b_generated::bics(),
+        ArmStatement::Bif => b_generated::bif(),
+        ArmStatement::Bit => b_generated::bit(),
+        ArmStatement::Bl => b::bl(),
+        ArmStatement::Blr => b::blr(),
+        ArmStatement::Blraa => b_generated::blraa(),
+        ArmStatement::Br => b_generated::br(),
+        ArmStatement::Braa => b_generated::braa(),
+        ArmStatement::Brk => b_generated::brk(),
+        ArmStatement::Brka => b_generated::brka(),
+        ArmStatement::Brkb => b_generated::brkb(),
+        ArmStatement::Brkn => b_generated::brkn(),
+        ArmStatement::Brkpa => b_generated::brkpa(),
+        ArmStatement::Brkpb => b_generated::brkpb(),
+        ArmStatement::Bsl => b_generated::bsl(),
+        ArmStatement::Bti => b_generated::bti(),
+        ArmStatement::Cas => c_generated::cas(),
+        ArmStatement::Casb => c_generated::casb(),
+        ArmStatement::Cash => c_generated::cash(),
+        ArmStatement::Casp => c_generated::casp(),
+        ArmStatement::Cbnz => c_generated::cbnz(),
+        ArmStatement::Cbz => c_generated::cbz(),
+        ArmStatement::Ccmn => c::ccmn(),
+        ArmStatement::Ccmp => c::ccmp(),
+        ArmStatement::Cfinv => c_generated::cfinv(),
+        ArmStatement::Cinc => c::cinc(),
+        ArmStatement::Cinv => c::cinv(),
+        ArmStatement::Clasta => c_generated::clasta(),
+        ArmStatement::Clastb => c_generated::clastb(),
+        ArmStatement::Clrex => c_generated::clrex(),
+        ArmStatement::Cls => c_generated::cls(),
+        ArmStatement::Clz => c_generated::clz(),
+        ArmStatement::Cmeq => c_generated::cmeq(),
+        ArmStatement::Cmge => c_generated::cmge(),
+        ArmStatement::Cmgt => c_generated::cmgt(),
+        ArmStatement::Cmhi => c_generated::cmhi(),
+        ArmStatement::Cmhs => c_generated::cmhs(),
+        ArmStatement::Cmle => c_generated::cmle(),
+        ArmStatement::Cmlt => c_generated::cmlt(),
+        ArmStatement::Cmn => c::cmn(),
+        ArmStatement::Cmp => c::cmp(),
+        ArmStatement::Cmpcc => c_generated::cmpcc(),
+        ArmStatement::Cmtst => c_generated::cmtst(),
+        ArmStatement::Cneg => c::cneg(),
+        ArmStatement::Cnot => c_generated::cnot(),
+        ArmStatement::Cnt => c_generated::cnt(),
+        ArmStatement::Cntb => c_generated::cntb(),
+        ArmStatement::Cntp => c_generated::cntp(),
+        ArmStatement::Compact => c_generated::compact(),
+        ArmStatement::Cpy => c_generated::cpy(),
+        ArmStatement::Crc32b => c_generated::crc32b(),
+        ArmStatement::Crc32cb => c_generated::crc32cb(),
+        ArmStatement::Csdb => c_generated::csdb(),
+        ArmStatement::Csel => c::csel(),
+        ArmStatement::Cset => c::cset(),
+        ArmStatement::Csetm => c::csetm(),
+        ArmStatement::Csinc => c::csinc(),
+        ArmStatement::Csinv => c::csinv(),
+        ArmStatement::Csneg => c::csneg(),
+        ArmStatement::Ctermeq => c_generated::ctermeq(),
+        ArmStatement::Dcps1 => d_generated::dcps1(),
+        ArmStatement::Dcps2 => d_generated::dcps2(),
+        ArmStatement::Dcps3 => d_generated::dcps3(),
+        ArmStatement::Decb => d_generated::decb(),
+        ArmStatement::Decd => d_generated::decd(),
+        ArmStatement::Decp => d_generated::decp(),
+        ArmStatement::Dgh => d_generated::dgh(),
+        ArmStatement::Dmb => d_generated::dmb(),
+        ArmStatement::Drps => d_generated::drps(),
+        ArmStatement::Dsb => d_generated::dsb(),
+        ArmStatement::Dup => d_generated::dup(),
+        ArmStatement::Dupm => d_generated::dupm(),
+        ArmStatement::Eon => e_generated::eon(),
+        ArmStatement::Eor => e_generated::eor(),
+        ArmStatement::Eor3 => e_generated::eor3(),
+        ArmStatement::Eorv => e_generated::eorv(),
+        ArmStatement::Eret => e_generated::eret(),
+        ArmStatement::Eretaa => e_generated::eretaa(),
+        ArmStatement::Esb => e_generated::esb(),
+        ArmStatement::Ext => e_generated::ext(),
+        ArmStatement::Extr => e_generated::extr(),
+        ArmStatement::Fabd => f_generated::fabd(),
+        ArmStatement::Fabs => f_generated::fabs(),
+        ArmStatement::Faccc => f_generated::faccc(),
+        ArmStatement::Facge => f_generated::facge(),
+        ArmStatement::Facgt => f_generated::facgt(),
+        ArmStatement::Fadd => f_generated::fadd(),
+        ArmStatement::Fadda => f_generated::fadda(),
+        ArmStatement::Faddp => f_generated::faddp(),
+        ArmStatement::Faddv => f_generated::faddv(),
+        ArmStatement::Fcadd => f_generated::fcadd(),
+        ArmStatement::Fccmp => f_generated::fccmp(),
+        ArmStatement::Fccmpe => f_generated::fccmpe(),
+        ArmStatement::Fcmcc => f_generated::fcmcc(),
+        ArmStatement::Fcmeq => f_generated::fcmeq(),
+        ArmStatement::Fcmge => f_generated::fcmge(),
+        ArmStatement::Fcmgt => f_generated::fcmgt(),
+        ArmStatement::Fcmla => f_generated::fcmla(),
+        ArmStatement::Fcmle => f_generated::fcmle(),
+        ArmStatement::Fcmlt => f_generated::fcmlt(),
+        ArmStatement::Fcmp => f_generated::fcmp(),
+        ArmStatement::Fcmpe => f_generated::fcmpe(),
+        ArmStatement::Fcpy => f_generated::fcpy(),
+        ArmStatement::Fcsel => f_generated::fcsel(),
+        ArmStatement::Fcvt => f_generated::fcvt(),
+        ArmStatement::Fcvtas => f_generated::fcvtas(),
+        ArmStatement::Fcvtau => f_generated::fcvtau(),
+        ArmStatement::Fcvtl => f_generated::fcvtl(),
+        ArmStatement::Fcvtms => f_generated::fcvtms(),
+        ArmStatement::Fcvtmu => f_generated::fcvtmu(),
+        ArmStatement::Fcvtn => f_generated::fcvtn(),
+        ArmStatement::Fcvtns => f_generated::fcvtns(),
+        ArmStatement::Fcvtnu => f_generated::fcvtnu(),
+        ArmStatement::Fcvtps => f_generated::fcvtps(),
+        ArmStatement::Fcvtpu => f_generated::fcvtpu(),
+        ArmStatement::Fcvtxn => f_generated::fcvtxn(),
+        ArmStatement::Fcvtzs => f_generated::fcvtzs(),
+        ArmStatement::Fcvtzu => f_generated::fcvtzu(),
+        ArmStatement::Fdiv => f_generated::fdiv(),
+        ArmStatement::Fdivr => f_generated::fdivr(),
+        ArmStatement::Fdup => f_generated::fdup(),
+        ArmStatement::Fexpa => f_generated::fexpa(),
+        ArmStatement::Fjcvtzs => f_generated::fjcvtzs(),
+        ArmStatement::Fmad => f_generated::fmad(),
+        ArmStatement::Fmadd => f_generated::fmadd(),
+        ArmStatement::Fmax => f_generated::fmax(),
+        ArmStatement::Fmaxnm => f_generated::fmaxnm(),
+        ArmStatement::Fmaxnmp => f_generated::fmaxnmp(),
+        ArmStatement::Fmaxnmv => f_generated::fmaxnmv(),
+        ArmStatement::Fmaxp => f_generated::fmaxp(),
+        ArmStatement::Fmaxv => f_generated::fmaxv(),
+        ArmStatement::Fmin => f_generated::fmin(),
+        ArmStatement::Fminnm => f_generated::fminnm(),
+        ArmStatement::Fminnmp => f_generated::fminnmp(),
+        ArmStatement::Fminnmv => f_generated::fminnmv(),
+        ArmStatement::Fminp => f_generated::fminp(),
+        ArmStatement::Fminv => f_generated::fminv(),
+        ArmStatement::Fmla => f_generated::fmla(),
+        ArmStatement::Fmlal => f_generated::fmlal(),
+        ArmStatement::Fmls => f_generated::fmls(),
+        ArmStatement::Fmlsl => f_generated::fmlsl(),
+        ArmStatement::Fmmla => f_generated::fmmla(),
+        ArmStatement::Fmov => f_generated::fmov(),
+        ArmStatement::Fmsb => f_generated::fmsb(),
+        ArmStatement::Fmsub => f_generated::fmsub(),
+        ArmStatement::Fmul => f_generated::fmul(),
+        ArmStatement::Fmulx => f_generated::fmulx(),
+        ArmStatement::Fneg => f_generated::fneg(),
+        ArmStatement::Fnmad => f_generated::fnmad(),
+        ArmStatement::Fnmadd => f_generated::fnmadd(),
+        ArmStatement::Fnmla => f_generated::fnmla(),
+        ArmStatement::Fnmls => f_generated::fnmls(),
+        ArmStatement::Fnmsb => f_generated::fnmsb(),
+        ArmStatement::Fnmsub => f_generated::fnmsub(),
+        ArmStatement::Fnmul => f_generated::fnmul(),
+        ArmStatement::Frecpe => f_generated::frecpe(),
+        ArmStatement::Frecps => f_generated::frecps(),
+        ArmStatement::Frecpx => f_generated::frecpx(),
+        ArmStatement::Frint32x => f_generated::frint32x(),
+        ArmStatement::Frint32z => f_generated::frint32z(),
+        ArmStatement::Frint64x => f_generated::frint64x(),
+        ArmStatement::Frint64z => f_generated::frint64z(),
+        ArmStatement::Frintr => f_generated::frintr(),
+        ArmStatement::Frinta => f_generated::frinta(),
+        ArmStatement::Frinti => f_generated::frinti(),
+        ArmStatement::Frintm => f_generated::frintm(),
+        ArmStatement::Frintn => f_generated::frintn(),
+        ArmStatement::Frintp => f_generated::frintp(),
+        ArmStatement::Frintx => f_generated::frintx(),
+        ArmStatement::Frintz => f_generated::frintz(),
+        ArmStatement::Frsqrte => f_generated::frsqrte(),
+        ArmStatement::Frsqrts => f_generated::frsqrts(),
+        ArmStatement::Fscale => f_generated::fscale(),
+        ArmStatement::Fsqrt => f_generated::fsqrt(),
+        ArmStatement::Fsub => f_generated::fsub(),
+        ArmStatement::Fsubr => f_generated::fsubr(),
+        ArmStatement::Ftmad => f_generated::ftmad(),
+        ArmStatement::Ftsmul => f_generated::ftsmul(),
+        ArmStatement::Ftssel => f_generated::ftssel(),
+        ArmStatement::Gmi => g_generated::gmi(),
+        ArmStatement::Hint => h_generated::hint(),
+        ArmStatement::Hlt => h_generated::hlt(),
+        ArmStatement::Hvc => h_generated::hvc(),
+        ArmStatement::Incb => i_generated::incb(),
+        ArmStatement::Incd => i_generated::incd(),
+        ArmStatement::Incp => i_generated::incp(),
+        ArmStatement::Index => i_generated::index(),
+        ArmStatement::Ins => i_generated::ins(),
+        ArmStatement::Insr => i_generated::insr(),
+        ArmStatement::Irg => i_generated::irg(),
+        ArmStatement::Isb => i_generated::isb(),
+        ArmStatement::Lasta => l_generated::lasta(),
+        ArmStatement::Lastb => l_generated::lastb(),
+        ArmStatement::Ld1 => l_generated::ld1(),
+        ArmStatement::Ld1b => l_generated::ld1b(),
+        ArmStatement::Ld1d => l_generated::ld1d(),
+        ArmStatement::Ld1h => l_generated::ld1h(),
+        ArmStatement::Ld1r => l_generated::ld1r(),
+        ArmStatement::Ld1rb => l_generated::ld1rb(),
+        ArmStatement::Ld1rd => l_generated::ld1rd(),
+        ArmStatement::Ld1rh => l_generated::ld1rh(),
+        ArmStatement::Ld1rob => l_generated::ld1rob(),
+        ArmStatement::Ld1rod => l_generated::ld1rod(),
+        ArmStatement::Ld1roh => l_generated::ld1roh(),
+        ArmStatement::Ld1row => l_generated::ld1row(),
+        ArmStatement::Ld1rqb => l_generated::ld1rqb(),
+        ArmStatement::Ld1rqd => l_generated::ld1rqd(),
+        ArmStatement::Ld1rqh => l_generated::ld1rqh(),
+        ArmStatement::Ld1rqw => l_generated::ld1rqw(),
+        ArmStatement::Ld1rsb => l_generated::ld1rsb(),
+        ArmStatement::Ld1rsh => l_generated::ld1rsh(),
+        ArmStatement::Ld1rsw => l_generated::ld1rsw(),
+        ArmStatement::Ld1rw => l_generated::ld1rw(),
+        ArmStatement::Ld1sb => l_generated::ld1sb(),
+        ArmStatement::Ld1sh => l_generated::ld1sh(),
+        ArmStatement::Ld1sw => l_generated::ld1sw(),
+        ArmStatement::Ld1w => l_generated::ld1w(),
+        ArmStatement::Ld2 => l_generated::ld2(),
+        ArmStatement::Ld2b => l_generated::ld2b(),
+        ArmStatement::Ld2d => l_generated::ld2d(),
+        ArmStatement::Ld2h => l_generated::ld2h(),
+        ArmStatement::Ld2r => l_generated::ld2r(),
+        ArmStatement::Ld2w => l_generated::ld2w(),
+        ArmStatement::Ld3 => l_generated::ld3(),
+        ArmStatement::Ld3b => l_generated::ld3b(),
+        ArmStatement::Ld3d => l_generated::ld3d(),
+        ArmStatement::Ld3h => l_generated::ld3h(),
+        ArmStatement::Ld3r => l_generated::ld3r(),
+        ArmStatement::Ld3w => l_generated::ld3w(),
+        ArmStatement::Ld4 => l_generated::ld4(),
+        ArmStatement::Ld4b => l_generated::ld4b(),
+        ArmStatement::Ld4d => l_generated::ld4d(),
+        ArmStatement::Ld4h => l_generated::ld4h(),
+        ArmStatement::Ld4r => l_generated::ld4r(),
+        ArmStatement::Ld4w => l_generated::ld4w(),
+        ArmStatement::Ldadd => l_generated::ldadd(),
+        ArmStatement::Ldaddb => l_generated::ldaddb(),
+        ArmStatement::Ldaddh => l_generated::ldaddh(),
+        ArmStatement::Ldapr => l_generated::ldapr(),
+        ArmStatement::Ldaprb => l_generated::ldaprb(),
+        ArmStatement::Ldaprh => l_generated::ldaprh(),
+        ArmStatement::Ldapur => l_generated::ldapur(),
+        ArmStatement::Ldapurb => l_generated::ldapurb(),
+        ArmStatement::Ldapurh => l_generated::ldapurh(),
+        ArmStatement::Ldapursb => l_generated::ldapursb(),
+        ArmStatement::Ldapursh => l_generated::ldapursh(),
+        ArmStatement::Ldapursw => l_generated::ldapursw(),
+        ArmStatement::Ldar => l_generated::ldar(),
+        ArmStatement::Ldarb => l_generated::ldarb(),
+        ArmStatement::Ldarh => l_generated::ldarh(),
+        ArmStatement::Ldaxp => l_generated::ldaxp(),
+        ArmStatement::Ldaxr => l_generated::ldaxr(),
+        ArmStatement::Ldaxrb => l_generated::ldaxrb(),
+        ArmStatement::Ldaxrh => l_generated::ldaxrh(),
+        ArmStatement::Ldclr => l_generated::ldclr(),
+        ArmStatement::Ldclrb => l_generated::ldclrb(),
+        ArmStatement::Ldclrh => l_generated::ldclrh(),
+        ArmStatement::Ldeor => l_generated::ldeor(),
+        ArmStatement::Ldeorb => l_generated::ldeorb(),
+        ArmStatement::Ldeorh => l_generated::ldeorh(),
+        ArmStatement::Ldff1b => l_generated::ldff1b(),
+        ArmStatement::Ldff1d => l_generated::ldff1d(),
+        ArmStatement::Ldff1h => l_generated::ldff1h(),
+        ArmStatement::Ldff1sb => l_generated::ldff1sb(),
+        ArmStatement::Ldff1sh => l_generated::ldff1sh(),
+        ArmStatement::Ldff1sw => l_generated::ldff1sw(),
+        ArmStatement::Ldff1w => l_generated::ldff1w(),
+        ArmStatement::Ldg => l_generated::ldg(),
+        ArmStatement::Ldgm => l_generated::ldgm(),
+        ArmStatement::Ldlar => l_generated::ldlar(),
+        ArmStatement::Ldlarb => l_generated::ldlarb(),
+        ArmStatement::Ldlarh => l_generated::ldlarh(),
+        ArmStatement::Ldnf1b => l_generated::ldnf1b(),
+        ArmStatement::Ldnf1d => l_generated::ldnf1d(),
+        ArmStatement::Ldnf1h => l_generated::ldnf1h(),
+        ArmStatement::Ldnf1sb => l_generated::ldnf1sb(),
+        ArmStatement::Ldnf1sh => l_generated::ldnf1sh(),
+        ArmStatement::Ldnf1sw => l_generated::ldnf1sw(),
+        ArmStatement::Ldnf1w => l_generated::ldnf1w(),
+        ArmStatement::Ldnp => l_generated::ldnp(),
+        ArmStatement::Ldnt1b => l_generated::ldnt1b(),
+        ArmStatement::Ldnt1d => l_generated::ldnt1d(),
+        ArmStatement::Ldnt1h => l_generated::ldnt1h(),
+        ArmStatement::Ldnt1w => l_generated::ldnt1w(),
+        ArmStatement::Ldp => l::ldp(instruction),
+        ArmStatement::Ldpsw => l::ldpsw(instruction),
+        ArmStatement::Ldr => l::ldr(instruction),
+        ArmStatement::Ldraa => l_generated::ldraa(),
+        ArmStatement::Ldrb => l::ldrb(instruction),
+        ArmStatement::Ldrh => l::ldrh(instruction),
+        ArmStatement::Ldrsb => l::ldrsb(instruction),
+        ArmStatement::Ldrsh => l::ldrsh(instruction),
+        ArmStatement::Ldrsw => l::ldrsw(instruction),
+        ArmStatement::Ldset => l_generated::ldset(),
+        ArmStatement::Ldsetb => l_generated::ldsetb(),
+        ArmStatement::Ldseth => l_generated::ldseth(),
+        ArmStatement::Ldsmax => l_generated::ldsmax(),
+        ArmStatement::Ldsmaxb => l_generated::ldsmaxb(),
+        ArmStatement::Ldsmaxh => l_generated::ldsmaxh(),
+        ArmStatement::Ldsmin => l_generated::ldsmin(),
+        ArmStatement::Ldsminb => l_generated::ldsminb(),
+        ArmStatement::Ldsminh => l_generated::ldsminh(),
+        ArmStatement::Ldtr => l_generated::ldtr(),
+        ArmStatement::Ldtrb => l_generated::ldtrb(),
+        ArmStatement::Ldtrh => l_generated::ldtrh(),
+        ArmStatement::Ldtrsb => l_generated::ldtrsb(),
+        ArmStatement::Ldtrsh => l_generated::ldtrsh(),
+        ArmStatement::Ldtrsw => l_generated::ldtrsw(),
+        ArmStatement::Ldumax => l_generated::ldumax(),
+        ArmStatement::Ldumaxb => l_generated::ldumaxb(),
+        ArmStatement::Ldumaxh => l_generated::ldumaxh(),
+        ArmStatement::Ldumin => l_generated::ldumin(),
+        ArmStatement::Lduminb => l_generated::lduminb(),
+        ArmStatement::Lduminh => l_generated::lduminh(),
+        ArmStatement::Ldur => l::ldur(instruction),
+        ArmStatement::Ldurb => l::ldurb(instruction),
+        ArmStatement::Ldurh => l::ldurh(instruction),
+        ArmStatement::Ldursb => l::ldursb(instruction),
+        ArmStatement::Ldursh => l::ldursh(instruction),
+        ArmStatement::Ldursw => l::ldursw(instruction),
+        ArmStatement::Ldxp => l_generated::ldxp(),
+        ArmStatement::Ldxr => l_generated::ldxr(),
+        ArmStatement::Ldxrb => l_generated::ldxrb(),
+        ArmStatement::Ldxrh => l_generated::ldxrh(),
+        ArmStatement::Lsl => l_generated::lsl(),
+        ArmStatement::Lslr => l_generated::lslr(),
+        ArmStatement::Lslv => l_generated::lslv(),
+        ArmStatement::Lsr => l_generated::lsr(),
+        ArmStatement::Lsrr => l_generated::lsrr(),
+        ArmStatement::Lsrv => l_generated::lsrv(),
+        ArmStatement::Mad => m_generated::mad(),
+        ArmStatement::Madd => m_generated::madd(),
+        ArmStatement::Mla => m_generated::mla(),
+        ArmStatement::Mls => m_generated::mls(),
+        ArmStatement::Mneg => m::mneg(),
+        ArmStatement::Mov => m::mov(),
+        ArmStatement::Movi => m_generated::movi(),
+        ArmStatement::Movk => m_generated::movk(),
+        ArmStatement::Movn => m_generated::movn(),
+        ArmStatement::Movprfx => m_generated::movprfx(),
+        ArmStatement::Movz => m_generated::movz(),
+        ArmStatement::Mrs => m_generated::mrs(),
+        ArmStatement::Msb => m_generated::msb(),
+        ArmStatement::Msr => m_generated::msr(),
+        ArmStatement::Msub => m_generated::msub(),
+        ArmStatement::Mul => m_generated::mul(),
+        ArmStatement::Mvn => m::mvn(),
+        ArmStatement::Mvni => m_generated::mvni(),
+        ArmStatement::Nand => n_generated::nand(),
+        ArmStatement::Neg => n_generated::neg(),
+        ArmStatement::Negs => n::negs(),
+        ArmStatement::Ngc => n::ngc(),
+        ArmStatement::Ngcs => n::ngcs(),
+        ArmStatement::Nop => n_generated::nop(),
+        ArmStatement::Nor => n_generated::nor(),
+        ArmStatement::Not => n_generated::not(),
+        ArmStatement::Orn => o_generated::orn(),
+        ArmStatement::Orr => o_generated::orr(),
+        ArmStatement::Orv => o_generated::orv(),
+        ArmStatement::Pacda => p_generated::pacda(),
+        ArmStatement::Pacdb => p_generated::pacdb(),
+        ArmStatement::Pacga => p_generated::pacga(),
+        ArmStatement::Pacia => p_generated::pacia(),
+        ArmStatement::Pacib => p_generated::pacib(),
+        ArmStatement::Pfalse => p_generated::pfalse(),
+        ArmStatement::Pfirst => p_generated::pfirst(),
+        ArmStatement::Pmul => p_generated::pmul(),
+        ArmStatement::Pmull => p_generated::pmull(),
+        ArmStatement::Pnext => p_generated::pnext(),
+        ArmStatement::Prfb => p_generated::prfb(),
+        ArmStatement::Prfd => p_generated::prfd(),
+        ArmStatement::Prfh => p_generated::prfh(),
+        ArmStatement::Prfm => p_generated::prfm(),
+        ArmStatement::Prfum => p_generated::prfum(),
+        ArmStatement::Prfw => p_generated::prfw(),
+        ArmStatement::Psb => p_generated::psb(),
+        ArmStatement::Pssbb => p_generated::pssbb(),
+        ArmStatement::Ptest => p_generated::ptest(),
+        ArmStatement::Ptrue => p_generated::ptrue(),
+        ArmStatement::Punpkhi => p_generated::punpkhi(),
+        ArmStatement::Raddhn => r_generated::raddhn(),
+        ArmStatement::Rax1 => r_generated::rax1(),
+        ArmStatement::Rbit => r_generated::rbit(),
+        ArmStatement::Rdffr => r_generated::rdffr(),
+        ArmStatement::Rdvl => r_generated::rdvl(),
+        ArmStatement::Ret => r::ret(instruction),
+        ArmStatement::Retaa => r::retaa(),
+        ArmStatement::Retab => r::retab(),
+        ArmStatement::Rev => r_generated::rev(),
+        ArmStatement::Rev16 => r_generated::rev16(),
+        ArmStatement::Rev32 => r_generated::rev32(),
+        ArmStatement::Rev64 => r_generated::rev64(),
+        ArmStatement::Revb => r_generated::revb(),
+        ArmStatement::Rmif => r_generated::rmif(),
+        ArmStatement::Ror => r::ror(),
+        ArmStatement::Rorv => r_generated::rorv(),
+        ArmStatement::Rshrn => r_generated::rshrn(),
+        ArmStatement::Rsubhn => r_generated::rsubhn(),
+        ArmStatement::Saba => s_generated::saba(),
+        ArmStatement::Sabal => s_generated::sabal(),
+        ArmStatement::Sabd => s_generated::sabd(),
+        ArmStatement::Sabdl => s_generated::sabdl(),
+        ArmStatement::Sadalp => s_generated::sadalp(),
+        ArmStatement::Saddl => s_generated::saddl(),
+        ArmStatement::Saddlp => s_generated::saddlp(),
+        ArmStatement::Saddlv => s_generated::saddlv(),
+        ArmStatement::Saddv => s_generated::saddv(),
+        ArmStatement::Saddw => s_generated::saddw(),
+        ArmStatement::Sb => s_generated::sb(),
+        ArmStatement::Sbc => s_generated::sbc(),
+        ArmStatement::Sbcs => s_generated::sbcs(),
+        ArmStatement::Sbfiz => s::sbfiz(),
+        ArmStatement::Sbfm => s_generated::sbfm(),
+        ArmStatement::Sbfx => s::sbfx(),
+        ArmStatement::Scvtf => s_generated::scvtf(),
+        ArmStatement::Sdiv => s_generated::sdiv(),
+        ArmStatement::Sdivr => s_generated::sdivr(),
+        ArmStatement::Sdot => s_generated::sdot(),
+        ArmStatement::Sel => s_generated::sel(),
+        ArmStatement::Setf8 => s_generated::setf8(),
+        ArmStatement::Setffr => s_generated::setffr(),
+        ArmStatement::Sev => s_generated::sev(),
+        ArmStatement::Sevl => s_generated::sevl(),
+        ArmStatement::Sha1c => s_generated::sha1c(),
+        ArmStatement::Sha1h => s_generated::sha1h(),
+        ArmStatement::Sha1m => s_generated::sha1m(),
+        ArmStatement::Sha1p => s_generated::sha1p(),
+        ArmStatement::Sha1su0 => s_generated::sha1su0(),
+        ArmStatement::Sha1su1 => s_generated::sha1su1(),
+        ArmStatement::Sha256h => s_generated::sha256h(),
+        ArmStatement::Sha256h2 => s_generated::sha256h2(),
+        ArmStatement::Sha256su0 => s_generated::sha256su0(),
+        ArmStatement::Sha256su1 => s_generated::sha256su1(),
+        ArmStatement::Sha512h => s_generated::sha512h(),
+        ArmStatement::Sha512h2 => s_generated::sha512h2(),
+        ArmStatement::Sha512su0 => s_generated::sha512su0(),
+        ArmStatement::Sha512su1 => s_generated::sha512su1(),
+        ArmStatement::Shadd => s_generated::shadd(),
+        ArmStatement::Shl => s_generated::shl(),
+        ArmStatement::Shll => s_generated::shll(),
+        ArmStatement::Shrn => s_generated::shrn(),
+        ArmStatement::Shsub => s_generated::shsub(),
+        ArmStatement::Sli => s_generated::sli(),
+        ArmStatement::Sm3partw1 => s_generated::sm3partw1(),
+        ArmStatement::Sm3partw2 => s_generated::sm3partw2(),
+        ArmStatement::Sm3ss1 => s_generated::sm3ss1(),
+        ArmStatement::Sm3tt1a => s_generated::sm3tt1a(),
+        ArmStatement::Sm3tt1b => s_generated::sm3tt1b(),
+        ArmStatement::Sm3tt2a => s_generated::sm3tt2a(),
+        ArmStatement::Sm3tt2b => s_generated::sm3tt2b(),
+        ArmStatement::Sm4e => s_generated::sm4e(),
+        ArmStatement::Sm4ekey => s_generated::sm4ekey(),
+        ArmStatement::Smaddl => s_generated::smaddl(),
+        ArmStatement::Smax => s_generated::smax(),
+        ArmStatement::Smaxp => s_generated::smaxp(),
+        ArmStatement::Smaxv => s_generated::smaxv(),
+        ArmStatement::Smc => s_generated::smc(),
+        ArmStatement::Smin => s_generated::smin(),
+        ArmStatement::Sminp => s_generated::sminp(),
+        ArmStatement::Sminv => s_generated::sminv(),
+        ArmStatement::Smlal => s_generated::smlal(),
+        ArmStatement::Smlsl => s_generated::smlsl(),
+        ArmStatement::Smmla => s_generated::smmla(),
+        ArmStatement::Smov => s_generated::smov(),
+        ArmStatement::Smsubl => s_generated::smsubl(),
+        ArmStatement::Smulh => s_generated::smulh(),
+        ArmStatement::Smull => s_generated::smull(),
+        ArmStatement::Splice => s_generated::splice(),
+        ArmStatement::Sqabs => s_generated::sqabs(),
+        ArmStatement::Sqadd => s_generated::sqadd(),
+        ArmStatement::Sqdecb => s_generated::sqdecb(),
+        ArmStatement::Sqdecd => s_generated::sqdecd(),
+        ArmStatement::Sqdech => s_generated::sqdech(),
+        ArmStatement::Sqdecp => s_generated::sqdecp(),
+        ArmStatement::Sqdecw => s_generated::sqdecw(),
+        ArmStatement::Sqdmlal => s_generated::sqdmlal(),
+        ArmStatement::Sqdmlsl => s_generated::sqdmlsl(),
+        ArmStatement::Sqdmulh => s_generated::sqdmulh(),
+        ArmStatement::Sqdmull => s_generated::sqdmull(),
+        ArmStatement::Sqincb => s_generated::sqincb(),
+        ArmStatement::Sqincd => s_generated::sqincd(),
+        ArmStatement::Sqinch => s_generated::sqinch(),
+        ArmStatement::Sqincp => s_generated::sqincp(),
+        ArmStatement::Sqincw => s_generated::sqincw(),
+        ArmStatement::Sqneg => s_generated::sqneg(),
+        ArmStatement::Sqrdmlah => s_generated::sqrdmlah(),
+        ArmStatement::Sqrdmlsh => s_generated::sqrdmlsh(),
+        ArmStatement::Sqrdmulh => s_generated::sqrdmulh(),
+        ArmStatement::Sqrshl => s_generated::sqrshl(),
+        ArmStatement::Sqrshrn => s_generated::sqrshrn(),
+        ArmStatement::Sqrshrun => s_generated::sqrshrun(),
+        ArmStatement::Sqshl => s_generated::sqshl(),
+        ArmStatement::Sqshlu => s_generated::sqshlu(),
+        ArmStatement::Sqshrn => s_generated::sqshrn(),
+        ArmStatement::Sqshrun => s_generated::sqshrun(),
+        ArmStatement::Sqsub => s_generated::sqsub(),
+        ArmStatement::Sqxtn => s_generated::sqxtn(),
+        ArmStatement::Sqxtun => s_generated::sqxtun(),
+        ArmStatement::Srhadd => s_generated::srhadd(),
+        ArmStatement::Sri => s_generated::sri(),
+        ArmStatement::Srshl => s_generated::srshl(),
+        ArmStatement::Srshr => s_generated::srshr(),
+        ArmStatement::Srsra => s_generated::srsra(),
+        ArmStatement::Ssbb => s_generated::ssbb(),
+        ArmStatement::Sshl => s_generated::sshl(),
+        ArmStatement::Sshll => s_generated::sshll(),
+        ArmStatement::Sshr => s_generated::sshr(),
+        ArmStatement::Ssra => s_generated::ssra(),
+        ArmStatement::Ssubl => s_generated::ssubl(),
+        ArmStatement::Ssubw => s_generated::ssubw(),
+        ArmStatement::St1 => s_generated::st1(),
+        ArmStatement::St1b => s_generated::st1b(),
+        ArmStatement::St1d => s_generated::st1d(),
+        ArmStatement::St1h => s_generated::st1h(),
+        ArmStatement::St1w => s_generated::st1w(),
+        ArmStatement::St2 => s_generated::st2(),
+        ArmStatement::St2b => s_generated::st2b(),
+        ArmStatement::St2d => s_generated::st2d(),
+        ArmStatement::St2g => s_generated::st2g(),
+        ArmStatement::St2h => s_generated::st2h(),
+        ArmStatement::St2w => s_generated::st2w(),
+        ArmStatement::St3 => s_generated::st3(),
+        ArmStatement::St3b => s_generated::st3b(),
+        ArmStatement::St3d => s_generated::st3d(),
+        ArmStatement::St3h => s_generated::st3h(),
+        ArmStatement::St3w => s_generated::st3w(),
+        ArmStatement::St4 => s_generated::st4(),
+        ArmStatement::St4b => s_generated::st4b(),
+        ArmStatement::St4d => s_generated::st4d(),
+        ArmStatement::St4h => s_generated::st4h(),
+        ArmStatement::St4w => s_generated::st4w(),
+        ArmStatement::Stg => s_generated::stg(),
+        ArmStatement::Stgm => s_generated::stgm(),
+        ArmStatement::Stgp => s_generated::stgp(),
+        ArmStatement::Stllr => s_generated::stllr(),
+        ArmStatement::Stllrb => s_generated::stllrb(),
+        ArmStatement::Stllrh => s_generated::stllrh(),
+        ArmStatement::Stlr => s_generated::stlr(),
+        ArmStatement::Stlrb => s_generated::stlrb(),
+        ArmStatement::Stlrh => s_generated::stlrh(),
+        ArmStatement::Stlur => s_generated::stlur(),
+        ArmStatement::Stlurb => s_generated::stlurb(),
+        ArmStatement::Stlurh => s_generated::stlurh(),
+        ArmStatement::Stlxp => s_generated::stlxp(),
+        ArmStatement::Stlxr => s_generated::stlxr(),
+        ArmStatement::Stlxrb => s_generated::stlxrb(),
+        ArmStatement::Stlxrh => s_generated::stlxrh(),
+        ArmStatement::Stnp => s_generated::stnp(),
+        ArmStatement::Stnt1b => s_generated::stnt1b(),
+        ArmStatement::Stnt1d => s_generated::stnt1d(),
+        ArmStatement::Stnt1h => s_generated::stnt1h(),
+        ArmStatement::Stnt1w => s_generated::stnt1w(),
+        ArmStatement::Stp => s::stp(instruction),
+        ArmStatement::Str => s::str(instruction),
+        ArmStatement::Strb => s::strb(instruction),
+        ArmStatement::Strh => s::strh(instruction),
+        ArmStatement::Sttr => s_generated::sttr(),
+        ArmStatement::Sttrb => s_generated::sttrb(),
+        ArmStatement::Sttrh => s_generated::sttrh(),
+        ArmStatement::Stur => s::stur(instruction),
+        ArmStatement::Sturb => s::sturb(instruction),
+        ArmStatement::Sturh => s::sturh(instruction),
+        ArmStatement::Stxp => s_generated::stxp(),
+        ArmStatement::Stxr => s_generated::stxr(),
+        ArmStatement::Stxrb => s_generated::stxrb(),
+        ArmStatement::Stxrh => s_generated::stxrh(),
+        ArmStatement::Stz2g => s_generated::stz2g(),
+        ArmStatement::Stzg => s_generated::stzg(),
+        ArmStatement::Stzgm => s_generated::stzgm(),
+        ArmStatement::Sub => s_generated::sub(),
+        ArmStatement::Subg => s_generated::subg(),
+        ArmStatement::Subhn => s_generated::subhn(),
+        ArmStatement::Subp => s_generated::subp(),
+        ArmStatement::Subps => s_generated::subps(),
+        ArmStatement::Subr => s_generated::subr(),
+        ArmStatement::Subs => s_generated::subs(),
+        ArmStatement::Sudot => s_generated::sudot(),
+        ArmStatement::Sunpkhi => s_generated::sunpkhi(),
+        ArmStatement::Suqadd => s_generated::suqadd(),
+        ArmStatement::Svc => s_generated::svc(),
+        ArmStatement::Swp => s_generated::swp(),
+        ArmStatement::Swpb => s_generated::swpb(),
+        ArmStatement::Swph => s_generated::swph(),
+        ArmStatement::Sxtb => s::sxtb(),
+        ArmStatement::Sxth => s::sxth(),
+        ArmStatement::Sxtw => s::sxtw(),
+        ArmStatement::Sys => s_generated::sys(),
+        ArmStatement::Sysl => s_generated::sysl(),
+        ArmStatement::Shared => s_generated::shared(),
+        ArmStatement::Tbl => t_generated::tbl(),
+        ArmStatement::Tbnz => t_generated::tbnz(),
+        ArmStatement::Tbx => t_generated::tbx(),
+        ArmStatement::Tbz => t_generated::tbz(),
+        ArmStatement::Trn1 => t_generated::trn1(),
+        ArmStatement::Trn2 => t_generated::trn2(),
+        ArmStatement::Tsb => t_generated::tsb(),
+        ArmStatement::Tst => t::tst(),
+        ArmStatement::Uaba => u_generated::uaba(),
+        ArmStatement::Uabal => u_generated::uabal(),
+        ArmStatement::Uabd => u_generated::uabd(),
+        ArmStatement::Uabdl => u_generated::uabdl(),
+        ArmStatement::Uadalp => u_generated::uadalp(),
+        ArmStatement::Uaddl => u_generated::uaddl(),
+        ArmStatement::Uaddlp => u_generated::uaddlp(),
+        ArmStatement::Uaddlv => u_generated::uaddlv(),
+        ArmStatement::Uaddv => u_generated::uaddv(),
+        ArmStatement::Uaddw => u_generated::uaddw(),
+        ArmStatement::Ubfiz => u::ubfiz(),
+        ArmStatement::Ubfm => u_generated::ubfm(),
+        ArmStatement::Ubfx => u::ubfx(),
+        ArmStatement::Ucvtf => u_generated::ucvtf(),
+        ArmStatement::Udf => u_generated::udf(),
+        ArmStatement::Udiv => u_generated::udiv(),
+        ArmStatement::Udivr => u_generated::udivr(),
+        ArmStatement::Udot => u_generated::udot(),
+        ArmStatement::Uhadd => u_generated::uhadd(),
+        ArmStatement::Uhsub => u_generated::uhsub(),
+        ArmStatement::Umaddl => u_generated::umaddl(),
+        ArmStatement::Umax => u_generated::umax(),
+        ArmStatement::Umaxp => u_generated::umaxp(),
+        ArmStatement::Umaxv => u_generated::umaxv(),
+        ArmStatement::Umin => u_generated::umin(),
+        ArmStatement::Uminp => u_generated::uminp(),
+        ArmStatement::Uminv => u_generated::uminv(),
+        ArmStatement::Umlal => u_generated::umlal(),
+        ArmStatement::Umlsl => u_generated::umlsl(),
+        ArmStatement::Ummla => u_generated::ummla(),
+        ArmStatement::Umov => u_generated::umov(),
+        ArmStatement::Umsubl => u_generated::umsubl(),
+        ArmStatement::Umulh => u_generated::umulh(),
+        ArmStatement::Umull => u_generated::umull(),
+        ArmStatement::Uqadd => u_generated::uqadd(),
+        ArmStatement::Uqdecb => u_generated::uqdecb(),
+        ArmStatement::Uqdecd => u_generated::uqdecd(),
+        ArmStatement::Uqdech => u_generated::uqdech(),
+        ArmStatement::Uqdecp => u_generated::uqdecp(),
+        ArmStatement::Uqdecw => u_generated::uqdecw(),
+        ArmStatement::Uqincb => u_generated::uqincb(),
+        ArmStatement::Uqincd => u_generated::uqincd(),
+        ArmStatement::Uqinch => u_generated::uqinch(),
+        ArmStatement::Uqincp => u_generated::uqincp(),
+        ArmStatement::Uqincw => u_generated::uqincw(),
+        ArmStatement::Uqrshl => u_generated::uqrshl(),
+        ArmStatement::Uqrshrn => u_generated::uqrshrn(),
+        ArmStatement::Uqshl => u_generated::uqshl(),
+        ArmStatement::Uqshrn => u_generated::uqshrn(),
+        ArmStatement::Uqsub => u_generated::uqsub(),
+        ArmStatement::Uqxtn => u_generated::uqxtn(),
+        ArmStatement::Urecpe => u_generated::urecpe(),
+        ArmStatement::Urhadd => u_generated::urhadd(),
+        ArmStatement::Urshl => u_generated::urshl(),
+        ArmStatement::Urshr => u_generated::urshr(),
+        ArmStatement::Ursqrte => u_generated::ursqrte(),
+        ArmStatement::Ursra => u_generated::ursra(),
+        ArmStatement::Usdot => u_generated::usdot(),
+        ArmStatement::Ushl => u_generated::ushl(),
+        ArmStatement::Ushll => u_generated::ushll(),
+        ArmStatement::Ushr => u_generated::ushr(),
+        ArmStatement::Usmmla => u_generated::usmmla(),
+        ArmStatement::Usqadd => u_generated::usqadd(),
+        ArmStatement::Usra => u_generated::usra(),
+        ArmStatement::Usubl => u_generated::usubl(),
+        ArmStatement::Usubw => u_generated::usubw(),
+        ArmStatement::Uunpkhi => u_generated::uunpkhi(),
+        ArmStatement::Uxtb => u::uxtb(),
+        ArmStatement::Uxth => u::uxth(),
+        ArmStatement::Uzp1 => u_generated::uzp1(),
+        ArmStatement::Uzp2 => u_generated::uzp2(),
+        ArmStatement::Wfe => w_generated::wfe(),
+        ArmStatement::Wfi => w_generated::wfi(),
+        ArmStatement::Whilele => w_generated::whilele(),
+        ArmStatement::Whilelo => w_generated::whilelo(),
+        ArmStatement::Whilels => w_generated::whilels(),
+        ArmStatement::Whilelt => w_generated::whilelt(),
+        ArmStatement::Wrffr => w_generated::wrffr(),
+        ArmStatement::Xaflag => x_generated::xaflag(),
+        ArmStatement::Xar => x_generated::xar(),
+        ArmStatement::Xpacd => x_generated::xpacd(),
+        ArmStatement::Xtn => x_generated::xtn(),
+        ArmStatement::Yield => y_generated::r#yield(),
+        ArmStatement::Zip1 => z_generated::zip1(),
+        ArmStatement::Zip2 => z_generated::zip2(),
+    })
+}
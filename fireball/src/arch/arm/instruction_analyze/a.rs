@@ -0,0 +1,18 @@
+use super::{super::static_register::*, shortcuts::*};
+use std::ops::Deref;
+
+/// Capstone already resolves the PC-relative label, so `o2` is the final address.
+#[box_to_static_reference]
+pub(super) fn adr() -> &'static [IrStatement] {
+    let assignment = assign(o2(), o1(), o1_size());
+    let type1 = type_specified(o1(), o1_size(), DataType::Address);
+    [assignment, type1].into()
+}
+
+/// Capstone already resolves the 4KB page, so `o2` is the final page address.
+#[box_to_static_reference]
+pub(super) fn adrp() -> &'static [IrStatement] {
+    let assignment = assign(o2(), o1(), o1_size());
+    let type1 = type_specified(o1(), o1_size(), DataType::Address);
+    [assignment, type1].into()
+}
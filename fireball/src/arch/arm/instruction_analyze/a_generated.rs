@@ -522,7 +522,6 @@ pub(super) fn asrv() -> &'static [IrStatement] {
 /// ```
 #[box_to_static_reference]
 pub(super) fn autda() -> &'static [IrStatement] {
-    let v_0 = unknown_data();
     let stmt_1 = assign(unknown_data(), o1(), o1_size());
     [stmt_1].into()
 }
@@ -539,7 +538,6 @@ pub(super) fn autda() -> &'static [IrStatement] {
 /// ```
 #[box_to_static_reference]
 pub(super) fn autdb() -> &'static [IrStatement] {
-    let v_0 = unknown_data();
     let stmt_1 = assign(unknown_data(), o1(), o1_size());
     [stmt_1].into()
 }
@@ -556,7 +554,6 @@ pub(super) fn autdb() -> &'static [IrStatement] {
 /// ```
 #[box_to_static_reference]
 pub(super) fn autia() -> &'static [IrStatement] {
-    let v_0 = unknown_data();
     let stmt_1 = assign(unknown_data(), o1(), o1_size());
     [stmt_1].into()
 }
@@ -573,7 +570,6 @@ pub(super) fn autia() -> &'static [IrStatement] {
 /// ```
 #[box_to_static_reference]
 pub(super) fn autib() -> &'static [IrStatement] {
-    let v_0 = unknown_data();
     let stmt_1 = assign(unknown_data(), o1(), o1_size());
     [stmt_1].into()
 }
@@ -0,0 +1,44 @@
+use super::{super::static_register::*, shortcuts::*};
+use std::ops::Deref;
+
+/// `b.<cond> label`, condition is passed as the last operand.
+#[box_to_static_reference]
+pub(super) fn bcond() -> &'static [IrStatement] {
+    let fallthrough = b::add(pc.clone(), instruction_byte_size());
+    [condition(o2(), [jump(o1())], [jump(fallthrough)])].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn bl() -> &'static [IrStatement] {
+    let ret_address = b::add(pc.clone(), instruction_byte_size());
+    let save_lr = assign(ret_address, x30.clone(), size_architecture());
+    let call = jump_by_call(o1());
+    let type1 = type_specified(o1(), o1_size(), DataType::Address);
+    let type2 = type_specified(x30.clone(), size_architecture(), DataType::Address);
+    [save_lr, call, type1, type2].into()
+}
+
+#[inline]
+pub(super) fn blr() -> &'static [IrStatement] {
+    bl()
+}
+
+/// `bfi xd, xn, #lsb, #width`
+#[box_to_static_reference]
+pub(super) fn bfi() -> &'static [IrStatement] {
+    let mask = b::shl(b::sub(b::shl(c(1), o4()), c(1)), o3());
+    let inserted = b::and(b::shl(o2(), o3()), mask.clone());
+    let kept = b::and(o1(), u::not(mask));
+    let assignment = assign(b::or(kept, inserted), o1(), o1_size());
+    [assignment].into()
+}
+
+/// `bfxil xd, xn, #lsb, #width`
+#[box_to_static_reference]
+pub(super) fn bfxil() -> &'static [IrStatement] {
+    let mask = b::sub(b::shl(c(1), o4()), c(1));
+    let extracted = b::and(b::shr(o2(), o3()), mask.clone());
+    let kept = b::and(o1(), u::not(mask));
+    let assignment = assign(b::or(kept, extracted), o1(), o1_size());
+    [assignment].into()
+}
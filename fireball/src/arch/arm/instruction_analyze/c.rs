@@ -0,0 +1,153 @@
+use super::{super::static_register::*, shortcuts::*};
+use crate::utils::Aos;
+use std::ops::Deref;
+
+#[inline]
+fn set_flags_from_immediate(nzcv_immediate: Aos<IrData>) -> [IrStatement; 4] {
+    let bit = |index: usize| b::and(b::shr(nzcv_immediate.clone(), c(index)), c(1));
+    [
+        assign(bit(3), pstate_n.clone(), size_relative(pstate_n.clone())),
+        assign(bit(2), pstate_z.clone(), size_relative(pstate_z.clone())),
+        assign(bit(1), pstate_c.clone(), size_relative(pstate_c.clone())),
+        assign(bit(0), pstate_v.clone(), size_relative(pstate_v.clone())),
+    ]
+}
+
+#[inline]
+fn conditional_select(false_value: Aos<IrData>) -> IrStatement {
+    condition(
+        o4(),
+        [assign(o2(), o1(), o1_size())],
+        [assign(false_value, o1(), o1_size())],
+    )
+}
+
+#[inline]
+fn conditional_apply(true_value: Aos<IrData>) -> IrStatement {
+    condition(
+        o3(),
+        [assign(true_value, o1(), o1_size())],
+        [assign(o2(), o1(), o1_size())],
+    )
+}
+
+#[box_to_static_reference]
+pub(super) fn cmp() -> &'static [IrStatement] {
+    let sub = b::sub(o1(), o2());
+    let calc_flags = calc_flags_automatically(
+        sub,
+        o1_size(),
+        &[&pstate_n, &pstate_z, &pstate_c, &pstate_v],
+    );
+    let type1 = type_specified(o1(), o1_size(), DataType::Int);
+    let type2 = type_specified(o2(), o2_size(), DataType::Int);
+    [calc_flags, type1, type2].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn cmn() -> &'static [IrStatement] {
+    let add = b::add(o1(), o2());
+    let calc_flags = calc_flags_automatically(
+        add,
+        o1_size(),
+        &[&pstate_n, &pstate_z, &pstate_c, &pstate_v],
+    );
+    let type1 = type_specified(o1(), o1_size(), DataType::Int);
+    let type2 = type_specified(o2(), o2_size(), DataType::Int);
+    [calc_flags, type1, type2].into()
+}
+
+/// `ccmp xn, #imm|xm, #nzcv, cond`
+#[box_to_static_reference]
+pub(super) fn ccmp() -> &'static [IrStatement] {
+    let sub = b::sub(o1(), o2());
+    let calc_flags = calc_flags_automatically(
+        sub,
+        o1_size(),
+        &[&pstate_n, &pstate_z, &pstate_c, &pstate_v],
+    );
+    [condition(
+        o4(),
+        [calc_flags],
+        set_flags_from_immediate(o3()),
+    )]
+    .into()
+}
+
+/// `ccmn xn, #imm|xm, #nzcv, cond`
+#[box_to_static_reference]
+pub(super) fn ccmn() -> &'static [IrStatement] {
+    let add = b::add(o1(), o2());
+    let calc_flags = calc_flags_automatically(
+        add,
+        o1_size(),
+        &[&pstate_n, &pstate_z, &pstate_c, &pstate_v],
+    );
+    [condition(
+        o4(),
+        [calc_flags],
+        set_flags_from_immediate(o3()),
+    )]
+    .into()
+}
+
+#[box_to_static_reference]
+pub(super) fn csel() -> &'static [IrStatement] {
+    [conditional_select(o3())].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn csinc() -> &'static [IrStatement] {
+    [conditional_select(b::add(o3(), c(1)))].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn csinv() -> &'static [IrStatement] {
+    [conditional_select(u::not(o3()))].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn csneg() -> &'static [IrStatement] {
+    [conditional_select(u::neg(o3()))].into()
+}
+
+/// `cset xd, cond`
+#[box_to_static_reference]
+pub(super) fn cset() -> &'static [IrStatement] {
+    let set = condition(
+        o2(),
+        [assign(c(1), o1(), o1_size())],
+        [assign(c(0), o1(), o1_size())],
+    );
+    let type1 = type_specified(o1(), o1_size(), DataType::Bool);
+    [set, type1].into()
+}
+
+/// `csetm xd, cond`
+#[box_to_static_reference]
+pub(super) fn csetm() -> &'static [IrStatement] {
+    let set = condition(
+        o2(),
+        [assign(bit_ones(o1_size()), o1(), o1_size())],
+        [assign(c(0), o1(), o1_size())],
+    );
+    [set].into()
+}
+
+/// `cinc xd, xn, cond`
+#[box_to_static_reference]
+pub(super) fn cinc() -> &'static [IrStatement] {
+    [conditional_apply(b::add(o2(), c(1)))].into()
+}
+
+/// `cinv xd, xn, cond`
+#[box_to_static_reference]
+pub(super) fn cinv() -> &'static [IrStatement] {
+    [conditional_apply(u::not(o2()))].into()
+}
+
+/// `cneg xd, xn, cond`
+#[box_to_static_reference]
+pub(super) fn cneg() -> &'static [IrStatement] {
+    [conditional_apply(u::neg(o2()))].into()
+}
@@ -0,0 +1,78 @@
+use super::{
+    super::static_register::*,
+    pair::{self, PairAccess},
+    shortcuts::*,
+    single::{self, SingleAccess},
+};
+use crate::core::Instruction;
+use std::ops::Deref;
+
+/// `ldp xt1, xt2, [xn, #imm]`, with pre/post-index writeback, see [`pair`]
+pub(super) fn ldp(instruction: &Instruction) -> &'static [IrStatement] {
+    pair::lift(instruction, PairAccess::Load)
+}
+
+/// `ldpsw xt1, xt2, [xn, #imm]`, see [`ldp`]
+pub(super) fn ldpsw(instruction: &Instruction) -> &'static [IrStatement] {
+    pair::lift(instruction, PairAccess::LoadSignedWord)
+}
+
+/// `ldr xt, [xn, #imm]`, with pre/post-index writeback, see [`single`]
+pub(super) fn ldr(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::Load)
+}
+
+/// `ldrb wt, [xn, #imm]`, see [`ldr`]
+pub(super) fn ldrb(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadUnsigned(1))
+}
+
+/// `ldrh wt, [xn, #imm]`, see [`ldr`]
+pub(super) fn ldrh(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadUnsigned(2))
+}
+
+/// `ldrsb xt, [xn, #imm]`, see [`ldr`]
+pub(super) fn ldrsb(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadSigned(1))
+}
+
+/// `ldrsh xt, [xn, #imm]`, see [`ldr`]
+pub(super) fn ldrsh(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadSigned(2))
+}
+
+/// `ldrsw xt, [xn, #imm]`, see [`ldr`]
+pub(super) fn ldrsw(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadSigned(4))
+}
+
+/// `ldur xt, [xn, #simm]`, see [`ldr`]
+pub(super) fn ldur(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::Load)
+}
+
+/// `ldurb wt, [xn, #simm]`, see [`ldr`]
+pub(super) fn ldurb(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadUnsigned(1))
+}
+
+/// `ldurh wt, [xn, #simm]`, see [`ldr`]
+pub(super) fn ldurh(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadUnsigned(2))
+}
+
+/// `ldursb xt, [xn, #simm]`, see [`ldr`]
+pub(super) fn ldursb(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadSigned(1))
+}
+
+/// `ldursh xt, [xn, #simm]`, see [`ldr`]
+pub(super) fn ldursh(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadSigned(2))
+}
+
+/// `ldursw xt, [xn, #simm]`, see [`ldr`]
+pub(super) fn ldursw(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::LoadSigned(4))
+}
@@ -0,0 +1,21 @@
+use super::{super::static_register::*, shortcuts::*};
+use std::ops::Deref;
+
+#[box_to_static_reference]
+pub(super) fn mov() -> &'static [IrStatement] {
+    let assignment = assign(o2(), o1(), o1_size());
+    [assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn mvn() -> &'static [IrStatement] {
+    let assignment = assign(u::not(o2()), o1(), o1_size());
+    [assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn mneg() -> &'static [IrStatement] {
+    let assignment = assign(u::neg(b::mul(o2(), o3())), o1(), o1_size());
+    let type1 = type_specified(o1(), o1_size(), DataType::Int);
+    [assignment, type1].into()
+}
@@ -0,0 +1,33 @@
+use super::{super::static_register::*, shortcuts::*};
+use std::ops::Deref;
+
+#[box_to_static_reference]
+pub(super) fn negs() -> &'static [IrStatement] {
+    let op = b::sub(c(0), o2());
+    let calc_flags = calc_flags_automatically(
+        op.clone(),
+        o1_size(),
+        &[&pstate_n, &pstate_z, &pstate_c, &pstate_v],
+    );
+    let assignment = assign(op, o1(), o1_size());
+    [calc_flags, assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn ngc() -> &'static [IrStatement] {
+    let op = b::sub(b::sub(c(0), o2()), u::not(pstate_c.clone()));
+    let assignment = assign(op, o1(), o1_size());
+    [assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn ngcs() -> &'static [IrStatement] {
+    let op = b::sub(b::sub(c(0), o2()), u::not(pstate_c.clone()));
+    let calc_flags = calc_flags_automatically(
+        op.clone(),
+        o1_size(),
+        &[&pstate_n, &pstate_z, &pstate_c, &pstate_v],
+    );
+    let assignment = assign(op, o1(), o1_size());
+    [calc_flags, assignment].into()
+}
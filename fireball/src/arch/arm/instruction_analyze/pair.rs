@@ -0,0 +1,442 @@
+//! Load/store pair instructions (`ldp`, `ldpsw`, `stp`)
+//!
+//! The second register of a pair is accessed right after the first one, and the base
+//! register may be written back, so unlike the other lifters the statements depend on
+//! the operands of the instruction. They are built once per distinct instruction.
+
+use super::shortcuts::*;
+use crate::{core::Instruction, ir::data::IrData, utils::Aos};
+use iceball::{Argument, ArmRegister, Memory, Register, RelativeAddressingArgument};
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+/// Addressing mode of a load/store instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum IndexMode {
+    /// `[xn, #imm]`
+    Offset,
+    /// `[xn, #imm]!`, the base is updated before the access
+    PreIndex,
+    /// `[xn], #imm`, the base is updated after the access
+    PostIndex,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PairAccess {
+    Load,
+    /// `ldpsw`, loads two words sign extended into X registers
+    LoadSignedWord,
+    Store,
+}
+
+/// Lifts `ldp`, `ldpsw` or `stp`.
+///
+/// ### Arguments
+/// - `instruction: &Instruction` - pair instruction
+/// - `access: PairAccess` - what the instruction does with the pair
+///
+/// ### Returns
+/// - `&'static [IrStatement]` - statements of the instruction, `exception` if the operands
+///   don't form a pair access
+pub(super) fn lift(instruction: &Instruction, access: PairAccess) -> &'static [IrStatement] {
+    static CACHE: LazyLock<Mutex<HashMap<String, &'static [IrStatement]>>> =
+        LazyLock::new(Default::default);
+
+    let mode = index_mode(instruction);
+    let key = format!("{:?} {:?} {}", access, mode, instruction.inner);
+    let mut cache = CACHE.lock().unwrap();
+    cache.entry(key).or_insert_with(|| {
+        let statements = build(&instruction.inner.arguments, access, mode)
+            .unwrap_or_else(|| vec![exception("invalid pair access")]);
+        Box::leak(statements.into_boxed_slice())
+    })
+}
+
+/// Reads the addressing mode from bits 24:23 of the encoding.
+fn index_mode(instruction: &Instruction) -> IndexMode {
+    match instruction.inner.bytes.as_deref() {
+        Some(&[b0, b1, b2, b3]) => match (u32::from_le_bytes([b0, b1, b2, b3]) >> 23) & 0b11 {
+            0b01 => IndexMode::PostIndex,
+            0b11 => IndexMode::PreIndex,
+            _ => IndexMode::Offset,
+        },
+        // the post-index immediate is the only operand after the memory one
+        _ if instruction.inner.arguments.len() > 3 => IndexMode::PostIndex,
+        _ => IndexMode::Offset,
+    }
+}
+
+fn build(arguments: &[Argument], access: PairAccess, mode: IndexMode) -> Option<Vec<IrStatement>> {
+    let [
+        Argument::Register(Register::Arm(first)),
+        Argument::Register(Register::Arm(second)),
+        memory @ Argument::Memory(Memory::RelativeAddressing(terms)),
+        rest @ ..,
+    ] = arguments
+    else {
+        return None;
+    };
+    let Some(RelativeAddressingArgument::Register(Register::Arm(base))) = terms.first() else {
+        return None;
+    };
+    let size = match access {
+        PairAccess::LoadSignedWord => 4,
+        PairAccess::Load | PairAccess::Store => register_byte_size(*first)?,
+    };
+
+    let address = dereferenced_address(memory)?;
+    let slot = |index: usize| -> Aos<IrData> {
+        match index {
+            0 => d(address.clone()),
+            _ => d(b::add(address.clone(), c(size * index))),
+        }
+    };
+    let access_slot = |register: ArmRegister, index: usize| -> IrStatement {
+        let value = sized(slot(index), size_result_byte(c(size)));
+        match access {
+            PairAccess::Store => assign(
+                sized(register_data(register), size_result_byte(c(size))),
+                slot(index),
+                size_result_byte(c(size)),
+            ),
+            PairAccess::LoadSignedWord => assign(
+                u::sign_extend(value),
+                register_data(extended_register(register)),
+                size_result_byte(c(8)),
+            ),
+            // writes to W registers clear the upper half of the X register
+            PairAccess::Load if extended_register(register) != register => assign(
+                u::zero_extend(value),
+                register_data(extended_register(register)),
+                size_result_byte(c(8)),
+            ),
+            PairAccess::Load => assign(value, register_data(register), size_result_byte(c(size))),
+        }
+    };
+
+    let mut statements = Vec::new();
+    // `ldp x0, x1, [x0]` must still read the second slot from the original base
+    if access != PairAccess::Store && same_register(*first, *base) {
+        statements.push(access_slot(*second, 1));
+        statements.push(access_slot(*first, 0));
+    } else {
+        statements.push(access_slot(*first, 0));
+        statements.push(access_slot(*second, 1));
+    }
+
+    statements.extend(writeback(mode, *base, address, rest)?);
+    Some(statements)
+}
+
+/// Statement updating the base register of a pre/post-indexed access.
+///
+/// ### Arguments
+/// - `mode: IndexMode` - addressing mode of the access
+/// - `base: ArmRegister` - base register of the memory operand
+/// - `address: Aos<IrData>` - address accessed, base plus the offset of the memory operand
+/// - `rest: &[Argument]` - operands after the memory one, holding the post-index immediate
+///
+/// ### Returns
+/// - `Option<Option<IrStatement>>` - the update if the base is written back, `None` if a
+///   post-index access has no immediate
+pub(super) fn writeback(
+    mode: IndexMode,
+    base: ArmRegister,
+    address: Aos<IrData>,
+    rest: &[Argument],
+) -> Option<Option<IrStatement>> {
+    let base_data = register_data(base);
+    match (mode, rest) {
+        (IndexMode::Offset, _) => Some(None),
+        (IndexMode::PreIndex, _) => Some(Some(assign(address, base_data, size_result_byte(c(8))))),
+        (IndexMode::PostIndex, [offset @ Argument::Constant(_)]) => {
+            let offset: Aos<IrData> = offset.into();
+            Some(Some(assign(
+                b::add(base_data.clone(), offset),
+                base_data,
+                size_result_byte(c(8)),
+            )))
+        }
+        (IndexMode::PostIndex, _) => None,
+    }
+}
+
+/// Address accessed by a memory operand
+pub(super) fn dereferenced_address(memory: &Argument) -> Option<Aos<IrData>> {
+    let data: Aos<IrData> = memory.into();
+    match data.as_ref() {
+        IrData::Dereference(address) => Some(address.clone()),
+        _ => None,
+    }
+}
+
+pub(super) fn register_data(register: ArmRegister) -> Aos<IrData> {
+    (&Argument::Register(Register::Arm(register))).into()
+}
+
+/// Bytes accessed by a register of the pair, `None` for the registers pairs can't use
+pub(super) fn register_byte_size(register: ArmRegister) -> Option<usize> {
+    match register {
+        ArmRegister::X(_) | ArmRegister::Xzr | ArmRegister::Fp | ArmRegister::Lr => Some(8),
+        ArmRegister::D(_) => Some(8),
+        ArmRegister::W(_) | ArmRegister::Wzr | ArmRegister::S(_) => Some(4),
+        ArmRegister::Q(_) | ArmRegister::V(_) => Some(16),
+        _ => None,
+    }
+}
+
+/// X register holding a W register
+pub(super) fn extended_register(register: ArmRegister) -> ArmRegister {
+    match register {
+        ArmRegister::W(n) => ArmRegister::X(n),
+        ArmRegister::Wzr => ArmRegister::Xzr,
+        ArmRegister::Wsp => ArmRegister::Sp,
+        x => x,
+    }
+}
+
+fn same_register(a: ArmRegister, b: ArmRegister) -> bool {
+    let number = |x: ArmRegister| match extended_register(x) {
+        ArmRegister::Fp => ArmRegister::X(29),
+        ArmRegister::Lr => ArmRegister::X(30),
+        x => x,
+    };
+    number(a) == number(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{super::static_register::x30, shortcuts::*};
+    use crate::{
+        core::{Address, Instruction, Sections},
+        ir::{Interpreter, Ir, SparseMemory, VirtualMachine, arm::ArmRange},
+    };
+    use iceball::{ArmStatement, MachineArchitecture, Statement};
+
+    const STACK: u64 = 0x7000_0000;
+
+    fn arm_register(name: &str) -> crate::ir::Register {
+        match crate::arch::arm::str_to_arm_register(name).as_ref() {
+            crate::ir::data::IrData::Register(register) => *register,
+            x => panic!("{x:?} is not a register"),
+        }
+    }
+
+    fn instruction(statement: ArmStatement, arguments: &[&str], encoding: u32) -> Instruction {
+        Instruction {
+            address: 0x1000,
+            inner: iceball::Instruction {
+                statement: Ok(Statement::Arm(statement)),
+                arguments: arguments
+                    .iter()
+                    .map(|x| iceball::parse_argument(MachineArchitecture::Arm64, x).unwrap())
+                    .collect(),
+                bytes: Some(encoding.to_le_bytes().into()),
+            },
+        }
+    }
+
+    /// Runs one instruction given by its encoding and the operands capstone prints for it.
+    fn execute(
+        interpreter: &mut Interpreter,
+        statement: ArmStatement,
+        arguments: &[&str],
+        encoding: u32,
+    ) {
+        let instruction = instruction(statement, arguments, encoding);
+        let ir = Ir {
+            address: Address::from_virtual_address(&Sections::new(), instruction.address),
+            statements: crate::arch::create_ir_statement(MachineArchitecture::Arm64, &instruction),
+        };
+        interpreter.execute_instruction(&ir, &instruction).unwrap();
+    }
+
+    fn interpreter() -> Interpreter {
+        let mut memory = SparseMemory::default();
+        memory.write(STACK - 0x1000, &[0; 0x2000]);
+        let mut interpreter = Interpreter::new(MachineArchitecture::Arm64, memory);
+        let vm = interpreter.vm_mut();
+        vm.write_register(&<VirtualMachine as ArmRange>::sp(), STACK as u128);
+        interpreter
+    }
+
+    #[test]
+    fn test_stp_pre_index() {
+        let mut interpreter = interpreter();
+        let vm = interpreter.vm_mut();
+        vm.write_register(&arm_register("x29"), 0x1111_2222_3333_4444);
+        vm.write_register(&arm_register("x30"), 0x5555_6666_7777_8888);
+
+        execute(
+            &mut interpreter,
+            ArmStatement::Stp,
+            &["x29", "x30", "[sp, #-0x10]!"],
+            0xa9bf7bfd,
+        );
+        let vm = interpreter.vm();
+        assert_eq!(
+            vm.read_register(&<VirtualMachine as ArmRange>::sp()),
+            (STACK - 0x10) as u128
+        );
+        let memory = vm.memory();
+        assert_eq!(
+            memory.read(STACK - 0x10, 8).unwrap(),
+            0x1111_2222_3333_4444_u64.to_le_bytes()
+        );
+        assert_eq!(
+            memory.read(STACK - 8, 8).unwrap(),
+            0x5555_6666_7777_8888_u64.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn test_ldp_post_index() {
+        let mut interpreter = interpreter();
+        let vm = interpreter.vm_mut();
+        vm.memory_mut().write(STACK, &0xaaaa_u64.to_le_bytes());
+        vm.memory_mut().write(STACK + 8, &0xbbbb_u64.to_le_bytes());
+
+        execute(
+            &mut interpreter,
+            ArmStatement::Ldp,
+            &["x29", "x30", "[sp]", "#0x10"],
+            0xa8c17bfd,
+        );
+        let vm = interpreter.vm();
+        assert_eq!(vm.read_register(&arm_register("x29")), 0xaaaa);
+        assert_eq!(vm.read_register(&arm_register("x30")), 0xbbbb);
+        assert_eq!(
+            vm.read_register(&<VirtualMachine as ArmRange>::sp()),
+            (STACK + 0x10) as u128
+        );
+    }
+
+    #[test]
+    fn test_ldp_word_clears_upper_half() {
+        let mut interpreter = interpreter();
+        let vm = interpreter.vm_mut();
+        vm.write_register(&arm_register("x2"), STACK as u128);
+        vm.write_register(&arm_register("x0"), u64::MAX as u128);
+        vm.memory_mut().write(STACK, &[1, 0, 0, 0, 2, 0, 0, 0]);
+
+        // ldp w0, w1, [x2]
+        execute(
+            &mut interpreter,
+            ArmStatement::Ldp,
+            &["w0", "w1", "[x2]"],
+            0x29400440,
+        );
+        let vm = interpreter.vm();
+        assert_eq!(vm.read_register(&arm_register("x0")), 1);
+        assert_eq!(vm.read_register(&arm_register("x1")), 2);
+        assert_eq!(
+            vm.read_register(&arm_register("x2")),
+            STACK as u128,
+            "no writeback without an index mode"
+        );
+    }
+
+    #[test]
+    fn test_str_pre_index() {
+        let mut interpreter = interpreter();
+        let vm = interpreter.vm_mut();
+        vm.write_register(&arm_register("x19"), 0x1111_2222_3333_4444);
+
+        execute(
+            &mut interpreter,
+            ArmStatement::Str,
+            &["x19", "[sp, #-0x20]!"],
+            0xf81e0ff3,
+        );
+        let vm = interpreter.vm();
+        assert_eq!(
+            vm.read_register(&<VirtualMachine as ArmRange>::sp()),
+            (STACK - 0x20) as u128
+        );
+        assert_eq!(
+            vm.memory().read(STACK - 0x20, 8).unwrap(),
+            0x1111_2222_3333_4444_u64.to_le_bytes()
+        );
+    }
+
+    #[test]
+    fn test_ldr_post_index() {
+        let mut interpreter = interpreter();
+        let vm = interpreter.vm_mut();
+        vm.memory_mut().write(STACK, &0xaaaa_u64.to_le_bytes());
+
+        execute(
+            &mut interpreter,
+            ArmStatement::Ldr,
+            &["x19", "[sp]", "#0x20"],
+            0xf84207f3,
+        );
+        let vm = interpreter.vm();
+        assert_eq!(vm.read_register(&arm_register("x19")), 0xaaaa);
+        assert_eq!(
+            vm.read_register(&<VirtualMachine as ArmRange>::sp()),
+            (STACK + 0x20) as u128
+        );
+    }
+
+    #[test]
+    fn test_ldrb_reads_one_byte() {
+        let mut interpreter = interpreter();
+        let vm = interpreter.vm_mut();
+        vm.write_register(&arm_register("x1"), STACK as u128);
+        vm.write_register(&arm_register("x0"), u64::MAX as u128);
+        vm.memory_mut().write(STACK, &[0x80, 0xff, 0xff, 0xff]);
+
+        // ldrb w0, [x1]
+        execute(
+            &mut interpreter,
+            ArmStatement::Ldrb,
+            &["w0", "[x1]"],
+            0x39400020,
+        );
+        let vm = interpreter.vm();
+        assert_eq!(vm.read_register(&arm_register("x0")), 0x80);
+        assert_eq!(
+            vm.read_register(&arm_register("x1")),
+            STACK as u128,
+            "no writeback without an index mode"
+        );
+    }
+
+    #[test]
+    fn test_ldrsb_word_clears_upper_half() {
+        let mut interpreter = interpreter();
+        let vm = interpreter.vm_mut();
+        vm.write_register(&arm_register("x1"), STACK as u128);
+        vm.write_register(&arm_register("x0"), u64::MAX as u128);
+        vm.memory_mut().write(STACK, &[0x80, 0, 0, 0]);
+
+        // ldrsb w0, [x1]
+        execute(
+            &mut interpreter,
+            ArmStatement::Ldrsb,
+            &["w0", "[x1]"],
+            0x39c00020,
+        );
+        let vm = interpreter.vm();
+        assert_eq!(vm.read_register(&arm_register("x0")), 0xffff_ff80);
+    }
+
+    #[test]
+    fn test_ret_register() {
+        let ret = |arguments: &[&str], encoding| {
+            let instruction = instruction(ArmStatement::Ret, arguments, encoding);
+            crate::arch::create_ir_statement(MachineArchitecture::Arm64, &instruction)
+                .unwrap()
+                .first()
+                .cloned()
+        };
+
+        // ret x1
+        assert_eq!(ret(&["x1"], 0xd65f0020), Some(jump(o1())));
+        // ret
+        assert_eq!(ret(&[], 0xd65f03c0), Some(jump(x30.clone())));
+    }
+}
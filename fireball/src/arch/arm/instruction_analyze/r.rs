@@ -0,0 +1,47 @@
+use super::{super::static_register::*, shortcuts::*};
+use crate::core::Instruction;
+use std::ops::Deref;
+
+/// `ret {xn}`, returning through x30 unless another register is given
+pub(super) fn ret(instruction: &Instruction) -> &'static [IrStatement] {
+    if instruction.inner.arguments.is_empty() {
+        ret_x30()
+    } else {
+        ret_register()
+    }
+}
+
+#[box_to_static_reference]
+fn ret_x30() -> &'static [IrStatement] {
+    let jmp = jump(x30.clone());
+    let halt = halt();
+    [jmp, halt].into()
+}
+
+#[box_to_static_reference]
+fn ret_register() -> &'static [IrStatement] {
+    let jmp = jump(o1());
+    let halt = halt();
+    [jmp, halt].into()
+}
+
+#[inline]
+pub(super) fn retaa() -> &'static [IrStatement] {
+    ret_x30()
+}
+
+#[inline]
+pub(super) fn retab() -> &'static [IrStatement] {
+    ret_x30()
+}
+
+/// `ror xd, xs, #shift` or `ror xd, xn, xm`
+#[box_to_static_reference]
+pub(super) fn ror() -> &'static [IrStatement] {
+    let op = b::or(
+        b::shr(o2(), o3()),
+        b::shl(o2(), b::sub(bit_size_of_o2(), o3())),
+    );
+    let assignment = assign(op, o1(), o1_size());
+    [assignment].into()
+}
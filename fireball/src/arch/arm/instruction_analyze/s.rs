@@ -0,0 +1,80 @@
+use super::{
+    super::static_register::*,
+    pair::{self, PairAccess},
+    shortcuts::*,
+    single::{self, SingleAccess},
+};
+use crate::core::Instruction;
+use std::ops::Deref;
+
+/// `stp xt1, xt2, [xn, #imm]`, with pre/post-index writeback, see [`pair`]
+pub(super) fn stp(instruction: &Instruction) -> &'static [IrStatement] {
+    pair::lift(instruction, PairAccess::Store)
+}
+
+/// `str xt, [xn, #imm]`, with pre/post-index writeback, see [`single`]
+pub(super) fn str(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::Store)
+}
+
+/// `strb wt, [xn, #imm]`, see [`str`]
+pub(super) fn strb(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::StoreLow(1))
+}
+
+/// `strh wt, [xn, #imm]`, see [`str`]
+pub(super) fn strh(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::StoreLow(2))
+}
+
+/// `stur xt, [xn, #simm]`, see [`str`]
+pub(super) fn stur(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::Store)
+}
+
+/// `sturb wt, [xn, #simm]`, see [`str`]
+pub(super) fn sturb(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::StoreLow(1))
+}
+
+/// `sturh wt, [xn, #simm]`, see [`str`]
+pub(super) fn sturh(instruction: &Instruction) -> &'static [IrStatement] {
+    single::lift(instruction, SingleAccess::StoreLow(2))
+}
+
+/// `sbfx xd, xn, #lsb, #width`
+#[box_to_static_reference]
+pub(super) fn sbfx() -> &'static [IrStatement] {
+    let left = b::sub(bit_size_of_o1(), b::add(o3(), o4()));
+    let right = b::sub(bit_size_of_o1(), o4());
+    let op = b::sar(b::shl(o2(), left), right);
+    let assignment = assign(op, o1(), o1_size());
+    [assignment].into()
+}
+
+/// `sbfiz xd, xn, #lsb, #width`
+#[box_to_static_reference]
+pub(super) fn sbfiz() -> &'static [IrStatement] {
+    let shift = b::sub(bit_size_of_o1(), o4());
+    let field = b::sar(b::shl(o2(), shift.clone()), shift);
+    let assignment = assign(b::shl(field, o3()), o1(), o1_size());
+    [assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn sxtb() -> &'static [IrStatement] {
+    let assignment = sign_extend(sized(o2(), size_result_byte(c(1))), o1(), o1_size());
+    [assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn sxth() -> &'static [IrStatement] {
+    let assignment = sign_extend(sized(o2(), size_result_byte(c(2))), o1(), o1_size());
+    [assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn sxtw() -> &'static [IrStatement] {
+    let assignment = sign_extend(sized(o2(), size_result_byte(c(4))), o1(), o1_size());
+    [assignment].into()
+}
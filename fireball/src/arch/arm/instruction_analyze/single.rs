@@ -0,0 +1,168 @@
+//! Single register load/store instructions (`ldr`, `ldrb`, `ldrsw`, `ldur`, `str`, `strh`, ...)
+//!
+//! Like the pair instructions, the base register may be written back and the accessed size
+//! comes from the mnemonic rather than the register, so the statements depend on the operands
+//! of the instruction. They are built once per distinct instruction.
+
+use super::{
+    pair::{self, IndexMode},
+    shortcuts::*,
+};
+use crate::{core::Instruction, ir::data::IrData, utils::Aos};
+use iceball::{Argument, ArmRegister, Memory, Register, RelativeAddressingArgument};
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SingleAccess {
+    /// `ldr`, `ldur`, as many bytes as the register holds
+    Load,
+    /// `ldrb`, `ldrh`, zero extended into the register
+    LoadUnsigned(usize),
+    /// `ldrsb`, `ldrsh`, `ldrsw`, sign extended into the register
+    LoadSigned(usize),
+    /// `str`, `stur`, as many bytes as the register holds
+    Store,
+    /// `strb`, `strh`, the low bytes of the register
+    StoreLow(usize),
+}
+
+/// Lifts a single register load or store.
+///
+/// ### Arguments
+/// - `instruction: &Instruction` - load/store instruction
+/// - `access: SingleAccess` - what the instruction does with the register
+///
+/// ### Returns
+/// - `&'static [IrStatement]` - statements of the instruction, `exception` if the operands
+///   don't form a register access
+pub(super) fn lift(instruction: &Instruction, access: SingleAccess) -> &'static [IrStatement] {
+    static CACHE: LazyLock<Mutex<HashMap<String, &'static [IrStatement]>>> =
+        LazyLock::new(Default::default);
+
+    let mode = index_mode(instruction);
+    let key = format!("{:?} {:?} {}", access, mode, instruction.inner);
+    let mut cache = CACHE.lock().unwrap();
+    cache.entry(key).or_insert_with(|| {
+        let statements = build(&instruction.inner.arguments, access, mode)
+            .unwrap_or_else(|| vec![exception("invalid register access")]);
+        Box::leak(statements.into_boxed_slice())
+    })
+}
+
+/// Reads the addressing mode from the encoding, only the immediate forms with bits 25:24
+/// and 21 clear index the base, by bits 11:10.
+fn index_mode(instruction: &Instruction) -> IndexMode {
+    match instruction.inner.bytes.as_deref() {
+        Some(&[b0, b1, b2, b3]) => {
+            let encoding = u32::from_le_bytes([b0, b1, b2, b3]);
+            let indexed = (encoding >> 27) & 0b111 == 0b111
+                && (encoding >> 24) & 0b11 == 0
+                && (encoding >> 21) & 1 == 0;
+            match (encoding >> 10) & 0b11 {
+                0b01 if indexed => IndexMode::PostIndex,
+                0b11 if indexed => IndexMode::PreIndex,
+                _ => IndexMode::Offset,
+            }
+        }
+        // the post-index immediate is the only operand after the memory one
+        _ if instruction.inner.arguments.len() > 2 => IndexMode::PostIndex,
+        _ => IndexMode::Offset,
+    }
+}
+
+fn build(
+    arguments: &[Argument],
+    access: SingleAccess,
+    mode: IndexMode,
+) -> Option<Vec<IrStatement>> {
+    let [
+        Argument::Register(Register::Arm(register)),
+        memory,
+        rest @ ..,
+    ] = arguments
+    else {
+        return None;
+    };
+    let (address, base) = match memory {
+        Argument::Memory(Memory::RelativeAddressing(terms)) => match terms.first() {
+            Some(RelativeAddressingArgument::Register(Register::Arm(base))) => {
+                (pair::dereferenced_address(memory)?, Some(*base))
+            }
+            _ => (pair::dereferenced_address(memory)?, None),
+        },
+        Argument::Memory(Memory::AbsoluteAddressing(_)) => {
+            (pair::dereferenced_address(memory)?, None)
+        }
+        // `ldr x0, #0x1000` loads from a literal pool
+        Argument::Constant(_)
+            if !matches!(access, SingleAccess::Store | SingleAccess::StoreLow(_)) =>
+        {
+            (memory.into(), None)
+        }
+        _ => return None,
+    };
+    let size = match access {
+        SingleAccess::Load | SingleAccess::Store => pair::register_byte_size(*register)?,
+        SingleAccess::LoadUnsigned(size)
+        | SingleAccess::LoadSigned(size)
+        | SingleAccess::StoreLow(size) => size,
+    };
+
+    let value = sized(d(address.clone()), size_result_byte(c(size)));
+    let extended = pair::extended_register(*register);
+    // writes to W registers clear the upper half of the X register
+    let is_word = extended != *register;
+    let mut statements = Vec::new();
+    match access {
+        SingleAccess::Store | SingleAccess::StoreLow(_) => statements.push(assign(
+            sized(pair::register_data(*register), size_result_byte(c(size))),
+            d(address.clone()),
+            size_result_byte(c(size)),
+        )),
+        SingleAccess::LoadSigned(_) if is_word => {
+            statements.push(assign(
+                u::sign_extend(value),
+                pair::register_data(*register),
+                size_result_byte(c(4)),
+            ));
+            statements.push(assign(
+                u::zero_extend(sized(
+                    pair::register_data(*register),
+                    size_result_byte(c(4)),
+                )),
+                pair::register_data(extended),
+                size_result_byte(c(8)),
+            ));
+        }
+        SingleAccess::LoadSigned(_) => statements.push(assign(
+            u::sign_extend(value),
+            pair::register_data(*register),
+            size_result_byte(c(8)),
+        )),
+        SingleAccess::LoadUnsigned(_) => statements.push(assign(
+            u::zero_extend(value),
+            pair::register_data(extended),
+            size_result_byte(c(8)),
+        )),
+        SingleAccess::Load if is_word => statements.push(assign(
+            u::zero_extend(value),
+            pair::register_data(extended),
+            size_result_byte(c(8)),
+        )),
+        SingleAccess::Load => statements.push(assign(
+            value,
+            pair::register_data(*register),
+            size_result_byte(c(size)),
+        )),
+    }
+
+    match base {
+        Some(base) => statements.extend(pair::writeback(mode, base, address, rest)?),
+        None if mode != IndexMode::Offset => return None,
+        None => {}
+    }
+    Some(statements)
+}
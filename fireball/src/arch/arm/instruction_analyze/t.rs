@@ -0,0 +1,11 @@
+use super::{super::static_register::*, shortcuts::*};
+use std::ops::Deref;
+
+#[box_to_static_reference]
+pub(super) fn tst() -> &'static [IrStatement] {
+    let and = b::and(o1(), o2());
+    let calc_flags = calc_flags_automatically(and, o1_size(), &[&pstate_n, &pstate_z]);
+    let set_c = assign(c(0), pstate_c.clone(), size_relative(pstate_c.clone()));
+    let set_v = assign(c(0), pstate_v.clone(), size_relative(pstate_v.clone()));
+    [calc_flags, set_c, set_v].into()
+}
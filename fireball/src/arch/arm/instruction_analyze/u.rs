@@ -0,0 +1,32 @@
+use super::{super::static_register::*, shortcuts::*};
+use std::ops::Deref;
+
+/// `ubfx xd, xn, #lsb, #width`
+#[box_to_static_reference]
+pub(super) fn ubfx() -> &'static [IrStatement] {
+    let mask = b::sub(b::shl(c(1), o4()), c(1));
+    let op = b::and(b::shr(o2(), o3()), mask);
+    let assignment = assign(op, o1(), o1_size());
+    [assignment].into()
+}
+
+/// `ubfiz xd, xn, #lsb, #width`
+#[box_to_static_reference]
+pub(super) fn ubfiz() -> &'static [IrStatement] {
+    let mask = b::sub(b::shl(c(1), o4()), c(1));
+    let op = b::shl(b::and(o2(), mask), o3());
+    let assignment = assign(op, o1(), o1_size());
+    [assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn uxtb() -> &'static [IrStatement] {
+    let assignment = assign(b::and(o2(), c(0xFF)), o1(), o1_size());
+    [assignment].into()
+}
+
+#[box_to_static_reference]
+pub(super) fn uxth() -> &'static [IrStatement] {
+    let assignment = assign(b::and(o2(), c(0xFFFF)), o1(), o1_size());
+    [assignment].into()
+}
@@ -65,6 +65,6 @@ use std::ops::Deref;
 ///     otherwise // do nothing
 /// ```
 #[box_to_static_reference]
-pub(super) fn yield() -> &'static [IrStatement] {
+pub(super) fn r#yield() -> &'static [IrStatement] {
     [exception("yield")].into()
 }
@@ -0,0 +1,801 @@
+//! Module that converts ARM/AArch64 architecture instructions into IR
+
+pub mod instruction_analyze;
+
+pub(crate) mod static_register {
+    #![allow(non_upper_case_globals, unused)]
+    use crate::{
+        ir::{Register, VirtualMachine, arm::ArmRange as ARM, data::IrData},
+        utils::Aos,
+    };
+    use std::sync::LazyLock;
+
+    macro_rules! static_register {
+        ($name:ident) => {
+            pub(crate) static $name: LazyLock<Aos<IrData>> = LazyLock::new(|| {
+                Aos::new_static(IrData::Register(<VirtualMachine as ARM>::$name()))
+            });
+        };
+    }
+
+    static_register!(x0);
+    static_register!(w0);
+
+    static_register!(x1);
+    static_register!(w1);
+
+    static_register!(x2);
+    static_register!(w2);
+
+    static_register!(x3);
+    static_register!(w3);
+
+    static_register!(x4);
+    static_register!(w4);
+
+    static_register!(x5);
+    static_register!(w5);
+
+    static_register!(x6);
+    static_register!(w6);
+
+    static_register!(x7);
+    static_register!(w7);
+
+    static_register!(x8);
+    static_register!(w8);
+
+    static_register!(x9);
+    static_register!(w9);
+
+    static_register!(x10);
+    static_register!(w10);
+
+    static_register!(x11);
+    static_register!(w11);
+
+    static_register!(x12);
+    static_register!(w12);
+
+    static_register!(x13);
+    static_register!(w13);
+
+    static_register!(x14);
+    static_register!(w14);
+
+    static_register!(x15);
+    static_register!(w15);
+
+    static_register!(x16);
+    static_register!(w16);
+
+    static_register!(x17);
+    static_register!(w17);
+
+    static_register!(x18);
+    static_register!(w18);
+
+    static_register!(x19);
+    static_register!(w19);
+
+    static_register!(x20);
+    static_register!(w20);
+
+    static_register!(x21);
+    static_register!(w21);
+
+    static_register!(x22);
+    static_register!(w22);
+
+    static_register!(x23);
+    static_register!(w23);
+
+    static_register!(x24);
+    static_register!(w24);
+
+    static_register!(x25);
+    static_register!(w25);
+
+    static_register!(x26);
+    static_register!(w26);
+
+    static_register!(x27);
+    static_register!(w27);
+
+    static_register!(x28);
+    static_register!(w28);
+
+    static_register!(x29);
+    static_register!(w29);
+
+    static_register!(x30);
+    static_register!(w30);
+
+    static_register!(sp);
+    static_register!(wsp);
+
+    /// Zero register, reads as zero.
+    static_register!(xzr);
+    static_register!(wzr);
+
+    static_register!(pc);
+
+    /// Condition flags, `N`, `Z`, `C`, `V` from msb.
+    static_register!(nzcv);
+    /// Negative condition flag.
+    static_register!(pstate_n);
+    /// Zero condition flag.
+    static_register!(pstate_z);
+    /// Carry condition flag.
+    static_register!(pstate_c);
+    /// Overflow condition flag.
+    static_register!(pstate_v);
+
+    static_register!(v0);
+    static_register!(q0);
+    static_register!(d0);
+    static_register!(s0);
+    static_register!(h0);
+    static_register!(b0);
+
+    static_register!(v1);
+    static_register!(q1);
+    static_register!(d1);
+    static_register!(s1);
+    static_register!(h1);
+    static_register!(b1);
+
+    static_register!(v2);
+    static_register!(q2);
+    static_register!(d2);
+    static_register!(s2);
+    static_register!(h2);
+    static_register!(b2);
+
+    static_register!(v3);
+    static_register!(q3);
+    static_register!(d3);
+    static_register!(s3);
+    static_register!(h3);
+    static_register!(b3);
+
+    static_register!(v4);
+    static_register!(q4);
+    static_register!(d4);
+    static_register!(s4);
+    static_register!(h4);
+    static_register!(b4);
+
+    static_register!(v5);
+    static_register!(q5);
+    static_register!(d5);
+    static_register!(s5);
+    static_register!(h5);
+    static_register!(b5);
+
+    static_register!(v6);
+    static_register!(q6);
+    static_register!(d6);
+    static_register!(s6);
+    static_register!(h6);
+    static_register!(b6);
+
+    static_register!(v7);
+    static_register!(q7);
+    static_register!(d7);
+    static_register!(s7);
+    static_register!(h7);
+    static_register!(b7);
+
+    static_register!(v8);
+    static_register!(q8);
+    static_register!(d8);
+    static_register!(s8);
+    static_register!(h8);
+    static_register!(b8);
+
+    static_register!(v9);
+    static_register!(q9);
+    static_register!(d9);
+    static_register!(s9);
+    static_register!(h9);
+    static_register!(b9);
+
+    static_register!(v10);
+    static_register!(q10);
+    static_register!(d10);
+    static_register!(s10);
+    static_register!(h10);
+    static_register!(b10);
+
+    static_register!(v11);
+    static_register!(q11);
+    static_register!(d11);
+    static_register!(s11);
+    static_register!(h11);
+    static_register!(b11);
+
+    static_register!(v12);
+    static_register!(q12);
+    static_register!(d12);
+    static_register!(s12);
+    static_register!(h12);
+    static_register!(b12);
+
+    static_register!(v13);
+    static_register!(q13);
+    static_register!(d13);
+    static_register!(s13);
+    static_register!(h13);
+    static_register!(b13);
+
+    static_register!(v14);
+    static_register!(q14);
+    static_register!(d14);
+    static_register!(s14);
+    static_register!(h14);
+    static_register!(b14);
+
+    static_register!(v15);
+    static_register!(q15);
+    static_register!(d15);
+    static_register!(s15);
+    static_register!(h15);
+    static_register!(b15);
+
+    static_register!(v16);
+    static_register!(q16);
+    static_register!(d16);
+    static_register!(s16);
+    static_register!(h16);
+    static_register!(b16);
+
+    static_register!(v17);
+    static_register!(q17);
+    static_register!(d17);
+    static_register!(s17);
+    static_register!(h17);
+    static_register!(b17);
+
+    static_register!(v18);
+    static_register!(q18);
+    static_register!(d18);
+    static_register!(s18);
+    static_register!(h18);
+    static_register!(b18);
+
+    static_register!(v19);
+    static_register!(q19);
+    static_register!(d19);
+    static_register!(s19);
+    static_register!(h19);
+    static_register!(b19);
+
+    static_register!(v20);
+    static_register!(q20);
+    static_register!(d20);
+    static_register!(s20);
+    static_register!(h20);
+    static_register!(b20);
+
+    static_register!(v21);
+    static_register!(q21);
+    static_register!(d21);
+    static_register!(s21);
+    static_register!(h21);
+    static_register!(b21);
+
+    static_register!(v22);
+    static_register!(q22);
+    static_register!(d22);
+    static_register!(s22);
+    static_register!(h22);
+    static_register!(b22);
+
+    static_register!(v23);
+    static_register!(q23);
+    static_register!(d23);
+    static_register!(s23);
+    static_register!(h23);
+    static_register!(b23);
+
+    static_register!(v24);
+    static_register!(q24);
+    static_register!(d24);
+    static_register!(s24);
+    static_register!(h24);
+    static_register!(b24);
+
+    static_register!(v25);
+    static_register!(q25);
+    static_register!(d25);
+    static_register!(s25);
+    static_register!(h25);
+    static_register!(b25);
+
+    static_register!(v26);
+    static_register!(q26);
+    static_register!(d26);
+    static_register!(s26);
+    static_register!(h26);
+    static_register!(b26);
+
+    static_register!(v27);
+    static_register!(q27);
+    static_register!(d27);
+    static_register!(s27);
+    static_register!(h27);
+    static_register!(b27);
+
+    static_register!(v28);
+    static_register!(q28);
+    static_register!(d28);
+    static_register!(s28);
+    static_register!(h28);
+    static_register!(b28);
+
+    static_register!(v29);
+    static_register!(q29);
+    static_register!(d29);
+    static_register!(s29);
+    static_register!(h29);
+    static_register!(b29);
+
+    static_register!(v30);
+    static_register!(q30);
+    static_register!(d30);
+    static_register!(s30);
+    static_register!(h30);
+    static_register!(b30);
+
+    static_register!(v31);
+    static_register!(q31);
+    static_register!(d31);
+    static_register!(s31);
+    static_register!(h31);
+    static_register!(b31);
+
+    static_register!(fpcr);
+    static_register!(fpsr);
+
+    static_register!(tmp64);
+    static_register!(tmp32);
+
+    static_register!(tmp2_64);
+    static_register!(tmp2_32);
+}
+
+/// Returns the register with the name, including the temporary ones used by the ir.
+pub(crate) fn arm_register_by_name(
+    data: &str,
+) -> Option<crate::utils::Aos<crate::ir::data::IrData>> {
+    let data = data.to_ascii_lowercase();
+    macro_rules! str_to_reg {
+        ($name:ident) => {
+            if data == stringify!($name) {
+                return Some(static_register::$name.clone());
+            }
+        };
+    }
+    macro_rules! str_to_alias {
+        ($alias:literal, $name:ident) => {
+            if data == $alias {
+                return Some(static_register::$name.clone());
+            }
+        };
+    }
+
+    str_to_alias!("fp", x29);
+    str_to_alias!("lr", x30);
+
+    str_to_reg!(x0);
+    str_to_reg!(w0);
+
+    str_to_reg!(x1);
+    str_to_reg!(w1);
+
+    str_to_reg!(x2);
+    str_to_reg!(w2);
+
+    str_to_reg!(x3);
+    str_to_reg!(w3);
+
+    str_to_reg!(x4);
+    str_to_reg!(w4);
+
+    str_to_reg!(x5);
+    str_to_reg!(w5);
+
+    str_to_reg!(x6);
+    str_to_reg!(w6);
+
+    str_to_reg!(x7);
+    str_to_reg!(w7);
+
+    str_to_reg!(x8);
+    str_to_reg!(w8);
+
+    str_to_reg!(x9);
+    str_to_reg!(w9);
+
+    str_to_reg!(x10);
+    str_to_reg!(w10);
+
+    str_to_reg!(x11);
+    str_to_reg!(w11);
+
+    str_to_reg!(x12);
+    str_to_reg!(w12);
+
+    str_to_reg!(x13);
+    str_to_reg!(w13);
+
+    str_to_reg!(x14);
+    str_to_reg!(w14);
+
+    str_to_reg!(x15);
+    str_to_reg!(w15);
+
+    str_to_reg!(x16);
+    str_to_reg!(w16);
+
+    str_to_reg!(x17);
+    str_to_reg!(w17);
+
+    str_to_reg!(x18);
+    str_to_reg!(w18);
+
+    str_to_reg!(x19);
+    str_to_reg!(w19);
+
+    str_to_reg!(x20);
+    str_to_reg!(w20);
+
+    str_to_reg!(x21);
+    str_to_reg!(w21);
+
+    str_to_reg!(x22);
+    str_to_reg!(w22);
+
+    str_to_reg!(x23);
+    str_to_reg!(w23);
+
+    str_to_reg!(x24);
+    str_to_reg!(w24);
+
+    str_to_reg!(x25);
+    str_to_reg!(w25);
+
+    str_to_reg!(x26);
+    str_to_reg!(w26);
+
+    str_to_reg!(x27);
+    str_to_reg!(w27);
+
+    str_to_reg!(x28);
+    str_to_reg!(w28);
+
+    str_to_reg!(x29);
+    str_to_reg!(w29);
+
+    str_to_reg!(x30);
+    str_to_reg!(w30);
+
+    str_to_reg!(sp);
+    str_to_reg!(wsp);
+
+    str_to_reg!(xzr);
+    str_to_reg!(wzr);
+
+    str_to_reg!(pc);
+
+    str_to_reg!(nzcv);
+    str_to_reg!(pstate_n);
+    str_to_reg!(pstate_z);
+    str_to_reg!(pstate_c);
+    str_to_reg!(pstate_v);
+
+    str_to_reg!(v0);
+    str_to_reg!(q0);
+    str_to_reg!(d0);
+    str_to_reg!(s0);
+    str_to_reg!(h0);
+    str_to_reg!(b0);
+
+    str_to_reg!(v1);
+    str_to_reg!(q1);
+    str_to_reg!(d1);
+    str_to_reg!(s1);
+    str_to_reg!(h1);
+    str_to_reg!(b1);
+
+    str_to_reg!(v2);
+    str_to_reg!(q2);
+    str_to_reg!(d2);
+    str_to_reg!(s2);
+    str_to_reg!(h2);
+    str_to_reg!(b2);
+
+    str_to_reg!(v3);
+    str_to_reg!(q3);
+    str_to_reg!(d3);
+    str_to_reg!(s3);
+    str_to_reg!(h3);
+    str_to_reg!(b3);
+
+    str_to_reg!(v4);
+    str_to_reg!(q4);
+    str_to_reg!(d4);
+    str_to_reg!(s4);
+    str_to_reg!(h4);
+    str_to_reg!(b4);
+
+    str_to_reg!(v5);
+    str_to_reg!(q5);
+    str_to_reg!(d5);
+    str_to_reg!(s5);
+    str_to_reg!(h5);
+    str_to_reg!(b5);
+
+    str_to_reg!(v6);
+    str_to_reg!(q6);
+    str_to_reg!(d6);
+    str_to_reg!(s6);
+    str_to_reg!(h6);
+    str_to_reg!(b6);
+
+    str_to_reg!(v7);
+    str_to_reg!(q7);
+    str_to_reg!(d7);
+    str_to_reg!(s7);
+    str_to_reg!(h7);
+    str_to_reg!(b7);
+
+    str_to_reg!(v8);
+    str_to_reg!(q8);
+    str_to_reg!(d8);
+    str_to_reg!(s8);
+    str_to_reg!(h8);
+    str_to_reg!(b8);
+
+    str_to_reg!(v9);
+    str_to_reg!(q9);
+    str_to_reg!(d9);
+    str_to_reg!(s9);
+    str_to_reg!(h9);
+    str_to_reg!(b9);
+
+    str_to_reg!(v10);
+    str_to_reg!(q10);
+    str_to_reg!(d10);
+    str_to_reg!(s10);
+    str_to_reg!(h10);
+    str_to_reg!(b10);
+
+    str_to_reg!(v11);
+    str_to_reg!(q11);
+    str_to_reg!(d11);
+    str_to_reg!(s11);
+    str_to_reg!(h11);
+    str_to_reg!(b11);
+
+    str_to_reg!(v12);
+    str_to_reg!(q12);
+    str_to_reg!(d12);
+    str_to_reg!(s12);
+    str_to_reg!(h12);
+    str_to_reg!(b12);
+
+    str_to_reg!(v13);
+    str_to_reg!(q13);
+    str_to_reg!(d13);
+    str_to_reg!(s13);
+    str_to_reg!(h13);
+    str_to_reg!(b13);
+
+    str_to_reg!(v14);
+    str_to_reg!(q14);
+    str_to_reg!(d14);
+    str_to_reg!(s14);
+    str_to_reg!(h14);
+    str_to_reg!(b14);
+
+    str_to_reg!(v15);
+    str_to_reg!(q15);
+    str_to_reg!(d15);
+    str_to_reg!(s15);
+    str_to_reg!(h15);
+    str_to_reg!(b15);
+
+    str_to_reg!(v16);
+    str_to_reg!(q16);
+    str_to_reg!(d16);
+    str_to_reg!(s16);
+    str_to_reg!(h16);
+    str_to_reg!(b16);
+
+    str_to_reg!(v17);
+    str_to_reg!(q17);
+    str_to_reg!(d17);
+    str_to_reg!(s17);
+    str_to_reg!(h17);
+    str_to_reg!(b17);
+
+    str_to_reg!(v18);
+    str_to_reg!(q18);
+    str_to_reg!(d18);
+    str_to_reg!(s18);
+    str_to_reg!(h18);
+    str_to_reg!(b18);
+
+    str_to_reg!(v19);
+    str_to_reg!(q19);
+    str_to_reg!(d19);
+    str_to_reg!(s19);
+    str_to_reg!(h19);
+    str_to_reg!(b19);
+
+    str_to_reg!(v20);
+    str_to_reg!(q20);
+    str_to_reg!(d20);
+    str_to_reg!(s20);
+    str_to_reg!(h20);
+    str_to_reg!(b20);
+
+    str_to_reg!(v21);
+    str_to_reg!(q21);
+    str_to_reg!(d21);
+    str_to_reg!(s21);
+    str_to_reg!(h21);
+    str_to_reg!(b21);
+
+    str_to_reg!(v22);
+    str_to_reg!(q22);
+    str_to_reg!(d22);
+    str_to_reg!(s22);
+    str_to_reg!(h22);
+    str_to_reg!(b22);
+
+    str_to_reg!(v23);
+    str_to_reg!(q23);
+    str_to_reg!(d23);
+    str_to_reg!(s23);
+    str_to_reg!(h23);
+    str_to_reg!(b23);
+
+    str_to_reg!(v24);
+    str_to_reg!(q24);
+    str_to_reg!(d24);
+    str_to_reg!(s24);
+    str_to_reg!(h24);
+    str_to_reg!(b24);
+
+    str_to_reg!(v25);
+    str_to_reg!(q25);
+    str_to_reg!(d25);
+    str_to_reg!(s25);
+    str_to_reg!(h25);
+    str_to_reg!(b25);
+
+    str_to_reg!(v26);
+    str_to_reg!(q26);
+    str_to_reg!(d26);
+    str_to_reg!(s26);
+    str_to_reg!(h26);
+    str_to_reg!(b26);
+
+    str_to_reg!(v27);
+    str_to_reg!(q27);
+    str_to_reg!(d27);
+    str_to_reg!(s27);
+    str_to_reg!(h27);
+    str_to_reg!(b27);
+
+    str_to_reg!(v28);
+    str_to_reg!(q28);
+    str_to_reg!(d28);
+    str_to_reg!(s28);
+    str_to_reg!(h28);
+    str_to_reg!(b28);
+
+    str_to_reg!(v29);
+    str_to_reg!(q29);
+    str_to_reg!(d29);
+    str_to_reg!(s29);
+    str_to_reg!(h29);
+    str_to_reg!(b29);
+
+    str_to_reg!(v30);
+    str_to_reg!(q30);
+    str_to_reg!(d30);
+    str_to_reg!(s30);
+    str_to_reg!(h30);
+    str_to_reg!(b30);
+
+    str_to_reg!(v31);
+    str_to_reg!(q31);
+    str_to_reg!(d31);
+    str_to_reg!(s31);
+    str_to_reg!(h31);
+    str_to_reg!(b31);
+
+    str_to_reg!(fpcr);
+    str_to_reg!(fpsr);
+
+    str_to_reg!(tmp32);
+    str_to_reg!(tmp64);
+    str_to_reg!(tmp2_32);
+    str_to_reg!(tmp2_64);
+
+    None
+}
+
+pub(crate) fn str_to_arm_register(data: &str) -> crate::utils::Aos<crate::ir::data::IrData> {
+    arm_register_by_name(data).unwrap_or_else(|| unreachable!("{}", data))
+}
+
+/// Builds the flag expression that holds when the given condition code is satisfied.
+///
+/// ### Arguments
+/// - `condition: iceball::ArmCondition` - condition code of the instruction
+///
+/// ### Returns
+/// - `Aos<IrData>` - expression over `pstate_n`, `pstate_z`, `pstate_c` and `pstate_v`
+pub(crate) fn condition_to_ir(
+    condition: iceball::ArmCondition,
+) -> crate::utils::Aos<crate::ir::data::IrData> {
+    use crate::{
+        ir::{data::*, operator::*},
+        utils::Aos,
+    };
+    use iceball::ArmCondition;
+    use static_register::*;
+
+    let not = |arg: Aos<IrData>| -> Aos<IrData> {
+        IrData::Operation(IrDataOperation::Unary {
+            operator: IrUnaryOperator::Not,
+            arg,
+        })
+        .into()
+    };
+    let binary = |operator: IrBinaryOperator, arg1: Aos<IrData>, arg2: Aos<IrData>| {
+        Aos::<IrData>::from(IrData::Operation(IrDataOperation::Binary {
+            operator,
+            arg1,
+            arg2,
+        }))
+    };
+    let n_eq_v = || {
+        binary(
+            IrBinaryOperator::Equal(IrAccessSize::RelativeWith(pstate_n.clone())),
+            pstate_n.clone(),
+            pstate_v.clone(),
+        )
+    };
+
+    match condition {
+        ArmCondition::Eq => pstate_z.clone(),
+        ArmCondition::Ne => not(pstate_z.clone()),
+        ArmCondition::Cs | ArmCondition::Hs => pstate_c.clone(),
+        ArmCondition::Cc | ArmCondition::Lo => not(pstate_c.clone()),
+        ArmCondition::Mi => pstate_n.clone(),
+        ArmCondition::Pl => not(pstate_n.clone()),
+        ArmCondition::Vs => pstate_v.clone(),
+        ArmCondition::Vc => not(pstate_v.clone()),
+        ArmCondition::Hi => binary(
+            IrBinaryOperator::And,
+            pstate_c.clone(),
+            not(pstate_z.clone()),
+        ),
+        ArmCondition::Ls => binary(
+            IrBinaryOperator::Or,
+            not(pstate_c.clone()),
+            pstate_z.clone(),
+        ),
+        ArmCondition::Ge => n_eq_v(),
+        ArmCondition::Lt => not(n_eq_v()),
+        ArmCondition::Gt => binary(IrBinaryOperator::And, not(pstate_z.clone()), n_eq_v()),
+        ArmCondition::Le => binary(IrBinaryOperator::Or, pstate_z.clone(), not(n_eq_v())),
+        ArmCondition::Al | ArmCondition::Nv => IrData::Constant(1).into(),
+    }
+}
@@ -5,6 +5,7 @@ use capstone::arch::BuildsCapstone;
 pub(crate) use iceball::MachineArchitecture;
 use std::pin::Pin;
 
+pub mod arm;
 pub mod x86_64;
 
 pub(crate) fn from_pe_machine(machine: u16, is_64: bool) -> MachineArchitecture {
@@ -72,6 +73,30 @@ pub(crate) fn create_ir_statement(
         MachineArchitecture::X86 | MachineArchitecture::X64 => {
             x86_64::instruction_analyze::create_ir_statement(instruction)
         }
-        MachineArchitecture::Arm | MachineArchitecture::Arm64 => None,
+        MachineArchitecture::Arm64 => arm::instruction_analyze::create_ir_statement(instruction),
+        MachineArchitecture::Arm => None,
+    }
+}
+
+/// Returns the operand holding the destination of a branch instruction.
+///
+/// ### Arguments
+/// - `inst: &iceball::Instruction` : branch, call or return instruction
+///
+/// ### Returns
+/// `Option<&iceball::Argument>` : destination operand, or `None` if it can't be determined.
+///
+/// ### Note
+/// x86 branches take the destination as their only operand, while AArch64 places it last
+/// (`cbz x0, target`, `tbnz w1, #3, target`) after any register or bit operands.
+pub(crate) fn branch_target_argument(inst: &iceball::Instruction) -> Option<&iceball::Argument> {
+    match &inst.statement {
+        Ok(iceball::Statement::Arm(_)) => inst
+            .arguments
+            .iter()
+            .rev()
+            .find(|x| !matches!(x, iceball::Argument::Condition(_))),
+        _ if inst.arguments.len() == 1 => inst.arguments.first(),
+        _ => None,
     }
 }
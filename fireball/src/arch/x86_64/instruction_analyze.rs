@@ -23,7 +23,7 @@ mod w_generated;
 mod x;
 
 #[path = "instruction_analyze_shortcuts"]
-mod shortcuts {
+pub(in crate::arch) mod shortcuts {
     #![allow(non_upper_case_globals, unused_imports)]
 
     mod data;
@@ -19,6 +19,72 @@ pub(super) fn push() -> &'static [IrStatement] {
         rsp.clone(),
         size_architecture(),
     );
-    let push = assign(rsp.clone(), o1(), o1_size());
+    let push = assign(o1(), d(rsp.clone()), o1_size());
     [set_sp, push].into()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core::{Address, Instruction, Sections},
+        ir::{Interpreter, Ir, SparseMemory, VirtualMachine, x86_64::X64Range},
+    };
+    use iceball::{MachineArchitecture, Statement, X64Statement};
+
+    const STACK: u64 = 0x7000_0000;
+
+    fn execute(interpreter: &mut Interpreter, statement: X64Statement, argument: &str) {
+        let instruction = Instruction {
+            address: 0x1000,
+            inner: iceball::Instruction {
+                statement: Ok(Statement::X64(statement)),
+                arguments: [iceball::parse_argument(MachineArchitecture::X64, argument).unwrap()]
+                    .into(),
+                bytes: None,
+            },
+        };
+        let ir = Ir {
+            address: Address::from_virtual_address(&Sections::new(), instruction.address),
+            statements: crate::arch::create_ir_statement(MachineArchitecture::X64, &instruction),
+        };
+        interpreter.execute_instruction(&ir, &instruction).unwrap();
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut memory = SparseMemory::default();
+        memory.write(STACK - 0x1000, &[0; 0x2000]);
+        let mut interpreter = Interpreter::new(MachineArchitecture::X64, memory);
+        let vm = interpreter.vm_mut();
+        vm.write_register(&<VirtualMachine as X64Range>::rsp(), STACK as u128);
+        vm.write_register(&<VirtualMachine as X64Range>::rbp(), 0x1122_3344_5566_7788);
+
+        // the value is stored at the decremented stack pointer
+        execute(&mut interpreter, X64Statement::Push, "rbp");
+        let vm = interpreter.vm();
+        assert_eq!(
+            vm.read_register(&<VirtualMachine as X64Range>::rsp()),
+            (STACK - 8) as u128
+        );
+        assert_eq!(
+            vm.memory().read(STACK - 8, 8).unwrap(),
+            0x1122_3344_5566_7788_u64.to_le_bytes()
+        );
+        assert_eq!(
+            vm.read_register(&<VirtualMachine as X64Range>::rbp()),
+            0x1122_3344_5566_7788,
+            "the pushed register is left untouched"
+        );
+
+        execute(&mut interpreter, X64Statement::Pop, "rcx");
+        let vm = interpreter.vm();
+        assert_eq!(
+            vm.read_register(&<VirtualMachine as X64Range>::rcx()),
+            0x1122_3344_5566_7788
+        );
+        assert_eq!(
+            vm.read_register(&<VirtualMachine as X64Range>::rsp()),
+            STACK as u128
+        );
+    }
+}
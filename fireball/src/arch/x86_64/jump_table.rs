@@ -0,0 +1,491 @@
+//! Module recovering the jump tables compiled `switch` statements dispatch through
+//!
+//! Handles the layouts emitted by MSVC, GCC and Clang:
+//! - `jmp qword ptr [table + index * 8]` - table of absolute destinations
+//! - `movsxd rax, dword ptr [rdx + index * 4]; add rax, rdx; jmp rax` - offsets from the table
+//! - `mov eax, dword ptr [rdx + index * 4 + rva]; add rax, rdx; jmp rax` - offsets from the
+//!   image base
+
+use crate::{
+    arch::x86_64::str_to_x64_register,
+    core::{Address, Blocks, Instruction, JumpTable, RelationType, Sections},
+    ir::data::IrData,
+    prelude::*,
+    utils::Aos,
+};
+use iceball::{
+    AddressingOperator, Argument, MachineArchitecture, Memory, RelativeAddressingArgument,
+    X64Statement,
+};
+
+/// Most entries a bounds check is trusted for
+pub(crate) const MAX_JUMP_TABLE_ENTRIES: u64 = 512;
+
+/// How an entry of a jump table turns into a destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JumpTableEntryKind {
+    /// The entry is the destination itself
+    Absolute,
+    /// The destination is `base + entry`
+    Relative { base: u64, signed: bool },
+}
+
+/// Jump table found from the instructions leading to an indirect jump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct JumpTableLayout {
+    /// Virtual address of the first entry
+    pub(crate) table: u64,
+    /// Size of an entry in bytes
+    pub(crate) entry_size: u8,
+    pub(crate) entry_kind: JumpTableEntryKind,
+    /// Register indexing the table
+    pub(crate) index: iceball::Register,
+}
+
+/// `[base + index * scale + displacement]`
+struct MemoryOperand {
+    base: Option<iceball::Register>,
+    index: Option<(iceball::Register, u8)>,
+    displacement: i128,
+}
+
+impl MemoryOperand {
+    fn parse(args: &[RelativeAddressingArgument]) -> Option<Self> {
+        let mut result = Self {
+            base: None,
+            index: None,
+            displacement: 0,
+        };
+        let mut sign = 1;
+        let mut i = 0;
+        while i < args.len() {
+            match args[i] {
+                RelativeAddressingArgument::Operator(AddressingOperator::Add) => sign = 1,
+                RelativeAddressingArgument::Operator(AddressingOperator::Sub) => sign = -1,
+                RelativeAddressingArgument::Operator(AddressingOperator::Mul)
+                | RelativeAddressingArgument::ExtendedRegister(..) => return None,
+                RelativeAddressingArgument::Constant(c) => result.displacement += sign * c,
+                RelativeAddressingArgument::Register(register) => {
+                    if let [
+                        RelativeAddressingArgument::Operator(AddressingOperator::Mul),
+                        RelativeAddressingArgument::Constant(scale),
+                    ] = args[i + 1..args.len().min(i + 3)]
+                    {
+                        if result.index.is_some() {
+                            return None;
+                        }
+                        result.index = Some((register, u8::try_from(scale).ok()?));
+                        i += 2;
+                    } else if result.base.is_none() {
+                        result.base = Some(register);
+                    } else if result.index.is_none() {
+                        result.index = Some((register, 1));
+                    } else {
+                        return None;
+                    }
+                }
+            }
+            i += 1;
+        }
+        Some(result)
+    }
+}
+
+/// Recovers the jump table the indirect jump ending the block dispatches through.
+///
+/// ### Arguments
+/// - `architecture: MachineArchitecture` - architecture of the binary
+/// - `sections: &Sections` - sections of the binary
+/// - `blocks: &Blocks` - blocks generated so far, searched for the bounds check
+/// - `binary: &[u8]` - content of the binary
+/// - `start_address: &Address` - start address of the block
+/// - `instructions: &[Instruction]` - instructions of the block
+/// - `is_code: impl Fn(&Address) -> bool` - whether the address looks like code
+///
+/// ### Returns
+/// - `Option<JumpTable>` - the jump table, if the jump goes through one
+///
+/// ### Note
+/// Without a bounds check, entries are read until one of them doesn't point to code or the
+/// table leaves its section.
+pub(crate) fn find_jump_table(
+    architecture: MachineArchitecture,
+    sections: &Sections,
+    blocks: &Blocks,
+    binary: &[u8],
+    start_address: &Address,
+    instructions: &[Instruction],
+    is_code: impl Fn(&Address) -> bool,
+) -> Option<JumpTable> {
+    if !matches!(
+        architecture,
+        MachineArchitecture::X86 | MachineArchitecture::X64
+    ) {
+        return None;
+    }
+    let layout = find_jump_table_layout(instructions)?;
+    // bounds check of the index, placed at the end of the block branching here
+    let (count, default) = blocks
+        .get_all()
+        .iter()
+        .find_map(|block| {
+            let relations = block.get_connected_to();
+            let reached_by_branch = relations.iter().find_map(|x| {
+                match (x.to().as_ref() == Some(start_address), x.relation_type()) {
+                    (true, RelationType::Jcc) => Some(true),
+                    (true, RelationType::Continued) => Some(false),
+                    _ => None,
+                }
+            })?;
+            let count =
+                find_jump_table_bound(block.get_instructions(), reached_by_branch, &layout.index)?;
+            // the other side of the bounds check
+            let default_relation = match reached_by_branch {
+                true => RelationType::Continued,
+                false => RelationType::Jcc,
+            };
+            let default = relations
+                .iter()
+                .find(|x| *x.relation_type() == default_relation)
+                .and_then(|x| x.to());
+            Some((Some(count), default))
+        })
+        .unwrap_or_default();
+    let table_section = Address::from_virtual_address(sections, layout.table).get_section()?;
+    let table_end = table_section.virtual_address + table_section.virtual_size;
+    let cases = read_jump_table(
+        &layout,
+        count,
+        |address, size| {
+            if address.checked_add(size as u64)? > table_end {
+                return None;
+            }
+            let address = Address::from_virtual_address(sections, address);
+            let file_offset = address.get_file_offset()? as usize;
+            binary.get(file_offset..file_offset.checked_add(size)?)
+        },
+        |address| is_code(&Address::from_virtual_address(sections, address)),
+    );
+    if cases.is_empty() {
+        return None;
+    }
+    debug!(
+        "Jump table of {} entries found at {:#x}",
+        cases.len(),
+        layout.table
+    );
+    let cases = cases
+        .into_iter()
+        .map(|(value, destination)| (value, Address::from_virtual_address(sections, destination)))
+        .collect();
+    Some(JumpTable::new(
+        index_to_ir_data(&layout.index)?,
+        cases,
+        default,
+    ))
+}
+
+/// Finds the jump table the indirect jump at the end of the block dispatches through.
+///
+/// ### Arguments
+/// - `instructions: &[Instruction]` - instructions of the block, ending with the jump
+///
+/// ### Returns
+/// - `Option<JumpTableLayout>` - layout of the table, `None` if the jump doesn't go through one
+pub(crate) fn find_jump_table_layout(instructions: &[Instruction]) -> Option<JumpTableLayout> {
+    let (jump, rest) = instructions.split_last()?;
+    if statement_of(jump) != Some(X64Statement::Jmp) {
+        return None;
+    }
+    match jump.inner.arguments.first()? {
+        // jmp qword ptr [table + index * 8]
+        Argument::Memory(Memory::RelativeAddressing(args)) => {
+            let operand = MemoryOperand::parse(args)?;
+            let (index, scale) = operand.index?;
+            if !matches!(scale, 4 | 8) {
+                return None;
+            }
+            let base = match &operand.base {
+                Some(base) => register_value(rest, base)?,
+                None => 0,
+            };
+            Some(JumpTableLayout {
+                table: u64::try_from(base as i128 + operand.displacement).ok()?,
+                entry_size: scale,
+                entry_kind: JumpTableEntryKind::Absolute,
+                index,
+            })
+        }
+        // movsxd target, dword ptr [table + index * 4]; add target, base; jmp target
+        Argument::Register(target) => {
+            let add_index = last_write(rest, target)?;
+            let add = &rest[add_index];
+            let added = match (statement_of(add)?, &add.inner.arguments[..]) {
+                (X64Statement::Add, [_, Argument::Register(added)]) => added,
+                _ => return None,
+            };
+            let load_index = last_write(&rest[..add_index], target)?;
+            let load = &rest[load_index];
+            let signed = match statement_of(load)? {
+                X64Statement::Movsxd => true,
+                X64Statement::Mov => false,
+                _ => return None,
+            };
+            let [_, Argument::Memory(Memory::RelativeAddressing(args))] = &load.inner.arguments[..]
+            else {
+                return None;
+            };
+            let operand = MemoryOperand::parse(args)?;
+            let (index, 4) = operand.index? else {
+                return None;
+            };
+            let table_base = match &operand.base {
+                Some(base) => register_value(&rest[..load_index], base)?,
+                None => 0,
+            };
+            Some(JumpTableLayout {
+                table: u64::try_from(table_base as i128 + operand.displacement).ok()?,
+                entry_size: 4,
+                entry_kind: JumpTableEntryKind::Relative {
+                    base: register_value(&rest[..add_index], added)?,
+                    signed,
+                },
+                index,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Finds the amount of entries from the bounds check guarding the index.
+///
+/// ### Arguments
+/// - `guard: &[Instruction]` - instructions of the block ending with the bounds check
+///   (`cmp index, max; ja default`)
+/// - `reached_by_branch: bool` - whether the jump table block is the destination of the
+///   conditional jump (otherwise, it is reached when the jump is not taken)
+/// - `index: &iceball::Register` - register indexing the table
+///
+/// ### Returns
+/// - `Option<u64>` - amount of entries
+pub(crate) fn find_jump_table_bound(
+    guard: &[Instruction],
+    reached_by_branch: bool,
+    index: &iceball::Register,
+) -> Option<u64> {
+    let [.., compare, branch] = guard else {
+        return None;
+    };
+    let [Argument::Register(compared), Argument::Constant(max)] = &compare.inner.arguments[..]
+    else {
+        return None;
+    };
+    if statement_of(compare) != Some(X64Statement::Cmp) || !same_register(compared, index) {
+        return None;
+    }
+    let inclusive = match (statement_of(branch)?, reached_by_branch) {
+        (X64Statement::Ja, false) | (X64Statement::Jbe, true) => true,
+        (X64Statement::Jae, false) | (X64Statement::Jb, true) => false,
+        _ => return None,
+    };
+    let count = if inclusive { max.checked_add(1)? } else { *max };
+    (count <= MAX_JUMP_TABLE_ENTRIES).then_some(count)
+}
+
+/// Reads the destinations of the table.
+///
+/// ### Arguments
+/// - `layout: &JumpTableLayout` - layout of the table
+/// - `count: Option<u64>` - amount of entries. if not known, entries are read until one of
+///   them doesn't point to code or can't be read
+/// - `read: impl Fn(u64, usize) -> Option<&'a [u8]>` - reads bytes at the virtual address
+/// - `is_code: impl Fn(u64) -> bool` - whether the virtual address looks like code
+///
+/// ### Returns
+/// - `Vec<(u64, u64)>` - (index value, destination) of each case. empty if the table is
+///   not readable
+pub(crate) fn read_jump_table<'a>(
+    layout: &JumpTableLayout,
+    count: Option<u64>,
+    read: impl Fn(u64, usize) -> Option<&'a [u8]>,
+    is_code: impl Fn(u64) -> bool,
+) -> Vec<(u64, u64)> {
+    let size = layout.entry_size as usize;
+    let mut result = Vec::new();
+    for value in 0..count.unwrap_or(u64::MAX) {
+        let Some(bytes) = read(layout.table + value * size as u64, size) else {
+            if count.is_some() {
+                return Vec::new();
+            }
+            break;
+        };
+        let entry = match size {
+            4 => u32::from_le_bytes(bytes.try_into().unwrap()) as u64,
+            8 => u64::from_le_bytes(bytes.try_into().unwrap()),
+            _ => unreachable!(),
+        };
+        let destination = match layout.entry_kind {
+            JumpTableEntryKind::Absolute => entry,
+            JumpTableEntryKind::Relative { base, signed: true } => {
+                base.wrapping_add(entry as u32 as i32 as i64 as u64)
+            }
+            JumpTableEntryKind::Relative {
+                base,
+                signed: false,
+            } => base.wrapping_add(entry),
+        };
+        if !is_code(destination) {
+            if count.is_some() {
+                warn!(
+                    "Jump table entry {} at {:#x} doesn't point to code",
+                    value, layout.table
+                );
+                return Vec::new();
+            }
+            break;
+        }
+        result.push((value, destination));
+    }
+    result
+}
+
+/// Turns the register indexing the table into IR data
+pub(crate) fn index_to_ir_data(index: &iceball::Register) -> Option<Aos<IrData>> {
+    match index {
+        iceball::Register::X64(register) => Some(str_to_x64_register(register.name())),
+        _ => None,
+    }
+}
+
+fn statement_of(instruction: &Instruction) -> Option<X64Statement> {
+    match instruction.inner.statement {
+        Ok(iceball::Statement::X64(statement)) => Some(statement),
+        _ => None,
+    }
+}
+
+/// Whether two registers overlap (e.g. `eax` and `rax`)
+fn same_register(a: &iceball::Register, b: &iceball::Register) -> bool {
+    let bit_start = |register: &iceball::Register| match index_to_ir_data(register)?.as_ref() {
+        IrData::Register(register) => Some(register.bit_range().start),
+        _ => None,
+    };
+    bit_start(a).is_some_and(|a| bit_start(b) == Some(a))
+}
+
+/// Index of the last instruction writing the register
+fn last_write(instructions: &[Instruction], register: &iceball::Register) -> Option<usize> {
+    instructions.iter().rposition(|x| {
+        matches!(x.inner.arguments.first(), Some(Argument::Register(written)) if same_register(written, register))
+            && !matches!(
+                statement_of(x),
+                Some(X64Statement::Cmp | X64Statement::Test | X64Statement::Push)
+            )
+    })
+}
+
+/// Value of the register, if it was set to an address by the instructions
+fn register_value(instructions: &[Instruction], register: &iceball::Register) -> Option<u64> {
+    let instruction = &instructions[last_write(instructions, register)?];
+    match (statement_of(instruction)?, &instruction.inner.arguments[..]) {
+        // lea rdx, [rip + offset]
+        (X64Statement::Lea, [_, Argument::Memory(Memory::RelativeAddressing(args))]) => {
+            let operand = MemoryOperand::parse(args)?;
+            if operand.index.is_some() {
+                return None;
+            }
+            let base = match operand.base {
+                Some(iceball::Register::X64(
+                    iceball::X64Register::Rip | iceball::X64Register::Eip,
+                )) => {
+                    let size = instruction.inner.bytes.as_ref()?.len() as u64;
+                    instruction.address + size
+                }
+                Some(_) => return None,
+                None => 0,
+            };
+            u64::try_from(base as i128 + operand.displacement).ok()
+        }
+        // mov edx, offset
+        (X64Statement::Mov, [_, Argument::Constant(value)]) => Some(*value),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instruction(address: u64, statement: X64Statement, arguments: &[&str]) -> Instruction {
+        Instruction {
+            address,
+            inner: iceball::Instruction {
+                statement: Ok(iceball::Statement::X64(statement)),
+                arguments: arguments
+                    .iter()
+                    .map(|x| iceball::x64::parse_argument(x).unwrap())
+                    .collect(),
+                bytes: Some(vec![0x90; 7].into()),
+            },
+        }
+    }
+
+    #[test]
+    fn test_relative_jump_table() {
+        let guard = [
+            instruction(0x1000, X64Statement::Cmp, &["edi", "0x5"]),
+            instruction(0x1007, X64Statement::Ja, &["0x1100"]),
+        ];
+        let block = [
+            instruction(
+                0x100e,
+                X64Statement::Lea,
+                &["rdx", "qword ptr [rip + 0x1000]"],
+            ),
+            instruction(
+                0x1015,
+                X64Statement::Movsxd,
+                &["rax", "dword ptr [rdx + rdi*4]"],
+            ),
+            instruction(0x101c, X64Statement::Add, &["rax", "rdx"]),
+            instruction(0x1023, X64Statement::Jmp, &["rax"]),
+        ];
+        let layout = find_jump_table_layout(&block).unwrap();
+        assert_eq!(layout.table, 0x2015);
+        assert_eq!(
+            layout.entry_kind,
+            JumpTableEntryKind::Relative {
+                base: 0x2015,
+                signed: true
+            }
+        );
+        assert_eq!(find_jump_table_bound(&guard, false, &layout.index), Some(6));
+        assert_eq!(find_jump_table_bound(&guard, true, &layout.index), None);
+
+        // -0x1000, -0x0ff0
+        let table = [0x00, 0xf0, 0xff, 0xff, 0x10, 0xf0, 0xff, 0xff];
+        let read = |address: u64, size: usize| {
+            let offset = (address - 0x2015) as usize;
+            table.get(offset..offset + size)
+        };
+        let cases = read_jump_table(&layout, None, read, |x| (0x1000..0x1100).contains(&x));
+        assert_eq!(cases, vec![(0, 0x1015), (1, 0x1025)]);
+        assert_eq!(read_jump_table(&layout, Some(2), read, |_| true).len(), 2);
+        assert!(read_jump_table(&layout, Some(6), read, |_| true).is_empty());
+        // without a bound, the table ends where it can no longer be read
+        assert_eq!(read_jump_table(&layout, None, read, |_| true).len(), 2);
+    }
+
+    #[test]
+    fn test_absolute_jump_table() {
+        let block = [instruction(
+            0x1000,
+            X64Statement::Jmp,
+            &["qword ptr [rax*8 + 0x402000]"],
+        )];
+        let layout = find_jump_table_layout(&block).unwrap();
+        assert_eq!(layout.table, 0x402000);
+        assert_eq!(layout.entry_size, 8);
+        assert_eq!(layout.entry_kind, JumpTableEntryKind::Absolute);
+    }
+}
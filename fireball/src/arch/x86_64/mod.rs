@@ -1,6 +1,7 @@
 //! Module that converts x86_64 architecture instructions into IR
 
 pub mod instruction_analyze;
+pub(crate) mod jump_table;
 
 pub(crate) mod static_register {
     #![allow(non_upper_case_globals, unused)]
@@ -375,12 +376,15 @@ pub(crate) mod static_register {
     static_register!(tmp4_512);
 }
 
-pub(crate) fn str_to_x64_register(data: &str) -> crate::utils::Aos<crate::ir::data::IrData> {
+/// Returns the register with the name, including the temporary ones used by the ir.
+pub(crate) fn x64_register_by_name(
+    data: &str,
+) -> Option<crate::utils::Aos<crate::ir::data::IrData>> {
     let data = data.to_ascii_lowercase();
     macro_rules! str_to_reg {
         ($name:ident) => {
             if data == stringify!($name) {
-                return static_register::$name.clone();
+                return Some(static_register::$name.clone());
             }
         };
     }
@@ -692,5 +696,38 @@ pub(crate) fn str_to_x64_register(data: &str) -> crate::utils::Aos<crate::ir::da
     str_to_reg!(dr14);
     str_to_reg!(dr15);
 
-    unreachable!("{}", data)
+    str_to_reg!(tmp8);
+    str_to_reg!(tmp16);
+    str_to_reg!(tmp32);
+    str_to_reg!(tmp64);
+    str_to_reg!(tmp128);
+    str_to_reg!(tmp256);
+    str_to_reg!(tmp512);
+    str_to_reg!(tmp2_8);
+    str_to_reg!(tmp2_16);
+    str_to_reg!(tmp2_32);
+    str_to_reg!(tmp2_64);
+    str_to_reg!(tmp2_128);
+    str_to_reg!(tmp2_256);
+    str_to_reg!(tmp2_512);
+    str_to_reg!(tmp3_8);
+    str_to_reg!(tmp3_16);
+    str_to_reg!(tmp3_32);
+    str_to_reg!(tmp3_64);
+    str_to_reg!(tmp3_128);
+    str_to_reg!(tmp3_256);
+    str_to_reg!(tmp3_512);
+    str_to_reg!(tmp4_8);
+    str_to_reg!(tmp4_16);
+    str_to_reg!(tmp4_32);
+    str_to_reg!(tmp4_64);
+    str_to_reg!(tmp4_128);
+    str_to_reg!(tmp4_256);
+    str_to_reg!(tmp4_512);
+
+    None
+}
+
+pub(crate) fn str_to_x64_register(data: &str) -> crate::utils::Aos<crate::ir::data::IrData> {
+    x64_register_by_name(data).unwrap_or_else(|| unreachable!("{}", data))
 }
@@ -1,7 +1,7 @@
 //! Module defining `Block`s generated per section by program analysis.
 
-use super::{Address, Relation, Section};
-use crate::{core::Instruction, ir::IrBlock};
+use super::{Address, JumpTable, Relation, RelationType, Section};
+use crate::{core::Instruction, exception_handling::ExceptionHandler, ir::IrBlock};
 use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 /// IR-level block created by analysis.
@@ -25,6 +25,10 @@ pub struct Block {
     section: Option<Arc<Section>>,
     /// Instructions of the block
     instructions: Arc<[Instruction]>,
+    /// Jump table the last instruction dispatches through
+    jump_table: Option<JumpTable>,
+    /// Handlers of the exceptions raised by the instructions
+    exception_handlers: Vec<ExceptionHandler>,
 
     /* Generated by analysis */
     /// The block's IR data
@@ -41,15 +45,20 @@ impl Block {
     /// - `start_address: Address` - block start address
     /// - `block_size: Option<u64>` - block size
     /// - `instructions: Arc<[Instruction]>` - instructions of the block
+    /// - `jump_table: Option<JumpTable>` - jump table the last instruction dispatches through
+    /// - `exception_handlers: Vec<ExceptionHandler>` - handlers of the exceptions the
+    ///   instructions raise
     ///
     /// ### Returns
     /// - `Arc<Self>` - the created block
-    pub(super) fn new(
+    pub(crate) fn new(
         id: usize,
         name: Option<String>,
         start_address: Address,
         block_size: Option<u64>,
         instructions: Arc<[Instruction]>,
+        jump_table: Option<JumpTable>,
+        exception_handlers: Vec<ExceptionHandler>,
     ) -> Arc<Self> {
         let section = start_address.get_section();
         Arc::new(Self {
@@ -61,6 +70,8 @@ impl Block {
             connected_to: Default::default(),
             section,
             instructions,
+            jump_table,
+            exception_handlers,
             ir: Default::default(),
         })
     }
@@ -129,6 +140,17 @@ impl Block {
         self.connected_from.write().unwrap().push(relation);
     }
 
+    /// Removes the relations of a block connecting to this block.
+    ///
+    /// ### Arguments
+    /// - `from: usize` - ID of the block that pointed to this block
+    pub(crate) fn remove_connected_from(&self, from: usize) {
+        self.connected_from
+            .write()
+            .unwrap()
+            .retain(|x| x.from() != from);
+    }
+
     /// Adds a relation for a block this block connects to.
     ///
     /// ### Arguments
@@ -136,8 +158,15 @@ impl Block {
     pub(crate) fn add_connected_to(&self, relation: Relation) {
         self.connected_to.write().unwrap().push(relation);
         debug_assert!(
-            self.connected_to.read().unwrap().len() <= 2,
-            "One block should not have more than 2 outgoing relations"
+            self.connected_to
+                .read()
+                .unwrap()
+                .iter()
+                .filter(|x| x.relation_type() != &RelationType::Exception)
+                .count()
+                <= 2
+                || self.jump_table.is_some(),
+            "One block should not have more than 2 outgoing relations without a jump table"
         );
     }
     /// Returns whether the block contains a specific address.
@@ -178,6 +207,20 @@ impl Block {
     pub fn get_instructions(&self) -> &Arc<[Instruction]> {
         &self.instructions
     }
+    /// Returns the jump table the last instruction dispatches through.
+    ///
+    /// ### Returns
+    /// - `Option<&JumpTable>` - jump table of the block
+    pub fn get_jump_table(&self) -> Option<&JumpTable> {
+        self.jump_table.as_ref()
+    }
+    /// Returns the handlers of the exceptions raised by the instructions of the block.
+    ///
+    /// ### Returns
+    /// - `&[ExceptionHandler]` - handlers whose protected range overlaps the block
+    pub fn get_exception_handlers(&self) -> &[ExceptionHandler] {
+        &self.exception_handlers
+    }
 }
 
 impl std::hash::Hash for Block {
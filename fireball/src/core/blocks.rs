@@ -1,9 +1,16 @@
 //! Module defining a structure that collects "Block"s resulting from program analysis
 
-use crate::core::{
-    Address, Block, Instruction, Relation, RelationType, Relations, relation::DestinationType,
+use crate::{
+    core::{
+        Address, Block, Instruction, JumpTable, Relation, RelationType, Relations,
+        relation::DestinationType,
+    },
+    exception_handling::ExceptionHandler,
+};
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
 };
-use std::sync::Arc;
 
 /// A structure that manages IR-level blocks
 ///
@@ -13,6 +20,8 @@ pub struct Blocks {
     data: std::sync::RwLock<std::collections::HashSet<Arc<Block>>>,
     /// Block relations
     relations: Arc<Relations>,
+    /// ID given to the next generated block
+    next_id: AtomicUsize,
 }
 
 #[derive(Clone)]
@@ -34,6 +43,7 @@ impl Blocks {
         Arc::new(Self {
             data: Default::default(),
             relations,
+            next_id: AtomicUsize::new(0),
         })
     }
 
@@ -45,9 +55,13 @@ impl Blocks {
     /// - `connected_to: &[BlockRelationInformation]`: the relations to other blocks
     /// - `name: Option<String>`: The name of the block
     /// - `instructions: Arc<[Instruction]>`: The instructions of the block
+    /// - `jump_table: Option<JumpTable>`: The jump table the last instruction dispatches through
+    /// - `exception_handlers: Vec<ExceptionHandler>`: The handlers of the exceptions the
+    ///   instructions raise
     ///
     /// ### Returns
     /// - `Arc<Block>`: The generated block
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn generate_block(
         &self,
         start_address: Address,
@@ -55,6 +69,8 @@ impl Blocks {
         connected_to: &[BlockRelationInformation],
         name: Option<String>,
         instructions: Arc<[Instruction]>,
+        jump_table: Option<JumpTable>,
+        exception_handlers: Vec<ExceptionHandler>,
     ) -> Arc<Block> {
         /* Before acquiring the lock, check relations targeting this block */
         let connected_from: Vec<_> = {
@@ -84,11 +100,13 @@ impl Blocks {
 
         /* Create the new block with the provided information */
         let new_block = Block::new(
-            blocks_writer.len(),
+            self.next_id.fetch_add(1, Ordering::Relaxed),
             name,
             start_address,
             block_size,
             instructions,
+            jump_table,
+            exception_handlers,
         );
 
         for connected_from in connected_from {
@@ -172,6 +190,68 @@ impl Blocks {
             .map(Arc::clone)
     }
 
+    /// Removes the blocks overlapping a range, along with the relations they start.
+    ///
+    /// ### Arguments
+    /// - `start: &Address`: The start address of the range
+    /// - `size: u64`: The size of the range
+    ///
+    /// ### Returns
+    /// - `Vec<Arc<Block>>`: The removed blocks
+    ///
+    /// ### Note
+    /// - Relations of other blocks pointing into the range are kept, so they connect to the
+    ///   blocks generated again at the same addresses.
+    pub(crate) fn remove_overlapping(&self, start: &Address, size: u64) -> Vec<Arc<Block>> {
+        let end = start + size;
+        let removed: Vec<_> = {
+            let blocks_writer = &mut self.data.write().unwrap();
+            let removed: Vec<_> = blocks_writer
+                .iter()
+                .filter(|block| match block.get_block_size() {
+                    Some(block_size) => {
+                        block.get_start_address() < &end
+                            && start < &(block.get_start_address() + *block_size)
+                    }
+                    None => block.contains(start),
+                })
+                .map(Arc::clone)
+                .collect();
+            for block in &removed {
+                blocks_writer.remove(block);
+            }
+            removed
+        };
+
+        for block in &removed {
+            self.relations.remove_relations_from(block.get_id());
+            for connected in self.get_all() {
+                connected.remove_connected_from(block.get_id());
+            }
+        }
+        removed
+    }
+
+    /// Puts back a block saved in a project, keeping its id.
+    ///
+    /// ### Arguments
+    /// - `block: Arc<Block>`: The restored block, without relations
+    ///
+    /// ### Note
+    /// - Blocks generated afterwards get ids following the ones of the restored blocks.
+    pub(crate) fn restore_block(&self, block: Arc<Block>) {
+        self.next_id
+            .fetch_max(block.get_id() + 1, Ordering::Relaxed);
+        self.data.write().unwrap().insert(block);
+    }
+
+    /// Removes every block and relation.
+    pub(crate) fn clear(&self) {
+        self.data.write().unwrap().clear();
+        self.relations.clear();
+        self.next_id.store(0, Ordering::Relaxed);
+    }
+
     /// Returns all blocks.
     ///
     /// ### Returns
@@ -1,8 +1,11 @@
 use crate::{
-    Fire,
+    BinaryKind, Fire,
+    abstract_syntax_tree::{Ast, AstCallingConvention},
     core::{Address, Block, Blocks, PreDefinedOffsets, Relations, Sections},
-    prelude::DecompileError,
+    debug_info::DebugInfo,
+    prelude::{DecompileError, FireballError},
 };
+use iceball::MachineArchitecture;
 use std::sync::Arc;
 
 /// Trait that gives access to the raw Fireball API.
@@ -64,4 +67,129 @@ pub trait FireRaw: Fire {
     /// ### Returns
     /// - `Arc<Relations>` - object managing connection information of generated blocks
     fn get_relations(&self) -> Arc<Relations>;
+    /// Returns the calling convention functions of the binary use unless detected otherwise.
+    ///
+    /// ### Returns
+    /// - `AstCallingConvention` - default calling convention of the binary's platform
+    ///
+    /// ### Note
+    /// - This function performs no additional computation.
+    fn get_calling_convention(&self) -> AstCallingConvention;
+    /// Returns the debug information read from the binary.
+    ///
+    /// ### Returns
+    /// - `Arc<DebugInfo>` - function, variable and type names and source lines, empty if the
+    ///   binary has none
+    ///
+    /// ### Note
+    /// - This function performs no additional computation.
+    fn get_debug_info(&self) -> Arc<DebugInfo>;
+    /// Returns the architecture of the instructions of the binary.
+    ///
+    /// ### Returns
+    /// - `Option<MachineArchitecture>` - the architecture, `None` if the file holds no code
+    ///
+    /// ### Note
+    /// - This function performs no additional computation.
+    fn get_architecture(&self) -> Option<MachineArchitecture>;
+    /// Returns the classification of the binary.
+    ///
+    /// ### Returns
+    /// - `BinaryKind` - executable, shared library, object file or debug information
+    ///
+    /// ### Note
+    /// - This function performs no additional computation.
+    fn get_kind(&self) -> BinaryKind;
+    /// Returns the in-memory copy of the binary for editing.
+    ///
+    /// ### Returns
+    /// - `&mut Vec<u8>` - the binary data
+    ///
+    /// ### Note
+    /// - The analysis is not updated by the edits, see `FireRaw::patch_bytes`.
+    fn get_binary_mut(&mut self) -> &mut Vec<u8>;
+    /// Generates the AST of analyzed blocks, with the symbols, calling convention and debug
+    /// information of the binary.
+    ///
+    /// ### Arguments
+    /// - `blocks: Vec<Arc<Block>>` - blocks to decompile
+    ///
+    /// ### Returns
+    /// - `Result<Ast, DecompileError>` - the unoptimized AST
+    fn generate_ast(&self, blocks: Vec<Arc<Block>>) -> Result<Ast, DecompileError>;
+    /// Replaces instructions with machine code, and decompiles the edited code again.
+    ///
+    /// ### Arguments
+    /// - `address: &Address` - address of the first replaced instruction
+    /// - `bytes: &[u8]` - machine code of the replacement instructions
+    ///
+    /// ### Returns
+    /// - `Result<Ast, FireballError>` - AST of the blocks analyzed again after patching
+    ///
+    /// ### Note
+    /// - Only the in-memory copy of the binary is patched, see `FireRaw::write_binary`.
+    /// - Blocks overlapping the patched bytes are removed from `Blocks` and `Relations`, and
+    ///   generated again from the patched bytes.
+    /// - On x86, an instruction partially overwritten by the patch is filled up with `nop`s.
+    fn patch_bytes(&mut self, address: &Address, bytes: &[u8]) -> Result<Ast, FireballError> {
+        let Some(architecture) = self.get_architecture() else {
+            return Err(FireballError::PatchFailed(
+                "the file contains no code".to_string(),
+            ));
+        };
+        let blocks = self.get_blocks();
+        let starts = crate::core::patch_binary(
+            self.get_binary_mut(),
+            &blocks,
+            architecture,
+            address,
+            bytes,
+        )?;
+        let blocks = starts
+            .iter()
+            .map(|start| self.analyze_block(start))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.generate_ast(blocks)?)
+    }
+    /// Replaces instructions with assembled ones, and decompiles the edited code again.
+    ///
+    /// ### Arguments
+    /// - `address: &Address` - address of the first replaced instruction
+    /// - `assembly: &str` - replacement instructions (`xor eax, eax; ret`), in intel syntax on x86
+    ///
+    /// ### Returns
+    /// - `Result<Ast, FireballError>` - AST of the blocks analyzed again after patching
+    ///
+    /// ### Note
+    /// - Works as `FireRaw::patch_bytes` with the assembled instructions.
+    #[cfg(feature = "keystone")]
+    fn patch_assembly(&mut self, address: &Address, assembly: &str) -> Result<Ast, FireballError> {
+        let Some(architecture) = self.get_architecture() else {
+            return Err(FireballError::PatchFailed(
+                "the file contains no code".to_string(),
+            ));
+        };
+        let bytes = crate::core::assemble(architecture, address.get_virtual_address(), assembly)?;
+        self.patch_bytes(address, &bytes)
+    }
+    /// Writes the binary data, including patches, to a file.
+    ///
+    /// ### Arguments
+    /// - `path: &str` - path of the written file
+    ///
+    /// ### Returns
+    /// - `Result<(), FireballError>` - an error if the file could not be written
+    ///
+    /// ### Note
+    /// - Object files are refused, as their data holds the relocations applied for analysis and
+    ///   a linker would apply them a second time.
+    fn write_binary(&self, path: &str) -> Result<(), FireballError> {
+        if self.get_kind() == BinaryKind::ObjectFile {
+            return Err(FireballError::PatchFailed(
+                "relocated object files can't be written back".to_string(),
+            ));
+        }
+        std::fs::write(path, self.get_binary())?;
+        Ok(())
+    }
 }
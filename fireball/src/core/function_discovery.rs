@@ -0,0 +1,279 @@
+//! Module discovering function starts by scanning the code of executable sections
+
+use super::Sections;
+use crate::prelude::*;
+use iceball::MachineArchitecture;
+use std::collections::BTreeSet;
+
+/// Bytes compilers fill the gap between two functions with
+const X86_PADDING: [u8; 2] = [0xCC, 0x90];
+/// `ret`, after which a new function commonly begins
+const X86_RETURN: u8 = 0xC3;
+/// `ret imm16`, followed by its 2 byte immediate
+const X86_RETURN_IMM16: u8 = 0xC2;
+/// Alignment compilers give to function starts
+const X86_FUNCTION_ALIGNMENT: usize = 16;
+
+/// Prologue signatures of 64-bit x86 functions
+const X64_PROLOGUES: &[&[u8]] = &[
+    // push rbp; mov rbp, rsp
+    &[0x55, 0x48, 0x89, 0xE5],
+    &[0x55, 0x48, 0x8B, 0xEC],
+    // endbr64
+    &[0xF3, 0x0F, 0x1E, 0xFA],
+    // mov [rsp+disp8], rbx (home space spill)
+    &[0x48, 0x89, 0x5C, 0x24],
+    // push rbx (REX form)
+    &[0x40, 0x53],
+    // sub rsp, imm8
+    &[0x48, 0x83, 0xEC],
+];
+
+/// Prologue signatures of 32-bit x86 functions
+const X86_PROLOGUES: &[&[u8]] = &[
+    // mov edi, edi; push ebp; mov ebp, esp (hot-patchable)
+    &[0x8B, 0xFF, 0x55, 0x8B, 0xEC],
+    // push ebp; mov ebp, esp
+    &[0x55, 0x8B, 0xEC],
+    &[0x55, 0x89, 0xE5],
+    // endbr32
+    &[0xF3, 0x0F, 0x1E, 0xFB],
+];
+
+/// Finds function starts within the executable sections of a binary.
+///
+/// Starts are found by matching prologue signatures at the boundaries between functions
+/// and by collecting the targets of direct calls found by a linear sweep.
+///
+/// ### Arguments
+/// - `binary: &[u8]` - file data the sections are read from
+/// - `sections: &Sections` - sections of the binary
+/// - `architecture: MachineArchitecture` - instruction set of the binary
+/// - `capstone: &capstone::Capstone` - disassembler for the instruction set
+///
+/// ### Returns
+/// - `BTreeSet<u64>` - virtual addresses of discovered functions
+pub(crate) fn discover_functions(
+    binary: &[u8],
+    sections: &Sections,
+    architecture: MachineArchitecture,
+    capstone: &capstone::Capstone,
+) -> BTreeSet<u64> {
+    let mut executable: Vec<_> = sections
+        .all()
+        .into_iter()
+        .filter(|section| section.is_executable())
+        .collect();
+    executable.sort_unstable_by_key(|section| section.virtual_address);
+    let is_code = |address: u64| {
+        executable.iter().any(|section| {
+            section.virtual_address <= address
+                && address < section.virtual_address + section.virtual_size
+        })
+    };
+
+    let mut result = BTreeSet::new();
+    for section in executable.iter() {
+        let start = section.file_offset as usize;
+        let end = start.saturating_add(section.size_of_file as usize);
+        let Some(code) = binary.get(start..end.min(binary.len())) else {
+            continue;
+        };
+        let base = section.virtual_address;
+
+        for offset in scan_prologues(code, architecture) {
+            result.insert(base + offset as u64);
+        }
+        for target in sweep_call_targets(code, base, architecture, capstone) {
+            if is_code(target) {
+                result.insert(target);
+            }
+        }
+    }
+    debug!(count = result.len(), "Functions discovered by code scan");
+    result
+}
+
+/// Finds the offsets of prologue signatures that begin a function.
+///
+/// ### Arguments
+/// - `code: &[u8]` - contents of an executable section
+/// - `architecture: MachineArchitecture` - instruction set of the code
+///
+/// ### Returns
+/// - `Vec<usize>` - offsets of matched prologues within `code`
+///
+/// ### Note
+/// x86 signatures are too short to be trusted anywhere, so they are only accepted at the start
+/// of the section, at aligned offsets after padding, and after a return that is aligned or
+/// padded.
+fn scan_prologues(code: &[u8], architecture: MachineArchitecture) -> Vec<usize> {
+    match architecture {
+        MachineArchitecture::X86 | MachineArchitecture::X64 => {
+            let prologues = if architecture == MachineArchitecture::X64 {
+                X64_PROLOGUES
+            } else {
+                X86_PROLOGUES
+            };
+            (0..code.len())
+                .filter(|&offset| is_x86_boundary(code, offset))
+                .filter(|&offset| {
+                    prologues
+                        .iter()
+                        .any(|prologue| code[offset..].starts_with(prologue))
+                })
+                .collect()
+        }
+        MachineArchitecture::Arm64 => words(code)
+            .filter(|&(_, word)| {
+                // stp x29, x30, [sp, #-n]!
+                word & 0xFFC0_7FFF == 0xA980_7BFD
+                    // paciasp
+                    || word == 0xD503_233F
+            })
+            .map(|(offset, _)| offset)
+            .collect(),
+        MachineArchitecture::Arm => words(code)
+            // push {..., lr}
+            .filter(|&(_, word)| word & 0xFFFF_4000 == 0xE92D_4000)
+            .map(|(offset, _)| offset)
+            .collect(),
+    }
+}
+
+/// Checks whether a function may begin at the offset of x86 code.
+///
+/// ### Note
+/// A return byte alone may be the end of another instruction, so a start after a return is
+/// also required to be aligned or to follow padding.
+fn is_x86_boundary(code: &[u8], offset: usize) -> bool {
+    if offset == 0 {
+        return true;
+    }
+    let end = code[..offset]
+        .iter()
+        .rposition(|x| !X86_PADDING.contains(x))
+        .map_or(0, |x| x + 1);
+    let padded = end < offset;
+    let aligned = offset.is_multiple_of(X86_FUNCTION_ALIGNMENT);
+    let returned = (end >= 1 && code[end - 1] == X86_RETURN)
+        || (end >= 3 && code[end - 3] == X86_RETURN_IMM16);
+    (padded && aligned) || (returned && (padded || aligned))
+}
+
+/// Iterates the aligned little-endian words of ARM code.
+fn words(code: &[u8]) -> impl Iterator<Item = (usize, u32)> + '_ {
+    code.chunks_exact(4)
+        .enumerate()
+        .map(|(index, word)| (index * 4, u32::from_le_bytes(word.try_into().unwrap())))
+}
+
+/// Collects the targets of direct calls by disassembling code from start to end.
+///
+/// ### Arguments
+/// - `code: &[u8]` - contents of an executable section
+/// - `base: u64` - virtual address of the first byte of `code`
+/// - `architecture: MachineArchitecture` - instruction set of the code
+/// - `capstone: &capstone::Capstone` - disassembler for the instruction set
+///
+/// ### Returns
+/// - `Vec<u64>` - virtual addresses called from the code
+///
+/// ### Note
+/// Bytes that fail to disassemble (e.g., data in code) are skipped and the sweep resumes after them.
+fn sweep_call_targets(
+    code: &[u8],
+    base: u64,
+    architecture: MachineArchitecture,
+    capstone: &capstone::Capstone,
+) -> Vec<u64> {
+    let (call_mnemonics, skip): (&[&str], usize) = match architecture {
+        MachineArchitecture::X86 | MachineArchitecture::X64 => (&["call"], 1),
+        MachineArchitecture::Arm64 => (&["bl"], 4),
+        MachineArchitecture::Arm => (&["bl", "blx"], 4),
+    };
+
+    let mut targets = Vec::new();
+    let mut offset = 0;
+    while offset < code.len() {
+        let insns = match capstone.disasm_all(&code[offset..], base + offset as u64) {
+            Ok(insns) => insns,
+            Err(e) => {
+                warn!(?e, "Linear sweep failed at {:#x}", base + offset as u64);
+                break;
+            }
+        };
+        let mut next = offset;
+        for insn in insns.iter() {
+            next = (insn.address() - base) as usize + insn.bytes().len();
+            if !insn
+                .mnemonic()
+                .is_some_and(|mnemonic| call_mnemonics.contains(&mnemonic))
+            {
+                continue;
+            }
+            if let Some(target) = insn.op_str().and_then(parse_immediate) {
+                targets.push(target);
+            }
+        }
+        // Resume after the instruction capstone stopped at
+        offset = next + skip;
+    }
+    targets
+}
+
+/// Parses an immediate operand such as `0x1234` or `#0x1234`.
+fn parse_immediate(operand: &str) -> Option<u64> {
+    let operand = operand.trim().trim_start_matches('#');
+    let hex = operand.strip_prefix("0x")?;
+    u64::from_str_radix(hex, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_prologues() {
+        // ret; int3 padding; push rbp; mov rbp, rsp
+        let mut code = vec![0xC3];
+        code.resize(16, 0xCC);
+        code.extend([0x55, 0x48, 0x89, 0xE5, 0xC3]);
+        // ret 8 ending at an aligned offset; sub rsp, 0x28
+        code.resize(45, 0x50);
+        code.extend([0xC2, 0x08, 0x00, 0x48, 0x83, 0xEC, 0x28]);
+        // ret; nop; sub rsp, 0x28 at an unaligned offset after padding
+        code.extend([0xC3, 0x90, 0x48, 0x83, 0xEC, 0x28]);
+        // ret 0x10; int3; sub rsp, 0x28
+        code.extend([0xC2, 0x10, 0x00, 0xCC, 0x48, 0x83, 0xEC, 0x28]);
+        assert_eq!(
+            scan_prologues(&code, MachineArchitecture::X64),
+            [16, 48, 54, 62]
+        );
+
+        // stp x29, x30, [sp, #-0x10]!; mov x29, sp; paciasp
+        let code = [
+            0xFD, 0x7B, 0xBF, 0xA9, 0xFD, 0x03, 0x00, 0x91, 0x3F, 0x23, 0x03, 0xD5,
+        ];
+        assert_eq!(scan_prologues(&code, MachineArchitecture::Arm64), [0, 8]);
+    }
+
+    #[test]
+    fn test_scan_prologues_inside_instructions() {
+        // mov al, 0xC3; sub rsp, 0x28 - the return byte is an immediate
+        let mut code = vec![0x50, 0xB0, 0xC3, 0x48, 0x83, 0xEC, 0x28];
+        // nop; push rbp; mov rbp, rsp - padding at an unaligned offset without a return
+        code.extend([0x90, 0x55, 0x48, 0x89, 0xE5]);
+        // mov ax, 0xC2; sub rsp, 0x28 - the `ret imm16` byte is not 3 bytes back
+        code.extend([0x66, 0xB8, 0xC2, 0x00, 0x48, 0x83, 0xEC, 0x28]);
+        assert!(scan_prologues(&code, MachineArchitecture::X64).is_empty());
+    }
+
+    #[test]
+    fn test_parse_immediate() {
+        assert_eq!(parse_immediate("0x140001000"), Some(0x1_4000_1000));
+        assert_eq!(parse_immediate("#0x4010"), Some(0x4010));
+        assert_eq!(parse_immediate("rax"), None);
+        assert_eq!(parse_immediate("qword ptr [rip + 0x10]"), None);
+    }
+}
@@ -12,6 +12,9 @@ pub struct Instruction {
 }
 
 impl Instruction {
+    pub fn address(&self) -> u64 {
+        self.address
+    }
     pub fn inner(&self) -> &iceball::Instruction {
         &self.inner
     }
@@ -0,0 +1,61 @@
+//! Module defining the jump table an indirect jump dispatches through
+
+use crate::{core::Address, ir::data::IrData, utils::Aos};
+
+/// Destinations of an indirect jump read from a jump table (e.g., compiled `switch`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpTable {
+    /// Data holding the index into the table when the jump is executed
+    index: Aos<IrData>,
+    /// Index value and destination of each entry
+    cases: Vec<(u64, Address)>,
+    /// Destination of the index values out of the table, taken by the bounds check
+    default: Option<Address>,
+}
+
+impl JumpTable {
+    /// Creates a jump table.
+    ///
+    /// ### Arguments
+    /// - `index: Aos<IrData>` - data holding the index into the table
+    /// - `cases: Vec<(u64, Address)>` - index value and destination of each entry
+    /// - `default: Option<Address>` - destination of the index values out of the table
+    ///
+    /// ### Returns
+    /// - `Self` - newly created jump table
+    pub(crate) fn new(
+        index: Aos<IrData>,
+        cases: Vec<(u64, Address)>,
+        default: Option<Address>,
+    ) -> Self {
+        Self {
+            index,
+            cases,
+            default,
+        }
+    }
+
+    /// Returns the data holding the index into the table.
+    ///
+    /// ### Returns
+    /// - `&Aos<IrData>` - index of the table
+    pub fn get_index(&self) -> &Aos<IrData> {
+        &self.index
+    }
+
+    /// Returns the entries of the table.
+    ///
+    /// ### Returns
+    /// - `&[(u64, Address)]` - index value and destination of each entry
+    pub fn get_cases(&self) -> &[(u64, Address)] {
+        &self.cases
+    }
+
+    /// Returns the destination of the index values out of the table.
+    ///
+    /// ### Returns
+    /// - `Option<&Address>` - where the bounds check jumps, if it was found
+    pub fn get_default(&self) -> Option<&Address> {
+        self.default.as_ref()
+    }
+}
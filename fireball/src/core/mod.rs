@@ -6,7 +6,11 @@ mod block;
 mod blocks;
 mod fire;
 mod fire_raw;
+mod function_discovery;
 mod instruction;
+mod jump_table;
+mod noreturn;
+mod patch;
 mod pre_defined_offset;
 mod pre_defined_offsets;
 mod relation;
@@ -20,7 +24,13 @@ pub(crate) use blocks::BlockRelationInformation;
 pub use blocks::Blocks;
 pub use fire::Fire;
 pub use fire_raw::FireRaw;
+pub(crate) use function_discovery::discover_functions;
 pub use instruction::Instruction;
+pub use jump_table::JumpTable;
+pub(crate) use noreturn::{NoReturnFunctions, is_ip_relative};
+#[cfg(feature = "keystone")]
+pub(crate) use patch::assemble;
+pub(crate) use patch::patch_binary;
 pub use pre_defined_offset::PreDefinedOffset;
 pub use pre_defined_offsets::PreDefinedOffsets;
 pub use relation::{DestinationType, Relation, RelationType};
@@ -0,0 +1,340 @@
+//! Module deciding which functions never return, so the bytes after a call to them are not
+//! analyzed as the continuation of the caller
+
+use super::{Address, Block, PreDefinedOffsets, RelationType};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    sync::{Arc, RwLock},
+};
+
+/// Library functions known to never return
+const KNOWN_NORETURN: &[&str] = &[
+    // C runtime
+    "abort",
+    "exit",
+    "_exit",
+    "_Exit",
+    "quick_exit",
+    "__libc_start_main",
+    "__assert_fail",
+    "__assert_perror_fail",
+    "__assert_rtn",
+    "__assert",
+    "_assert",
+    "_wassert",
+    "__stack_chk_fail",
+    "__stack_chk_fail_local",
+    "__chk_fail",
+    "__fortify_fail",
+    "longjmp",
+    "_longjmp",
+    "siglongjmp",
+    "__longjmp_chk",
+    "pthread_exit",
+    "err",
+    "errx",
+    "verr",
+    "verrx",
+    "_amsg_exit",
+    "_invalid_parameter_noinfo",
+    "_invalid_parameter_noinfo_noreturn",
+    "_invoke_watson",
+    "__report_gsfailure",
+    "__report_rangecheckfailure",
+    // C++ runtime
+    "__cxa_throw",
+    "__cxa_rethrow",
+    "__cxa_bad_cast",
+    "__cxa_bad_typeid",
+    "__cxa_pure_virtual",
+    "__cxa_throw_bad_array_new_length",
+    "_Unwind_Resume",
+    "_CxxThrowException",
+    "__std_terminate",
+    "terminate",
+    "std::terminate",
+    "std::_Xbad_alloc",
+    "std::_Xbad_function_call",
+    "std::_Xinvalid_argument",
+    "std::_Xlength_error",
+    "std::_Xout_of_range",
+    "std::_Xoverflow_error",
+    "std::_Xruntime_error",
+    "std::_Throw_bad_array_new_length",
+    // Windows
+    "ExitProcess",
+    "ExitThread",
+    "FreeLibraryAndExitThread",
+    "FatalExit",
+    "FatalAppExitA",
+    "FatalAppExitW",
+    "RtlExitUserProcess",
+    "RtlExitUserThread",
+];
+
+/// Prefixes of library function families that never return
+const KNOWN_NORETURN_PREFIXES: &[&str] = &[
+    "std::__throw_",
+    "core::panicking::",
+    "std::panicking::begin_panic",
+    "std::process::exit",
+];
+
+/// Blocks walked at most to decide a function never returns
+const MAX_BLOCKS: usize = 4096;
+/// Functions analyzed at most at the same time, through the calls of one another
+const MAX_DEPTH: usize = 64;
+
+thread_local! {
+    /// Functions being analyzed by this thread, outermost first, and whether their result
+    /// relies on an assumption (a function deeper than [`MAX_DEPTH`] or one of the functions
+    /// being analyzed assumed to return)
+    static IN_PROGRESS: RefCell<Vec<(u64, bool)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Whether the symbol names a library function known to never return.
+///
+/// ### Arguments
+/// - `name: &str` - name of the symbol, demangled
+///
+/// ### Returns
+/// - `bool` - whether the function never returns
+pub(crate) fn is_known_noreturn(name: &str) -> bool {
+    let name = normalize_name(name);
+    KNOWN_NORETURN.contains(&name) || KNOWN_NORETURN_PREFIXES.iter().any(|x| name.starts_with(x))
+}
+
+/// Strips the parts of a symbol name that don't identify the function: the dll of PE imports,
+/// the import thunk prefix, the parameters of demangled names, symbol versions and the hash of
+/// rust symbols.
+fn normalize_name(name: &str) -> &str {
+    let name = match name.split_once("::") {
+        Some((dll, rest)) if dll.to_ascii_lowercase().ends_with(".dll") => rest,
+        _ => name,
+    };
+    let name = name.strip_prefix("__imp_").unwrap_or(name);
+    let name = name.split(['(', '@']).next().unwrap_or(name).trim();
+    match name.rsplit_once("::h") {
+        Some((path, hash)) if hash.len() == 16 && hash.bytes().all(|x| x.is_ascii_hexdigit()) => {
+            path
+        }
+        _ => name,
+    }
+}
+
+/// Whether the only registers of the memory operand are RIP/EIP.
+pub(crate) fn is_ip_relative(args: &[iceball::RelativeAddressingArgument]) -> bool {
+    args.iter()
+        .filter(|x| {
+            matches!(
+                x,
+                iceball::RelativeAddressingArgument::Register(_)
+                    | iceball::RelativeAddressingArgument::ExtendedRegister(..)
+            )
+        })
+        .all(|x| {
+            matches!(
+                x,
+                iceball::RelativeAddressingArgument::Register(iceball::Register::X64(
+                    iceball::X64Register::Eip | iceball::X64Register::Rip,
+                ))
+            )
+        })
+}
+
+/// Functions found to never return
+#[derive(Default)]
+pub(crate) struct NoReturnFunctions {
+    /// Addresses of the symbols of known library functions
+    known: HashSet<u64>,
+    /// Result of the functions analyzed
+    analyzed: RwLock<HashMap<u64, bool>>,
+}
+
+impl NoReturnFunctions {
+    /// Creates the container, with the symbols of known library functions.
+    ///
+    /// ### Arguments
+    /// - `defined: &PreDefinedOffsets` - symbols of the binary (imports, PLT stubs, exports)
+    ///
+    /// ### Returns
+    /// - `Self` - the container
+    pub(crate) fn new(defined: &PreDefinedOffsets) -> Self {
+        let known = defined
+            .get_reader()
+            .iter()
+            .filter(|x| is_known_noreturn(&x.name))
+            .map(|x| x.address.get_virtual_address())
+            .collect();
+        Self {
+            known,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the address is the symbol (function, PLT stub or import slot) of a library
+    /// function known to never return.
+    pub(crate) fn is_known(&self, address: &Address) -> bool {
+        self.known.contains(&address.get_virtual_address())
+    }
+
+    /// Whether the call never returns, by the name of its target or by analyzing the target.
+    ///
+    /// ### Arguments
+    /// - `inst: &iceball::Instruction` - the call instruction
+    /// - `destination: Option<&Address>` - target of the call, if known
+    /// - `ip_relative: &dyn Fn(&[iceball::RelativeAddressingArgument], u64) -> Address` -
+    ///   resolves a RIP-relative operand of an instruction of the given length
+    /// - `generate: &dyn Fn(&Address) -> Arc<Block>` - generates the block starting at an address
+    ///
+    /// ### Returns
+    /// - `bool` - whether the call never returns
+    pub(crate) fn is_noreturn_call(
+        &self,
+        inst: &iceball::Instruction,
+        destination: Option<&Address>,
+        ip_relative: &dyn Fn(&[iceball::RelativeAddressingArgument], u64) -> Address,
+        generate: &dyn Fn(&Address) -> Arc<Block>,
+    ) -> bool {
+        // slot an imported function is called through
+        let slot = match crate::arch::branch_target_argument(inst) {
+            Some(iceball::Argument::Memory(iceball::Memory::AbsoluteAddressing(offset))) => {
+                Some(*offset)
+            }
+            Some(iceball::Argument::Memory(iceball::Memory::RelativeAddressing(args)))
+                if is_ip_relative(args) =>
+            {
+                let instruction_len = inst.bytes.as_ref().map(|x| x.len() as u64).unwrap_or(0);
+                Some(ip_relative(args, instruction_len).get_virtual_address())
+            }
+            _ => None,
+        };
+        if slot.is_some_and(|x| self.known.contains(&x))
+            || destination.is_some_and(|x| self.is_known(x))
+        {
+            return true;
+        }
+        destination.is_some_and(|x| self.is_noreturn(x, generate))
+    }
+
+    /// Whether the function never returns, that is none of its paths reach a return.
+    ///
+    /// ### Arguments
+    /// - `address: &Address` - start address of the function
+    /// - `generate: &dyn Fn(&Address) -> Arc<Block>` - generates the block starting at an address
+    ///
+    /// ### Returns
+    /// - `bool` - whether the function never returns
+    ///
+    /// ### Note
+    /// Calls of the function to itself, functions too large to walk, and paths leaving through
+    /// an unknown destination are assumed to return. Results relying on a function being
+    /// analyzed further up the calls are not kept, so they don't depend on where the analysis
+    /// started.
+    pub(crate) fn is_noreturn(
+        &self,
+        address: &Address,
+        generate: &dyn Fn(&Address) -> Arc<Block>,
+    ) -> bool {
+        let va = address.get_virtual_address();
+        if self.known.contains(&va) {
+            return true;
+        }
+        if let Some(result) = self.analyzed.read().unwrap().get(&va) {
+            return *result;
+        }
+        let assumed = IN_PROGRESS.with_borrow_mut(|in_progress| {
+            // the functions after the one called again rely on it returning
+            let assumed_from = match in_progress.iter().position(|(x, _)| *x == va) {
+                Some(index) => index + 1,
+                None if in_progress.len() >= MAX_DEPTH => 0,
+                None => {
+                    in_progress.push((va, false));
+                    return false;
+                }
+            };
+            for (_, assumed) in &mut in_progress[assumed_from..] {
+                *assumed = true;
+            }
+            true
+        });
+        if assumed {
+            return false;
+        }
+        let result = !self.reaches_return(address, generate);
+        let (_, assumed) = IN_PROGRESS.with_borrow_mut(|x| x.pop()).unwrap();
+        if !assumed {
+            self.analyzed.write().unwrap().insert(va, result);
+        }
+        result
+    }
+
+    /// Whether any path from the start of the function reaches a return.
+    ///
+    /// Calls whose callee never returns are generated without the relation to the instruction
+    /// after them, so the path ends there.
+    fn reaches_return(&self, start: &Address, generate: &dyn Fn(&Address) -> Arc<Block>) -> bool {
+        let mut visited = HashSet::new();
+        let mut queue = vec![start.clone()];
+        while let Some(address) = queue.pop() {
+            if !visited.insert(address.get_virtual_address()) {
+                continue;
+            }
+            if visited.len() > MAX_BLOCKS {
+                return true;
+            }
+            let block = generate(&address);
+            let relations = block.get_connected_to();
+            // the instructions couldn't be parsed
+            if relations.is_empty() {
+                return true;
+            }
+            for relation in relations.iter() {
+                match *relation.relation_type() {
+                    RelationType::Return => return true,
+                    RelationType::Call => {}
+                    RelationType::Jump
+                    | RelationType::Jcc
+                    | RelationType::Continued
+                    | RelationType::Halt
+                    | RelationType::Switch
+                    | RelationType::Exception => match relation.to() {
+                        // tail call to a function known to never return
+                        Some(to)
+                            if *relation.relation_type() == RelationType::Jump
+                                && self.is_known(&to) => {}
+                        Some(to) => queue.push(to),
+                        None => return true,
+                    },
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_known_noreturn() {
+        assert!(is_known_noreturn("exit"));
+        assert!(is_known_noreturn("KERNEL32.dll::ExitProcess"));
+        assert!(is_known_noreturn(
+            "api-ms-win-crt-runtime-l1-1-0.dll::_invalid_parameter_noinfo"
+        ));
+        assert!(is_known_noreturn("__imp_abort"));
+        assert!(is_known_noreturn("std::terminate()"));
+        assert!(is_known_noreturn("std::__throw_length_error(char const*)"));
+        assert!(is_known_noreturn(
+            "core::panicking::panic_fmt::h0123456789abcdef"
+        ));
+        assert!(is_known_noreturn("__stack_chk_fail@GLIBC_2.4"));
+        assert!(!is_known_noreturn("printf"));
+        assert!(!is_known_noreturn("KERNEL32.dll::GetProcAddress"));
+        assert!(!is_known_noreturn("std::exit_guard::release()"));
+        assert!(!is_known_noreturn("exit_handler"));
+    }
+}
@@ -0,0 +1,141 @@
+//! Module patching instructions of the loaded binary
+
+use crate::{
+    core::{Address, Blocks},
+    prelude::*,
+};
+use iceball::MachineArchitecture;
+#[cfg(feature = "keystone")]
+use keystone_engine::{Arch, Keystone, Mode, OptionType, OptionValue};
+
+/// Writes machine code over the binary data.
+///
+/// ### Arguments
+/// - `binary: &mut [u8]` - binary data to patch
+/// - `blocks: &Blocks` - analyzed blocks, the ones overlapping the patch are removed
+/// - `architecture: MachineArchitecture` - architecture of the instructions
+/// - `address: &Address` - address of the first replaced instruction
+/// - `bytes: &[u8]` - machine code of the replacement instructions
+///
+/// ### Returns
+/// - `Result<Vec<Address>, FireballError>` - start addresses of the blocks to analyze again
+///
+/// ### Note
+/// - On x86, an instruction partially overwritten by the patch is filled up with `nop`s.
+pub(crate) fn patch_binary(
+    binary: &mut [u8],
+    blocks: &Blocks,
+    architecture: MachineArchitecture,
+    address: &Address,
+    bytes: &[u8],
+) -> Result<Vec<Address>, FireballError> {
+    let start = address.get_virtual_address();
+    if bytes.is_empty() {
+        return Err(FireballError::PatchFailed(format!(
+            "no instructions to patch at {start:#x}"
+        )));
+    }
+    let mut bytes = bytes.to_vec();
+
+    // Fill up the instruction the patch ends in
+    let end = start + bytes.len() as u64;
+    let is_x86 = matches!(
+        architecture,
+        MachineArchitecture::X64 | MachineArchitecture::X86
+    );
+    let partial_end = blocks
+        .get_all()
+        .iter()
+        .flat_map(|block| block.get_instructions().iter())
+        .find_map(|instruction| {
+            let size = instruction
+                .inner
+                .bytes
+                .as_ref()
+                .map(|x| x.len())
+                .unwrap_or(0);
+            let instruction_end = instruction.address + size as u64;
+            (instruction.address < end && end < instruction_end).then_some(instruction_end)
+        });
+    if is_x86 && let Some(partial_end) = partial_end {
+        bytes.resize((partial_end - start) as usize, 0x90);
+    }
+
+    write_bytes(binary, address, &bytes)?;
+    debug!("Patched {} bytes at {:#x}", bytes.len(), start);
+
+    let mut starts: Vec<Address> = blocks
+        .remove_overlapping(address, bytes.len() as u64)
+        .iter()
+        .map(|block| block.get_start_address().clone())
+        .collect();
+    if !starts.iter().any(|x| x <= address) {
+        starts.push(address.clone());
+    }
+    starts.sort_unstable_by_key(|x| x.get_virtual_address());
+    starts.dedup();
+    Ok(starts)
+}
+
+/// Assembles instructions with keystone.
+///
+/// ### Arguments
+/// - `architecture: MachineArchitecture` - architecture of the instructions
+/// - `address: u64` - virtual address the instructions are placed at
+/// - `assembly: &str` - instructions, separated by `;` or new lines
+///
+/// ### Returns
+/// - `Result<Vec<u8>, FireballError>` - the machine code
+#[cfg(feature = "keystone")]
+pub(crate) fn assemble(
+    architecture: MachineArchitecture,
+    address: u64,
+    assembly: &str,
+) -> Result<Vec<u8>, FireballError> {
+    let failed = |err| FireballError::PatchFailed(format!("{err:?}"));
+    let (arch, mode) = match architecture {
+        MachineArchitecture::X64 => (Arch::X86, Mode::MODE_64),
+        MachineArchitecture::X86 => (Arch::X86, Mode::MODE_32),
+        MachineArchitecture::Arm64 => (Arch::ARM64, Mode::LITTLE_ENDIAN),
+        MachineArchitecture::Arm => (Arch::ARM, Mode::ARM),
+    };
+    let engine = Keystone::new(arch, mode).map_err(failed)?;
+    if matches!(
+        architecture,
+        MachineArchitecture::X64 | MachineArchitecture::X86
+    ) {
+        engine
+            .option(OptionType::SYNTAX, OptionValue::SYNTAX_INTEL)
+            .map_err(failed)?;
+    }
+    let result = engine.asm(assembly.to_string(), address).map_err(failed)?;
+    Ok(result.bytes)
+}
+
+/// Writes bytes at an address, which must lie in the file data of a section.
+fn write_bytes(binary: &mut [u8], address: &Address, bytes: &[u8]) -> Result<(), FireballError> {
+    let (Some(section), Some(file_offset)) = (address.get_section(), address.get_file_offset())
+    else {
+        return Err(FireballError::PatchFailed(format!(
+            "{:#x} is not backed by file data",
+            address.get_virtual_address()
+        )));
+    };
+    let end = file_offset + bytes.len() as u64;
+    if end > section.file_offset + section.size_of_file {
+        return Err(FireballError::PatchFailed(format!(
+            "patch of {} bytes at {:#x} exceeds section {}",
+            bytes.len(),
+            address.get_virtual_address(),
+            section.name
+        )));
+    }
+    let Some(target) = binary.get_mut(file_offset as usize..end as usize) else {
+        return Err(FireballError::PatchFailed(format!(
+            "{:#x} is outside of the binary",
+            address.get_virtual_address()
+        )));
+    };
+    target.copy_from_slice(bytes);
+    Ok(())
+}
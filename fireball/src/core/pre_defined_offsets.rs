@@ -27,6 +27,11 @@ impl PreDefinedOffsets {
         self.data.write().unwrap().push(data);
     }
 
+    /// Removes every pre-defined offset.
+    pub(crate) fn clear(&self) {
+        self.data.write().unwrap().clear();
+    }
+
     /// Returns a read guard for the internal data.
     ///
     /// ### Returns
@@ -15,7 +15,7 @@ pub struct Relation {
     relation_type: RelationType,
 }
 
-#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum DestinationType {
     /// Static address
     Static,
@@ -24,7 +24,7 @@ pub enum DestinationType {
 }
 
 /// Types of connections between code blocks
-#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, Hash, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum RelationType {
     /// Indicates a call connection
     Call,
@@ -37,6 +37,10 @@ pub enum RelationType {
     Continued,
     /// Indicates a return connection
     Return,
+    /// Indicates one destination of a jump table
+    Switch,
+    /// Indicates the handler of the exceptions raised in the block
+    Exception,
 }
 
 impl Relation {
@@ -23,6 +23,17 @@ impl Relations {
     pub(crate) fn add_relation(&self, relation: Relation) {
         self.data.write().unwrap().push(relation);
     }
+    /// Removes the relations starting from a block.
+    ///
+    /// ### Arguments
+    /// - `from: usize`: ID of the source block
+    pub(crate) fn remove_relations_from(&self, from: usize) {
+        self.data.write().unwrap().retain(|x| x.from() != from);
+    }
+    /// Removes every relation.
+    pub(crate) fn clear(&self) {
+        self.data.write().unwrap().clear();
+    }
     pub fn get_relations(&self) -> RwLockReadGuard<'_, Vec<Relation>> {
         self.data.read().unwrap()
     }
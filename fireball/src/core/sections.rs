@@ -3,6 +3,9 @@
 use super::Section;
 use std::sync::Arc;
 
+/// Address the first section of a relocatable object is laid out at
+pub(crate) const OBJECT_BASE_ADDRESS: u64 = 0x1000;
+
 /// Struct that holding all section's information.
 /// Data in this struct is immutable, only can write when start analysis. (By build_all method)
 pub struct Sections {
@@ -50,6 +53,28 @@ impl Sections {
                     }));
                 }
             }
+            goblin::Object::COFF(coff) => {
+                // Sections of an object all start at zero
+                let (layout, _) = Self::coff_object_layout(&coff);
+                for (section, virtual_address) in coff.sections.iter().zip(layout) {
+                    let Some(virtual_address) = virtual_address else {
+                        continue;
+                    };
+                    let id = section_writer.len();
+                    let name = section.name().unwrap_or("").to_string();
+
+                    section_writer.insert(Arc::new(Section {
+                        id,
+                        name,
+                        real_name: section.real_name.clone(),
+                        virtual_address,
+                        virtual_size: section.size_of_raw_data as u64,
+                        file_offset: section.pointer_to_raw_data as u64,
+                        size_of_file: section.size_of_raw_data as u64,
+                        characteristics: section.characteristics,
+                    }));
+                }
+            }
             goblin::Object::Elf(elf) => {
                 // ELF section flag constants
                 const SHF_WRITE: u64 = 0x1;
@@ -60,11 +85,18 @@ impl Sections {
                 const IMAGE_SCN_MEM_READ: u32 = 0x4000_0000;
                 const IMAGE_SCN_MEM_WRITE: u32 = 0x8000_0000;
 
-                for sh in &elf.section_headers {
+                // Sections of an object all start at zero
+                let layout = (elf.header.e_type == goblin::elf::header::ET_REL)
+                    .then(|| Self::elf_object_layout(&elf).0);
+                for (index, sh) in elf.section_headers.iter().enumerate() {
                     // Only include sections with SHF_ALLOC (runtime-mapped sections)
                     if sh.sh_flags & SHF_ALLOC == 0 {
                         continue;
                     }
+                    let virtual_address = match &layout {
+                        Some(layout) => layout[index].unwrap_or(sh.sh_addr),
+                        None => sh.sh_addr,
+                    };
 
                     let id = section_writer.len();
                     let name = elf.shdr_strtab.get_at(sh.sh_name).unwrap_or("").to_string();
@@ -85,7 +117,7 @@ impl Sections {
                         id,
                         name,
                         real_name: None,
-                        virtual_address: sh.sh_addr,
+                        virtual_address,
                         virtual_size: sh.sh_size,
                         file_offset: sh.sh_offset,
                         size_of_file: sh.sh_size,
@@ -163,6 +195,79 @@ impl Sections {
         }
     }
 
+    /// Assigns each loaded section of a relocatable object a distinct address.
+    ///
+    /// ### Arguments
+    /// - `sections: impl IntoIterator<Item = Option<(u64, u64)>>` - size and alignment of each
+    ///   section, or `None` if the section is not loaded
+    ///
+    /// ### Returns
+    /// - `(Vec<Option<u64>>, u64)` - address of each section, and the end of the laid out sections
+    pub(crate) fn layout_object_sections(
+        sections: impl IntoIterator<Item = Option<(u64, u64)>>,
+    ) -> (Vec<Option<u64>>, u64) {
+        let mut cursor = OBJECT_BASE_ADDRESS;
+        let layout = sections
+            .into_iter()
+            .map(|section| {
+                let (size, align) = section?;
+                let address = cursor.next_multiple_of(align.max(1));
+                cursor = address + size;
+                Some(address)
+            })
+            .collect();
+        (layout, cursor)
+    }
+
+    /// Lays out the `SHF_ALLOC` sections of an ELF relocatable object.
+    ///
+    /// ### Arguments
+    /// - `elf: &goblin::elf::Elf` - parsed relocatable object
+    ///
+    /// ### Returns
+    /// - `(Vec<Option<u64>>, u64)` - address of each section header, and the end of the layout
+    pub(crate) fn elf_object_layout(elf: &goblin::elf::Elf) -> (Vec<Option<u64>>, u64) {
+        const SHF_ALLOC: u64 = 0x2;
+        Self::layout_object_sections(
+            elf.section_headers
+                .iter()
+                .map(|sh| (sh.sh_flags & SHF_ALLOC != 0).then_some((sh.sh_size, sh.sh_addralign))),
+        )
+    }
+
+    /// Lays out the sections of a COFF object that end up in the image.
+    ///
+    /// ### Arguments
+    /// - `coff: &goblin::pe::Coff` - parsed object
+    ///
+    /// ### Returns
+    /// - `(Vec<Option<u64>>, u64)` - address of each section, and the end of the layout
+    ///
+    /// ### Note
+    /// - Linker directives (`.drectve`) and discardable sections (`.debug$S`) are not loaded.
+    pub(crate) fn coff_object_layout(coff: &goblin::pe::Coff) -> (Vec<Option<u64>>, u64) {
+        const IMAGE_SCN_CNT_ANY: u32 = 0x0000_00e0;
+        const IMAGE_SCN_LNK_INFO: u32 = 0x0000_0200;
+        const IMAGE_SCN_LNK_REMOVE: u32 = 0x0000_0800;
+        const IMAGE_SCN_MEM_DISCARDABLE: u32 = 0x0200_0000;
+        Self::layout_object_sections(coff.sections.iter().map(|section| {
+            let characteristics = section.characteristics;
+            if characteristics & IMAGE_SCN_CNT_ANY == 0
+                || characteristics
+                    & (IMAGE_SCN_LNK_INFO | IMAGE_SCN_LNK_REMOVE | IMAGE_SCN_MEM_DISCARDABLE)
+                    != 0
+            {
+                return None;
+            }
+            // IMAGE_SCN_ALIGN_*BYTES, 16 bytes when not specified
+            let align = match (characteristics >> 20) & 0xf {
+                0 => 16,
+                n => 1 << (n - 1),
+            };
+            Some((section.size_of_raw_data as u64, align))
+        }))
+    }
+
     /// Function that return section by virtual address.
     /// It calcs section's from and to address, and return section if virtual address is in range.
     #[allow(clippy::wrong_self_convention)]
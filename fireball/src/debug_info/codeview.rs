@@ -0,0 +1,731 @@
+//! Module reading CodeView debug information from PDB files
+
+use super::{DebugFunction, DebugInfo, DebugLocation, DebugVariable, SourceLine};
+use crate::{
+    abstract_syntax_tree::{AstValueType, AstVariable, AstVariableId},
+    prelude::*,
+};
+use hashbrown::HashMap;
+use iceball::MachineArchitecture;
+use pdb::FallibleIterator;
+
+type PdbSource = std::io::Cursor<Vec<u8>>;
+
+// Symbol kinds the pdb crate does not parse
+const S_FRAMEPROC: u16 = 0x1012;
+const S_DEFRANGE_REGISTER: u16 = 0x1141;
+const S_DEFRANGE_FRAMEPOINTER_REL: u16 = 0x1142;
+const S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE: u16 = 0x1144;
+const S_DEFRANGE_REGISTER_REL: u16 = 0x1145;
+// Procedures whose type index refers to the id stream
+const S_LPROC32_ID: u16 = 0x1146;
+const S_GPROC32_ID: u16 = 0x1147;
+const S_LPROC32_DPC_ID: u16 = 0x1156;
+
+/// Symbols and debug information read from a PDB file
+pub(crate) struct PdbDebugInfo {
+    /// Public (mangled) and private symbol names, by relative virtual address
+    pub(crate) symbols: Vec<(String, u64)>,
+    pub(crate) debug_info: DebugInfo,
+}
+
+/// Returns whether a PDB file was written along with the binary of a CodeView record.
+///
+/// ### Arguments
+/// - `data: &[u8]` - PDB file data
+/// - `signature: &[u8; 16]` - GUID of the CodeView (`RSDS`) record of the binary
+/// - `age: u32` - age of the CodeView record of the binary
+pub(crate) fn pdb_matches(data: &[u8], signature: &[u8; 16], age: u32) -> bool {
+    let Ok(mut pdb_file) = pdb::PDB::open(std::io::Cursor::new(data.to_vec())) else {
+        return false;
+    };
+    let Ok(information) = pdb_file.pdb_information() else {
+        return false;
+    };
+    // the debug information stream keeps the age the binary was linked with
+    let pdb_age = pdb_file
+        .debug_information()
+        .ok()
+        .and_then(|dbi| dbi.age())
+        .unwrap_or(information.age);
+    information.guid.to_bytes_le() == *signature && pdb_age == age
+}
+
+/// Reads the symbols, functions, local variables and source lines of a PDB file.
+///
+/// ### Arguments
+/// - `data: Vec<u8>` - PDB file data
+/// - `architecture: MachineArchitecture` - architecture registers are named with
+///
+/// ### Returns
+/// - `Result<PdbDebugInfo, FireballError>` - the symbols and debug information
+///
+/// ### Note
+/// - Addresses are relative virtual addresses, as `Pe` uses them.
+pub(crate) fn load_pdb(
+    data: Vec<u8>,
+    architecture: MachineArchitecture,
+) -> Result<PdbDebugInfo, FireballError> {
+    let mut pdb_file = pdb::PDB::open(std::io::Cursor::new(data))?;
+    let address_map = pdb_file.address_map()?;
+    let type_information = pdb_file.type_information()?;
+    // PDBs written before the id stream existed keep function types in procedure symbols
+    let id_information = pdb_file.id_information().ok();
+    let string_table = pdb_file.string_table().ok();
+
+    let mut types = TypeReader::new(&type_information)?;
+    let mut ids = id_information.as_ref().map(|x| x.finder());
+    if let (Some(information), Some(ids)) = (&id_information, &mut ids) {
+        let mut iter = information.iter();
+        while iter.next()?.is_some() {
+            ids.update(&iter);
+        }
+    }
+
+    let mut symbols = Vec::new();
+    let mut info = DebugInfo::default();
+
+    // Private symbols, functions and lines of each module
+    let dbi = pdb_file.debug_information()?;
+    let mut modules = dbi.modules()?;
+    while let Some(module) = modules.next()? {
+        let Some(module_info) = pdb_file.module_info(&module)? else {
+            continue;
+        };
+        let mut reader = ProcedureReader {
+            types: &mut types,
+            ids: ids.as_ref(),
+            architecture,
+            function: None,
+            end: pdb::SymbolIndex(0),
+            parameter_count: 0,
+            frame_pointer_is_base: false,
+            pending_local: None,
+        };
+        let mut module_symbols = module_info.symbols()?;
+        while let Some(symbol) = module_symbols.next()? {
+            match symbol.parse() {
+                Ok(pdb::SymbolData::Procedure(procedure)) => {
+                    let Some(rva) = procedure.offset.to_rva(&address_map) else {
+                        continue;
+                    };
+                    let name = procedure.name.to_string().into_owned();
+                    symbols.push((name.clone(), rva.0 as u64));
+                    if let Some(function) = reader.finish() {
+                        info.insert_function(function);
+                    }
+                    reader.start(symbol.raw_kind(), &procedure, name, rva.0 as u64);
+                }
+                Ok(pdb::SymbolData::Data(data)) => {
+                    if let Some(rva) = data.offset.to_rva(&address_map) {
+                        symbols.push((data.name.to_string().into_owned(), rva.0 as u64));
+                    }
+                }
+                Ok(pdb::SymbolData::RegisterRelative(variable)) => reader.add_register_relative(
+                    variable.name.to_string().into_owned(),
+                    variable.type_index,
+                    variable.register,
+                    variable.offset,
+                ),
+                Ok(pdb::SymbolData::RegisterVariable(variable)) => {
+                    let location = register_name(architecture, variable.register)
+                        .map(|x| DebugLocation::Register(x.to_string()));
+                    reader.add_variable(
+                        variable.name.to_string().into_owned(),
+                        variable.type_index,
+                        location,
+                        None,
+                    );
+                }
+                Ok(pdb::SymbolData::Local(local)) => reader.add_local(
+                    local.name.to_string().into_owned(),
+                    local.type_index,
+                    local.flags.isparam,
+                ),
+                _ => reader.read_raw(
+                    symbol.raw_kind(),
+                    symbol.raw_bytes().get(2..).unwrap_or_default(),
+                ),
+            }
+            if symbol.index() == reader.end
+                && let Some(function) = reader.finish()
+            {
+                info.insert_function(function);
+            }
+        }
+        if let Some(function) = reader.finish() {
+            info.insert_function(function);
+        }
+
+        if let Some(string_table) = &string_table {
+            read_lines(&module_info, &address_map, string_table, &mut info)?;
+        }
+    }
+
+    // Public symbols, after the private ones naming the same functions without decoration
+    if let Ok(global_symbols) = pdb_file.global_symbols() {
+        let mut iter = global_symbols.iter();
+        while let Some(symbol) = iter.next()? {
+            if let Ok(pdb::SymbolData::Public(public)) = symbol.parse()
+                && let Some(rva) = public.offset.to_rva(&address_map)
+            {
+                symbols.push((public.name.to_string().into_owned(), rva.0 as u64));
+            }
+        }
+    }
+
+    Ok(PdbDebugInfo {
+        symbols,
+        debug_info: info,
+    })
+}
+
+fn read_lines(
+    module_info: &pdb::ModuleInfo<'_>,
+    address_map: &pdb::AddressMap<'_>,
+    string_table: &pdb::StringTable<'_>,
+    info: &mut DebugInfo,
+) -> Result<(), FireballError> {
+    let program = module_info.line_program()?;
+    let mut files: HashMap<pdb::FileIndex, String> = HashMap::new();
+    let mut lines = program.lines();
+    while let Some(line) = lines.next()? {
+        let Some(rva) = line.offset.to_rva(address_map) else {
+            continue;
+        };
+        let file = match files.get(&line.file_index) {
+            Some(file) => file.clone(),
+            None => {
+                let path = program.get_file_info(line.file_index)?.name;
+                let path = path.to_string_lossy(string_table)?;
+                // paths are written on Windows
+                let name = path
+                    .rsplit(['\\', '/'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string();
+                files.insert(line.file_index, name.clone());
+                name
+            }
+        };
+        info.insert_line(
+            rva.0 as u64,
+            Some(SourceLine {
+                file,
+                line: line.line_start as u64,
+            }),
+        );
+        if let Some(length) = line.length {
+            info.insert_line(rva.0 as u64 + length as u64, None);
+        }
+    }
+    Ok(())
+}
+
+/// Builds the function of a procedure symbol from the symbols following it
+struct ProcedureReader<'r, 't> {
+    types: &'r mut TypeReader<'t>,
+    ids: Option<&'r pdb::IdFinder<'t>>,
+    architecture: MachineArchitecture,
+    /// Function being read
+    function: Option<DebugFunction>,
+    /// Index of the symbol ending the procedure
+    end: pdb::SymbolIndex,
+    /// Number of parameters of the procedure type, the first register relative variables
+    parameter_count: usize,
+    /// Whether `S_DEFRANGE_FRAMEPOINTER_REL` offsets are from the frame base pointer
+    frame_pointer_is_base: bool,
+    /// `S_LOCAL` variable waiting for its location, and whether it is a parameter
+    pending_local: Option<(DebugVariable, bool)>,
+}
+
+impl ProcedureReader<'_, '_> {
+    fn start(&mut self, kind: u16, procedure: &pdb::ProcedureSymbol<'_>, name: String, start: u64) {
+        let function_type = match kind {
+            S_LPROC32_ID | S_GPROC32_ID | S_LPROC32_DPC_ID => {
+                let id = self
+                    .ids
+                    .map(|x| x.find(pdb::IdIndex(procedure.type_index.0)));
+                match id.and_then(|x| x.and_then(|x| x.parse()).ok()) {
+                    Some(pdb::IdData::Function(function)) => Some(function.function_type),
+                    Some(pdb::IdData::MemberFunction(function)) => Some(function.function_type),
+                    _ => None,
+                }
+            }
+            _ => Some(procedure.type_index),
+        };
+        let (return_type, parameter_types) = function_type
+            .map(|x| self.types.signature(x))
+            .unwrap_or((AstValueType::Unknown, Vec::new()));
+        self.parameter_count = parameter_types.len();
+        self.end = procedure.end;
+        self.frame_pointer_is_base = false;
+        self.pending_local = None;
+        self.function = Some(DebugFunction {
+            name,
+            start,
+            end: start + procedure.len as u64,
+            return_type,
+            parameters: Vec::new(),
+            variables: Vec::new(),
+        });
+    }
+
+    fn finish(&mut self) -> Option<DebugFunction> {
+        self.flush_local();
+        self.function.take()
+    }
+
+    fn add_variable(
+        &mut self,
+        name: String,
+        type_index: pdb::TypeIndex,
+        location: Option<DebugLocation>,
+        is_parameter: Option<bool>,
+    ) {
+        let var_type = self.types.type_of(type_index);
+        let Some(function) = &mut self.function else {
+            return;
+        };
+        let is_parameter = is_parameter.unwrap_or(function.parameters.len() < self.parameter_count);
+        let variable = DebugVariable {
+            name,
+            var_type,
+            location,
+        };
+        if is_parameter {
+            function.parameters.push(variable);
+        } else {
+            function.variables.push(variable);
+        }
+    }
+
+    fn add_register_relative(
+        &mut self,
+        name: String,
+        type_index: pdb::TypeIndex,
+        register: pdb::Register,
+        offset: i32,
+    ) {
+        // parameters come first, in declaration order
+        let location = is_base_pointer(self.architecture, register)
+            .then_some(DebugLocation::FrameOffset(offset as i64));
+        let is_parameter = self
+            .function
+            .as_ref()
+            .is_some_and(|x| x.variables.is_empty() && x.parameters.len() < self.parameter_count);
+        self.add_variable(name, type_index, location, Some(is_parameter));
+    }
+
+    fn add_local(&mut self, name: String, type_index: pdb::TypeIndex, is_parameter: bool) {
+        self.flush_local();
+        let variable = DebugVariable {
+            name,
+            var_type: self.types.type_of(type_index),
+            location: None,
+        };
+        self.pending_local = Some((variable, is_parameter));
+    }
+
+    fn flush_local(&mut self) {
+        let Some((variable, is_parameter)) = self.pending_local.take() else {
+            return;
+        };
+        let Some(function) = &mut self.function else {
+            return;
+        };
+        if is_parameter {
+            function.parameters.push(variable);
+        } else {
+            function.variables.push(variable);
+        }
+    }
+
+    /// Reads the frame and location records the pdb crate does not parse.
+    fn read_raw(&mut self, kind: u16, data: &[u8]) {
+        let u16_at = |offset: usize| {
+            data.get(offset..offset + 2)
+                .map(|x| u16::from_le_bytes([x[0], x[1]]))
+        };
+        let i32_at = |offset: usize| {
+            data.get(offset..offset + 4)
+                .map(|x| i32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+        };
+        let location = match kind {
+            S_FRAMEPROC => {
+                // flags follow 5 sizes and offsets and the exception handler section
+                if let Some(flags) = data.get(22..26) {
+                    let flags = u32::from_le_bytes([flags[0], flags[1], flags[2], flags[3]]);
+                    // encoded local base pointer: none, sp, bp, or r13 / ebx
+                    self.frame_pointer_is_base = (flags >> 14) & 0b11 == 2;
+                }
+                return;
+            }
+            S_DEFRANGE_REGISTER => u16_at(0)
+                .and_then(|x| register_name(self.architecture, pdb::Register(x)))
+                .map(|x| DebugLocation::Register(x.to_string())),
+            S_DEFRANGE_FRAMEPOINTER_REL | S_DEFRANGE_FRAMEPOINTER_REL_FULL_SCOPE => i32_at(0)
+                .filter(|_| self.frame_pointer_is_base)
+                .map(|x| DebugLocation::FrameOffset(x as i64)),
+            S_DEFRANGE_REGISTER_REL => u16_at(0)
+                .filter(|x| is_base_pointer(self.architecture, pdb::Register(*x)))
+                .and(i32_at(4))
+                .map(|x| DebugLocation::FrameOffset(x as i64)),
+            _ => return,
+        };
+        // the first range of a variable gives its location
+        if let Some((variable, _)) = &mut self.pending_local
+            && variable.location.is_none()
+        {
+            variable.location = location;
+        }
+    }
+}
+
+/// Returns the name of a CodeView register, as the ir names it.
+fn register_name(
+    architecture: MachineArchitecture,
+    register: pdb::Register,
+) -> Option<&'static str> {
+    const X86: [&str; 8] = ["eax", "ecx", "edx", "ebx", "esp", "ebp", "esi", "edi"];
+    const X64: [&str; 16] = [
+        "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp", "r8", "r9", "r10", "r11", "r12",
+        "r13", "r14", "r15",
+    ];
+    let register = register.0 as usize;
+    match architecture {
+        // CV_REG_EAX = 17, also used by x64 binaries for 32 bit values
+        MachineArchitecture::X86 => X86.get(register.checked_sub(17)?).copied(),
+        // CV_AMD64_RAX = 328
+        MachineArchitecture::X64 => match register {
+            17..=24 => X86.get(register - 17).copied(),
+            _ => X64.get(register.checked_sub(328)?).copied(),
+        },
+        // CV_ARM64_X0 = 50, up to CV_ARM64_LR = 80
+        MachineArchitecture::Arm64 => {
+            const ARM64: [&str; 31] = [
+                "x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x8", "x9", "x10", "x11", "x12",
+                "x13", "x14", "x15", "x16", "x17", "x18", "x19", "x20", "x21", "x22", "x23", "x24",
+                "x25", "x26", "x27", "x28", "x29", "x30",
+            ];
+            ARM64.get(register.checked_sub(50)?).copied()
+        }
+        // CV_ARM_R0 = 10
+        MachineArchitecture::Arm => {
+            const ARM: [&str; 16] = [
+                "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "r11", "r12",
+                "sp", "lr", "pc",
+            ];
+            ARM.get(register.checked_sub(10)?).copied()
+        }
+    }
+}
+
+fn is_base_pointer(architecture: MachineArchitecture, register: pdb::Register) -> bool {
+    matches!(
+        register_name(architecture, register),
+        Some("ebp" | "rbp" | "x29" | "r11")
+    )
+}
+
+/// Converts the type records of a PDB into `AstValueType`s
+struct TypeReader<'t> {
+    finder: pdb::TypeFinder<'t>,
+    /// Complete definitions of structs, by name, for forward references
+    definitions: HashMap<String, pdb::TypeIndex>,
+    /// Converted types, structs are inserted without fields while their fields are converted
+    cache: HashMap<pdb::TypeIndex, AstValueType>,
+    /// Number of structs whose fields are being converted
+    building: usize,
+}
+
+impl<'t> TypeReader<'t> {
+    fn new(information: &'t pdb::TypeInformation<'_>) -> Result<Self, FireballError> {
+        let mut finder = information.finder();
+        let mut definitions = HashMap::new();
+        let mut iter = information.iter();
+        while let Some(item) = iter.next()? {
+            finder.update(&iter);
+            let (name, properties) = match item.parse() {
+                Ok(pdb::TypeData::Class(class)) => (class.name, class.properties),
+                Ok(pdb::TypeData::Union(union)) => (union.name, union.properties),
+                _ => continue,
+            };
+            if !properties.forward_reference() {
+                definitions.insert(name.to_string().into_owned(), item.index());
+            }
+        }
+        Ok(Self {
+            finder,
+            definitions,
+            cache: HashMap::new(),
+            building: 0,
+        })
+    }
+
+    /// Returns the return type and the parameter types of a procedure type.
+    fn signature(&mut self, index: pdb::TypeIndex) -> (AstValueType, Vec<AstValueType>) {
+        let (return_type, argument_list) = match self.parse(index) {
+            Some(pdb::TypeData::Procedure(procedure)) => {
+                (procedure.return_type, procedure.argument_list)
+            }
+            Some(pdb::TypeData::MemberFunction(function)) => {
+                (Some(function.return_type), function.argument_list)
+            }
+            _ => return (AstValueType::Unknown, Vec::new()),
+        };
+        let return_type = match return_type {
+            Some(return_type) => self.type_of(return_type),
+            None => AstValueType::Void,
+        };
+        let parameters = match self.parse(argument_list) {
+            Some(pdb::TypeData::ArgumentList(list)) => list.arguments,
+            _ => Vec::new(),
+        };
+        let parameters = parameters.into_iter().map(|x| self.type_of(x)).collect();
+        (return_type, parameters)
+    }
+
+    fn parse(&self, index: pdb::TypeIndex) -> Option<pdb::TypeData<'t>> {
+        self.finder.find(index).and_then(|x| x.parse()).ok()
+    }
+
+    fn type_of(&mut self, index: pdb::TypeIndex) -> AstValueType {
+        if let Some(known) = self.cache.get(&index) {
+            return known.clone();
+        }
+        let value_type = match self.parse(index) {
+            Some(pdb::TypeData::Primitive(primitive)) => {
+                let base = primitive_type(primitive.kind);
+                match primitive.indirection {
+                    Some(_) => AstValueType::Pointer(Box::new(base)),
+                    None => base,
+                }
+            }
+            Some(pdb::TypeData::Pointer(pointer)) => {
+                AstValueType::Pointer(Box::new(self.type_of(pointer.underlying_type)))
+            }
+            Some(pdb::TypeData::Modifier(modifier)) => self.type_of(modifier.underlying_type),
+            Some(pdb::TypeData::Bitfield(bitfield)) => self.type_of(bitfield.underlying_type),
+            Some(pdb::TypeData::Enumeration(enumeration)) => {
+                self.type_of(enumeration.underlying_type)
+            }
+            Some(pdb::TypeData::Array(array)) => {
+                let element = self.type_of(array.element_type);
+                // dimensions are in bytes, each including the lower dimensions
+                let mut inner_size = type_size(&element);
+                array.dimensions.iter().fold(element, |inner, size| {
+                    let count = (*size as usize).checked_div(inner_size).unwrap_or(0);
+                    inner_size = *size as usize;
+                    AstValueType::Array(Box::new(inner), count)
+                })
+            }
+            Some(pdb::TypeData::Class(class)) => {
+                let name = class.name.to_string().into_owned();
+                match (
+                    class.properties.forward_reference(),
+                    self.definitions.get(&name),
+                ) {
+                    (true, Some(definition)) => self.type_of(*definition),
+                    _ => self.record_type(index, name, false, class.fields),
+                }
+            }
+            Some(pdb::TypeData::Union(union)) => {
+                let name = union.name.to_string().into_owned();
+                match (
+                    union.properties.forward_reference(),
+                    self.definitions.get(&name),
+                ) {
+                    (true, Some(definition)) => self.type_of(*definition),
+                    _ => self.record_type(index, name, true, Some(union.fields)),
+                }
+            }
+            _ => AstValueType::Unknown,
+        };
+        // types referring to a struct being converted hold it without fields
+        let is_record = matches!(
+            value_type,
+            AstValueType::Struct(..) | AstValueType::Union(..)
+        );
+        if self.building == 0 || is_record {
+            self.cache.insert(index, value_type.clone());
+        }
+        value_type
+    }
+
+    fn record_type(
+        &mut self,
+        index: pdb::TypeIndex,
+        name: String,
+        is_union: bool,
+        field_list: Option<pdb::TypeIndex>,
+    ) -> AstValueType {
+        let record = |fields| match is_union {
+            true => AstValueType::Union(name.clone(), fields),
+            false => AstValueType::Struct(name.clone(), fields),
+        };
+        self.cache.insert(index, record(Vec::new()));
+        self.building += 1;
+
+        let mut fields = Vec::new();
+        let mut next = field_list;
+        while let Some(list) = next {
+            let Some(pdb::TypeData::FieldList(list)) = self.parse(list) else {
+                break;
+            };
+            for field in list.fields {
+                let pdb::TypeData::Member(member) = field else {
+                    continue;
+                };
+                fields.push(AstVariable {
+                    name: Some(member.name.to_string().into_owned()),
+                    id: AstVariableId {
+                        index: fields.len() as u32,
+                        parent: None,
+                    },
+                    var_type: self.type_of(member.field_type),
+                    const_value: None,
+                    data_access_ir: None,
+                    offset: Some(member.offset),
+                });
+            }
+            // long field lists continue in other records
+            next = list.continuation;
+        }
+        self.building -= 1;
+        record(fields)
+    }
+}
+
+fn primitive_type(kind: pdb::PrimitiveKind) -> AstValueType {
+    use pdb::PrimitiveKind;
+
+    match kind {
+        PrimitiveKind::NoType | PrimitiveKind::Void => AstValueType::Void,
+        PrimitiveKind::Char | PrimitiveKind::RChar => AstValueType::Char,
+        PrimitiveKind::I8 => AstValueType::Int8,
+        PrimitiveKind::UChar | PrimitiveKind::U8 => AstValueType::UInt8,
+        PrimitiveKind::Short | PrimitiveKind::I16 => AstValueType::Int16,
+        PrimitiveKind::WChar
+        | PrimitiveKind::RChar16
+        | PrimitiveKind::UShort
+        | PrimitiveKind::U16 => AstValueType::UInt16,
+        PrimitiveKind::Long | PrimitiveKind::I32 | PrimitiveKind::HRESULT => AstValueType::Int32,
+        PrimitiveKind::RChar32 | PrimitiveKind::ULong | PrimitiveKind::U32 => AstValueType::UInt32,
+        PrimitiveKind::Quad | PrimitiveKind::I64 => AstValueType::Int64,
+        PrimitiveKind::UQuad | PrimitiveKind::U64 => AstValueType::UInt64,
+        PrimitiveKind::F32 | PrimitiveKind::F32PP => AstValueType::Float,
+        PrimitiveKind::F64 => AstValueType::Double,
+        PrimitiveKind::Bool8
+        | PrimitiveKind::Bool16
+        | PrimitiveKind::Bool32
+        | PrimitiveKind::Bool64 => AstValueType::Bool,
+        _ => AstValueType::Unknown,
+    }
+}
+
+/// Returns the size of a type in bytes, 0 if unknown.
+fn type_size(value_type: &AstValueType) -> usize {
+    match value_type {
+        AstValueType::Char | AstValueType::Bool | AstValueType::Int8 | AstValueType::UInt8 => 1,
+        AstValueType::Int16 | AstValueType::UInt16 => 2,
+        AstValueType::Int32 | AstValueType::UInt32 | AstValueType::Float => 4,
+        AstValueType::Int64
+        | AstValueType::UInt64
+        | AstValueType::Double
+        | AstValueType::Pointer(_) => 8,
+        AstValueType::Array(inner, count) => type_size(inner) * count,
+        AstValueType::Struct(_, fields) => fields
+            .iter()
+            .map(|x| x.offset.unwrap_or(0) as usize + type_size(&x.var_type))
+            .max()
+            .unwrap_or(0),
+        AstValueType::Union(_, fields) => fields
+            .iter()
+            .map(|x| type_size(&x.var_type))
+            .max()
+            .unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `int add(int a, struct node *p) { int sum; ... }` at 0x1010, with `struct node { int x;
+    /// struct node *next; }`. Written by `llvm-pdbutil yaml2pdb`, with the `.text` section header
+    /// and the frame pointer of `S_FRAMEPROC` (rbp) added afterwards.
+    fn add_pdb() -> Vec<u8> {
+        include_bytes!("../../tests/resources/add.pdb").to_vec()
+    }
+
+    #[test]
+    fn test_load_pdb() {
+        let pdb = load_pdb(add_pdb(), MachineArchitecture::X64).unwrap();
+        assert_eq!(pdb.symbols, [("add".to_string(), 0x1010)]);
+
+        let function = pdb.debug_info.function_at(0x1010).unwrap();
+        assert_eq!(function.name, "add");
+        assert_eq!(function.end, 0x1030);
+        assert_eq!(function.return_type, AstValueType::Int32);
+        let [a, p] = function.parameters.as_slice() else {
+            panic!("expected two parameters: {:?}", function.parameters);
+        };
+        assert_eq!(a.name, "a");
+        assert_eq!(a.var_type, AstValueType::Int32);
+        assert_eq!(a.location, Some(DebugLocation::FrameOffset(16)));
+        assert_eq!(p.name, "p");
+        assert_eq!(p.location, Some(DebugLocation::Register("rcx".to_string())));
+        let AstValueType::Pointer(pointee) = &p.var_type else {
+            panic!("not a pointer: {:?}", p.var_type);
+        };
+        let AstValueType::Struct(struct_name, fields) = pointee.as_ref() else {
+            panic!("not a struct: {pointee:?}");
+        };
+        assert_eq!(struct_name, "node");
+        let fields: Vec<_> = fields
+            .iter()
+            .map(|x| (x.name(), x.offset, x.var_type.clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("x".to_string(), Some(0), AstValueType::Int32),
+                (
+                    "next".to_string(),
+                    Some(8),
+                    // self references are cut at the struct
+                    AstValueType::Pointer(Box::new(AstValueType::Struct(
+                        "node".to_string(),
+                        Vec::new()
+                    )))
+                ),
+            ]
+        );
+        let variables: Vec<_> = function
+            .variables
+            .iter()
+            .map(|x| (x.name.as_str(), x.var_type.clone(), x.location.clone()))
+            .collect();
+        assert_eq!(
+            variables,
+            [(
+                "sum",
+                AstValueType::Int32,
+                Some(DebugLocation::FrameOffset(-4))
+            )]
+        );
+    }
+
+    #[test]
+    fn test_pdb_matches() {
+        let guid = [
+            0x04, 0x03, 0x02, 0x01, 0x06, 0x05, 0x08, 0x07, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+            0x0F, 0x10,
+        ];
+        assert!(pdb_matches(&add_pdb(), &guid, 1));
+        assert!(!pdb_matches(&add_pdb(), &guid, 2));
+        assert!(!pdb_matches(&add_pdb(), &[0; 16], 1));
+    }
+}
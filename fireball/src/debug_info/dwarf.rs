@@ -0,0 +1,1089 @@
+//! Module reading DWARF debug information with gimli
+
+use super::{DebugFunction, DebugInfo, DebugLocation, DebugVariable, SourceLine};
+use crate::{
+    abstract_syntax_tree::{AstValueType, AstVariable, AstVariableId},
+    prelude::*,
+};
+use gimli::{AttributeValue, EndianSlice, Operation, RunTimeEndian, SectionId, UnitOffset};
+use hashbrown::HashMap;
+use iceball::MachineArchitecture;
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+};
+
+type Reader<'a> = EndianSlice<'a, RunTimeEndian>;
+type Dwarf<'a> = gimli::Dwarf<Reader<'a>>;
+type Unit<'a> = gimli::Unit<Reader<'a>>;
+
+/// Debug sections by their ELF name (`.debug_info`, `.debug_info.dwo`, ...)
+type DebugSections<'a> = HashMap<String, Cow<'a, [u8]>>;
+
+/// Prefix of the names given to structs and unions declared without one
+const ANONYMOUS_PREFIX: &str = "anonymous_";
+
+/// Reads the DWARF debug information of an ELF file.
+///
+/// ### Arguments
+/// - `gl: &goblin::elf::Elf` - parsed ELF file
+/// - `binary: &[u8]` - raw file data
+/// - `path: Option<&str>` - path of the file, to find separate debug files next to it
+/// - `architecture: MachineArchitecture` - architecture registers are named with
+///
+/// ### Returns
+/// - `DebugInfo` - the debug information, empty if there is none
+///
+/// ### Note
+/// - Without `.debug_info`, the file named by `.gnu_debuglink` is read instead.
+/// - Split units are read from the `.dwo` files named by their skeleton units.
+pub(crate) fn load_elf_dwarf(
+    gl: &goblin::elf::Elf,
+    binary: &[u8],
+    path: Option<&str>,
+    architecture: MachineArchitecture,
+) -> DebugInfo {
+    let endian = if gl.little_endian {
+        RunTimeEndian::Little
+    } else {
+        RunTimeEndian::Big
+    };
+    let directory = path.and_then(|x| Path::new(x).parent());
+    let sections = elf_debug_sections(gl, binary);
+    if sections.contains_key(".debug_info") {
+        return load(&sections, endian, architecture, directory);
+    }
+
+    let (Some(path), Some(link)) = (path, sections.get(".gnu_debuglink")) else {
+        return DebugInfo::default();
+    };
+    let name = link.split(|x| *x == 0).next().unwrap_or_default();
+    let name = String::from_utf8_lossy(name).into_owned();
+    for candidate in debug_link_candidates(Path::new(path), &name) {
+        let Ok(data) = std::fs::read(&candidate) else {
+            continue;
+        };
+        let Ok(debug) = goblin::elf::Elf::parse(&data) else {
+            continue;
+        };
+        let sections = elf_debug_sections(&debug, &data);
+        if sections.contains_key(".debug_info") {
+            debug!("Reading debug information from {}", candidate.display());
+            return load(&sections, endian, architecture, directory);
+        }
+    }
+    DebugInfo::default()
+}
+
+/// Reads the DWARF debug information of a Mach-O file.
+///
+/// ### Arguments
+/// - `macho: &goblin::mach::MachO` - parsed Mach-O file
+/// - `path: Option<&str>` - path of the file, to find the `.dSYM` bundle next to it
+/// - `architecture: MachineArchitecture` - architecture registers are named with
+///
+/// ### Returns
+/// - `DebugInfo` - the debug information, empty if there is none
+pub(crate) fn load_macho_dwarf(
+    macho: &goblin::mach::MachO,
+    path: Option<&str>,
+    architecture: MachineArchitecture,
+) -> DebugInfo {
+    let endian = if macho.little_endian {
+        RunTimeEndian::Little
+    } else {
+        RunTimeEndian::Big
+    };
+    let directory = path.and_then(|x| Path::new(x).parent());
+    let sections = macho_debug_sections(macho);
+    if sections.contains_key(".debug_info") {
+        return load(&sections, endian, architecture, directory);
+    }
+
+    // dsymutil places the debug information in a bundle next to the binary
+    let Some(path) = path.map(Path::new) else {
+        return DebugInfo::default();
+    };
+    let Some(file_name) = path.file_name() else {
+        return DebugInfo::default();
+    };
+    let mut bundle = path.as_os_str().to_owned();
+    bundle.push(".dSYM");
+    let candidate = PathBuf::from(bundle)
+        .join("Contents/Resources/DWARF")
+        .join(file_name);
+    let Ok(data) = std::fs::read(&candidate) else {
+        return DebugInfo::default();
+    };
+    let debug = match goblin::mach::Mach::parse(&data) {
+        Ok(goblin::mach::Mach::Binary(debug)) => Some(debug),
+        // universal bundles hold the debug information of every architecture
+        Ok(goblin::mach::Mach::Fat(multi)) => multi.arches().ok().and_then(|arches| {
+            arches
+                .iter()
+                .filter(|x| x.cputype == macho.header.cputype)
+                .find_map(|x| {
+                    let slice = data.get(x.offset as usize..(x.offset + x.size) as usize)?;
+                    goblin::mach::MachO::parse(slice, 0).ok()
+                })
+        }),
+        Err(_) => None,
+    };
+    let Some(debug) = debug else {
+        return DebugInfo::default();
+    };
+    debug!("Reading debug information from {}", candidate.display());
+    load(
+        &macho_debug_sections(&debug),
+        endian,
+        architecture,
+        directory,
+    )
+}
+
+/// Collects the debug sections of an ELF file, decompressing `SHF_COMPRESSED` ones.
+fn elf_debug_sections<'a>(gl: &goblin::elf::Elf, binary: &'a [u8]) -> DebugSections<'a> {
+    let mut sections = HashMap::new();
+    for header in gl.section_headers.iter() {
+        let Some(name) = gl.shdr_strtab.get_at(header.sh_name) else {
+            continue;
+        };
+        if !name.starts_with(".debug_") && name != ".gnu_debuglink" {
+            continue;
+        }
+        if header.sh_type == goblin::elf::section_header::SHT_NOBITS {
+            continue;
+        }
+        let start = header.sh_offset as usize;
+        let Some(data) = binary.get(start..start + header.sh_size as usize) else {
+            continue;
+        };
+        let data = if header.sh_flags & goblin::elf::section_header::SHF_COMPRESSED as u64 != 0 {
+            let Some(data) = decompress_elf_section(data, gl.is_64, gl.little_endian) else {
+                warn!("Unsupported compression of section {}", name);
+                continue;
+            };
+            Cow::Owned(data)
+        } else {
+            Cow::Borrowed(data)
+        };
+        sections.insert(name.to_string(), data);
+    }
+    sections
+}
+
+/// Decompresses the data of an `SHF_COMPRESSED` section, only zlib is supported.
+fn decompress_elf_section(data: &[u8], is_64: bool, little_endian: bool) -> Option<Vec<u8>> {
+    use std::io::Read;
+
+    let compression: [u8; 4] = data.get(0..4)?.try_into().ok()?;
+    let compression = if little_endian {
+        u32::from_le_bytes(compression)
+    } else {
+        u32::from_be_bytes(compression)
+    };
+    // ELFCOMPRESS_ZLIB
+    if compression != 1 {
+        return None;
+    }
+    let header_size = if is_64 { 24 } else { 12 };
+    let mut decompressed = Vec::new();
+    flate2::read::ZlibDecoder::new(data.get(header_size..)?)
+        .read_to_end(&mut decompressed)
+        .ok()?;
+    Some(decompressed)
+}
+
+/// Collects the `__DWARF` sections of a Mach-O file, by their ELF name.
+fn macho_debug_sections<'a>(macho: &goblin::mach::MachO<'a>) -> DebugSections<'a> {
+    let mut sections = HashMap::new();
+    for segment in macho.segments.iter() {
+        let Ok(segment_sections) = segment.sections() else {
+            continue;
+        };
+        for (section, data) in segment_sections {
+            let Ok(name) = section.name() else {
+                continue;
+            };
+            let Some(name) = name.strip_prefix("__debug_") else {
+                continue;
+            };
+            // section names are cut at 16 bytes
+            let name = match name {
+                "str_offs" => "str_offsets",
+                name => name,
+            };
+            sections.insert(format!(".debug_{name}"), Cow::Borrowed(data));
+        }
+    }
+    sections
+}
+
+/// Returns the paths gdb looks for a `.gnu_debuglink` file at.
+fn debug_link_candidates(path: &Path, name: &str) -> Vec<PathBuf> {
+    let directory = path.parent().unwrap_or(Path::new(""));
+    let mut candidates = vec![directory.join(name), directory.join(".debug").join(name)];
+    if let Ok(directory) = directory.canonicalize() {
+        candidates.push(
+            Path::new("/usr/lib/debug")
+                .join(directory.strip_prefix("/").unwrap_or(&directory))
+                .join(name),
+        );
+    }
+    candidates
+}
+
+fn load_dwarf<'a>(
+    sections: &'a DebugSections<'_>,
+    endian: RunTimeEndian,
+    dwo: bool,
+) -> gimli::Result<Dwarf<'a>> {
+    gimli::Dwarf::load(|id: SectionId| -> gimli::Result<Reader<'a>> {
+        let name = if dwo { id.dwo_name() } else { Some(id.name()) };
+        let data = name
+            .and_then(|name| sections.get(name))
+            .map(|x| x.as_ref())
+            .unwrap_or_default();
+        Ok(EndianSlice::new(data, endian))
+    })
+}
+
+fn load(
+    sections: &DebugSections<'_>,
+    endian: RunTimeEndian,
+    architecture: MachineArchitecture,
+    directory: Option<&Path>,
+) -> DebugInfo {
+    let dwo_files = split_dwarf_files(sections, endian, directory);
+    let mut info = DebugInfo::default();
+    if let Err(err) = read_dwarf(sections, &dwo_files, endian, architecture, &mut info) {
+        warn!("Failed to read DWARF debug information: {}", err);
+    }
+    info
+}
+
+/// Reads the `.dwo` files the skeleton units of split DWARF refer to, by their dwo id.
+fn split_dwarf_files(
+    sections: &DebugSections<'_>,
+    endian: RunTimeEndian,
+    directory: Option<&Path>,
+) -> HashMap<u64, Vec<u8>> {
+    let mut files = HashMap::new();
+    let Ok(dwarf) = load_dwarf(sections, endian, false) else {
+        return files;
+    };
+    let mut headers = dwarf.units();
+    while let Ok(Some(header)) = headers.next() {
+        let Ok(unit) = dwarf.unit(header) else {
+            continue;
+        };
+        let Some(dwo_id) = unit.dwo_id else {
+            continue;
+        };
+        let Ok(mut tree) = unit.entries_tree(None) else {
+            continue;
+        };
+        let Ok(root) = tree.root() else {
+            continue;
+        };
+        let name = [gimli::DW_AT_dwo_name, gimli::DW_AT_GNU_dwo_name]
+            .into_iter()
+            .find_map(|attr| root.entry().attr_value(attr))
+            .and_then(|value| dwarf.attr_string(&unit, value).ok())
+            .map(|name| name.to_string_lossy().into_owned());
+        let Some(name) = name else {
+            continue;
+        };
+        let comp_dir = unit
+            .comp_dir
+            .map(|x| PathBuf::from(x.to_string_lossy().as_ref()));
+        let candidates = [
+            comp_dir.map(|x| x.join(&name)),
+            directory.map(|x| x.join(&name)),
+        ];
+        match candidates
+            .into_iter()
+            .flatten()
+            .find_map(|x| std::fs::read(x).ok())
+        {
+            Some(data) => {
+                files.insert(dwo_id.0, data);
+            }
+            None => warn!("Split debug information {} not found", name),
+        }
+    }
+    files
+}
+
+fn read_dwarf(
+    sections: &DebugSections<'_>,
+    dwo_files: &HashMap<u64, Vec<u8>>,
+    endian: RunTimeEndian,
+    architecture: MachineArchitecture,
+    info: &mut DebugInfo,
+) -> gimli::Result<()> {
+    let dwo_sections: HashMap<u64, DebugSections<'_>> = dwo_files
+        .iter()
+        .filter_map(|(id, data)| {
+            let elf = goblin::elf::Elf::parse(data).ok()?;
+            Some((*id, elf_debug_sections(&elf, data)))
+        })
+        .collect();
+    let dwarf = load_dwarf(sections, endian, false)?;
+    let mut headers = dwarf.units();
+    while let Some(header) = headers.next()? {
+        let unit = dwarf.unit(header)?;
+        // skeleton units keep the line table
+        read_lines(&dwarf, &unit, info)?;
+        match unit.dwo_id.and_then(|id| dwo_sections.get(&id.0)) {
+            Some(dwo_sections) => {
+                let mut dwo = load_dwarf(dwo_sections, endian, true)?;
+                dwo.make_dwo(&dwarf);
+                let mut dwo_headers = dwo.units();
+                while let Some(dwo_header) = dwo_headers.next()? {
+                    let mut dwo_unit = dwo.unit(dwo_header)?;
+                    dwo_unit.copy_relocated_attributes(&unit);
+                    read_functions(&dwo, &dwo_unit, architecture, info)?;
+                }
+            }
+            None => read_functions(&dwarf, &unit, architecture, info)?,
+        }
+    }
+    Ok(())
+}
+
+fn read_lines(dwarf: &Dwarf<'_>, unit: &Unit<'_>, info: &mut DebugInfo) -> gimli::Result<()> {
+    let Some(program) = unit.line_program.clone() else {
+        return Ok(());
+    };
+    let mut files: HashMap<u64, String> = HashMap::new();
+    let mut rows = program.rows();
+    while let Some((header, row)) = rows.next_row()? {
+        if row.end_sequence() {
+            info.insert_line(row.address(), None);
+            continue;
+        }
+        let (Some(line), Some(file)) = (row.line(), row.file(header)) else {
+            continue;
+        };
+        let file = match files.get(&row.file_index()) {
+            Some(file) => file.clone(),
+            None => {
+                let path = dwarf.attr_string(unit, file.path_name())?;
+                let path = path.to_string_lossy();
+                let name = Path::new(path.as_ref())
+                    .file_name()
+                    .map(|x| x.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.into_owned());
+                files.insert(row.file_index(), name.clone());
+                name
+            }
+        };
+        info.insert_line(
+            row.address(),
+            Some(SourceLine {
+                file,
+                line: line.get(),
+            }),
+        );
+    }
+    Ok(())
+}
+
+fn read_functions(
+    dwarf: &Dwarf<'_>,
+    unit: &Unit<'_>,
+    architecture: MachineArchitecture,
+    info: &mut DebugInfo,
+) -> gimli::Result<()> {
+    let mut types = TypeReader {
+        dwarf,
+        unit,
+        cache: HashMap::new(),
+        building: 0,
+    };
+    let mut tree = unit.entries_tree(None)?;
+    let mut scopes = HashMap::new();
+    read_scope(
+        &mut types,
+        tree.root()?,
+        "",
+        &mut scopes,
+        architecture,
+        info,
+    )
+}
+
+/// Reads the functions declared in a unit, namespace or class.
+///
+/// ### Arguments
+/// - `scope: &str` - qualifier of the names declared in the scope (`ns::Class::`)
+/// - `scopes: &mut HashMap<UnitOffset, String>` - qualifiers of the functions declared so far,
+///   for definitions outside of their class
+fn read_scope<'a>(
+    types: &mut TypeReader<'_, 'a>,
+    node: gimli::EntriesTreeNode<Reader<'a>>,
+    scope: &str,
+    scopes: &mut HashMap<UnitOffset, String>,
+    architecture: MachineArchitecture,
+    info: &mut DebugInfo,
+) -> gimli::Result<()> {
+    let mut children = node.children();
+    while let Some(child) = children.next()? {
+        match child.entry().tag() {
+            gimli::DW_TAG_subprogram => {
+                let offset = child.entry().offset();
+                let declaration = match child.entry().attr_value(gimli::DW_AT_specification) {
+                    Some(AttributeValue::UnitRef(declaration)) => scopes.get(&declaration),
+                    _ => None,
+                };
+                let scope = declaration.cloned().unwrap_or_else(|| scope.to_string());
+                match read_function(types, child, architecture)? {
+                    Some(mut function) => {
+                        function.name.insert_str(0, &scope);
+                        info.insert_function(function);
+                    }
+                    None if !scope.is_empty() => {
+                        scopes.insert(offset, scope);
+                    }
+                    None => {}
+                }
+            }
+            gimli::DW_TAG_namespace | gimli::DW_TAG_class_type | gimli::DW_TAG_structure_type => {
+                let name = types
+                    .name(child.entry())?
+                    .unwrap_or_else(|| "(anonymous namespace)".to_string());
+                let scope = format!("{scope}{name}::");
+                read_scope(types, child, &scope, scopes, architecture, info)?
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Reads a function, `None` if it is only declared.
+fn read_function<'a>(
+    types: &mut TypeReader<'_, 'a>,
+    node: gimli::EntriesTreeNode<Reader<'a>>,
+    architecture: MachineArchitecture,
+) -> gimli::Result<Option<DebugFunction>> {
+    let entry = node.entry();
+    let Some((start, end)) = types.pc_range(entry)? else {
+        return Ok(None);
+    };
+    let name = types
+        .name(entry)?
+        .unwrap_or_else(|| format!("sub_{start:x}"));
+    let return_type = types.attr_type(entry)?;
+    let frame_base = match entry.attr_value(gimli::DW_AT_frame_base) {
+        Some(value) => frame_base_offset(types.unit, value, architecture),
+        None => None,
+    };
+    let mut function = DebugFunction {
+        name,
+        start,
+        end,
+        return_type,
+        parameters: Vec::new(),
+        variables: Vec::new(),
+    };
+    read_variables(types, node, architecture, frame_base, &mut function)?;
+    Ok(Some(function))
+}
+
+/// Reads the parameters and variables of a function or one of its scopes.
+fn read_variables<'a>(
+    types: &mut TypeReader<'_, 'a>,
+    node: gimli::EntriesTreeNode<Reader<'a>>,
+    architecture: MachineArchitecture,
+    frame_base: Option<i64>,
+    function: &mut DebugFunction,
+) -> gimli::Result<()> {
+    let mut children = node.children();
+    while let Some(child) = children.next()? {
+        let entry = child.entry();
+        let tag = entry.tag();
+        match tag {
+            gimli::DW_TAG_formal_parameter | gimli::DW_TAG_variable => {
+                let Some(name) = types.name(entry)? else {
+                    continue;
+                };
+                let var_type = types.attr_type(entry)?;
+                let location = match entry.attr_value(gimli::DW_AT_location) {
+                    Some(value) => variable_location(types.unit, value, frame_base, architecture),
+                    None => None,
+                };
+                let variable = DebugVariable {
+                    name,
+                    var_type,
+                    location,
+                };
+                if tag == gimli::DW_TAG_formal_parameter {
+                    function.parameters.push(variable);
+                } else {
+                    function.variables.push(variable);
+                }
+            }
+            gimli::DW_TAG_lexical_block => {
+                read_variables(types, child, architecture, frame_base, function)?
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Returns the offset of the frame base (`DW_AT_frame_base`) from the frame base pointer.
+fn frame_base_offset(
+    unit: &Unit<'_>,
+    value: AttributeValue<Reader<'_>>,
+    architecture: MachineArchitecture,
+) -> Option<i64> {
+    let AttributeValue::Exprloc(expression) = value else {
+        return None;
+    };
+    let mut operations = expression.operations(unit.encoding());
+    match operations.next().ok()?? {
+        Operation::Register { register } if is_base_pointer(architecture, register) => Some(0),
+        Operation::RegisterOffset {
+            register, offset, ..
+        } if is_base_pointer(architecture, register) => Some(offset),
+        // the canonical frame address is above the return address and the saved frame pointer
+        Operation::CallFrameCFA => match architecture {
+            MachineArchitecture::X64 => Some(16),
+            MachineArchitecture::X86 => Some(8),
+            MachineArchitecture::Arm64 | MachineArchitecture::Arm => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns where a variable is stored.
+///
+/// ### Note
+/// - Location lists, used by optimized code, are not followed.
+fn variable_location(
+    unit: &Unit<'_>,
+    value: AttributeValue<Reader<'_>>,
+    frame_base: Option<i64>,
+    architecture: MachineArchitecture,
+) -> Option<DebugLocation> {
+    let AttributeValue::Exprloc(expression) = value else {
+        return None;
+    };
+    let mut operations = expression.operations(unit.encoding());
+    let operation = operations.next().ok()??;
+    if !matches!(operations.next(), Ok(None)) {
+        return None;
+    }
+    match operation {
+        Operation::Register { register } => Some(DebugLocation::Register(register_name(
+            architecture,
+            register,
+        )?)),
+        Operation::FrameOffset { offset } => Some(DebugLocation::FrameOffset(frame_base? + offset)),
+        Operation::RegisterOffset {
+            register, offset, ..
+        } if is_base_pointer(architecture, register) => Some(DebugLocation::FrameOffset(offset)),
+        _ => None,
+    }
+}
+
+/// Returns the name of a DWARF register, as the ir names it.
+fn register_name(architecture: MachineArchitecture, register: gimli::Register) -> Option<String> {
+    let name = match architecture {
+        MachineArchitecture::X64 => gimli::X86_64::register_name(register),
+        MachineArchitecture::X86 => gimli::X86::register_name(register),
+        MachineArchitecture::Arm64 => gimli::AArch64::register_name(register),
+        MachineArchitecture::Arm => gimli::Arm::register_name(register),
+    }?;
+    Some(name.to_ascii_lowercase())
+}
+
+fn is_base_pointer(architecture: MachineArchitecture, register: gimli::Register) -> bool {
+    let base_pointer = match architecture {
+        MachineArchitecture::X64 => "rbp",
+        MachineArchitecture::X86 => "ebp",
+        MachineArchitecture::Arm64 => "x29",
+        MachineArchitecture::Arm => "r11",
+    };
+    register_name(architecture, register).as_deref() == Some(base_pointer)
+}
+
+/// Converts the types of a unit into `AstValueType`s
+struct TypeReader<'u, 'a> {
+    dwarf: &'u Dwarf<'a>,
+    unit: &'u Unit<'a>,
+    /// Converted types by their offset, structs are inserted without fields while their fields
+    /// are converted
+    cache: HashMap<UnitOffset, AstValueType>,
+    /// Number of structs whose fields are being converted
+    building: usize,
+}
+
+impl<'u, 'a> TypeReader<'u, 'a> {
+    /// Returns the name of an entry, or of the declaration it defines.
+    fn name(
+        &self,
+        entry: &gimli::DebuggingInformationEntry<Reader<'a>>,
+    ) -> gimli::Result<Option<String>> {
+        if let Some(value) = entry.attr_value(gimli::DW_AT_name) {
+            let name = self.dwarf.attr_string(self.unit, value)?;
+            return Ok(Some(name.to_string_lossy().into_owned()));
+        }
+        for attr in [gimli::DW_AT_specification, gimli::DW_AT_abstract_origin] {
+            if let Some(AttributeValue::UnitRef(offset)) = entry.attr_value(attr) {
+                let origin = self.unit.entry(offset)?;
+                return self.name(&origin);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the first and the end address of the code of an entry.
+    fn pc_range(
+        &self,
+        entry: &gimli::DebuggingInformationEntry<Reader<'a>>,
+    ) -> gimli::Result<Option<(u64, u64)>> {
+        let Some(low) = entry.attr_value(gimli::DW_AT_low_pc) else {
+            // non-contiguous functions start with their first range
+            let mut ranges = self.dwarf.die_ranges(self.unit, entry)?;
+            return Ok(ranges.next()?.map(|range| (range.begin, range.end)));
+        };
+        let Some(start) = self.dwarf.attr_address(self.unit, low)? else {
+            return Ok(None);
+        };
+        let end = match entry.attr_value(gimli::DW_AT_high_pc) {
+            Some(value @ (AttributeValue::Addr(_) | AttributeValue::DebugAddrIndex(_))) => {
+                self.dwarf.attr_address(self.unit, value)?.unwrap_or(start)
+            }
+            Some(value) => start + value.udata_value().unwrap_or(0),
+            None => start,
+        };
+        Ok(Some((start, end)))
+    }
+
+    /// Returns the type (`DW_AT_type`) of an entry, `void` if it has none.
+    fn attr_type(
+        &mut self,
+        entry: &gimli::DebuggingInformationEntry<Reader<'a>>,
+    ) -> gimli::Result<AstValueType> {
+        match entry.attr_value(gimli::DW_AT_type) {
+            Some(AttributeValue::UnitRef(offset)) => self.type_at(offset),
+            // types of other units are not followed
+            Some(_) => Ok(AstValueType::Unknown),
+            None => {
+                for attr in [gimli::DW_AT_specification, gimli::DW_AT_abstract_origin] {
+                    if let Some(AttributeValue::UnitRef(offset)) = entry.attr_value(attr) {
+                        let unit = self.unit;
+                        let origin = unit.entry(offset)?;
+                        return self.attr_type(&origin);
+                    }
+                }
+                Ok(AstValueType::Void)
+            }
+        }
+    }
+
+    fn type_at(&mut self, offset: UnitOffset) -> gimli::Result<AstValueType> {
+        if let Some(known) = self.cache.get(&offset) {
+            return Ok(known.clone());
+        }
+        let unit = self.unit;
+        let entry = unit.entry(offset)?;
+        let value_type = match entry.tag() {
+            gimli::DW_TAG_base_type => base_type(&entry)?,
+            gimli::DW_TAG_pointer_type
+            | gimli::DW_TAG_reference_type
+            | gimli::DW_TAG_rvalue_reference_type
+            | gimli::DW_TAG_ptr_to_member_type => {
+                AstValueType::Pointer(Box::new(self.attr_type(&entry)?))
+            }
+            gimli::DW_TAG_const_type
+            | gimli::DW_TAG_volatile_type
+            | gimli::DW_TAG_restrict_type
+            | gimli::DW_TAG_atomic_type => self.attr_type(&entry)?,
+            gimli::DW_TAG_typedef => {
+                let name = self.name(&entry)?;
+                match (self.attr_type(&entry)?, name) {
+                    // `typedef struct { ... } name;` names the struct
+                    (AstValueType::Struct(anonymous, fields), Some(name))
+                        if anonymous.starts_with(ANONYMOUS_PREFIX) =>
+                    {
+                        AstValueType::Struct(name, fields)
+                    }
+                    (AstValueType::Union(anonymous, fields), Some(name))
+                        if anonymous.starts_with(ANONYMOUS_PREFIX) =>
+                    {
+                        AstValueType::Union(name, fields)
+                    }
+                    (target, _) => target,
+                }
+            }
+            gimli::DW_TAG_structure_type | gimli::DW_TAG_class_type | gimli::DW_TAG_union_type => {
+                self.record_type(offset, &entry)?
+            }
+            gimli::DW_TAG_enumeration_type => match entry.attr_value(gimli::DW_AT_type) {
+                Some(_) => self.attr_type(&entry)?,
+                None => int_type(byte_size(&entry)?, true),
+            },
+            gimli::DW_TAG_array_type => self.array_type(offset, &entry)?,
+            // function types are only used through pointers
+            _ => AstValueType::Unknown,
+        };
+        // types referring to a struct being converted hold it without fields
+        let is_record = matches!(
+            value_type,
+            AstValueType::Struct(..) | AstValueType::Union(..)
+        );
+        if self.building == 0 || is_record {
+            self.cache.insert(offset, value_type.clone());
+        }
+        Ok(value_type)
+    }
+
+    fn record_type(
+        &mut self,
+        offset: UnitOffset,
+        entry: &gimli::DebuggingInformationEntry<Reader<'a>>,
+    ) -> gimli::Result<AstValueType> {
+        let name = self
+            .name(entry)?
+            .unwrap_or_else(|| format!("{ANONYMOUS_PREFIX}{:x}", offset.0));
+        let is_union = entry.tag() == gimli::DW_TAG_union_type;
+        let record = |fields| match is_union {
+            true => AstValueType::Union(name.clone(), fields),
+            false => AstValueType::Struct(name.clone(), fields),
+        };
+        self.cache.insert(offset, record(Vec::new()));
+        self.building += 1;
+
+        let unit = self.unit;
+        let mut fields = Vec::new();
+        let mut tree = unit.entries_tree(Some(offset))?;
+        let mut children = tree.root()?.children();
+        while let Some(child) = children.next()? {
+            let member = child.entry();
+            if member.tag() != gimli::DW_TAG_member {
+                continue;
+            }
+            // static members have no offset
+            let field_offset = match member.attr_value(gimli::DW_AT_data_member_location) {
+                Some(value) => value.udata_value(),
+                None if is_union => Some(0),
+                None => None,
+            };
+            let Some(field_offset) = field_offset else {
+                continue;
+            };
+            let field_name = self
+                .name(member)?
+                .unwrap_or_else(|| format!("field_{field_offset:x}"));
+            fields.push(AstVariable {
+                name: Some(field_name),
+                id: AstVariableId {
+                    index: fields.len() as u32,
+                    parent: None,
+                },
+                var_type: self.attr_type(member)?,
+                const_value: None,
+                data_access_ir: None,
+                offset: Some(field_offset),
+            });
+        }
+        self.building -= 1;
+        Ok(record(fields))
+    }
+
+    fn array_type(
+        &mut self,
+        offset: UnitOffset,
+        entry: &gimli::DebuggingInformationEntry<Reader<'a>>,
+    ) -> gimli::Result<AstValueType> {
+        let element = self.attr_type(entry)?;
+        let unit = self.unit;
+        let mut counts = Vec::new();
+        let mut tree = unit.entries_tree(Some(offset))?;
+        let mut children = tree.root()?.children();
+        while let Some(child) = children.next()? {
+            let range = child.entry();
+            if range.tag() != gimli::DW_TAG_subrange_type {
+                continue;
+            }
+            let count = match range.attr_value(gimli::DW_AT_count) {
+                Some(value) => value.udata_value(),
+                None => range
+                    .attr_value(gimli::DW_AT_upper_bound)
+                    .and_then(|x| x.udata_value())
+                    .map(|x| x + 1),
+            };
+            counts.push(count.unwrap_or(0) as usize);
+        }
+        if counts.is_empty() {
+            counts.push(0);
+        }
+        // `int a[2][3]` is an array of 2 arrays of 3
+        Ok(counts.into_iter().rev().fold(element, |inner, count| {
+            AstValueType::Array(Box::new(inner), count)
+        }))
+    }
+}
+
+fn byte_size(entry: &gimli::DebuggingInformationEntry<Reader<'_>>) -> gimli::Result<u64> {
+    Ok(entry
+        .attr_value(gimli::DW_AT_byte_size)
+        .and_then(|x| x.udata_value())
+        .unwrap_or(0))
+}
+
+fn base_type(entry: &gimli::DebuggingInformationEntry<Reader<'_>>) -> gimli::Result<AstValueType> {
+    let size = byte_size(entry)?;
+    let Some(AttributeValue::Encoding(encoding)) = entry.attr_value(gimli::DW_AT_encoding) else {
+        return Ok(AstValueType::Unknown);
+    };
+    Ok(match encoding {
+        gimli::DW_ATE_boolean => AstValueType::Bool,
+        gimli::DW_ATE_float if size == 4 => AstValueType::Float,
+        gimli::DW_ATE_float => AstValueType::Double,
+        gimli::DW_ATE_signed_char | gimli::DW_ATE_unsigned_char if size == 1 => AstValueType::Char,
+        gimli::DW_ATE_signed | gimli::DW_ATE_signed_char => int_type(size, true),
+        gimli::DW_ATE_unsigned | gimli::DW_ATE_unsigned_char | gimli::DW_ATE_UTF => {
+            int_type(size, false)
+        }
+        _ => AstValueType::Unknown,
+    })
+}
+
+fn int_type(size: u64, signed: bool) -> AstValueType {
+    match (size, signed) {
+        (1, true) => AstValueType::Int8,
+        (2, true) => AstValueType::Int16,
+        (4, true) => AstValueType::Int32,
+        (8, true) => AstValueType::Int64,
+        (_, true) => AstValueType::Int,
+        (1, false) => AstValueType::UInt8,
+        (2, false) => AstValueType::UInt16,
+        (4, false) => AstValueType::UInt32,
+        (8, false) => AstValueType::UInt64,
+        (_, false) => AstValueType::UInt,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gimli::write::{
+        Address, AttributeValue, DwarfUnit, EndianVec, Expression, LineProgram, LineString,
+        Sections, UnitEntryId,
+    };
+
+    /// Writes the DWARF of `namespace geo { int area(struct point *p) }` compiled from main.c.
+    fn debug_sections() -> DebugSections<'static> {
+        let encoding = gimli::Encoding {
+            format: gimli::Format::Dwarf32,
+            version: 4,
+            address_size: 8,
+        };
+        let mut dwarf = DwarfUnit::new(encoding);
+
+        // lines 3 and 4 of main.c at 0x1000 and 0x1008, up to 0x1020
+        let mut program = LineProgram::new(
+            encoding,
+            gimli::LineEncoding::default(),
+            LineString::String(b"/src".to_vec()),
+            None,
+            LineString::String(b"main.c".to_vec()),
+            None,
+        );
+        let directory = program.default_directory();
+        let file = program.add_file(LineString::String(b"main.c".to_vec()), directory, None);
+        program.begin_sequence(Some(Address::Constant(0x1000)));
+        for (offset, line) in [(0, 3), (8, 4)] {
+            program.row().address_offset = offset;
+            program.row().file = file;
+            program.row().line = line;
+            program.generate_row();
+        }
+        program.end_sequence(0x20);
+        dwarf.unit.line_program = program;
+
+        let unit = &mut dwarf.unit;
+        let root = unit.root();
+        let mut add = |parent: UnitEntryId, tag, attributes: Vec<(gimli::DwAt, AttributeValue)>| {
+            let id = unit.add(parent, tag);
+            for (name, value) in attributes {
+                unit.get_mut(id).set(name, value);
+            }
+            id
+        };
+        let name = |name: &str| (gimli::DW_AT_name, AttributeValue::String(name.into()));
+        let expression = |build: &dyn Fn(&mut Expression)| {
+            let mut expression = Expression::new();
+            build(&mut expression);
+            AttributeValue::Exprloc(expression)
+        };
+
+        let int = add(
+            root,
+            gimli::DW_TAG_base_type,
+            vec![
+                name("int"),
+                (gimli::DW_AT_byte_size, AttributeValue::Udata(4)),
+                (
+                    gimli::DW_AT_encoding,
+                    AttributeValue::Encoding(gimli::DW_ATE_signed),
+                ),
+            ],
+        );
+        let point = add(
+            root,
+            gimli::DW_TAG_structure_type,
+            vec![
+                name("point"),
+                (gimli::DW_AT_byte_size, AttributeValue::Udata(8)),
+            ],
+        );
+        for (field, offset) in [("x", 0), ("y", 4)] {
+            add(
+                point,
+                gimli::DW_TAG_member,
+                vec![
+                    name(field),
+                    (gimli::DW_AT_type, AttributeValue::UnitRef(int)),
+                    (
+                        gimli::DW_AT_data_member_location,
+                        AttributeValue::Udata(offset),
+                    ),
+                ],
+            );
+        }
+        let point_pointer = add(
+            root,
+            gimli::DW_TAG_pointer_type,
+            vec![
+                (gimli::DW_AT_byte_size, AttributeValue::Udata(8)),
+                (gimli::DW_AT_type, AttributeValue::UnitRef(point)),
+            ],
+        );
+        let namespace = add(root, gimli::DW_TAG_namespace, vec![name("geo")]);
+        let area = add(
+            namespace,
+            gimli::DW_TAG_subprogram,
+            vec![
+                name("area"),
+                (
+                    gimli::DW_AT_low_pc,
+                    AttributeValue::Address(Address::Constant(0x1000)),
+                ),
+                (gimli::DW_AT_high_pc, AttributeValue::Udata(0x20)),
+                (gimli::DW_AT_type, AttributeValue::UnitRef(int)),
+                (
+                    gimli::DW_AT_frame_base,
+                    expression(&|x| x.op(gimli::DW_OP_call_frame_cfa)),
+                ),
+            ],
+        );
+        add(
+            area,
+            gimli::DW_TAG_formal_parameter,
+            vec![
+                name("p"),
+                (gimli::DW_AT_type, AttributeValue::UnitRef(point_pointer)),
+                (gimli::DW_AT_location, expression(&|x| x.op_fbreg(-24))),
+            ],
+        );
+        add(
+            area,
+            gimli::DW_TAG_variable,
+            vec![
+                name("result"),
+                (gimli::DW_AT_type, AttributeValue::UnitRef(int)),
+                (
+                    gimli::DW_AT_location,
+                    expression(&|x| x.op_breg(gimli::X86_64::RBP, -4)),
+                ),
+            ],
+        );
+        let scope = add(area, gimli::DW_TAG_lexical_block, Vec::new());
+        add(
+            scope,
+            gimli::DW_TAG_variable,
+            vec![
+                name("i"),
+                (gimli::DW_AT_type, AttributeValue::UnitRef(int)),
+                (
+                    gimli::DW_AT_location,
+                    expression(&|x| x.op_reg(gimli::X86_64::RBX)),
+                ),
+            ],
+        );
+
+        let mut sections = Sections::new(EndianVec::new(gimli::LittleEndian));
+        dwarf.write(&mut sections).unwrap();
+        let mut result = DebugSections::new();
+        sections
+            .for_each(|id, data| {
+                result.insert(id.name().to_string(), Cow::Owned(data.slice().to_vec()));
+                Ok::<_, ()>(())
+            })
+            .unwrap();
+        result
+    }
+
+    #[test]
+    fn test_read_dwarf() {
+        let info = load(
+            &debug_sections(),
+            RunTimeEndian::Little,
+            MachineArchitecture::X64,
+            None,
+        );
+
+        let function = info.function_at(0x1000).unwrap();
+        assert_eq!(function.name, "geo::area");
+        assert_eq!(function.end, 0x1020);
+        assert_eq!(function.return_type, AstValueType::Int32);
+        let [parameter] = function.parameters.as_slice() else {
+            panic!("expected one parameter: {:?}", function.parameters);
+        };
+        assert_eq!(parameter.name, "p");
+        // the frame base is the canonical frame address, 16 bytes above the base pointer
+        assert_eq!(parameter.location, Some(DebugLocation::FrameOffset(-8)));
+        let AstValueType::Pointer(pointee) = &parameter.var_type else {
+            panic!("not a pointer: {:?}", parameter.var_type);
+        };
+        let AstValueType::Struct(struct_name, fields) = pointee.as_ref() else {
+            panic!("not a struct: {pointee:?}");
+        };
+        assert_eq!(struct_name, "point");
+        let fields: Vec<_> = fields
+            .iter()
+            .map(|x| (x.name(), x.offset, x.var_type.clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("x".to_string(), Some(0), AstValueType::Int32),
+                ("y".to_string(), Some(4), AstValueType::Int32),
+            ]
+        );
+        let variables: Vec<_> = function
+            .variables
+            .iter()
+            .map(|x| (x.name.as_str(), x.location.clone()))
+            .collect();
+        assert_eq!(
+            variables,
+            [
+                ("result", Some(DebugLocation::FrameOffset(-4))),
+                ("i", Some(DebugLocation::Register("rbx".to_string()))),
+            ]
+        );
+
+        assert_eq!(info.line_at(0x1004).unwrap().to_string(), "main.c:3");
+        assert_eq!(info.line_at(0x1010).unwrap().to_string(), "main.c:4");
+        assert_eq!(info.line_at(0x1020), None);
+        assert_eq!(info.line_at(0xfff), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Module containing the debug information (names, types and source lines) of the loaded binary
+//!
+//! Debug information is read once when the binary is loaded, and applied to the functions and
+//! variables of the `Ast` while it is optimized.
+
+mod codeview;
+mod dwarf;
+
+pub(crate) use codeview::{PdbDebugInfo, load_pdb, pdb_matches};
+pub(crate) use dwarf::{load_elf_dwarf, load_macho_dwarf};
+
+use crate::abstract_syntax_tree::AstValueType;
+use std::collections::BTreeMap;
+
+/// Position in a source file
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceLine {
+    /// Name of the source file, without its directory
+    pub file: String,
+    /// 1-based line number
+    pub line: u64,
+}
+
+impl std::fmt::Display for SourceLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// Where a variable is stored while its function runs
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DebugLocation {
+    /// Register, by name (`rdi`, `x0`, ...)
+    Register(String),
+    /// Offset from the frame base pointer (`rbp`, `ebp`, `x29`)
+    FrameOffset(i64),
+}
+
+/// Parameter or local variable of a function
+#[derive(Debug, Clone)]
+pub struct DebugVariable {
+    pub name: String,
+    pub var_type: AstValueType,
+    /// `None` if the variable moves between locations, or is optimized out
+    pub location: Option<DebugLocation>,
+}
+
+/// Function described by the debug information
+#[derive(Debug, Clone)]
+pub struct DebugFunction {
+    pub name: String,
+    /// Virtual address of the first instruction
+    pub start: u64,
+    /// Virtual address right after the last instruction
+    pub end: u64,
+    pub return_type: AstValueType,
+    /// Parameters, in declaration order
+    pub parameters: Vec<DebugVariable>,
+    /// Local variables of the function and its nested scopes
+    pub variables: Vec<DebugVariable>,
+}
+
+/// Debug information of a binary, empty if it has none
+#[derive(Debug, Clone, Default)]
+pub struct DebugInfo {
+    /// Functions by start address
+    functions: BTreeMap<u64, DebugFunction>,
+    /// Source line of the instructions starting at each address, `None` where a sequence ends
+    lines: BTreeMap<u64, Option<SourceLine>>,
+}
+
+impl DebugInfo {
+    /// Returns whether no function or source line is known.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.lines.is_empty()
+    }
+
+    pub(crate) fn insert_function(&mut self, function: DebugFunction) {
+        self.functions.insert(function.start, function);
+    }
+
+    /// Records the source line of the instructions from an address up to the next recorded one.
+    ///
+    /// ### Arguments
+    /// - `address: u64` - virtual address of the first instruction
+    /// - `line: Option<SourceLine>` - source line, or `None` to end the previous line
+    pub(crate) fn insert_line(&mut self, address: u64, line: Option<SourceLine>) {
+        match line {
+            Some(line) => {
+                self.lines.insert(address, Some(line));
+            }
+            // a sequence ending where the next one starts keeps the next one's line
+            None => {
+                self.lines.entry(address).or_insert(None);
+            }
+        }
+    }
+
+    /// Returns all functions, ordered by start address.
+    pub fn functions(&self) -> impl Iterator<Item = &DebugFunction> {
+        self.functions.values()
+    }
+
+    /// Returns the function starting at an address.
+    ///
+    /// ### Arguments
+    /// - `address: u64` - virtual address of the first instruction
+    ///
+    /// ### Returns
+    /// - `Option<&DebugFunction>` - the function, if it is described
+    pub fn function_at(&self, address: u64) -> Option<&DebugFunction> {
+        self.functions.get(&address)
+    }
+
+    /// Returns the function whose instructions contain an address.
+    pub fn function_containing(&self, address: u64) -> Option<&DebugFunction> {
+        self.functions
+            .range(..=address)
+            .next_back()
+            .map(|(_, function)| function)
+            .filter(|function| address < function.end)
+    }
+
+    /// Returns the source line an instruction is compiled from.
+    ///
+    /// ### Arguments
+    /// - `address: u64` - virtual address of the instruction
+    ///
+    /// ### Returns
+    /// - `Option<&SourceLine>` - the line, or `None` if the address is not covered by the line
+    ///   table
+    pub fn line_at(&self, address: u64) -> Option<&SourceLine> {
+        self.lines
+            .range(..=address)
+            .next_back()
+            .and_then(|(_, line)| line.as_ref())
+    }
+}
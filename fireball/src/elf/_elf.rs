@@ -3,7 +3,12 @@
 use super::Elf;
 use crate::{
     BinaryKind, arch,
-    core::{Address, Blocks, PreDefinedOffset, PreDefinedOffsets, Relations, Sections},
+    core::{
+        Address, Blocks, NoReturnFunctions, PreDefinedOffset, PreDefinedOffsets, Relations,
+        Sections,
+    },
+    debug_info::{self, DebugInfo},
+    exception_handling::{ExceptionHandlers, load_eh_frame_exception_handlers},
     prelude::*,
 };
 use iceball::MachineArchitecture;
@@ -20,6 +25,10 @@ impl Elf {
     }
 
     /// Creates an Elf struct from binary data.
+    ///
+    /// ### Note
+    /// - Relocatable objects are laid out at synthetic addresses and their relocations are
+    ///   applied to the kept binary data, so it differs from the file on disk.
     pub(crate) fn new(path: Option<String>, binary: Vec<u8>) -> Result<Self, FireballError> {
         let gl = goblin::elf::Elf::parse(&binary)
             .map_err(|e| FireballError::ElfParsingFailed(e.to_string()))?;
@@ -42,7 +51,12 @@ impl Elf {
         let capstone = arch::build_capstone(architecture)?;
 
         // Generate predefined binary offset information from symbol tables
-        let defined = {
+        let mut patches = Vec::new();
+        let defined = if kind == BinaryKind::ObjectFile {
+            let defined = PreDefinedOffsets::new();
+            patches = super::object::load_object(&gl, &sections, architecture, &defined);
+            defined
+        } else {
             let defined = PreDefinedOffsets::new();
 
             // Static symbol table
@@ -85,13 +99,57 @@ impl Elf {
                 });
             }
 
+            // Imported symbols reached through PLT stubs and GOT slots
+            super::relocation::insert_relocation_symbols(
+                &gl,
+                &binary,
+                &sections,
+                architecture,
+                &defined,
+            );
+
             defined
         };
 
+        // Addresses in the debug information of an object file are not relocated
+        let debug_info = if kind == BinaryKind::ObjectFile {
+            DebugInfo::default()
+        } else {
+            debug_info::load_elf_dwarf(&gl, &binary, path.as_deref(), architecture)
+        };
+        // Landing pads of an object are only known after its relocations are applied
+        let exception_handlers = if kind == BinaryKind::ObjectFile {
+            ExceptionHandlers::default()
+        } else {
+            let endian = if gl.little_endian {
+                gimli::RunTimeEndian::Little
+            } else {
+                gimli::RunTimeEndian::Big
+            };
+            let address_size = if is_64 { 8 } else { 4 };
+            load_eh_frame_exception_handlers(
+                &binary,
+                &sections,
+                ".eh_frame",
+                endian,
+                address_size,
+                &defined,
+            )
+        };
+
+        let entry = Address::from_virtual_address(&sections, gl.entry);
+        let mut binary = binary;
+        for (offset, bytes) in patches {
+            if let Some(target) = binary.get_mut(offset..offset + bytes.len()) {
+                target.copy_from_slice(&bytes);
+            }
+        }
+
+        let noreturn = NoReturnFunctions::new(&defined);
         let relations = Relations::new();
         Ok(Elf {
             kind,
-            entry: Address::from_virtual_address(&sections, gl.entry),
+            entry,
             path,
             binary,
             architecture,
@@ -100,6 +158,9 @@ impl Elf {
             sections,
             relations: relations.clone(),
             blocks: Blocks::new(relations),
+            debug_info: std::sync::Arc::new(debug_info),
+            exception_handlers: std::sync::Arc::new(exception_handlers),
+            noreturn: std::sync::Arc::new(noreturn),
             cancel_token: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
         })
     }
@@ -127,7 +188,7 @@ impl Elf {
 
 /// Demangle a symbol name (C++ or Rust), returning the original if
 /// demangling fails.
-fn demangle_symbol(name: &str) -> String {
+pub(crate) fn demangle_symbol(name: &str) -> String {
     if let Ok(sym) = cpp_demangle::Symbol::new(name) {
         if let Ok(demangled) = sym.demangle() {
             return demangled;
@@ -1,5 +1,7 @@
 use crate::{
-    core::{Address, Block, BlockRelationInformation, DestinationType, RelationType},
+    core::{
+        Address, Block, BlockRelationInformation, DestinationType, RelationType, is_ip_relative,
+    },
     elf::Elf,
     prelude::*,
 };
@@ -70,6 +72,7 @@ impl Elf {
 
         /* Find connected blocks */
         let mut connected_to = Vec::new();
+        let mut jump_table = None;
         // if the last instruction is not set, there is no connected block
         if let Some(last_instruction_address) = &last_instruction_address {
             let inst = &self.parse_assem_count(last_instruction_address, 1).unwrap()[0].inner;
@@ -78,7 +81,24 @@ impl Elf {
                     + inst.bytes.as_ref().unwrap().len() as u64,
             );
             let relation_type = Self::control_flow_relation_type(inst);
-            if matches!(relation_type, Some(RelationType::Jcc | RelationType::Call)) {
+            // address that the last instruction points to
+            let relation =
+                self.get_connected_address_and_relation_type(last_instruction_address, inst);
+            // calls to functions that never return don't continue to the next instruction
+            let noreturn = matches!(relation_type, Some(RelationType::Call))
+                && self.noreturn.is_noreturn_call(
+                    inst,
+                    relation.destination.as_ref(),
+                    &|args, instruction_len| {
+                        self.calc_relative_address_with_ip(
+                            last_instruction_address,
+                            args,
+                            instruction_len,
+                        )
+                    },
+                    &|x| self.generate_block_from_address(x),
+                );
+            if matches!(relation_type, Some(RelationType::Jcc | RelationType::Call)) && !noreturn {
                 // false branch or halt
                 let relation_type = if matches!(relation_type, Some(RelationType::Jcc)) {
                     RelationType::Continued
@@ -93,21 +113,59 @@ impl Elf {
                     relation_type,
                 });
             }
-            // address that the last instruction points to
-            connected_to
-                .push(self.get_connected_address_and_relation_type(last_instruction_address, inst));
+            if relation.relation_type == RelationType::Jump && relation.destination.is_none() {
+                jump_table = crate::arch::x86_64::jump_table::find_jump_table(
+                    self.architecture,
+                    &self.sections,
+                    &self.blocks,
+                    &self.binary,
+                    &start_address,
+                    &instructions,
+                    |x| self.is_likely_code_address(x),
+                );
+            }
+            match &jump_table {
+                Some(jump_table) => {
+                    let mut destinations: Vec<_> =
+                        jump_table.get_cases().iter().map(|x| &x.1).collect();
+                    destinations.sort_unstable_by_key(|x| x.get_virtual_address());
+                    destinations.dedup();
+                    for destination in destinations {
+                        connected_to.push(BlockRelationInformation {
+                            destination: Some(destination.clone()),
+                            destination_type: DestinationType::Static,
+                            relation_type: RelationType::Switch,
+                        });
+                    }
+                }
+                None => connected_to.push(relation),
+            }
         }
 
+        // connect the handlers of the exceptions the instructions may raise
+        let exception_handlers = self.exception_handlers.connect_block(
+            &self.sections,
+            &start_address,
+            &instructions,
+            &mut connected_to,
+        );
+
         debug!(
             ?connected_to,
             "Block generation done for size {:?}", block_size
         );
+        // analyzing a callee that calls back into this function may have generated the block
+        if let Some(block) = self.blocks.get_by_start_address(&start_address) {
+            return block;
+        }
         self.blocks.generate_block(
             start_address,
             block_size,
             &connected_to,
             None,
             instructions.into(),
+            jump_table,
+            exception_handlers,
         )
     }
 
@@ -119,16 +177,17 @@ impl Elf {
     ) -> BlockRelationInformation {
         let relation_type =
             Self::control_flow_relation_type(inst).unwrap_or_else(|| unreachable!("{:?}", inst));
-        if inst.arguments.len() != 1 {
+        let Some(arg) = crate::arch::branch_target_argument(inst) else {
             return BlockRelationInformation {
                 destination: None,
                 destination_type: DestinationType::Dynamic,
                 relation_type,
             };
-        }
-        let arg = &inst.arguments[0];
+        };
         match arg {
-            iceball::Argument::Register(_) => BlockRelationInformation {
+            iceball::Argument::Register(_)
+            | iceball::Argument::ShiftedRegister(..)
+            | iceball::Argument::Condition(_) => BlockRelationInformation {
                 destination: None,
                 destination_type: DestinationType::Dynamic,
                 relation_type,
@@ -141,20 +200,7 @@ impl Elf {
                 }
             }
             iceball::Argument::Memory(iceball::Memory::RelativeAddressing(args)) => {
-                if args
-                    .iter()
-                    .filter(|x| matches!(x, iceball::RelativeAddressingArgument::Register(_)))
-                    .all(|x| {
-                        matches!(
-                            x,
-                            iceball::RelativeAddressingArgument::Register(iceball::Register::X64(
-                                iceball::X64Register::Eip,
-                            )) | iceball::RelativeAddressingArgument::Register(
-                                iceball::Register::X64(iceball::X64Register::Rip,)
-                            )
-                        )
-                    })
-                {
+                if is_ip_relative(args) {
                     let instruction_len = inst.bytes.as_ref().map(|x| x.len() as u64).unwrap_or(0);
                     let indirect_slot =
                         self.calc_relative_address_with_ip(ip, args, instruction_len);
@@ -340,7 +386,10 @@ impl Elf {
     }
 
     fn control_flow_relation_type(inst: &iceball::Instruction) -> Option<RelationType> {
-        if let Some(bytes) = inst.bytes.as_deref() {
+        // Only use byte-pattern fast path for x86/x64 architectures
+        let is_x86 = matches!(&inst.statement, Ok(iceball::Statement::X64(_)));
+
+        if is_x86 && let Some(bytes) = inst.bytes.as_deref() {
             match bytes {
                 [0x70..=0x7F, ..] | [0x0F, 0x80..=0x8F, ..] | [0xE3, ..] => {
                     return Some(RelationType::Jcc);
@@ -20,7 +20,8 @@ impl Elf {
             "Full-program block analysis started"
         );
 
-        let mut queue: Vec<_> = seeds;
+        // Seeds are popped from the back, so reverse them to analyze the first one first
+        let mut queue: Vec<_> = seeds.into_iter().rev().collect();
         let mut visited = HashSet::new();
         let mut result = Vec::new();
 
@@ -36,7 +37,14 @@ impl Elf {
             if !visited.insert(current_va) {
                 continue;
             }
-            let block = self.analyze_block(&address)?;
+            // Discovered seeds may turn out not to be code
+            let block = match self.analyze_block(&address) {
+                Ok(block) => block,
+                Err(e) => {
+                    tracing::warn!(?e, va = current_va, "Skipping block that failed to analyze");
+                    continue;
+                }
+            };
             result.push(block.clone());
             let mut discovered = block
                 .get_connected_to()
@@ -60,21 +68,25 @@ impl Elf {
 
     /// Collect starting addresses for BFS traversal based on binary kind.
     fn collect_analysis_seeds(&self) -> Vec<crate::core::Address> {
-        use crate::core::Address;
+        use crate::core::{Address, discover_functions};
 
+        let mut seeds = Vec::new();
+        let mut seen = HashSet::new();
         match self.kind {
             BinaryKind::Executable => {
-                vec![self.entry.clone()]
+                seen.insert(self.entry.get_virtual_address());
+                seeds.push(self.entry.clone());
             }
             BinaryKind::SharedLibrary | BinaryKind::ObjectFile | BinaryKind::DebugInfo => {
-                let mut seeds = Vec::new();
-                let mut seen = HashSet::new();
-
-                // Use symbols as seeds
+                // Use symbols as seeds, skipping data (e.g., GOT slots)
                 let reader = self.defined.get_reader();
                 for def in reader.iter() {
                     let va = def.address.get_virtual_address();
-                    if va != 0 && seen.insert(va) {
+                    let is_code = def
+                        .address
+                        .get_section()
+                        .is_some_and(|section| section.is_executable());
+                    if va != 0 && is_code && seen.insert(va) {
                         seeds.push(def.address.clone());
                     }
                 }
@@ -86,21 +98,84 @@ impl Elf {
                         seeds.push(self.entry.clone());
                     }
                 }
+            }
+        }
 
-                // Fallback: executable section starts
-                if seeds.is_empty() {
-                    for section in self.sections.all() {
-                        if section.is_executable() && seen.insert(section.virtual_address) {
-                            seeds.push(Address::from_virtual_address(
-                                &self.sections,
-                                section.virtual_address,
-                            ));
-                        }
-                    }
+        // Functions only reached through pointers (e.g., vtables) are found by
+        // the unwind table and by scanning the code
+        let discovered = self
+            .function_table_starts()
+            .into_iter()
+            .chain(discover_functions(
+                &self.binary,
+                &self.sections,
+                self.architecture,
+                &self.capstone,
+            ));
+        for va in discovered {
+            if seen.insert(va) {
+                seeds.push(Address::from_virtual_address(&self.sections, va));
+            }
+        }
+
+        // Fallback: executable section starts
+        if seeds.is_empty() {
+            for section in self.sections.all() {
+                if section.is_executable() && seen.insert(section.virtual_address) {
+                    seeds.push(Address::from_virtual_address(
+                        &self.sections,
+                        section.virtual_address,
+                    ));
                 }
+            }
+        }
+
+        seeds
+    }
+
+    /// Collect function starts from the frame description entries of `.eh_frame`.
+    fn function_table_starts(&self) -> Vec<u64> {
+        use gimli::{BaseAddresses, CieOrFde, EhFrame, RunTimeEndian, UnwindSection};
 
-                seeds
+        let Ok(elf) = goblin::elf::Elf::parse(&self.binary) else {
+            return Vec::new();
+        };
+        // Entries of an object are only meaningful after their relocations are applied
+        if elf.header.e_type == goblin::elf::header::ET_REL {
+            return Vec::new();
+        }
+        let Some(header) = elf
+            .section_headers
+            .iter()
+            .find(|sh| elf.shdr_strtab.get_at(sh.sh_name) == Some(".eh_frame"))
+        else {
+            return Vec::new();
+        };
+        let Some(data) = header.file_range().and_then(|range| self.binary.get(range)) else {
+            return Vec::new();
+        };
+
+        let endian = if elf.little_endian {
+            RunTimeEndian::Little
+        } else {
+            RunTimeEndian::Big
+        };
+        let mut eh_frame = EhFrame::new(data, endian);
+        eh_frame.set_address_size(if elf.is_64 { 8 } else { 4 });
+        let bases = BaseAddresses::default().set_eh_frame(header.sh_addr);
+
+        let mut starts = Vec::new();
+        let mut entries = eh_frame.entries(&bases);
+        while let Ok(Some(entry)) = entries.next() {
+            let CieOrFde::Fde(partial) = entry else {
+                continue;
+            };
+            if let Ok(fde) =
+                partial.parse(|section, bases, offset| section.cie_from_offset(bases, offset))
+            {
+                starts.push(fde.initial_address());
             }
         }
+        starts
     }
 }
@@ -0,0 +1,194 @@
+//! Module laying out relocatable objects and resolving their relocations
+
+use super::_elf::demangle_symbol;
+use crate::{
+    core::{Address, PreDefinedOffset, PreDefinedOffsets, Sections},
+    prelude::*,
+};
+use goblin::elf::{Elf as GoblinElf, reloc, section_header, sym};
+use hashbrown::HashMap;
+use iceball::MachineArchitecture;
+
+/// Size of each synthetic slot given to undefined symbols and GOT entries
+const EXTERN_SLOT_SIZE: u64 = 8;
+
+/// Symbols of a relocatable object placed at their laid out addresses
+struct ObjectSymbols {
+    /// Address of each symbol table entry, `None` if it can not be placed
+    addresses: Vec<Option<u64>>,
+    /// Next free synthetic slot after the laid out sections
+    next_slot: u64,
+    /// Synthetic GOT entry of each symbol referenced through the GOT
+    got: HashMap<usize, u64>,
+}
+
+impl ObjectSymbols {
+    /// Allocates a synthetic slot after the laid out sections.
+    fn allocate_slot(&mut self) -> u64 {
+        let slot = self.next_slot;
+        self.next_slot += EXTERN_SLOT_SIZE;
+        slot
+    }
+}
+
+/// Places the symbols of a relocatable object, and computes the bytes its relocations patch.
+///
+/// ### Arguments
+/// - `gl: &GoblinElf` - parsed relocatable object
+/// - `sections: &Sections` - laid out sections of the object
+/// - `architecture: MachineArchitecture` - architecture of the object
+/// - `defined: &PreDefinedOffsets` - container the placed symbols are inserted into
+///
+/// ### Returns
+/// - `Vec<(usize, Vec<u8>)>` - file offset and replacement bytes of each applied relocation
+///
+/// ### Note
+/// - Undefined and common symbols get synthetic slots after the laid out sections, so calls
+///   and loads of imported symbols resolve to their names.
+/// - Only x86-64 relocations are applied, other architectures keep their unrelocated bytes
+///   with a warning.
+pub(super) fn load_object(
+    gl: &GoblinElf,
+    sections: &Sections,
+    architecture: MachineArchitecture,
+    defined: &PreDefinedOffsets,
+) -> Vec<(usize, Vec<u8>)> {
+    let (layout, end) = Sections::elf_object_layout(gl);
+    let mut symbols = ObjectSymbols {
+        addresses: Vec::with_capacity(gl.syms.len()),
+        next_slot: end.next_multiple_of(0x1000),
+        got: HashMap::new(),
+    };
+
+    for symbol in gl.syms.iter() {
+        let name = gl
+            .strtab
+            .get_at(symbol.st_name)
+            .filter(|x| !x.is_empty())
+            .map(demangle_symbol);
+        let address = match symbol.st_shndx as u32 {
+            section_header::SHN_UNDEF if name.is_some() => Some(symbols.allocate_slot()),
+            section_header::SHN_UNDEF => None,
+            section_header::SHN_COMMON => Some(symbols.allocate_slot()),
+            section_header::SHN_ABS => Some(symbol.st_value),
+            index => layout
+                .get(index as usize)
+                .copied()
+                .flatten()
+                .map(|base| base + symbol.st_value),
+        };
+        symbols.addresses.push(address);
+
+        let (Some(name), Some(address)) = (name, address) else {
+            continue;
+        };
+        let named = symbol.st_shndx as u32 == section_header::SHN_UNDEF
+            || matches!(
+                symbol.st_type(),
+                sym::STT_FUNC | sym::STT_OBJECT | sym::STT_GNU_IFUNC
+            );
+        if named {
+            defined.insert(PreDefinedOffset {
+                name,
+                address: Address::from_virtual_address(sections, address),
+            });
+        }
+    }
+
+    if architecture != MachineArchitecture::X64 {
+        warn!("relocations of {:?} objects are not applied", architecture);
+        return Vec::new();
+    }
+
+    let mut patches = Vec::new();
+    for (index, relocations) in gl.shdr_relocs.iter() {
+        let Some(header) = gl.section_headers.get(*index) else {
+            continue;
+        };
+        let target = header.sh_info as usize;
+        let (Some(Some(base)), Some(target_header)) =
+            (layout.get(target), gl.section_headers.get(target))
+        else {
+            continue;
+        };
+        if target_header.sh_type == section_header::SHT_NOBITS {
+            continue;
+        }
+        for relocation in relocations.iter() {
+            let Some(bytes) = apply_x64_relocation(&mut symbols, &relocation, *base) else {
+                continue;
+            };
+            let offset = target_header.sh_offset + relocation.r_offset;
+            patches.push((offset as usize, bytes));
+        }
+    }
+
+    // name the GOT entries after the symbols they hold
+    for (index, slot) in symbols.got.iter() {
+        let Some(name) = gl
+            .syms
+            .get(*index)
+            .and_then(|x| gl.strtab.get_at(x.st_name))
+            .filter(|x| !x.is_empty())
+        else {
+            continue;
+        };
+        defined.insert(PreDefinedOffset {
+            name: demangle_symbol(name),
+            address: Address::from_virtual_address(sections, *slot),
+        });
+    }
+    debug!("{} relocations applied", patches.len());
+    patches
+}
+
+/// Computes the bytes an x86-64 relocation stores.
+///
+/// ### Arguments
+/// - `symbols: &mut ObjectSymbols` - placed symbols, GOT entries are allocated on use
+/// - `relocation: &reloc::Reloc` - relocation to apply
+/// - `base: u64` - address of the section the relocation patches
+///
+/// ### Returns
+/// - `Option<Vec<u8>>` - little endian bytes to store, or `None` if the relocation is not applied
+fn apply_x64_relocation(
+    symbols: &mut ObjectSymbols,
+    relocation: &reloc::Reloc,
+    base: u64,
+) -> Option<Vec<u8>> {
+    let symbol = *symbols.addresses.get(relocation.r_sym)?;
+    let addend = relocation.r_addend.unwrap_or(0);
+    let place = base + relocation.r_offset;
+    let bytes = match relocation.r_type {
+        reloc::R_X86_64_64 => symbol?.wrapping_add_signed(addend).to_le_bytes().to_vec(),
+        reloc::R_X86_64_PC64 => symbol?
+            .wrapping_add_signed(addend)
+            .wrapping_sub(place)
+            .to_le_bytes()
+            .to_vec(),
+        reloc::R_X86_64_PC32 | reloc::R_X86_64_PLT32 => {
+            (symbol?.wrapping_add_signed(addend).wrapping_sub(place) as u32)
+                .to_le_bytes()
+                .to_vec()
+        }
+        reloc::R_X86_64_32 | reloc::R_X86_64_32S => (symbol?.wrapping_add_signed(addend) as u32)
+            .to_le_bytes()
+            .to_vec(),
+        reloc::R_X86_64_GOTPCREL | reloc::R_X86_64_GOTPCRELX | reloc::R_X86_64_REX_GOTPCRELX => {
+            symbol?;
+            let got = match symbols.got.get(&relocation.r_sym) {
+                Some(got) => *got,
+                None => {
+                    let got = symbols.allocate_slot();
+                    symbols.got.insert(relocation.r_sym, got);
+                    got
+                }
+            };
+            (got.wrapping_add_signed(addend).wrapping_sub(place) as u32)
+                .to_le_bytes()
+                .to_vec()
+        }
+        _ => return None,
+    };
+    Some(bytes)
+}
@@ -0,0 +1,302 @@
+//! Module resolving the imported symbols PLT stubs and GOT slots refer to
+
+use super::_elf::demangle_symbol;
+use crate::{
+    core::{Address, PreDefinedOffset, PreDefinedOffsets, Sections},
+    prelude::*,
+};
+use goblin::elf::{Elf as GoblinElf, reloc};
+use hashbrown::HashMap;
+use iceball::MachineArchitecture;
+
+/// What a relocation stores in the slot it patches
+enum RelocationKind {
+    /// Address of a symbol (`JUMP_SLOT`, `GLOB_DAT`)
+    Symbol,
+    /// Load base plus the addend (`RELATIVE`)
+    Relative,
+    /// Return value of the resolver at the addend (`IRELATIVE`)
+    IndirectRelative,
+}
+
+/// Classifies the relocation types resolved into symbols.
+///
+/// ### Arguments
+/// - `architecture: MachineArchitecture` - architecture of the binary
+/// - `r_type: u32` - type of the relocation
+///
+/// ### Returns
+/// - `Option<RelocationKind>` - kind of the relocation, or `None` if it is not resolved
+fn relocation_kind(architecture: MachineArchitecture, r_type: u32) -> Option<RelocationKind> {
+    let kind = match (architecture, r_type) {
+        (MachineArchitecture::X64, reloc::R_X86_64_JUMP_SLOT | reloc::R_X86_64_GLOB_DAT)
+        | (MachineArchitecture::X86, reloc::R_386_JMP_SLOT | reloc::R_386_GLOB_DAT)
+        | (MachineArchitecture::Arm, reloc::R_ARM_JUMP_SLOT | reloc::R_ARM_GLOB_DAT)
+        | (MachineArchitecture::Arm64, reloc::R_AARCH64_JUMP_SLOT | reloc::R_AARCH64_GLOB_DAT) => {
+            RelocationKind::Symbol
+        }
+        (MachineArchitecture::X64, reloc::R_X86_64_RELATIVE)
+        | (MachineArchitecture::X86, reloc::R_386_RELATIVE)
+        | (MachineArchitecture::Arm, reloc::R_ARM_RELATIVE)
+        | (MachineArchitecture::Arm64, reloc::R_AARCH64_RELATIVE) => RelocationKind::Relative,
+        (MachineArchitecture::X64, reloc::R_X86_64_IRELATIVE)
+        | (MachineArchitecture::X86, reloc::R_386_IRELATIVE)
+        | (MachineArchitecture::Arm, reloc::R_ARM_IRELATIVE)
+        | (MachineArchitecture::Arm64, reloc::R_AARCH64_IRELATIVE) => {
+            RelocationKind::IndirectRelative
+        }
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// Maps the GOT slots filled by the dynamic linker and the PLT stubs jumping through them
+/// to the symbols they refer to.
+///
+/// ### Arguments
+/// - `gl: &GoblinElf` - parsed ELF file
+/// - `binary: &[u8]` - raw file data
+/// - `sections: &Sections` - section information of the file
+/// - `architecture: MachineArchitecture` - architecture of the file
+/// - `defined: &PreDefinedOffsets` - container the resolved symbols are inserted into
+///
+/// ### Note
+/// - `JUMP_SLOT` and `GLOB_DAT` slots are named after the imported symbol, `RELATIVE` and
+///   `IRELATIVE` slots after the symbol (or ifunc resolver) defined at their addend.
+/// - `RELATIVE` relocations also patch data pointers (e.g., vtables, function pointer tables),
+///   so only the ones inside `.got` and `.got.plt` are named.
+/// - Static binaries keep their `IRELATIVE` relocations without a dynamic section, so the
+///   relocation sections are read when the dynamic section has none.
+pub(super) fn insert_relocation_symbols(
+    gl: &GoblinElf,
+    binary: &[u8],
+    sections: &Sections,
+    architecture: MachineArchitecture,
+    defined: &PreDefinedOffsets,
+) {
+    // symbols defined inside the file, for the targets of relative relocations
+    let mut local_symbols: HashMap<u64, String> = HashMap::new();
+    for (symtab, strtab) in [(&gl.syms, &gl.strtab), (&gl.dynsyms, &gl.dynstrtab)] {
+        for sym in symtab.iter() {
+            if sym.st_value == 0 || sym.st_shndx == 0 {
+                continue;
+            }
+            let Some(name) = strtab.get_at(sym.st_name).filter(|x| !x.is_empty()) else {
+                continue;
+            };
+            local_symbols
+                .entry(sym.st_value)
+                .or_insert_with(|| demangle_symbol(name));
+        }
+    }
+
+    // relative relocations outside the GOT patch data, not slots
+    let got_ranges: Vec<std::ops::Range<u64>> = gl
+        .section_headers
+        .iter()
+        .filter(|x| matches!(gl.shdr_strtab.get_at(x.sh_name), Some(".got" | ".got.plt")))
+        .map(|x| x.sh_addr..x.sh_addr.saturating_add(x.sh_size))
+        .collect();
+    let is_got_slot = |address: u64| got_ranges.iter().any(|x| x.contains(&address));
+
+    let read_slot = |address: u64| -> Option<u64> {
+        let size = if gl.is_64 { 8 } else { 4 };
+        let file_offset = Address::from_virtual_address(sections, address).get_file_offset()?;
+        let bytes = binary.get(file_offset as usize..file_offset as usize + size)?;
+        Some(if gl.is_64 {
+            u64::from_le_bytes(bytes.try_into().unwrap())
+        } else {
+            u32::from_le_bytes(bytes.try_into().unwrap()) as u64
+        })
+    };
+
+    let dynamic = [&gl.pltrelocs, &gl.dynrelas, &gl.dynrels];
+    let relocations: Vec<(reloc::Reloc, bool)> = if dynamic.iter().any(|x| !x.is_empty()) {
+        dynamic
+            .iter()
+            .flat_map(|x| x.iter())
+            .map(|x| (x, true))
+            .collect()
+    } else {
+        gl.shdr_relocs
+            .iter()
+            .flat_map(|(_, x)| x.iter())
+            .map(|x| (x, false))
+            .collect()
+    };
+
+    let mut slots: HashMap<u64, String> = HashMap::new();
+    for (relocation, is_dynamic) in relocations {
+        let Some(kind) = relocation_kind(architecture, relocation.r_type) else {
+            continue;
+        };
+        if matches!(kind, RelocationKind::Relative) && !is_got_slot(relocation.r_offset) {
+            continue;
+        }
+        let name = match kind {
+            RelocationKind::Symbol => {
+                let (symtab, strtab) = if is_dynamic {
+                    (&gl.dynsyms, &gl.dynstrtab)
+                } else {
+                    (&gl.syms, &gl.strtab)
+                };
+                symtab
+                    .get(relocation.r_sym)
+                    .and_then(|sym| strtab.get_at(sym.st_name))
+                    .filter(|x| !x.is_empty())
+                    .map(demangle_symbol)
+            }
+            RelocationKind::Relative | RelocationKind::IndirectRelative => {
+                // REL relocations keep the addend inside the slot
+                let target = match relocation.r_addend {
+                    Some(addend) => Some(addend as u64),
+                    None => read_slot(relocation.r_offset),
+                };
+                target.and_then(|x| local_symbols.get(&x).cloned())
+            }
+        };
+        let Some(name) = name else {
+            continue;
+        };
+        slots.entry(relocation.r_offset).or_insert(name);
+    }
+    if slots.is_empty() {
+        return;
+    }
+
+    let stubs = find_plt_stubs(gl, binary, architecture, &slots);
+    debug!(
+        "{} relocated slots and {} PLT stubs resolved",
+        slots.len(),
+        stubs.len()
+    );
+    for (address, name) in slots.into_iter().chain(stubs) {
+        defined.insert(PreDefinedOffset {
+            name,
+            address: Address::from_virtual_address(sections, address),
+        });
+    }
+}
+
+/// Finds the PLT stubs jumping through the resolved slots.
+///
+/// ### Arguments
+/// - `gl: &GoblinElf` - parsed ELF file
+/// - `binary: &[u8]` - raw file data
+/// - `architecture: MachineArchitecture` - architecture of the file
+/// - `slots: &HashMap<u64, String>` - resolved slots and their symbol names
+///
+/// ### Returns
+/// - `Vec<(u64, String)>` - start address of each stub and the symbol it jumps to
+///
+/// ### Note
+/// - Stubs are searched in `.plt`, `.plt.sec`, `.plt.got` and `.iplt`.
+/// - x86-64 stubs are `[endbr64] [bnd] jmp [rip + slot]`, x86 stubs `jmp [slot]` or
+///   `jmp [ebx + slot - got]`, AArch64 stubs `[bti c] adrp x16, slot; ldr x17, [x16, slot]`.
+fn find_plt_stubs(
+    gl: &GoblinElf,
+    binary: &[u8],
+    architecture: MachineArchitecture,
+    slots: &HashMap<u64, String>,
+) -> Vec<(u64, String)> {
+    let section_named = |name: &str| {
+        gl.section_headers
+            .iter()
+            .find(|x| gl.shdr_strtab.get_at(x.sh_name) == Some(name))
+    };
+    // `jmp [ebx + offset]` is relative to the GOT holding the PLT slots
+    let got_base = section_named(".got.plt")
+        .or_else(|| section_named(".got"))
+        .map(|x| x.sh_addr);
+
+    let mut result = Vec::new();
+    for section in [".plt", ".plt.sec", ".plt.got", ".iplt"] {
+        let Some(header) = section_named(section) else {
+            continue;
+        };
+        if header.sh_type == goblin::elf::section_header::SHT_NOBITS {
+            continue;
+        }
+        let start = header.sh_offset as usize;
+        let Some(bytes) = binary.get(start..start.saturating_add(header.sh_size as usize)) else {
+            continue;
+        };
+        let address = header.sh_addr;
+        match architecture {
+            MachineArchitecture::X86 | MachineArchitecture::X64 => {
+                for position in 0..bytes.len().saturating_sub(5) {
+                    let slot = match (bytes[position], bytes[position + 1]) {
+                        (0xff, 0x25) => {
+                            let operand = i32::from_le_bytes(
+                                bytes[position + 2..position + 6].try_into().unwrap(),
+                            );
+                            if architecture == MachineArchitecture::X64 {
+                                (address + position as u64 + 6).wrapping_add_signed(operand as i64)
+                            } else {
+                                operand as u32 as u64
+                            }
+                        }
+                        (0xff, 0xa3) if architecture == MachineArchitecture::X86 => {
+                            let Some(got_base) = got_base else {
+                                continue;
+                            };
+                            let operand = i32::from_le_bytes(
+                                bytes[position + 2..position + 6].try_into().unwrap(),
+                            );
+                            got_base.wrapping_add_signed(operand as i64)
+                        }
+                        _ => continue,
+                    };
+                    let Some(name) = slots.get(&slot) else {
+                        continue;
+                    };
+                    let mut stub = position;
+                    if stub >= 1 && bytes[stub - 1] == 0xf2 {
+                        stub -= 1;
+                    }
+                    if stub >= 4
+                        && matches!(&bytes[stub - 4..stub], [0xf3, 0x0f, 0x1e, 0xfa | 0xfb])
+                    {
+                        stub -= 4;
+                    }
+                    result.push((address + stub as u64, name.clone()));
+                }
+            }
+            MachineArchitecture::Arm64 => {
+                const BTI_C: u32 = 0xd503_245f;
+                let words: Vec<u32> = bytes
+                    .chunks_exact(4)
+                    .map(|x| u32::from_le_bytes(x.try_into().unwrap()))
+                    .collect();
+                for (index, pair) in words.windows(2).enumerate() {
+                    let (adrp, ldr) = (pair[0], pair[1]);
+                    // adrp x16, page
+                    if adrp & 0x9f00_001f != 0x9000_0010 {
+                        continue;
+                    }
+                    // ldr x17, [x16, #offset]
+                    if ldr & 0xffc0_03ff != 0xf940_0211 {
+                        continue;
+                    }
+                    let pc = address + index as u64 * 4;
+                    let immediate = (((adrp >> 5) & 0x7ffff) << 2) | ((adrp >> 29) & 0b11);
+                    // sign extend the 21 bit page count
+                    let pages = ((immediate as i64) << 43) >> 43;
+                    let page = (pc & !0xfff).wrapping_add_signed(pages << 12);
+                    let slot = page + ((ldr >> 10) & 0xfff) as u64 * 8;
+                    let Some(name) = slots.get(&slot) else {
+                        continue;
+                    };
+                    let stub = if index >= 1 && words[index - 1] == BTI_C {
+                        pc - 4
+                    } else {
+                        pc
+                    };
+                    result.push((stub, name.clone()));
+                }
+            }
+            MachineArchitecture::Arm => {}
+        }
+    }
+    result
+}
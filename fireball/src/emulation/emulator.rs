@@ -0,0 +1,289 @@
+//! Module containing the emulator running code of the loaded binary
+
+use super::{EmulationInput, EmulationStep, EmulationStop, EmulationTrace, registers};
+use crate::{
+    Fireball,
+    core::{Address, Block, Fire, FireRaw},
+    prelude::*,
+};
+use iceball::MachineArchitecture;
+use std::collections::{BTreeMap, BTreeSet};
+use unicorn_engine::{
+    Unicorn,
+    unicorn_const::{Arch, HookType, Mode, Prot},
+};
+
+/// Granularity of unicorn memory mappings
+const PAGE_SIZE: u64 = 0x1000;
+/// Lowest address of the emulated stack
+const STACK_BASE: u64 = 0x7000_0000;
+/// Size of the emulated stack below the stack pointer
+const STACK_SIZE: u64 = 0x10_0000;
+/// Return address given to emulated functions, reaching it ends the emulation
+const RETURN_ADDRESS: u64 = 0x6fff_f000;
+
+/// State collected by the hooks while emulating
+#[derive(Default)]
+struct TraceState {
+    steps: Vec<EmulationStep>,
+    written: BTreeSet<u64>,
+    /// Address of the instruction the emulation ends after
+    last_instruction: Option<u64>,
+}
+
+/// Emulator running functions and blocks of a loaded binary
+pub struct Emulator<'a> {
+    fireball: &'a Fireball,
+    architecture: MachineArchitecture,
+}
+
+impl<'a> Emulator<'a> {
+    /// Creates an emulator for a loaded binary.
+    ///
+    /// ### Arguments
+    /// - `fireball: &'a Fireball` - binary whose sections are mapped
+    ///
+    /// ### Returns
+    /// - `Result<Self, FireballError>` - the emulator, or an error if the architecture can not be
+    ///   emulated
+    pub fn new(fireball: &'a Fireball) -> Result<Self, FireballError> {
+        let Some(architecture) = fireball.get_architecture() else {
+            return Err(FireballError::EmulationFailed(
+                "the binary contains no code".to_string(),
+            ));
+        };
+        if architecture == MachineArchitecture::Arm {
+            return Err(FireballError::EmulationFailed(
+                "32-bit ARM is not supported".to_string(),
+            ));
+        }
+        Ok(Self {
+            fireball,
+            architecture,
+        })
+    }
+
+    /// Emulates a function until it returns.
+    ///
+    /// ### Arguments
+    /// - `address: &Address` - start address of the function
+    /// - `input: &EmulationInput` - registers, stack and memory the function starts with
+    ///
+    /// ### Returns
+    /// - `Result<EmulationTrace, FireballError>` - executed instructions and the final state
+    ///
+    /// ### Note
+    /// - Faults while running (unmapped memory, calls into imports, ...) end the trace instead of
+    ///   returning an error, see `EmulationTrace::stop`.
+    pub fn run_function(
+        &self,
+        address: &Address,
+        input: &EmulationInput,
+    ) -> Result<EmulationTrace, FireballError> {
+        self.run(address.get_virtual_address(), RETURN_ADDRESS, None, input)
+    }
+
+    /// Emulates a block until its last instruction executed.
+    ///
+    /// ### Arguments
+    /// - `block: &Block` - analyzed block
+    /// - `input: &EmulationInput` - registers, stack and memory the block starts with
+    ///
+    /// ### Returns
+    /// - `Result<EmulationTrace, FireballError>` - executed instructions and the final state
+    ///
+    /// ### Note
+    /// - The trace finishes once the last instruction executed, wherever it jumped to.
+    pub fn run_block(
+        &self,
+        block: &Block,
+        input: &EmulationInput,
+    ) -> Result<EmulationTrace, FireballError> {
+        let start = block.get_start_address().get_virtual_address();
+        let Some(size) = block.get_block_size() else {
+            return Err(FireballError::EmulationFailed(format!(
+                "block at {start:#x} has no size"
+            )));
+        };
+        let last_instruction = block.get_instructions().last().map(|x| x.address());
+        self.run(start, start + size, last_instruction, input)
+    }
+
+    /// Emulates from `start` until `until` is reached, or `last_instruction` executed.
+    fn run(
+        &self,
+        start: u64,
+        until: u64,
+        last_instruction: Option<u64>,
+        input: &EmulationInput,
+    ) -> Result<EmulationTrace, FireballError> {
+        let (arch, mode) = match self.architecture {
+            MachineArchitecture::X64 => (Arch::X86, Mode::MODE_64),
+            MachineArchitecture::X86 => (Arch::X86, Mode::MODE_32),
+            MachineArchitecture::Arm64 | MachineArchitecture::Arm => {
+                (Arch::ARM64, Mode::LITTLE_ENDIAN)
+            }
+        };
+        let state = TraceState {
+            last_instruction,
+            ..Default::default()
+        };
+        let mut emu = Unicorn::new_with_data(arch, mode, state)?;
+        let mut mapped = BTreeSet::new();
+
+        // Sections of the binary
+        let binary = self.fireball.get_binary();
+        for section in self.fireball.get_sections().all() {
+            let size = section.virtual_size.max(section.size_of_file);
+            // sections not loaded into memory (symbols, debug information, ...) have no address
+            if size == 0 || section.virtual_address == 0 {
+                continue;
+            }
+            map_range(&mut emu, &mut mapped, section.virtual_address, size)?;
+            let file_size = section.size_of_file.min(size) as usize;
+            let file_offset = section.file_offset as usize;
+            if let Some(bytes) = binary.get(file_offset..file_offset + file_size) {
+                emu.mem_write(section.virtual_address, bytes)?;
+            }
+        }
+
+        // Stack, holding the return address and the stack arguments above the stack pointer
+        let stack_size = STACK_SIZE + (input.stack.len() as u64 + 8).next_multiple_of(PAGE_SIZE);
+        map_range(&mut emu, &mut mapped, STACK_BASE, stack_size)?;
+        let stack_pointer = STACK_BASE + STACK_SIZE;
+        let (sp, arguments) = match self.architecture {
+            MachineArchitecture::X64 => {
+                emu.mem_write(stack_pointer, &RETURN_ADDRESS.to_le_bytes())?;
+                ("rsp", stack_pointer + 8)
+            }
+            MachineArchitecture::X86 => {
+                emu.mem_write(stack_pointer, &(RETURN_ADDRESS as u32).to_le_bytes())?;
+                ("esp", stack_pointer + 4)
+            }
+            MachineArchitecture::Arm64 | MachineArchitecture::Arm => {
+                let lr = registers::register_id(self.architecture, "lr").unwrap();
+                emu.reg_write(lr, RETURN_ADDRESS)?;
+                ("sp", stack_pointer)
+            }
+        };
+        let sp = registers::register_id(self.architecture, sp).unwrap();
+        emu.reg_write(sp, stack_pointer)?;
+        emu.mem_write(arguments, &input.stack)?;
+
+        // Memory and registers given by the caller
+        for (address, bytes) in &input.memory {
+            map_range(&mut emu, &mut mapped, *address, bytes.len() as u64)?;
+            emu.mem_write(*address, bytes)?;
+        }
+        for (name, value) in &input.registers {
+            let Some(id) = registers::register_id(self.architecture, name) else {
+                return Err(FireballError::EmulationFailed(format!(
+                    "unknown register {name}"
+                )));
+            };
+            emu.reg_write(id, *value)?;
+        }
+
+        // Trace every instruction and memory write
+        let traced = registers::traced_registers(self.architecture);
+        let hook_registers = traced.clone();
+        emu.add_code_hook(1, 0, move |uc, address, size| {
+            let state = uc.get_data();
+            if state.last_instruction.is_some()
+                && state.steps.last().map(|x| x.address) == state.last_instruction
+            {
+                let _ = uc.emu_stop();
+                return;
+            }
+            let registers = hook_registers
+                .iter()
+                .map(|(name, id)| (*name, uc.reg_read(*id).unwrap_or(0)))
+                .collect();
+            uc.get_data_mut().steps.push(EmulationStep {
+                address,
+                size,
+                registers,
+                memory_writes: Vec::new(),
+            });
+        })?;
+        emu.add_mem_hook(HookType::MEM_WRITE, 1, 0, |uc, _, address, size, value| {
+            let bytes = value.to_le_bytes()[..size.min(8)].to_vec();
+            let state = uc.get_data_mut();
+            state.written.extend(address..address + size as u64);
+            if let Some(step) = state.steps.last_mut() {
+                step.memory_writes.push((address, bytes));
+            }
+            true
+        })?;
+
+        let limit = input
+            .instruction_limit
+            .unwrap_or(EmulationInput::DEFAULT_INSTRUCTION_LIMIT);
+        debug!("emulating from {:#x} until {:#x}", start, until);
+        let result = emu.emu_start(start, until, 0, limit);
+        let pc = emu.pc_read().unwrap_or(0);
+        let finished = last_instruction.is_some()
+            && emu.get_data().steps.last().map(|x| x.address) == last_instruction;
+        let stop = match result {
+            // the last instruction jumped somewhere not mapped
+            Err(_) if finished => EmulationStop::Finished,
+            Err(err) => EmulationStop::Fault(format!("{err:?} at {pc:#x}")),
+            Ok(()) if pc != until && emu.get_data().steps.len() >= limit => {
+                EmulationStop::InstructionLimit
+            }
+            Ok(()) => EmulationStop::Finished,
+        };
+
+        let registers = traced
+            .iter()
+            .map(|(name, id)| (*name, emu.reg_read(*id).unwrap_or(0)))
+            .collect();
+        let state = std::mem::take(emu.get_data_mut());
+        let mut memory = BTreeMap::new();
+        for (start, size) in runs_of(&state.written, 1) {
+            let Ok(bytes) = emu.mem_read_as_vec(start, size as usize) else {
+                continue;
+            };
+            memory.extend((start..).zip(bytes));
+        }
+        Ok(EmulationTrace::new(state.steps, registers, memory, stop))
+    }
+}
+
+/// Maps the pages of a range which are not mapped yet.
+///
+/// ### Arguments
+/// - `emu: &mut Unicorn<TraceState>` - emulator to map into
+/// - `mapped: &mut BTreeSet<u64>` - start addresses of the mapped pages
+/// - `address: u64` - start of the range
+/// - `size: u64` - size of the range
+fn map_range(
+    emu: &mut Unicorn<TraceState>,
+    mapped: &mut BTreeSet<u64>,
+    address: u64,
+    size: u64,
+) -> Result<(), FireballError> {
+    let start = address & !(PAGE_SIZE - 1);
+    let end = (address + size).next_multiple_of(PAGE_SIZE);
+    let pages: BTreeSet<u64> = (start..end)
+        .step_by(PAGE_SIZE as usize)
+        .filter(|page| !mapped.contains(page))
+        .collect();
+    for (run_start, run_size) in runs_of(&pages, PAGE_SIZE) {
+        emu.mem_map(run_start, run_size, Prot::ALL)?;
+    }
+    mapped.extend(pages);
+    Ok(())
+}
+
+/// Groups addresses spaced `step` apart into contiguous ranges.
+fn runs_of(addresses: &BTreeSet<u64>, step: u64) -> Vec<(u64, u64)> {
+    let mut result: Vec<(u64, u64)> = Vec::new();
+    for address in addresses {
+        match result.last_mut() {
+            Some((start, size)) if *start + *size == *address => *size += step,
+            _ => result.push((*address, step)),
+        }
+    }
+    result
+}
@@ -0,0 +1,137 @@
+//! Module emulating functions and blocks of the loaded binary with unicorn.
+//!
+//! The sections of the binary are mapped at their virtual addresses, the given registers,
+//! stack and memory are written, and every executed instruction is recorded with the
+//! registers it saw and the memory it wrote. Trace steps are indexed by instruction address,
+//! so they can be joined with the instructions of a `Block`, the statements of its `IrBlock`
+//! and the AST statements generated from them.
+
+mod emulator;
+mod registers;
+
+pub use emulator::Emulator;
+use std::collections::BTreeMap;
+
+/// Values an emulation starts with
+#[derive(Debug, Clone, Default)]
+pub struct EmulationInput {
+    /// Register values, by register name (`rdi`, `x0`, ...)
+    pub registers: Vec<(String, u64)>,
+    /// Bytes placed on the stack right above the return address (stack arguments)
+    pub stack: Vec<u8>,
+    /// Memory regions written before the emulation starts, mapped if not yet mapped
+    pub memory: Vec<(u64, Vec<u8>)>,
+    /// Maximum number of executed instructions, `EmulationInput::DEFAULT_INSTRUCTION_LIMIT`
+    /// if not set
+    pub instruction_limit: Option<usize>,
+}
+
+impl EmulationInput {
+    pub const DEFAULT_INSTRUCTION_LIMIT: usize = 100_000;
+
+    /// Creates an input setting the given registers.
+    ///
+    /// ### Arguments
+    /// - `registers: impl IntoIterator<Item = (&'a str, u64)>` - register names and values
+    ///
+    /// ### Returns
+    /// - `Self` - input with an empty stack and no additional memory
+    pub fn with_registers<'a>(registers: impl IntoIterator<Item = (&'a str, u64)>) -> Self {
+        Self {
+            registers: registers
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// One executed instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmulationStep {
+    /// Virtual address of the instruction
+    pub address: u64,
+    /// Size of the instruction in bytes
+    pub size: u32,
+    /// Register values before the instruction executed
+    pub registers: Vec<(&'static str, u64)>,
+    /// Memory written by the instruction, as address and little endian bytes
+    pub memory_writes: Vec<(u64, Vec<u8>)>,
+}
+
+/// Why an emulation stopped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmulationStop {
+    /// The function returned, or the block ended
+    Finished,
+    /// The instruction limit was reached
+    InstructionLimit,
+    /// Unicorn stopped with an error (unmapped memory, invalid instruction, ...)
+    Fault(String),
+}
+
+/// Result of an emulation
+#[derive(Debug, Clone)]
+pub struct EmulationTrace {
+    /// Executed instructions in execution order
+    pub steps: Vec<EmulationStep>,
+    /// Register values after the emulation stopped
+    pub registers: Vec<(&'static str, u64)>,
+    /// Final value of every byte written during the emulation, by address
+    pub memory: BTreeMap<u64, u8>,
+    /// Why the emulation stopped
+    pub stop: EmulationStop,
+    /// Indexes into `steps`, by instruction address
+    by_address: BTreeMap<u64, Vec<usize>>,
+}
+
+impl EmulationTrace {
+    pub(crate) fn new(
+        steps: Vec<EmulationStep>,
+        registers: Vec<(&'static str, u64)>,
+        memory: BTreeMap<u64, u8>,
+        stop: EmulationStop,
+    ) -> Self {
+        let mut by_address: BTreeMap<u64, Vec<usize>> = BTreeMap::new();
+        for (index, step) in steps.iter().enumerate() {
+            by_address.entry(step.address).or_default().push(index);
+        }
+        Self {
+            steps,
+            registers,
+            memory,
+            stop,
+            by_address,
+        }
+    }
+
+    /// Returns every execution of the instruction at an address.
+    ///
+    /// ### Arguments
+    /// - `address: u64` - virtual address of the instruction
+    ///
+    /// ### Returns
+    /// - `impl Iterator<Item = &EmulationStep>` - executions in execution order
+    pub fn steps_at(&self, address: u64) -> impl Iterator<Item = &EmulationStep> {
+        self.by_address
+            .get(&address)
+            .into_iter()
+            .flatten()
+            .map(|index| &self.steps[*index])
+    }
+
+    /// Returns the final value of a register.
+    ///
+    /// ### Arguments
+    /// - `name: &str` - register name, as captured in the trace
+    ///
+    /// ### Returns
+    /// - `Option<u64>` - register value, or `None` if the register is not traced
+    pub fn register(&self, name: &str) -> Option<u64> {
+        self.registers
+            .iter()
+            .find(|(x, _)| x.eq_ignore_ascii_case(name))
+            .map(|(_, value)| *value)
+    }
+}
@@ -0,0 +1,162 @@
+//! Module mapping register names to the register ids of unicorn
+
+use iceball::MachineArchitecture;
+use unicorn_engine::{RegisterARM64, RegisterX86};
+
+/// Registers captured in the trace of an x86-64 emulation
+const X64_REGISTERS: &[(&str, RegisterX86)] = &[
+    ("rax", RegisterX86::RAX),
+    ("rbx", RegisterX86::RBX),
+    ("rcx", RegisterX86::RCX),
+    ("rdx", RegisterX86::RDX),
+    ("rsi", RegisterX86::RSI),
+    ("rdi", RegisterX86::RDI),
+    ("rbp", RegisterX86::RBP),
+    ("rsp", RegisterX86::RSP),
+    ("r8", RegisterX86::R8),
+    ("r9", RegisterX86::R9),
+    ("r10", RegisterX86::R10),
+    ("r11", RegisterX86::R11),
+    ("r12", RegisterX86::R12),
+    ("r13", RegisterX86::R13),
+    ("r14", RegisterX86::R14),
+    ("r15", RegisterX86::R15),
+    ("rip", RegisterX86::RIP),
+    ("rflags", RegisterX86::RFLAGS),
+];
+
+/// Registers captured in the trace of an x86 emulation
+const X86_REGISTERS: &[(&str, RegisterX86)] = &[
+    ("eax", RegisterX86::EAX),
+    ("ebx", RegisterX86::EBX),
+    ("ecx", RegisterX86::ECX),
+    ("edx", RegisterX86::EDX),
+    ("esi", RegisterX86::ESI),
+    ("edi", RegisterX86::EDI),
+    ("ebp", RegisterX86::EBP),
+    ("esp", RegisterX86::ESP),
+    ("eip", RegisterX86::EIP),
+    ("eflags", RegisterX86::EFLAGS),
+];
+
+/// Registers captured in the trace of an AArch64 emulation
+const ARM64_REGISTERS: &[(&str, RegisterARM64)] = &[
+    ("x0", RegisterARM64::X0),
+    ("x1", RegisterARM64::X1),
+    ("x2", RegisterARM64::X2),
+    ("x3", RegisterARM64::X3),
+    ("x4", RegisterARM64::X4),
+    ("x5", RegisterARM64::X5),
+    ("x6", RegisterARM64::X6),
+    ("x7", RegisterARM64::X7),
+    ("x8", RegisterARM64::X8),
+    ("x9", RegisterARM64::X9),
+    ("x10", RegisterARM64::X10),
+    ("x11", RegisterARM64::X11),
+    ("x12", RegisterARM64::X12),
+    ("x13", RegisterARM64::X13),
+    ("x14", RegisterARM64::X14),
+    ("x15", RegisterARM64::X15),
+    ("x16", RegisterARM64::X16),
+    ("x17", RegisterARM64::X17),
+    ("x18", RegisterARM64::X18),
+    ("x19", RegisterARM64::X19),
+    ("x20", RegisterARM64::X20),
+    ("x21", RegisterARM64::X21),
+    ("x22", RegisterARM64::X22),
+    ("x23", RegisterARM64::X23),
+    ("x24", RegisterARM64::X24),
+    ("x25", RegisterARM64::X25),
+    ("x26", RegisterARM64::X26),
+    ("x27", RegisterARM64::X27),
+    ("x28", RegisterARM64::X28),
+    ("x29", RegisterARM64::X29),
+    ("x30", RegisterARM64::X30),
+    ("sp", RegisterARM64::SP),
+    ("pc", RegisterARM64::PC),
+    ("nzcv", RegisterARM64::NZCV),
+];
+
+/// Returns the registers captured in the trace of an emulation.
+///
+/// ### Arguments
+/// - `architecture: MachineArchitecture` - architecture being emulated
+///
+/// ### Returns
+/// - `Vec<(&'static str, i32)>` - name and unicorn id of each register, named like the
+///   registers of `VirtualMachine`
+pub(super) fn traced_registers(architecture: MachineArchitecture) -> Vec<(&'static str, i32)> {
+    match architecture {
+        MachineArchitecture::X64 => X64_REGISTERS
+            .iter()
+            .map(|(name, id)| (*name, i32::from(*id)))
+            .collect(),
+        MachineArchitecture::X86 => X86_REGISTERS
+            .iter()
+            .map(|(name, id)| (*name, i32::from(*id)))
+            .collect(),
+        MachineArchitecture::Arm64 => ARM64_REGISTERS
+            .iter()
+            .map(|(name, id)| (*name, i32::from(*id)))
+            .collect(),
+        MachineArchitecture::Arm => Vec::new(),
+    }
+}
+
+/// Finds the unicorn id of a register by its name.
+///
+/// ### Arguments
+/// - `architecture: MachineArchitecture` - architecture being emulated
+/// - `name: &str` - register name, case insensitive (`rax`, `x0`, `w0`, `lr`, ...)
+///
+/// ### Returns
+/// - `Option<i32>` - unicorn register id, or `None` if the register is unknown
+///
+/// ### Note
+/// - Lower halves of the AArch64 registers (`w0` ~ `w30`) write the whole `x` register, so they
+///   are resolved to it.
+pub(super) fn register_id(architecture: MachineArchitecture, name: &str) -> Option<i32> {
+    let name = name.to_ascii_lowercase();
+    let name = match (architecture, name.as_str()) {
+        (MachineArchitecture::Arm64, "lr") => "x30".to_string(),
+        (MachineArchitecture::Arm64, "fp") => "x29".to_string(),
+        (MachineArchitecture::Arm64, w) if w.starts_with('w') && w != "wsp" => {
+            format!("x{}", &w[1..])
+        }
+        (MachineArchitecture::Arm64, "wsp") => "sp".to_string(),
+        _ => name,
+    };
+    let find = |registers: &[(&str, RegisterX86)]| {
+        registers
+            .iter()
+            .find(|(x, _)| *x == name)
+            .map(|(_, id)| i32::from(*id))
+    };
+    match architecture {
+        MachineArchitecture::X64 => find(X64_REGISTERS).or_else(|| find(X86_REGISTERS)),
+        MachineArchitecture::X86 => find(X86_REGISTERS),
+        MachineArchitecture::Arm64 => ARM64_REGISTERS
+            .iter()
+            .find(|(x, _)| *x == name)
+            .map(|(_, id)| i32::from(*id)),
+        MachineArchitecture::Arm => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_aliases() {
+        let x0 = register_id(MachineArchitecture::Arm64, "x0");
+        assert!(x0.is_some());
+        assert_eq!(register_id(MachineArchitecture::Arm64, "W0"), x0);
+        assert_eq!(
+            register_id(MachineArchitecture::Arm64, "lr"),
+            register_id(MachineArchitecture::Arm64, "x30")
+        );
+        assert!(register_id(MachineArchitecture::X64, "eax").is_some());
+        assert!(register_id(MachineArchitecture::X86, "rax").is_none());
+    }
+}
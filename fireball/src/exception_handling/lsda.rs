@@ -0,0 +1,408 @@
+//! Module reading the exception handlers of `.eh_frame` based binaries (ELF, Mach-O)
+//!
+//! An FDE of a function with landing pads points to its LSDA (`.gcc_except_table`). The LSDA
+//! maps call sites to landing pads, and each landing pad to a chain of actions naming the
+//! types it catches.
+
+use super::{ExceptionHandler, ExceptionHandlerKind, ExceptionHandlers, bytes_at};
+use crate::{
+    core::{PreDefinedOffsets, Sections},
+    prelude::*,
+};
+use gimli::{BaseAddresses, CieOrFde, EhFrame, Pointer, RunTimeEndian, UnwindSection};
+use std::collections::HashMap;
+
+/// `DW_EH_PE_omit`, the value is not present
+const DW_EH_PE_OMIT: u8 = 0xFF;
+/// `DW_EH_PE_indirect`, the value is the address of the actual value
+const DW_EH_PE_INDIRECT: u8 = 0x80;
+/// `DW_EH_PE_pcrel`, the value is relative to its own address
+const DW_EH_PE_PCREL: u8 = 0x10;
+/// Actions followed from one call site at most, against cyclic chains
+const MAX_ACTIONS: usize = 0x100;
+
+/// Reads the exception handlers of the functions described in an `.eh_frame` section.
+///
+/// ### Arguments
+/// - `binary: &[u8]` - raw file data
+/// - `sections: &Sections` - section information of the file
+/// - `section_name: &str` - name of the `.eh_frame` section in the file
+/// - `endian: RunTimeEndian` - byte order of the file
+/// - `address_size: u8` - size of a pointer in bytes
+/// - `defined: &PreDefinedOffsets` - symbols naming the type information objects
+///
+/// ### Returns
+/// - `ExceptionHandlers` - handlers of `catch` blocks and cleanup landing pads
+///
+/// ### Note
+/// Only absolute and pc-relative pointer encodings are supported, which covers what GCC and
+/// Clang emit.
+pub(crate) fn load_eh_frame_exception_handlers(
+    binary: &[u8],
+    sections: &Sections,
+    section_name: &str,
+    endian: RunTimeEndian,
+    address_size: u8,
+    defined: &PreDefinedOffsets,
+) -> ExceptionHandlers {
+    let mut result = ExceptionHandlers::default();
+    let Some(section) = sections.from_name(section_name) else {
+        return result;
+    };
+    let start = section.file_offset as usize;
+    let Some(data) = binary.get(start..start + section.size_of_file as usize) else {
+        return result;
+    };
+    let names: HashMap<u64, String> = defined
+        .get_reader()
+        .iter()
+        .map(|x| (x.address.get_virtual_address(), x.name.clone()))
+        .collect();
+    let reader = LsdaReader {
+        bytes: &|address| bytes_at(binary, sections, address),
+        names: &names,
+        endian,
+        address_size,
+    };
+
+    let mut eh_frame = EhFrame::new(data, endian);
+    eh_frame.set_address_size(address_size);
+    let bases = BaseAddresses::default().set_eh_frame(section.virtual_address);
+    let mut entries = eh_frame.entries(&bases);
+    while let Ok(Some(entry)) = entries.next() {
+        let CieOrFde::Fde(partial) = entry else {
+            continue;
+        };
+        let Ok(fde) =
+            partial.parse(|section, bases, offset| section.cie_from_offset(bases, offset))
+        else {
+            continue;
+        };
+        let lsda = match fde.lsda() {
+            Some(Pointer::Direct(address)) => address,
+            Some(Pointer::Indirect(address)) => match reader.pointer(address) {
+                Some(address) => address,
+                None => continue,
+            },
+            None => continue,
+        };
+        let function = fde.initial_address();
+        if reader.read_lsda(function, lsda, &mut result).is_none() {
+            warn!("Malformed LSDA of the function at {function:#x}");
+        }
+    }
+    debug!("{} exception handlers found", result.get_all().len());
+    result
+}
+
+/// Reader of the LSDAs of a binary
+struct LsdaReader<'a> {
+    /// Returns the bytes from a virtual address to the end of its section
+    bytes: &'a dyn Fn(u64) -> Option<&'a [u8]>,
+    /// Symbol names by virtual address
+    names: &'a HashMap<u64, String>,
+    endian: RunTimeEndian,
+    address_size: u8,
+}
+
+impl<'a> LsdaReader<'a> {
+    fn cursor(&self, address: u64) -> Option<Cursor<'a>> {
+        Some(Cursor {
+            data: (self.bytes)(address)?,
+            position: 0,
+            address,
+            endian: self.endian,
+            address_size: self.address_size,
+        })
+    }
+
+    /// Reads a pointer stored at a virtual address.
+    fn pointer(&self, address: u64) -> Option<u64> {
+        self.cursor(address)?.fixed(self.address_size as usize)
+    }
+
+    /// Reads the call site table of an LSDA and inserts a handler for every landing pad.
+    ///
+    /// ### Arguments
+    /// - `function: u64` - virtual address of the function the LSDA belongs to
+    /// - `lsda: u64` - virtual address of the LSDA
+    /// - `result: &mut ExceptionHandlers` - container the handlers are inserted into
+    ///
+    /// ### Returns
+    /// - `Option<()>` - `None` if the LSDA could not be read
+    fn read_lsda(&self, function: u64, lsda: u64, result: &mut ExceptionHandlers) -> Option<()> {
+        let mut cursor = self.cursor(lsda)?;
+        let landing_pad_base = match cursor.u8()? {
+            DW_EH_PE_OMIT => function,
+            encoding => cursor.encoded(encoding)?,
+        };
+        let type_encoding = cursor.u8()?;
+        let type_table = if type_encoding == DW_EH_PE_OMIT {
+            None
+        } else {
+            let offset = cursor.uleb128()?;
+            Some(cursor.address().checked_add(offset)?)
+        };
+        let call_site_encoding = cursor.u8()?;
+        let call_site_length = cursor.uleb128()?;
+        let action_table = cursor.address().checked_add(call_site_length)?;
+
+        while cursor.address() < action_table {
+            // call site fields are offsets, their application is not used
+            let start = cursor.encoded(call_site_encoding & 0x0F)?;
+            let length = cursor.encoded(call_site_encoding & 0x0F)?;
+            let landing_pad = cursor.encoded(call_site_encoding & 0x0F)?;
+            let action = cursor.uleb128()?;
+            if landing_pad == 0 {
+                continue;
+            }
+            let kind = if action == 0 {
+                ExceptionHandlerKind::Cleanup
+            } else {
+                let types =
+                    self.read_actions(action_table + action - 1, type_encoding, type_table)?;
+                if types.is_empty() {
+                    ExceptionHandlerKind::Cleanup
+                } else {
+                    ExceptionHandlerKind::Catch(types)
+                }
+            };
+            let start = function.wrapping_add(start);
+            result.insert(ExceptionHandler {
+                start,
+                end: start.wrapping_add(length),
+                handler: landing_pad_base.wrapping_add(landing_pad),
+                kind,
+            });
+        }
+        Some(())
+    }
+
+    /// Follows a chain of action records and returns the types they catch.
+    ///
+    /// ### Returns
+    /// - `Option<Vec<Option<String>>>` - name of each caught type, `None` for `catch (...)`
+    fn read_actions(
+        &self,
+        mut action: u64,
+        type_encoding: u8,
+        type_table: Option<u64>,
+    ) -> Option<Vec<Option<String>>> {
+        let mut types = Vec::new();
+        for _ in 0..MAX_ACTIONS {
+            let mut cursor = self.cursor(action)?;
+            let filter = cursor.sleb128()?;
+            // negative filters are exception specifications, zero is a cleanup
+            if filter > 0 {
+                let size = encoded_size(type_encoding, self.address_size)?;
+                let entry = type_table?.checked_sub(filter as u64 * size)?;
+                types.push(self.read_type(entry, type_encoding)?);
+            }
+            let next = cursor.address();
+            let displacement = cursor.sleb128()?;
+            if displacement == 0 {
+                break;
+            }
+            action = next.wrapping_add(displacement as u64);
+        }
+        Some(types)
+    }
+
+    /// Reads an entry of the type table.
+    ///
+    /// ### Returns
+    /// - `Option<Option<String>>` - name of the type, `None` for `catch (...)`
+    fn read_type(&self, entry: u64, type_encoding: u8) -> Option<Option<String>> {
+        let mut cursor = self.cursor(entry)?;
+        // the entry of `catch (...)` is null before the encoding is applied
+        if cursor.clone().encoded(type_encoding & 0x0F)? == 0 {
+            return Some(None);
+        }
+        let value = cursor.encoded(type_encoding)?;
+        // an indirect entry points to a GOT slot, named after the symbol it is bound to
+        let name = self.names.get(&value).cloned().or_else(|| {
+            (type_encoding & DW_EH_PE_INDIRECT != 0)
+                .then(|| self.pointer(value))
+                .flatten()
+                .and_then(|x| self.names.get(&x).cloned())
+        });
+        Some(Some(match name {
+            Some(name) => type_info_name(&name),
+            None => format!("type_{value:x}"),
+        }))
+    }
+}
+
+/// Returns the name of the type a `typeinfo` symbol describes.
+fn type_info_name(symbol: &str) -> String {
+    symbol
+        .strip_prefix("typeinfo for ")
+        .unwrap_or(symbol)
+        .to_string()
+}
+
+/// Returns the size of a fixed-size pointer encoding.
+fn encoded_size(encoding: u8, address_size: u8) -> Option<u64> {
+    match encoding & 0x0F {
+        0x00 => Some(address_size as u64),
+        0x02 | 0x0A => Some(2),
+        0x03 | 0x0B => Some(4),
+        0x04 | 0x0C => Some(8),
+        _ => None,
+    }
+}
+
+/// Sequential reader of the bytes at a virtual address
+#[derive(Clone)]
+struct Cursor<'a> {
+    data: &'a [u8],
+    position: usize,
+    /// Virtual address of the first byte of `data`
+    address: u64,
+    endian: RunTimeEndian,
+    address_size: u8,
+}
+
+impl Cursor<'_> {
+    /// Returns the virtual address of the next byte to read.
+    fn address(&self) -> u64 {
+        self.address + self.position as u64
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        let value = *self.data.get(self.position)?;
+        self.position += 1;
+        Some(value)
+    }
+
+    fn uleb128(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    fn sleb128(&mut self) -> Option<i64> {
+        let mut value = 0i64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7F) as i64) << shift;
+            if byte & 0x80 == 0 {
+                if shift < 57 && byte & 0x40 != 0 {
+                    value |= -1 << (shift + 7);
+                }
+                return Some(value);
+            }
+        }
+        None
+    }
+
+    /// Reads an unsigned value of 2, 4 or 8 bytes.
+    fn fixed(&mut self, size: usize) -> Option<u64> {
+        let bytes = self.data.get(self.position..self.position + size)?;
+        self.position += size;
+        let value = bytes
+            .iter()
+            .enumerate()
+            .fold(0u64, |value, (index, &byte)| {
+                let shift = match self.endian {
+                    RunTimeEndian::Little => index,
+                    RunTimeEndian::Big => size - 1 - index,
+                } * 8;
+                value | (byte as u64) << shift
+            });
+        Some(value)
+    }
+
+    /// Reads a value stored with a `DW_EH_PE_*` encoding.
+    ///
+    /// ### Returns
+    /// - `Option<u64>` - the value, `None` if it could not be read or the encoding is not
+    ///   supported. An indirect value is returned as the address it is stored at.
+    fn encoded(&mut self, encoding: u8) -> Option<u64> {
+        let address = self.address();
+        let value = match encoding & 0x0F {
+            0x00 => self.fixed(self.address_size as usize)?,
+            0x01 => self.uleb128()?,
+            0x02 => self.fixed(2)?,
+            0x03 => self.fixed(4)?,
+            0x04 => self.fixed(8)?,
+            0x09 => self.sleb128()? as u64,
+            0x0A => self.fixed(2)? as i16 as u64,
+            0x0B => self.fixed(4)? as i32 as u64,
+            0x0C => self.fixed(8)?,
+            _ => return None,
+        };
+        match encoding & 0x70 {
+            0x00 => Some(value),
+            DW_EH_PE_PCREL => Some(address.wrapping_add(value)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_lsda() {
+        const LSDA: u64 = 0x2000;
+        const FUNCTION: u64 = 0x1000;
+        #[rustfmt::skip]
+        let data = [
+            // landing pad base omitted, udata4 type table ending at +0x20, uleb128 call sites
+            0xFF, 0x03, 0x1D, 0x01, 0x0C,
+            // call site 0x10..0x18 catching through the first action
+            0x10, 0x08, 0x40, 0x01,
+            // call site 0x20..0x24 with a cleanup
+            0x20, 0x04, 0x50, 0x00,
+            // call site without landing pad
+            0x30, 0x04, 0x00, 0x00,
+            // action 1: type 1, then action 2 (displacement 1 from its own position)
+            0x01, 0x01,
+            // action 2: type 2, end of chain
+            0x02, 0x00,
+            0x00, 0x00, 0x00,
+            // type 2 (catch all), type 1
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x30, 0x00, 0x00,
+        ];
+        let names = HashMap::from([(0x3000, "typeinfo for std::exception".to_string())]);
+        let bytes = |address: u64| data.get(address.checked_sub(LSDA)? as usize..);
+        let reader = LsdaReader {
+            bytes: &bytes,
+            names: &names,
+            endian: RunTimeEndian::Little,
+            address_size: 8,
+        };
+
+        let mut result = ExceptionHandlers::default();
+        assert!(reader.read_lsda(FUNCTION, LSDA, &mut result).is_some());
+        assert_eq!(
+            result.get_all(),
+            [
+                ExceptionHandler {
+                    start: 0x1010,
+                    end: 0x1018,
+                    handler: 0x1040,
+                    kind: ExceptionHandlerKind::Catch(vec![
+                        Some("std::exception".to_string()),
+                        None
+                    ]),
+                },
+                ExceptionHandler {
+                    start: 0x1020,
+                    end: 0x1024,
+                    handler: 0x1050,
+                    kind: ExceptionHandlerKind::Cleanup,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Module containing the exception handlers of the loaded binary
+//!
+//! Handlers are read from the unwind information once when the binary is loaded. Blocks inside a
+//! protected range are connected to its handler, and the range is turned into a `try` statement
+//! of the `Ast`.
+
+mod lsda;
+mod seh;
+
+pub(crate) use lsda::load_eh_frame_exception_handlers;
+pub(crate) use seh::load_pe_exception_handlers;
+
+use crate::core::{
+    Address, BlockRelationInformation, DestinationType, Instruction, RelationType, Sections,
+};
+
+/// Decision of an SEH filter on whether its `__except` block handles the exception
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExceptionFilter {
+    /// Constant result, such as `EXCEPTION_EXECUTE_HANDLER` (1)
+    Constant(i32),
+    /// Virtual address of the function evaluating the filter
+    Function(u64),
+}
+
+/// How a handler treats the exceptions raised in its protected range
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExceptionHandlerKind {
+    /// C++ `catch`, with the name of each type the handler catches. `None` catches everything.
+    Catch(Vec<Option<String>>),
+    /// SEH `__except`, entered when the filter accepts the exception
+    Except(ExceptionFilter),
+    /// SEH `__finally`, or a landing pad destroying locals before the exception moves on
+    Cleanup,
+}
+
+/// Handler of the exceptions raised in a protected range of instructions
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExceptionHandler {
+    /// Virtual address of the first protected instruction
+    pub start: u64,
+    /// Virtual address right after the last protected instruction
+    pub end: u64,
+    /// Virtual address of the code handling the exception
+    pub handler: u64,
+    pub kind: ExceptionHandlerKind,
+}
+
+/// Exception handlers of a binary, empty if it has none
+#[derive(Debug, Clone, Default)]
+pub struct ExceptionHandlers {
+    /// Handlers sorted by the start of their protected range
+    handlers: Vec<ExceptionHandler>,
+}
+
+impl ExceptionHandlers {
+    /// Returns whether no handler is known.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Returns every handler, sorted by the start of its protected range.
+    pub fn get_all(&self) -> &[ExceptionHandler] {
+        &self.handlers
+    }
+
+    pub(crate) fn insert(&mut self, handler: ExceptionHandler) {
+        if handler.start >= handler.end || self.handlers.contains(&handler) {
+            return;
+        }
+        let index = self
+            .handlers
+            .partition_point(|x| (x.start, x.end) <= (handler.start, handler.end));
+        self.handlers.insert(index, handler);
+    }
+
+    /// Returns the handlers whose protected range overlaps a range of instructions.
+    ///
+    /// ### Arguments
+    /// - `start: u64` - virtual address of the first instruction
+    /// - `end: u64` - virtual address right after the last instruction
+    ///
+    /// ### Returns
+    /// - `Vec<ExceptionHandler>` - handlers of the exceptions raised in the range
+    pub fn covering(&self, start: u64, end: u64) -> Vec<ExceptionHandler> {
+        self.handlers
+            .iter()
+            .take_while(|x| x.start < end)
+            .filter(|x| start < x.end)
+            .cloned()
+            .collect()
+    }
+
+    /// Connects a block to the handlers of the exceptions its instructions may raise.
+    ///
+    /// ### Arguments
+    /// - `sections: &Sections` - sections of the binary
+    /// - `start: &Address` - start address of the block
+    /// - `instructions: &[Instruction]` - instructions of the block
+    /// - `connected_to: &mut Vec<BlockRelationInformation>` - relations of the block, getting
+    ///   an exception relation to each handler
+    ///
+    /// ### Returns
+    /// - `Vec<ExceptionHandler>` - handlers of the exceptions raised in the block
+    pub(crate) fn connect_block(
+        &self,
+        sections: &Sections,
+        start: &Address,
+        instructions: &[Instruction],
+        connected_to: &mut Vec<BlockRelationInformation>,
+    ) -> Vec<ExceptionHandler> {
+        let end = start.get_virtual_address()
+            + instructions
+                .iter()
+                .filter_map(|x| x.inner.bytes.as_ref())
+                .map(|x| x.len() as u64)
+                .sum::<u64>();
+        let handlers = self.covering(start.get_virtual_address(), end);
+        let mut handler_addresses: Vec<_> = handlers.iter().map(|x| x.handler).collect();
+        handler_addresses.sort_unstable();
+        handler_addresses.dedup();
+        for handler in handler_addresses {
+            connected_to.push(BlockRelationInformation {
+                destination: Some(Address::from_virtual_address(sections, handler)),
+                destination_type: DestinationType::Static,
+                relation_type: RelationType::Exception,
+            });
+        }
+        handlers
+    }
+}
+
+/// Returns the bytes of the binary from a virtual address to the end of its section.
+///
+/// ### Returns
+/// - `Option<&[u8]>` - the bytes, `None` if the address is not stored in the file
+fn bytes_at<'a>(binary: &'a [u8], sections: &Sections, address: u64) -> Option<&'a [u8]> {
+    let address = Address::from_virtual_address(sections, address);
+    let section = address.get_section()?;
+    let offset = address.get_file_offset()? as usize;
+    let end = (section.file_offset + section.size_of_file) as usize;
+    binary.get(offset..end)
+}
@@ -0,0 +1,260 @@
+//! Module reading the exception handlers of x64 PE files
+//!
+//! A function with a language specific handler stores the handler's data after its
+//! `UNWIND_INFO`: a scope table for SEH (`__C_specific_handler`), or the address of a
+//! `FuncInfo` for C++ (`__CxxFrameHandler3`).
+
+use super::{ExceptionFilter, ExceptionHandler, ExceptionHandlerKind, ExceptionHandlers, bytes_at};
+use crate::{core::Sections, prelude::*};
+use goblin::pe::{
+    PE,
+    exception::{RuntimeFunction, ScopeTableIterator, UnwindHandler},
+};
+use iceball::MachineArchitecture;
+
+/// Magic numbers of the `FuncInfo` versions read by `__CxxFrameHandler3`
+const FUNC_INFO_MAGICS: std::ops::RangeInclusive<u32> = 0x1993_0520..=0x1993_0522;
+/// Size of a `TryBlockMapEntry`
+const TRY_BLOCK_SIZE: u64 = 20;
+/// Size of a `HandlerType`
+const HANDLER_TYPE_SIZE: u64 = 20;
+/// Entries read from one table at most, against corrupted counts
+const MAX_TABLE_ENTRIES: u32 = 0x1000;
+
+/// Reads the exception handlers of the functions listed in the exception directory (`.pdata`).
+///
+/// ### Arguments
+/// - `gl: &PE` - parsed PE file
+/// - `binary: &[u8]` - raw file data
+/// - `sections: &Sections` - section information of the file
+/// - `architecture: MachineArchitecture` - architecture of the file
+///
+/// ### Returns
+/// - `ExceptionHandlers` - handlers of SEH scopes and C++ `catch` blocks
+///
+/// ### Note
+/// Only x64 unwind information is read. The compressed `FuncInfo` of `__CxxFrameHandler4`
+/// is not supported.
+pub(crate) fn load_pe_exception_handlers(
+    gl: &PE,
+    binary: &[u8],
+    sections: &Sections,
+    architecture: MachineArchitecture,
+) -> ExceptionHandlers {
+    let mut result = ExceptionHandlers::default();
+    if architecture != MachineArchitecture::X64 {
+        return result;
+    }
+    let Some(exception_data) = gl.exception_data.as_ref() else {
+        return result;
+    };
+
+    for function in exception_data.functions().filter_map(Result::ok) {
+        let Ok(unwind_info) = exception_data.get_unwind_info(function, &gl.sections) else {
+            continue;
+        };
+        let Some(
+            UnwindHandler::ExceptionHandler(_, data) | UnwindHandler::TerminationHandler(_, data),
+        ) = unwind_info.handler
+        else {
+            continue;
+        };
+        if let Some(func_info) = cxx_func_info(binary, sections, data) {
+            if read_cxx_handlers(binary, sections, &function, func_info, &mut result).is_none() {
+                warn!(
+                    "Malformed FuncInfo of the function at {:#x}",
+                    function.begin_address
+                );
+            }
+        } else if let Some(scopes) = unwind_info.c_scope_table_entries() {
+            read_scope_table(scopes, &mut result);
+        }
+    }
+    debug!("{} exception handlers found", result.get_all().len());
+    result
+}
+
+/// Reads the `__try` scopes of a function handled by `__C_specific_handler`.
+fn read_scope_table(scopes: ScopeTableIterator<'_>, result: &mut ExceptionHandlers) {
+    for scope in scopes {
+        // `__finally` has no jump target, its handler is called while unwinding
+        let (handler, kind) = if scope.target == 0 {
+            (scope.handler, ExceptionHandlerKind::Cleanup)
+        } else {
+            let filter = match scope.handler as i32 {
+                // EXCEPTION_CONTINUE_EXECUTION, EXCEPTION_CONTINUE_SEARCH, EXCEPTION_EXECUTE_HANDLER
+                value @ -1..=1 => ExceptionFilter::Constant(value),
+                _ => ExceptionFilter::Function(scope.handler as u64),
+            };
+            (scope.target, ExceptionHandlerKind::Except(filter))
+        };
+        result.insert(ExceptionHandler {
+            start: scope.begin as u64,
+            end: scope.end as u64,
+            handler: handler as u64,
+            kind,
+        });
+    }
+}
+
+/// Returns the address of the `FuncInfo` the handler data refers to, if it is C++ data.
+fn cxx_func_info(binary: &[u8], sections: &Sections, data: &[u8]) -> Option<u64> {
+    let func_info = u32::from_le_bytes(data.get(..4)?.try_into().unwrap()) as u64;
+    let magic = read_u32(binary, sections, func_info)?;
+    FUNC_INFO_MAGICS.contains(&magic).then_some(func_info)
+}
+
+/// Reads the `catch` blocks of a function handled by `__CxxFrameHandler3`.
+///
+/// ### Arguments
+/// - `binary: &[u8]` - raw file data
+/// - `sections: &Sections` - section information of the file
+/// - `function: &RuntimeFunction` - function the `FuncInfo` belongs to
+/// - `func_info: u64` - address of the `FuncInfo`
+/// - `result: &mut ExceptionHandlers` - container the handlers are inserted into
+///
+/// ### Returns
+/// - `Option<()>` - `None` if a table could not be read
+///
+/// ### Note
+/// Try blocks are given as ranges of states. The protected range is made of the instructions
+/// the ip-to-state map puts in those states.
+fn read_cxx_handlers(
+    binary: &[u8],
+    sections: &Sections,
+    function: &RuntimeFunction,
+    func_info: u64,
+    result: &mut ExceptionHandlers,
+) -> Option<()> {
+    let read = |address: u64| read_u32(binary, sections, address);
+    let try_block_count = read(func_info + 12)?.min(MAX_TABLE_ENTRIES) as u64;
+    let try_block_map = read(func_info + 16)? as u64;
+    let ip_map_count = read(func_info + 20)?.min(MAX_TABLE_ENTRIES) as u64;
+    let ip_map = read(func_info + 24)? as u64;
+
+    // (start, state) of each range of instructions
+    let mut states = Vec::new();
+    for index in 0..ip_map_count {
+        let entry = ip_map + index * 8;
+        states.push((read(entry)? as u64, read(entry + 4)? as i32));
+    }
+    let ranges: Vec<_> = states
+        .iter()
+        .enumerate()
+        .map(|(index, &(start, state))| {
+            let end = states
+                .get(index + 1)
+                .map_or(function.end_address as u64, |x| x.0);
+            (start, end, state)
+        })
+        .collect();
+
+    for index in 0..try_block_count {
+        let entry = try_block_map + index * TRY_BLOCK_SIZE;
+        let states = read(entry)? as i32..=read(entry + 4)? as i32;
+        let catch_count = read(entry + 12)?.min(MAX_TABLE_ENTRIES) as u64;
+        let handler_array = read(entry + 16)? as u64;
+
+        let protected = ranges.iter().filter(|x| states.contains(&x.2));
+        let start = protected.clone().map(|x| x.0).min();
+        let end = protected.map(|x| x.1).max();
+        let (Some(start), Some(end)) = (start, end) else {
+            continue;
+        };
+        for catch in 0..catch_count {
+            let handler_type = handler_array + catch * HANDLER_TYPE_SIZE;
+            let type_descriptor = read(handler_type + 4)? as u64;
+            let handler = read(handler_type + 12)? as u64;
+            // a catch without type descriptor is `catch (...)`
+            let type_name = (type_descriptor != 0)
+                .then(|| type_descriptor_name(binary, sections, type_descriptor));
+            result.insert(ExceptionHandler {
+                start,
+                end,
+                handler,
+                kind: ExceptionHandlerKind::Catch(vec![type_name]),
+            });
+        }
+    }
+    Some(())
+}
+
+/// Returns the name of the type a `TypeDescriptor` describes.
+fn type_descriptor_name(binary: &[u8], sections: &Sections, type_descriptor: u64) -> String {
+    // the decorated name follows the vftable pointer and a reserved pointer
+    let name = bytes_at(binary, sections, type_descriptor + 16).map(|bytes| {
+        bytes
+            .iter()
+            .take(0x100)
+            .take_while(|&&x| x != 0)
+            .map(|&x| x as char)
+            .collect::<String>()
+    });
+    match name {
+        Some(name) if !name.is_empty() => undecorate_type_name(&name),
+        _ => format!("type_{type_descriptor:x}"),
+    }
+}
+
+/// Turns the decorated name of a `TypeDescriptor` into the name of the type.
+///
+/// ### Arguments
+/// - `name: &str` - decorated name (e.g., `.?AVexception@std@@`, `.H`)
+///
+/// ### Returns
+/// - `String` - name of the type (e.g., `std::exception`, `int`), or the decorated name if it
+///   is not understood
+fn undecorate_type_name(name: &str) -> String {
+    let Some(decorated) = name.strip_prefix('.') else {
+        return name.to_string();
+    };
+    // class (V), struct (U) or union (T), with its scopes from the innermost one
+    if let Some(qualified) = decorated
+        .strip_prefix("?A")
+        .and_then(|x| x.get(1..))
+        .and_then(|x| x.strip_suffix("@@"))
+    {
+        return qualified.split('@').rev().collect::<Vec<_>>().join("::");
+    }
+    let name = match decorated {
+        "C" => "signed char",
+        "D" => "char",
+        "E" => "unsigned char",
+        "F" => "short",
+        "G" => "unsigned short",
+        "H" => "int",
+        "I" => "unsigned int",
+        "J" => "long",
+        "K" => "unsigned long",
+        "M" => "float",
+        "N" => "double",
+        "O" => "long double",
+        "_J" => "__int64",
+        "_K" => "unsigned __int64",
+        "_N" => "bool",
+        "_W" => "wchar_t",
+        _ => name,
+    };
+    name.to_string()
+}
+
+fn read_u32(binary: &[u8], sections: &Sections, address: u64) -> Option<u32> {
+    let bytes = bytes_at(binary, sections, address)?.get(..4)?;
+    Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undecorate_type_name() {
+        assert_eq!(
+            undecorate_type_name(".?AVexception@std@@"),
+            "std::exception"
+        );
+        assert_eq!(undecorate_type_name(".?AUpoint@@"), "point");
+        assert_eq!(undecorate_type_name(".H"), "int");
+        assert_eq!(undecorate_type_name(".PEAD"), ".PEAD");
+    }
+}
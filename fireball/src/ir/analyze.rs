@@ -1,13 +1,19 @@
 pub mod block_grouper;
+pub mod control_flow;
 pub mod data_access;
 pub mod datatype;
 pub mod ir_function;
 pub mod ir_to_ast;
+pub mod ssa;
+pub mod stack_frame;
 pub mod variables;
 
 pub use block_grouper::{BlockGroup, BlockGrouper};
+pub use control_flow::{ControlFlowGraph, ControlFlowHandler, ControlFlowSwitch, NaturalLoop};
 pub use data_access::analyze_data_access;
 pub use datatype::{DataType, KnownDataType, analyze_datatype};
 pub use ir_function::IrFunction;
 pub use ir_to_ast::{generate_ast, generate_ast_with_pre_defined_symbols};
+pub use ssa::{SsaDefinition, SsaForm, SsaPhi, SsaUse, SsaValue};
+pub use stack_frame::{StackFrame, StackPointerState, StackSlot, StackSlotKind};
 pub use variables::{IrVariable, analyze_variables};
@@ -99,6 +99,8 @@ fn relation_type_rank(ty: RelationType) -> u8 {
         RelationType::Jcc => 3,
         RelationType::Continued => 4,
         RelationType::Return => 5,
+        RelationType::Switch => 6,
+        RelationType::Exception => 7,
     }
 }
 
@@ -113,6 +115,7 @@ pub struct BlockGroup {
 }
 
 impl BlockGroup {
+    /// Returns the blocks of the function, the block it starts at first, then by address.
     pub fn get_blocks(&self) -> &Vec<Arc<Block>> {
         &self.blocks
     }
@@ -217,12 +220,35 @@ pub fn analyze_block_groups(blocks: &[Arc<Block>], relations: &[Relation]) -> Ve
                     }
                 }
             }
+            // The function starts at a called block, or where the search started from
+            let function_start = component_ids
+                .iter()
+                .copied()
+                .filter(|id| {
+                    id_to_block
+                        .get(id)
+                        .unwrap()
+                        .get_connected_from()
+                        .iter()
+                        .any(|x| *x.relation_type() == RelationType::Call)
+                })
+                .min_by_key(|id| {
+                    id_to_block
+                        .get(id)
+                        .unwrap()
+                        .get_start_address()
+                        .get_virtual_address()
+                })
+                .unwrap_or(start_node_id);
             component_ids.sort_unstable_by_key(|id| {
-                id_to_block
-                    .get(id)
-                    .unwrap()
-                    .get_start_address()
-                    .get_virtual_address()
+                (
+                    *id != function_start,
+                    id_to_block
+                        .get(id)
+                        .unwrap()
+                        .get_start_address()
+                        .get_virtual_address(),
+                )
             });
             let component_blocks: Vec<Arc<Block>> = component_ids
                 .iter()
@@ -245,3 +271,47 @@ pub fn analyze_block_groups(blocks: &[Arc<Block>], relations: &[Relation]) -> Ve
 
     block_groups
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{BlockRelationInformation, Blocks, DestinationType, Relations, Sections};
+
+    #[test]
+    fn test_function_start_first() {
+        let sections = Sections::new();
+        let address = |x| Address::from_virtual_address(&sections, x);
+        let relation = |to, relation_type| BlockRelationInformation {
+            destination: Some(address(to)),
+            destination_type: DestinationType::Static,
+            relation_type,
+        };
+        let blocks = Blocks::new(Relations::new());
+        let generate = |start, connected_to: &[BlockRelationInformation]| {
+            blocks.generate_block(
+                address(start),
+                Some(0x10),
+                connected_to,
+                None,
+                Vec::new().into(),
+                None,
+                Vec::new(),
+            )
+        };
+        // 0x20 is called from 0x40, and jumps back and forth with the cold block at 0x10
+        let caller = generate(0x40, &[relation(0x20, RelationType::Call)]);
+        let function = generate(0x20, &[relation(0x10, RelationType::Jcc)]);
+        let cold = generate(0x10, &[relation(0x20, RelationType::Jump)]);
+
+        let mut grouper = BlockGrouper::new();
+        grouper.add_targets([cold, function, caller]);
+        let groups = grouper.analyze();
+        let group = groups.iter().find(|x| x.get_blocks().len() == 2).unwrap();
+        let starts: Vec<_> = group
+            .get_blocks()
+            .iter()
+            .map(|x| x.get_start_address().get_virtual_address())
+            .collect();
+        assert_eq!(starts, [0x20, 0x10]);
+    }
+}
@@ -0,0 +1,657 @@
+//! Module building the control flow graph of an IR function and the dominance based analyses
+//! (dominator tree, post dominator tree, natural loops) used to restore structured control flow.
+
+use crate::{
+    core::{Address, Block, RelationType},
+    exception_handling::ExceptionHandlerKind,
+    ir::data::IrData,
+    prelude::*,
+    utils::Aos,
+};
+use std::{collections::BTreeSet, ops::Range, sync::Arc};
+
+/// Kind of an edge between two blocks of a [`ControlFlowGraph`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlFlowEdgeKind {
+    /// Unconditional jump
+    Jump,
+    /// Taken when the branch condition holds
+    Taken,
+    /// Taken when the branch condition does not hold
+    NotTaken,
+    /// Execution continues into the next block (e.g. after a call returns)
+    Fallthrough,
+    /// Selected by the index of a jump table
+    Case,
+}
+
+/// Edge between two blocks of a [`ControlFlowGraph`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ControlFlowEdge {
+    /// Index of the destination block in the graph
+    pub to: usize,
+    pub kind: ControlFlowEdgeKind,
+}
+
+/// Block of a [`ControlFlowGraph`]
+#[derive(Debug, Clone)]
+pub struct ControlFlowBlock {
+    /// ID of the original block
+    block_id: usize,
+    start_address: Address,
+    /// Range of the ir(instruction) indexes of the function that belongs to this block
+    ir_range: Range<u32>,
+    successors: Vec<ControlFlowEdge>,
+    predecessors: Vec<usize>,
+    switch: Option<ControlFlowSwitch>,
+    /// Destinations of the calls made from this block, `None` for dynamic ones
+    calls: Vec<Option<Address>>,
+    /// Whether the block ends with a call that never returns
+    noreturn_call: bool,
+}
+
+/// Jump table dispatch ending a block of a [`ControlFlowGraph`]
+#[derive(Debug, Clone)]
+pub struct ControlFlowSwitch {
+    /// Data holding the index into the table
+    pub index: Aos<IrData>,
+    /// Index value and destination block of each case
+    pub cases: Vec<(u64, usize)>,
+    /// Block the bounds check of the index jumps to when the index is out of the table
+    pub default: Option<usize>,
+}
+
+impl ControlFlowBlock {
+    pub fn get_block_id(&self) -> usize {
+        self.block_id
+    }
+    pub fn get_start_address(&self) -> &Address {
+        &self.start_address
+    }
+    pub fn get_ir_range(&self) -> &Range<u32> {
+        &self.ir_range
+    }
+    pub fn get_successors(&self) -> &[ControlFlowEdge] {
+        &self.successors
+    }
+    pub fn get_predecessors(&self) -> &[usize] {
+        &self.predecessors
+    }
+    pub fn get_switch(&self) -> Option<&ControlFlowSwitch> {
+        self.switch.as_ref()
+    }
+    pub fn get_calls(&self) -> &[Option<Address>] {
+        &self.calls
+    }
+    pub fn has_noreturn_call(&self) -> bool {
+        self.noreturn_call
+    }
+}
+
+/// Handler of the exceptions raised in a protected range of a [`ControlFlowGraph`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlFlowHandler {
+    /// Virtual address of the first protected instruction
+    pub start: u64,
+    /// Virtual address right after the last protected instruction
+    pub end: u64,
+    /// Index of the block handling the exception
+    pub handler: usize,
+    pub kind: ExceptionHandlerKind,
+}
+
+/// Loop found from back edges of the dominator tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NaturalLoop {
+    /// Block every iteration starts from
+    pub header: usize,
+    /// Blocks jumping back to the header
+    pub latches: Vec<usize>,
+    /// Every block in the loop, header and latches included
+    pub nodes: BTreeSet<usize>,
+}
+
+/// Control flow graph between the blocks of an IR function
+///
+/// Blocks are stored in the order their IR appears in the function.
+#[derive(Debug, Clone, Default)]
+pub struct ControlFlowGraph {
+    blocks: Vec<ControlFlowBlock>,
+    entry: usize,
+    /// Exception handlers of the protected ranges in the function
+    handlers: Vec<ControlFlowHandler>,
+}
+
+impl ControlFlowGraph {
+    /// Builds the graph from the blocks of a function.
+    ///
+    /// ### Arguments
+    /// - `blocks: &[Arc<Block>]` - blocks of the function, in the order their IR gets merged,
+    ///   starting with the block the function starts at
+    ///
+    /// ### Returns
+    /// - `Self` - the graph. blocks without analyzed IR are skipped.
+    ///
+    /// ### Note
+    /// Calls and returns leave the function, so only the remaining relations turn into edges.
+    /// Destinations of calls are kept on the blocks making them, and exception handlers on the
+    /// graph. A call without the halt relation to the next instruction never returns.
+    pub fn new(blocks: &[Arc<Block>]) -> Self {
+        let blocks: Vec<&Arc<Block>> = blocks.iter().filter(|x| x.get_ir().is_some()).collect();
+
+        let mut result = Vec::with_capacity(blocks.len());
+        let mut ir_index = 0;
+        for block in blocks.iter() {
+            let ir_count = block.get_ir().as_ref().unwrap().ir().len() as u32;
+            result.push(ControlFlowBlock {
+                block_id: block.get_id(),
+                start_address: block.get_start_address().clone(),
+                ir_range: ir_index..ir_index + ir_count,
+                successors: Vec::new(),
+                predecessors: Vec::new(),
+                switch: None,
+                calls: Vec::new(),
+                noreturn_call: false,
+            });
+            ir_index += ir_count;
+        }
+        let find_block = |address: &Address| {
+            blocks
+                .iter()
+                .position(|x| x.get_start_address() == address)
+                .or_else(|| blocks.iter().position(|x| x.contains(address)))
+        };
+
+        for (from, block) in blocks.iter().enumerate() {
+            let relations = block.get_connected_to();
+            result[from].noreturn_call = relations
+                .iter()
+                .any(|x| *x.relation_type() == RelationType::Call)
+                && !relations
+                    .iter()
+                    .any(|x| *x.relation_type() == RelationType::Halt);
+            for relation in relations.iter() {
+                let kind = match relation.relation_type() {
+                    RelationType::Jump => ControlFlowEdgeKind::Jump,
+                    RelationType::Jcc => ControlFlowEdgeKind::Taken,
+                    RelationType::Continued => ControlFlowEdgeKind::NotTaken,
+                    RelationType::Halt => ControlFlowEdgeKind::Fallthrough,
+                    RelationType::Switch => ControlFlowEdgeKind::Case,
+                    RelationType::Call => {
+                        let to = relation.to();
+                        if !result[from].calls.contains(&to) {
+                            result[from].calls.push(to);
+                        }
+                        continue;
+                    }
+                    RelationType::Return | RelationType::Exception => continue,
+                };
+                let Some(to) = relation.to().as_ref().and_then(find_block) else {
+                    continue;
+                };
+                let edge = ControlFlowEdge { to, kind };
+                if !result[from].successors.contains(&edge) {
+                    result[from].successors.push(edge);
+                }
+            }
+            if let Some(jump_table) = block.get_jump_table() {
+                let cases: Vec<_> = jump_table
+                    .get_cases()
+                    .iter()
+                    .filter_map(|(value, address)| Some((*value, find_block(address)?)))
+                    .collect();
+                if !cases.is_empty() {
+                    result[from].switch = Some(ControlFlowSwitch {
+                        index: jump_table.get_index().clone(),
+                        cases,
+                        default: jump_table.get_default().and_then(find_block),
+                    });
+                }
+            }
+        }
+        let mut handlers = Vec::new();
+        for handler in blocks.iter().flat_map(|x| x.get_exception_handlers()) {
+            let Some(index) = blocks
+                .iter()
+                .position(|x| x.get_start_address().get_virtual_address() == handler.handler)
+            else {
+                continue;
+            };
+            let handler = ControlFlowHandler {
+                start: handler.start,
+                end: handler.end,
+                handler: index,
+                kind: handler.kind.clone(),
+            };
+            if !handlers.contains(&handler) {
+                handlers.push(handler);
+            }
+        }
+        for from in 0..result.len() {
+            // Keep taken edge first so the branch shape doesn't depend on relation order
+            result[from]
+                .successors
+                .sort_by_key(|edge| edge.kind != ControlFlowEdgeKind::Taken);
+            for edge in result[from].successors.clone() {
+                if !result[edge.to].predecessors.contains(&from) {
+                    result[edge.to].predecessors.push(from);
+                }
+            }
+        }
+
+        // The function starts at its first block, even when a loop jumps back to it
+        let entry = 0;
+        debug!(
+            "Control flow graph generated with {} blocks (entry: {})",
+            result.len(),
+            entry
+        );
+        Self {
+            blocks: result,
+            entry,
+            handlers,
+        }
+    }
+
+    /// Builds the graph from raw edges.
+    ///
+    /// ### Arguments
+    /// - `count: usize` - amount of blocks
+    /// - `entry: usize` - index of the entry block
+    /// - `edges: &[(usize, usize, ControlFlowEdgeKind)]` - (from, to, kind) of each edge
+    #[cfg(test)]
+    pub(crate) fn from_edges(
+        count: usize,
+        entry: usize,
+        edges: &[(usize, usize, ControlFlowEdgeKind)],
+    ) -> Self {
+        let mut blocks: Vec<_> = (0..count)
+            .map(|i| ControlFlowBlock {
+                block_id: i,
+                start_address: Address::from_virtual_address(
+                    &crate::core::Sections::new(),
+                    i as u64,
+                ),
+                ir_range: i as u32..i as u32 + 1,
+                successors: Vec::new(),
+                predecessors: Vec::new(),
+                switch: None,
+                calls: Vec::new(),
+                noreturn_call: false,
+            })
+            .collect();
+        for &(from, to, kind) in edges {
+            blocks[from].successors.push(ControlFlowEdge { to, kind });
+            blocks[to].predecessors.push(from);
+        }
+        Self {
+            blocks,
+            entry,
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Sets the jump table the block dispatches through.
+    #[cfg(test)]
+    pub(crate) fn set_switch(&mut self, block: usize, switch: ControlFlowSwitch) {
+        self.blocks[block].switch = Some(switch);
+    }
+
+    pub fn get_blocks(&self) -> &[ControlFlowBlock] {
+        &self.blocks
+    }
+    pub fn get_entry(&self) -> usize {
+        self.entry
+    }
+    pub fn get_handlers(&self) -> &[ControlFlowHandler] {
+        &self.handlers
+    }
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    /// Finds the block containing the ir index.
+    ///
+    /// ### Arguments
+    /// - `ir_index: u32` - index of the ir(instruction) in the function
+    ///
+    /// ### Returns
+    /// - `Option<usize>` - index of the block
+    pub fn block_of_ir_index(&self, ir_index: u32) -> Option<usize> {
+        self.blocks
+            .iter()
+            .position(|x| x.ir_range.contains(&ir_index))
+    }
+
+    /// Returns blocks reachable from the entry, in reverse post order.
+    pub fn reverse_post_order(&self) -> Vec<usize> {
+        if self.blocks.is_empty() {
+            return Vec::new();
+        }
+        let successors: Vec<Vec<usize>> = self
+            .blocks
+            .iter()
+            .map(|x| x.successors.iter().map(|edge| edge.to).collect())
+            .collect();
+        reverse_post_order(self.entry, &successors)
+    }
+
+    /// Computes the immediate dominator of every block.
+    ///
+    /// ### Returns
+    /// - `Vec<Option<usize>>` - immediate dominator of each block.
+    ///   `None` for the entry and for blocks unreachable from it.
+    pub fn immediate_dominators(&self) -> Vec<Option<usize>> {
+        if self.blocks.is_empty() {
+            return Vec::new();
+        }
+        let successors: Vec<Vec<usize>> = self
+            .blocks
+            .iter()
+            .map(|x| x.successors.iter().map(|edge| edge.to).collect())
+            .collect();
+        let predecessors: Vec<Vec<usize>> =
+            self.blocks.iter().map(|x| x.predecessors.clone()).collect();
+        immediate_dominators(self.entry, &successors, &predecessors)
+    }
+
+    /// Computes the immediate post dominator of every block.
+    ///
+    /// ### Returns
+    /// - `Vec<Option<usize>>` - immediate post dominator of each block.
+    ///   `None` when the block is only post dominated by the function exit, or never leaves a loop.
+    pub fn immediate_post_dominators(&self) -> Vec<Option<usize>> {
+        let count = self.blocks.len();
+        if count == 0 {
+            return Vec::new();
+        }
+        // Reverse the graph, with a virtual exit node connected to every exiting block.
+        // Calls that never return end their path without leaving the function, unless nothing
+        // else does.
+        let exit = count;
+        let returns = self
+            .blocks
+            .iter()
+            .any(|x| x.successors.is_empty() && !x.noreturn_call);
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); count + 1];
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count + 1];
+        for (from, block) in self.blocks.iter().enumerate() {
+            for edge in block.successors.iter() {
+                if !successors[edge.to].contains(&from) {
+                    successors[edge.to].push(from);
+                    predecessors[from].push(edge.to);
+                }
+            }
+            if block.successors.is_empty() && !(returns && block.noreturn_call) {
+                successors[exit].push(from);
+                predecessors[from].push(exit);
+            }
+        }
+        let mut result = immediate_dominators(exit, &successors, &predecessors);
+        result.truncate(count);
+        result
+            .into_iter()
+            .map(|x| x.filter(|x| *x != exit))
+            .collect()
+    }
+
+    /// Computes the dominance frontier of every block.
+    ///
+    /// ### Arguments
+    /// - `idom: &[Option<usize>]` - result of [`Self::immediate_dominators`]
+    ///
+    /// ### Returns
+    /// - `Vec<BTreeSet<usize>>` - blocks where the dominance of each block ends.
+    ///   Empty for blocks unreachable from the entry.
+    pub fn dominance_frontiers(&self, idom: &[Option<usize>]) -> Vec<BTreeSet<usize>> {
+        let reachable = |block: usize| block == self.entry || idom[block].is_some();
+        let mut result = vec![BTreeSet::new(); self.blocks.len()];
+        for (block, x) in self.blocks.iter().enumerate() {
+            if !reachable(block) {
+                continue;
+            }
+            for &pred in x.predecessors.iter().filter(|x| reachable(**x)) {
+                let mut runner = Some(pred);
+                while let Some(now) = runner
+                    && runner != idom[block]
+                {
+                    result[now].insert(block);
+                    runner = idom[now];
+                }
+            }
+        }
+        result
+    }
+
+    /// Finds natural loops. Back edges sharing a header are merged into one loop.
+    ///
+    /// ### Arguments
+    /// - `idom: &[Option<usize>]` - result of [`Self::immediate_dominators`]
+    ///
+    /// ### Returns
+    /// - `Vec<NaturalLoop>` - loops, outer loops first
+    pub fn natural_loops(&self, idom: &[Option<usize>]) -> Vec<NaturalLoop> {
+        let mut result: Vec<NaturalLoop> = Vec::new();
+        for latch in self.reverse_post_order() {
+            for edge in self.blocks[latch].successors.iter() {
+                let header = edge.to;
+                if !dominates(idom, header, latch) {
+                    continue;
+                }
+                let index = match result.iter().position(|x| x.header == header) {
+                    Some(index) => index,
+                    None => {
+                        result.push(NaturalLoop {
+                            header,
+                            latches: Vec::new(),
+                            nodes: BTreeSet::from([header]),
+                        });
+                        result.len() - 1
+                    }
+                };
+                let natural_loop = &mut result[index];
+                if !natural_loop.latches.contains(&latch) {
+                    natural_loop.latches.push(latch);
+                }
+                let mut stack = vec![latch];
+                while let Some(node) = stack.pop() {
+                    if natural_loop.nodes.insert(node) {
+                        stack.extend(
+                            self.blocks[node]
+                                .predecessors
+                                .iter()
+                                .filter(|x| dominates(idom, header, **x)),
+                        );
+                    }
+                }
+            }
+        }
+        result.sort_by_key(|x| std::cmp::Reverse(x.nodes.len()));
+        result
+    }
+}
+
+/// Returns true if `a` dominates `b`.
+///
+/// ### Arguments
+/// - `idom: &[Option<usize>]` - immediate dominators
+/// - `a: usize` - dominator candidate
+/// - `b: usize` - dominated candidate
+pub fn dominates(idom: &[Option<usize>], a: usize, b: usize) -> bool {
+    let mut now = Some(b);
+    while let Some(node) = now {
+        if node == a {
+            return true;
+        }
+        now = idom[node];
+    }
+    false
+}
+
+fn reverse_post_order(root: usize, successors: &[Vec<usize>]) -> Vec<usize> {
+    let mut visited = vec![false; successors.len()];
+    let mut post_order = Vec::with_capacity(successors.len());
+    let mut stack = vec![(root, 0usize)];
+    visited[root] = true;
+    while let Some((node, next)) = stack.last_mut() {
+        if let Some(&to) = successors[*node].get(*next) {
+            *next += 1;
+            if !visited[to] {
+                visited[to] = true;
+                stack.push((to, 0));
+            }
+        } else {
+            post_order.push(*node);
+            stack.pop();
+        }
+    }
+    post_order.reverse();
+    post_order
+}
+
+/// "A Simple, Fast Dominance Algorithm" (Cooper, Harvey, Kennedy)
+fn immediate_dominators(
+    root: usize,
+    successors: &[Vec<usize>],
+    predecessors: &[Vec<usize>],
+) -> Vec<Option<usize>> {
+    let rpo = reverse_post_order(root, successors);
+    let mut order = vec![usize::MAX; successors.len()];
+    for (index, node) in rpo.iter().enumerate() {
+        order[*node] = index;
+    }
+
+    let mut idom: Vec<Option<usize>> = vec![None; successors.len()];
+    idom[root] = Some(root);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &node in rpo.iter().skip(1) {
+            let mut new_idom: Option<usize> = None;
+            for &pred in predecessors[node].iter() {
+                if idom[pred].is_none() {
+                    continue;
+                }
+                new_idom = Some(match new_idom {
+                    None => pred,
+                    Some(mut other) => {
+                        let mut pred = pred;
+                        while pred != other {
+                            while order[pred] > order[other] {
+                                pred = idom[pred].unwrap();
+                            }
+                            while order[other] > order[pred] {
+                                other = idom[other].unwrap();
+                            }
+                        }
+                        pred
+                    }
+                });
+            }
+            if new_idom != idom[node] {
+                idom[node] = new_idom;
+                changed = true;
+            }
+        }
+    }
+    idom[root] = None;
+    idom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ControlFlowEdgeKind::*, *};
+
+    /// 0 -> 1 -> 2 -> 1, 1 -> 3
+    fn while_loop() -> ControlFlowGraph {
+        ControlFlowGraph::from_edges(
+            4,
+            0,
+            &[(0, 1, Jump), (1, 3, Taken), (1, 2, NotTaken), (2, 1, Jump)],
+        )
+    }
+
+    #[test]
+    fn test_dominators() {
+        let cfg = while_loop();
+        assert_eq!(
+            cfg.immediate_dominators(),
+            vec![None, Some(0), Some(1), Some(1)]
+        );
+        assert_eq!(
+            cfg.immediate_post_dominators(),
+            vec![Some(1), Some(3), Some(1), None]
+        );
+    }
+
+    #[test]
+    fn test_noreturn_call_post_dominators() {
+        // 0 -> 1 (abort), 0 -> 2 -> 3
+        let mut cfg =
+            ControlFlowGraph::from_edges(4, 0, &[(0, 1, Taken), (0, 2, NotTaken), (2, 3, Jump)]);
+        assert_eq!(
+            cfg.immediate_post_dominators(),
+            vec![None, None, Some(3), None]
+        );
+        cfg.blocks[1].noreturn_call = true;
+        assert_eq!(
+            cfg.immediate_post_dominators(),
+            vec![Some(2), None, Some(3), None]
+        );
+    }
+
+    #[test]
+    fn test_natural_loops() {
+        let cfg = while_loop();
+        let loops = cfg.natural_loops(&cfg.immediate_dominators());
+        assert_eq!(
+            loops,
+            vec![NaturalLoop {
+                header: 1,
+                latches: vec![2],
+                nodes: BTreeSet::from([1, 2]),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_dominance_frontiers() {
+        let cfg = while_loop();
+        assert_eq!(
+            cfg.dominance_frontiers(&cfg.immediate_dominators()),
+            vec![
+                BTreeSet::new(),
+                BTreeSet::from([1]),
+                BTreeSet::from([1]),
+                BTreeSet::new()
+            ]
+        );
+
+        // 0 branches into 1 and 2, joining at 3
+        let cfg = ControlFlowGraph::from_edges(
+            4,
+            0,
+            &[(0, 1, Taken), (0, 2, NotTaken), (1, 3, Jump), (2, 3, Jump)],
+        );
+        assert_eq!(
+            cfg.dominance_frontiers(&cfg.immediate_dominators()),
+            vec![
+                BTreeSet::new(),
+                BTreeSet::from([3]),
+                BTreeSet::from([3]),
+                BTreeSet::new()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_irreducible_loop_is_not_natural() {
+        // 0 branches into both 1 and 2, which jump into each other
+        let cfg = ControlFlowGraph::from_edges(
+            3,
+            0,
+            &[(0, 1, Taken), (0, 2, NotTaken), (1, 2, Jump), (2, 1, Jump)],
+        );
+        assert!(cfg.natural_loops(&cfg.immediate_dominators()).is_empty());
+    }
+}
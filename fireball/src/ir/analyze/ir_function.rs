@@ -1,8 +1,15 @@
 use crate::{
     core::{Block, Instruction},
-    ir::{Ir, IrBlock, analyze::DataType, data::IrDataAccess, utils::IrStatementDescriptorMap},
+    ir::{
+        Ir, IrBlock,
+        analyze::{DataType, IrVariable, SsaForm, control_flow::ControlFlowGraph},
+        data::{IrData, IrDataAccess, IrDataAccessType},
+        utils::{IrStatementDescriptor, IrStatementDescriptorMap},
+    },
     prelude::*,
+    utils::Aos,
 };
+use hashbrown::HashMap;
 use std::sync::Arc;
 
 pub fn generate_ir_function(blocks: &[Arc<Block>]) -> IrFunction {
@@ -33,29 +40,117 @@ pub fn generate_ir_function(blocks: &[Arc<Block>]) -> IrFunction {
         .analyze_variables()
         .expect("Variable analysis failed");
 
-    // Collect merged variables
+    let control_flow = ControlFlowGraph::new(blocks);
+
+    // Split variables by the live ranges of the merged function
     let vars = ir_block.variables.unwrap();
-    let merged_vars = vars
-        .into_iter()
-        .map(|v| IrFunctionVariable {
-            data_type: v.data_type,
-            data_accesses: v.into_data_accesses(),
-        })
-        .collect();
+    let ssa = SsaForm::new(&control_flow, &collect_data_accesses(&vars));
+    let merged_vars = split_variables(vars, &ssa);
 
     info!("IrFunction generation completed");
     IrFunction {
         instructions,
         ir: combined_ir,
         variables: merged_vars,
+        control_flow,
+        ssa,
     }
 }
 
+/// Collects the data accesses of every variable, each access once.
+fn collect_data_accesses(variables: &[IrVariable]) -> IrStatementDescriptorMap<Vec<IrDataAccess>> {
+    let mut result = IrStatementDescriptorMap::new();
+    for variable in variables.iter() {
+        for (position, accesses) in variable.get_data_accesses().iter() {
+            result.insert_checked(position, Vec::new());
+            let now = result.get_mut(position).unwrap();
+            for access in accesses.iter() {
+                if !now.contains(access) {
+                    now.push(access.clone());
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Splits variables so each live range of the SSA form becomes one variable.
+///
+/// ### Arguments
+/// - `variables: Vec<IrVariable>` - variables of the linear analysis
+/// - `ssa: &SsaForm` - SSA form built from the accesses of the variables
+///
+/// ### Returns
+/// - `Vec<IrFunctionVariable>` - variables ordered by their first access. Locations never
+///   written keep one variable per location.
+fn split_variables(variables: Vec<IrVariable>, ssa: &SsaForm) -> Vec<IrFunctionVariable> {
+    #[derive(PartialEq, Eq, Hash)]
+    enum Key {
+        Web(usize),
+        Location(Aos<IrData>),
+    }
+    let mut web_of_value = vec![0; ssa.get_values().len()];
+    for (web, values) in ssa.webs().into_iter().enumerate() {
+        for value in values {
+            web_of_value[value] = web;
+        }
+    }
+
+    let variable_count = variables.len();
+    let mut accesses: Vec<(IrStatementDescriptor, IrDataAccess, DataType)> = Vec::new();
+    for variable in variables {
+        let data_type = variable.data_type;
+        for (position, x) in variable.into_data_accesses().iter() {
+            accesses.extend(x.iter().map(|access| (position, access.clone(), data_type)));
+        }
+    }
+    accesses.sort_by_key(|(position, _, _)| position.to_u64());
+
+    let mut keys: HashMap<Key, usize> = HashMap::new();
+    let mut result: Vec<IrFunctionVariable> = Vec::new();
+    for (position, access, data_type) in accesses {
+        let value = match access.access_type() {
+            IrDataAccessType::Read => ssa.value_read(position, access.location()),
+            IrDataAccessType::Write => ssa.value_written(position, access.location()),
+        };
+        let key = match value {
+            Some(value) => Key::Web(web_of_value[value]),
+            None => Key::Location(access.location().clone()),
+        };
+        let index = *keys.entry(key).or_insert_with(|| {
+            result.push(IrFunctionVariable {
+                data_accesses: IrStatementDescriptorMap::new(),
+                data_type: DataType::Unknown,
+            });
+            result.len() - 1
+        });
+        let variable = &mut result[index];
+        if variable.data_type == DataType::Unknown {
+            variable.data_type = data_type;
+        }
+        variable.data_accesses.insert_checked(position, Vec::new());
+        let now = variable.data_accesses.get_mut(position).unwrap();
+        if !now.contains(&access) {
+            now.push(access);
+        }
+    }
+    debug!(
+        "{} variables split into {} variables",
+        variable_count,
+        result.len()
+    );
+    result
+}
+
 #[derive(Debug, Clone)]
 pub struct IrFunction {
     instructions: Arc<[Instruction]>,
     ir: Vec<Ir>,
     variables: Vec<IrFunctionVariable>,
+    /// Relations between the blocks the function is made of
+    control_flow: ControlFlowGraph,
+    /// Values of the locations written in the function
+    ssa: SsaForm,
 }
 
 impl IrFunction {
@@ -68,6 +163,8 @@ impl IrFunction {
             instructions,
             ir,
             variables,
+            control_flow: ControlFlowGraph::default(),
+            ssa: SsaForm::default(),
         }
     }
     pub fn get_ir(&self) -> &Vec<Ir> {
@@ -79,10 +176,33 @@ impl IrFunction {
     pub fn get_variables(&self) -> &Vec<IrFunctionVariable> {
         &self.variables
     }
+    pub fn get_control_flow(&self) -> &ControlFlowGraph {
+        &self.control_flow
+    }
+    pub fn get_ssa(&self) -> &SsaForm {
+        &self.ssa
+    }
 
     pub fn get_entry_address(&self) -> Option<u64> {
         self.ir.first().map(|ir| ir.address.get_virtual_address())
     }
+
+    /// Returns the bytes the function pops off the stack when returning (`ret imm16`).
+    pub fn get_callee_cleanup(&self) -> Option<u64> {
+        self.instructions.iter().find_map(|instruction| {
+            let inner = &instruction.inner;
+            if !matches!(
+                inner.statement,
+                Ok(iceball::Statement::X64(iceball::X64Statement::Ret))
+            ) {
+                return None;
+            }
+            match inner.arguments.first() {
+                Some(iceball::Argument::Constant(x)) => Some(*x),
+                _ => None,
+            }
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
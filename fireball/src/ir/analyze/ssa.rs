@@ -0,0 +1,494 @@
+//! Module building the static single assignment form of an IR function.
+//!
+//! Every write of a location defines a new value, and phi nodes merge the values reaching the
+//! blocks where control flow joins. Locations are compared as a whole, the same way the
+//! variable analysis does, so overlapping registers and aliasing memory are separate locations.
+
+use crate::{
+    ir::{
+        analyze::ControlFlowGraph,
+        data::{IrData, IrDataAccess, IrDataAccessType},
+        utils::{IrStatementDescriptor, IrStatementDescriptorMap},
+    },
+    prelude::*,
+    utils::Aos,
+};
+use hashbrown::HashMap;
+use std::{collections::BTreeSet, ops::Range};
+
+/// Where a [`SsaValue`] gets defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SsaDefinition {
+    /// Held by the location when the function is entered
+    Entry,
+    /// Written by a statement
+    Statement(IrStatementDescriptor),
+    /// Result of the phi node with the index
+    Phi(usize),
+}
+
+/// Where a [`SsaValue`] gets read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SsaUse {
+    /// Read by a statement
+    Statement(IrStatementDescriptor),
+    /// Operand of the phi node with the index
+    Phi(usize),
+}
+
+/// Value defined exactly once
+#[derive(Debug, Clone)]
+pub struct SsaValue {
+    /// Operand resolved location holding the value
+    pub location: Aos<IrData>,
+    pub definition: SsaDefinition,
+    /// Every use of the value, in the order they were found
+    pub uses: Vec<SsaUse>,
+}
+
+/// Merge of the values of a location reaching a join block
+#[derive(Debug, Clone)]
+pub struct SsaPhi {
+    /// Index of the block in the control flow graph
+    pub block: usize,
+    pub location: Aos<IrData>,
+    /// Value defined by the phi node
+    pub value: usize,
+    /// (predecessor block, value coming from it) of each incoming edge
+    pub operands: Vec<(usize, usize)>,
+}
+
+/// Static single assignment form of an IR function
+#[derive(Debug, Clone, Default)]
+pub struct SsaForm {
+    values: Vec<SsaValue>,
+    phis: Vec<SsaPhi>,
+    location_ids: HashMap<Aos<IrData>, usize>,
+    /// Value read by each statement, keyed by statement and location id
+    reads: HashMap<(u64, usize), usize>,
+    /// Value written by each statement, keyed by statement and location id
+    writes: HashMap<(u64, usize), usize>,
+}
+
+struct Builder<'a> {
+    form: SsaForm,
+    /// Location of each location id
+    locations: Vec<Aos<IrData>>,
+    /// Statements of each ir index with their data accesses, in statement order
+    statements: Vec<Vec<(IrStatementDescriptor, &'a [IrDataAccess])>>,
+    /// Location ids that are written somewhere in the function
+    written: Vec<bool>,
+    /// Reaching values of each location
+    stacks: Vec<Vec<usize>>,
+    /// Entry value of each location
+    entries: Vec<Option<usize>>,
+}
+
+impl SsaForm {
+    /// Builds the SSA form of a function.
+    ///
+    /// ### Arguments
+    /// - `cfg: &ControlFlowGraph` - control flow graph of the function
+    /// - `accesses: &IrStatementDescriptorMap<Vec<IrDataAccess>>` - operand resolved data
+    ///   accesses of every statement of the function
+    ///
+    /// ### Returns
+    /// - `Self` - the SSA form. Only locations written somewhere in the function get values.
+    ///
+    /// ### Note
+    /// Reads of a statement happen before its writes. Instructions outside of every block
+    /// are renamed in order, as if they were one block unreachable from the entry.
+    pub fn new(
+        cfg: &ControlFlowGraph,
+        accesses: &IrStatementDescriptorMap<Vec<IrDataAccess>>,
+    ) -> Self {
+        let mut form = SsaForm::default();
+        let mut keys = accesses.keys();
+        keys.sort_unstable_by_key(|x| x.to_u64());
+        let ir_count = keys.last().map(|x| x.ir_index() as usize + 1).unwrap_or(0);
+        let mut statements = vec![Vec::new(); ir_count];
+        let mut written = Vec::new();
+        let mut read = Vec::new();
+        for key in keys {
+            let accesses = accesses.get(key).unwrap().as_slice();
+            for access in accesses {
+                let next = form.location_ids.len();
+                let id = *form
+                    .location_ids
+                    .entry(access.location().clone())
+                    .or_insert(next);
+                if id == next {
+                    written.push(false);
+                    read.push(false);
+                }
+                match access.access_type() {
+                    IrDataAccessType::Write => written[id] = true,
+                    IrDataAccessType::Read => read[id] = true,
+                }
+            }
+            statements[key.ir_index() as usize].push((key, accesses));
+        }
+        let mut locations = vec![None; form.location_ids.len()];
+        for (location, id) in form.location_ids.iter() {
+            locations[*id] = Some(location.clone());
+        }
+        let locations: Vec<Aos<IrData>> = locations.into_iter().map(Option::unwrap).collect();
+
+        /* Insert phi nodes at the iterated dominance frontiers of the writes */
+        let blocks = cfg.get_blocks();
+        let idom = cfg.immediate_dominators();
+        let frontiers = cfg.dominance_frontiers(&idom);
+        let mut definitions: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); locations.len()];
+        for (block, x) in blocks.iter().enumerate() {
+            for ir_index in x.get_ir_range().clone() {
+                let Some(statements) = statements.get(ir_index as usize) else {
+                    break;
+                };
+                for access in statements.iter().flat_map(|(_, x)| x.iter()) {
+                    if *access.access_type() == IrDataAccessType::Write {
+                        definitions[form.location_ids[access.location()]].insert(block);
+                    }
+                }
+            }
+        }
+        let mut phis_of_block: Vec<Vec<usize>> = vec![Vec::new(); blocks.len()];
+        for (location, definitions) in definitions.iter().enumerate() {
+            // A location never read has no value to merge
+            if !read[location] {
+                continue;
+            }
+            let mut has_phi = BTreeSet::new();
+            let mut worklist: Vec<usize> = definitions.iter().copied().collect();
+            while let Some(block) = worklist.pop() {
+                for &frontier in frontiers[block].iter() {
+                    if !has_phi.insert(frontier) {
+                        continue;
+                    }
+                    let phi = form.phis.len();
+                    let value = form.values.len();
+                    form.values.push(SsaValue {
+                        location: locations[location].clone(),
+                        definition: SsaDefinition::Phi(phi),
+                        uses: Vec::new(),
+                    });
+                    form.phis.push(SsaPhi {
+                        block: frontier,
+                        location: locations[location].clone(),
+                        value,
+                        operands: Vec::new(),
+                    });
+                    phis_of_block[frontier].push(phi);
+                    if !definitions.contains(&frontier) {
+                        worklist.push(frontier);
+                    }
+                }
+            }
+        }
+
+        /* Rename along the dominator tree */
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); blocks.len()];
+        let mut roots = Vec::new();
+        for (block, idom) in idom.iter().enumerate() {
+            match idom {
+                Some(idom) => children[*idom].push(block),
+                None if block == cfg.get_entry() => roots.insert(0, block),
+                None => roots.push(block),
+            }
+        }
+        let location_count = locations.len();
+        let mut builder = Builder {
+            form,
+            locations,
+            statements,
+            written,
+            stacks: vec![Vec::new(); location_count],
+            entries: vec![None; location_count],
+        };
+        enum Visit {
+            Enter(usize),
+            Leave(Vec<usize>),
+        }
+        let mut work: Vec<Visit> = roots.into_iter().rev().map(Visit::Enter).collect();
+        while let Some(visit) = work.pop() {
+            let block = match visit {
+                Visit::Enter(block) => block,
+                Visit::Leave(defined) => {
+                    for location in defined {
+                        builder.stacks[location].pop();
+                    }
+                    continue;
+                }
+            };
+            let mut defined = Vec::new();
+            for &phi in phis_of_block[block].iter() {
+                let phi = &builder.form.phis[phi];
+                let location = builder.form.location_ids[&phi.location];
+                builder.stacks[location].push(phi.value);
+                defined.push(location);
+            }
+            builder.rename(blocks[block].get_ir_range().clone(), &mut defined);
+            for edge in blocks[block].get_successors() {
+                for &phi in phis_of_block[edge.to].iter() {
+                    if builder.form.phis[phi].operands.iter().any(|x| x.0 == block) {
+                        continue;
+                    }
+                    let location = builder.form.location_ids[&builder.form.phis[phi].location];
+                    let value = builder.current(location);
+                    builder.form.phis[phi].operands.push((block, value));
+                    builder.form.values[value].uses.push(SsaUse::Phi(phi));
+                }
+            }
+            work.push(Visit::Leave(defined));
+            work.extend(children[block].iter().rev().map(|x| Visit::Enter(*x)));
+        }
+
+        /* Instructions outside of every block, renamed ones are already taken */
+        builder.rename(0..ir_count as u32, &mut Vec::new());
+
+        let form = builder.form;
+        debug!(
+            "SSA form generated with {} values and {} phi nodes",
+            form.values.len(),
+            form.phis.len()
+        );
+        form
+    }
+
+    pub fn get_values(&self) -> &[SsaValue] {
+        &self.values
+    }
+    pub fn get_phis(&self) -> &[SsaPhi] {
+        &self.phis
+    }
+
+    /// Finds the value a statement reads from a location.
+    ///
+    /// ### Arguments
+    /// - `position: IrStatementDescriptor` - the statement
+    /// - `location: &Aos<IrData>` - operand resolved location
+    ///
+    /// ### Returns
+    /// - `Option<usize>` - index of the value, `None` if the location is never written
+    pub fn value_read(
+        &self,
+        position: IrStatementDescriptor,
+        location: &Aos<IrData>,
+    ) -> Option<usize> {
+        let location = *self.location_ids.get(location)?;
+        self.reads.get(&(position.to_u64(), location)).copied()
+    }
+
+    /// Finds the value a statement writes to a location.
+    ///
+    /// ### Arguments
+    /// - `position: IrStatementDescriptor` - the statement
+    /// - `location: &Aos<IrData>` - operand resolved location
+    ///
+    /// ### Returns
+    /// - `Option<usize>` - index of the value
+    pub fn value_written(
+        &self,
+        position: IrStatementDescriptor,
+        location: &Aos<IrData>,
+    ) -> Option<usize> {
+        let location = *self.location_ids.get(location)?;
+        self.writes.get(&(position.to_u64(), location)).copied()
+    }
+
+    /// Groups values into webs, values of a web share one live range.
+    ///
+    /// ### Returns
+    /// - `Vec<Vec<usize>>` - values of each web, ordered by their first value
+    ///
+    /// ### Note
+    /// A phi node shares the web of its operands. Values read and written by the same
+    /// instruction also share a web, since statements of an instruction become one statement.
+    pub fn webs(&self) -> Vec<Vec<usize>> {
+        let mut parent: Vec<usize> = (0..self.values.len()).collect();
+        fn find(parent: &mut [usize], mut value: usize) -> usize {
+            while parent[value] != value {
+                parent[value] = parent[parent[value]];
+                value = parent[value];
+            }
+            value
+        }
+        let mut union = |a: usize, b: usize| {
+            let a = find(&mut parent, a);
+            let b = find(&mut parent, b);
+            if a != b {
+                parent[a.max(b)] = a.min(b);
+            }
+        };
+
+        for phi in self.phis.iter() {
+            for &(_, operand) in phi.operands.iter() {
+                union(phi.value, operand);
+            }
+        }
+        let mut instruction_values: HashMap<(u32, usize), usize> = HashMap::new();
+        for (&(position, location), &value) in self.reads.iter().chain(self.writes.iter()) {
+            let ir_index = IrStatementDescriptor::from_u64(position).ir_index();
+            match instruction_values.get(&(ir_index, location)) {
+                Some(&other) => union(other, value),
+                None => {
+                    instruction_values.insert((ir_index, location), value);
+                }
+            }
+        }
+
+        let mut web_of_root: HashMap<usize, usize> = HashMap::new();
+        let mut result: Vec<Vec<usize>> = Vec::new();
+        for value in 0..self.values.len() {
+            let root = find(&mut parent, value);
+            let web = *web_of_root.entry(root).or_insert_with(|| {
+                result.push(Vec::new());
+                result.len() - 1
+            });
+            result[web].push(value);
+        }
+        result
+    }
+}
+
+impl Builder<'_> {
+    /// Value of the location reaching the current statement
+    fn current(&mut self, location: usize) -> usize {
+        if let Some(value) = self.stacks[location].last() {
+            return *value;
+        }
+        if let Some(value) = self.entries[location] {
+            return value;
+        }
+        let value = self.new_value(location, SsaDefinition::Entry);
+        self.entries[location] = Some(value);
+        value
+    }
+
+    fn new_value(&mut self, location: usize, definition: SsaDefinition) -> usize {
+        self.form.values.push(SsaValue {
+            location: self.locations[location].clone(),
+            definition,
+            uses: Vec::new(),
+        });
+        self.form.values.len() - 1
+    }
+
+    /// Renames the accesses of the instructions in the range.
+    ///
+    /// ### Arguments
+    /// - `ir_range: Range<u32>` - instructions to rename
+    /// - `defined: &mut Vec<usize>` - locations whose stacks got pushed, once per push
+    ///
+    /// ### Note
+    /// Statements of the instructions are taken, so each instruction is renamed once.
+    fn rename(&mut self, ir_range: Range<u32>, defined: &mut Vec<usize>) {
+        for ir_index in ir_range {
+            let Some(statements) = self.statements.get_mut(ir_index as usize) else {
+                return;
+            };
+            for (position, accesses) in std::mem::take(statements) {
+                let key = position.to_u64();
+                for access in accesses
+                    .iter()
+                    .filter(|x| *x.access_type() == IrDataAccessType::Read)
+                {
+                    let location = self.form.location_ids[access.location()];
+                    if !self.written[location] || self.form.reads.contains_key(&(key, location)) {
+                        continue;
+                    }
+                    let value = self.current(location);
+                    self.form.reads.insert((key, location), value);
+                    self.form.values[value]
+                        .uses
+                        .push(SsaUse::Statement(position));
+                }
+                for access in accesses
+                    .iter()
+                    .filter(|x| *x.access_type() == IrDataAccessType::Write)
+                {
+                    let location = self.form.location_ids[access.location()];
+                    if self.form.writes.contains_key(&(key, location)) {
+                        continue;
+                    }
+                    let value = self.new_value(location, SsaDefinition::Statement(position));
+                    self.form.writes.insert((key, location), value);
+                    self.stacks[location].push(value);
+                    defined.push(location);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{
+        VirtualMachine, analyze::control_flow::ControlFlowEdgeKind::*, data::IrAccessSize,
+        x86_64::X64Range,
+    };
+
+    /// One access of rax by each ir index
+    fn accesses(types: &[IrDataAccessType]) -> IrStatementDescriptorMap<Vec<IrDataAccess>> {
+        let rax: Aos<IrData> = (&<VirtualMachine as X64Range>::rax()).into();
+        let mut result = IrStatementDescriptorMap::new();
+        for (ir_index, access_type) in types.iter().enumerate() {
+            result.insert(
+                IrStatementDescriptor::new(ir_index as u32, 0),
+                vec![IrDataAccess::new(
+                    rax.clone(),
+                    *access_type,
+                    IrAccessSize::ArchitectureSize,
+                )],
+            );
+        }
+        result
+    }
+
+    #[test]
+    fn test_phi_at_join() {
+        use IrDataAccessType::*;
+        // 0 branches into 1 and 2, joining at 3
+        let cfg = ControlFlowGraph::from_edges(
+            4,
+            0,
+            &[(0, 1, Taken), (0, 2, NotTaken), (1, 3, Jump), (2, 3, Jump)],
+        );
+        let ssa = SsaForm::new(&cfg, &accesses(&[Write, Write, Read, Read]));
+        let rax: Aos<IrData> = (&<VirtualMachine as X64Range>::rax()).into();
+        let at = |ir_index| IrStatementDescriptor::new(ir_index, 0);
+
+        let first = ssa.value_written(at(0), &rax).unwrap();
+        let second = ssa.value_written(at(1), &rax).unwrap();
+        assert_eq!(ssa.value_read(at(2), &rax), Some(first));
+
+        assert_eq!(ssa.get_phis().len(), 1);
+        let phi = &ssa.get_phis()[0];
+        assert_eq!(phi.block, 3);
+        let mut operands = phi.operands.clone();
+        operands.sort();
+        assert_eq!(operands, vec![(1, second), (2, first)]);
+        assert_eq!(ssa.value_read(at(3), &rax), Some(phi.value));
+        assert_eq!(
+            ssa.get_values()[first].uses,
+            vec![SsaUse::Statement(at(2)), SsaUse::Phi(0)]
+        );
+        assert_eq!(ssa.webs().len(), 1);
+    }
+
+    #[test]
+    fn test_webs_split_live_ranges() {
+        use IrDataAccessType::*;
+        let cfg = ControlFlowGraph::from_edges(4, 0, &[(0, 1, Jump), (1, 2, Jump), (2, 3, Jump)]);
+        let ssa = SsaForm::new(&cfg, &accesses(&[Write, Read, Write, Read]));
+        let rax: Aos<IrData> = (&<VirtualMachine as X64Range>::rax()).into();
+        let at = |ir_index| IrStatementDescriptor::new(ir_index, 0);
+
+        assert!(ssa.get_phis().is_empty());
+        let first = ssa.value_written(at(0), &rax).unwrap();
+        let second = ssa.value_written(at(2), &rax).unwrap();
+        assert_eq!(ssa.value_read(at(1), &rax), Some(first));
+        assert_eq!(ssa.value_read(at(3), &rax), Some(second));
+        assert_eq!(ssa.webs(), vec![vec![first], vec![second]]);
+    }
+}
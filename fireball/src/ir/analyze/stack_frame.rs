@@ -0,0 +1,729 @@
+//! Module tracking the stack pointer through an IR function and partitioning its stack frame
+//! into slots.
+//!
+//! Offsets are relative to the stack pointer at the function entry. Negative offsets are local
+//! variables of the function, offsets above the return address are arguments of the caller.
+
+use crate::{
+    ir::{
+        analyze::{
+            IrFunction, variables::resolve_ir_operand_of_access_size, variables::resolve_operand,
+        },
+        data::{IrAccessSize, IrData, IrDataOperation, IrIntrinsic},
+        operator::{IrBinaryOperator, IrUnaryOperator},
+        statements::IrStatement,
+        utils::{IrStatementDescriptor, IrStatementDescriptorMap},
+    },
+    prelude::*,
+};
+use iceball::MachineArchitecture;
+use std::collections::BTreeMap;
+
+/// Offsets of the stack and frame pointers from the stack pointer at the function entry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StackPointerState {
+    /// `None` when the stack pointer can't be tracked
+    pub sp: Option<isize>,
+    /// `None` before the frame pointer is set up, or when it can't be tracked
+    pub bp: Option<isize>,
+}
+
+/// Kind of a [`StackSlot`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackSlotKind {
+    /// Local variable
+    Local,
+    /// Local array, accessed by index or through its address
+    Array { element_size: usize },
+    /// Argument passed on the stack
+    Argument,
+    /// Register argument the function stores to its home on the stack
+    SpilledArgument,
+}
+
+/// Part of the stack frame holding one variable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackSlot {
+    /// Offset from the stack pointer at the function entry
+    pub offset: isize,
+    /// Size in bytes
+    pub size: usize,
+    pub kind: StackSlotKind,
+}
+
+impl StackSlot {
+    /// Name of the variable held by the slot (`local_28`, `arg_8`)
+    pub fn name(&self) -> String {
+        match self.kind {
+            StackSlotKind::Local | StackSlotKind::Array { .. } => {
+                format!("local_{:x}", self.offset.unsigned_abs())
+            }
+            StackSlotKind::Argument | StackSlotKind::SpilledArgument => {
+                format!("arg_{:x}", self.offset)
+            }
+        }
+    }
+    pub fn contains(&self, offset: isize) -> bool {
+        self.offset <= offset && offset < self.offset + self.size as isize
+    }
+}
+
+/// Stack pointer offsets and stack slots of an IR function
+#[derive(Debug, Clone)]
+pub struct StackFrame {
+    /// State before each statement
+    states: IrStatementDescriptorMap<StackPointerState>,
+    /// State before each instruction
+    instruction_states: Vec<StackPointerState>,
+    /// Slots ordered by offset
+    slots: Vec<StackSlot>,
+    /// Size of addresses and of the stack pointer, in bytes
+    address_size: isize,
+}
+
+/// Access of the stack frame found while tracking
+struct FrameAccess {
+    offset: isize,
+    /// Accessed bytes, if known
+    size: Option<usize>,
+    write: bool,
+    /// Only the address of the slot is taken
+    address_taken: bool,
+    /// Size of the elements, if the slot is accessed by a variable index
+    element_size: Option<usize>,
+    /// A register is pushed or popped, which bounds the slots around it
+    saved: bool,
+}
+
+impl StackFrame {
+    /// Tracks the stack pointer through the function and partitions its frame.
+    ///
+    /// ### Arguments
+    /// - `function: &IrFunction` - the function
+    /// - `architecture: MachineArchitecture` - architecture of the binary, sizing the pushed
+    ///   values and return addresses
+    /// - `callee_cleanup: impl Fn(u64) -> Option<u64>` - bytes the function at the address pops
+    ///   off the stack when returning (stdcall)
+    ///
+    /// ### Returns
+    /// - `Self` - the frame. Blocks unreachable from the entry have unknown stack pointers.
+    ///
+    /// ### Note
+    /// - Pushes, pops and constant additions change the stack pointer. Writing anything else to
+    ///   it makes it unknown.
+    /// - A call leaves the stack pointer where it was before the call instruction, moved by the
+    ///   cleanup of the callee.
+    pub fn new(
+        function: &IrFunction,
+        architecture: MachineArchitecture,
+        callee_cleanup: impl Fn(u64) -> Option<u64>,
+    ) -> Self {
+        let ir = function.get_ir();
+        let address_size = match architecture {
+            MachineArchitecture::X64 | MachineArchitecture::Arm64 => 8,
+            MachineArchitecture::X86 | MachineArchitecture::Arm => 4,
+        };
+        let mut frame = Self {
+            states: IrStatementDescriptorMap::new(),
+            instruction_states: vec![StackPointerState::default(); ir.len()],
+            slots: Vec::new(),
+            address_size,
+        };
+        let mut accesses = Vec::new();
+        let mut track_range = |frame: &mut Self, range: std::ops::Range<u32>, state| {
+            let mut state = state;
+            for ir_index in range {
+                state = frame.track_instruction(
+                    function,
+                    ir_index,
+                    state,
+                    &callee_cleanup,
+                    &mut accesses,
+                );
+            }
+            state
+        };
+        let entry = StackPointerState {
+            sp: Some(0),
+            bp: None,
+        };
+
+        let cfg = function.get_control_flow();
+        if cfg.is_empty() {
+            track_range(&mut frame, 0..ir.len() as u32, entry);
+        } else {
+            let blocks = cfg.get_blocks();
+            let mut entry_states: Vec<Option<StackPointerState>> = vec![None; blocks.len()];
+            entry_states[cfg.get_entry()] = Some(entry);
+            let mut visited = vec![false; blocks.len()];
+            let order = cfg.reverse_post_order();
+            let unreachable: Vec<usize> =
+                (0..blocks.len()).filter(|x| !order.contains(x)).collect();
+            for block in order.into_iter().chain(unreachable) {
+                visited[block] = true;
+                let state = entry_states[block].unwrap_or_default();
+                let state = track_range(&mut frame, blocks[block].get_ir_range().clone(), state);
+                for edge in blocks[block].get_successors() {
+                    if !visited[edge.to] {
+                        entry_states[edge.to].get_or_insert(state);
+                    }
+                }
+            }
+        }
+
+        // calls push the return address on x86, AArch64 keeps it in the link register
+        let return_address_size = match architecture {
+            MachineArchitecture::X86 | MachineArchitecture::X64 => address_size,
+            MachineArchitecture::Arm | MachineArchitecture::Arm64 => 0,
+        };
+        frame.slots = partition(&accesses, return_address_size, address_size);
+        debug!("Stack frame analyzed with {} slots", frame.slots.len());
+        frame
+    }
+
+    pub fn get_slots(&self) -> &[StackSlot] {
+        &self.slots
+    }
+
+    /// Returns the stack and frame pointer offsets before the statement.
+    pub fn state_at(&self, position: IrStatementDescriptor) -> StackPointerState {
+        self.states.get(position).copied().unwrap_or_default()
+    }
+
+    /// Returns the stack and frame pointer offsets before the instruction.
+    pub fn instruction_state(&self, ir_index: u32) -> StackPointerState {
+        self.instruction_states
+            .get(ir_index as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Finds the slot holding the offset.
+    ///
+    /// ### Returns
+    /// - `Option<usize>` - index of the slot in [`Self::get_slots`]
+    pub fn slot_of(&self, offset: isize) -> Option<usize> {
+        let index = self
+            .slots
+            .partition_point(|x| x.offset <= offset)
+            .checked_sub(1)?;
+        self.slots[index].contains(offset).then_some(index)
+    }
+
+    /// Resolves an address into the stack frame.
+    ///
+    /// ### Arguments
+    /// - `address: &IrData` - operand resolved address (e.g. `rbp - 0x28`)
+    /// - `state: StackPointerState` - stack and frame pointers where the address is used
+    ///
+    /// ### Returns
+    /// - `Option<(isize, Option<usize>)>` - offset from the stack pointer at the entry, and the
+    ///   element size if a variable index is added to it
+    pub fn frame_address(
+        &self,
+        address: &IrData,
+        state: StackPointerState,
+    ) -> Option<(isize, Option<usize>)> {
+        frame_address(address, state, self.address_size)
+    }
+
+    fn track_instruction(
+        &mut self,
+        function: &IrFunction,
+        ir_index: u32,
+        state: StackPointerState,
+        callee_cleanup: &impl Fn(u64) -> Option<u64>,
+        accesses: &mut Vec<FrameAccess>,
+    ) -> StackPointerState {
+        self.instruction_states[ir_index as usize] = state;
+        let Some(statements) = function.get_ir()[ir_index as usize].statements.as_ref() else {
+            return state;
+        };
+        let arguments = &function.get_instructions()[ir_index as usize]
+            .inner
+            .arguments;
+        let mut tracker = Tracker {
+            address_size: self.address_size,
+            arguments,
+            callee_cleanup,
+            start: state,
+            accesses,
+        };
+        let mut state = state;
+        for (statement_index, statement) in statements.iter().enumerate() {
+            let position = IrStatementDescriptor::new(ir_index, statement_index as u8);
+            self.states.insert(position, state);
+            state = tracker.statement(statement, state);
+        }
+        state
+    }
+}
+
+struct Tracker<'a, F: Fn(u64) -> Option<u64>> {
+    address_size: isize,
+    arguments: &'a [iceball::Argument],
+    callee_cleanup: &'a F,
+    /// State before the instruction
+    start: StackPointerState,
+    accesses: &'a mut Vec<FrameAccess>,
+}
+
+impl<F: Fn(u64) -> Option<u64>> Tracker<'_, F> {
+    fn statement(
+        &mut self,
+        statement: &IrStatement,
+        state: StackPointerState,
+    ) -> StackPointerState {
+        let mut state = state;
+        match statement {
+            IrStatement::Assignment { from, to, size } => {
+                let from = resolve_operand(from, self.arguments);
+                let to = resolve_operand(to, self.arguments);
+                if let IrData::Register(register) = to.as_ref()
+                    && (register.is_sp() || register.is_bp())
+                {
+                    // restored from the stack (`pop rbp`), or moved by a variable amount
+                    let value = frame_address(&from, state, self.address_size)
+                        .filter(|(_, element_size)| element_size.is_none())
+                        .map(|(offset, _)| offset);
+                    if register.is_sp() {
+                        state.sp = value;
+                    } else {
+                        state.bp = value;
+                    }
+                    return state;
+                }
+                let size = resolve_ir_operand_of_access_size(size, self.arguments);
+                let address_size = self.address_size;
+                let to_size = access_size(&size, &to, &from, address_size);
+                let from_size = access_size(&size, &from, &to, address_size);
+                self.record(&to, true, to_size, state);
+                if let Some((offset, element_size)) = frame_address(&from, state, self.address_size)
+                    && !matches!(from.as_ref(), IrData::Register(_))
+                {
+                    self.accesses.push(FrameAccess {
+                        offset,
+                        size: None,
+                        write: false,
+                        address_taken: true,
+                        element_size,
+                        saved: false,
+                    });
+                } else {
+                    self.record(&from, false, from_size, state);
+                }
+            }
+            IrStatement::Jump { target } => {
+                let target = resolve_operand(target, self.arguments);
+                self.record(&target, false, None, state);
+            }
+            IrStatement::JumpByCall { target } => {
+                let target = resolve_operand(target, self.arguments);
+                self.record(&target, false, None, state);
+                // the callee returns past what the call pushed, popping its stack arguments
+                let cleanup = target
+                    .constant()
+                    .and_then(|address| (self.callee_cleanup)(address as u64))
+                    .unwrap_or(0);
+                state.sp = self.start.sp.map(|sp| sp + cleanup as isize);
+            }
+            IrStatement::Condition {
+                condition,
+                true_branch,
+                false_branch,
+            } => {
+                let condition = resolve_operand(condition, self.arguments);
+                self.record(&condition, false, None, state);
+                let mut state_true = state;
+                for statement in true_branch.iter() {
+                    state_true = self.statement(statement, state_true);
+                }
+                let mut state_false = state;
+                for statement in false_branch.iter() {
+                    state_false = self.statement(statement, state_false);
+                }
+                state.sp = state_true.sp.filter(|_| state_true.sp == state_false.sp);
+                state.bp = state_true.bp.filter(|_| state_true.bp == state_false.bp);
+            }
+            IrStatement::Undefined
+            | IrStatement::Exception(_)
+            | IrStatement::Halt
+            | IrStatement::Special(_) => {}
+        }
+        state
+    }
+
+    /// Records the accesses of the stack frame made by reading or writing the data.
+    fn record(
+        &mut self,
+        data: &IrData,
+        write: bool,
+        size: Option<usize>,
+        state: StackPointerState,
+    ) {
+        match data {
+            // pushes and pops save registers, not variables
+            IrData::Dereference(address) if address.is_sp() => {
+                if let Some(offset) = state.sp {
+                    self.accesses.push(FrameAccess {
+                        offset,
+                        size,
+                        write,
+                        address_taken: false,
+                        element_size: None,
+                        saved: true,
+                    });
+                }
+            }
+            IrData::Dereference(address) => {
+                if let Some((offset, element_size)) =
+                    frame_address(address, state, self.address_size)
+                {
+                    self.accesses.push(FrameAccess {
+                        offset,
+                        size,
+                        write,
+                        address_taken: false,
+                        element_size,
+                        saved: false,
+                    });
+                }
+                self.record(address, false, None, state);
+            }
+            IrData::Operation(IrDataOperation::Unary { arg, .. }) => {
+                self.record(arg, false, None, state)
+            }
+            IrData::Operation(IrDataOperation::Binary { arg1, arg2, .. }) => {
+                self.record(arg1, false, None, state);
+                self.record(arg2, false, None, state);
+            }
+            IrData::Intrinsic(IrIntrinsic::Sized(data, _)) => self.record(data, write, size, state),
+            IrData::Intrinsic(_)
+            | IrData::Constant(_)
+            | IrData::Register(_)
+            | IrData::Operand(_) => {}
+        }
+    }
+}
+
+/// Resolves an address into the stack frame, see [`StackFrame::frame_address`].
+fn frame_address(
+    address: &IrData,
+    state: StackPointerState,
+    address_size: isize,
+) -> Option<(isize, Option<usize>)> {
+    let constant = |data: &IrData| constant(data, address_size);
+    match address {
+        IrData::Register(register) if register.is_sp() => Some((state.sp?, None)),
+        IrData::Register(register) if register.is_bp() => Some((state.bp?, None)),
+        // `mov rbp, rsp` extends the copied value to the destination
+        IrData::Operation(IrDataOperation::Unary {
+            operator: IrUnaryOperator::SignExtend | IrUnaryOperator::ZeroExtend,
+            arg,
+        }) => frame_address(arg, state, address_size),
+        IrData::Operation(IrDataOperation::Binary {
+            operator: IrBinaryOperator::Add,
+            arg1,
+            arg2,
+        }) => {
+            if let Some(value) = constant(arg2) {
+                let (offset, element_size) = frame_address(arg1, state, address_size)?;
+                return Some((offset + value, element_size));
+            }
+            if let Some(value) = constant(arg1) {
+                let (offset, element_size) = frame_address(arg2, state, address_size)?;
+                return Some((offset + value, element_size));
+            }
+            if let Some((offset, _)) = frame_address(arg1, state, address_size) {
+                return Some((offset, Some(element_size(arg2, address_size))));
+            }
+            let (offset, _) = frame_address(arg2, state, address_size)?;
+            Some((offset, Some(element_size(arg1, address_size))))
+        }
+        IrData::Operation(IrDataOperation::Binary {
+            operator: IrBinaryOperator::Sub,
+            arg1,
+            arg2,
+        }) => {
+            let (offset, element_size) = frame_address(arg1, state, address_size)?;
+            Some((offset - constant(arg2)?, element_size))
+        }
+        _ => None,
+    }
+}
+
+/// Evaluates data made of constants.
+fn constant(data: &IrData, address_size: isize) -> Option<isize> {
+    let constant = |data: &IrData| constant(data, address_size);
+    match data {
+        IrData::Constant(value) => Some(*value as isize),
+        IrData::Intrinsic(IrIntrinsic::ArchitectureByteSize) => Some(address_size),
+        IrData::Operation(IrDataOperation::Unary { operator, arg }) => match operator {
+            IrUnaryOperator::Negation => Some(-constant(arg)?),
+            IrUnaryOperator::SignExtend | IrUnaryOperator::ZeroExtend => constant(arg),
+            IrUnaryOperator::Not => None,
+        },
+        IrData::Operation(IrDataOperation::Binary {
+            operator,
+            arg1,
+            arg2,
+        }) => {
+            let (a, b) = (constant(arg1)?, constant(arg2)?);
+            match operator {
+                IrBinaryOperator::Add => Some(a + b),
+                IrBinaryOperator::Sub => Some(a - b),
+                IrBinaryOperator::Mul => Some(a * b),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Size of an element indexed by the data (`rax * 4` indexes 4 byte elements)
+fn element_size(index: &IrData, address_size: isize) -> usize {
+    let IrData::Operation(IrDataOperation::Binary {
+        operator,
+        arg1,
+        arg2,
+    }) = index
+    else {
+        return 1;
+    };
+    let scale = constant(arg2, address_size).or_else(|| constant(arg1, address_size));
+    match (operator, scale) {
+        (IrBinaryOperator::Mul, Some(scale)) if scale > 0 => scale as usize,
+        (IrBinaryOperator::Shl, Some(shift)) if (0..8).contains(&shift) => 1 << shift,
+        _ => 1,
+    }
+}
+
+/// Returns the bytes accessed by an assignment between `data` and `other`.
+fn access_size(
+    size: &IrAccessSize,
+    data: &IrData,
+    other: &IrData,
+    address_size: isize,
+) -> Option<usize> {
+    let register_size = |data: &IrData| match data {
+        IrData::Register(register) => Some(register.bit_len() / 8),
+        _ => None,
+    };
+    match size {
+        IrAccessSize::ResultOfByte(x) => x.constant(),
+        IrAccessSize::ResultOfBit(x) => x.constant().map(|x| x / 8),
+        IrAccessSize::ArchitectureSize => Some(address_size as usize),
+        IrAccessSize::RelativeWith(x) => register_size(x)
+            .or_else(|| register_size(data))
+            .or_else(|| register_size(other)),
+        IrAccessSize::Unlimited => None,
+    }
+    .filter(|x| *x != 0)
+}
+
+/// Partitions the frame into slots, one starting at each accessed offset.
+///
+/// ### Arguments
+/// - `accesses: &[FrameAccess]` - accesses in the order they were found
+/// - `return_address_size: isize` - bytes of the return address at the entry stack pointer
+/// - `address_size: isize` - size of addresses, used for the slots of unknown size
+fn partition(
+    accesses: &[FrameAccess],
+    return_address_size: isize,
+    address_size: isize,
+) -> Vec<StackSlot> {
+    struct Usage {
+        size: Option<usize>,
+        first_write: bool,
+        address_taken: bool,
+        element_size: Option<usize>,
+        saved: bool,
+    }
+    let mut usages: BTreeMap<isize, Usage> = BTreeMap::new();
+    for access in accesses.iter() {
+        if (0..return_address_size).contains(&access.offset) {
+            continue;
+        }
+        let usage = usages.entry(access.offset).or_insert(Usage {
+            size: None,
+            first_write: access.write,
+            address_taken: false,
+            element_size: None,
+            saved: true,
+        });
+        usage.saved &= access.saved;
+        usage.size = usage.size.max(access.size);
+        usage.address_taken |= access.address_taken;
+        usage.element_size = usage.element_size.or(access.element_size);
+    }
+
+    let offsets: Vec<isize> = usages.keys().copied().collect();
+    let mut result = Vec::with_capacity(offsets.len());
+    for (i, (offset, usage)) in usages.into_iter().enumerate() {
+        if usage.saved {
+            continue;
+        }
+        let next = offsets.get(i + 1).copied();
+        // locals end at the return address
+        let end = if offset < 0 {
+            Some(next.map_or(0, |next| next.min(0)))
+        } else {
+            next
+        };
+        let gap = end.map(|end| (end - offset) as usize);
+        let accessed = usage.size.or(usage.element_size);
+        let kind = if offset >= return_address_size {
+            if usage.first_write {
+                StackSlotKind::SpilledArgument
+            } else {
+                StackSlotKind::Argument
+            }
+        } else if usage.element_size.is_some()
+            || (usage.address_taken
+                && gap.is_some_and(|gap| gap > accessed.unwrap_or(address_size as usize)))
+        {
+            StackSlotKind::Array {
+                element_size: accessed.unwrap_or(1),
+            }
+        } else {
+            StackSlotKind::Local
+        };
+        let size = match kind {
+            StackSlotKind::Array { element_size } => gap.unwrap_or(element_size),
+            _ => {
+                let size = accessed.unwrap_or(address_size as usize);
+                gap.map_or(size, |gap| size.min(gap))
+            }
+        };
+        result.push(StackSlot { offset, size, kind });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn access(offset: isize, size: usize, write: bool) -> FrameAccess {
+        FrameAccess {
+            offset,
+            size: Some(size),
+            write,
+            address_taken: false,
+            element_size: None,
+            saved: false,
+        }
+    }
+
+    #[test]
+    fn test_partition() {
+        let accesses = [
+            // spilled rcx, then a stack argument
+            access(0x8, 8, true),
+            access(0x28, 4, false),
+            access(-0xc, 4, true),
+            access(-0x18, 8, false),
+            FrameAccess {
+                offset: -0x40,
+                size: None,
+                write: false,
+                address_taken: true,
+                element_size: None,
+                saved: false,
+            },
+            // the return address
+            access(0, 8, false),
+        ];
+        let slots = partition(&accesses, 8, 8);
+        assert_eq!(
+            slots,
+            vec![
+                StackSlot {
+                    offset: -0x40,
+                    size: 0x28,
+                    kind: StackSlotKind::Array { element_size: 1 },
+                },
+                StackSlot {
+                    offset: -0x18,
+                    size: 8,
+                    kind: StackSlotKind::Local,
+                },
+                StackSlot {
+                    offset: -0xc,
+                    size: 4,
+                    kind: StackSlotKind::Local,
+                },
+                StackSlot {
+                    offset: 0x8,
+                    size: 8,
+                    kind: StackSlotKind::SpilledArgument,
+                },
+                StackSlot {
+                    offset: 0x28,
+                    size: 4,
+                    kind: StackSlotKind::Argument,
+                },
+            ]
+        );
+        assert_eq!(slots[0].name(), "local_40");
+        assert_eq!(slots[3].name(), "arg_8");
+    }
+
+    #[test]
+    fn test_frame_address() {
+        use crate::ir::{VirtualMachine, x86_64::X64Range};
+        let frame = StackFrame {
+            states: IrStatementDescriptorMap::new(),
+            instruction_states: Vec::new(),
+            slots: Vec::new(),
+            address_size: 8,
+        };
+        let rbp = IrData::Register(<VirtualMachine as X64Range>::rbp());
+        let rax = IrData::Register(<VirtualMachine as X64Range>::rax());
+        let state = StackPointerState {
+            sp: Some(-0x30),
+            bp: Some(-0x8),
+        };
+        let binary = |operator, arg1: &IrData, arg2: &IrData| {
+            IrData::Operation(IrDataOperation::Binary {
+                operator,
+                arg1: arg1.clone().into(),
+                arg2: arg2.clone().into(),
+            })
+        };
+
+        let local = binary(IrBinaryOperator::Sub, &rbp, &IrData::Constant(0x28));
+        assert_eq!(frame.frame_address(&local, state), Some((-0x30, None)));
+        let negative = binary(
+            IrBinaryOperator::Add,
+            &rbp,
+            &IrData::Operation(IrDataOperation::Unary {
+                operator: IrUnaryOperator::Negation,
+                arg: IrData::Constant(0x10).into(),
+            }),
+        );
+        assert_eq!(frame.frame_address(&negative, state), Some((-0x18, None)));
+        let index = binary(IrBinaryOperator::Mul, &rax, &IrData::Constant(4));
+        let indexed = binary(
+            IrBinaryOperator::Sub,
+            &binary(IrBinaryOperator::Add, &rbp, &index),
+            &IrData::Constant(0x40),
+        );
+        assert_eq!(frame.frame_address(&indexed, state), Some((-0x48, Some(4))));
+        assert_eq!(frame.frame_address(&rax, state), None);
+
+        // pushed values take the size of the binary's addresses
+        let pushed = binary(
+            IrBinaryOperator::Sub,
+            &IrData::Register(<VirtualMachine as X64Range>::rsp()),
+            &IrData::Intrinsic(IrIntrinsic::ArchitectureByteSize),
+        );
+        assert_eq!(frame.frame_address(&pushed, state), Some((-0x38, None)));
+        assert_eq!(frame_address(&pushed, state, 4), Some((-0x34, None)));
+        let copied = IrData::Operation(IrDataOperation::Unary {
+            operator: IrUnaryOperator::ZeroExtend,
+            arg: rbp.clone().into(),
+        });
+        assert_eq!(frame.frame_address(&copied, state), Some((-0x8, None)));
+    }
+}
@@ -20,7 +20,7 @@ use std::{
 
 mod private {
     use super::*;
-    #[derive(Clone, PartialEq, Eq)]
+    #[derive(Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
     pub struct IrVariable {
         pub live_in: Option<u32>,
         pub shown_in: Vec<u32>,
@@ -0,0 +1,14 @@
+use crate::ir::{VirtualMachine, arm::ARM};
+use std::cell::UnsafeCell;
+
+impl ARM for VirtualMachine {
+    #[inline(always)]
+    fn new() -> Self {
+        let mut register = bitvec::prelude::BitVec::new();
+        register.resize(104 * 64, false);
+        Self {
+            register: UnsafeCell::new(register.into_boxed_bitslice()),
+            memory: Default::default(),
+        }
+    }
+}
@@ -1,3 +1,364 @@
+//! Submodule for ARM/AArch64 architectures.
+
+mod base;
+mod range;
+
+use crate::ir::Register;
+
+macro_rules! generate_range {
+    ($name:ident) => {
+        fn $name() -> Register;
+    };
+}
+
+/// Trait for ARM CPU registers.
 pub trait ARM {
     fn new() -> Self;
 }
+
+/// Bit ranges of AArch64 registers inside the [`crate::ir::VirtualMachine`].
+///
+/// ### Note
+/// - `wN` aliases the low 32 bits of `xN`, `qN`/`dN`/`sN`/`hN`/`bN` alias the low bits of `vN`.
+/// - `xzr`/`wzr` have their own slot so they never alias `sp`.
+pub(crate) trait ArmRange {
+    generate_range!(x0);
+    generate_range!(w0);
+
+    generate_range!(x1);
+    generate_range!(w1);
+
+    generate_range!(x2);
+    generate_range!(w2);
+
+    generate_range!(x3);
+    generate_range!(w3);
+
+    generate_range!(x4);
+    generate_range!(w4);
+
+    generate_range!(x5);
+    generate_range!(w5);
+
+    generate_range!(x6);
+    generate_range!(w6);
+
+    generate_range!(x7);
+    generate_range!(w7);
+
+    generate_range!(x8);
+    generate_range!(w8);
+
+    generate_range!(x9);
+    generate_range!(w9);
+
+    generate_range!(x10);
+    generate_range!(w10);
+
+    generate_range!(x11);
+    generate_range!(w11);
+
+    generate_range!(x12);
+    generate_range!(w12);
+
+    generate_range!(x13);
+    generate_range!(w13);
+
+    generate_range!(x14);
+    generate_range!(w14);
+
+    generate_range!(x15);
+    generate_range!(w15);
+
+    generate_range!(x16);
+    generate_range!(w16);
+
+    generate_range!(x17);
+    generate_range!(w17);
+
+    generate_range!(x18);
+    generate_range!(w18);
+
+    generate_range!(x19);
+    generate_range!(w19);
+
+    generate_range!(x20);
+    generate_range!(w20);
+
+    generate_range!(x21);
+    generate_range!(w21);
+
+    generate_range!(x22);
+    generate_range!(w22);
+
+    generate_range!(x23);
+    generate_range!(w23);
+
+    generate_range!(x24);
+    generate_range!(w24);
+
+    generate_range!(x25);
+    generate_range!(w25);
+
+    generate_range!(x26);
+    generate_range!(w26);
+
+    generate_range!(x27);
+    generate_range!(w27);
+
+    generate_range!(x28);
+    generate_range!(w28);
+
+    generate_range!(x29);
+    generate_range!(w29);
+
+    generate_range!(x30);
+    generate_range!(w30);
+
+    generate_range!(sp);
+    generate_range!(wsp);
+
+    generate_range!(xzr);
+    generate_range!(wzr);
+
+    generate_range!(pc);
+
+    generate_range!(nzcv);
+    generate_range!(pstate_n);
+    generate_range!(pstate_z);
+    generate_range!(pstate_c);
+    generate_range!(pstate_v);
+
+    generate_range!(v0);
+    generate_range!(q0);
+    generate_range!(d0);
+    generate_range!(s0);
+    generate_range!(h0);
+    generate_range!(b0);
+
+    generate_range!(v1);
+    generate_range!(q1);
+    generate_range!(d1);
+    generate_range!(s1);
+    generate_range!(h1);
+    generate_range!(b1);
+
+    generate_range!(v2);
+    generate_range!(q2);
+    generate_range!(d2);
+    generate_range!(s2);
+    generate_range!(h2);
+    generate_range!(b2);
+
+    generate_range!(v3);
+    generate_range!(q3);
+    generate_range!(d3);
+    generate_range!(s3);
+    generate_range!(h3);
+    generate_range!(b3);
+
+    generate_range!(v4);
+    generate_range!(q4);
+    generate_range!(d4);
+    generate_range!(s4);
+    generate_range!(h4);
+    generate_range!(b4);
+
+    generate_range!(v5);
+    generate_range!(q5);
+    generate_range!(d5);
+    generate_range!(s5);
+    generate_range!(h5);
+    generate_range!(b5);
+
+    generate_range!(v6);
+    generate_range!(q6);
+    generate_range!(d6);
+    generate_range!(s6);
+    generate_range!(h6);
+    generate_range!(b6);
+
+    generate_range!(v7);
+    generate_range!(q7);
+    generate_range!(d7);
+    generate_range!(s7);
+    generate_range!(h7);
+    generate_range!(b7);
+
+    generate_range!(v8);
+    generate_range!(q8);
+    generate_range!(d8);
+    generate_range!(s8);
+    generate_range!(h8);
+    generate_range!(b8);
+
+    generate_range!(v9);
+    generate_range!(q9);
+    generate_range!(d9);
+    generate_range!(s9);
+    generate_range!(h9);
+    generate_range!(b9);
+
+    generate_range!(v10);
+    generate_range!(q10);
+    generate_range!(d10);
+    generate_range!(s10);
+    generate_range!(h10);
+    generate_range!(b10);
+
+    generate_range!(v11);
+    generate_range!(q11);
+    generate_range!(d11);
+    generate_range!(s11);
+    generate_range!(h11);
+    generate_range!(b11);
+
+    generate_range!(v12);
+    generate_range!(q12);
+    generate_range!(d12);
+    generate_range!(s12);
+    generate_range!(h12);
+    generate_range!(b12);
+
+    generate_range!(v13);
+    generate_range!(q13);
+    generate_range!(d13);
+    generate_range!(s13);
+    generate_range!(h13);
+    generate_range!(b13);
+
+    generate_range!(v14);
+    generate_range!(q14);
+    generate_range!(d14);
+    generate_range!(s14);
+    generate_range!(h14);
+    generate_range!(b14);
+
+    generate_range!(v15);
+    generate_range!(q15);
+    generate_range!(d15);
+    generate_range!(s15);
+    generate_range!(h15);
+    generate_range!(b15);
+
+    generate_range!(v16);
+    generate_range!(q16);
+    generate_range!(d16);
+    generate_range!(s16);
+    generate_range!(h16);
+    generate_range!(b16);
+
+    generate_range!(v17);
+    generate_range!(q17);
+    generate_range!(d17);
+    generate_range!(s17);
+    generate_range!(h17);
+    generate_range!(b17);
+
+    generate_range!(v18);
+    generate_range!(q18);
+    generate_range!(d18);
+    generate_range!(s18);
+    generate_range!(h18);
+    generate_range!(b18);
+
+    generate_range!(v19);
+    generate_range!(q19);
+    generate_range!(d19);
+    generate_range!(s19);
+    generate_range!(h19);
+    generate_range!(b19);
+
+    generate_range!(v20);
+    generate_range!(q20);
+    generate_range!(d20);
+    generate_range!(s20);
+    generate_range!(h20);
+    generate_range!(b20);
+
+    generate_range!(v21);
+    generate_range!(q21);
+    generate_range!(d21);
+    generate_range!(s21);
+    generate_range!(h21);
+    generate_range!(b21);
+
+    generate_range!(v22);
+    generate_range!(q22);
+    generate_range!(d22);
+    generate_range!(s22);
+    generate_range!(h22);
+    generate_range!(b22);
+
+    generate_range!(v23);
+    generate_range!(q23);
+    generate_range!(d23);
+    generate_range!(s23);
+    generate_range!(h23);
+    generate_range!(b23);
+
+    generate_range!(v24);
+    generate_range!(q24);
+    generate_range!(d24);
+    generate_range!(s24);
+    generate_range!(h24);
+    generate_range!(b24);
+
+    generate_range!(v25);
+    generate_range!(q25);
+    generate_range!(d25);
+    generate_range!(s25);
+    generate_range!(h25);
+    generate_range!(b25);
+
+    generate_range!(v26);
+    generate_range!(q26);
+    generate_range!(d26);
+    generate_range!(s26);
+    generate_range!(h26);
+    generate_range!(b26);
+
+    generate_range!(v27);
+    generate_range!(q27);
+    generate_range!(d27);
+    generate_range!(s27);
+    generate_range!(h27);
+    generate_range!(b27);
+
+    generate_range!(v28);
+    generate_range!(q28);
+    generate_range!(d28);
+    generate_range!(s28);
+    generate_range!(h28);
+    generate_range!(b28);
+
+    generate_range!(v29);
+    generate_range!(q29);
+    generate_range!(d29);
+    generate_range!(s29);
+    generate_range!(h29);
+    generate_range!(b29);
+
+    generate_range!(v30);
+    generate_range!(q30);
+    generate_range!(d30);
+    generate_range!(s30);
+    generate_range!(h30);
+    generate_range!(b30);
+
+    generate_range!(v31);
+    generate_range!(q31);
+    generate_range!(d31);
+    generate_range!(s31);
+    generate_range!(h31);
+    generate_range!(b31);
+
+    generate_range!(fpcr);
+    generate_range!(fpsr);
+
+    generate_range!(tmp64);
+    generate_range!(tmp32);
+
+    generate_range!(tmp2_64);
+    generate_range!(tmp2_32);
+}
@@ -0,0 +1,356 @@
+use crate::ir::{Architecture, Register, VirtualMachine, arm::ArmRange};
+
+macro_rules! generate_range {
+    ($name:ident, $block:literal, $from:literal, $to:literal) => {
+        #[inline(always)]
+        fn $name() -> Register {
+            Register::new(
+                Architecture::Arm64,
+                stringify!($name),
+                (($block * 64 + $from)..($block * 64 + $to)),
+            )
+        }
+    };
+}
+
+impl ArmRange for VirtualMachine {
+    generate_range!(x0, 0, 0, 64);
+    generate_range!(w0, 0, 0, 32);
+
+    generate_range!(x1, 1, 0, 64);
+    generate_range!(w1, 1, 0, 32);
+
+    generate_range!(x2, 2, 0, 64);
+    generate_range!(w2, 2, 0, 32);
+
+    generate_range!(x3, 3, 0, 64);
+    generate_range!(w3, 3, 0, 32);
+
+    generate_range!(x4, 4, 0, 64);
+    generate_range!(w4, 4, 0, 32);
+
+    generate_range!(x5, 5, 0, 64);
+    generate_range!(w5, 5, 0, 32);
+
+    generate_range!(x6, 6, 0, 64);
+    generate_range!(w6, 6, 0, 32);
+
+    generate_range!(x7, 7, 0, 64);
+    generate_range!(w7, 7, 0, 32);
+
+    generate_range!(x8, 8, 0, 64);
+    generate_range!(w8, 8, 0, 32);
+
+    generate_range!(x9, 9, 0, 64);
+    generate_range!(w9, 9, 0, 32);
+
+    generate_range!(x10, 10, 0, 64);
+    generate_range!(w10, 10, 0, 32);
+
+    generate_range!(x11, 11, 0, 64);
+    generate_range!(w11, 11, 0, 32);
+
+    generate_range!(x12, 12, 0, 64);
+    generate_range!(w12, 12, 0, 32);
+
+    generate_range!(x13, 13, 0, 64);
+    generate_range!(w13, 13, 0, 32);
+
+    generate_range!(x14, 14, 0, 64);
+    generate_range!(w14, 14, 0, 32);
+
+    generate_range!(x15, 15, 0, 64);
+    generate_range!(w15, 15, 0, 32);
+
+    generate_range!(x16, 16, 0, 64);
+    generate_range!(w16, 16, 0, 32);
+
+    generate_range!(x17, 17, 0, 64);
+    generate_range!(w17, 17, 0, 32);
+
+    generate_range!(x18, 18, 0, 64);
+    generate_range!(w18, 18, 0, 32);
+
+    generate_range!(x19, 19, 0, 64);
+    generate_range!(w19, 19, 0, 32);
+
+    generate_range!(x20, 20, 0, 64);
+    generate_range!(w20, 20, 0, 32);
+
+    generate_range!(x21, 21, 0, 64);
+    generate_range!(w21, 21, 0, 32);
+
+    generate_range!(x22, 22, 0, 64);
+    generate_range!(w22, 22, 0, 32);
+
+    generate_range!(x23, 23, 0, 64);
+    generate_range!(w23, 23, 0, 32);
+
+    generate_range!(x24, 24, 0, 64);
+    generate_range!(w24, 24, 0, 32);
+
+    generate_range!(x25, 25, 0, 64);
+    generate_range!(w25, 25, 0, 32);
+
+    generate_range!(x26, 26, 0, 64);
+    generate_range!(w26, 26, 0, 32);
+
+    generate_range!(x27, 27, 0, 64);
+    generate_range!(w27, 27, 0, 32);
+
+    generate_range!(x28, 28, 0, 64);
+    generate_range!(w28, 28, 0, 32);
+
+    generate_range!(x29, 29, 0, 64);
+    generate_range!(w29, 29, 0, 32);
+
+    generate_range!(x30, 30, 0, 64);
+    generate_range!(w30, 30, 0, 32);
+
+    generate_range!(sp, 31, 0, 64);
+    generate_range!(wsp, 31, 0, 32);
+
+    generate_range!(xzr, 32, 0, 64);
+    generate_range!(wzr, 32, 0, 32);
+
+    generate_range!(pc, 33, 0, 64);
+
+    generate_range!(nzcv, 34, 0, 4);
+    generate_range!(pstate_n, 34, 3, 4);
+    generate_range!(pstate_z, 34, 2, 3);
+    generate_range!(pstate_c, 34, 1, 2);
+    generate_range!(pstate_v, 34, 0, 1);
+
+    generate_range!(v0, 35, 0, 128);
+    generate_range!(q0, 35, 0, 128);
+    generate_range!(d0, 35, 0, 64);
+    generate_range!(s0, 35, 0, 32);
+    generate_range!(h0, 35, 0, 16);
+    generate_range!(b0, 35, 0, 8);
+
+    generate_range!(v1, 37, 0, 128);
+    generate_range!(q1, 37, 0, 128);
+    generate_range!(d1, 37, 0, 64);
+    generate_range!(s1, 37, 0, 32);
+    generate_range!(h1, 37, 0, 16);
+    generate_range!(b1, 37, 0, 8);
+
+    generate_range!(v2, 39, 0, 128);
+    generate_range!(q2, 39, 0, 128);
+    generate_range!(d2, 39, 0, 64);
+    generate_range!(s2, 39, 0, 32);
+    generate_range!(h2, 39, 0, 16);
+    generate_range!(b2, 39, 0, 8);
+
+    generate_range!(v3, 41, 0, 128);
+    generate_range!(q3, 41, 0, 128);
+    generate_range!(d3, 41, 0, 64);
+    generate_range!(s3, 41, 0, 32);
+    generate_range!(h3, 41, 0, 16);
+    generate_range!(b3, 41, 0, 8);
+
+    generate_range!(v4, 43, 0, 128);
+    generate_range!(q4, 43, 0, 128);
+    generate_range!(d4, 43, 0, 64);
+    generate_range!(s4, 43, 0, 32);
+    generate_range!(h4, 43, 0, 16);
+    generate_range!(b4, 43, 0, 8);
+
+    generate_range!(v5, 45, 0, 128);
+    generate_range!(q5, 45, 0, 128);
+    generate_range!(d5, 45, 0, 64);
+    generate_range!(s5, 45, 0, 32);
+    generate_range!(h5, 45, 0, 16);
+    generate_range!(b5, 45, 0, 8);
+
+    generate_range!(v6, 47, 0, 128);
+    generate_range!(q6, 47, 0, 128);
+    generate_range!(d6, 47, 0, 64);
+    generate_range!(s6, 47, 0, 32);
+    generate_range!(h6, 47, 0, 16);
+    generate_range!(b6, 47, 0, 8);
+
+    generate_range!(v7, 49, 0, 128);
+    generate_range!(q7, 49, 0, 128);
+    generate_range!(d7, 49, 0, 64);
+    generate_range!(s7, 49, 0, 32);
+    generate_range!(h7, 49, 0, 16);
+    generate_range!(b7, 49, 0, 8);
+
+    generate_range!(v8, 51, 0, 128);
+    generate_range!(q8, 51, 0, 128);
+    generate_range!(d8, 51, 0, 64);
+    generate_range!(s8, 51, 0, 32);
+    generate_range!(h8, 51, 0, 16);
+    generate_range!(b8, 51, 0, 8);
+
+    generate_range!(v9, 53, 0, 128);
+    generate_range!(q9, 53, 0, 128);
+    generate_range!(d9, 53, 0, 64);
+    generate_range!(s9, 53, 0, 32);
+    generate_range!(h9, 53, 0, 16);
+    generate_range!(b9, 53, 0, 8);
+
+    generate_range!(v10, 55, 0, 128);
+    generate_range!(q10, 55, 0, 128);
+    generate_range!(d10, 55, 0, 64);
+    generate_range!(s10, 55, 0, 32);
+    generate_range!(h10, 55, 0, 16);
+    generate_range!(b10, 55, 0, 8);
+
+    generate_range!(v11, 57, 0, 128);
+    generate_range!(q11, 57, 0, 128);
+    generate_range!(d11, 57, 0, 64);
+    generate_range!(s11, 57, 0, 32);
+    generate_range!(h11, 57, 0, 16);
+    generate_range!(b11, 57, 0, 8);
+
+    generate_range!(v12, 59, 0, 128);
+    generate_range!(q12, 59, 0, 128);
+    generate_range!(d12, 59, 0, 64);
+    generate_range!(s12, 59, 0, 32);
+    generate_range!(h12, 59, 0, 16);
+    generate_range!(b12, 59, 0, 8);
+
+    generate_range!(v13, 61, 0, 128);
+    generate_range!(q13, 61, 0, 128);
+    generate_range!(d13, 61, 0, 64);
+    generate_range!(s13, 61, 0, 32);
+    generate_range!(h13, 61, 0, 16);
+    generate_range!(b13, 61, 0, 8);
+
+    generate_range!(v14, 63, 0, 128);
+    generate_range!(q14, 63, 0, 128);
+    generate_range!(d14, 63, 0, 64);
+    generate_range!(s14, 63, 0, 32);
+    generate_range!(h14, 63, 0, 16);
+    generate_range!(b14, 63, 0, 8);
+
+    generate_range!(v15, 65, 0, 128);
+    generate_range!(q15, 65, 0, 128);
+    generate_range!(d15, 65, 0, 64);
+    generate_range!(s15, 65, 0, 32);
+    generate_range!(h15, 65, 0, 16);
+    generate_range!(b15, 65, 0, 8);
+
+    generate_range!(v16, 67, 0, 128);
+    generate_range!(q16, 67, 0, 128);
+    generate_range!(d16, 67, 0, 64);
+    generate_range!(s16, 67, 0, 32);
+    generate_range!(h16, 67, 0, 16);
+    generate_range!(b16, 67, 0, 8);
+
+    generate_range!(v17, 69, 0, 128);
+    generate_range!(q17, 69, 0, 128);
+    generate_range!(d17, 69, 0, 64);
+    generate_range!(s17, 69, 0, 32);
+    generate_range!(h17, 69, 0, 16);
+    generate_range!(b17, 69, 0, 8);
+
+    generate_range!(v18, 71, 0, 128);
+    generate_range!(q18, 71, 0, 128);
+    generate_range!(d18, 71, 0, 64);
+    generate_range!(s18, 71, 0, 32);
+    generate_range!(h18, 71, 0, 16);
+    generate_range!(b18, 71, 0, 8);
+
+    generate_range!(v19, 73, 0, 128);
+    generate_range!(q19, 73, 0, 128);
+    generate_range!(d19, 73, 0, 64);
+    generate_range!(s19, 73, 0, 32);
+    generate_range!(h19, 73, 0, 16);
+    generate_range!(b19, 73, 0, 8);
+
+    generate_range!(v20, 75, 0, 128);
+    generate_range!(q20, 75, 0, 128);
+    generate_range!(d20, 75, 0, 64);
+    generate_range!(s20, 75, 0, 32);
+    generate_range!(h20, 75, 0, 16);
+    generate_range!(b20, 75, 0, 8);
+
+    generate_range!(v21, 77, 0, 128);
+    generate_range!(q21, 77, 0, 128);
+    generate_range!(d21, 77, 0, 64);
+    generate_range!(s21, 77, 0, 32);
+    generate_range!(h21, 77, 0, 16);
+    generate_range!(b21, 77, 0, 8);
+
+    generate_range!(v22, 79, 0, 128);
+    generate_range!(q22, 79, 0, 128);
+    generate_range!(d22, 79, 0, 64);
+    generate_range!(s22, 79, 0, 32);
+    generate_range!(h22, 79, 0, 16);
+    generate_range!(b22, 79, 0, 8);
+
+    generate_range!(v23, 81, 0, 128);
+    generate_range!(q23, 81, 0, 128);
+    generate_range!(d23, 81, 0, 64);
+    generate_range!(s23, 81, 0, 32);
+    generate_range!(h23, 81, 0, 16);
+    generate_range!(b23, 81, 0, 8);
+
+    generate_range!(v24, 83, 0, 128);
+    generate_range!(q24, 83, 0, 128);
+    generate_range!(d24, 83, 0, 64);
+    generate_range!(s24, 83, 0, 32);
+    generate_range!(h24, 83, 0, 16);
+    generate_range!(b24, 83, 0, 8);
+
+    generate_range!(v25, 85, 0, 128);
+    generate_range!(q25, 85, 0, 128);
+    generate_range!(d25, 85, 0, 64);
+    generate_range!(s25, 85, 0, 32);
+    generate_range!(h25, 85, 0, 16);
+    generate_range!(b25, 85, 0, 8);
+
+    generate_range!(v26, 87, 0, 128);
+    generate_range!(q26, 87, 0, 128);
+    generate_range!(d26, 87, 0, 64);
+    generate_range!(s26, 87, 0, 32);
+    generate_range!(h26, 87, 0, 16);
+    generate_range!(b26, 87, 0, 8);
+
+    generate_range!(v27, 89, 0, 128);
+    generate_range!(q27, 89, 0, 128);
+    generate_range!(d27, 89, 0, 64);
+    generate_range!(s27, 89, 0, 32);
+    generate_range!(h27, 89, 0, 16);
+    generate_range!(b27, 89, 0, 8);
+
+    generate_range!(v28, 91, 0, 128);
+    generate_range!(q28, 91, 0, 128);
+    generate_range!(d28, 91, 0, 64);
+    generate_range!(s28, 91, 0, 32);
+    generate_range!(h28, 91, 0, 16);
+    generate_range!(b28, 91, 0, 8);
+
+    generate_range!(v29, 93, 0, 128);
+    generate_range!(q29, 93, 0, 128);
+    generate_range!(d29, 93, 0, 64);
+    generate_range!(s29, 93, 0, 32);
+    generate_range!(h29, 93, 0, 16);
+    generate_range!(b29, 93, 0, 8);
+
+    generate_range!(v30, 95, 0, 128);
+    generate_range!(q30, 95, 0, 128);
+    generate_range!(d30, 95, 0, 64);
+    generate_range!(s30, 95, 0, 32);
+    generate_range!(h30, 95, 0, 16);
+    generate_range!(b30, 95, 0, 8);
+
+    generate_range!(v31, 97, 0, 128);
+    generate_range!(q31, 97, 0, 128);
+    generate_range!(d31, 97, 0, 64);
+    generate_range!(s31, 97, 0, 32);
+    generate_range!(h31, 97, 0, 16);
+    generate_range!(b31, 97, 0, 8);
+
+    generate_range!(fpcr, 99, 0, 32);
+    generate_range!(fpsr, 100, 0, 32);
+
+    generate_range!(tmp64, 101, 0, 64);
+    generate_range!(tmp32, 101, 0, 32);
+
+    generate_range!(tmp2_64, 102, 0, 64);
+    generate_range!(tmp2_32, 102, 0, 32);
+}
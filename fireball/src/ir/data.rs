@@ -5,7 +5,7 @@ use crate::{
 use std::num::NonZeroU8;
 
 /// Data used internally by the IR
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum IrData {
     /// The literal value (e.g., 0x1234) in `mov eax, 0x1234`
     Constant(usize),
@@ -21,7 +21,7 @@ pub enum IrData {
     Operand(NonZeroU8),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum IrIntrinsic {
     Unknown,
     Undefined,
@@ -42,7 +42,7 @@ pub enum IrIntrinsic {
     ArchitectureByteSizeCondition(NumCondition),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Copy, Hash, serde::Serialize, serde::Deserialize)]
 pub enum NumCondition {
     Higher(u16),
     HigherOrEqual(u16),
@@ -53,19 +53,19 @@ pub enum NumCondition {
     RangeInclusive(u16, u16),
     ExcludesRange(u16, u16),
 }
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct IrDataAccess {
     location: Aos<IrData>,
     access_type: IrDataAccessType,
     size: IrAccessSize,
 }
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, serde::Serialize, serde::Deserialize)]
 pub enum IrDataAccessType {
     Read,
     Write,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum IrDataOperation {
     Unary {
         operator: IrUnaryOperator,
@@ -78,7 +78,7 @@ pub enum IrDataOperation {
     },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum IrAccessSize {
     ResultOfBit(Aos<IrData>),
     ResultOfByte(Aos<IrData>),
@@ -208,7 +208,11 @@ impl From<&iceball::Argument> for Aos<IrData> {
         use iceball::{AddressingOperator, Argument, Memory, Register, RelativeAddressingArgument};
         match value {
             Argument::Constant(c) => IrData::Constant((*c).try_into().unwrap()).into(),
-            Argument::Register(Register::Arm(_)) => IrData::Intrinsic(IrIntrinsic::Unknown).into(),
+            Argument::Register(Register::Arm(register)) => arm_reg_to_ir_reg(*register),
+            Argument::ShiftedRegister(register, shift, amount) => {
+                shifted_reg_to_ir_data(*register, *shift, *amount)
+            }
+            Argument::Condition(condition) => crate::arch::arm::condition_to_ir(*condition),
             Argument::Memory(Memory::AbsoluteAddressing(v)) => {
                 IrData::Dereference(IrData::Constant((*v).try_into().unwrap()).into()).into()
             }
@@ -222,10 +226,10 @@ impl From<&iceball::Argument> for Aos<IrData> {
                 };
                 let to_ir_operand = |arg: &RelativeAddressingArgument| -> Option<Aos<IrData>> {
                     match arg {
-                        RelativeAddressingArgument::Register(reg) => match reg {
-                            Register::X64(x64_reg) => Some(x64_reg_to_ir_reg(*x64_reg)),
-                            Register::Arm(_) => None,
-                        },
+                        RelativeAddressingArgument::Register(reg) => Some(reg_to_ir_reg(*reg)),
+                        RelativeAddressingArgument::ExtendedRegister(reg, shift) => {
+                            Some(shifted_reg_to_ir_data(*reg, *shift, 0))
+                        }
                         RelativeAddressingArgument::Constant(c) => {
                             if *c >= 0 {
                                 Some(IrData::Constant((*c).try_into().unwrap()).into())
@@ -242,10 +246,18 @@ impl From<&iceball::Argument> for Aos<IrData> {
                         RelativeAddressingArgument::Operator(_) => None,
                     }
                 };
-                let mut current_expr: Aos<IrData> = match to_ir_operand(arg1) {
+
+                // `Mul` scales the term right before it, the terms are summed afterwards
+                let mut sum: Option<Aos<IrData>> = None;
+                let mut term: Aos<IrData> = match to_ir_operand(arg1) {
                     Some(value) => value,
                     None => IrData::Constant(0).into(),
                 };
+                let mut term_operator = IrBinaryOperator::Add;
+                let join = |sum: Option<Aos<IrData>>, operator, term| match sum {
+                    Some(sum) => binary_ir_data(operator, sum, term),
+                    None => term,
+                };
 
                 while let Some(token) = iter.next() {
                     let (operator, operand_token) = match token {
@@ -259,29 +271,97 @@ impl From<&iceball::Argument> for Aos<IrData> {
                         continue;
                     };
 
-                    let binary_op_ir = match operator {
-                        AddressingOperator::Add => IrBinaryOperator::Add,
-                        AddressingOperator::Sub => IrBinaryOperator::Sub,
-                        AddressingOperator::Mul => IrBinaryOperator::Mul,
-                    };
-
-                    current_expr = IrData::Operation(IrDataOperation::Binary {
-                        operator: binary_op_ir,
-                        arg1: current_expr,
-                        arg2: operand,
-                    })
-                    .into();
+                    match operator {
+                        AddressingOperator::Mul => {
+                            term = binary_ir_data(IrBinaryOperator::Mul, term, operand);
+                        }
+                        AddressingOperator::Add | AddressingOperator::Sub => {
+                            sum = Some(join(sum, term_operator, term));
+                            term_operator = match operator {
+                                AddressingOperator::Sub => IrBinaryOperator::Sub,
+                                _ => IrBinaryOperator::Add,
+                            };
+                            term = operand;
+                        }
+                    }
                 }
 
-                IrData::Dereference(current_expr).into()
+                IrData::Dereference(join(sum, term_operator, term)).into()
             }
             Argument::Register(Register::X64(register)) => x64_reg_to_ir_reg(*register),
         }
     }
 }
+fn binary_ir_data(operator: IrBinaryOperator, arg1: Aos<IrData>, arg2: Aos<IrData>) -> Aos<IrData> {
+    IrData::Operation(IrDataOperation::Binary {
+        operator,
+        arg1,
+        arg2,
+    })
+    .into()
+}
+fn reg_to_ir_reg(reg: iceball::Register) -> Aos<IrData> {
+    match reg {
+        iceball::Register::X64(reg) => x64_reg_to_ir_reg(reg),
+        iceball::Register::Arm(reg) => arm_reg_to_ir_reg(reg),
+    }
+}
+/// Value of an AArch64 register operand extended and shifted before use
+fn shifted_reg_to_ir_data(
+    reg: iceball::Register,
+    shift: iceball::ArmShift,
+    amount: u8,
+) -> Aos<IrData> {
+    use iceball::ArmShift;
+    let mut value = reg_to_ir_reg(reg);
+    if let Some(bytes) = shift.extended_bytes() {
+        let low = IrData::Intrinsic(IrIntrinsic::Sized(
+            value,
+            IrAccessSize::ResultOfByte(IrData::Constant(bytes as usize).into()),
+        ));
+        value = IrData::Operation(IrDataOperation::Unary {
+            operator: if shift.is_signed_extension() {
+                IrUnaryOperator::SignExtend
+            } else {
+                IrUnaryOperator::ZeroExtend
+            },
+            arg: low.into(),
+        })
+        .into();
+    }
+    if amount == 0 {
+        return value;
+    }
+    let amount: Aos<IrData> = IrData::Constant(amount as usize).into();
+    match shift {
+        ArmShift::Lsr => binary_ir_data(IrBinaryOperator::Shr, value, amount),
+        ArmShift::Asr => binary_ir_data(IrBinaryOperator::Sar, value, amount),
+        ArmShift::Ror => {
+            let bits = IrData::Intrinsic(IrIntrinsic::BitSizeOf(value.clone())).into();
+            let left = binary_ir_data(IrBinaryOperator::Sub, bits, amount.clone());
+            binary_ir_data(
+                IrBinaryOperator::Or,
+                binary_ir_data(IrBinaryOperator::Shr, value.clone(), amount),
+                binary_ir_data(IrBinaryOperator::Shl, value, left),
+            )
+        }
+        _ => binary_ir_data(IrBinaryOperator::Shl, value, amount),
+    }
+}
 fn x64_reg_to_ir_reg(reg: iceball::X64Register) -> Aos<IrData> {
     crate::arch::x86_64::str_to_x64_register(reg.name())
 }
+fn arm_reg_to_ir_reg(reg: iceball::ArmRegister) -> Aos<IrData> {
+    use iceball::ArmRegister;
+    match reg {
+        ArmRegister::Xzr | ArmRegister::Wzr => IrData::Constant(0).into(),
+        // SVE registers are not modeled
+        ArmRegister::Z(_) | ArmRegister::P(_) | ArmRegister::Ffr => {
+            IrData::Intrinsic(IrIntrinsic::Unknown).into()
+        }
+        _ => crate::arch::arm::str_to_arm_register(&reg.to_string()),
+    }
+}
 
 impl std::fmt::Display for IrData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -0,0 +1,681 @@
+//! Module interpreting lifted IR statements on the `VirtualMachine`
+//!
+//! The interpreter executes the statements generated for each instruction literally, so the
+//! registers and memory it ends with can be diffed against native execution (for example the
+//! trace of the `emulation` module) to find mistakes in the lifters.
+
+use crate::{
+    core::{Block, Instruction},
+    ir::{
+        Ir, IrBlock, Register, SparseMemory, VirtualMachine,
+        arm::{ARM, ArmRange},
+        data::{IrAccessSize, IrData, IrDataOperation, IrIntrinsic, NumCondition},
+        operator::{IrBinaryOperator, IrUnaryOperator},
+        statements::{IrStatement, IrStatementSpecial},
+        x86_64::{X64, X64Range},
+    },
+    prelude::*,
+    utils::Aos,
+};
+use iceball::MachineArchitecture;
+use std::{num::NonZeroU8, sync::Arc};
+
+/// Where execution continues after an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpreterFlow {
+    /// The next instruction
+    Next,
+    /// A jump target
+    Jump(u64),
+    /// A called function, the return address is already saved
+    Call(u64),
+    /// Execution halted
+    Halt,
+}
+
+/// Why the interpreter could not continue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InterpreterFault {
+    /// Memory at the address is neither written nor inside a section
+    UnmappedMemory(u64),
+    /// Division by zero
+    DivideByZero(u64),
+    /// The data can not be read or written by the interpreter
+    UnsupportedData(String),
+    /// An `Undefined` statement was executed
+    Undefined(u64),
+    /// An `Exception` statement was executed
+    Exception(u64, &'static str),
+    /// An `Assertion` statement did not hold
+    AssertionFailed(u64, String),
+    /// The block at the address was not analyzed, or has no IR
+    BlockNotFound(u64),
+    /// The instruction limit of `Interpreter::run` was reached
+    InstructionLimit,
+}
+
+/// State shared while interpreting the statements of an instruction
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    instruction: &'a Instruction,
+    /// Whether the evaluated data came from an instruction operand
+    in_operand: bool,
+}
+
+impl Context<'_> {
+    fn address(&self) -> u64 {
+        self.instruction.address
+    }
+
+    fn byte_size(&self) -> u64 {
+        self.instruction
+            .inner
+            .bytes
+            .as_ref()
+            .map(|x| x.len() as u64)
+            .unwrap_or(0)
+    }
+
+    fn operand(self) -> Self {
+        Self {
+            in_operand: true,
+            ..self
+        }
+    }
+}
+
+/// Interpreter executing IR statements over the registers and memory of a `VirtualMachine`
+pub struct Interpreter {
+    vm: VirtualMachine,
+    architecture: MachineArchitecture,
+    /// Addresses of executed instructions without IR, which were skipped
+    unlifted: Vec<u64>,
+}
+
+impl Interpreter {
+    /// Creates an interpreter with zeroed registers.
+    ///
+    /// ### Arguments
+    /// - `architecture: MachineArchitecture` - architecture of the interpreted code
+    /// - `memory: SparseMemory` - memory the interpreted code accesses
+    ///
+    /// ### Returns
+    /// - `Self` - the interpreter
+    pub fn new(architecture: MachineArchitecture, memory: SparseMemory) -> Self {
+        let mut vm = match architecture {
+            MachineArchitecture::X64 | MachineArchitecture::X86 => <VirtualMachine as X64>::new(),
+            MachineArchitecture::Arm64 | MachineArchitecture::Arm => <VirtualMachine as ARM>::new(),
+        };
+        *vm.memory_mut() = memory;
+        Self {
+            vm,
+            architecture,
+            unlifted: Vec::new(),
+        }
+    }
+
+    /// Returns the virtual machine holding the registers and memory
+    pub fn vm(&self) -> &VirtualMachine {
+        &self.vm
+    }
+
+    /// Returns the virtual machine holding the registers and memory (mutable)
+    pub fn vm_mut(&mut self) -> &mut VirtualMachine {
+        &mut self.vm
+    }
+
+    /// Returns the addresses of executed instructions which had no IR and were skipped
+    pub fn unlifted_instructions(&self) -> &[u64] {
+        &self.unlifted
+    }
+
+    /// Executes the statements of one instruction.
+    ///
+    /// ### Arguments
+    /// - `ir: &Ir` - statements of the instruction
+    /// - `instruction: &Instruction` - the instruction, for its operands and size
+    ///
+    /// ### Returns
+    /// - `Result<InterpreterFlow, InterpreterFault>` - where execution continues
+    ///
+    /// ### Note
+    /// - The instruction pointer holds the address of the instruction while its statements run,
+    ///   and reads as the address of the next instruction inside x86 operands (`[rip + 0x10]`).
+    /// - Undefined and unknown values read as zero.
+    pub fn execute_instruction(
+        &mut self,
+        ir: &Ir,
+        instruction: &Instruction,
+    ) -> Result<InterpreterFlow, InterpreterFault> {
+        let context = Context {
+            instruction,
+            in_operand: false,
+        };
+        let ip = self.instruction_pointer();
+        self.vm.write_register(&ip, context.address() as u128);
+
+        let Some(statements) = ir.statements else {
+            debug!("skipping instruction without IR: {}", instruction);
+            self.unlifted.push(context.address());
+            return Ok(InterpreterFlow::Next);
+        };
+        let mut flow = InterpreterFlow::Next;
+        self.execute(statements, context, &mut flow)?;
+        Ok(flow)
+    }
+
+    /// Executes the statements of a block until control leaves it.
+    ///
+    /// ### Arguments
+    /// - `block: &IrBlock` - the block
+    ///
+    /// ### Returns
+    /// - `Result<(InterpreterFlow, usize), InterpreterFault>` - where execution continues and
+    ///   the number of executed instructions
+    pub fn execute_block(
+        &mut self,
+        block: &IrBlock,
+    ) -> Result<(InterpreterFlow, usize), InterpreterFault> {
+        let mut executed = 0;
+        for (ir, instruction) in block.ir().iter().zip(block.instructions().iter()) {
+            executed += 1;
+            let flow = self.execute_instruction(ir, instruction)?;
+            if flow != InterpreterFlow::Next {
+                return Ok((flow, executed));
+            }
+        }
+        Ok((InterpreterFlow::Next, executed))
+    }
+
+    /// Executes blocks from an address until control reaches code without a block.
+    ///
+    /// ### Arguments
+    /// - `start: u64` - virtual address execution starts at
+    /// - `block_at: impl FnMut(u64) -> Option<Arc<Block>>` - analyzed block at an address, e.g.
+    ///   `|x| fireball.analyze_from_virtual_address(x).ok()`
+    /// - `instruction_limit: usize` - maximum number of executed instructions
+    ///
+    /// ### Returns
+    /// - `Result<u64, InterpreterFault>` - address execution left the analyzed code at (such as
+    ///   the return address of the first function), or of the halting instruction
+    pub fn run(
+        &mut self,
+        start: u64,
+        mut block_at: impl FnMut(u64) -> Option<Arc<Block>>,
+        instruction_limit: usize,
+    ) -> Result<u64, InterpreterFault> {
+        let mut address = start;
+        let mut executed = 0;
+        while let Some(block) = block_at(address) {
+            let ir = block.get_ir();
+            let Some(ir) = ir.as_ref() else {
+                return Err(InterpreterFault::BlockNotFound(address));
+            };
+            let (flow, count) = self.execute_block(ir)?;
+            executed += count;
+            address = match flow {
+                InterpreterFlow::Next => {
+                    let Some(size) = block.get_block_size() else {
+                        return Err(InterpreterFault::BlockNotFound(address));
+                    };
+                    block.get_start_address().get_virtual_address() + *size
+                }
+                InterpreterFlow::Jump(target) | InterpreterFlow::Call(target) => target,
+                InterpreterFlow::Halt => {
+                    let ip = self.instruction_pointer();
+                    return Ok(self.vm.read_register(&ip) as u64);
+                }
+            };
+            if executed >= instruction_limit {
+                return Err(InterpreterFault::InstructionLimit);
+            }
+        }
+        Ok(address)
+    }
+
+    fn instruction_pointer(&self) -> Register {
+        match self.architecture {
+            MachineArchitecture::X64 => <VirtualMachine as X64Range>::rip(),
+            MachineArchitecture::X86 => <VirtualMachine as X64Range>::eip(),
+            MachineArchitecture::Arm64 | MachineArchitecture::Arm => {
+                <VirtualMachine as ArmRange>::pc()
+            }
+        }
+    }
+
+    fn architecture_bits(&self) -> u32 {
+        match self.architecture {
+            MachineArchitecture::X64 | MachineArchitecture::Arm64 => 64,
+            MachineArchitecture::X86 | MachineArchitecture::Arm => 32,
+        }
+    }
+
+    fn execute(
+        &mut self,
+        statements: &[IrStatement],
+        context: Context,
+        flow: &mut InterpreterFlow,
+    ) -> Result<(), InterpreterFault> {
+        for statement in statements {
+            match statement {
+                IrStatement::Assignment { from, to, size } => {
+                    let bits = self.access_size(size, context)?;
+                    let value = self.evaluate(from, context, Some(bits))?;
+                    self.assign(to, value, bits, context)?;
+                }
+                IrStatement::Jump { target } => {
+                    *flow = InterpreterFlow::Jump(self.evaluate(target, context, None)? as u64);
+                }
+                IrStatement::JumpByCall { target } => {
+                    *flow = InterpreterFlow::Call(self.evaluate(target, context, None)? as u64);
+                }
+                IrStatement::Halt => *flow = InterpreterFlow::Halt,
+                IrStatement::Condition {
+                    condition,
+                    true_branch,
+                    false_branch,
+                } => {
+                    let branch = if self.evaluate(condition, context, None)? != 0 {
+                        true_branch
+                    } else {
+                        false_branch
+                    };
+                    self.execute(branch, context, flow)?;
+                }
+                IrStatement::Undefined => {
+                    return Err(InterpreterFault::Undefined(context.address()));
+                }
+                IrStatement::Exception(message) => {
+                    return Err(InterpreterFault::Exception(context.address(), message));
+                }
+                IrStatement::Special(IrStatementSpecial::TypeSpecified { .. }) => {}
+                IrStatement::Special(IrStatementSpecial::Assertion { condition }) => {
+                    if self.evaluate(condition, context, None)? == 0 {
+                        return Err(InterpreterFault::AssertionFailed(
+                            context.address(),
+                            condition.to_string(),
+                        ));
+                    }
+                }
+                IrStatement::Special(IrStatementSpecial::CalcFlagsAutomatically {
+                    operation,
+                    size,
+                    flags,
+                }) => {
+                    let bits = self.access_size(size, context)?;
+                    self.calculate_flags(operation, bits, flags, context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stores the flags an operation produces.
+    fn calculate_flags(
+        &mut self,
+        operation: &Aos<IrData>,
+        bits: u32,
+        flags: &[Aos<IrData>],
+        context: Context,
+    ) -> Result<(), InterpreterFault> {
+        let result = self.evaluate(operation, context, Some(bits))? & mask(bits);
+        // operands of additions and subtractions, for carry and overflow
+        let operands = match operation.as_ref() {
+            IrData::Operation(IrDataOperation::Binary {
+                operator: operator @ (IrBinaryOperator::Add | IrBinaryOperator::Sub),
+                arg1,
+                arg2,
+            }) => {
+                let a = self.evaluate(arg1, context, Some(bits))? & mask(bits);
+                let b = self.evaluate(arg2, context, Some(bits))? & mask(bits);
+                Some((*operator == IrBinaryOperator::Add, a, b))
+            }
+            _ => None,
+        };
+        let top = |x: u128| (x >> (bits - 1)) & 1;
+
+        for flag in flags {
+            let IrData::Register(register) = flag.as_ref() else {
+                continue;
+            };
+            let value = match (register.name(), operands) {
+                ("zf" | "pstate_z", _) => (result == 0) as u128,
+                ("sf" | "pstate_n", _) => top(result),
+                ("pf", _) => (result as u8).count_ones().is_multiple_of(2) as u128,
+                ("cf" | "pstate_c", Some((true, a, b))) => {
+                    let (sum, overflow) = a.overflowing_add(b);
+                    (overflow || sum > mask(bits)) as u128
+                }
+                // x86 sets the borrow, AArch64 its inverse
+                ("cf", Some((false, a, b))) => (a < b) as u128,
+                ("pstate_c", Some((false, a, b))) => (a >= b) as u128,
+                ("of" | "pstate_v", Some((is_add, a, b))) => {
+                    let same_sign = top(a) == top(b);
+                    (same_sign == is_add && top(result) != top(a)) as u128
+                }
+                ("af", Some((_, a, b))) => ((a ^ b ^ result) >> 4) & 1,
+                _ => 0,
+            };
+            self.vm.write_register(register, value);
+        }
+        Ok(())
+    }
+
+    /// Evaluates data.
+    ///
+    /// `hint` is the size in bits dereferences without a known size are read with.
+    fn evaluate(
+        &self,
+        data: &Aos<IrData>,
+        context: Context,
+        hint: Option<u32>,
+    ) -> Result<u128, InterpreterFault> {
+        let value = match data.as_ref() {
+            IrData::Constant(c) => *c as u128,
+            IrData::Register(register) => {
+                if context.in_operand
+                    && register.is_ip()
+                    && matches!(
+                        self.architecture,
+                        MachineArchitecture::X64 | MachineArchitecture::X86
+                    )
+                {
+                    (context.address() + context.byte_size()) as u128
+                } else {
+                    self.vm.read_register(register)
+                }
+            }
+            IrData::Dereference(address) => {
+                let address = self.evaluate(address, context, None)? as u64;
+                let bits = hint.unwrap_or(self.architecture_bits());
+                self.load(address, bits)?
+            }
+            IrData::Operand(index) => {
+                let operand = operand(*index, context);
+                let bits = self.width_of(data, context)?;
+                match operand.as_ref() {
+                    IrData::Dereference(_) => {
+                        self.evaluate(&operand, context.operand(), Some(bits))?
+                    }
+                    _ => self.evaluate(&operand, context.operand(), hint)?,
+                }
+            }
+            IrData::Operation(operation) => self.evaluate_operation(operation, context, hint)?,
+            IrData::Intrinsic(intrinsic) => self.evaluate_intrinsic(intrinsic, context)?,
+        };
+        Ok(value)
+    }
+
+    fn evaluate_operation(
+        &self,
+        operation: &IrDataOperation,
+        context: Context,
+        hint: Option<u32>,
+    ) -> Result<u128, InterpreterFault> {
+        let value = match operation {
+            IrDataOperation::Unary { operator, arg } => {
+                let bits = self.width_of(arg, context)?;
+                let value = self.evaluate(arg, context, hint)? & mask(bits);
+                match operator {
+                    IrUnaryOperator::Not => !value & mask(bits),
+                    IrUnaryOperator::Negation => value.wrapping_neg() & mask(bits),
+                    IrUnaryOperator::SignExtend => sign_extend(value, bits) as u128,
+                    IrUnaryOperator::ZeroExtend => value,
+                }
+            }
+            IrDataOperation::Binary {
+                operator,
+                arg1,
+                arg2,
+            } => {
+                let bits = self
+                    .width_of(arg1, context)?
+                    .max(self.width_of(arg2, context)?);
+                let a = self.evaluate(arg1, context, hint)?;
+                let b = self.evaluate(arg2, context, hint)?;
+                let signed = |x: u128| sign_extend(x & mask(bits), bits);
+                let compare = |size: &IrAccessSize| -> Result<(u128, u128, u32), InterpreterFault> {
+                    let bits = self.access_size(size, context)?;
+                    Ok((a & mask(bits), b & mask(bits), bits))
+                };
+                match operator {
+                    IrBinaryOperator::And => a & b,
+                    IrBinaryOperator::Or => a | b,
+                    IrBinaryOperator::Xor => a ^ b,
+                    IrBinaryOperator::Shl => a.checked_shl(b as u32).unwrap_or(0),
+                    IrBinaryOperator::Shr => (a & mask(bits)).checked_shr(b as u32).unwrap_or(0),
+                    IrBinaryOperator::Sar => {
+                        (signed(a) >> (b as u32).min(127)) as u128 & mask(bits)
+                    }
+                    IrBinaryOperator::Add => a.wrapping_add(b),
+                    IrBinaryOperator::Sub => a.wrapping_sub(b),
+                    IrBinaryOperator::Mul => a.wrapping_mul(b),
+                    IrBinaryOperator::SignedDiv | IrBinaryOperator::SignedRem
+                        if b & mask(bits) == 0 =>
+                    {
+                        return Err(InterpreterFault::DivideByZero(context.address()));
+                    }
+                    IrBinaryOperator::UnsignedDiv | IrBinaryOperator::UnsignedRem
+                        if b & mask(bits) == 0 =>
+                    {
+                        return Err(InterpreterFault::DivideByZero(context.address()));
+                    }
+                    IrBinaryOperator::SignedDiv => {
+                        signed(a).wrapping_div(signed(b)) as u128 & mask(bits)
+                    }
+                    IrBinaryOperator::SignedRem => {
+                        signed(a).wrapping_rem(signed(b)) as u128 & mask(bits)
+                    }
+                    IrBinaryOperator::UnsignedDiv => (a & mask(bits)) / (b & mask(bits)),
+                    IrBinaryOperator::UnsignedRem => (a & mask(bits)) % (b & mask(bits)),
+                    IrBinaryOperator::Equal(size) => {
+                        let (a, b, _) = compare(size)?;
+                        (a == b) as u128
+                    }
+                    IrBinaryOperator::SignedLess(size) => {
+                        let (a, b, bits) = compare(size)?;
+                        (sign_extend(a, bits) < sign_extend(b, bits)) as u128
+                    }
+                    IrBinaryOperator::SignedLessOrEqual(size) => {
+                        let (a, b, bits) = compare(size)?;
+                        (sign_extend(a, bits) <= sign_extend(b, bits)) as u128
+                    }
+                    IrBinaryOperator::UnsignedLess(size) => {
+                        let (a, b, _) = compare(size)?;
+                        (a < b) as u128
+                    }
+                    IrBinaryOperator::UnsignedLessOrEqual(size) => {
+                        let (a, b, _) = compare(size)?;
+                        (a <= b) as u128
+                    }
+                }
+            }
+        };
+        Ok(value)
+    }
+
+    fn evaluate_intrinsic(
+        &self,
+        intrinsic: &IrIntrinsic,
+        context: Context,
+    ) -> Result<u128, InterpreterFault> {
+        let value = match intrinsic {
+            IrIntrinsic::Unknown | IrIntrinsic::Undefined => 0,
+            IrIntrinsic::SignedMax(size) => mask(self.access_size(size, context)?) >> 1,
+            IrIntrinsic::SignedMin(size) => 1 << (self.access_size(size, context)? - 1),
+            IrIntrinsic::UnsignedMax(size) | IrIntrinsic::BitOnes(size) => {
+                mask(self.access_size(size, context)?)
+            }
+            IrIntrinsic::UnsignedMin(_) | IrIntrinsic::BitZeros(_) => 0,
+            IrIntrinsic::ArchitectureByteSize => self.architecture_bits() as u128 / 8,
+            IrIntrinsic::ArchitectureBitSize => self.architecture_bits() as u128,
+            IrIntrinsic::ArchitectureBitPerByte => 8,
+            IrIntrinsic::InstructionByteSize => context.byte_size() as u128,
+            IrIntrinsic::ByteSizeOf(data) => self.width_of(data, context)?.div_ceil(8) as u128,
+            IrIntrinsic::BitSizeOf(data) => self.width_of(data, context)? as u128,
+            IrIntrinsic::Sized(data, size) => {
+                let bits = self.access_size(size, context)?;
+                self.evaluate(data, context, Some(bits))? & mask(bits)
+            }
+            IrIntrinsic::OperandExists(index) => {
+                (index.get() as usize <= context.instruction.inner.arguments.len()) as u128
+            }
+            IrIntrinsic::ArchitectureByteSizeCondition(condition) => {
+                let size = self.architecture_bits() as u16 / 8;
+                let holds = match *condition {
+                    NumCondition::Higher(x) => size > x,
+                    NumCondition::HigherOrEqual(x) => size >= x,
+                    NumCondition::Lower(x) => size < x,
+                    NumCondition::LowerOrEqual(x) => size <= x,
+                    NumCondition::Equal(x) => size == x,
+                    NumCondition::NotEqual(x) => size != x,
+                    NumCondition::RangeInclusive(from, to) => (from..=to).contains(&size),
+                    NumCondition::ExcludesRange(from, to) => !(from..=to).contains(&size),
+                };
+                holds as u128
+            }
+        };
+        Ok(value)
+    }
+
+    /// Writes a value to a register, memory or operand.
+    fn assign(
+        &mut self,
+        to: &Aos<IrData>,
+        value: u128,
+        bits: u32,
+        context: Context,
+    ) -> Result<(), InterpreterFault> {
+        match to.as_ref() {
+            IrData::Register(register) => {
+                self.vm.write_register(register, value & mask(bits));
+            }
+            IrData::Dereference(address) => {
+                let address = self.evaluate(address, context, None)? as u64;
+                let bytes = value.to_le_bytes();
+                self.vm
+                    .memory_mut()
+                    .write(address, &bytes[..bits.div_ceil(8).min(16) as usize]);
+            }
+            IrData::Operand(index) => {
+                let operand = operand(*index, context);
+                self.assign(&operand, value, bits, context.operand())?;
+            }
+            IrData::Intrinsic(IrIntrinsic::Sized(data, size)) => {
+                let bits = self.access_size(size, context)?;
+                self.assign(data, value, bits, context)?;
+            }
+            // writes to the zero register, or to discarded results
+            IrData::Constant(_)
+            | IrData::Intrinsic(IrIntrinsic::Unknown | IrIntrinsic::Undefined) => {}
+            IrData::Intrinsic(_) | IrData::Operation(_) => {
+                return Err(InterpreterFault::UnsupportedData(to.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a little endian value from memory.
+    fn load(&self, address: u64, bits: u32) -> Result<u128, InterpreterFault> {
+        let size = bits.div_ceil(8).min(16) as usize;
+        let Some(bytes) = self.vm.memory().read(address, size) else {
+            return Err(InterpreterFault::UnmappedMemory(address));
+        };
+        let mut buffer = [0u8; 16];
+        buffer[..size].copy_from_slice(&bytes);
+        Ok(u128::from_le_bytes(buffer))
+    }
+
+    /// Evaluates an access size into bits, between 1 and 128.
+    fn access_size(&self, size: &IrAccessSize, context: Context) -> Result<u32, InterpreterFault> {
+        let bits = match size {
+            IrAccessSize::ResultOfBit(data) => self.evaluate(data, context, None)? as u32,
+            IrAccessSize::ResultOfByte(data) => self.evaluate(data, context, None)? as u32 * 8,
+            IrAccessSize::RelativeWith(data) => self.width_of(data, context)?,
+            IrAccessSize::ArchitectureSize => self.architecture_bits(),
+            IrAccessSize::Unlimited => 128,
+        };
+        Ok(bits.clamp(1, 128))
+    }
+
+    /// Returns the size of data in bits.
+    ///
+    /// ### Note
+    /// - Memory operands carry no size, they are as wide as the first register operand of the
+    ///   instruction, or the architecture size without one (`movzx eax, byte ptr [rbx]` reads
+    ///   32 bits).
+    fn width_of(&self, data: &Aos<IrData>, context: Context) -> Result<u32, InterpreterFault> {
+        let bits = match data.as_ref() {
+            IrData::Register(register) => register.bit_len().min(128) as u32,
+            IrData::Operand(index) => {
+                let operand = operand(*index, context);
+                match operand.as_ref() {
+                    IrData::Dereference(_) => context
+                        .instruction
+                        .inner
+                        .arguments
+                        .iter()
+                        .map(Aos::<IrData>::from)
+                        .find_map(|x| x.register())
+                        .map(|x| x.bit_len().min(128) as u32)
+                        .unwrap_or(self.architecture_bits()),
+                    _ => self.width_of(&operand, context.operand())?,
+                }
+            }
+            IrData::Intrinsic(IrIntrinsic::Sized(_, size)) => self.access_size(size, context)?,
+            IrData::Operation(IrDataOperation::Unary { operator, arg }) => match operator {
+                IrUnaryOperator::Not | IrUnaryOperator::Negation => self.width_of(arg, context)?,
+                IrUnaryOperator::SignExtend | IrUnaryOperator::ZeroExtend => 128,
+            },
+            IrData::Operation(IrDataOperation::Binary {
+                operator,
+                arg1,
+                arg2,
+            }) => match operator {
+                IrBinaryOperator::Equal(_)
+                | IrBinaryOperator::SignedLess(_)
+                | IrBinaryOperator::SignedLessOrEqual(_)
+                | IrBinaryOperator::UnsignedLess(_)
+                | IrBinaryOperator::UnsignedLessOrEqual(_) => 1,
+                _ => self
+                    .width_of(arg1, context)?
+                    .max(self.width_of(arg2, context)?),
+            },
+            IrData::Constant(_) | IrData::Dereference(_) | IrData::Intrinsic(_) => {
+                self.architecture_bits()
+            }
+        };
+        Ok(bits)
+    }
+}
+
+/// Resolves an operand placeholder into the data of the instruction's operand.
+fn operand(index: NonZeroU8, context: Context) -> Aos<IrData> {
+    match context
+        .instruction
+        .inner
+        .arguments
+        .get(index.get() as usize - 1)
+    {
+        Some(argument) => argument.into(),
+        None => IrData::Intrinsic(IrIntrinsic::Undefined).into(),
+    }
+}
+
+/// Returns a mask of the low `bits` bits.
+fn mask(bits: u32) -> u128 {
+    if bits >= 128 {
+        u128::MAX
+    } else {
+        (1u128 << bits) - 1
+    }
+}
+
+/// Sign extends the low `bits` bits of a value.
+fn sign_extend(value: u128, bits: u32) -> i128 {
+    if bits >= 128 {
+        return value as i128;
+    }
+    let shift = 128 - bits;
+    ((value << shift) as i128) >> shift
+}
@@ -0,0 +1,117 @@
+//! Module containing the sparse memory of the `VirtualMachine`
+
+use crate::core::Sections;
+use hashbrown::HashMap;
+use std::sync::Arc;
+
+/// Size of the pages written memory is kept in
+const PAGE_SIZE: u64 = 0x1000;
+
+/// Memory only storing the pages written to, reading everything else from the sections of
+/// the loaded binary
+#[derive(Clone, Default)]
+pub struct SparseMemory {
+    /// Written pages by their start address
+    pages: HashMap<u64, Box<[u8]>>,
+    /// Sections and raw file data unwritten memory is read from
+    backing: Option<(Arc<Sections>, Arc<[u8]>)>,
+}
+
+impl SparseMemory {
+    /// Creates a memory backed by the sections of a binary.
+    ///
+    /// ### Arguments
+    /// - `sections: Arc<Sections>` - sections mapped into the memory
+    /// - `binary: &[u8]` - raw file data of the sections
+    ///
+    /// ### Returns
+    /// - `Self` - memory reading the section data until it is overwritten
+    pub fn from_sections(sections: Arc<Sections>, binary: &[u8]) -> Self {
+        Self {
+            pages: HashMap::new(),
+            backing: Some((sections, binary.into())),
+        }
+    }
+
+    /// Reads a byte.
+    ///
+    /// ### Arguments
+    /// - `address: u64` - virtual address to read
+    ///
+    /// ### Returns
+    /// - `Option<u8>` - the byte, or `None` if the address is neither written nor inside a section
+    ///
+    /// ### Note
+    /// - Section bytes past the file data (`.bss`) read as zero.
+    pub fn read_u8(&self, address: u64) -> Option<u8> {
+        let page = address & !(PAGE_SIZE - 1);
+        if let Some(data) = self.pages.get(&page) {
+            return Some(data[(address - page) as usize]);
+        }
+        let (sections, binary) = self.backing.as_ref()?;
+        let section = sections.from_virtual_address(address)?;
+        let offset = address - section.virtual_address;
+        if offset >= section.virtual_size.max(section.size_of_file) {
+            return None;
+        }
+        if offset >= section.size_of_file {
+            return Some(0);
+        }
+        binary.get((section.file_offset + offset) as usize).copied()
+    }
+
+    /// Reads consecutive bytes.
+    ///
+    /// ### Arguments
+    /// - `address: u64` - virtual address of the first byte
+    /// - `size: usize` - number of bytes to read
+    ///
+    /// ### Returns
+    /// - `Option<Vec<u8>>` - the bytes, or `None` if any of them is not mapped
+    pub fn read(&self, address: u64, size: usize) -> Option<Vec<u8>> {
+        (0..size as u64)
+            .map(|index| self.read_u8(address.wrapping_add(index)))
+            .collect()
+    }
+
+    /// Writes consecutive bytes, mapping the pages they are written to.
+    ///
+    /// ### Arguments
+    /// - `address: u64` - virtual address of the first byte
+    /// - `bytes: &[u8]` - bytes to write
+    pub fn write(&mut self, address: u64, bytes: &[u8]) {
+        for (index, byte) in bytes.iter().enumerate() {
+            let address = address.wrapping_add(index as u64);
+            let page = address & !(PAGE_SIZE - 1);
+            if !self.pages.contains_key(&page) {
+                // keep the bytes of the section the page overlaps
+                let data = (page..page + PAGE_SIZE)
+                    .map(|x| self.read_u8(x).unwrap_or(0))
+                    .collect();
+                self.pages.insert(page, data);
+            }
+            self.pages.get_mut(&page).unwrap()[(address - page) as usize] = *byte;
+        }
+    }
+
+    /// Returns whether the page holding a virtual address was written to.
+    pub fn is_written(&self, address: u64) -> bool {
+        self.pages.contains_key(&(address & !(PAGE_SIZE - 1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseMemory;
+
+    #[test]
+    fn write_and_read_back() {
+        let mut memory = SparseMemory::default();
+        assert_eq!(memory.read_u8(0x1000), None);
+        memory.write(0x1ffe, &[1, 2, 3, 4]);
+        assert_eq!(memory.read(0x1ffe, 4), Some(vec![1, 2, 3, 4]));
+        assert_eq!(memory.read_u8(0x2010), Some(0));
+        assert!(memory.is_written(0x2003));
+        assert!(!memory.is_written(0x3000));
+    }
+}
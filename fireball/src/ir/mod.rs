@@ -3,6 +3,8 @@
 pub mod analyze;
 pub mod arm;
 pub mod data;
+mod interpreter;
+mod memory;
 pub mod operator;
 mod register;
 pub mod statements;
@@ -18,6 +20,8 @@ use crate::{
     prelude::{BitBox, *},
     utils::error::ir_analyze_assertion_error::IrAnalyzeAssertionFailure,
 };
+pub use interpreter::{Interpreter, InterpreterFault, InterpreterFlow};
+pub use memory::SparseMemory;
 pub use register::Register;
 use statements::IrStatement;
 use std::{cell::UnsafeCell, sync::Arc};
@@ -26,10 +30,12 @@ use utils::IrStatementDescriptorMap;
 /// A structure to simulate the computer's behavior
 ///
 /// ### Todo
-/// - Handle memory transformations, file I/O, etc., in addition to register data.
+/// - Handle file I/O, etc., in addition to register and memory data.
 pub struct VirtualMachine {
     /// CPU registers storage (e.g., bits 0-64 for rax, 64-128 for rbx)
     register: UnsafeCell<BitBox>,
+    /// Memory the IR reads and writes through dereferences
+    memory: SparseMemory,
 }
 
 /// Basic interface for IR data behavior (e.g., file transformations); not yet implemented
@@ -42,6 +48,35 @@ impl VirtualMachine {
     pub fn get_raw_mut(&mut self) -> &mut BitBox {
         unsafe { &mut *self.register.get() }
     }
+    /// Returns the memory
+    pub fn memory(&self) -> &SparseMemory {
+        &self.memory
+    }
+    /// Returns the memory (mutable)
+    pub fn memory_mut(&mut self) -> &mut SparseMemory {
+        &mut self.memory
+    }
+    /// Reads the value of a register.
+    ///
+    /// ### Note
+    /// - Only the low 128 bits of wider registers (ymm, zmm) are read.
+    pub fn read_register(&self, register: &Register) -> u128 {
+        use bitvec::field::BitField;
+        let range = register.bit_range();
+        let bits = &self.get_raw()[range];
+        bits[..bits.len().min(128)].load_le::<u128>()
+    }
+    /// Writes the value of a register, truncated to the register size.
+    ///
+    /// ### Note
+    /// - Only the low 128 bits of wider registers (ymm, zmm) are written.
+    pub fn write_register(&mut self, register: &Register, value: u128) {
+        use bitvec::field::BitField;
+        let range = register.bit_range();
+        let bits = &mut self.get_raw_mut()[range];
+        let len = bits.len().min(128);
+        bits[..len].store_le::<u128>(value);
+    }
 }
 
 /// Structure that stores how IR instructions operate within a block
@@ -142,7 +177,8 @@ pub struct Ir {
     pub statements: Option<&'static [IrStatement]>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, serde::Serialize, serde::Deserialize)]
 pub enum Architecture {
     X64,
+    Arm64,
 }
@@ -3,14 +3,14 @@ use crate::{
     utils::Aos,
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum IrUnaryOperator {
     Not,
     Negation,
     SignExtend,
     ZeroExtend,
 }
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum IrBinaryOperator {
     And,
     Or,
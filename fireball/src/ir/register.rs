@@ -1,4 +1,4 @@
-use crate::ir::{Architecture, VirtualMachine, x86_64::X64Range};
+use crate::ir::{Architecture, VirtualMachine, arm::ArmRange, x86_64::X64Range};
 use std::{hash::Hasher, ops::Range, sync::LazyLock};
 
 #[derive(Debug, Clone, Eq, Copy)]
@@ -40,7 +40,6 @@ impl Register {
         self.name
     }
     pub fn is_stack_related(&self) -> bool {
-        use crate::ir::{Architecture, VirtualMachine, x86_64::X64Range};
         match self.architecture() {
             Architecture::X64 => {
                 static SP_BIT_START: LazyLock<usize> =
@@ -50,6 +49,14 @@ impl Register {
                 let register_start = self.bit_range().start;
                 *SP_BIT_START == register_start || *BP_BIT_START == register_start
             }
+            Architecture::Arm64 => {
+                static SP_BIT_START: LazyLock<usize> =
+                    LazyLock::new(|| <VirtualMachine as ArmRange>::sp().bit_range().start);
+                static FP_BIT_START: LazyLock<usize> =
+                    LazyLock::new(|| <VirtualMachine as ArmRange>::x29().bit_range().start);
+                let register_start = self.bit_range().start;
+                *SP_BIT_START == register_start || *FP_BIT_START == register_start
+            }
         }
     }
     pub const BP_MATCHLIST: LazyLock<Box<[Register]>> = LazyLock::new(|| {
@@ -58,6 +65,8 @@ impl Register {
             <VirtualMachine as X64Range>::ebp(),
             <VirtualMachine as X64Range>::bp(),
             <VirtualMachine as X64Range>::bpl(),
+            <VirtualMachine as ArmRange>::x29(),
+            <VirtualMachine as ArmRange>::w29(),
         ]
         .into()
     });
@@ -67,6 +76,8 @@ impl Register {
             <VirtualMachine as X64Range>::esp(),
             <VirtualMachine as X64Range>::sp(),
             <VirtualMachine as X64Range>::spl(),
+            <VirtualMachine as ArmRange>::sp(),
+            <VirtualMachine as ArmRange>::wsp(),
         ]
         .into()
     });
@@ -75,6 +86,7 @@ impl Register {
             <VirtualMachine as X64Range>::rip(),
             <VirtualMachine as X64Range>::eip(),
             <VirtualMachine as X64Range>::ip(),
+            <VirtualMachine as ArmRange>::pc(),
         ]
         .into()
     });
@@ -96,19 +108,47 @@ impl std::fmt::Display for Register {
 }
 impl PartialEq for Register {
     fn eq(&self, other: &Self) -> bool {
-        std::ptr::eq(self.name, other.name)
+        // names like `sp` are shared between architectures, and the same literal may be
+        // emitted at several addresses, so registers are told apart by their bits
+        self.architecture == other.architecture
+            && self.bit_start == other.bit_start
+            && self.bit_end == other.bit_end
     }
 }
 impl std::hash::Hash for Register {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.architecture.hash(state);
-        self.name.hash(state);
+        self.bit_start.hash(state);
+        self.bit_end.hash(state);
+    }
+}
+/// Registers are stored by their name, and looked up again when read so they stay the same as
+/// the lifted ones
+impl serde::Serialize for Register {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&(self.architecture, self.name), serializer)
+    }
+}
+impl<'de> serde::Deserialize<'de> for Register {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (architecture, name): (Architecture, String) =
+            serde::Deserialize::deserialize(deserializer)?;
+        let register = match architecture {
+            Architecture::X64 => crate::arch::x86_64::x64_register_by_name(&name),
+            Architecture::Arm64 => crate::arch::arm::arm_register_by_name(&name),
+        };
+        match register.as_deref() {
+            Some(crate::ir::data::IrData::Register(register)) => Ok(*register),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown register `{name}`"
+            ))),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ir::{VirtualMachine, x86_64::X64Range};
+    use crate::ir::{VirtualMachine, arm::ArmRange, x86_64::X64Range};
 
     #[test]
     fn test_stack_related() {
@@ -125,6 +165,18 @@ mod tests {
         assert!(!<VirtualMachine as X64Range>::xmm4().is_stack_related());
     }
     #[test]
+    fn test_arm_stack_related() {
+        assert!(<VirtualMachine as ArmRange>::sp().is_stack_related());
+        assert!(<VirtualMachine as ArmRange>::x29().is_stack_related());
+        assert!(!<VirtualMachine as ArmRange>::x0().is_stack_related());
+        assert!(<VirtualMachine as ArmRange>::sp().is_sp());
+        assert!(!<VirtualMachine as ArmRange>::sp().is_bp());
+        assert_ne!(
+            <VirtualMachine as ArmRange>::sp(),
+            <VirtualMachine as X64Range>::sp()
+        );
+    }
+    #[test]
     fn register_equals() {
         let one = <VirtualMachine as X64Range>::fpu_pe();
         let two = <VirtualMachine as X64Range>::fpu_pe();
@@ -2,19 +2,19 @@
 
 use crate::{
     ir::data::{IrAccessSize, IrData, IrDataContainable},
-    utils::Aos,
+    utils::{Aos, static_str::StaticStr},
 };
 
 /// Enum representing each IR statement
 ///
 /// ### Note
 /// snowman's expressions.h, StatementBase based classes, or snowman's ir::statement.h classes
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum IrStatement {
     /// Undefined statement
     Undefined,
     /// Exception occurred
-    Exception(&'static str),
+    Exception(#[serde(with = "crate::utils::static_str")] StaticStr),
     /// Variable assignment
     Assignment {
         from: Aos<IrData>,
@@ -40,7 +40,7 @@ pub enum IrStatement {
     Special(IrStatementSpecial),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum IrStatementSpecial {
     TypeSpecified {
         location: Aos<IrData>,
@@ -56,6 +56,23 @@ pub struct IrStatementDescriptorMap<T> {
     inner: HashMap<u64, T>,
 }
 
+/// Entries are stored in descriptor order, so saving the same map gives the same bytes
+impl<T: serde::Serialize> serde::Serialize for IrStatementDescriptorMap<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entries: Vec<_> = self.inner.iter().collect();
+        entries.sort_unstable_by_key(|(descriptor, _)| **descriptor);
+        serde::Serialize::serialize(&entries, serializer)
+    }
+}
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for IrStatementDescriptorMap<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries: Vec<(u64, T)> = serde::Deserialize::deserialize(deserializer)?;
+        Ok(Self {
+            inner: entries.into_iter().collect(),
+        })
+    }
+}
+
 impl<T> IrStatementDescriptorMap<T> {
     #[inline]
     pub fn new() -> Self {
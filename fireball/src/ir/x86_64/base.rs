@@ -23,6 +23,7 @@ impl X64 for VirtualMachine {
         register.resize(192 * 64, false);
         Self {
             register: UnsafeCell::new(register.into_boxed_bitslice()),
+            memory: Default::default(),
         }
     }
 
@@ -3,12 +3,17 @@
 pub mod abstract_syntax_tree;
 pub mod arch;
 pub mod core;
+pub mod debug_info;
 pub mod elf;
+#[cfg(feature = "unicorn")]
+pub mod emulation;
+pub mod exception_handling;
 pub mod ir;
 pub mod macho;
 pub mod pdb;
 pub mod pe;
 pub mod prelude;
+pub mod project;
 #[cfg(test)]
 pub mod tests;
 pub mod utils;
@@ -99,6 +104,17 @@ impl Fireball {
         }
     }
 
+    /// Loads the symbols and debug information of a PDB file for a PE binary.
+    ///
+    /// ### Note
+    /// - Only PE binaries have program databases; other formats return `UnsupportedFormat`.
+    pub fn load_pdb(&mut self, path: &str) -> Result<(), FireballError> {
+        match self {
+            Fireball::Pe(pe) => pe.load_pdb(path),
+            _ => Err(FireballError::UnsupportedFormat),
+        }
+    }
+
     pub fn cancel_analysis(&self) {
         dispatch!(self, cancel_analysis);
     }
@@ -106,6 +122,33 @@ impl Fireball {
     pub fn reset_analysis_cancellation(&self) {
         dispatch!(self, reset_analysis_cancellation);
     }
+
+    /// Saves the analysis state of the binary to a project file.
+    ///
+    /// ### Arguments
+    /// - `path` - file to write
+    /// - `ast` - ast to keep along, with every version of its functions
+    /// - `annotations` - annotations to keep along
+    pub fn save_project(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        ast: Option<&abstract_syntax_tree::Ast>,
+        annotations: &abstract_syntax_tree::AstAnnotations,
+    ) -> Result<(), FireballError> {
+        project::save(self, path.as_ref(), ast, annotations)
+    }
+
+    /// Replaces the analysis state of the binary by the one saved in a project file.
+    ///
+    /// ### Note
+    /// - Returns `StaleProject` if the project was saved for another binary.
+    /// - A project failing to load leaves the analysis state untouched.
+    pub fn load_project(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<project::Project, FireballError> {
+        project::load(self, path.as_ref())
+    }
 }
 
 impl Fire for Fireball {
@@ -182,4 +225,31 @@ impl FireRaw for Fireball {
     fn get_relations(&self) -> std::sync::Arc<core::Relations> {
         dispatch!(self, get_relations)
     }
+
+    fn get_calling_convention(&self) -> abstract_syntax_tree::AstCallingConvention {
+        dispatch!(self, get_calling_convention)
+    }
+
+    fn get_debug_info(&self) -> std::sync::Arc<debug_info::DebugInfo> {
+        dispatch!(self, get_debug_info)
+    }
+
+    fn get_architecture(&self) -> Option<iceball::MachineArchitecture> {
+        dispatch!(self, get_architecture)
+    }
+
+    fn get_kind(&self) -> crate::BinaryKind {
+        dispatch!(self, get_kind)
+    }
+
+    fn get_binary_mut(&mut self) -> &mut Vec<u8> {
+        dispatch!(self, get_binary_mut)
+    }
+
+    fn generate_ast(
+        &self,
+        blocks: Vec<std::sync::Arc<core::Block>>,
+    ) -> Result<abstract_syntax_tree::Ast, prelude::DecompileError> {
+        dispatch!(self, generate_ast, blocks)
+    }
 }
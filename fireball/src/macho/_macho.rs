@@ -3,7 +3,12 @@
 use super::MachO;
 use crate::{
     BinaryKind, arch,
-    core::{Address, Blocks, PreDefinedOffset, PreDefinedOffsets, Relations, Sections},
+    core::{
+        Address, Blocks, NoReturnFunctions, PreDefinedOffset, PreDefinedOffsets, Relations,
+        Sections,
+    },
+    debug_info::{self, DebugInfo},
+    exception_handling::load_eh_frame_exception_handlers,
     prelude::*,
 };
 use iceball::MachineArchitecture;
@@ -24,12 +29,21 @@ impl MachO {
         // Extract architecture and entry point from the Mach-O header.
         // We parse inside a block so the borrow of `binary` is released
         // before we move `binary` into the struct.
-        let (cputype, filetype, entry_addr, symbols_info) = {
+        let (cputype, filetype, entry_addr, symbols_info, debug_info) = {
             let mach = goblin::mach::Mach::parse(&binary)
                 .map_err(|e| FireballError::MachOParsingFailed(e.to_string()))?;
 
             match mach {
-                goblin::mach::Mach::Binary(ref macho) => extract_macho_info(macho),
+                goblin::mach::Mach::Binary(ref macho) => {
+                    let (cputype, filetype, entry_addr, symbols_info) = extract_macho_info(macho);
+                    let debug_info = match arch::from_mach_cputype(cputype) {
+                        Some(architecture) => {
+                            debug_info::load_macho_dwarf(macho, path.as_deref(), architecture)
+                        }
+                        None => DebugInfo::default(),
+                    };
+                    (cputype, filetype, entry_addr, symbols_info, debug_info)
+                }
                 goblin::mach::Mach::Fat(ref multi) => {
                     // For fat binaries, iterate through all architecture slices
                     // and try to parse each one until we find a valid one.
@@ -106,6 +120,20 @@ impl MachO {
             defined
         };
 
+        let address_size = match architecture {
+            MachineArchitecture::X64 | MachineArchitecture::Arm64 => 8,
+            MachineArchitecture::X86 | MachineArchitecture::Arm => 4,
+        };
+        let exception_handlers = load_eh_frame_exception_handlers(
+            &binary,
+            &sections,
+            "__TEXT,__eh_frame",
+            gimli::RunTimeEndian::Little,
+            address_size,
+            &defined,
+        );
+
+        let noreturn = NoReturnFunctions::new(&defined);
         let relations = Relations::new();
         Ok(MachO {
             kind,
@@ -118,6 +146,9 @@ impl MachO {
             sections,
             relations: relations.clone(),
             blocks: Blocks::new(relations),
+            debug_info: std::sync::Arc::new(debug_info),
+            exception_handlers: std::sync::Arc::new(exception_handlers),
+            noreturn: std::sync::Arc::new(noreturn),
             cancel_token: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
         })
     }
@@ -96,6 +96,12 @@ impl MachO {
                     }
                     if let Some(argument) = Self::parse_argument_lossy(parser_architecture, &op) {
                         arguments.push(argument);
+                    } else if let Some(shifted) = arguments
+                        .last()
+                        .and_then(|x| iceball::arm::apply_shift(x, &op))
+                    {
+                        // `lsl #2` of `add x0, x1, x2, lsl #2` applies to the operand before it
+                        *arguments.last_mut().unwrap() = shifted;
                     } else {
                         warn!(
                             "Failed to parse argument `{}` at {:#x}; dropping operand",
@@ -105,6 +111,14 @@ impl MachO {
                     }
                 }
             }
+            // `b.<cond>` carries its condition in the mnemonic, keep it as an operand
+            if matches!(
+                parser_architecture,
+                iceball::MachineArchitecture::Arm | iceball::MachineArchitecture::Arm64
+            ) && let Some(condition) = iceball::arm::mnemonic_condition(mnemonic)
+            {
+                arguments.push(iceball::Argument::Condition(condition));
+            }
             let bytes = Some(item.bytes().into());
             let data = Instruction {
                 address: item.address(),
@@ -8,7 +8,9 @@ mod analyze_from_virtual_address;
 
 use super::MachO;
 use crate::{
+    abstract_syntax_tree::{Ast, AstCallingConvention},
     core::{Address, Block, Blocks, Fire, FireRaw, PreDefinedOffsets, Relations, Sections},
+    debug_info::DebugInfo,
     prelude::DecompileError,
 };
 use std::sync::Arc;
@@ -24,38 +26,22 @@ impl Fire for MachO {
 
     fn decompile_all(&self) -> Result<String, DecompileError> {
         let blocks = self.analyze_all()?;
-        Ok(
-            crate::ir::analyze::generate_ast_with_pre_defined_symbols(blocks, self.get_defined())?
-                .optimize(None)?
-                .print(None),
-        )
+        Ok(self.generate_ast(blocks)?.optimize(None)?.print(None))
     }
 
     fn decompile_from_entry(&self) -> Result<String, DecompileError> {
         let block = self.analyze_from_entry()?;
-        Ok(
-            crate::ir::analyze::generate_ast_with_pre_defined_symbols([block], self.get_defined())?
-                .optimize(None)?
-                .print(None),
-        )
+        Ok(self.generate_ast(vec![block])?.optimize(None)?.print(None))
     }
 
     fn decompile_from_file_offset(&self, address: u64) -> Result<String, DecompileError> {
         let block = self.analyze_from_file_offset(address)?;
-        Ok(
-            crate::ir::analyze::generate_ast_with_pre_defined_symbols([block], self.get_defined())?
-                .optimize(None)?
-                .print(None),
-        )
+        Ok(self.generate_ast(vec![block])?.optimize(None)?.print(None))
     }
 
     fn decompile_from_virtual_address(&self, address: u64) -> Result<String, DecompileError> {
         let block = self.analyze_from_virtual_address(address)?;
-        Ok(
-            crate::ir::analyze::generate_ast_with_pre_defined_symbols([block], self.get_defined())?
-                .optimize(None)?
-                .print(None),
-        )
+        Ok(self.generate_ast(vec![block])?.optimize(None)?.print(None))
     }
 }
 
@@ -95,4 +81,33 @@ impl FireRaw for MachO {
     fn get_relations(&self) -> Arc<Relations> {
         self.relations.clone()
     }
+
+    fn get_calling_convention(&self) -> AstCallingConvention {
+        AstCallingConvention::platform_default(self.architecture, false)
+    }
+
+    fn get_debug_info(&self) -> Arc<DebugInfo> {
+        self.debug_info.clone()
+    }
+
+    fn get_architecture(&self) -> Option<iceball::MachineArchitecture> {
+        Some(self.architecture)
+    }
+
+    fn get_kind(&self) -> crate::BinaryKind {
+        self.kind
+    }
+
+    fn get_binary_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.binary
+    }
+
+    fn generate_ast(&self, blocks: Vec<Arc<Block>>) -> Result<Ast, DecompileError> {
+        let mut ast =
+            crate::ir::analyze::generate_ast_with_pre_defined_symbols(blocks, self.get_defined())?;
+        ast.set_calling_convention(self.get_calling_convention());
+        ast.set_architecture(self.architecture());
+        ast.set_debug_info(self.get_debug_info());
+        Ok(ast)
+    }
 }
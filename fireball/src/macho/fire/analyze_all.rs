@@ -20,7 +20,8 @@ impl MachO {
             "Full-program block analysis started"
         );
 
-        let mut queue: Vec<_> = seeds;
+        // Seeds are popped from the back, so reverse them to analyze the first one first
+        let mut queue: Vec<_> = seeds.into_iter().rev().collect();
         let mut visited = HashSet::new();
         let mut result = Vec::new();
 
@@ -36,7 +37,14 @@ impl MachO {
             if !visited.insert(current_va) {
                 continue;
             }
-            let block = self.analyze_block(&address)?;
+            // Discovered seeds may turn out not to be code
+            let block = match self.analyze_block(&address) {
+                Ok(block) => block,
+                Err(e) => {
+                    tracing::warn!(?e, va = current_va, "Skipping block that failed to analyze");
+                    continue;
+                }
+            };
             result.push(block.clone());
             let mut discovered = block
                 .get_connected_to()
@@ -60,21 +68,25 @@ impl MachO {
 
     /// Collect starting addresses for BFS traversal based on binary kind.
     fn collect_analysis_seeds(&self) -> Vec<crate::core::Address> {
-        use crate::core::Address;
+        use crate::core::{Address, discover_functions};
 
+        let mut seeds = Vec::new();
+        let mut seen = HashSet::new();
         match self.kind {
             BinaryKind::Executable => {
-                vec![self.entry.clone()]
+                seen.insert(self.entry.get_virtual_address());
+                seeds.push(self.entry.clone());
             }
             BinaryKind::SharedLibrary | BinaryKind::ObjectFile | BinaryKind::DebugInfo => {
-                let mut seeds = Vec::new();
-                let mut seen = HashSet::new();
-
-                // Use symbols as seeds
+                // Use symbols as seeds, skipping data (e.g., non-lazy pointers)
                 let reader = self.defined.get_reader();
                 for def in reader.iter() {
                     let va = def.address.get_virtual_address();
-                    if va != 0 && seen.insert(va) {
+                    let is_code = def
+                        .address
+                        .get_section()
+                        .is_some_and(|section| section.is_executable());
+                    if va != 0 && is_code && seen.insert(va) {
                         seeds.push(def.address.clone());
                     }
                 }
@@ -86,21 +98,93 @@ impl MachO {
                         seeds.push(self.entry.clone());
                     }
                 }
+            }
+        }
 
-                // Fallback: executable section starts
-                if seeds.is_empty() {
-                    for section in self.sections.all() {
-                        if section.is_executable() && seen.insert(section.virtual_address) {
-                            seeds.push(Address::from_virtual_address(
-                                &self.sections,
-                                section.virtual_address,
-                            ));
-                        }
-                    }
+        // Functions only reached through pointers (e.g., vtables) are found by
+        // the unwind table and by scanning the code
+        let discovered = self
+            .function_table_starts()
+            .into_iter()
+            .chain(discover_functions(
+                &self.binary,
+                &self.sections,
+                self.architecture,
+                &self.capstone,
+            ));
+        for va in discovered {
+            if seen.insert(va) {
+                seeds.push(Address::from_virtual_address(&self.sections, va));
+            }
+        }
+
+        // Fallback: executable section starts
+        if seeds.is_empty() {
+            for section in self.sections.all() {
+                if section.is_executable() && seen.insert(section.virtual_address) {
+                    seeds.push(Address::from_virtual_address(
+                        &self.sections,
+                        section.virtual_address,
+                    ));
                 }
+            }
+        }
+
+        seeds
+    }
+
+    /// Collect function starts from the `LC_FUNCTION_STARTS` load command.
+    fn function_table_starts(&self) -> Vec<u64> {
+        use goblin::mach::load_command::CommandVariant;
 
-                seeds
+        let Ok(macho) = goblin::mach::MachO::parse(&self.binary, 0) else {
+            return Vec::new();
+        };
+        let Some(text) = macho
+            .segments
+            .iter()
+            .find(|segment| segment.name().is_ok_and(|name| name == "__TEXT"))
+        else {
+            return Vec::new();
+        };
+        let Some(data) = macho
+            .load_commands
+            .iter()
+            .find_map(|command| match &command.command {
+                CommandVariant::FunctionStarts(command) => {
+                    let start = command.dataoff as usize;
+                    self.binary.get(start..start + command.datasize as usize)
+                }
+                _ => None,
+            })
+        else {
+            return Vec::new();
+        };
+
+        // Starts are ULEB128 deltas from the previous start, beginning at __TEXT,
+        // and the list ends at a zero delta
+        let mut starts = Vec::new();
+        let mut address = text.vmaddr;
+        let mut delta = 0u64;
+        let mut shift = 0;
+        for &byte in data {
+            delta |= u64::from(byte & 0x7F).checked_shl(shift).unwrap_or(0);
+            shift += 7;
+            if byte & 0x80 != 0 {
+                continue;
+            }
+            if delta == 0 {
+                break;
             }
+            // Corrupted deltas would wrap around the address space
+            let Some(next) = address.checked_add(delta) else {
+                break;
+            };
+            address = next;
+            starts.push(address);
+            delta = 0;
+            shift = 0;
         }
+        starts
     }
 }
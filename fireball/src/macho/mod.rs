@@ -8,7 +8,9 @@ mod fmt;
 
 use crate::{
     BinaryKind,
-    core::{Address, Blocks, PreDefinedOffsets, Relations, Sections},
+    core::{Address, Blocks, NoReturnFunctions, PreDefinedOffsets, Relations, Sections},
+    debug_info::DebugInfo,
+    exception_handling::ExceptionHandlers,
 };
 use iceball::MachineArchitecture;
 use std::{
@@ -38,6 +40,12 @@ pub struct MachO {
     blocks: Arc<Blocks>,
     /// Block relation information data
     relations: Arc<Relations>,
+    /// Debug information (DWARF) of the binary
+    debug_info: Arc<DebugInfo>,
+    /// Exception handlers read from the unwind information
+    exception_handlers: Arc<ExceptionHandlers>,
+    /// Functions found to never return
+    noreturn: Arc<NoReturnFunctions>,
     /// Cooperative cancellation flag for long-running analysis
     cancel_token: Arc<AtomicBool>,
 }
@@ -7,10 +7,12 @@
 use super::StandalonePdb;
 use crate::{
     BinaryKind,
+    abstract_syntax_tree::AstCallingConvention,
     core::{
         Address, Block, Blocks, Fire, FireRaw, PreDefinedOffset, PreDefinedOffsets, Relations,
         Sections,
     },
+    debug_info::DebugInfo,
     prelude::*,
 };
 use pdb::FallibleIterator;
@@ -130,6 +132,33 @@ impl FireRaw for StandalonePdb {
     fn get_relations(&self) -> Arc<Relations> {
         self.relations.clone()
     }
+
+    fn get_calling_convention(&self) -> AstCallingConvention {
+        AstCallingConvention::Unknown
+    }
+
+    fn get_debug_info(&self) -> Arc<DebugInfo> {
+        Arc::new(DebugInfo::default())
+    }
+
+    fn get_architecture(&self) -> Option<iceball::MachineArchitecture> {
+        None
+    }
+
+    fn get_kind(&self) -> crate::BinaryKind {
+        self.kind()
+    }
+
+    fn get_binary_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.binary
+    }
+
+    fn generate_ast(
+        &self,
+        blocks: Vec<Arc<Block>>,
+    ) -> Result<crate::abstract_syntax_tree::Ast, DecompileError> {
+        crate::ir::analyze::generate_ast(blocks)
+    }
 }
 
 // ---------------------------------------------------------------------------
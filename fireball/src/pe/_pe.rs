@@ -3,10 +3,18 @@
 use super::Pe;
 use crate::{
     BinaryKind, arch,
-    core::{Address, Blocks, PreDefinedOffset, PreDefinedOffsets, Relations, Sections},
+    core::{
+        Address, Blocks, NoReturnFunctions, PreDefinedOffset, PreDefinedOffsets, Relations,
+        Sections,
+    },
+    debug_info::{PdbDebugInfo, load_pdb, pdb_matches},
+    elf::demangle_symbol,
+    exception_handling::load_pe_exception_handlers,
     prelude::*,
 };
-use std::sync::atomic::Ordering;
+use goblin::pe::debug::CodeviewPDB70DebugInfo;
+use hashbrown::HashSet;
+use std::sync::{Arc, atomic::Ordering};
 
 impl Pe {
     pub fn from_path(path: &str) -> Result<Pe, FireballError> {
@@ -19,10 +27,17 @@ impl Pe {
     }
 
     /// Creates a PE struct from binary data.
+    ///
+    /// ### Note
+    /// - COFF objects (without the MZ header) are laid out at synthetic addresses, see
+    ///   `Pe::new_object`.
     pub(crate) fn new(path: Option<String>, binary: Vec<u8>) -> Result<Self, FireballError> {
         // 1. Build section information
         // 2. Create Capstone object
         // 3. Generate predefined binary offset information
+        if !binary.starts_with(b"MZ") {
+            return Self::new_object(path, binary);
+        }
 
         // Common objects used throughout
         let gl = goblin::pe::PE::parse(&binary)?;
@@ -43,6 +58,16 @@ impl Pe {
         // Create Capstone object
         let capstone = arch::build_capstone(architecture)?;
 
+        // Find the program database the linker wrote along with the binary
+        let pdb = gl
+            .debug_data
+            .as_ref()
+            .and_then(|x| x.codeview_pdb70_debug_info.as_ref())
+            .and_then(|x| find_pdb(path.as_deref(), x));
+
+        // Read the exception handlers from the unwind tables
+        let exception_handlers = load_pe_exception_handlers(&gl, &binary, &sections, architecture);
+
         // Generate predefined binary offset information
         let defined = {
             let defined = PreDefinedOffsets::new();
@@ -70,19 +95,7 @@ impl Pe {
                 };
 
                 let name = if let Some(name) = export.name {
-                    // Try C++ demangling for exported symbols
-                    if let Ok(sym) = cpp_demangle::Symbol::new(name) {
-                        sym.demangle().unwrap_or_else(|_| name.to_string())
-                    } else {
-                        // Try Rust demangling as fallback
-                        let demangled = rustc_demangle::demangle(name);
-                        let demangled_str = demangled.to_string();
-                        if demangled_str != name {
-                            demangled_str
-                        } else {
-                            name.to_string()
-                        }
-                    }
+                    demangle_symbol(name)
                 } else {
                     format!("0x{:x}", offset_raw)
                 };
@@ -97,6 +110,19 @@ impl Pe {
             defined
         };
 
+        let debug_info = match pdb.map(|x| load_pdb(x, architecture)) {
+            Some(Ok(pdb)) => {
+                insert_pdb_symbols(&defined, &sections, &pdb);
+                Arc::new(pdb.debug_info)
+            }
+            Some(Err(e)) => {
+                warn!("Failed to read the program database: {e}");
+                Default::default()
+            }
+            None => Default::default(),
+        };
+
+        let noreturn = NoReturnFunctions::new(&defined);
         let relations = Relations::new();
         Ok(Pe {
             kind,
@@ -109,10 +135,38 @@ impl Pe {
             sections,
             relations: relations.clone(),
             blocks: Blocks::new(relations),
+            debug_info,
+            exception_handlers: Arc::new(exception_handlers),
+            noreturn: Arc::new(noreturn),
             cancel_token: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
         })
     }
 
+    /// Loads the symbols and debug information of a program database (PDB) file.
+    ///
+    /// ### Arguments
+    /// - `path: &str` - path of the PDB file
+    ///
+    /// ### Note
+    /// - A PDB not matching the CodeView record of the binary is loaded with a warning.
+    /// - Matching PDBs next to the binary are loaded when it is created.
+    pub fn load_pdb(&mut self, path: &str) -> Result<(), FireballError> {
+        let data = std::fs::read(path)?;
+        let gl = goblin::pe::PE::parse(&self.binary)?;
+        match gl
+            .debug_data
+            .as_ref()
+            .and_then(|x| x.codeview_pdb70_debug_info.as_ref())
+        {
+            Some(codeview) if pdb_matches(&data, &codeview.signature, codeview.age) => {}
+            _ => warn!("{path} does not match the CodeView record of the binary"),
+        }
+        let pdb = load_pdb(data, self.architecture)?;
+        insert_pdb_symbols(&self.defined, &self.sections, &pdb);
+        self.debug_info = Arc::new(pdb.debug_info);
+        Ok(())
+    }
+
     pub fn kind(&self) -> BinaryKind {
         self.kind
     }
@@ -133,3 +187,51 @@ impl Pe {
         self.cancel_token.store(false, Ordering::Relaxed);
     }
 }
+
+/// Returns the data of the PDB file matching a CodeView record.
+///
+/// ### Arguments
+/// - `path: Option<&str>` - path of the binary
+/// - `codeview: &CodeviewPDB70DebugInfo` - CodeView record of the binary
+///
+/// ### Returns
+/// - `Option<Vec<u8>>` - the data of the first matching file of the path written by the linker,
+///   the file of that name next to the binary, and the binary path with a `.pdb` extension
+fn find_pdb(path: Option<&str>, codeview: &CodeviewPDB70DebugInfo) -> Option<Vec<u8>> {
+    let filename = codeview.filename.split(|x| *x == 0).next()?;
+    let filename = String::from_utf8_lossy(filename).into_owned();
+    let mut candidates = vec![std::path::PathBuf::from(&filename)];
+    if let Some(path) = path.map(std::path::Path::new) {
+        // the linker writes a Windows path
+        let basename = filename.rsplit(['\\', '/']).next().unwrap_or_default();
+        if let Some(directory) = path.parent() {
+            candidates.push(directory.join(basename));
+        }
+        candidates.push(path.with_extension("pdb"));
+    }
+    candidates.into_iter().find_map(|candidate| {
+        let data = std::fs::read(&candidate).ok()?;
+        let matches = pdb_matches(&data, &codeview.signature, codeview.age);
+        if matches {
+            info!("Loading program database {}", candidate.display());
+        }
+        matches.then_some(data)
+    })
+}
+
+/// Inserts the symbols of a PDB at addresses no export or other symbol named yet.
+fn insert_pdb_symbols(defined: &PreDefinedOffsets, sections: &Sections, pdb: &PdbDebugInfo) {
+    let mut named: HashSet<u64> = defined
+        .get_reader()
+        .iter()
+        .map(|x| x.address.get_virtual_address())
+        .collect();
+    for (name, rva) in &pdb.symbols {
+        if named.insert(*rva) {
+            defined.insert(PreDefinedOffset {
+                name: demangle_symbol(name),
+                address: Address::from_virtual_address(sections, *rva),
+            });
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::Pe;
 use crate::{BinaryKind, FireRaw, core::Block, prelude::DecompileError};
+use iceball::MachineArchitecture;
 use std::{
     collections::HashSet,
     sync::{Arc, atomic::Ordering},
@@ -20,7 +21,8 @@ impl Pe {
             "Full-program block analysis started"
         );
 
-        let mut queue: Vec<_> = seeds;
+        // Seeds are popped from the back, so reverse them to analyze the first one first
+        let mut queue: Vec<_> = seeds.into_iter().rev().collect();
         let mut visited = HashSet::new();
         let mut result = Vec::new();
 
@@ -36,7 +38,14 @@ impl Pe {
             if !visited.insert(current_va) {
                 continue;
             }
-            let block = self.analyze_block(&address)?;
+            // Discovered seeds may turn out not to be code
+            let block = match self.analyze_block(&address) {
+                Ok(block) => block,
+                Err(e) => {
+                    tracing::warn!(?e, va = current_va, "Skipping block that failed to analyze");
+                    continue;
+                }
+            };
             result.push(block.clone());
             let mut discovered = block
                 .get_connected_to()
@@ -60,21 +69,25 @@ impl Pe {
 
     /// Collect starting addresses for BFS traversal based on binary kind.
     fn collect_analysis_seeds(&self) -> Vec<crate::core::Address> {
-        use crate::core::Address;
+        use crate::core::{Address, discover_functions};
 
+        let mut seeds = Vec::new();
+        let mut seen = HashSet::new();
         match self.kind {
             BinaryKind::Executable => {
-                vec![self.entry.clone()]
+                seen.insert(self.entry.get_virtual_address());
+                seeds.push(self.entry.clone());
             }
             BinaryKind::SharedLibrary | BinaryKind::ObjectFile | BinaryKind::DebugInfo => {
-                let mut seeds = Vec::new();
-                let mut seen = HashSet::new();
-
-                // Use exports/symbols as seeds
+                // Use exports/symbols as seeds, skipping data (e.g., IAT slots)
                 let reader = self.defined.get_reader();
                 for def in reader.iter() {
                     let va = def.address.get_virtual_address();
-                    if va != 0 && seen.insert(va) {
+                    let is_code = def
+                        .address
+                        .get_section()
+                        .is_some_and(|section| section.is_executable());
+                    if va != 0 && is_code && seen.insert(va) {
                         seeds.push(def.address.clone());
                     }
                 }
@@ -86,21 +99,63 @@ impl Pe {
                         seeds.push(self.entry.clone());
                     }
                 }
+            }
+        }
 
-                // Fallback: executable section starts
-                if seeds.is_empty() {
-                    for section in self.sections.all() {
-                        if section.is_executable() && seen.insert(section.virtual_address) {
-                            seeds.push(Address::from_virtual_address(
-                                &self.sections,
-                                section.virtual_address,
-                            ));
-                        }
-                    }
-                }
+        // Functions only reached through pointers (e.g., vtables) are found by
+        // the unwind table and by scanning the code
+        let discovered = self
+            .function_table_starts()
+            .into_iter()
+            .chain(discover_functions(
+                &self.binary,
+                &self.sections,
+                self.architecture,
+                &self.capstone,
+            ));
+        for va in discovered {
+            if seen.insert(va) {
+                seeds.push(Address::from_virtual_address(&self.sections, va));
+            }
+        }
 
-                seeds
+        // Fallback: executable section starts
+        if seeds.is_empty() {
+            for section in self.sections.all() {
+                if section.is_executable() && seen.insert(section.virtual_address) {
+                    seeds.push(Address::from_virtual_address(
+                        &self.sections,
+                        section.virtual_address,
+                    ));
+                }
             }
         }
+
+        seeds
+    }
+
+    /// Collect function starts listed in the exception directory (`.pdata`).
+    fn function_table_starts(&self) -> Vec<u64> {
+        let Ok(gl) = goblin::pe::PE::parse(&self.binary) else {
+            return Vec::new();
+        };
+        let Some(exception_data) = gl.exception_data else {
+            return Vec::new();
+        };
+
+        // Begin addresses are relative virtual addresses, as are section addresses
+        if self.architecture == MachineArchitecture::Arm64 {
+            exception_data
+                .functions_arm64()
+                .filter_map(Result::ok)
+                .map(|function| function.begin_address as u64)
+                .collect()
+        } else {
+            exception_data
+                .functions()
+                .filter_map(Result::ok)
+                .map(|function| function.begin_address as u64)
+                .collect()
+        }
     }
 }
@@ -5,10 +5,13 @@ mod asm;
 mod block;
 mod fire;
 mod fmt;
+mod object;
 
 use crate::{
     BinaryKind,
-    core::{Address, Blocks, PreDefinedOffsets, Relations, Sections},
+    core::{Address, Blocks, NoReturnFunctions, PreDefinedOffsets, Relations, Sections},
+    debug_info::DebugInfo,
+    exception_handling::ExceptionHandlers,
 };
 use iceball::MachineArchitecture;
 use std::{
@@ -23,7 +26,7 @@ pub struct Pe {
     entry: Address,
     /// File path
     path: Option<String>,
-    /// Binary data
+    /// Binary data, with the relocations of an object file applied
     binary: Vec<u8>,
     /// Detected instruction-set architecture for parsing and IR lowering.
     architecture: MachineArchitecture,
@@ -38,6 +41,12 @@ pub struct Pe {
     blocks: Arc<Blocks>,
     /// Block relation information data
     relations: Arc<Relations>,
+    /// Debug information of the program database (PDB) matching the binary
+    debug_info: Arc<DebugInfo>,
+    /// Exception handlers read from the unwind information
+    exception_handlers: Arc<ExceptionHandlers>,
+    /// Functions found to never return
+    noreturn: Arc<NoReturnFunctions>,
     /// Cooperative cancellation flag for long-running analysis
     cancel_token: Arc<AtomicBool>,
 }
@@ -0,0 +1,211 @@
+//! Module laying out COFF objects and resolving their relocations
+
+use super::Pe;
+use crate::{
+    BinaryKind, arch,
+    core::{
+        Address, Blocks, NoReturnFunctions, PreDefinedOffset, PreDefinedOffsets, Relations,
+        Sections,
+    },
+    elf::demangle_symbol,
+    prelude::*,
+};
+use hashbrown::HashMap;
+use iceball::MachineArchitecture;
+
+/// Size of each synthetic slot given to undefined symbols
+const EXTERN_SLOT_SIZE: u64 = 8;
+/// Complex type of function symbols (`IMAGE_SYM_DTYPE_FUNCTION << 4`)
+const IMAGE_SYM_TYPE_FUNCTION: u16 = 0x20;
+/// Storage class of symbols visible outside the object
+const IMAGE_SYM_CLASS_EXTERNAL: u8 = 2;
+/// Prefix of the import address slots referenced by `__declspec(dllimport)` calls
+const IMPORT_PREFIX: &str = "__imp_";
+
+// x86-64 relocation types
+const IMAGE_REL_AMD64_ADDR64: u16 = 0x0001;
+const IMAGE_REL_AMD64_ADDR32: u16 = 0x0002;
+const IMAGE_REL_AMD64_ADDR32NB: u16 = 0x0003;
+const IMAGE_REL_AMD64_REL32: u16 = 0x0004;
+const IMAGE_REL_AMD64_REL32_5: u16 = 0x0009;
+
+impl Pe {
+    /// Creates a PE struct from a COFF object.
+    ///
+    /// ### Note
+    /// - Sections are laid out at distinct addresses, undefined symbols get synthetic slots after
+    ///   them, and x86-64 relocations are applied to the kept binary data. Other architectures
+    ///   keep their unrelocated bytes with a warning.
+    /// - Functions defined by the object are the analysis seeds, there is no entry point.
+    pub(super) fn new_object(
+        path: Option<String>,
+        mut binary: Vec<u8>,
+    ) -> Result<Self, FireballError> {
+        let coff = goblin::pe::Coff::parse(&binary)?;
+        let machine = coff.header.machine;
+        let is_64 = matches!(
+            machine,
+            goblin::pe::header::COFF_MACHINE_X86_64 | goblin::pe::header::COFF_MACHINE_ARM64
+        );
+        let architecture = arch::from_pe_machine(machine, is_64);
+
+        // Build section information for the entire binary
+        let sections = Sections::new();
+        sections.build_all(&binary);
+
+        // Create Capstone object
+        let capstone = arch::build_capstone(architecture)?;
+
+        let (layout, end) = Sections::coff_object_layout(&coff);
+        let defined = PreDefinedOffsets::new();
+        let mut next_slot = end.next_multiple_of(0x1000);
+        // symbol table index (counting auxiliary records) to address
+        let mut symbols: HashMap<usize, u64> = HashMap::new();
+        if let Some(table) = &coff.symbols {
+            for (index, inline_name, symbol) in table.iter() {
+                let name = match inline_name {
+                    Some(name) => Some(name),
+                    None => coff.strings.as_ref().and_then(|x| symbol.name(x).ok()),
+                }
+                .filter(|x| !x.is_empty());
+
+                let address = match symbol.section_number {
+                    // undefined, or common data when a size is given
+                    0 if name.is_some() => {
+                        let slot = next_slot;
+                        next_slot += EXTERN_SLOT_SIZE.max(symbol.value as u64);
+                        slot
+                    }
+                    number if number > 0 => {
+                        let Some(Some(base)) = layout.get(number as usize - 1) else {
+                            continue;
+                        };
+                        base + symbol.value as u64
+                    }
+                    // absolute and debug symbols
+                    _ => continue,
+                };
+                symbols.insert(index, address);
+
+                let Some(name) = name else {
+                    continue;
+                };
+                let named = symbol.section_number == 0
+                    || symbol.typ == IMAGE_SYM_TYPE_FUNCTION
+                    || symbol.storage_class == IMAGE_SYM_CLASS_EXTERNAL;
+                if !named {
+                    continue;
+                }
+                let name = name.strip_prefix(IMPORT_PREFIX).unwrap_or(name);
+                defined.insert(PreDefinedOffset {
+                    name: demangle_symbol(name),
+                    address: Address::from_virtual_address(&sections, address),
+                });
+            }
+        }
+
+        // Apply the relocations
+        let mut patches = Vec::new();
+        if architecture == MachineArchitecture::X64 {
+            for (section, base) in coff.sections.iter().zip(&layout) {
+                let Some(base) = base else {
+                    continue;
+                };
+                let Ok(relocations) = section.relocations(&binary) else {
+                    continue;
+                };
+                for relocation in relocations {
+                    let Some(symbol) = symbols.get(&(relocation.symbol_table_index as usize))
+                    else {
+                        continue;
+                    };
+                    let offset =
+                        section.pointer_to_raw_data as usize + relocation.virtual_address as usize;
+                    let place = base + relocation.virtual_address as u64;
+                    let Some(bytes) =
+                        apply_amd64_relocation(&binary, offset, relocation.typ, *symbol, place)
+                    else {
+                        continue;
+                    };
+                    patches.push((offset, bytes));
+                }
+            }
+        } else {
+            warn!("relocations of {:?} objects are not applied", architecture);
+        }
+        debug!("{} relocations applied", patches.len());
+        drop(coff);
+        for (offset, bytes) in patches {
+            if let Some(target) = binary.get_mut(offset..offset + bytes.len()) {
+                target.copy_from_slice(&bytes);
+            }
+        }
+
+        let noreturn = NoReturnFunctions::new(&defined);
+        let relations = Relations::new();
+        Ok(Pe {
+            kind: BinaryKind::ObjectFile,
+            entry: Address::from_virtual_address(&sections, 0),
+            path,
+            binary,
+            architecture,
+            capstone,
+            defined,
+            sections,
+            relations: relations.clone(),
+            blocks: Blocks::new(relations),
+            debug_info: Default::default(),
+            exception_handlers: Default::default(),
+            noreturn: std::sync::Arc::new(noreturn),
+            cancel_token: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        })
+    }
+}
+
+/// Computes the bytes an x86-64 COFF relocation stores.
+///
+/// ### Arguments
+/// - `binary: &[u8]` - raw object data, holding the implicit addend
+/// - `offset: usize` - file offset of the relocated field
+/// - `typ: u16` - type of the relocation
+/// - `symbol: u64` - address of the referenced symbol
+/// - `place: u64` - address of the relocated field
+///
+/// ### Returns
+/// - `Option<Vec<u8>>` - little endian bytes to store, or `None` if the relocation is not applied
+///
+/// ### Note
+/// - `ADDR32NB` is image relative, and objects are laid out from the image base of zero.
+fn apply_amd64_relocation(
+    binary: &[u8],
+    offset: usize,
+    typ: u16,
+    symbol: u64,
+    place: u64,
+) -> Option<Vec<u8>> {
+    let read_u32 = || {
+        binary
+            .get(offset..offset + 4)
+            .map(|x| u32::from_le_bytes(x.try_into().unwrap()))
+    };
+    let bytes = match typ {
+        IMAGE_REL_AMD64_ADDR64 => {
+            let addend = u64::from_le_bytes(binary.get(offset..offset + 8)?.try_into().unwrap());
+            symbol.wrapping_add(addend).to_le_bytes().to_vec()
+        }
+        IMAGE_REL_AMD64_ADDR32 | IMAGE_REL_AMD64_ADDR32NB => {
+            let addend = read_u32()?;
+            (symbol as u32).wrapping_add(addend).to_le_bytes().to_vec()
+        }
+        IMAGE_REL_AMD64_REL32..=IMAGE_REL_AMD64_REL32_5 => {
+            let addend = read_u32()? as i32 as i64;
+            // REL32_n relocations are followed by n bytes of the instruction
+            let next = place + 4 + (typ - IMAGE_REL_AMD64_REL32) as u64;
+            (symbol.wrapping_add_signed(addend).wrapping_sub(next) as u32)
+                .to_le_bytes()
+                .to_vec()
+        }
+        _ => return None,
+    };
+    Some(bytes)
+}
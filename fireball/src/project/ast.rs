@@ -0,0 +1,180 @@
+//! Records of the ast stored in project files.
+//!
+//! Versions of a function share their variable map and the ir function they were generated
+//! from, so both are stored once in tables of the ast and referred to by index, see
+//! [`super::shared`]. Ir functions are generated again from the restored blocks they were
+//! made of.
+
+use crate::{
+    abstract_syntax_tree::{
+        Ast, AstCallingConvention, AstFunction, AstFunctionId, AstFunctionVersion, AstVariable,
+    },
+    core::Block,
+    ir::analyze::ir_function::generate_ir_function,
+    project::shared::{self, SharedTables},
+    utils::version_map::VersionMap,
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct AstRecord {
+    function_versions: Vec<(u64, usize)>,
+    last_variable_id: Vec<(u64, u32)>,
+    pre_defined_symbols: Vec<(u64, String)>,
+    calling_convention: Option<AstCallingConvention>,
+    /// Variable maps shared by the functions, with their variables in id order
+    variable_maps: Vec<Vec<AstVariable>>,
+    /// Ids of the blocks each ir function was generated from, in the order they got merged
+    origins: Vec<Vec<usize>>,
+    /// Versions of each function by entry address, encoded on their own since they are only
+    /// read once the tables above are restored
+    functions: Vec<u8>,
+}
+
+impl AstRecord {
+    pub(super) fn new(ast: &Ast) -> Result<Self, String> {
+        let functions = ast.functions.read().unwrap();
+        let mut versions: Vec<(u64, Vec<(usize, &AstFunction)>)> = functions
+            .iter()
+            .map(|(id, versions)| {
+                let mut versions: Vec<_> = versions
+                    .raw()
+                    .iter()
+                    .map(|(version, function)| (version.0, function))
+                    .collect();
+                versions.sort_unstable_by_key(|(version, _)| *version);
+                (id.address, versions)
+            })
+            .collect();
+        versions.sort_unstable_by_key(|(address, _)| *address);
+        let (encoded, tables) = shared::record(|| bitcode::serialize(&versions));
+        let encoded = encoded.map_err(|err| format!("failed to encode the ast: {err}"))?;
+
+        let variable_maps = tables
+            .variable_maps
+            .iter()
+            .map(|variables| {
+                let mut variables: Vec<_> = variables.read().unwrap().values().cloned().collect();
+                variables.sort_unstable_by_key(|x| x.id);
+                variables
+            })
+            .collect();
+        let origins = tables
+            .origins
+            .iter()
+            .map(|origin| {
+                origin
+                    .get_control_flow()
+                    .get_blocks()
+                    .iter()
+                    .map(|x| x.get_block_id())
+                    .collect()
+            })
+            .collect();
+        let mut function_versions: Vec<_> = ast
+            .function_versions
+            .iter()
+            .map(|(id, version)| (id.address, version.0))
+            .collect();
+        function_versions.sort_unstable();
+        let mut last_variable_id: Vec<_> = ast
+            .last_variable_id
+            .iter()
+            .map(|(id, index)| (id.address, *index))
+            .collect();
+        last_variable_id.sort_unstable();
+        let mut pre_defined_symbols: Vec<_> = ast
+            .pre_defined_symbols
+            .iter()
+            .map(|(address, name)| (*address, name.clone()))
+            .collect();
+        pre_defined_symbols.sort_unstable();
+        Ok(Self {
+            function_versions,
+            last_variable_id,
+            pre_defined_symbols,
+            calling_convention: ast.calling_convention,
+            variable_maps,
+            origins,
+            functions: encoded,
+        })
+    }
+
+    /// Restores the ast, generating its ir functions again from the restored blocks.
+    ///
+    /// ### Note
+    /// - The debug information and annotations are not stored, and are left to the caller.
+    pub(super) fn restore(&self, blocks: &[Arc<Block>]) -> Result<Ast, String> {
+        let blocks: HashMap<usize, Arc<Block>> =
+            blocks.iter().map(|x| (x.get_id(), x.clone())).collect();
+        let origins = self
+            .origins
+            .iter()
+            .map(|ids| {
+                let blocks = ids
+                    .iter()
+                    .map(|id| {
+                        blocks
+                            .get(id)
+                            .cloned()
+                            .ok_or_else(|| format!("the ast refers to missing block {id}"))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Arc::new(generate_ir_function(&blocks)))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let variable_maps = self
+            .variable_maps
+            .iter()
+            .map(|variables| {
+                let variables = variables.iter().map(|x| (x.id, x.clone())).collect();
+                Arc::new(RwLock::new(variables))
+            })
+            .collect();
+        let versions: Vec<(u64, Vec<(usize, AstFunction)>)> =
+            shared::restore(SharedTables::new(variable_maps, origins), || {
+                bitcode::deserialize(&self.functions)
+            })
+            .map_err(|err| format!("failed to decode the ast: {err}"))?;
+
+        let mut ast = Ast::new();
+        let mut functions = HashMap::new();
+        for (address, versions) in versions {
+            let id = AstFunctionId { address };
+            let mut result: Option<VersionMap<AstFunctionVersion, AstFunction>> = None;
+            for (version, function) in versions {
+                let version = AstFunctionVersion(version);
+                match result.as_mut() {
+                    Some(x) => x
+                        .insert(version, function)
+                        .map_err(|_| format!("duplicated version of {}", id.get_default_name()))?,
+                    None => result = Some(VersionMap::new(version, function)),
+                }
+            }
+            if let Some(result) = result {
+                functions.insert(id, result);
+            }
+        }
+        *ast.functions.write().unwrap() = functions;
+        ast.function_versions = self
+            .function_versions
+            .iter()
+            .map(|(address, version)| {
+                (
+                    AstFunctionId { address: *address },
+                    AstFunctionVersion(*version),
+                )
+            })
+            .collect();
+        ast.last_variable_id = self
+            .last_variable_id
+            .iter()
+            .map(|(address, index)| (AstFunctionId { address: *address }, *index))
+            .collect();
+        ast.pre_defined_symbols = self.pre_defined_symbols.iter().cloned().collect();
+        ast.calling_convention = self.calling_convention;
+        Ok(ast)
+    }
+}
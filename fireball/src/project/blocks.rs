@@ -0,0 +1,290 @@
+//! Records of the sections, symbols, blocks and relations stored in project files.
+
+use crate::{
+    core::{
+        Address, Block, DestinationType, Instruction, JumpTable, PreDefinedOffset, Relation,
+        RelationType, Section, Sections,
+    },
+    exception_handling::{ExceptionFilter, ExceptionHandler, ExceptionHandlerKind},
+    ir::data::IrData,
+    project::ir::IrBlockRecord,
+    utils::Aos,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(super) struct SectionRecord {
+    virtual_address: u64,
+    virtual_size: u64,
+    file_offset: u64,
+    size_of_file: u64,
+    name: String,
+    real_name: Option<String>,
+    characteristics: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct PreDefinedOffsetRecord {
+    address: u64,
+    name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct RelationRecord {
+    from: usize,
+    to: Option<u64>,
+    destination_type: DestinationType,
+    relation_type: RelationType,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JumpTableRecord {
+    index: Aos<IrData>,
+    cases: Vec<(u64, u64)>,
+    default: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum ExceptionHandlerKindRecord {
+    Catch(Vec<Option<String>>),
+    ExceptConstant(i32),
+    ExceptFunction(u64),
+    Cleanup,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExceptionHandlerRecord {
+    start: u64,
+    end: u64,
+    handler: u64,
+    kind: ExceptionHandlerKindRecord,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct BlockRecord {
+    pub(super) id: usize,
+    name: Option<String>,
+    start_address: u64,
+    block_size: Option<u64>,
+    /// Instructions, with their virtual address
+    instructions: Vec<(u64, iceball::Instruction)>,
+    jump_table: Option<JumpTableRecord>,
+    exception_handlers: Vec<ExceptionHandlerRecord>,
+    connected_from: Vec<RelationRecord>,
+    connected_to: Vec<RelationRecord>,
+    /// Address of each lifted instruction, and the analyses of the block, if analyzed
+    ir: Option<(Vec<u64>, IrBlockRecord)>,
+}
+
+impl From<&Section> for SectionRecord {
+    fn from(value: &Section) -> Self {
+        Self {
+            virtual_address: value.virtual_address,
+            virtual_size: value.virtual_size,
+            file_offset: value.file_offset,
+            size_of_file: value.size_of_file,
+            name: value.name.clone(),
+            real_name: value.real_name.clone(),
+            characteristics: value.characteristics,
+        }
+    }
+}
+
+/// Returns the records of the sections, in address order.
+pub(super) fn record_sections(sections: &Sections) -> Vec<SectionRecord> {
+    let mut result: Vec<SectionRecord> = sections.all().iter().map(Into::into).collect();
+    result.sort_unstable();
+    result
+}
+
+impl From<&PreDefinedOffset> for PreDefinedOffsetRecord {
+    fn from(value: &PreDefinedOffset) -> Self {
+        Self {
+            address: value.address.get_virtual_address(),
+            name: value.name.clone(),
+        }
+    }
+}
+
+impl PreDefinedOffsetRecord {
+    pub(super) fn restore(&self, sections: &Sections) -> PreDefinedOffset {
+        PreDefinedOffset {
+            address: Address::from_virtual_address(sections, self.address),
+            name: self.name.clone(),
+        }
+    }
+}
+
+impl From<&Relation> for RelationRecord {
+    fn from(value: &Relation) -> Self {
+        Self {
+            from: value.from(),
+            to: value.to().map(|x| x.get_virtual_address()),
+            destination_type: *value.destination_type(),
+            relation_type: *value.relation_type(),
+        }
+    }
+}
+
+impl RelationRecord {
+    pub(super) fn restore(&self, sections: &Sections) -> Relation {
+        Relation::new(
+            self.from,
+            self.to.map(|x| Address::from_virtual_address(sections, x)),
+            self.destination_type,
+            self.relation_type,
+        )
+    }
+}
+
+impl From<&ExceptionHandler> for ExceptionHandlerRecord {
+    fn from(value: &ExceptionHandler) -> Self {
+        let kind = match &value.kind {
+            ExceptionHandlerKind::Catch(x) => ExceptionHandlerKindRecord::Catch(x.clone()),
+            ExceptionHandlerKind::Except(ExceptionFilter::Constant(x)) => {
+                ExceptionHandlerKindRecord::ExceptConstant(*x)
+            }
+            ExceptionHandlerKind::Except(ExceptionFilter::Function(x)) => {
+                ExceptionHandlerKindRecord::ExceptFunction(*x)
+            }
+            ExceptionHandlerKind::Cleanup => ExceptionHandlerKindRecord::Cleanup,
+        };
+        Self {
+            start: value.start,
+            end: value.end,
+            handler: value.handler,
+            kind,
+        }
+    }
+}
+
+impl ExceptionHandlerRecord {
+    fn restore(&self) -> ExceptionHandler {
+        let kind = match &self.kind {
+            ExceptionHandlerKindRecord::Catch(x) => ExceptionHandlerKind::Catch(x.clone()),
+            ExceptionHandlerKindRecord::ExceptConstant(x) => {
+                ExceptionHandlerKind::Except(ExceptionFilter::Constant(*x))
+            }
+            ExceptionHandlerKindRecord::ExceptFunction(x) => {
+                ExceptionHandlerKind::Except(ExceptionFilter::Function(*x))
+            }
+            ExceptionHandlerKindRecord::Cleanup => ExceptionHandlerKind::Cleanup,
+        };
+        ExceptionHandler {
+            start: self.start,
+            end: self.end,
+            handler: self.handler,
+            kind,
+        }
+    }
+}
+
+impl From<&Block> for BlockRecord {
+    fn from(value: &Block) -> Self {
+        let jump_table = value.get_jump_table().map(|x| JumpTableRecord {
+            index: x.get_index().clone(),
+            cases: x
+                .get_cases()
+                .iter()
+                .map(|(case, address)| (*case, address.get_virtual_address()))
+                .collect(),
+            default: x.get_default().map(Address::get_virtual_address),
+        });
+        let ir = value.get_ir().as_ref().map(|x| {
+            let addresses = x
+                .ir()
+                .iter()
+                .map(|ir| ir.address.get_virtual_address())
+                .collect();
+            let analyses = IrBlockRecord {
+                data_access: x.data_access.clone(),
+                known_datatypes: x.known_datatypes.clone(),
+                variables: x.variables.clone(),
+            };
+            (addresses, analyses)
+        });
+        Self {
+            id: value.get_id(),
+            name: value.get_name().cloned(),
+            start_address: value.get_start_address().get_virtual_address(),
+            block_size: value.get_block_size().copied(),
+            instructions: value
+                .get_instructions()
+                .iter()
+                .map(|x| (x.address, x.inner.clone()))
+                .collect(),
+            jump_table,
+            exception_handlers: value
+                .get_exception_handlers()
+                .iter()
+                .map(Into::into)
+                .collect(),
+            connected_from: value.get_connected_from().iter().map(Into::into).collect(),
+            connected_to: value.get_connected_to().iter().map(Into::into).collect(),
+            ir,
+        }
+    }
+}
+
+impl BlockRecord {
+    pub(super) fn start_address(&self, sections: &Sections) -> Address {
+        Address::from_virtual_address(sections, self.start_address)
+    }
+
+    pub(super) fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    pub(super) fn block_size(&self) -> Option<u64> {
+        self.block_size
+    }
+
+    pub(super) fn instructions(&self) -> Arc<[Instruction]> {
+        self.instructions
+            .iter()
+            .map(|(address, inner)| Instruction {
+                address: *address,
+                inner: inner.clone(),
+            })
+            .collect()
+    }
+
+    pub(super) fn jump_table(&self, sections: &Sections) -> Result<Option<JumpTable>, String> {
+        let Some(jump_table) = &self.jump_table else {
+            return Ok(None);
+        };
+        let cases = jump_table
+            .cases
+            .iter()
+            .map(|(case, address)| (*case, Address::from_virtual_address(sections, *address)))
+            .collect();
+        let default = jump_table
+            .default
+            .map(|address| Address::from_virtual_address(sections, address));
+        Ok(Some(JumpTable::new(
+            jump_table.index.clone(),
+            cases,
+            default,
+        )))
+    }
+
+    pub(super) fn exception_handlers(&self) -> Vec<ExceptionHandler> {
+        self.exception_handlers
+            .iter()
+            .map(ExceptionHandlerRecord::restore)
+            .collect()
+    }
+
+    pub(super) fn connected_from(&self) -> &[RelationRecord] {
+        &self.connected_from
+    }
+
+    pub(super) fn connected_to(&self) -> &[RelationRecord] {
+        &self.connected_to
+    }
+
+    pub(super) fn ir(&self) -> Option<&(Vec<u64>, IrBlockRecord)> {
+        self.ir.as_ref()
+    }
+}
@@ -0,0 +1,20 @@
+//! Records of the block analyses stored in project files.
+//!
+//! Statements lifted from instructions live for the whole program, so blocks only store their
+//! instructions and lift them again when loaded. The data here is what the analyses computed
+//! from them.
+
+use crate::ir::{
+    analyze::{IrVariable, KnownDataType},
+    data::IrDataAccess,
+    utils::IrStatementDescriptorMap,
+};
+use serde::{Deserialize, Serialize};
+
+/// Analyses of a block, the statements themselves being lifted again from the instructions
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct IrBlockRecord {
+    pub(super) data_access: Option<IrStatementDescriptorMap<Vec<IrDataAccess>>>,
+    pub(super) known_datatypes: Option<IrStatementDescriptorMap<Vec<KnownDataType>>>,
+    pub(super) variables: Option<Vec<IrVariable>>,
+}
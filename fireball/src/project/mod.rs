@@ -0,0 +1,281 @@
+//! Module saving the analysis state of a binary to a project file, and loading it back.
+//!
+//! A project holds the sections, symbols, blocks with their instructions and ir analyses,
+//! relations, the function versions of an ast and the annotations, so a session starts where
+//! the last one stopped instead of analyzing the binary again. A project is only loaded for the
+//! binary it was saved for, recognized by its hash.
+
+mod ast;
+mod blocks;
+mod ir;
+pub(crate) mod shared;
+
+use crate::{
+    Fireball,
+    abstract_syntax_tree::{Ast, AstAnnotations},
+    core::{Block, Fire, FireRaw},
+    ir::{Ir, IrBlock},
+    prelude::*,
+};
+use ast::AstRecord;
+use blocks::{BlockRecord, PreDefinedOffsetRecord, RelationRecord, SectionRecord};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{Read, Write},
+    path::Path,
+    sync::Arc,
+};
+
+const PROJECT_MAGIC: &str = "fbp";
+const PROJECT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ProjectPayload {
+    magic: String,
+    version: u32,
+    /// Hash of the binary the project was saved for
+    binary_hash: [u8; 32],
+    sections: Vec<SectionRecord>,
+    defined: Vec<PreDefinedOffsetRecord>,
+    blocks: Vec<BlockRecord>,
+    relations: Vec<RelationRecord>,
+    ast: Option<AstRecord>,
+    annotations: AstAnnotations,
+}
+
+/// What a project gives back besides the analysis state of the binary
+#[derive(Debug)]
+pub struct Project {
+    /// Ast of the last decompilation, with every version of its functions
+    pub ast: Option<Ast>,
+    /// Names, types and comments given by the user
+    pub annotations: AstAnnotations,
+}
+
+/// Saves the analysis state of the binary to a project file.
+///
+/// ### Arguments
+/// - `fireball: &Fireball` - analyzed binary
+/// - `path: &Path` - file to write
+/// - `ast: Option<&Ast>` - ast to keep along, with every version of its functions
+/// - `annotations: &AstAnnotations` - annotations to keep along
+pub(crate) fn save(
+    fireball: &Fireball,
+    path: &Path,
+    ast: Option<&Ast>,
+    annotations: &AstAnnotations,
+) -> Result<(), FireballError> {
+    let mut blocks = fireball.get_blocks().get_all();
+    blocks.sort_unstable_by_key(|x| x.get_id());
+    let payload = ProjectPayload {
+        magic: PROJECT_MAGIC.to_string(),
+        version: PROJECT_VERSION,
+        binary_hash: *blake3::hash(fireball.get_binary()).as_bytes(),
+        sections: blocks::record_sections(&fireball.get_sections()),
+        defined: fireball
+            .get_defined()
+            .get_reader()
+            .iter()
+            .map(Into::into)
+            .collect(),
+        blocks: blocks.iter().map(|x| x.as_ref().into()).collect(),
+        relations: fireball
+            .get_relations()
+            .get_relations()
+            .iter()
+            .map(Into::into)
+            .collect(),
+        ast: ast
+            .map(AstRecord::new)
+            .transpose()
+            .map_err(FireballError::ProjectFailed)?,
+        annotations: annotations.clone(),
+    };
+    let encoded = encode(&payload).map_err(FireballError::ProjectFailed)?;
+    fs::write(path, encoded).map_err(|err| {
+        FireballError::ProjectFailed(format!("failed to write {}: {err}", path.display()))
+    })?;
+    info!(
+        blocks = payload.blocks.len(),
+        "Project saved to {}",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Replaces the analysis state of the binary by the one saved in a project file.
+///
+/// ### Arguments
+/// - `fireball: &Fireball` - binary the project was saved for
+/// - `path: &Path` - file to read
+///
+/// ### Returns
+/// - `Result<Project, FireballError>` - the ast and annotations of the project, or
+///   `StaleProject` if it was saved for another binary
+///
+/// ### Note
+/// - The analysis state is only replaced once the whole project is restored, a failing load
+///   leaves it untouched.
+/// - The statements of the blocks are lifted again from their instructions, only the analyses
+///   of the blocks are read from the project.
+pub(crate) fn load(fireball: &Fireball, path: &Path) -> Result<Project, FireballError> {
+    let bytes = fs::read(path).map_err(|err| {
+        FireballError::ProjectFailed(format!("failed to read {}: {err}", path.display()))
+    })?;
+    let payload = decode(&bytes).map_err(FireballError::ProjectFailed)?;
+    if payload.binary_hash != *blake3::hash(fireball.get_binary()).as_bytes() {
+        return Err(FireballError::StaleProject);
+    }
+    let sections = fireball.get_sections();
+    if payload.sections != blocks::record_sections(&sections) {
+        return Err(FireballError::ProjectFailed(
+            "the sections of the project differ from the ones of the binary".to_owned(),
+        ));
+    }
+
+    // every record is restored before the analysis state is touched, so a project failing to
+    // load leaves it as it was
+    let defined: Vec<_> = payload
+        .defined
+        .iter()
+        .map(|x| x.restore(&sections))
+        .collect();
+    let relations: Vec<_> = payload
+        .relations
+        .iter()
+        .map(|x| x.restore(&sections))
+        .collect();
+    let mut blocks = Vec::with_capacity(payload.blocks.len());
+    for record in payload.blocks.iter() {
+        let block = Block::new(
+            record.id,
+            record.name(),
+            record.start_address(&sections),
+            record.block_size(),
+            record.instructions(),
+            record
+                .jump_table(&sections)
+                .map_err(FireballError::ProjectFailed)?,
+            record.exception_handlers(),
+        );
+        for relation in record.connected_from() {
+            block.add_connected_from(relation.restore(&sections));
+        }
+        for relation in record.connected_to() {
+            block.add_connected_to(relation.restore(&sections));
+        }
+        restore_ir(fireball, &block, record).map_err(FireballError::ProjectFailed)?;
+        blocks.push(block);
+    }
+    let ast = match &payload.ast {
+        Some(record) => {
+            let mut ast = record
+                .restore(&blocks)
+                .map_err(FireballError::ProjectFailed)?;
+            if let Some(architecture) = fireball.get_architecture() {
+                ast.set_architecture(architecture);
+            }
+            ast.set_debug_info(fireball.get_debug_info());
+            ast.set_annotations(Arc::new(payload.annotations.clone()));
+            Some(ast)
+        }
+        None => None,
+    };
+
+    let fireball_defined = fireball.get_defined();
+    fireball_defined.clear();
+    for x in defined {
+        fireball_defined.insert(x);
+    }
+    let fireball_blocks = fireball.get_blocks();
+    // also removes the relations
+    fireball_blocks.clear();
+    let fireball_relations = fireball.get_relations();
+    for x in relations {
+        fireball_relations.add_relation(x);
+    }
+    for x in blocks {
+        fireball_blocks.restore_block(x);
+    }
+
+    info!(
+        blocks = payload.blocks.len(),
+        "Project loaded from {}",
+        path.display()
+    );
+    Ok(Project {
+        ast,
+        annotations: payload.annotations,
+    })
+}
+
+/// Lifts the instructions of a restored block again, and puts the analyses of the project on
+/// the statements.
+fn restore_ir(fireball: &Fireball, block: &Block, record: &BlockRecord) -> Result<(), String> {
+    let Some((addresses, analyses)) = record.ir() else {
+        return Ok(());
+    };
+    let instructions = block.get_instructions().clone();
+    if addresses.len() != instructions.len() {
+        return Err(format!(
+            "block {} has {} instructions but {} lifted ones",
+            block.get_start_address(),
+            instructions.len(),
+            addresses.len()
+        ));
+    }
+    let architecture = fireball
+        .get_architecture()
+        .ok_or("the binary has no instructions to lift")?;
+    let sections = fireball.get_sections();
+    let ir = addresses
+        .iter()
+        .zip(instructions.iter())
+        .map(|(address, instruction)| Ir {
+            address: crate::core::Address::from_virtual_address(&sections, *address),
+            statements: crate::arch::create_ir_statement(architecture, instruction),
+        })
+        .collect();
+    let mut ir_block = IrBlock::new(ir, instructions);
+    ir_block.data_access = analyses.data_access.clone();
+    ir_block.known_datatypes = analyses.known_datatypes.clone();
+    ir_block.variables = analyses.variables.clone();
+    block.set_ir(ir_block);
+    Ok(())
+}
+
+/// Encodes the project into bytes (bitcode + gzip).
+fn encode(payload: &ProjectPayload) -> Result<Vec<u8>, String> {
+    let encoded =
+        bitcode::serialize(payload).map_err(|err| format!("failed to encode project: {err}"))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&encoded)
+        .map_err(|err| format!("failed to compress project: {err}"))?;
+    encoder
+        .finish()
+        .map_err(|err| format!("failed to finish project: {err}"))
+}
+
+/// Decodes a project encoded by [`encode`].
+fn decode(bytes: &[u8]) -> Result<ProjectPayload, String> {
+    let mut decoder = GzDecoder::new(bytes);
+    let mut decoded = Vec::new();
+    decoder
+        .read_to_end(&mut decoded)
+        .map_err(|err| format!("failed to decompress project: {err}"))?;
+    let payload = bitcode::deserialize::<ProjectPayload>(&decoded)
+        .map_err(|err| format!("failed to decode project: {err}"))?;
+    if payload.magic != PROJECT_MAGIC {
+        return Err(format!("invalid project magic `{}`", payload.magic));
+    }
+    if payload.version != PROJECT_VERSION {
+        return Err(format!(
+            "unsupported project version `{}` (expected `{PROJECT_VERSION}`)",
+            payload.version
+        ));
+    }
+    Ok(payload)
+}
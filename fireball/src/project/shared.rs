@@ -0,0 +1,126 @@
+//! Serde helpers for the ast fields shared between functions.
+//!
+//! Functions and expressions hold their variable map and the ir function they were generated
+//! from behind an `Arc`, shared by every version of a function. Project files store each of
+//! them once in tables of the ast, and the fields only store their index in those tables.
+//!
+//! The tables are collected while [`record`] serializes the functions, and must be given to
+//! [`restore`] before they are deserialized. Outside of those, the fields fail to serialize.
+
+use crate::{abstract_syntax_tree::ArcAstVariableMap, ir::analyze::IrFunction};
+use hashbrown::HashMap;
+use serde::{Deserialize, Deserializer, Serializer, de, ser};
+use std::{cell::RefCell, sync::Arc};
+
+thread_local! {
+    static RECORDING: RefCell<Option<SharedTables>> = const { RefCell::new(None) };
+    static RESTORING: RefCell<Option<SharedTables>> = const { RefCell::new(None) };
+}
+
+/// Values shared by the functions of an ast, by index
+#[derive(Default)]
+pub(super) struct SharedTables {
+    pub(super) variable_maps: Vec<ArcAstVariableMap>,
+    pub(super) origins: Vec<Arc<IrFunction>>,
+    /// Index of each recorded value, by address
+    indexes: HashMap<usize, usize>,
+}
+
+impl SharedTables {
+    pub(super) fn new(
+        variable_maps: Vec<ArcAstVariableMap>,
+        origins: Vec<Arc<IrFunction>>,
+    ) -> Self {
+        Self {
+            variable_maps,
+            origins,
+            indexes: HashMap::new(),
+        }
+    }
+}
+
+/// Runs `serialize` while collecting the shared values it meets into tables.
+pub(super) fn record<T>(serialize: impl FnOnce() -> T) -> (T, SharedTables) {
+    RECORDING.set(Some(SharedTables::default()));
+    let result = serialize();
+    (result, RECORDING.take().unwrap_or_default())
+}
+
+/// Runs `deserialize` with the tables the shared values are read from.
+pub(super) fn restore<T>(tables: SharedTables, deserialize: impl FnOnce() -> T) -> T {
+    RESTORING.set(Some(tables));
+    let result = deserialize();
+    RESTORING.set(None);
+    result
+}
+
+fn index_of<T, E: ser::Error>(
+    value: &Arc<T>,
+    table: impl FnOnce(&mut SharedTables) -> &mut Vec<Arc<T>>,
+) -> Result<u64, E> {
+    RECORDING.with_borrow_mut(|tables| {
+        let tables = tables
+            .as_mut()
+            .ok_or_else(|| E::custom("shared ast values are only stored in project files"))?;
+        let key = Arc::as_ptr(value) as *const () as usize;
+        if let Some(index) = tables.indexes.get(&key) {
+            return Ok(*index as u64);
+        }
+        let table = table(tables);
+        table.push(value.clone());
+        let index = table.len() - 1;
+        tables.indexes.insert(key, index);
+        Ok(index as u64)
+    })
+}
+
+fn value_at<T, E: de::Error>(
+    index: u64,
+    table: impl FnOnce(&SharedTables) -> &Vec<Arc<T>>,
+) -> Result<Arc<T>, E> {
+    RESTORING.with_borrow(|tables| {
+        let tables = tables
+            .as_ref()
+            .ok_or_else(|| E::custom("shared ast values are only read from project files"))?;
+        table(tables)
+            .get(index as usize)
+            .cloned()
+            .ok_or_else(|| E::custom(format!("the ast refers to missing shared value {index}")))
+    })
+}
+
+/// `#[serde(with = "crate::project::shared::variable_map")]`
+pub(crate) mod variable_map {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        variables: &ArcAstVariableMap,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(index_of(variables, |x| &mut x.variable_maps)?)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ArcAstVariableMap, D::Error> {
+        value_at(u64::deserialize(deserializer)?, |x| &x.variable_maps)
+    }
+}
+
+/// `#[serde(with = "crate::project::shared::origin")]`
+pub(crate) mod origin {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        origin: &Arc<IrFunction>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(index_of(origin, |x| &mut x.origins)?)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Arc<IrFunction>, D::Error> {
+        value_at(u64::deserialize(deserializer)?, |x| &x.origins)
+    }
+}
@@ -0,0 +1,29 @@
+use super::hello_world_dynamic_elf_binary;
+use crate::{Fireball, core::FireRaw};
+
+#[test]
+fn elf_dynamic_generate_ast_debug_info() {
+    let binary = hello_world_dynamic_elf_binary();
+    let fireball = Fireball::from_binary(binary.to_vec()).unwrap();
+    let blocks = vec![
+        fireball.analyze_from_virtual_address(0x401126).unwrap(),
+        fireball.analyze_from_virtual_address(0x40113a).unwrap(),
+        fireball.analyze_from_virtual_address(0x401148).unwrap(),
+    ];
+    let code = fireball
+        .generate_ast(blocks)
+        .unwrap()
+        .optimize(None)
+        .unwrap()
+        .print(None);
+
+    // the plt stub is named after its jump slot, the functions after their dwarf entries
+    assert!(code.contains("int32_t main("), "{code}");
+    assert!(code.contains("puts();"), "{code}");
+    assert!(code.contains("add();"), "{code}");
+    assert!(code.contains("int32_t add("), "{code}");
+    assert!(code.contains("int32_t   first;"), "{code}");
+    assert!(code.contains("int32_t   second;"), "{code}");
+    assert!(code.contains("/* dynamic.c:4 */"), "{code}");
+    assert!(code.contains("/* dynamic.c:9 */"), "{code}");
+}
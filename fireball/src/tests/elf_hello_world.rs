@@ -47,6 +47,24 @@ fn elf_hello_world_entry_analysis() {
     });
 }
 
+#[test]
+fn elf_hello_world_noreturn_entry_call() {
+    use crate::core::RelationType;
+
+    let binary = hello_world_elf_binary();
+    let elf = Elf::from_binary(binary.to_vec()).unwrap();
+    let block = elf.analyze_from_entry().unwrap();
+
+    // _start ends with `call __libc_start_main`, which never returns
+    let relations = block.get_connected_to();
+    let relation_types: Vec<_> = relations.iter().map(|x| *x.relation_type()).collect();
+    assert_eq!(relation_types, [RelationType::Call]);
+    assert_eq!(
+        relations[0].to().map(|x| x.get_virtual_address()),
+        Some(0x403d60)
+    );
+}
+
 #[test]
 fn elf_hello_world_decompile_from_entry() {
     let subscriber =
@@ -83,3 +101,230 @@ fn elf_hello_world_sections_loaded() {
         );
     });
 }
+
+#[test]
+fn elf_hello_world_calling_convention() {
+    let binary = hello_world_elf_binary();
+    let elf = Elf::from_binary(binary.to_vec()).unwrap();
+    assert_eq!(
+        elf.get_calling_convention(),
+        crate::abstract_syntax_tree::AstCallingConvention::X64SystemV
+    );
+}
+
+#[test]
+fn elf_hello_world_source_map() {
+    let binary = hello_world_elf_binary();
+    let elf = Elf::from_binary(binary.to_vec()).unwrap();
+    let block = elf.analyze_from_entry().unwrap();
+    let start = block.get_start_address().get_virtual_address();
+    let end = start + *block.get_block_size().unwrap();
+    let ast = crate::ir::analyze::generate_ast([block])
+        .unwrap()
+        .optimize(None)
+        .unwrap();
+    let (code, source_map) = ast.print_with_source_map(None);
+    assert_eq!(source_map.line_count(), code.lines().count());
+
+    let origins: Vec<_> = (0..source_map.line_count())
+        .filter_map(|line| source_map.address_of_line(line))
+        .collect();
+    assert!(!origins.is_empty(), "statements of _start should be mapped");
+    for origin in origins {
+        assert!(start <= origin.start && origin.end <= end);
+        let line = source_map.line_of_address(origin.start).unwrap();
+        assert!(
+            source_map
+                .address_of_line(line)
+                .unwrap()
+                .contains(origin.start)
+        );
+    }
+    assert!(source_map.lines_of_address(0).is_empty());
+}
+
+#[test]
+fn elf_hello_world_interpret_entry_block() {
+    use crate::ir::{Interpreter, InterpreterFlow, SparseMemory, VirtualMachine, x86_64::X64Range};
+
+    let binary = hello_world_elf_binary();
+    let elf = Elf::from_binary(binary.to_vec()).unwrap();
+    let block = elf.analyze_from_entry().unwrap();
+    let mut memory = SparseMemory::from_sections(elf.get_sections(), binary);
+    memory.write(0x7000_0000 - 0x1000, &[0; 0x2000]);
+    let mut interpreter = Interpreter::new(iceball::MachineArchitecture::X64, memory);
+    let vm = interpreter.vm_mut();
+    vm.write_register(&<VirtualMachine as X64Range>::rdx(), 0x1234);
+    vm.write_register(&<VirtualMachine as X64Range>::rsp(), 0x7000_0000);
+
+    // _start ends with `mov rdi, main` and `call __libc_start_main`
+    let ir = block.get_ir();
+    let (flow, _) = interpreter.execute_block(ir.as_ref().unwrap()).unwrap();
+    assert_eq!(flow, InterpreterFlow::Call(0x403d60));
+    let vm = interpreter.vm();
+    assert_eq!(
+        vm.read_register(&<VirtualMachine as X64Range>::r9()),
+        0x1234
+    );
+    assert_eq!(
+        vm.read_register(&<VirtualMachine as X64Range>::rdi()),
+        0x4018b5
+    );
+}
+
+#[cfg(feature = "keystone")]
+#[test]
+fn elf_hello_world_patch_entry() {
+    let binary = hello_world_elf_binary();
+    let mut elf = Elf::from_binary(binary.to_vec()).unwrap();
+    let block = elf.analyze_from_entry().unwrap();
+    let entry = block.get_start_address().clone();
+
+    // `endbr64` is 4 bytes, the rest of it is filled with `nop`s
+    elf.patch_assembly(&entry, "xor eax, eax").unwrap();
+    let file_offset = entry.get_file_offset().unwrap() as usize;
+    assert_eq!(
+        &elf.get_binary()[file_offset..file_offset + 4],
+        &[0x31, 0xc0, 0x90, 0x90]
+    );
+    let patched = elf.get_blocks().get_by_start_address(&entry).unwrap();
+    assert_ne!(patched.get_id(), block.get_id());
+    assert!(patched.get_ir().is_some());
+}
+
+#[test]
+fn elf_hello_world_patch_bytes_entry() {
+    let binary = hello_world_elf_binary();
+    let mut elf = Elf::from_binary(binary.to_vec()).unwrap();
+    let block = elf.analyze_from_entry().unwrap();
+    let entry = block.get_start_address().clone();
+
+    // `xor eax, eax` over `endbr64`
+    elf.patch_bytes(&entry, &[0x31, 0xc0]).unwrap();
+    let file_offset = entry.get_file_offset().unwrap() as usize;
+    assert_eq!(
+        &elf.get_binary()[file_offset..file_offset + 4],
+        &[0x31, 0xc0, 0x90, 0x90]
+    );
+    let patched = elf.get_blocks().get_by_start_address(&entry).unwrap();
+    assert_ne!(patched.get_id(), block.get_id());
+    assert!(patched.get_ir().is_some());
+    assert!(elf.patch_bytes(&entry, &[]).is_err());
+}
+
+#[test]
+fn elf_hello_world_debug_info() {
+    use crate::{
+        abstract_syntax_tree::AstValueType,
+        debug_info::{DebugFunction, DebugInfo, DebugLocation, DebugVariable, SourceLine},
+    };
+
+    let binary = hello_world_elf_binary();
+    let elf = Elf::from_binary(binary.to_vec()).unwrap();
+    let block = elf.analyze_from_virtual_address(0x4018b5).unwrap();
+
+    // `int main() { puts("Hello, World!"); return 0; }` of hello.c
+    let mut info = DebugInfo::default();
+    info.insert_function(DebugFunction {
+        name: "main".to_string(),
+        start: 0x4018b5,
+        end: 0x4018d3,
+        return_type: AstValueType::Int32,
+        parameters: Vec::new(),
+        variables: vec![DebugVariable {
+            name: "message".to_string(),
+            var_type: AstValueType::Pointer(Box::new(AstValueType::Char)),
+            location: Some(DebugLocation::Register("rax".to_string())),
+        }],
+    });
+    for (address, line) in [(0x4018b5, 3), (0x4018bd, 4), (0x4018cc, 5)] {
+        let file = "hello.c".to_string();
+        info.insert_line(address, Some(SourceLine { file, line }));
+    }
+    info.insert_line(0x4018d3, None);
+
+    let mut ast = crate::ir::analyze::generate_ast([block]).unwrap();
+    ast.set_debug_info(std::sync::Arc::new(info));
+    let code = ast.optimize(None).unwrap().print(None);
+    assert!(code.contains("int32_t main()"), "{code}");
+    // `lea rax, [rip + ...]` loads the string into the variable named by its register
+    assert!(
+        code.lines()
+            .any(|line| line.contains("char*") && line.contains("message;")),
+        "{code}"
+    );
+    assert!(
+        code.lines()
+            .any(|line| line.contains("message = ") && line.ends_with("/* hello.c:4 */")),
+        "{code}"
+    );
+}
+
+/// Runs `_IO_marker_difference`, returning `a->_pos - b->_pos` of two markers.
+#[cfg(feature = "unicorn")]
+#[test]
+fn elf_hello_world_emulate_function() {
+    use crate::{
+        Fireball,
+        core::Address,
+        emulation::{EmulationInput, EmulationStop, Emulator},
+    };
+
+    let binary = hello_world_elf_binary();
+    let fireball = Fireball::from_binary(binary.to_vec()).unwrap();
+    let emulator = Emulator::new(&fireball).unwrap();
+    let address = Address::from_virtual_address(&fireball.get_sections(), 0x40a780);
+
+    let mut input = EmulationInput::with_registers([("rdi", 0x1000_0000), ("rsi", 0x1000_0100)]);
+    input
+        .memory
+        .push((0x1000_0010, 50u32.to_le_bytes().to_vec()));
+    input
+        .memory
+        .push((0x1000_0110, 8u32.to_le_bytes().to_vec()));
+    // stack arguments larger than a page are mapped too
+    input.stack = vec![0xAB; 0x2000];
+    let trace = emulator.run_function(&address, &input).unwrap();
+
+    assert_eq!(trace.stop, EmulationStop::Finished);
+    assert_eq!(
+        trace.steps.iter().map(|x| x.address).collect::<Vec<_>>(),
+        [0x40a780, 0x40a784, 0x40a787, 0x40a78a]
+    );
+    assert_eq!(trace.register("rax"), Some(42));
+    assert_eq!(trace.steps_at(0x40a787).count(), 1);
+}
+
+/// Runs the first block of `main`, which ends by calling `puts`.
+#[cfg(feature = "unicorn")]
+#[test]
+fn elf_hello_world_emulate_block() {
+    use crate::{
+        Fireball,
+        emulation::{EmulationInput, EmulationStop, Emulator},
+    };
+
+    let binary = hello_world_elf_binary();
+    let elf = Elf::from_binary(binary.to_vec()).unwrap();
+    let block = elf.analyze_from_virtual_address(0x4018b5).unwrap();
+    let fireball = Fireball::Elf(elf);
+    let emulator = Emulator::new(&fireball).unwrap();
+
+    let trace = emulator
+        .run_block(&block, &EmulationInput::default())
+        .unwrap();
+
+    assert_eq!(trace.stop, EmulationStop::Finished);
+    assert_eq!(trace.steps.last().unwrap().address, 0x4018c7);
+    // `lea rax, [rip + ...]` loaded the string passed to `puts`
+    assert_eq!(trace.register("rdi"), Some(0x482010));
+    // `push rbp` and the return address of the call were written to the stack
+    assert_eq!(
+        trace.steps_at(0x4018b9).next().unwrap().memory_writes.len(),
+        1
+    );
+    assert_eq!(
+        trace.steps_at(0x4018c7).next().unwrap().memory_writes.len(),
+        1
+    );
+}
@@ -8,6 +8,24 @@ pub(super) fn hello_world_elf_binary() -> &'static [u8] {
     include_bytes!("../../tests/resources/hello_world_elf")
 }
 
+/// `dynamic.c` compiled with `gcc -g -no-pie -fcf-protection=none`: `main` calls `puts` through
+/// the plt, then `add(1, 2)`
+pub(super) fn hello_world_dynamic_elf_binary() -> &'static [u8] {
+    include_bytes!("../../tests/resources/hello_world_dynamic")
+}
+
+/// `hello.c` compiled with `gcc -c`: `greet` calls `puts` and `main` calls `greet`
+pub(super) fn hello_world_elf_object() -> &'static [u8] {
+    include_bytes!("../../tests/resources/hello_world.o")
+}
+
+/// `hello_world.o` converted to COFF with `objcopy -O pe-x86-64`
+pub(super) fn hello_world_coff_object() -> &'static [u8] {
+    include_bytes!("../../tests/resources/hello_world.obj")
+}
+
+mod elf_dynamic;
 mod elf_hello_world;
+mod object_files;
 mod pe_error_paths;
 mod pe_hello_world;
@@ -0,0 +1,61 @@
+use super::{hello_world_coff_object, hello_world_elf_object};
+use crate::{
+    BinaryKind, Fireball,
+    core::{FireRaw, RelationType},
+    prelude::FireballError,
+};
+
+/// Checks the calls of an object are relocated to the symbols they call.
+fn assert_calls_relocated(binary: &[u8]) {
+    let fireball = Fireball::from_binary(binary.to_vec()).unwrap();
+    assert_eq!(fireball.kind(), BinaryKind::ObjectFile);
+
+    let address_of = |name: &str| {
+        fireball
+            .get_defined()
+            .get_reader()
+            .iter()
+            .find(|x| x.name == name)
+            .map(|x| x.address.get_virtual_address())
+            .unwrap_or_else(|| panic!("{name} is not defined"))
+    };
+    let (main, greet, puts) = (address_of("main"), address_of("greet"), address_of("puts"));
+    assert_ne!(main, greet);
+    assert_ne!(greet, puts);
+
+    for (caller, callee) in [(main, greet), (greet, puts)] {
+        let block = fireball.analyze_from_virtual_address(caller).unwrap();
+        let calls: Vec<_> = block
+            .get_connected_to()
+            .iter()
+            .filter(|x| *x.relation_type() == RelationType::Call)
+            .filter_map(|x| x.to().map(|x| x.get_virtual_address()))
+            .collect();
+        assert_eq!(calls, [callee], "calls of {caller:#x}");
+    }
+}
+
+#[test]
+fn elf_object_relocated() {
+    assert_calls_relocated(hello_world_elf_object());
+}
+
+#[test]
+fn coff_object_relocated() {
+    assert_calls_relocated(hello_world_coff_object());
+}
+
+#[test]
+fn object_not_written_back() {
+    let path = std::env::temp_dir().join("__fireball_object_not_written_back__.o");
+    let _ = std::fs::remove_file(&path);
+    for binary in [hello_world_elf_object(), hello_world_coff_object()] {
+        let fireball = Fireball::from_binary(binary.to_vec()).unwrap();
+        // the relocated calls would be relocated again by the linker
+        assert!(matches!(
+            fireball.write_binary(path.to_str().unwrap()),
+            Err(FireballError::PatchFailed(_))
+        ));
+        assert!(!path.exists());
+    }
+}
@@ -25,3 +25,14 @@ fn analyze_all_respects_cancellation_request() {
         crate::prelude::DecompileError::Unknown(Some(msg)) if msg == "analysis cancelled"
     ));
 }
+
+#[test]
+fn load_pdb_rejects_non_pdb_input() {
+    let path = std::env::temp_dir().join("__fireball_not_a_pdb_for_error_test__.pdb");
+    std::fs::write(&path, [0x00, 0x01, 0x02, 0x03]).unwrap();
+    let mut pe = Pe::from_binary(hello_world_binary().to_vec()).unwrap();
+
+    let err = pe.load_pdb(path.to_str().unwrap()).unwrap_err();
+    std::fs::remove_file(&path).ok();
+    assert!(matches!(err, FireballError::PdbParsingFailed(_)));
+}
@@ -138,11 +138,12 @@ fn pe_hello_world_block_relation() {
         let entry_block = entry_block.unwrap();
         let entry_block_id = entry_block.get_id();
         let entry_connected_to = entry_block.get_connected_to();
-        assert_eq!(entry_connected_to.len(), 2);
+        // the call is also protected by a __try, whose handler is connected
+        assert_eq!(entry_connected_to.len(), 3);
         for connected_to in entry_connected_to.iter() {
             assert!(matches!(
                 connected_to.relation_type(),
-                &RelationType::Call | &RelationType::Halt
+                &RelationType::Call | &RelationType::Halt | &RelationType::Exception
             ));
         }
 
@@ -352,3 +353,195 @@ fn pe_hello_world_decom() {
         std::fs::write("logs/fireball_pe_hello_world_ast.log", ast_result).unwrap();
     });
 }
+
+#[test]
+fn pe_hello_world_calling_convention() {
+    let binary = hello_world_binary();
+    let pe = Pe::from_binary(binary.to_vec()).unwrap();
+    assert_eq!(
+        pe.get_calling_convention(),
+        crate::abstract_syntax_tree::AstCallingConvention::X64Microsoft
+    );
+}
+
+#[test]
+fn pe_hello_world_stack_frame() {
+    use crate::ir::analyze::{StackFrame, StackSlotKind, ir_function::generate_ir_function};
+
+    let binary = hello_world_binary();
+    let pe = Pe::from_binary(binary.to_vec()).unwrap();
+    let block = pe.analyze_from_entry().unwrap();
+    let function = generate_ir_function(&[block]);
+    let frame = StackFrame::new(&function, pe.architecture(), |_| None);
+
+    /*
+    push rbp
+    mov rbp, rsp
+    sub rsp, 0x30
+    mov dword ptr [rbp - 4], 0xff
+     */
+    let state = frame.instruction_state(3);
+    assert_eq!(state.sp, Some(-0x38));
+    assert_eq!(state.bp, Some(-0x8));
+    let slot = frame.slot_of(-0xc).expect("local at rbp - 4 not found");
+    let slot = &frame.get_slots()[slot];
+    assert_eq!(slot.offset, -0xc);
+    assert_eq!(slot.size, 4);
+    assert_eq!(slot.kind, StackSlotKind::Local);
+    assert_eq!(slot.name(), "local_c");
+    // the saved frame pointer is not a variable
+    assert_eq!(frame.slot_of(-0x8), None);
+}
+
+#[test]
+fn pe_hello_world_function_discovery() {
+    let binary = hello_world_binary();
+    let pe = Pe::from_binary(binary.to_vec()).unwrap();
+    pe.analyze_all().unwrap();
+
+    // Every function listed in .pdata is analyzed, not only those reachable from the entry
+    let gl = goblin::pe::PE::parse(binary).unwrap();
+    let sections = pe.get_sections();
+    let blocks = pe.get_blocks();
+    for function in gl.exception_data.unwrap().functions() {
+        let begin =
+            Address::from_virtual_address(&sections, function.unwrap().begin_address as u64);
+        assert!(
+            blocks.get_by_start_address(&begin).is_some(),
+            "function at {begin} not analyzed"
+        );
+    }
+}
+
+#[test]
+fn pe_hello_world_project() {
+    let binary = hello_world_binary();
+    let fireball = crate::Fireball::from_binary(binary.to_vec()).unwrap();
+    let blocks = fireball.analyze_all().unwrap();
+    let mut ast =
+        crate::ir::analyze::generate_ast_with_pre_defined_symbols(blocks, fireball.get_defined())
+            .unwrap()
+            .optimize(None)
+            .unwrap();
+    // the entry point
+    let mut annotations = crate::abstract_syntax_tree::AstAnnotations::new();
+    annotations.set_function_name(0x1125, Some("parse_header".to_owned()));
+    let path = std::env::temp_dir().join("__fireball_pe_hello_world_project__.fbp");
+    fireball
+        .save_project(&path, Some(&ast), &annotations)
+        .unwrap();
+
+    // A fresh session gets the blocks, the ast and the annotations back without analyzing
+    let restored = crate::Fireball::from_binary(binary.to_vec()).unwrap();
+    let project = restored.load_project(&path).unwrap();
+    assert_eq!(
+        restored.get_blocks().get_all().len(),
+        fireball.get_blocks().get_all().len()
+    );
+    assert_eq!(project.annotations, annotations);
+    let code = project.ast.unwrap().print(None);
+    assert!(code.contains(" parse_header("), "{code}");
+    ast.set_annotations(std::sync::Arc::new(annotations.clone()));
+    assert_eq!(code, ast.print(None));
+
+    // A project failing to load leaves the analysis state as it was
+    let partial = crate::Fireball::from_binary(binary.to_vec()).unwrap();
+    partial.analyze_from_entry().unwrap();
+    partial
+        .save_project(&path, Some(&ast), &annotations)
+        .unwrap();
+    let block_count = restored.get_blocks().get_all().len();
+    let relation_count = restored.get_relations().get_relations().len();
+    let defined_count = restored.get_defined().get_reader().len();
+    assert!(matches!(
+        restored.load_project(&path).unwrap_err(),
+        FireballError::ProjectFailed(_)
+    ));
+    assert_eq!(restored.get_blocks().get_all().len(), block_count);
+    assert_eq!(
+        restored.get_relations().get_relations().len(),
+        relation_count
+    );
+    assert_eq!(restored.get_defined().get_reader().len(), defined_count);
+
+    // Projects are refused for other binaries
+    let other = crate::Fireball::from_binary(super::hello_world_elf_binary().to_vec()).unwrap();
+    assert_eq!(
+        other.load_project(&path).unwrap_err(),
+        FireballError::StaleProject
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn pe_hello_world_export() {
+    let binary = hello_world_binary();
+    let fireball = crate::Fireball::from_binary(binary.to_vec()).unwrap();
+    let blocks = fireball.analyze_all().unwrap();
+    let ast =
+        crate::ir::analyze::generate_ast_with_pre_defined_symbols(blocks, fireball.get_defined())
+            .unwrap()
+            .optimize(None)
+            .unwrap();
+    let export = ast.export(None).unwrap();
+    assert_eq!(export.functions.len(), ast.function_versions.len());
+    assert!(
+        export
+            .functions
+            .windows(2)
+            .all(|x| x[0].address < x[1].address)
+    );
+    assert_eq!(export.source, ast.print(None));
+    let line_count = export.source.lines().count();
+    assert!(
+        export
+            .lines
+            .iter()
+            .all(|x| 1 <= x.line && x.line <= line_count && x.start < x.end)
+    );
+    for function in export.functions.iter() {
+        assert!(function.signature.contains(&function.name));
+        assert!(
+            function
+                .parameters
+                .iter()
+                .all(|x| function.signature.contains(&x.name))
+        );
+    }
+}
+
+#[test]
+fn pe_hello_world_exception_relation() {
+    use crate::exception_handling::{ExceptionFilter, ExceptionHandlerKind};
+
+    let binary = hello_world_binary();
+    let pe = Pe::from_binary(binary.to_vec()).unwrap();
+    let block = pe.generate_block_from_address(pe.entry());
+
+    // the call at the end of the entry block is in a __try with a filter function
+    let handlers = block.get_exception_handlers();
+    assert_eq!(handlers.len(), 1);
+    let handler = &handlers[0];
+    assert_eq!((handler.start, handler.end), (0x1134, 0x114a));
+    assert!(matches!(
+        handler.kind,
+        ExceptionHandlerKind::Except(ExceptionFilter::Function(_))
+    ));
+    let landing_pads: Vec<_> = block
+        .get_connected_to()
+        .iter()
+        .filter(|x| *x.relation_type() == RelationType::Exception)
+        .map(|x| x.to().unwrap().get_virtual_address())
+        .collect();
+    assert_eq!(landing_pads, [handler.handler]);
+
+    // the landing pad is reached only through the exception
+    let landing_pad = Address::from_virtual_address(&pe.get_sections(), handler.handler);
+    let landing_pad = pe.generate_block_from_address(&landing_pad);
+    assert!(
+        landing_pad
+            .get_connected_from()
+            .iter()
+            .all(|x| *x.relation_type() == RelationType::Exception)
+    );
+}
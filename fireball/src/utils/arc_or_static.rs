@@ -107,6 +107,16 @@ impl<T: std::hash::Hash> std::hash::Hash for Aos<T> {
         core::mem::discriminant(inner).hash(state);
     }
 }
+impl<T: serde::Serialize> serde::Serialize for Aos<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Aos<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
 impl<T: std::fmt::Display> std::fmt::Display for Aos<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_ref())
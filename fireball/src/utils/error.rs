@@ -13,11 +13,16 @@ pub enum FireballError {
     PeParsingFailed(String),
     ElfParsingFailed(String),
     MachOParsingFailed(String),
+    PdbParsingFailed(String),
     CapstoneInitializationFailed(String),
     MalformedPe(String),
     MalformedElf(String),
     MalformedMachO(String),
     UnsupportedFormat,
+    EmulationFailed(String),
+    PatchFailed(String),
+    ProjectFailed(String),
+    StaleProject,
 }
 
 impl std::fmt::Display for FireballError {
@@ -30,6 +35,7 @@ impl std::fmt::Display for FireballError {
             Self::PeParsingFailed(err) => write!(f, "PE parsing failed: {err}"),
             Self::ElfParsingFailed(err) => write!(f, "ELF parsing failed: {err}"),
             Self::MachOParsingFailed(err) => write!(f, "Mach-O parsing failed: {err}"),
+            Self::PdbParsingFailed(err) => write!(f, "PDB parsing failed: {err}"),
             Self::CapstoneInitializationFailed(err) => {
                 write!(f, "Capstone initialization failed: {err}")
             }
@@ -37,6 +43,10 @@ impl std::fmt::Display for FireballError {
             Self::MalformedElf(err) => write!(f, "Malformed ELF metadata: {err}"),
             Self::MalformedMachO(err) => write!(f, "Malformed Mach-O metadata: {err}"),
             Self::UnsupportedFormat => write!(f, "Unsupported binary format"),
+            Self::EmulationFailed(err) => write!(f, "Emulation failed: {err}"),
+            Self::PatchFailed(err) => write!(f, "Patch failed: {err}"),
+            Self::ProjectFailed(err) => write!(f, "Project file error: {err}"),
+            Self::StaleProject => write!(f, "Project file was saved for another binary"),
         }
     }
 }
@@ -71,8 +81,21 @@ impl From<goblin::error::Error> for FireballError {
     }
 }
 
+impl From<pdb::Error> for FireballError {
+    fn from(err: pdb::Error) -> Self {
+        Self::PdbParsingFailed(err.to_string())
+    }
+}
+
 impl From<capstone::Error> for FireballError {
     fn from(err: capstone::Error) -> Self {
         Self::CapstoneInitializationFailed(err.to_string())
     }
 }
+
+#[cfg(feature = "unicorn")]
+impl From<unicorn_engine::unicorn_const::uc_error> for FireballError {
+    fn from(err: unicorn_engine::unicorn_const::uc_error) -> Self {
+        Self::EmulationFailed(format!("{err:?}"))
+    }
+}
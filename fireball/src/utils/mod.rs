@@ -1,6 +1,7 @@
 mod arc_or_static;
 pub mod error;
 mod logs;
+pub(crate) mod static_str;
 pub mod version_map;
 
 pub use arc_or_static::*;
@@ -0,0 +1,40 @@
+//! Serde helpers for the `&'static str` fields of statements, such as exception messages.
+//!
+//! Use with `#[serde(with = "crate::utils::static_str")]` on a field of type [`StaticStr`].
+
+use serde::{Deserialize, Deserializer, Serializer};
+use std::{
+    collections::HashSet,
+    sync::{LazyLock, Mutex},
+};
+
+/// `&'static str`, named so serde doesn't borrow the field from the input
+pub(crate) type StaticStr = &'static str;
+
+pub(crate) fn serialize<S: Serializer>(
+    text: &&'static str,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(text)
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<&'static str, D::Error> {
+    String::deserialize(deserializer).map(|x| intern(&x))
+}
+
+/// Returns a string living as long as the program, for the statements holding static messages.
+///
+/// ### Note
+/// - Each distinct text is leaked once, so reading the same statements again doesn't leak more.
+pub(crate) fn intern(text: &str) -> &'static str {
+    static INTERNED: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+    let mut interned = INTERNED.lock().unwrap();
+    if let Some(x) = interned.get(text) {
+        return x;
+    }
+    let leaked: &'static str = Box::leak(text.to_owned().into_boxed_str());
+    interned.insert(leaked);
+    leaked
+}
@@ -1,21 +1,28 @@
+pub mod control_flow_graph;
 pub mod default_tabs;
 pub mod display_current_ast;
+pub mod listing;
 pub mod select_optimization;
 pub mod select_target_block;
+pub mod selection;
 
 use crate::Firebat;
+use control_flow_graph::ControlFlowGraphData;
 use display_current_ast::DisplayCurrentAstData;
 use egui::emath::TSTransform;
 use fireball::{
     Fireball,
-    abstract_syntax_tree::Ast,
-    core::{Address, FireRaw},
+    abstract_syntax_tree::{Ast, AstAnnotations},
+    core::{Address, Block, FireRaw},
 };
+use listing::ListingData;
 use select_optimization::{SelectOptimizationChoice, SelectOptimizationData};
 use select_target_block::SelectTargetBlockData;
+use selection::BoardSelection;
 use std::{
     collections::{HashMap, HashSet, VecDeque, hash_map::DefaultHasher},
     hash::{Hash, Hasher},
+    ops::Range,
     sync::Arc,
 };
 
@@ -45,6 +52,10 @@ pub struct BoardData {
     pub pipeline: BoardPipeline,
     /// Raised by the top bar, consumed at the beginning of the next frame.
     pub decompile_requested: bool,
+    /// Block clicked in a graph window, consumed at the beginning of the next frame.
+    pub focus_requested: Option<Range<u64>>,
+    /// Line, instruction or ir statement clicked last, highlighted in every window showing it.
+    pub selection: Option<BoardSelection>,
     /// Bumped whenever another binary is loaded, invalidating every cached ast.
     binary_generation: u64,
 }
@@ -72,7 +83,9 @@ pub struct BoardWindow {
 pub enum BoardWindowKind {
     SelectTargetBlock(SelectTargetBlockData),
     SelectOptimization(SelectOptimizationData),
-    DisplayCurrentAst(DisplayCurrentAstData),
+    DisplayCurrentAst(Box<DisplayCurrentAstData>),
+    ControlFlowGraph(Box<ControlFlowGraphData>),
+    Listing(Box<ListingData>),
 }
 
 /// Inputs a window ast depends on, so an unchanged window can keep the ast it already holds.
@@ -90,6 +103,10 @@ pub struct BoardPipeline {
     /// Amount of windows spawned so far, used to build unique identifiers.
     spawned: u64,
     blocks: Vec<u64>,
+    /// Names, types and comments given by the user, applied to every generated ast.
+    annotations: AstAnnotations,
+    /// Bumped whenever the annotations change, rebuilding every ast.
+    annotations_revision: u64,
 }
 
 impl BoardWindow {
@@ -123,6 +140,8 @@ impl BoardWindowKind {
             Self::SelectTargetBlock(data) => select_target_block::ui(app, data, ui),
             Self::SelectOptimization(data) => select_optimization::ui(app, id, data, ui),
             Self::DisplayCurrentAst(data) => display_current_ast::ui(app, id, data, ui),
+            Self::ControlFlowGraph(data) => control_flow_graph::ui(app, id, data, ui),
+            Self::Listing(data) => listing::ui(app, id, data, ui),
         }
     }
 }
@@ -133,12 +152,35 @@ impl BoardPipeline {
         self.blocks = blocks;
     }
 
+    /// Names, types and comments given by the user.
+    pub fn annotations(&self) -> &AstAnnotations {
+        &self.annotations
+    }
+
+    /// Edits the annotations, so the next decompilation applies them.
+    pub fn annotate(&mut self, edit: impl FnOnce(&mut AstAnnotations)) {
+        self.annotations_revision += 1;
+        edit(&mut self.annotations);
+    }
+
     /// Identifier for a window spawned from the given parent.
     fn spawn_id(&mut self, parent: &str, kind: &str) -> String {
         self.spawned += 1;
         format!("{parent}::{kind}::{}", self.spawned)
     }
 
+    /// Selected blocks, skipping the ones not analyzed.
+    fn targets(&self, fireball: &Fireball) -> Vec<Arc<Block>> {
+        let sections = fireball.get_sections();
+        let known = fireball.get_blocks();
+        self.blocks
+            .iter()
+            .filter_map(|address| {
+                known.get_by_start_address(&Address::from_virtual_address(&sections, *address))
+            })
+            .collect()
+    }
+
     /// Builds the ast every chain of windows starts from.
     fn generate_ast(&self, fireball: Option<&Fireball>) -> Result<SharedAst, String> {
         let Some(fireball) = fireball else {
@@ -148,23 +190,13 @@ impl BoardPipeline {
             return Err("no block is selected".to_owned());
         }
 
-        let sections = fireball.get_sections();
-        let known = fireball.get_blocks();
-        let mut targets = Vec::with_capacity(self.blocks.len());
-        for address in &self.blocks {
-            let address = Address::from_virtual_address(&sections, *address);
-            let Some(block) = known.get_by_start_address(&address) else {
-                continue;
-            };
-            targets.push(block);
-        }
-
-        fireball::ir::analyze::generate_ast_with_pre_defined_symbols(
-            targets,
-            fireball.get_defined(),
-        )
-        .map(Arc::new)
-        .map_err(|error| format!("ast generation failed: {error:?}"))
+        fireball
+            .generate_ast(self.targets(fireball))
+            .map(|mut ast| {
+                ast.set_annotations(Arc::new(self.annotations.clone()));
+                Arc::new(ast)
+            })
+            .map_err(|error| format!("ast generation failed: {error:?}"))
     }
 
     /// Applies the given optimization on the ast of the parent window.
@@ -201,9 +233,49 @@ impl BoardData {
         window.connected_to.push(to.to_owned());
     }
 
+    /// Selects the block in the windows picking blocks, and shows its statements in the windows
+    /// printing the ast.
+    fn focus(&mut self, block: Range<u64>) {
+        for window in self.windows.iter_mut() {
+            match &mut window.kind {
+                BoardWindowKind::SelectTargetBlock(data) => {
+                    let picked = data.focus(block.start);
+                    if picked {
+                        self.pipeline.set_blocks(data.selected_blocks());
+                    }
+                }
+                BoardWindowKind::DisplayCurrentAst(data) => data.scroll_to(&block),
+                _ => {}
+            }
+        }
+    }
+
+    /// Lists the blocks restored from a project as analyzed and picked in the windows picking
+    /// blocks, dropping the asts built from the blocks they replace.
+    pub fn restore_blocks(&mut self, mut blocks: Vec<Arc<Block>>) {
+        blocks.sort_unstable_by_key(|it| it.get_start_address().get_virtual_address());
+        self.invalidate();
+        for window in self.windows.iter_mut() {
+            if let BoardWindowKind::SelectTargetBlock(data) = &mut window.kind {
+                data.insert_blocks(&blocks);
+                self.pipeline.set_blocks(data.selected_blocks());
+            }
+        }
+        self.decompile_requested = true;
+    }
+
+    /// Ast generated from the picked blocks, before any optimization.
+    pub fn root_ast(&self) -> Option<&Ast> {
+        self.windows
+            .iter()
+            .find(|it| it.id == select_target_block::WINDOW_ID)
+            .and_then(|it| it.ast.as_deref())
+    }
+
     /// Marks every cached ast as stale, since the binary they were built from changed.
     pub fn invalidate(&mut self) {
         self.binary_generation += 1;
+        self.selection = None;
     }
 
     /// Rebuilds the ast of the windows whose inputs changed, keeping the ones already up to date.
@@ -225,14 +297,19 @@ impl BoardData {
             let fingerprint = BoardWindowFingerprint {
                 parent: parent_ast.as_ref().map(|it| Arc::as_ptr(it) as usize),
                 own: match &self.windows[index].kind {
-                    BoardWindowKind::SelectTargetBlock(_) => {
-                        format!("{}:{:?}", self.binary_generation, self.pipeline.blocks)
-                    }
+                    BoardWindowKind::SelectTargetBlock(_) => format!(
+                        "{}:{:?}:{}",
+                        self.binary_generation,
+                        self.pipeline.blocks,
+                        self.pipeline.annotations_revision
+                    ),
                     BoardWindowKind::SelectOptimization(_) => match &choice {
                         Some(choice) => choice_fingerprint(choice),
                         None => String::new(),
                     },
-                    BoardWindowKind::DisplayCurrentAst(_) => String::new(),
+                    BoardWindowKind::DisplayCurrentAst(_)
+                    | BoardWindowKind::ControlFlowGraph(_)
+                    | BoardWindowKind::Listing(_) => String::new(),
                 },
             };
 
@@ -267,7 +344,10 @@ impl BoardData {
                 },
                 BoardWindowKind::DisplayCurrentAst(data) => match &parent_ast {
                     Some(ast) => {
-                        data.set_ast(ast.print(None));
+                        let (code, source_map) = ast.print_with_source_map(None);
+                        data.set_ast(code);
+                        data.set_source_map(source_map);
+                        data.set_decompiled(ast.clone());
                         Ok(ast.clone())
                     }
                     None => {
@@ -276,6 +356,14 @@ impl BoardData {
                         Err(error)
                     }
                 },
+                // The graph and listings follow the selected blocks by themselves, and only hand
+                // the ast over.
+                BoardWindowKind::ControlFlowGraph(_) | BoardWindowKind::Listing(_) => {
+                    match &parent_ast {
+                        Some(ast) => Ok(ast.clone()),
+                        None => Err(missing_ast(parent_id.as_deref(), &errors)),
+                    }
+                }
             };
 
             match result {
@@ -375,6 +463,8 @@ impl Default for BoardData {
             windows: Vec::new(),
             pipeline: BoardPipeline::default(),
             decompile_requested: false,
+            focus_requested: None,
+            selection: None,
             binary_generation: 0,
         }
     }
@@ -384,6 +474,9 @@ pub fn ui(app: &mut Firebat, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
     if std::mem::take(&mut app.board.decompile_requested) {
         app.board.decompile(app.fireball.as_ref());
     }
+    if let Some(block) = app.board.focus_requested.take() {
+        app.board.focus(block);
+    }
 
     let outer_rect = ui.available_rect_before_wrap();
     if app.board.scene_rect.size() == egui::Vec2::ZERO {
@@ -608,6 +701,32 @@ fn header_actions(
             ));
         }
     }
+
+    // The graph and listings only depend on the selected blocks, so they hang from the window
+    // picking them.
+    if matches!(kind, BoardWindowKind::SelectTargetBlock(_)) {
+        if ui.small_button("CFG").clicked() {
+            let spawn_id = app.board.pipeline.spawn_id(id, "cfg");
+            spawned.push((
+                id.to_owned(),
+                control_flow_graph::window(spawn_id, spawn_pos),
+            ));
+        }
+        if ui.small_button("Asm").clicked() {
+            let spawn_id = app.board.pipeline.spawn_id(id, "disassembly");
+            spawned.push((
+                id.to_owned(),
+                listing::window(spawn_id, spawn_pos, listing::ListingKind::Disassembly),
+            ));
+        }
+        if ui.small_button("IR").clicked() {
+            let spawn_id = app.board.pipeline.spawn_id(id, "ir");
+            spawned.push((
+                id.to_owned(),
+                listing::window(spawn_id, spawn_pos, listing::ListingKind::Ir),
+            ));
+        }
+    }
 }
 
 /// Draws an arrowed line between every connected window.
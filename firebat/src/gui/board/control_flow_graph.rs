@@ -0,0 +1,490 @@
+use crate::{
+    Firebat,
+    gui::board::{BoardWindow, BoardWindowKind},
+};
+use fireball::{
+    core::{Block, RelationType},
+    ir::statements::{IrStatement, IrStatementSpecial},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    ops::RangeInclusive,
+    sync::Arc,
+};
+
+/// Shown while no block is selected.
+const EMPTY_HINT: &str = "select blocks in Select Target Block";
+
+/// Width of the canvas the graph is drawn on.
+const VIEW_WIDTH: f32 = 720.0;
+
+/// Height of the canvas the graph is drawn on.
+const VIEW_HEIGHT: f32 = 520.0;
+
+const ZOOM_RANGE: RangeInclusive<f32> = 0.05..=4.0;
+
+/// Zoom applied by a single press of the zoom buttons.
+const ZOOM_STEP: f32 = 1.25;
+
+/// Size of the node text at a zoom of 1.
+const FONT_SIZE: f32 = 11.0;
+
+/// Text smaller than this, in points, is not drawn since it can't be read anyway.
+const MIN_FONT_SIZE: f32 = 3.0;
+
+/// Space between the text of a node and its border, in graph coordinates.
+const NODE_PADDING: f32 = 6.0;
+
+/// Distance between two nodes of the same layer, in graph coordinates.
+const NODE_SPACING: f32 = 40.0;
+
+/// Distance between two layers, in graph coordinates.
+const LAYER_SPACING: f32 = 60.0;
+
+/// Size of the arrow head drawn at the end of an edge, in graph coordinates.
+const ARROW_SIZE: f32 = 8.0;
+
+const TAKEN_COLOR: egui::Color32 = egui::Color32::from_rgb(0x4c, 0xaf, 0x50);
+const NOT_TAKEN_COLOR: egui::Color32 = egui::Color32::from_rgb(0xe5, 0x39, 0x35);
+const CALL_COLOR: egui::Color32 = egui::Color32::from_rgb(0xab, 0x47, 0xbc);
+const SWITCH_COLOR: egui::Color32 = egui::Color32::from_rgb(0x42, 0xa5, 0xf5);
+const EXCEPTION_COLOR: egui::Color32 = egui::Color32::from_rgb(0xff, 0x98, 0x00);
+
+/// State of the window which draws the graph of the selected blocks.
+pub struct ControlFlowGraphData {
+    /// Binary generation and blocks the graph was built from, to rebuild it when they change.
+    source: Option<(u64, Vec<u64>)>,
+    nodes: Vec<ControlFlowGraphNode>,
+    edges: Vec<ControlFlowGraphEdge>,
+    /// Size of the laid out graph, in graph coordinates.
+    size: egui::Vec2,
+    /// Height of a line of node text, in graph coordinates.
+    line_height: f32,
+    zoom: f32,
+    /// Position of the graph origin inside the canvas, in points.
+    offset: egui::Vec2,
+    /// Start address of the node clicked last.
+    selected: Option<u64>,
+}
+
+/// A block of the graph.
+struct ControlFlowGraphNode {
+    start_address: u64,
+    end_address: u64,
+    lines: Vec<(ControlFlowGraphLine, String)>,
+    /// Top left corner, in graph coordinates.
+    pos: egui::Pos2,
+    size: egui::Vec2,
+}
+
+/// What a line of node text shows, deciding its color.
+#[derive(Clone, Copy)]
+enum ControlFlowGraphLine {
+    Header,
+    Instruction,
+    Ir,
+    /// Where the control leaves the graph (calls, returns)
+    Exit,
+}
+
+/// A relation between two blocks of the graph.
+struct ControlFlowGraphEdge {
+    from: usize,
+    to: usize,
+    kind: RelationType,
+}
+
+impl Default for ControlFlowGraphData {
+    fn default() -> Self {
+        Self {
+            source: None,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            size: egui::Vec2::ZERO,
+            line_height: FONT_SIZE,
+            zoom: 1.0,
+            offset: egui::Vec2::ZERO,
+            selected: None,
+        }
+    }
+}
+
+impl ControlFlowGraphData {
+    /// Rebuilds the nodes and edges from the given blocks, and lays them out.
+    fn build(&mut self, ui: &egui::Ui, blocks: Vec<Arc<Block>>) {
+        let font = egui::FontId::monospace(FONT_SIZE);
+        let (char_width, line_height) = ui.ctx().fonts_mut(|fonts| {
+            (
+                fonts.glyph_width(&font, ' ').max(1.0),
+                fonts.row_height(&font),
+            )
+        });
+
+        let index: HashMap<u64, usize> = blocks
+            .iter()
+            .enumerate()
+            .map(|(i, block)| (block.get_start_address().get_virtual_address(), i))
+            .collect();
+        self.nodes = blocks.iter().map(|block| node(block, &index)).collect();
+        self.edges = Vec::new();
+        for (from, block) in blocks.iter().enumerate() {
+            for relation in block.get_connected_to().iter() {
+                let Some(to) = relation
+                    .to()
+                    .and_then(|to| index.get(&to.get_virtual_address()).copied())
+                else {
+                    continue;
+                };
+                self.edges.push(ControlFlowGraphEdge {
+                    from,
+                    to,
+                    kind: *relation.relation_type(),
+                });
+            }
+        }
+
+        for node in self.nodes.iter_mut() {
+            let widest = node
+                .lines
+                .iter()
+                .map(|(_, text)| text.chars().count())
+                .max()
+                .unwrap_or(0);
+            node.size = egui::vec2(
+                widest as f32 * char_width,
+                node.lines.len() as f32 * line_height,
+            ) + egui::Vec2::splat(NODE_PADDING * 2.0);
+        }
+        self.line_height = line_height;
+        self.size = layout(&mut self.nodes, &self.edges);
+        self.fit();
+    }
+
+    /// Zooms and moves the graph so it fits the canvas.
+    fn fit(&mut self) {
+        if self.size == egui::Vec2::ZERO {
+            return;
+        }
+        let view = egui::vec2(VIEW_WIDTH, VIEW_HEIGHT);
+        self.zoom = (view.x / self.size.x)
+            .min(view.y / self.size.y)
+            .clamp(*ZOOM_RANGE.start(), 1.0);
+        self.offset = (view - self.size * self.zoom) / 2.0;
+    }
+
+    /// Zooms by the given factor, keeping the graph point under the anchor in place.
+    fn zoom_by(&mut self, factor: f32, anchor: egui::Vec2) {
+        let zoom = (self.zoom * factor).clamp(*ZOOM_RANGE.start(), *ZOOM_RANGE.end());
+        self.offset = anchor - (anchor - self.offset) * (zoom / self.zoom);
+        self.zoom = zoom;
+    }
+}
+
+/// Creates the window which draws the graph of the selected blocks.
+pub fn window(id: impl Into<String>, pos: egui::Pos2) -> BoardWindow {
+    BoardWindow::new(
+        id,
+        "Control Flow Graph",
+        pos,
+        BoardWindowKind::ControlFlowGraph(Box::default()),
+    )
+}
+
+pub fn ui(app: &mut Firebat, _id: &str, data: &mut ControlFlowGraphData, ui: &mut egui::Ui) {
+    let source = (
+        app.board.binary_generation,
+        app.board.pipeline.blocks.clone(),
+    );
+    if data.source.as_ref() != Some(&source) {
+        let blocks = app
+            .fireball
+            .as_ref()
+            .map(|fireball| app.board.pipeline.targets(fireball))
+            .unwrap_or_default();
+        data.build(ui, blocks);
+        data.source = Some(source);
+    }
+
+    ui.horizontal(|ui| {
+        let center = egui::vec2(VIEW_WIDTH, VIEW_HEIGHT) / 2.0;
+        if ui.small_button("-").clicked() {
+            data.zoom_by(1.0 / ZOOM_STEP, center);
+        }
+        if ui.small_button("+").clicked() {
+            data.zoom_by(ZOOM_STEP, center);
+        }
+        if ui.small_button("Fit").clicked() {
+            data.fit();
+        }
+        ui.label(format!("{:.0}%", data.zoom * 100.0));
+    });
+
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(VIEW_WIDTH, VIEW_HEIGHT),
+        egui::Sense::click_and_drag(),
+    );
+    data.offset += response.drag_delta();
+    if response.hovered() {
+        let factor = ui.input(|i| i.zoom_delta());
+        if factor != 1.0
+            && let Some(pointer) = response.hover_pos()
+        {
+            data.zoom_by(factor, pointer - rect.min);
+        }
+    }
+
+    let to_screen = |pos: egui::Pos2| rect.min + data.offset + pos.to_vec2() * data.zoom;
+    if response.clicked()
+        && let Some(pointer) = response.interact_pointer_pos()
+    {
+        let clicked = data.nodes.iter().find(|node| {
+            egui::Rect::from_min_size(to_screen(node.pos), node.size * data.zoom).contains(pointer)
+        });
+        if let Some(node) = clicked {
+            data.selected = Some(node.start_address);
+            app.board.focus_requested = Some(node.start_address..node.end_address);
+        }
+    }
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+    if data.nodes.is_empty() {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            EMPTY_HINT,
+            egui::FontId::proportional(FONT_SIZE),
+            ui.visuals().weak_text_color(),
+        );
+        return;
+    }
+
+    for edge in &data.edges {
+        let from = &data.nodes[edge.from];
+        let to = &data.nodes[edge.to];
+        let (color, width) = match edge.kind {
+            RelationType::Jcc => (TAKEN_COLOR, 1.5),
+            RelationType::Continued => (NOT_TAKEN_COLOR, 1.5),
+            RelationType::Call => (CALL_COLOR, 1.0),
+            RelationType::Switch => (SWITCH_COLOR, 1.0),
+            RelationType::Exception => (EXCEPTION_COLOR, 1.0),
+            RelationType::Halt => (ui.visuals().weak_text_color(), 1.0),
+            RelationType::Jump | RelationType::Return => (ui.visuals().text_color(), 1.5),
+        };
+        let stroke = egui::Stroke::new(width * data.zoom.max(0.5), color);
+        let points = edge_points(from, to).map(to_screen);
+        painter.add(egui::epaint::CubicBezierShape::from_points_stroke(
+            points,
+            false,
+            egui::Color32::TRANSPARENT,
+            stroke,
+        ));
+        arrow(
+            &painter,
+            points[2],
+            points[3],
+            ARROW_SIZE * data.zoom,
+            color,
+        );
+    }
+
+    let font_size = FONT_SIZE * data.zoom;
+    let font = egui::FontId::monospace(font_size);
+    for node in &data.nodes {
+        let node_rect = egui::Rect::from_min_size(to_screen(node.pos), node.size * data.zoom);
+        let stroke = if data.selected == Some(node.start_address) {
+            egui::Stroke::new(2.0, ui.visuals().selection.stroke.color)
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        painter.rect(
+            node_rect,
+            4.0 * data.zoom,
+            ui.visuals().window_fill,
+            stroke,
+            egui::StrokeKind::Inside,
+        );
+        if font_size < MIN_FONT_SIZE {
+            continue;
+        }
+        for (row, (line, text)) in node.lines.iter().enumerate() {
+            let color = match line {
+                ControlFlowGraphLine::Header => ui.visuals().strong_text_color(),
+                ControlFlowGraphLine::Instruction => ui.visuals().text_color(),
+                ControlFlowGraphLine::Ir => ui.visuals().weak_text_color(),
+                ControlFlowGraphLine::Exit => ui.visuals().warn_fg_color,
+            };
+            let pos = node.pos
+                + egui::Vec2::splat(NODE_PADDING)
+                + egui::vec2(0.0, row as f32 * data.line_height);
+            painter.text(
+                to_screen(pos),
+                egui::Align2::LEFT_TOP,
+                text,
+                font.clone(),
+                color,
+            );
+        }
+    }
+}
+
+/// Text of a node: the address range, the disassembly with the ir lifted from each instruction
+/// below it, and where the control leaves the graph.
+fn node(block: &Block, index: &HashMap<u64, usize>) -> ControlFlowGraphNode {
+    let start_address = block.get_start_address().get_virtual_address();
+    let end_address = start_address + block.get_block_size().copied().unwrap_or(0);
+    let mut lines = vec![(
+        ControlFlowGraphLine::Header,
+        format!("0x{start_address:x} - 0x{end_address:x}"),
+    )];
+
+    let ir = block.get_ir();
+    let ir = ir.as_ref().map(|ir| ir.ir());
+    for (i, instruction) in block.get_instructions().iter().enumerate() {
+        lines.push((ControlFlowGraphLine::Instruction, instruction.to_string()));
+        let Some(statements) = ir.and_then(|ir| ir.get(i)).and_then(|ir| ir.statements) else {
+            continue;
+        };
+        // Type hints only matter to the analysis, and would double the size of every node
+        let statements = statements.iter().filter(|statement| {
+            !matches!(
+                statement,
+                IrStatement::Special(IrStatementSpecial::TypeSpecified { .. })
+            )
+        });
+        for statement in statements {
+            lines.push((ControlFlowGraphLine::Ir, format!("  {statement}")));
+        }
+    }
+
+    for relation in block.get_connected_to().iter() {
+        let to = relation.to().map(|to| to.get_virtual_address());
+        let text = match (relation.relation_type(), to) {
+            (RelationType::Return, _) => "return".to_owned(),
+            (RelationType::Call, Some(to)) if !index.contains_key(&to) => format!("call 0x{to:x}"),
+            (RelationType::Call, None) => "call ?".to_owned(),
+            (RelationType::Jump, None) => "jump ?".to_owned(),
+            _ => continue,
+        };
+        lines.push((ControlFlowGraphLine::Exit, text));
+    }
+
+    ControlFlowGraphNode {
+        start_address,
+        end_address,
+        lines,
+        pos: egui::Pos2::ZERO,
+        size: egui::Vec2::ZERO,
+    }
+}
+
+/// Places the nodes in layers by their distance from the nodes nothing jumps to, each layer
+/// centered below the previous one.
+///
+/// ### Returns
+/// - `egui::Vec2` - size of the laid out graph
+fn layout(nodes: &mut [ControlFlowGraphNode], edges: &[ControlFlowGraphEdge]) -> egui::Vec2 {
+    let mut successors = vec![Vec::new(); nodes.len()];
+    let mut has_predecessor = vec![false; nodes.len()];
+    for edge in edges {
+        if edge.from != edge.to && edge.kind != RelationType::Call {
+            successors[edge.from].push(edge.to);
+            has_predecessor[edge.to] = true;
+        }
+    }
+
+    // Breadth first from the roots, then from every node left over (e.g. loops entered from
+    // outside of the selection), in address order.
+    let mut by_address: Vec<usize> = (0..nodes.len()).collect();
+    by_address.sort_by_key(|i| nodes[*i].start_address);
+    let roots = by_address
+        .iter()
+        .filter(|i| !has_predecessor[**i])
+        .chain(by_address.iter());
+    let mut layer_of: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut layers: Vec<Vec<usize>> = Vec::new();
+    for root in roots {
+        if layer_of[*root].is_some() {
+            continue;
+        }
+        let mut queue = VecDeque::from([(*root, 0)]);
+        layer_of[*root] = Some(0);
+        while let Some((node, layer)) = queue.pop_front() {
+            if layers.len() <= layer {
+                layers.resize(layer + 1, Vec::new());
+            }
+            layers[layer].push(node);
+            for next in &successors[node] {
+                if layer_of[*next].is_none() {
+                    layer_of[*next] = Some(layer + 1);
+                    queue.push_back((*next, layer + 1));
+                }
+            }
+        }
+    }
+
+    let widths: Vec<f32> = layers
+        .iter()
+        .map(|layer| {
+            layer.iter().map(|i| nodes[*i].size.x).sum::<f32>()
+                + NODE_SPACING * layer.len().saturating_sub(1) as f32
+        })
+        .collect();
+    let width = widths.iter().copied().fold(0.0, f32::max);
+    let mut y = 0.0;
+    for (layer, layer_width) in layers.iter().zip(widths) {
+        let mut x = (width - layer_width) / 2.0;
+        let mut height: f32 = 0.0;
+        for i in layer {
+            nodes[*i].pos = egui::pos2(x, y);
+            x += nodes[*i].size.x + NODE_SPACING;
+            height = height.max(nodes[*i].size.y);
+        }
+        y += height + LAYER_SPACING;
+    }
+    egui::vec2(width, (y - LAYER_SPACING).max(0.0))
+}
+
+/// Control points of the curve drawn for an edge, in graph coordinates.
+///
+/// Edges going down leave from the bottom of the node and enter the top of the next one, the
+/// others (loops) go around the right side of both nodes.
+fn edge_points(from: &ControlFlowGraphNode, to: &ControlFlowGraphNode) -> [egui::Pos2; 4] {
+    let from_rect = egui::Rect::from_min_size(from.pos, from.size);
+    let to_rect = egui::Rect::from_min_size(to.pos, to.size);
+    if from_rect.bottom() < to_rect.top() {
+        let start = from_rect.center_bottom();
+        let end = to_rect.center_top();
+        let bend = egui::vec2(0.0, (end.y - start.y) / 2.0);
+        [start, start + bend, end - bend, end]
+    } else {
+        let start = from_rect.right_center();
+        let end = to_rect.right_center();
+        let bend = egui::vec2(
+            LAYER_SPACING + (from_rect.right() - to_rect.right()).abs() / 2.0,
+            0.0,
+        );
+        [start, start + bend, end + bend, end]
+    }
+}
+
+/// Draws the arrow head of an edge arriving at `tip` from `from`.
+fn arrow(
+    painter: &egui::Painter,
+    from: egui::Pos2,
+    tip: egui::Pos2,
+    size: f32,
+    color: egui::Color32,
+) {
+    let direction = (tip - from).normalized();
+    if !direction.is_finite() {
+        return;
+    }
+    let normal = egui::vec2(-direction.y, direction.x);
+    let base = tip - direction * size;
+    painter.add(egui::Shape::convex_polygon(
+        vec![tip, base + normal * size / 2.0, base - normal * size / 2.0],
+        color,
+        egui::Stroke::NONE,
+    ));
+}
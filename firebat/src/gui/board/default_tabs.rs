@@ -60,6 +60,9 @@ fn default_optimizations(config: &AstOptimizationConfig) -> Vec<&'static str> {
     if config.ir_analyzation {
         names.push("Ir Analyzation");
     }
+    if config.stack_frame_analyzation {
+        names.push("Stack Frame Analyzation");
+    }
     if config.parameter_analyzation {
         names.push("Parameter Analyzation");
     }
@@ -73,6 +76,9 @@ fn default_optimizations(config: &AstOptimizationConfig) -> Vec<&'static str> {
             names.push("Collapse Unused Variables");
         }
     }
+    if config.struct_recovery {
+        names.push("Struct Recovery");
+    }
 
     names
 }
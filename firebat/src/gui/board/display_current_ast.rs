@@ -1,7 +1,11 @@
 use crate::{
     Firebat,
-    gui::board::{BoardWindow, BoardWindowKind},
+    gui::board::{BoardWindow, BoardWindowKind, SharedAst, selection::BoardSelection},
 };
+use fireball::abstract_syntax_tree::{
+    AstAnnotations, AstFunctionId, AstSourceMap, AstValueType, AstVariableKey, PrintWithConfig,
+};
+use std::{collections::HashSet, ops::Range};
 
 /// Shown until the first decompilation is requested.
 const EMPTY_HINT: &str = "press Decompile in the top bar";
@@ -18,6 +22,44 @@ pub struct DisplayCurrentAstData {
     lines: Vec<String>,
     /// Longest line in characters, used to size the horizontal scroll area.
     widest: usize,
+    /// Instructions each line was decompiled from, if known.
+    source_map: Option<AstSourceMap>,
+    /// Lines decompiled from the block clicked last in a graph window, or showing the selection.
+    highlighted: HashSet<usize>,
+    /// Selection the window highlighted last, to only follow the ones made elsewhere.
+    seen: Option<BoardSelection>,
+    /// Line to bring into view on the next frame.
+    scroll_to: Option<usize>,
+    /// Ast the lines were printed from.
+    decompiled: Option<SharedAst>,
+    /// Annotations of the function of the line clicked last.
+    form: AnnotationForm,
+}
+
+/// Annotations of a function being edited, applied all at once.
+///
+/// ### Note
+/// Empty fields give back what was detected, which is shown while they are empty.
+#[derive(Default)]
+struct AnnotationForm {
+    /// Edited function
+    function: Option<AstFunctionId>,
+    /// Instruction the clicked line was decompiled from, the one the comment goes to
+    instruction: Option<u64>,
+    name: String,
+    return_type: String,
+    parameter_count: String,
+    comment: String,
+    /// Detected name, return type and parameter count, shown while the fields are empty
+    detected: [String; 3],
+    /// Variables of the function, by printed name, with their detected type
+    variables: Vec<(String, AstVariableKey, String)>,
+    /// Index of the variable being edited
+    variable: Option<usize>,
+    variable_name: String,
+    variable_type: String,
+    /// Why the last apply failed
+    error: Option<String>,
 }
 
 impl Default for DisplayCurrentAstData {
@@ -25,6 +67,12 @@ impl Default for DisplayCurrentAstData {
         let mut data = Self {
             lines: Vec::new(),
             widest: 0,
+            source_map: None,
+            highlighted: HashSet::new(),
+            seen: None,
+            scroll_to: None,
+            decompiled: None,
+            form: AnnotationForm::default(),
         };
         data.set_ast(EMPTY_HINT.to_owned());
         data
@@ -44,6 +92,227 @@ impl DisplayCurrentAstData {
             .map(|line| line.chars().count())
             .max()
             .unwrap_or(0);
+        self.source_map = None;
+        self.decompiled = None;
+        self.highlighted.clear();
+        self.seen = None;
+        self.scroll_to = None;
+    }
+
+    /// Stores the instructions each line of the last decompilation came from.
+    pub fn set_source_map(&mut self, source_map: AstSourceMap) {
+        self.source_map = Some(source_map);
+    }
+
+    /// Stores the ast the last decompilation was printed from, to annotate its functions.
+    pub fn set_decompiled(&mut self, ast: SharedAst) {
+        // Names and types shown by the form are the ones just decompiled
+        if let Some(function) = self.form.function {
+            let variable = self.form.selected_variable();
+            self.form.detect(&ast, function);
+            self.form.variable = self
+                .form
+                .variables
+                .iter()
+                .position(|(_, key, _)| Some(*key) == variable);
+        }
+        self.decompiled = Some(ast);
+    }
+
+    /// Returns the function a line belongs to, and the instruction the line came from.
+    ///
+    /// ### Note
+    /// Lines without an origin, like function headers and declarations, belong to the function
+    /// of the closest line below them which has one.
+    fn line_target(&self, line: usize) -> Option<(AstFunctionId, Option<u64>)> {
+        let ast = self.decompiled.as_ref()?;
+        let source_map = self.source_map.as_ref()?;
+        let instruction = source_map.address_of_line(line).map(|x| x.start);
+        let function = instruction
+            .or_else(|| {
+                (line..source_map.line_count())
+                    .chain((0..line).rev())
+                    .find_map(|x| source_map.address_of_line(x))
+                    .map(|x| x.start)
+            })
+            .and_then(|x| ast.function_at(x))?;
+        Some((function, instruction))
+    }
+
+    /// Highlights the lines decompiled from a block and scrolls to the first of them.
+    ///
+    /// ### Note
+    /// Lines starting before the block, like the header of a loop around it, are not counted.
+    pub fn scroll_to(&mut self, block: &Range<u64>) {
+        let Some(source_map) = &self.source_map else {
+            return;
+        };
+        self.highlighted = (0..source_map.line_count())
+            .filter(|line| {
+                source_map
+                    .address_of_line(*line)
+                    .is_some_and(|origin| block.contains(&origin.start))
+            })
+            .collect();
+        self.scroll_to = self.highlighted.iter().min().copied();
+    }
+
+    /// Highlights the lines showing a selection made in another window and scrolls to the first
+    /// of them.
+    ///
+    /// ### Note
+    /// A single instruction only highlights the line closest to it, not every statement around it.
+    fn follow(&mut self, selection: &BoardSelection) {
+        let Some(source_map) = &self.source_map else {
+            return;
+        };
+        self.highlighted = match selection.instruction() {
+            Some(address) => source_map.line_of_address(address).into_iter().collect(),
+            None => (0..source_map.line_count())
+                .filter(|line| {
+                    source_map
+                        .address_of_line(*line)
+                        .is_some_and(|origin| selection.covers_instruction(origin.start))
+                })
+                .collect(),
+        };
+        self.scroll_to = self.highlighted.iter().min().copied();
+    }
+}
+
+impl AnnotationForm {
+    /// Starts editing a function, with the annotations it already has.
+    fn load(
+        &mut self,
+        ast: &SharedAst,
+        annotations: &AstAnnotations,
+        function: AstFunctionId,
+        instruction: Option<u64>,
+    ) {
+        let annotation = annotations.function(function.address());
+        let text = |x: Option<&String>| x.cloned().unwrap_or_default();
+        *self = Self {
+            function: Some(function),
+            name: text(annotation.and_then(|x| x.name.as_ref())),
+            return_type: text(annotation.and_then(|x| x.return_type.as_ref())),
+            parameter_count: annotation
+                .and_then(|x| x.parameter_count)
+                .map(|x| x.to_string())
+                .unwrap_or_default(),
+            ..Self::default()
+        };
+        self.point_at(instruction, annotations);
+        self.detect(ast, function);
+    }
+
+    /// Moves the comment to another instruction of the edited function.
+    fn point_at(&mut self, instruction: Option<u64>, annotations: &AstAnnotations) {
+        self.instruction = instruction;
+        self.comment = self
+            .function
+            .zip(instruction)
+            .and_then(|(function, address)| {
+                annotations
+                    .function(function.address())?
+                    .comments
+                    .get(&address)
+                    .cloned()
+            })
+            .unwrap_or_default();
+    }
+
+    /// Reads what was detected about the function and its variables.
+    fn detect(&mut self, ast: &SharedAst, function: AstFunctionId) {
+        let functions = ast.functions.read().unwrap();
+        let Some(decompiled) = ast
+            .function_versions
+            .get(&function)
+            .and_then(|version| functions.get(&function)?.get(version))
+        else {
+            self.detected = Default::default();
+            self.variables.clear();
+            return;
+        };
+        self.detected = [
+            decompiled.name(),
+            decompiled.return_type.to_string_with_config(None),
+            decompiled.parameters.len().to_string(),
+        ];
+        let mut variables: Vec<_> = decompiled
+            .variables
+            .read()
+            .unwrap()
+            .values()
+            .map(|x| {
+                let var_type = x.var_type.to_string_with_config(None);
+                (x.name(), AstVariableKey::of(x), var_type)
+            })
+            .collect();
+        variables.sort_by(|a, b| a.0.cmp(&b.0));
+        self.variables = variables;
+    }
+
+    fn selected_variable(&self) -> Option<AstVariableKey> {
+        self.variable
+            .and_then(|x| self.variables.get(x))
+            .map(|(_, key, _)| *key)
+    }
+
+    /// Picks the variable to edit, with the annotations it already has.
+    fn pick_variable(&mut self, index: usize, annotations: &AstAnnotations) {
+        self.variable = Some(index);
+        let annotation = self
+            .function
+            .zip(self.selected_variable())
+            .and_then(|(function, key)| {
+                annotations
+                    .function(function.address())?
+                    .variables
+                    .get(&key)
+            });
+        self.variable_name = annotation.and_then(|x| x.name.clone()).unwrap_or_default();
+        self.variable_type = annotation
+            .and_then(|x| x.var_type.clone())
+            .unwrap_or_default();
+    }
+
+    /// Writes the fields into the annotations.
+    ///
+    /// ### Returns
+    /// - `Result<(), String>` - why a field could not be read, nothing is written then
+    fn apply(&self, annotations: &mut AstAnnotations) -> Result<(), String> {
+        let function = self
+            .function
+            .ok_or("no function is being annotated")?
+            .address();
+        let return_type = parse_type(&self.return_type)?;
+        let variable_type = parse_type(&self.variable_type)?;
+        let parameter_count = match self.parameter_count.trim() {
+            "" => None,
+            x => Some(
+                x.parse()
+                    .map_err(|_| format!("invalid parameter count `{x}`"))?,
+            ),
+        };
+        annotations.set_function_name(function, Some(self.name.clone()));
+        annotations.set_return_type(function, return_type.as_ref());
+        annotations.set_parameter_count(function, parameter_count);
+        if let Some(instruction) = self.instruction {
+            annotations.set_comment(function, instruction, Some(self.comment.clone()));
+        }
+        if let Some(key) = self.selected_variable() {
+            annotations.set_variable_name(function, key, Some(self.variable_name.clone()));
+            annotations.set_variable_type(function, key, variable_type.as_ref());
+        }
+        Ok(())
+    }
+}
+
+/// Parses a type typed in the form, `None` when empty.
+fn parse_type(text: &str) -> Result<Option<AstValueType>, String> {
+    match text.trim() {
+        "" => Ok(None),
+        x => x.parse().map(Some),
     }
 }
 
@@ -53,11 +322,19 @@ pub fn window(id: impl Into<String>, pos: egui::Pos2) -> BoardWindow {
         id,
         "Display Current AST",
         pos,
-        BoardWindowKind::DisplayCurrentAst(DisplayCurrentAstData::default()),
+        BoardWindowKind::DisplayCurrentAst(Box::default()),
     )
 }
 
-pub fn ui(_app: &mut Firebat, _id: &str, data: &mut DisplayCurrentAstData, ui: &mut egui::Ui) {
+pub fn ui(app: &mut Firebat, id: &str, data: &mut DisplayCurrentAstData, ui: &mut egui::Ui) {
+    if data.seen != app.board.selection {
+        data.seen = app.board.selection.clone();
+        match &app.board.selection {
+            Some(selection) => data.follow(selection),
+            None => data.highlighted.clear(),
+        }
+    }
+
     ui.set_min_size(egui::vec2(VIEW_WIDTH, VIEW_HEIGHT));
 
     let font = egui::TextStyle::Monospace.resolve(ui.style());
@@ -66,17 +343,141 @@ pub fn ui(_app: &mut Firebat, _id: &str, data: &mut DisplayCurrentAstData, ui: &
         .fonts_mut(|fonts| fonts.glyph_width(&font, ' ').max(1.0));
     let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
 
+    let highlight = ui.visuals().selection.bg_fill;
+
+    let spacing = ui.spacing().item_spacing;
     ui.spacing_mut().item_spacing.y = 0.0;
-    egui::ScrollArea::both()
+    let mut area = egui::ScrollArea::both()
         .auto_shrink([false, false])
-        .max_height(VIEW_HEIGHT)
-        .show_rows(ui, row_height, data.lines.len(), |ui, rows| {
-            ui.set_min_width(char_width * data.widest as f32);
-            for line in &data.lines[rows] {
-                ui.add(
-                    egui::Label::new(egui::RichText::new(line).monospace())
-                        .wrap_mode(egui::TextWrapMode::Extend),
-                );
+        .max_height(VIEW_HEIGHT);
+    if let Some(line) = data.scroll_to.take() {
+        area = area.vertical_scroll_offset(line as f32 * row_height);
+    }
+    let mut clicked = None;
+    area.show_rows(ui, row_height, data.lines.len(), |ui, rows| {
+        ui.set_min_width(char_width * data.widest as f32);
+        for (index, line) in data.lines[rows.clone()].iter().enumerate() {
+            let mut text = egui::RichText::new(line).monospace();
+            if data.highlighted.contains(&(rows.start + index)) {
+                text = text.background_color(highlight);
+            }
+            let response = ui.add(
+                egui::Label::new(text)
+                    .wrap_mode(egui::TextWrapMode::Extend)
+                    .sense(egui::Sense::click()),
+            );
+            if response.clicked() {
+                clicked = Some(rows.start + index);
+            }
+        }
+    });
+
+    if let Some(line) = clicked {
+        let origin = data
+            .source_map
+            .as_ref()
+            .and_then(|source_map| source_map.address_of_line(line))
+            .map(|origin| origin.start..origin.end);
+        let selection = BoardSelection::AstLine {
+            window: id.to_owned(),
+            line,
+            origin,
+        };
+        data.highlighted = HashSet::from([line]);
+        data.seen = Some(selection.clone());
+        app.board.selection = Some(selection);
+
+        if let Some((function, instruction)) = data.line_target(line) {
+            let annotations = app.board.pipeline.annotations();
+            if data.form.function == Some(function) {
+                data.form.point_at(instruction, annotations);
+            } else if let Some(ast) = &data.decompiled {
+                data.form.load(ast, annotations, function, instruction);
+            }
+        }
+    }
+
+    ui.spacing_mut().item_spacing = spacing;
+    annotation_panel(app, &mut data.form, ui);
+}
+
+/// Edits the annotations of the function of the line clicked last.
+fn annotation_panel(app: &mut Firebat, form: &mut AnnotationForm, ui: &mut egui::Ui) {
+    egui::CollapsingHeader::new("Annotate")
+        .id_salt("annotate")
+        .show(ui, |ui| {
+            let Some(function) = form.function else {
+                ui.weak("click a line of a function to annotate it");
+                return;
+            };
+            egui::Grid::new("annotation_fields")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("Function");
+                    ui.monospace(format!("0x{:x}", function.address()));
+                    ui.end_row();
+                    field(ui, "Name", &mut form.name, &form.detected[0]);
+                    field(ui, "Return type", &mut form.return_type, &form.detected[1]);
+                    field(
+                        ui,
+                        "Parameters",
+                        &mut form.parameter_count,
+                        &form.detected[2],
+                    );
+                    if form.instruction.is_some() {
+                        field(ui, "Comment", &mut form.comment, "");
+                    }
+
+                    ui.label("Variable");
+                    let selected = form
+                        .variable
+                        .and_then(|x| form.variables.get(x))
+                        .map(|(name, ..)| name.clone())
+                        .unwrap_or_default();
+                    let mut picked = None;
+                    egui::ComboBox::from_id_salt("annotation_variable")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for (index, (name, ..)) in form.variables.iter().enumerate() {
+                                if ui
+                                    .selectable_label(form.variable == Some(index), name)
+                                    .clicked()
+                                {
+                                    picked = Some(index);
+                                }
+                            }
+                        });
+                    ui.end_row();
+                    if let Some(index) = picked {
+                        form.pick_variable(index, app.board.pipeline.annotations());
+                    }
+                    if let Some((name, _, var_type)) =
+                        form.variable.and_then(|x| form.variables.get(x)).cloned()
+                    {
+                        field(ui, "Variable name", &mut form.variable_name, &name);
+                        field(ui, "Variable type", &mut form.variable_type, &var_type);
+                    }
+                });
+
+            if ui.button("Apply").clicked() {
+                let mut annotations = app.board.pipeline.annotations().clone();
+                form.error = form.apply(&mut annotations).err();
+                if form.error.is_none() {
+                    app.board
+                        .pipeline
+                        .annotate(|current| *current = annotations);
+                    app.board.decompile_requested = true;
+                }
+            }
+            if let Some(error) = &form.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
             }
         });
 }
+
+/// A labeled text field of the annotation form, showing what was detected while empty.
+fn field(ui: &mut egui::Ui, label: &str, text: &mut String, detected: &str) {
+    ui.label(label);
+    ui.add(egui::TextEdit::singleline(text).hint_text(detected));
+    ui.end_row();
+}
@@ -0,0 +1,365 @@
+use crate::{
+    Firebat,
+    gui::board::{BoardWindow, BoardWindowKind, selection::BoardSelection},
+};
+use fireball::{core::Block, ir::utils::IrStatementDescriptor};
+use std::sync::Arc;
+
+/// Shown while no block is selected.
+const EMPTY_HINT: &str = "select blocks in Select Target Block";
+
+/// Width of the scrolled view, wide enough for a typical lifted statement.
+const VIEW_WIDTH: f32 = 720.0;
+
+/// Height of the scrolled view, leaving room for the details below it.
+const VIEW_HEIGHT: f32 = 400.0;
+
+/// Height of the details of the selected statement.
+const DETAILS_HEIGHT: f32 = 120.0;
+
+/// What a listing window prints.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ListingKind {
+    /// The instructions of the selected blocks
+    Disassembly,
+    /// The instructions of the selected blocks, each followed by the statements lifted from it
+    Ir,
+}
+
+/// State of a window listing the selected blocks.
+pub struct ListingData {
+    kind: ListingKind,
+    /// Binary generation and blocks the listing was built from, to rebuild it when they change.
+    source: Option<(u64, Vec<u64>)>,
+    /// Listed blocks, in address order.
+    blocks: Vec<Arc<Block>>,
+    rows: Vec<ListingRow>,
+    /// Longest row in characters, used to size the horizontal scroll area.
+    widest: usize,
+    /// Selection the window scrolled to last, to only follow the ones made elsewhere.
+    seen: Option<BoardSelection>,
+    /// Row to bring into view on the next frame.
+    scroll_to: Option<usize>,
+}
+
+/// A printed row of the listing.
+struct ListingRow {
+    line: ListingLine,
+    text: String,
+}
+
+/// What a row of the listing shows, deciding how it is highlighted and what clicking it selects.
+enum ListingLine {
+    /// Address range of a block
+    Header,
+    /// An instruction, by its virtual address
+    Instruction(u64),
+    /// A statement lifted from an instruction
+    Statement {
+        block: u64,
+        instruction: u64,
+        ir_index: u32,
+        statement_index: u8,
+    },
+}
+
+impl ListingData {
+    fn new(kind: ListingKind) -> Self {
+        Self {
+            kind,
+            source: None,
+            blocks: Vec::new(),
+            rows: Vec::new(),
+            widest: 0,
+            seen: None,
+            scroll_to: None,
+        }
+    }
+
+    /// Rebuilds the rows from the given blocks.
+    fn build(&mut self, mut blocks: Vec<Arc<Block>>) {
+        blocks.sort_by_key(|block| block.get_start_address().get_virtual_address());
+        self.rows = Vec::new();
+        for block in &blocks {
+            if !self.rows.is_empty() {
+                self.rows.push(ListingRow {
+                    line: ListingLine::Header,
+                    text: String::new(),
+                });
+            }
+            self.push_block(block);
+        }
+        if self.rows.is_empty() {
+            self.rows.push(ListingRow {
+                line: ListingLine::Header,
+                text: EMPTY_HINT.to_owned(),
+            });
+        }
+        self.widest = self
+            .rows
+            .iter()
+            .map(|row| row.text.chars().count())
+            .max()
+            .unwrap_or(0);
+        self.blocks = blocks;
+        self.seen = None;
+    }
+
+    /// Adds the rows of a block: its address range, its instructions and, for ir listings, the
+    /// statements lifted from each instruction below it.
+    fn push_block(&mut self, block: &Block) {
+        let start_address = block.get_start_address().get_virtual_address();
+        let end_address = start_address + block.get_block_size().copied().unwrap_or(0);
+        self.rows.push(ListingRow {
+            line: ListingLine::Header,
+            text: format!("0x{start_address:x} - 0x{end_address:x}"),
+        });
+
+        let ir = block.get_ir();
+        let ir = ir.as_ref().map(|ir| ir.ir());
+        for (ir_index, instruction) in block.get_instructions().iter().enumerate() {
+            self.rows.push(ListingRow {
+                line: ListingLine::Instruction(instruction.address()),
+                text: instruction.to_string(),
+            });
+            if self.kind != ListingKind::Ir {
+                continue;
+            }
+            let Some(statements) = ir
+                .and_then(|ir| ir.get(ir_index))
+                .and_then(|ir| ir.statements)
+            else {
+                self.rows.push(ListingRow {
+                    line: ListingLine::Instruction(instruction.address()),
+                    text: "  (not lifted)".to_owned(),
+                });
+                continue;
+            };
+            for (statement_index, statement) in statements.iter().enumerate() {
+                self.rows.push(ListingRow {
+                    line: ListingLine::Statement {
+                        block: start_address,
+                        instruction: instruction.address(),
+                        ir_index: ir_index as u32,
+                        statement_index: statement_index as u8,
+                    },
+                    text: format!("  {statement}"),
+                });
+            }
+        }
+    }
+}
+
+impl ListingRow {
+    /// Whether the row shows the selection, or the instruction it came from.
+    fn is_selected(&self, selection: &BoardSelection) -> bool {
+        match (&self.line, selection) {
+            (ListingLine::Header, _) => false,
+            (ListingLine::Instruction(address), _) => selection.covers_instruction(*address),
+            // A selected statement doesn't highlight the other ones of its instruction
+            (ListingLine::Statement { .. }, BoardSelection::IrStatement { .. }) => {
+                self.selection().as_ref() == Some(selection)
+            }
+            (ListingLine::Statement { instruction, .. }, _) => {
+                selection.covers_instruction(*instruction)
+            }
+        }
+    }
+
+    /// What clicking the row selects.
+    fn selection(&self) -> Option<BoardSelection> {
+        match self.line {
+            ListingLine::Header => None,
+            ListingLine::Instruction(address) => Some(BoardSelection::Instruction(address)),
+            ListingLine::Statement {
+                block,
+                instruction,
+                ir_index,
+                statement_index,
+            } => Some(BoardSelection::IrStatement {
+                block,
+                instruction,
+                ir_index,
+                statement_index,
+            }),
+        }
+    }
+}
+
+/// Creates a window listing the selected blocks.
+pub fn window(id: impl Into<String>, pos: egui::Pos2, kind: ListingKind) -> BoardWindow {
+    let title = match kind {
+        ListingKind::Disassembly => "Disassembly",
+        ListingKind::Ir => "IR",
+    };
+    BoardWindow::new(
+        id,
+        title,
+        pos,
+        BoardWindowKind::Listing(Box::new(ListingData::new(kind))),
+    )
+}
+
+pub fn ui(app: &mut Firebat, _id: &str, data: &mut ListingData, ui: &mut egui::Ui) {
+    let source = (
+        app.board.binary_generation,
+        app.board.pipeline.blocks.clone(),
+    );
+    if data.source.as_ref() != Some(&source) {
+        let blocks = app
+            .fireball
+            .as_ref()
+            .map(|fireball| app.board.pipeline.targets(fireball))
+            .unwrap_or_default();
+        data.build(blocks);
+        data.source = Some(source);
+    }
+
+    // Follow the selections made in other windows
+    if data.seen != app.board.selection {
+        data.seen = app.board.selection.clone();
+        if let Some(selection) = &data.seen {
+            data.scroll_to = data.rows.iter().position(|row| row.is_selected(selection));
+        }
+    }
+
+    ui.set_min_size(egui::vec2(VIEW_WIDTH, VIEW_HEIGHT + DETAILS_HEIGHT));
+
+    let font = egui::TextStyle::Monospace.resolve(ui.style());
+    let char_width = ui
+        .ctx()
+        .fonts_mut(|fonts| fonts.glyph_width(&font, ' ').max(1.0));
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    let highlight = ui.visuals().selection.bg_fill;
+
+    let mut clicked = None;
+    let mut area = egui::ScrollArea::both()
+        .id_salt("rows")
+        .auto_shrink([false, false])
+        .max_height(VIEW_HEIGHT);
+    if let Some(row) = data.scroll_to.take() {
+        area = area.vertical_scroll_offset(row as f32 * row_height);
+    }
+    ui.scope(|ui| {
+        ui.spacing_mut().item_spacing.y = 0.0;
+        area.show_rows(ui, row_height, data.rows.len(), |ui, rows| {
+            ui.set_min_width(char_width * data.widest as f32);
+            for row in &data.rows[rows] {
+                let mut text = egui::RichText::new(&row.text).monospace();
+                text = match row.line {
+                    ListingLine::Header => text.strong(),
+                    // Instructions are only context in between the statements of ir listings
+                    ListingLine::Instruction(_) if data.kind == ListingKind::Ir => text.weak(),
+                    _ => text,
+                };
+                if let Some(selection) = &app.board.selection
+                    && row.is_selected(selection)
+                {
+                    text = text.background_color(highlight);
+                }
+                let response = ui.add(
+                    egui::Label::new(text)
+                        .wrap_mode(egui::TextWrapMode::Extend)
+                        .sense(egui::Sense::click()),
+                );
+                if response.clicked() {
+                    clicked = row.selection();
+                }
+            }
+        });
+    });
+    if let Some(selection) = clicked {
+        // Clicked here, so already in view
+        data.seen = Some(selection.clone());
+        app.board.selection = Some(selection);
+    }
+
+    ui.separator();
+    egui::ScrollArea::vertical()
+        .id_salt("details")
+        .auto_shrink([false, false])
+        .max_height(DETAILS_HEIGHT)
+        .show(ui, |ui| match &app.board.selection {
+            Some(BoardSelection::IrStatement {
+                block,
+                ir_index,
+                statement_index,
+                ..
+            }) => {
+                let block = data
+                    .blocks
+                    .iter()
+                    .find(|it| it.get_start_address().get_virtual_address() == *block);
+                match block {
+                    Some(block) => details(ui, block, *ir_index, *statement_index),
+                    None => {
+                        ui.weak("the statement is not in the listed blocks");
+                    }
+                }
+            }
+            _ => {
+                ui.weak("select an ir statement to see its analysis");
+            }
+        });
+}
+
+/// Prints what the analysis of the block found about a statement: the data it accesses, the
+/// types known from it, and the variables it belongs to.
+fn details(ui: &mut egui::Ui, block: &Block, ir_index: u32, statement_index: u8) {
+    let ir = block.get_ir();
+    let Some(ir) = ir.as_ref() else {
+        ui.weak("the block is not lifted");
+        return;
+    };
+    let descriptor = IrStatementDescriptor::new(ir_index, statement_index);
+
+    let accesses = ir
+        .data_access
+        .as_ref()
+        .and_then(|accesses| accesses.get(descriptor));
+    ui.strong("Data access");
+    match accesses {
+        Some(accesses) if !accesses.is_empty() => {
+            for access in accesses {
+                ui.monospace(access.to_string());
+            }
+        }
+        _ => {
+            ui.weak("none");
+        }
+    }
+
+    let datatypes = ir
+        .known_datatypes
+        .as_ref()
+        .and_then(|datatypes| datatypes.get(descriptor));
+    ui.strong("Known datatypes");
+    match datatypes {
+        Some(datatypes) if !datatypes.is_empty() => {
+            for datatype in datatypes {
+                ui.monospace(datatype.to_string());
+            }
+        }
+        _ => {
+            ui.weak("none");
+        }
+    }
+
+    ui.strong("Variables");
+    let mut member = false;
+    for (index, variable) in ir.variables.iter().flatten().enumerate() {
+        let Some(accesses) = variable.get_data_accesses().get(descriptor) else {
+            continue;
+        };
+        member = true;
+        let accesses: Vec<String> = accesses.iter().map(ToString::to_string).collect();
+        ui.monospace(format!(
+            "v{index} {}: {}",
+            variable.data_type,
+            accesses.join(", ")
+        ));
+    }
+    if !member {
+        ui.weak("none");
+    }
+}
@@ -11,6 +11,8 @@ pub const OPTIMIZATION_KIND: &[&str] = &[
     "Parameter Analyzation",
     "Constant Folding",
     "Collapse Unused Variables",
+    "Struct Recovery",
+    "Stack Frame Analyzation",
     "Custom Pattern",
 ];
 
@@ -136,7 +138,9 @@ pub fn choice_to_ast_optimization_kind(choice: &SelectOptimizationChoice) -> Ast
         1 => AstOptimizationKind::ParameterAnalyzation,
         2 => AstOptimizationKind::ConstantFolding,
         3 => AstOptimizationKind::CollapseUnusedVariables,
-        4 => AstOptimizationKind::PatternMatching(Box::new(custom_pattern)),
+        4 => AstOptimizationKind::StructRecovery,
+        5 => AstOptimizationKind::StackFrameAnalyzation,
+        6 => AstOptimizationKind::PatternMatching(Box::new(custom_pattern)),
         _ => unreachable!(),
     }
 }
@@ -12,6 +12,8 @@ pub const WINDOW_ID: &str = "select_target_block";
 pub struct SelectTargetBlockData {
     input: String,
     blocks: Vec<SelectTargetBlockDataBlock>,
+    /// Block clicked last in a graph window, highlighted in the list.
+    focused: Option<u64>,
 }
 
 pub struct SelectTargetBlockDataBlock {
@@ -21,6 +23,38 @@ pub struct SelectTargetBlockDataBlock {
     selected: bool,
 }
 
+impl SelectTargetBlockData {
+    /// Start addresses of the analyzed blocks picked for decompilation.
+    pub fn selected_blocks(&self) -> Vec<u64> {
+        self.blocks
+            .iter()
+            .filter(|it| it.analyzed && it.selected)
+            .map(|it| it.start_address)
+            .collect()
+    }
+
+    /// Highlights the block starting at the given address and picks it, returning whether the
+    /// picked blocks changed.
+    pub fn focus(&mut self, address: u64) -> bool {
+        self.focused = Some(address);
+        let Some(block) = self
+            .blocks
+            .iter_mut()
+            .find(|it| it.analyzed && it.start_address == address)
+        else {
+            return false;
+        };
+        !std::mem::replace(&mut block.selected, true)
+    }
+
+    /// Lists blocks analyzed elsewhere, such as the ones restored from a project.
+    pub fn insert_blocks(&mut self, blocks: &[Arc<Block>]) {
+        for block in blocks {
+            insert_block(self, block.clone());
+        }
+    }
+}
+
 /// Creates the window which picks the blocks to decompile.
 pub fn window(pos: egui::Pos2) -> BoardWindow {
     BoardWindow::new(
@@ -66,6 +100,10 @@ pub fn ui(app: &mut Firebat, data: &mut SelectTargetBlockData, ui: &mut egui::Ui
                 Some(end) => format!("0x{:06x} - 0x{:06x}", block.start_address, end),
                 None => format!("0x{:06x}", block.start_address),
             };
+            let mut label = egui::RichText::new(label);
+            if data.focused == Some(block.start_address) {
+                label = label.background_color(ui.visuals().selection.bg_fill);
+            }
             if block.analyzed {
                 changed |= ui.checkbox(&mut block.selected, label).changed();
             } else {
@@ -83,13 +121,7 @@ pub fn ui(app: &mut Firebat, data: &mut SelectTargetBlockData, ui: &mut egui::Ui
     }
 
     if changed {
-        let blocks = data
-            .blocks
-            .iter()
-            .filter(|it| it.analyzed && it.selected)
-            .map(|it| it.start_address)
-            .collect();
-        app.board.pipeline.set_blocks(blocks);
+        app.board.pipeline.set_blocks(data.selected_blocks());
     }
 }
 
@@ -0,0 +1,52 @@
+use std::ops::Range;
+
+/// What was clicked last, shared by the windows so each highlights what it shows of it.
+#[derive(Clone, PartialEq, Eq)]
+pub enum BoardSelection {
+    /// A line of a printed ast
+    AstLine {
+        /// Window the line was clicked in, the only one whose line numbers match
+        window: String,
+        /// Zero based line number
+        line: usize,
+        /// Instructions the line was decompiled from, if known
+        origin: Option<Range<u64>>,
+    },
+    /// An instruction, by its virtual address
+    Instruction(u64),
+    /// A statement lifted from an instruction
+    IrStatement {
+        /// Start address of the block holding the instruction
+        block: u64,
+        /// Virtual address of the instruction
+        instruction: u64,
+        /// Index of the instruction in its block
+        ir_index: u32,
+        /// Index of the statement among the ones lifted from the instruction
+        statement_index: u8,
+    },
+}
+
+impl BoardSelection {
+    /// Whether the instruction is selected, or the selected line or statement came from it.
+    pub fn covers_instruction(&self, address: u64) -> bool {
+        match self {
+            Self::AstLine { origin, .. } => origin
+                .as_ref()
+                .is_some_and(|origin| origin.contains(&address)),
+            Self::Instruction(instruction) | Self::IrStatement { instruction, .. } => {
+                *instruction == address
+            }
+        }
+    }
+
+    /// The single instruction selected, `None` for ast lines which may come from many.
+    pub fn instruction(&self) -> Option<u64> {
+        match self {
+            Self::AstLine { .. } => None,
+            Self::Instruction(instruction) | Self::IrStatement { instruction, .. } => {
+                Some(*instruction)
+            }
+        }
+    }
+}
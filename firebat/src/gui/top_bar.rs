@@ -3,7 +3,8 @@ use crate::{
     gui::board::{default_tabs, select_target_block},
 };
 use eframe::egui;
-use fireball::Fireball;
+use fireball::{Fireball, abstract_syntax_tree::AstAnnotations, core::FireRaw};
+use std::path::Path;
 
 pub const TOP_BAR_HEIGHT: f32 = 26.0;
 const MENU_PADDING_X: f32 = 16.0;
@@ -33,12 +34,69 @@ pub fn ui(app: &mut Firebat, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
                                         select_target_block::WINDOW_ID,
                                         root_pos,
                                     );
+                                    if let Some(project) = app.project.clone()
+                                        && project.is_file()
+                                    {
+                                        load_project(app, &project);
+                                    }
                                 } else {
                                     tracing::warn!("failed to open {}", path.display());
                                 }
                             }
                         }
                     }
+                    if ui.button("Save annotations").clicked() {
+                        ui.close();
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("annotations", &["fba"])
+                            .save_file()
+                        {
+                            match app.board.pipeline.annotations().save(&path) {
+                                Ok(()) => tracing::info!("saved annotations to {}", path.display()),
+                                Err(error) => tracing::warn!("{error}"),
+                            }
+                        }
+                    }
+                    if ui.button("Load annotations").clicked() {
+                        ui.close();
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("annotations", &["fba"])
+                            .pick_file()
+                        {
+                            match AstAnnotations::load(&path) {
+                                Ok(annotations) => {
+                                    tracing::info!("loaded annotations from {}", path.display());
+                                    app.board
+                                        .pipeline
+                                        .annotate(|current| *current = annotations);
+                                    app.board.decompile_requested = true;
+                                }
+                                Err(error) => tracing::warn!("{error}"),
+                            }
+                        }
+                    }
+                    if app.fireball.is_some() {
+                        if ui.button("Save project").clicked() {
+                            ui.close();
+                            let path = app.project.clone().or_else(|| {
+                                rfd::FileDialog::new()
+                                    .add_filter("project", &["fbp"])
+                                    .save_file()
+                            });
+                            if let Some(path) = path {
+                                save_project(app, &path);
+                            }
+                        }
+                        if ui.button("Load project").clicked() {
+                            ui.close();
+                            if let Some(path) = rfd::FileDialog::new()
+                                .add_filter("project", &["fbp"])
+                                .pick_file()
+                            {
+                                load_project(app, &path);
+                            }
+                        }
+                    }
                     if ui.button("Exit").clicked() {
                         ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
                     }
@@ -52,6 +110,39 @@ pub fn ui(app: &mut Firebat, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
         });
 }
 
+/// Saves the analyzed blocks, the generated ast and the annotations, and remembers the path for
+/// the next save.
+fn save_project(app: &mut Firebat, path: &Path) {
+    let Some(fireball) = app.fireball.as_ref() else {
+        return;
+    };
+    match fireball.save_project(path, app.board.root_ast(), app.board.pipeline.annotations()) {
+        Ok(()) => {
+            tracing::info!("saved project to {}", path.display());
+            app.project = Some(path.to_owned());
+        }
+        Err(error) => tracing::warn!("{error}"),
+    }
+}
+
+/// Restores the analyzed blocks and the annotations of a project saved for the opened binary.
+fn load_project(app: &mut Firebat, path: &Path) {
+    let Some(fireball) = app.fireball.as_ref() else {
+        return;
+    };
+    match fireball.load_project(path) {
+        Ok(project) => {
+            tracing::info!("loaded project from {}", path.display());
+            app.board
+                .pipeline
+                .annotate(|current| *current = project.annotations);
+            app.board.restore_blocks(fireball.get_blocks().get_all());
+            app.project = Some(path.to_owned());
+        }
+        Err(error) => tracing::warn!("failed to load {}: {error}", path.display()),
+    }
+}
+
 /// Draws a clickable area filling the whole bar height, without any button frame.
 fn menu_area(ui: &mut egui::Ui, text: &str) -> egui::Response {
     let font = egui::TextStyle::Body.resolve(ui.style());
@@ -4,7 +4,7 @@ mod gui;
 
 use crate::gui::{BoardData, LogBarData};
 use fireball::Fireball;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 const ICON_PNG: &[u8] = include_bytes!("../resources/icons/icon.png");
 const FONT_REGULAR: &[u8] = include_bytes!("../resources/fonts/NotoSansCJKsc-Regular.otf");
@@ -12,6 +12,8 @@ const FONT_NAME: &str = "NotoSansCJKsc-Regular";
 
 struct Firebat {
     fireball: Option<Fireball>,
+    /// Project file given with `--project`, loaded when a binary is opened
+    project: Option<PathBuf>,
     board: BoardData,
     log_bar: LogBarData,
 }
@@ -30,6 +32,17 @@ fn main() {
     .unwrap();
 }
 
+/// Returns the path following `--project` on the command line.
+fn project_arg() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--project" {
+            return args.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
 fn load_icon() -> Option<Arc<egui::IconData>> {
     let image = match image::load_from_memory(ICON_PNG) {
         Ok(image) => image.into_rgba8(),
@@ -67,6 +80,7 @@ impl Firebat {
         setup_fonts(&cc.egui_ctx);
         Self {
             fireball: None,
+            project: project_arg(),
             board: BoardData::default(),
             log_bar: LogBarData::default(),
         }
@@ -1,5 +1,6 @@
-use crate::{DecompileArgs, JsonPreset};
-use fireball::{core::FireRaw, pattern_matching::AstPattern};
+use crate::{DecompileArgs, JsonPreset, OutputFormat};
+use fireball::{abstract_syntax_tree::AstAnnotations, core::FireRaw, pattern_matching::AstPattern};
+use std::sync::Arc;
 
 pub fn decompile(args: DecompileArgs) {
     let DecompileArgs {
@@ -7,6 +8,8 @@ pub fn decompile(args: DecompileArgs) {
         output,
         custom_script,
         json: json_preset_path,
+        project,
+        format,
     } = args;
     let json_preset: JsonPreset = if let Some(json_preset_path) = json_preset_path
         && let Ok(json_preset) = std::fs::read_to_string(&json_preset_path)
@@ -43,13 +46,49 @@ pub fn decompile(args: DecompileArgs) {
     let print_config = json_preset.print_config.to_fireball_print_config();
 
     let fireball = fireball::Fireball::from_path(input.to_str().unwrap()).unwrap();
-    let blocks = fireball.analyze_all().unwrap();
-    let defined = fireball.get_defined();
-    let result = fireball::ir::analyze::generate_ast_with_pre_defined_symbols(blocks, defined)
-        .unwrap()
-        .optimize(Some(optimization_config))
-        .unwrap()
-        .print(Some(print_config));
+    let mut annotations = AstAnnotations::default();
+    // a project failing to load is left as it is, instead of being overwritten by a new one
+    let mut save_to = project.as_ref();
+    let loaded = match &project {
+        Some(path) if path.is_file() => match fireball.load_project(path) {
+            Ok(loaded) => {
+                annotations = loaded.annotations;
+                loaded.ast
+            }
+            Err(e) => {
+                eprintln!(
+                    "Ignoring project {} and leaving it unchanged: {}",
+                    path.display(),
+                    e
+                );
+                save_to = None;
+                None
+            }
+        },
+        _ => None,
+    };
+    let ast = match loaded {
+        Some(ast) => ast,
+        None => {
+            let blocks = fireball.analyze_all().unwrap();
+            let mut ast = fireball.generate_ast(blocks).unwrap();
+            ast.set_annotations(Arc::new(annotations.clone()));
+            if let Some(path) = save_to {
+                eprintln!("Saving the analysis to project {}", path.display());
+                if let Err(e) = fireball.save_project(path, Some(&ast), &annotations) {
+                    eprintln!("Error saving project {}: {}", path.display(), e);
+                }
+            }
+            ast
+        }
+    };
+    let ast = ast.optimize(Some(optimization_config)).unwrap();
+    let result = match format {
+        OutputFormat::Text => ast.print(Some(print_config)),
+        OutputFormat::Json => {
+            serde_json::to_string_pretty(&ast.export(Some(print_config)).unwrap()).unwrap()
+        }
+    };
     if let Some(out) = output {
         std::fs::write(out, result).unwrap();
     } else {
@@ -14,6 +14,8 @@ struct ResolvedArgs {
     print_json_sample: bool,
     custom_script: Vec<String>,
     json: Option<String>,
+    project: Option<String>,
+    format: OutputFormat,
 }
 
 impl ResolvedArgs {
@@ -27,6 +29,7 @@ impl ResolvedArgs {
             input: self.input,
             custom_script: self.custom_script,
             json: self.json,
+            project: self.project,
         })
     }
     fn to_decompile_args(self) -> Result<DecompileArgs, String> {
@@ -38,6 +41,8 @@ impl ResolvedArgs {
             output: self.output.map(|path| PathBuf::from(path)),
             custom_script: self.custom_script,
             json: self.json,
+            project: self.project.map(PathBuf::from),
+            format: self.format,
         };
         // validate
         if !args.input.is_file() {
@@ -55,6 +60,7 @@ struct TuiArgs {
     input: Option<String>,      // if invalid path, handle in tui
     custom_script: Vec<String>, // if invalid path, ignore
     json: Option<String>,       // if invalid path, ignore
+    project: Option<String>,    // if missing or stale, ignore
 }
 
 struct DecompileArgs {
@@ -62,6 +68,17 @@ struct DecompileArgs {
     output: Option<PathBuf>,
     custom_script: Vec<String>, // if invalid path, ignore
     json: Option<String>,       // if invalid path, ignore
+    project: Option<PathBuf>,   // if missing, analyze and save; if invalid, ignore
+    format: OutputFormat,
+}
+
+/// How the decompilation result is written
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// Printed code
+    Text,
+    /// Functions, statements, calls and source map, see [fireball::abstract_syntax_tree::AstExport]
+    Json,
 }
 
 fn main() {
@@ -120,6 +137,19 @@ fn parse_arg() -> ArgMatches {
                 .value_name("PATH")
                 .action(ArgAction::Set)
                 .help("Write the printed AST to PATH"),
+            Arg::new("project")
+                .short('p')
+                .long("project")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .help("Load the analysis from a project file, or save it there"),
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .action(ArgAction::Set)
+                .help("Write the result as printed code or as JSON"),
         ])
         .get_matches()
 }
@@ -136,6 +166,11 @@ fn resolve_args(args: ArgMatches) -> ResolvedArgs {
         input: args.get_one::<String>("input").cloned(),
         output: args.get_one::<String>("output").cloned(),
         json: args.get_one::<String>("json").cloned(),
+        project: args.get_one::<String>("project").cloned(),
+        format: match args.get_one::<String>("format").map(String::as_str) {
+            Some("json") => OutputFormat::Json,
+            _ => OutputFormat::Text,
+        },
     }
 }
 
@@ -153,6 +188,10 @@ struct JsonPresetOptimizationConfig {
     parameter_analyzation: bool,
     constant_folding: bool,
     collapse_unused_variable: bool,
+    #[serde(default)]
+    struct_recovery: bool,
+    #[serde(default)]
+    stack_frame_analyzation: bool,
     pattern_matching: Vec<String>, // if invalid name, ignore
     max_pass_iterations: usize,
     use_embedded_passes: bool,
@@ -167,6 +206,8 @@ impl JsonPresetOptimizationConfig {
             parameter_analyzation: self.parameter_analyzation,
             constant_folding: self.constant_folding,
             collapse_unused_variable: self.collapse_unused_variable,
+            struct_recovery: self.struct_recovery,
+            stack_frame_analyzation: self.stack_frame_analyzation,
             pattern_matching: self
                 .pattern_matching
                 .iter()
@@ -184,6 +225,8 @@ impl JsonPresetOptimizationConfig {
             parameter_analyzation: o.parameter_analyzation,
             constant_folding: o.constant_folding,
             collapse_unused_variable: o.collapse_unused_variable,
+            struct_recovery: o.struct_recovery,
+            stack_frame_analyzation: o.stack_frame_analyzation,
             pattern_matching: o
                 .pattern_matching
                 .iter()
@@ -24,6 +24,10 @@ struct TuiApp {
     fireball: Option<Fireball>,
     optimization_config: fireball::abstract_syntax_tree::AstOptimizationConfig,
     print_config: fireball::abstract_syntax_tree::AstPrintConfig,
+    /// Names, types and comments given with the `:` commands
+    annotations: fireball::abstract_syntax_tree::AstAnnotations,
+    /// Project file loaded when the binary is opened, and saved with `:project`
+    project: Option<String>,
     data: TuiData<'static>,
 }
 #[derive(Default)]
@@ -49,6 +53,8 @@ impl TuiApp {
             fireball: None,
             optimization_config: Default::default(),
             print_config: Default::default(),
+            annotations: Default::default(),
+            project: None,
             data: Default::default(),
         }
     }
@@ -57,7 +63,9 @@ impl TuiApp {
             input,
             custom_script,
             json: json_preset_path,
+            project,
         } = args;
+        self.project = project;
 
         // input
         let fireball = match input {
@@ -69,6 +77,7 @@ impl TuiApp {
         };
         if fireball.is_some() {
             self.fireball = fireball;
+            self.load_project();
             self.state = TuiState::Tab(0);
         } else {
             self.state = TuiState::NavigateInput;
@@ -104,6 +113,20 @@ impl TuiApp {
         self.optimization_config = optimization_config;
         self.print_config = print_config;
     }
+    /// Restores the blocks and annotations of the project file, if there is one for the opened
+    /// binary.
+    ///
+    /// ### Note
+    /// - A missing or stale project is left alone, `:project` writes it for the current binary.
+    fn load_project(&mut self) {
+        let (Some(fireball), Some(project)) = (self.fireball.as_ref(), self.project.as_ref())
+        else {
+            return;
+        };
+        if let Ok(project) = fireball.load_project(project) {
+            self.annotations = project.annotations;
+        }
+    }
     fn run(&mut self, terminal: &mut ratatui::DefaultTerminal) -> std::io::Result<()> {
         while self.state != TuiState::Exit {
             terminal.draw(|frame| self.draw(frame))?;
@@ -41,7 +41,24 @@ pub fn draw(app: &mut TuiApp, terminal: &mut Frame) {
                 "esc: focus to list or custom path",
             ]
             .into(),
-            TuiTab::DisplayCurrentAST(..) => [].into(),
+            TuiTab::DisplayCurrentAST(..) => [
+                "tab/shift+tab/n/shift+n: navigate tabs",
+                "o: new optimization tab",
+                "d: new display ast tab",
+                "D: delete current tab",
+                "up/down/pu/pd: navigate",
+                ":: annotate the function under the cursor (esc to cancel)",
+                ":name NAME: rename the function",
+                ":rename VAR NAME: rename a variable",
+                ":type VAR TYPE: retype a variable",
+                ":ret TYPE: set the return type",
+                ":params N: set the parameter count",
+                ":comment TEXT: comment the line",
+                ":save PATH / :load PATH: save or load annotations",
+                ":project [PATH]: save the blocks, ast and annotations to a project",
+                "no value: back to what was detected",
+            ]
+            .into(),
         },
         TuiState::Help { .. } => Vec::new(),
         TuiState::Exit => Vec::new(),
@@ -205,6 +205,7 @@ pub fn handle_event(app: &mut TuiApp, event: event::Event) {
             let fireball =
                 fireball::Fireball::from_path(current.to_str().unwrap()).expect("unsupported file");
             app.fireball = Some(fireball);
+            app.load_project();
             app.state = TuiState::Tab(0);
         }
         _ => {}
@@ -6,10 +6,11 @@ mod select_target_block;
 use crate::tui::TuiApp;
 use crossterm::event;
 use fireball::{
-    abstract_syntax_tree::Ast,
+    abstract_syntax_tree::{Ast, AstSourceMap},
     core::{Address, FireRaw},
 };
 use ratatui::{Frame, style, widgets};
+use std::sync::Arc;
 
 #[derive(Default)]
 pub struct TuiTabData<'tui> {
@@ -49,9 +50,18 @@ struct SelectOptimizationData<'tui> {
     custom_y_cursor: widgets::ListState,
     focus: u8, // 0-list, 1-path, 2-buf
 }
+#[derive(Default)]
 struct DisplayCurrentASTData<'tui> {
     list: widgets::List<'tui>,
     state: widgets::ListState,
+    /// Printed ast, to find the function and variables under the cursor
+    ast: Option<Ast>,
+    /// Instructions each line was decompiled from
+    source_map: Option<AstSourceMap>,
+    /// Annotation command being typed, after `:`
+    command: Option<String>,
+    /// Result of the last command
+    message: Option<String>,
 }
 
 pub fn draw(app: &mut TuiApp, terminal: &mut Frame) {
@@ -88,12 +98,19 @@ fn init(app: &mut TuiApp) {
     }
     app.data.tab.init = true;
 
-    let data = SelectTargetBlockData {
+    let mut data = SelectTargetBlockData {
         input: String::new(),
         blocks: Vec::new(),
         blocks_list: widgets::List::default(),
         state: widgets::ListState::default(),
     };
+    // blocks restored from a project are listed as already analyzed
+    let mut blocks = app.fireball.as_ref().unwrap().get_blocks().get_all();
+    blocks.sort_unstable_by_key(|block| block.get_start_address().get_virtual_address());
+    for block in blocks {
+        select_target_block::insert_block(&mut data, block);
+    }
+    select_target_block::refresh_list(&mut data);
 
     app.data
         .tab
@@ -103,10 +120,7 @@ fn init(app: &mut TuiApp) {
     app.data
         .tab
         .tabs
-        .push(TuiTab::DisplayCurrentAST(Box::new(DisplayCurrentASTData {
-            list: Default::default(),
-            state: Default::default(),
-        })));
+        .push(TuiTab::DisplayCurrentAST(Box::default()));
     refresh_tab_widget(app);
     refresh_decompile(app);
 }
@@ -128,12 +142,16 @@ fn refresh_tab_widget(app: &mut TuiApp) {
 }
 /// decompile sequence from current tab
 fn refresh_decompile(app: &mut TuiApp) {
+    refresh_decompile_from(app, app.data.tab.current_tab_index);
+}
+/// decompile sequence from the given tab
+fn refresh_decompile_from(app: &mut TuiApp, first_tab: usize) {
     let data = &mut app.data.tab;
     let fireball = app.fireball.as_ref().unwrap();
     data.ast_and_tab_index
-        .retain(|(_, tab_index)| *tab_index < data.current_tab_index);
+        .retain(|(_, tab_index)| *tab_index < first_tab);
 
-    for current_tab in data.current_tab_index..data.tabs.len() {
+    for current_tab in first_tab..data.tabs.len() {
         match &mut data.tabs[current_tab] {
             TuiTab::SelectTargetBlock(dat) => {
                 let blocks = &dat.blocks;
@@ -149,11 +167,8 @@ fn refresh_decompile(app: &mut TuiApp) {
                         Address::from_virtual_address(&sections, selected_block.start_address);
                     v.push(blocks.get_by_start_address(&address).unwrap());
                 }
-                let ast = fireball::ir::analyze::generate_ast_with_pre_defined_symbols(
-                    v,
-                    fireball.get_defined(),
-                )
-                .unwrap();
+                let mut ast = fireball.generate_ast(v).unwrap();
+                ast.set_annotations(Arc::new(app.annotations.clone()));
                 data.ast_and_tab_index.push((ast, current_tab));
             }
             TuiTab::SelectOptimization(dat) => {
@@ -166,10 +181,12 @@ fn refresh_decompile(app: &mut TuiApp) {
             }
             TuiTab::DisplayCurrentAST(dat) => {
                 let ast = &data.ast_and_tab_index.last().unwrap().0;
-                let ast = ast.print(Some(app.print_config));
-                dat.list = widgets::List::new(ast.split("\n").map(|x| x.to_string()))
+                let (printed, source_map) = ast.print_with_source_map(Some(app.print_config));
+                dat.list = widgets::List::new(printed.split("\n").map(|x| x.to_string()))
                     .highlight_style(style::Style::new().fg(style::Color::Blue))
                     .block(widgets::Block::bordered());
+                dat.ast = Some(ast.clone());
+                dat.source_map = Some(source_map);
             }
         }
     }
@@ -235,10 +252,7 @@ fn handle_new_tab(app: &mut TuiApp, event: &event::Event) -> bool {
             app.data.tab.current_tab_index += 1;
             app.data.tab.tabs.insert(
                 app.data.tab.current_tab_index,
-                TuiTab::DisplayCurrentAST(Box::new(DisplayCurrentASTData {
-                    list: Default::default(),
-                    state: Default::default(),
-                })),
+                TuiTab::DisplayCurrentAST(Box::default()),
             );
             refresh_tab_widget(app);
             refresh_decompile(app);
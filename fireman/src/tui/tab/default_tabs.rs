@@ -34,6 +34,9 @@ pub fn default_tabs(app: &mut TuiApp) {
     if config.ir_analyzation {
         insert_tab(app, "Ir Analyzation");
     }
+    if config.stack_frame_analyzation {
+        insert_tab(app, "Stack Frame Analyzation");
+    }
     if config.parameter_analyzation {
         insert_tab(app, "Parameter Analyzation");
     }
@@ -47,4 +50,7 @@ pub fn default_tabs(app: &mut TuiApp) {
             insert_tab(app, "Collapse Unused Variables");
         }
     }
+    if config.struct_recovery {
+        insert_tab(app, "Struct Recovery");
+    }
 }
@@ -1,15 +1,40 @@
 use crate::tui::{
     TuiApp,
-    tab::{DisplayCurrentASTData, TuiTab, handle_del_tab, handle_new_tab, handle_turn_tab},
+    tab::{
+        DisplayCurrentASTData, TuiTab, handle_del_tab, handle_new_tab, handle_turn_tab,
+        refresh_decompile_from,
+    },
 };
 use crossterm::event;
+use fireball::abstract_syntax_tree::{AstAnnotations, AstFunctionId, AstValueType, AstVariableKey};
 use ratatui::{Frame, prelude::*};
 
-pub fn draw(data: &mut DisplayCurrentASTData, area: Rect, terminal: &mut Frame) {
+pub fn draw(data: &mut DisplayCurrentASTData, mut area: Rect, terminal: &mut Frame) {
     clamp_cursor(data);
+    let prompt = match (&data.command, &data.message) {
+        (Some(command), _) => Some(format!(":{command}")),
+        (None, Some(message)) => Some(message.clone()),
+        (None, None) => None,
+    };
+    if let Some(prompt) = prompt {
+        let mut prompt_area = area;
+        area.height = area.height.saturating_sub(1);
+        prompt_area.y += area.height;
+        prompt_area.height = 1;
+        if data.command.is_some() {
+            let column = prompt.chars().count() as u16;
+            if column < prompt_area.width {
+                terminal.set_cursor_position((prompt_area.x + column, prompt_area.y));
+            }
+        }
+        terminal.render_widget(prompt.as_str(), prompt_area);
+    }
     terminal.render_stateful_widget(&data.list, area, &mut data.state);
 }
 pub fn handle_event(app: &mut TuiApp, event: event::Event) {
+    if handle_command_input(app, &event) {
+        return;
+    }
     if handle_turn_tab(app, &event) || handle_new_tab(app, &event) || handle_del_tab(app, &event) {
         return;
     }
@@ -43,11 +68,181 @@ pub fn handle_event(app: &mut TuiApp, event: event::Event) {
         event::KeyCode::End => {
             data.state.select(Some(last_line));
         }
+        event::KeyCode::Char(':') => {
+            data.command = Some(String::new());
+            data.message = None;
+        }
         _ => {}
     }
     clamp_cursor(data);
 }
 
+/// handles typing an annotation command, after `:`
+///
+/// ### Returns
+/// bool -> true if handled
+fn handle_command_input(app: &mut TuiApp, event: &event::Event) -> bool {
+    let current_tab_index = app.data.tab.current_tab_index;
+    let TuiTab::DisplayCurrentAST(data) = &mut app.data.tab.tabs[current_tab_index] else {
+        unreachable!()
+    };
+    let Some(command) = data.command.as_mut() else {
+        return false;
+    };
+    let Some(event) = event.as_key_press_event() else {
+        return true;
+    };
+    match event.code {
+        event::KeyCode::Char(c) => command.push(c),
+        event::KeyCode::Backspace => {
+            command.pop();
+        }
+        event::KeyCode::Esc => data.command = None,
+        event::KeyCode::Enter => {
+            let command = data.command.take().unwrap_or_default();
+            let message = match run_command(app, &command) {
+                Ok(message) => message,
+                Err(message) => format!("error: {message}"),
+            };
+            // the tab is still there, commands don't add or remove tabs
+            let TuiTab::DisplayCurrentAST(data) = &mut app.data.tab.tabs[current_tab_index] else {
+                unreachable!()
+            };
+            data.message = Some(message);
+        }
+        _ => {}
+    }
+    true
+}
+
+/// Runs an annotation command on the function under the cursor, and decompiles again.
+///
+/// ### Returns
+/// - `Result<String, String>` - what was done, or why it wasn't
+fn run_command(app: &mut TuiApp, command: &str) -> Result<String, String> {
+    let command = command.trim();
+    let (verb, rest) = command
+        .split_once(' ')
+        .map(|(verb, rest)| (verb, rest.trim()))
+        .unwrap_or((command, ""));
+    let message = match verb {
+        "save" => {
+            app.annotations.save(rest)?;
+            return Ok(format!("saved annotations to {rest}"));
+        }
+        "project" => return save_project(app, rest),
+        "load" => {
+            app.annotations = AstAnnotations::load(rest)?;
+            format!("loaded annotations from {rest}")
+        }
+        "name" | "ret" | "params" | "comment" | "rename" | "type" => annotate(app, verb, rest)?,
+        _ => return Err(format!("unknown command `{verb}`")),
+    };
+    refresh_decompile_from(app, 0);
+    Ok(message)
+}
+
+/// Saves the analyzed blocks, the decompiled ast and the annotations to a project file.
+///
+/// ### Note
+/// Giving no path saves to the project given with `--project`.
+fn save_project(app: &mut TuiApp, path: &str) -> Result<String, String> {
+    if !path.is_empty() {
+        app.project = Some(path.to_owned());
+    }
+    let path = app.project.as_deref().ok_or("no project path given")?;
+    let ast = app.data.tab.ast_and_tab_index.first().map(|(ast, _)| ast);
+    app.fireball
+        .as_ref()
+        .unwrap()
+        .save_project(path, ast, &app.annotations)
+        .map_err(|err| err.to_string())?;
+    Ok(format!("saved project to {path}"))
+}
+
+/// Applies an annotation command to the function under the cursor.
+///
+/// ### Note
+/// Giving no value gives back what was detected (`name` alone restores the default name).
+fn annotate(app: &mut TuiApp, verb: &str, rest: &str) -> Result<String, String> {
+    let TuiTab::DisplayCurrentAST(data) = &app.data.tab.tabs[app.data.tab.current_tab_index] else {
+        unreachable!()
+    };
+    let (function, address) = cursor_target(data)?;
+    let value = |x: &str| Some(x.to_owned()).filter(|x| !x.is_empty());
+    let value_type = |x: &str| match x {
+        "" => Ok(None),
+        x => x.parse::<AstValueType>().map(Some),
+    };
+    let variable = match verb {
+        "rename" | "type" => {
+            let (name, _) = rest.split_once(' ').unwrap_or((rest, ""));
+            let variable = data
+                .ast
+                .as_ref()
+                .and_then(|ast| ast.variable_by_name(&function, name))
+                .ok_or_else(|| {
+                    format!("no variable `{name}` in {}", function.get_default_name())
+                })?;
+            Some(AstVariableKey::of(&variable))
+        }
+        _ => None,
+    };
+
+    let annotations = &mut app.annotations;
+    match (verb, variable) {
+        ("name", _) => annotations.set_function_name(function.address(), value(rest)),
+        ("ret", _) => annotations.set_return_type(function.address(), value_type(rest)?.as_ref()),
+        ("params", _) => {
+            let count = match rest {
+                "" => None,
+                x => Some(
+                    x.parse()
+                        .map_err(|_| format!("invalid parameter count `{x}`"))?,
+                ),
+            };
+            annotations.set_parameter_count(function.address(), count);
+        }
+        ("comment", _) => {
+            let address = address.ok_or("the line has no instruction to comment")?;
+            annotations.set_comment(function.address(), address, value(rest));
+        }
+        ("rename", Some(key)) => {
+            let name = rest.split_once(' ').map(|(_, x)| x.trim()).unwrap_or("");
+            annotations.set_variable_name(function.address(), key, value(name));
+        }
+        ("type", Some(key)) => {
+            let var_type = rest.split_once(' ').map(|(_, x)| x.trim()).unwrap_or("");
+            annotations.set_variable_type(function.address(), key, value_type(var_type)?.as_ref());
+        }
+        _ => unreachable!(),
+    }
+    Ok(format!("annotated {}", function.get_default_name()))
+}
+
+/// Returns the function under the cursor, and the instruction the cursor line came from.
+///
+/// ### Note
+/// Lines without an origin, like function headers and declarations, belong to the function of
+/// the closest line below them which has one.
+fn cursor_target(data: &DisplayCurrentASTData) -> Result<(AstFunctionId, Option<u64>), String> {
+    let (Some(ast), Some(source_map)) = (&data.ast, &data.source_map) else {
+        return Err("nothing is decompiled".to_owned());
+    };
+    let line = data.state.selected().unwrap_or(0);
+    let address = source_map.address_of_line(line).map(|x| x.start);
+    let function = address
+        .or_else(|| {
+            (line..source_map.line_count())
+                .chain((0..line).rev())
+                .find_map(|x| source_map.address_of_line(x))
+                .map(|x| x.start)
+        })
+        .and_then(|x| ast.function_at(x))
+        .ok_or("no function on the line")?;
+    Ok((function, address))
+}
+
 /// Keeps the cursor inside the list
 fn clamp_cursor(data: &mut DisplayCurrentASTData) {
     if data.list.is_empty() {
@@ -152,9 +152,11 @@ pub const OPTIMIZATION_KIND: &[&str] = &[
     "Parameter Analyzation",
     "Constant Folding",
     "Collapse Unused Variables",
+    "Struct Recovery",
+    "Stack Frame Analyzation",
     "Custom Pattern",
 ];
-pub const CUSTOM_PATTERN_INDEX: usize = 4;
+pub const CUSTOM_PATTERN_INDEX: usize = 6;
 pub fn selected_to_ast_optimization_kind(data: &mut SelectOptimizationData) -> AstOptimizationKind {
     let selected = data.selected;
     let custom_pattern = if selected == CUSTOM_PATTERN_INDEX {
@@ -171,7 +173,9 @@ pub fn selected_to_ast_optimization_kind(data: &mut SelectOptimizationData) -> A
         1 => AstOptimizationKind::ParameterAnalyzation,
         2 => AstOptimizationKind::ConstantFolding,
         3 => AstOptimizationKind::CollapseUnusedVariables,
-        4 => AstOptimizationKind::PatternMatching(Box::new(custom_pattern)),
+        4 => AstOptimizationKind::StructRecovery,
+        5 => AstOptimizationKind::StackFrameAnalyzation,
+        6 => AstOptimizationKind::PatternMatching(Box::new(custom_pattern)),
         _ => unreachable!(),
     }
 }
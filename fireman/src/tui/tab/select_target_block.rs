@@ -43,48 +43,6 @@ pub fn handle_event(app: &mut TuiApp, event: event::Event) {
     let TuiTab::SelectTargetBlock(data) = current_tab else {
         unreachable!()
     };
-    fn insert_block(data: &mut SelectTargetBlockData, block: Arc<Block>) {
-        let start_address = block.get_start_address().get_virtual_address();
-        let block_size = block.get_block_size();
-        let end_address = block_size.map(|x| start_address + x);
-        let connected_to = block
-            .get_connected_to()
-            .iter()
-            .filter_map(|x| x.to())
-            .map(|x| x.get_virtual_address())
-            .collect::<Vec<_>>();
-        if let Some(block) = data
-            .blocks
-            .iter_mut()
-            .find(|block| block.start_address == start_address)
-        {
-            block.end_address = end_address;
-            block.analyzed = true;
-            block.selected = true;
-        } else {
-            data.blocks.push(SelectTargetBlockDataBlock {
-                start_address,
-                end_address,
-                analyzed: true,
-                selected: true,
-            });
-        }
-        for connected_to in connected_to {
-            let exist = data
-                .blocks
-                .iter()
-                .find(|block| block.start_address == connected_to)
-                .is_some();
-            if !exist {
-                data.blocks.push(SelectTargetBlockDataBlock {
-                    start_address: connected_to,
-                    end_address: None,
-                    analyzed: false,
-                    selected: false,
-                });
-            }
-        }
-    }
 
     // handle address input. 0~9 ,x, backspace
     if let Some(event) = event.as_key_press_event()
@@ -227,6 +185,50 @@ pub fn handle_event(app: &mut TuiApp, event: event::Event) {
     }
 }
 
+/// Lists an analyzed block as selected, and the blocks it connects to as not analyzed yet.
+pub(super) fn insert_block(data: &mut SelectTargetBlockData, block: Arc<Block>) {
+    let start_address = block.get_start_address().get_virtual_address();
+    let block_size = block.get_block_size();
+    let end_address = block_size.map(|x| start_address + x);
+    let connected_to = block
+        .get_connected_to()
+        .iter()
+        .filter_map(|x| x.to())
+        .map(|x| x.get_virtual_address())
+        .collect::<Vec<_>>();
+    if let Some(block) = data
+        .blocks
+        .iter_mut()
+        .find(|block| block.start_address == start_address)
+    {
+        block.end_address = end_address;
+        block.analyzed = true;
+        block.selected = true;
+    } else {
+        data.blocks.push(SelectTargetBlockDataBlock {
+            start_address,
+            end_address,
+            analyzed: true,
+            selected: true,
+        });
+    }
+    for connected_to in connected_to {
+        let exist = data
+            .blocks
+            .iter()
+            .find(|block| block.start_address == connected_to)
+            .is_some();
+        if !exist {
+            data.blocks.push(SelectTargetBlockDataBlock {
+                start_address: connected_to,
+                end_address: None,
+                analyzed: false,
+                selected: false,
+            });
+        }
+    }
+}
+
 fn is_select_all_modifier(event: event::KeyEvent) -> bool {
     #[cfg(target_os = "macos")]
     {
@@ -235,7 +237,7 @@ fn is_select_all_modifier(event: event::KeyEvent) -> bool {
     event.modifiers == event::KeyModifiers::CONTROL
 }
 
-fn refresh_list(data: &mut SelectTargetBlockData) {
+pub(super) fn refresh_list(data: &mut SelectTargetBlockData) {
     let list: Vec<_> = data
         .blocks
         .iter()
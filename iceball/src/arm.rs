@@ -1,19 +1,23 @@
+pub mod condition;
 pub mod register;
 mod register_impl;
+pub mod shift;
 pub mod statements;
 
 use crate::{
     AddressingOperator, Argument, DisassembleError, Memory, Register, RelativeAddressingArgument,
     Statement, StatementInner,
 };
+pub use condition::ArmCondition;
 pub use register::ArmRegister;
+pub use shift::ArmShift;
 pub use statements::ArmStatement;
 
 impl StatementInner for ArmStatement {
     fn is_jcc(&self) -> bool {
         matches!(
             *self,
-            ArmStatement::B
+            ArmStatement::Bcond
                 | ArmStatement::Cbnz
                 | ArmStatement::Cbz
                 | ArmStatement::Tbnz
@@ -22,7 +26,10 @@ impl StatementInner for ArmStatement {
     }
 
     fn is_jmp(&self) -> bool {
-        matches!(*self, ArmStatement::Br | ArmStatement::Braa)
+        matches!(
+            *self,
+            ArmStatement::B | ArmStatement::Br | ArmStatement::Braa
+        )
     }
 
     fn is_call(&self) -> bool {
@@ -34,6 +41,7 @@ impl StatementInner for ArmStatement {
             *self,
             ArmStatement::Ret
                 | ArmStatement::Retaa
+                | ArmStatement::Retab
                 | ArmStatement::Eret
                 | ArmStatement::Eretaa
                 | ArmStatement::Drps
@@ -44,6 +52,9 @@ impl StatementInner for ArmStatement {
 impl ArmStatement {
     pub(crate) fn parse(mnemonic: impl AsRef<str>) -> Result<Statement, DisassembleError> {
         let mnemonic = mnemonic.as_ref().to_ascii_uppercase();
+        if mnemonic_condition(&mnemonic).is_some() {
+            return Ok(Statement::Arm(ArmStatement::Bcond));
+        }
         if let Ok(stmt) = mnemonic.parse::<ArmStatement>() {
             Ok(Statement::Arm(stmt))
         } else {
@@ -52,6 +63,21 @@ impl ArmStatement {
     }
 }
 
+/// Extracts the condition code embedded in a conditional branch mnemonic.
+///
+/// ### Arguments
+/// - `mnemonic: &str` - mnemonic such as `b.eq` or `b.ne`
+///
+/// ### Returns
+/// - `Option<ArmCondition>` - condition of the branch, `None` if the mnemonic has no condition
+pub fn mnemonic_condition(mnemonic: &str) -> Option<ArmCondition> {
+    let (statement, condition) = mnemonic.trim().split_once('.')?;
+    if !statement.eq_ignore_ascii_case("b") {
+        return None;
+    }
+    condition.parse::<ArmCondition>().ok()
+}
+
 pub fn parse_argument(op: impl AsRef<str>) -> Result<Argument, DisassembleError> {
     let op = op.as_ref().trim();
     if op.is_empty() {
@@ -66,6 +92,17 @@ pub fn parse_argument(op: impl AsRef<str>) -> Result<Argument, DisassembleError>
         return Ok(Argument::Constant(constant?));
     }
 
+    if op.starts_with("#-")
+        && let Some(constant) = parse_immediate_i128(op)
+    {
+        // negative immediates are kept as two's complement
+        return Ok(Argument::Constant(constant? as u64));
+    }
+
+    if let Ok(condition) = op.parse::<ArmCondition>() {
+        return Ok(Argument::Condition(condition));
+    }
+
     parse_register(op).map(Argument::Register)
 }
 
@@ -104,32 +141,40 @@ fn parse_memory(op: &str) -> Result<Argument, DisassembleError> {
             continue;
         }
 
-        if let Some(shift_operand) = normalized.strip_prefix("lsl ") {
-            let shift = parse_shift_amount(shift_operand)?;
-            items.push(RelativeAddressingArgument::Operator(
-                AddressingOperator::Mul,
-            ));
-            items.push(RelativeAddressingArgument::Constant(i128::from(
-                1_u64
-                    .checked_shl(u32::from(shift))
-                    .ok_or(DisassembleError::Unknown)?,
-            )));
-            continue;
-        }
-
-        if normalized.eq_ignore_ascii_case("lsl") {
-            let shift = terms
-                .next()
-                .ok_or(DisassembleError::Unknown)
-                .and_then(parse_shift_amount)?;
-            items.push(RelativeAddressingArgument::Operator(
-                AddressingOperator::Mul,
-            ));
-            items.push(RelativeAddressingArgument::Constant(i128::from(
-                1_u64
-                    .checked_shl(u32::from(shift))
-                    .ok_or(DisassembleError::Unknown)?,
-            )));
+        if let Some((shift, amount)) = parse_shift(normalized) {
+            let amount = match amount {
+                Some(amount) => amount?,
+                // `lsl` may be split from its amount by the comma splitting above
+                None if shift == ArmShift::Lsl => terms
+                    .next()
+                    .ok_or(DisassembleError::Unknown)
+                    .and_then(parse_shift_amount)?,
+                None => 0,
+            };
+            match shift {
+                ArmShift::Lsl | ArmShift::Uxtx | ArmShift::Sxtx => {}
+                ArmShift::Lsr | ArmShift::Asr | ArmShift::Ror => {
+                    return Err(DisassembleError::Unknown);
+                }
+                _ => {
+                    let Some(RelativeAddressingArgument::Register(register)) = items.pop() else {
+                        return Err(DisassembleError::Unknown);
+                    };
+                    items.push(RelativeAddressingArgument::ExtendedRegister(
+                        register, shift,
+                    ));
+                }
+            }
+            if amount != 0 {
+                items.push(RelativeAddressingArgument::Operator(
+                    AddressingOperator::Mul,
+                ));
+                items.push(RelativeAddressingArgument::Constant(i128::from(
+                    1_u64
+                        .checked_shl(u32::from(amount))
+                        .ok_or(DisassembleError::Unknown)?,
+                )));
+            }
             continue;
         }
 
@@ -141,11 +186,43 @@ fn parse_memory(op: &str) -> Result<Argument, DisassembleError> {
     )))
 }
 
+/// Applies a shift or extension operand (`lsl #12`, `sxtw #2`) to the operand before it.
+///
+/// ### Arguments
+/// - `argument: &Argument` - operand the shift follows
+/// - `op: &str` - shift operand, as split from the operand list
+///
+/// ### Returns
+/// - `Option<Argument>` - shifted operand, `None` if `op` is not a shift of `argument`
+pub fn apply_shift(argument: &Argument, op: &str) -> Option<Argument> {
+    let (shift, amount) = parse_shift(op.trim())?;
+    let amount = amount.unwrap_or(Ok(0)).ok()?;
+    match argument {
+        Argument::Register(register) => Some(Argument::ShiftedRegister(*register, shift, amount)),
+        Argument::Constant(constant) if shift == ArmShift::Lsl => constant
+            .checked_shl(u32::from(amount))
+            .map(Argument::Constant),
+        _ => None,
+    }
+}
+
+/// Splits a shift operand into the shift and its amount, if present.
+fn parse_shift(value: &str) -> Option<(ArmShift, Option<Result<u8, DisassembleError>>)> {
+    let (shift, amount) = value
+        .split_once(char::is_whitespace)
+        .map_or((value, None), |(shift, amount)| {
+            (shift, Some(amount.trim()))
+        });
+    let shift = shift.parse::<ArmShift>().ok()?;
+    Some((shift, amount.map(parse_shift_amount)))
+}
+
 fn push_term(items: &mut Vec<RelativeAddressingArgument>, term: RelativeAddressingArgument) {
     if matches!(
         items.last(),
         Some(RelativeAddressingArgument::Register(_))
             | Some(RelativeAddressingArgument::Constant(_))
+            | Some(RelativeAddressingArgument::ExtendedRegister(..))
     ) {
         items.push(RelativeAddressingArgument::Operator(
             AddressingOperator::Add,
@@ -220,9 +297,10 @@ impl std::fmt::Display for ArmStatement {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_argument;
+    use super::{apply_shift, mnemonic_condition, parse_argument};
     use crate::{
-        AddressingOperator, Argument, ArmRegister, Memory, Register, RelativeAddressingArgument,
+        AddressingOperator, Argument, ArmCondition, ArmRegister, ArmShift, ArmStatement, Memory,
+        Register, RelativeAddressingArgument, Statement, parse_statement,
     };
 
     #[test]
@@ -273,4 +351,79 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn parses_arm_memory_with_extended_index() {
+        let parsed = parse_argument("[x0, w1, sxtw #3]").unwrap();
+        assert_eq!(
+            parsed,
+            Argument::Memory(Memory::RelativeAddressing(
+                [
+                    RelativeAddressingArgument::Register(Register::Arm(ArmRegister::X(0))),
+                    RelativeAddressingArgument::Operator(AddressingOperator::Add),
+                    RelativeAddressingArgument::ExtendedRegister(
+                        Register::Arm(ArmRegister::W(1)),
+                        ArmShift::Sxtw
+                    ),
+                    RelativeAddressingArgument::Operator(AddressingOperator::Mul),
+                    RelativeAddressingArgument::Constant(8),
+                ]
+                .into()
+            ))
+        );
+        assert!(parse_argument("[x0, x1, ror #2]").is_err());
+    }
+
+    #[test]
+    fn applies_shift_to_previous_operand() {
+        let register = Argument::Register(Register::Arm(ArmRegister::X(2)));
+        assert_eq!(
+            apply_shift(&register, "lsl #2"),
+            Some(Argument::ShiftedRegister(
+                Register::Arm(ArmRegister::X(2)),
+                ArmShift::Lsl,
+                2
+            ))
+        );
+        assert_eq!(
+            apply_shift(&register, "sxtw"),
+            Some(Argument::ShiftedRegister(
+                Register::Arm(ArmRegister::X(2)),
+                ArmShift::Sxtw,
+                0
+            ))
+        );
+        assert_eq!(
+            apply_shift(&Argument::Constant(1), "lsl #12"),
+            Some(Argument::Constant(0x1000))
+        );
+        assert_eq!(apply_shift(&register, "x3"), None);
+    }
+
+    #[test]
+    fn parses_arm_condition_operand() {
+        assert_eq!(
+            parse_argument("ne").unwrap(),
+            Argument::Condition(ArmCondition::Ne)
+        );
+        assert_eq!(
+            parse_argument("#-0x10").unwrap(),
+            Argument::Constant(-16_i64 as u64)
+        );
+    }
+
+    #[test]
+    fn parses_conditional_branch_mnemonic() {
+        assert_eq!(mnemonic_condition("b.eq"), Some(ArmCondition::Eq));
+        assert_eq!(mnemonic_condition("b"), None);
+        assert_eq!(mnemonic_condition("bl"), None);
+        assert_eq!(
+            parse_statement(crate::MachineArchitecture::Arm64, "b.hs"),
+            Ok(Statement::Arm(ArmStatement::Bcond))
+        );
+        assert_eq!(
+            parse_statement(crate::MachineArchitecture::Arm64, "b"),
+            Ok(Statement::Arm(ArmStatement::B))
+        );
+    }
 }
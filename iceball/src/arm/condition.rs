@@ -0,0 +1,78 @@
+/// Condition codes used by `B.cond`, `CSEL`, `CCMP` and friends.
+///
+/// Each condition is evaluated against the `NZCV` flags of `PSTATE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, fireman_macro::StrToEnum, fireman_macro::EnumToStr)]
+#[str_to_enum_type(crate::DisassembleError)]
+#[str_to_enum_item(crate::DisassembleError::Unknown)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArmCondition {
+    /// Equal (`Z == 1`)
+    Eq,
+    /// Not equal (`Z == 0`)
+    Ne,
+    /// Carry set / unsigned higher or same (`C == 1`)
+    Cs,
+    /// Same as `CS`
+    Hs,
+    /// Carry clear / unsigned lower (`C == 0`)
+    Cc,
+    /// Same as `CC`
+    Lo,
+    /// Minus, negative (`N == 1`)
+    Mi,
+    /// Plus, positive or zero (`N == 0`)
+    Pl,
+    /// Overflow (`V == 1`)
+    Vs,
+    /// No overflow (`V == 0`)
+    Vc,
+    /// Unsigned higher (`C == 1 && Z == 0`)
+    Hi,
+    /// Unsigned lower or same (`C == 0 || Z == 1`)
+    Ls,
+    /// Signed greater than or equal (`N == V`)
+    Ge,
+    /// Signed less than (`N != V`)
+    Lt,
+    /// Signed greater than (`Z == 0 && N == V`)
+    Gt,
+    /// Signed less than or equal (`Z == 1 || N != V`)
+    Le,
+    /// Always
+    Al,
+    /// Always (reserved encoding of `AL`)
+    Nv,
+}
+
+impl ArmCondition {
+    /// Returns the condition that holds exactly when this one does not.
+    ///
+    /// ### Returns
+    /// - `ArmCondition` - inverted condition (`AL` and `NV` are returned as is)
+    pub fn invert(&self) -> Self {
+        match self {
+            Self::Eq => Self::Ne,
+            Self::Ne => Self::Eq,
+            Self::Cs | Self::Hs => Self::Cc,
+            Self::Cc | Self::Lo => Self::Cs,
+            Self::Mi => Self::Pl,
+            Self::Pl => Self::Mi,
+            Self::Vs => Self::Vc,
+            Self::Vc => Self::Vs,
+            Self::Hi => Self::Ls,
+            Self::Ls => Self::Hi,
+            Self::Ge => Self::Lt,
+            Self::Lt => Self::Ge,
+            Self::Gt => Self::Le,
+            Self::Le => Self::Gt,
+            Self::Al => Self::Al,
+            Self::Nv => Self::Nv,
+        }
+    }
+}
+
+impl std::fmt::Display for ArmCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name().to_ascii_lowercase())
+    }
+}
@@ -0,0 +1,59 @@
+/// Shifts and extensions applied to a register operand before it is used
+/// (`lsl` of `add x0, x1, x2, lsl #2`, `sxtw` of `ldr x0, [x1, w2, sxtw #3]`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, fireman_macro::StrToEnum, fireman_macro::EnumToStr)]
+#[str_to_enum_type(crate::DisassembleError)]
+#[str_to_enum_item(crate::DisassembleError::Unknown)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArmShift {
+    /// Logical shift left
+    Lsl,
+    /// Logical shift right
+    Lsr,
+    /// Arithmetic shift right
+    Asr,
+    /// Rotate right
+    Ror,
+    /// Zero extend the low byte, then shift left
+    Uxtb,
+    /// Zero extend the low halfword, then shift left
+    Uxth,
+    /// Zero extend the low word, then shift left
+    Uxtw,
+    /// Shift left (same as `LSL`)
+    Uxtx,
+    /// Sign extend the low byte, then shift left
+    Sxtb,
+    /// Sign extend the low halfword, then shift left
+    Sxth,
+    /// Sign extend the low word, then shift left
+    Sxtw,
+    /// Shift left (same as `LSL`)
+    Sxtx,
+}
+
+impl ArmShift {
+    /// Returns the width of the low part the extension reads.
+    ///
+    /// ### Returns
+    /// - `Option<u8>` - extended width in bytes, `None` for the plain shifts
+    pub fn extended_bytes(&self) -> Option<u8> {
+        match self {
+            Self::Uxtb | Self::Sxtb => Some(1),
+            Self::Uxth | Self::Sxth => Some(2),
+            Self::Uxtw | Self::Sxtw => Some(4),
+            Self::Uxtx | Self::Sxtx => Some(8),
+            Self::Lsl | Self::Lsr | Self::Asr | Self::Ror => None,
+        }
+    }
+
+    /// Whether the extension sign extends the low part it reads.
+    pub fn is_signed_extension(&self) -> bool {
+        matches!(self, Self::Sxtb | Self::Sxth | Self::Sxtw | Self::Sxtx)
+    }
+}
+
+impl std::fmt::Display for ArmShift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name().to_ascii_lowercase())
+    }
+}
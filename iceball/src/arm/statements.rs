@@ -1802,6 +1802,17 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Bcax,
+    /// # B.cond
+    ///
+    /// Branch conditionally
+    ///
+    /// - `B.<cond>  <label>` — 19-bit signed PC-relative branch offset
+    ///
+    /// ## Description
+    ///
+    /// The condition is carried as the last operand of the instruction.
+    ///
+    Bcond,
     /// # BFCVT
     ///
     /// Floating-point convert from single-precision to BFloat16 format (scalar)
@@ -1998,6 +2009,14 @@ pub enum ArmStatement {
     /// ```
     ///
     Bfdot,
+    /// # BFI
+    ///
+    /// Bitfield Insert (alias of `BFM`)
+    ///
+    /// - `BFI  <Wd>, <Wn>, #<lsb>, #<width>` — 32-bit
+    /// - `BFI  <Xd>, <Xn>, #<lsb>, #<width>` — 64-bit
+    ///
+    Bfi,
     /// # BFM
     ///
     /// Bitfield Move
@@ -2282,6 +2301,14 @@ pub enum ArmStatement {
     /// ```
     ///
     Bfmmla,
+    /// # BFXIL
+    ///
+    /// Bitfield extract and insert at low end (alias of `BFM`)
+    ///
+    /// - `BFXIL  <Wd>, <Wn>, #<lsb>, #<width>` — 32-bit
+    /// - `BFXIL  <Xd>, <Xn>, #<lsb>, #<width>` — 64-bit
+    ///
+    Bfxil,
     /// # BIC (vector, immediate)
     ///
     /// Bitwise bit Clear (vector, immediate)
@@ -4134,6 +4161,22 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Cfinv,
+    /// # CINC
+    ///
+    /// Conditional Increment (alias of `CSINC`)
+    ///
+    /// - `CINC  <Wd>, <Wn>, <cond>` — 32-bit
+    /// - `CINC  <Xd>, <Xn>, <cond>` — 64-bit
+    ///
+    Cinc,
+    /// # CINV
+    ///
+    /// Conditional Invert (alias of `CSINV`)
+    ///
+    /// - `CINV  <Wd>, <Wn>, <cond>` — 32-bit
+    /// - `CINV  <Xd>, <Xn>, <cond>` — 64-bit
+    ///
+    Cinv,
     /// # CLASTA (scalar)
     ///
     ///
@@ -4984,6 +5027,22 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Cmlt,
+    /// # CMN
+    ///
+    /// Compare Negative (alias of `ADDS`)
+    ///
+    /// - `CMN  <Xn|SP>, #<imm>{, <shift>}` — 64-bit immediate
+    /// - `CMN  <Xn>, <Xm>{, <shift> #<amount>}` — 64-bit shifted register
+    ///
+    Cmn,
+    /// # CMP
+    ///
+    /// Compare (alias of `SUBS`)
+    ///
+    /// - `CMP  <Xn|SP>, #<imm>{, <shift>}` — 64-bit immediate
+    /// - `CMP  <Xn>, <Xm>{, <shift> #<amount>}` — 64-bit shifted register
+    ///
+    Cmp,
     /// # CMP<cc> (immediate)
     ///
     ///
@@ -5230,6 +5289,14 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Cmtst,
+    /// # CNEG
+    ///
+    /// Conditional Negate (alias of `CSNEG`)
+    ///
+    /// - `CNEG  <Wd>, <Wn>, <cond>` — 32-bit
+    /// - `CNEG  <Xd>, <Xn>, <cond>` — 64-bit
+    ///
+    Cneg,
     /// # CNOT
     ///
     ///
@@ -5872,6 +5939,22 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Csel,
+    /// # CSET
+    ///
+    /// Conditional Set (alias of `CSINC`)
+    ///
+    /// - `CSET  <Wd>, <cond>` — 32-bit
+    /// - `CSET  <Xd>, <cond>` — 64-bit
+    ///
+    Cset,
+    /// # CSETM
+    ///
+    /// Conditional Set Mask (alias of `CSINV`)
+    ///
+    /// - `CSETM  <Wd>, <cond>` — 32-bit
+    /// - `CSETM  <Xd>, <cond>` — 64-bit
+    ///
+    Csetm,
     /// # CSINC
     ///
     /// Conditional Select Increment
@@ -29261,6 +29344,23 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Mls,
+    /// # MNEG
+    ///
+    /// Multiply-Negate (alias of `MSUB`)
+    ///
+    /// - `MNEG  <Wd>, <Wn>, <Wm>` — 32-bit
+    /// - `MNEG  <Xd>, <Xn>, <Xm>` — 64-bit
+    ///
+    Mneg,
+    /// # MOV
+    ///
+    /// Move (alias of `ORR`, `ADD`, `MOVZ` or `MOVN`)
+    ///
+    /// - `MOV  <Xd>, <Xm>` — register
+    /// - `MOV  <Xd|SP>, <Xn|SP>` — to/from SP
+    /// - `MOV  <Xd>, #<imm>` — immediate
+    ///
+    Mov,
     /// # MOVI
     ///
     /// Move Immediate (vector)
@@ -29973,6 +30073,14 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Mul,
+    /// # MVN
+    ///
+    /// Bitwise NOT (alias of `ORN`)
+    ///
+    /// - `MVN  <Wd>, <Wm>{, <shift> #<amount>}` — 32-bit
+    /// - `MVN  <Xd>, <Xm>{, <shift> #<amount>}` — 64-bit
+    ///
+    Mvn,
     /// # MVNI
     ///
     /// Move inverted Immediate (vector)
@@ -30206,6 +30314,30 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Neg,
+    /// # NEGS
+    ///
+    /// Negate, setting flags (alias of `SUBS`)
+    ///
+    /// - `NEGS  <Wd>, <Wm>{, <shift> #<amount>}` — 32-bit
+    /// - `NEGS  <Xd>, <Xm>{, <shift> #<amount>}` — 64-bit
+    ///
+    Negs,
+    /// # NGC
+    ///
+    /// Negate with Carry (alias of `SBC`)
+    ///
+    /// - `NGC  <Wd>, <Wm>` — 32-bit
+    /// - `NGC  <Xd>, <Xm>` — 64-bit
+    ///
+    Ngc,
+    /// # NGCS
+    ///
+    /// Negate with Carry, setting flags (alias of `SBCS`)
+    ///
+    /// - `NGCS  <Wd>, <Wm>` — 32-bit
+    /// - `NGCS  <Xd>, <Xm>` — 64-bit
+    ///
+    Ngcs,
     /// # NOP
     ///
     /// No Operation
@@ -32491,6 +32623,13 @@ pub enum ArmStatement {
     /// ```
     ///
     Retaa,
+    /// # RETAB
+    ///
+    /// Return from subroutine, with pointer authentication using key B
+    ///
+    /// - `RETAB` — Integer
+    ///
+    Retab,
     /// # REV
     ///
     /// Reverse Bytes
@@ -32942,6 +33081,14 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Rmif,
+    /// # ROR
+    ///
+    /// Rotate right (alias of `EXTR` or `RORV`)
+    ///
+    /// - `ROR  <Xd>, <Xs>, #<shift>` — immediate
+    /// - `ROR  <Xd>, <Xn>, <Xm>` — register
+    ///
+    Ror,
     /// # RORV
     ///
     /// Rotate Right Variable
@@ -33978,6 +34125,13 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Sbcs,
+    /// # SBFIZ
+    ///
+    /// Signed Bitfield Insert in Zero (alias of `SBFM`)
+    ///
+    /// - `SBFIZ  <Xd>, <Xn>, #<lsb>, #<width>` — 64-bit
+    ///
+    Sbfiz,
     /// # SBFM
     ///
     /// Signed Bitfield Move
@@ -34069,6 +34223,13 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Sbfm,
+    /// # SBFX
+    ///
+    /// Signed Bitfield Extract (alias of `SBFM`)
+    ///
+    /// - `SBFX  <Xd>, <Xn>, #<lsb>, #<width>` — 64-bit
+    ///
+    Sbfx,
     /// # SCVTF (vector, fixed-point)
     ///
     /// Signed fixed-point Convert to Floating-point (vector)
@@ -47398,6 +47559,21 @@ pub enum ArmStatement {
     /// ```
     ///
     Sxtb,
+    /// # SXTH
+    ///
+    /// Signed Extend Halfword (alias of `SBFM`)
+    ///
+    /// - `SXTH  <Wd>, <Wn>` — 32-bit
+    /// - `SXTH  <Xd>, <Wn>` — 64-bit
+    ///
+    Sxth,
+    /// # SXTW
+    ///
+    /// Sign Extend Word (alias of `SBFM`)
+    ///
+    /// - `SXTW  <Xd>, <Wn>` — 64-bit
+    ///
+    Sxtw,
     /// # SYS
     ///
     /// System instruction
@@ -63903,6 +64079,14 @@ pub enum ArmStatement {
     /// ```
     ///
     Tsb,
+    /// # TST
+    ///
+    /// Test bits (alias of `ANDS`)
+    ///
+    /// - `TST  <Xn>, #<imm>` — 64-bit immediate
+    /// - `TST  <Xn>, <Xm>{, <shift> #<amount>}` — 64-bit shifted register
+    ///
+    Tst,
     /// # UABA
     ///
     /// Unsigned Absolute difference and Accumulate
@@ -64570,6 +64754,13 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Uaddw,
+    /// # UBFIZ
+    ///
+    /// Unsigned Bitfield Insert in Zero (alias of `UBFM`)
+    ///
+    /// - `UBFIZ  <Xd>, <Xn>, #<lsb>, #<width>` — 64-bit
+    ///
+    Ubfiz,
     /// # UBFM
     ///
     /// Unsigned Bitfield Move
@@ -64661,6 +64852,13 @@ pub enum ArmStatement {
     /// - The values of the NZCV flags.
     ///
     Ubfm,
+    /// # UBFX
+    ///
+    /// Unsigned Bitfield Extract (alias of `UBFM`)
+    ///
+    /// - `UBFX  <Xd>, <Xn>, #<lsb>, #<width>` — 64-bit
+    ///
+    Ubfx,
     /// # UCVTF (vector, fixed-point)
     ///
     /// Unsigned fixed-point Convert to Floating-point (vector)
@@ -68376,6 +68574,13 @@ pub enum ArmStatement {
     /// ```
     ///
     Uxtb,
+    /// # UXTH
+    ///
+    /// Unsigned Extend Halfword (alias of `UBFM`)
+    ///
+    /// - `UXTH  <Wd>, <Wn>` — 32-bit
+    ///
+    Uxth,
     /// # UZP1
     ///
     /// Unzip vectors (primary)
@@ -1,6 +1,6 @@
 pub mod arm;
 pub mod x64;
-pub use arm::{ArmRegister, ArmStatement};
+pub use arm::{ArmCondition, ArmRegister, ArmShift, ArmStatement};
 pub use x64::{register::X64Register, statement::X64Statement};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,11 +30,41 @@ pub struct Instruction {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Statement {
     Arm(ArmStatement),
     X64(X64Statement),
 }
+
+/// Statement kept by its name, as formats like bitcode encode at most 256 variants of an enum
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+enum StatementName {
+    Arm(String),
+    X64(String),
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Statement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Statement::Arm(statement) => StatementName::Arm(statement.name().to_owned()),
+            Statement::X64(statement) => StatementName::X64(statement.name().to_owned()),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Statement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let statement = match StatementName::deserialize(deserializer)? {
+            StatementName::Arm(name) => name.parse().map(Statement::Arm).map_err(|_| name),
+            StatementName::X64(name) => name.parse().map(Statement::X64).map_err(|_| name),
+        };
+        statement.map_err(|name| serde::de::Error::custom(format!("unknown statement {name}")))
+    }
+}
+
 pub trait StatementInner {
     fn is_jcc(&self) -> bool;
     fn is_jmp(&self) -> bool;
@@ -48,6 +78,11 @@ pub enum Argument {
     Register(Register),
     Constant(u64),
     Memory(Memory),
+    /// Condition code operand (e.g. `eq` of `csel x0, x1, x2, eq`)
+    Condition(ArmCondition),
+    /// Register shifted or extended by an amount before use
+    /// (e.g. `x2, lsl #2` of `add x0, x1, x2, lsl #2`)
+    ShiftedRegister(Register, ArmShift, u8),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -63,6 +98,8 @@ pub enum RelativeAddressingArgument {
     Register(Register),
     Constant(i128),
     Operator(AddressingOperator),
+    /// Index register extended before use (e.g. `w2, sxtw` of `[x1, w2, sxtw #3]`)
+    ExtendedRegister(Register, ArmShift),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -163,7 +200,15 @@ pub fn parse_argument(
 
 impl std::fmt::Display for Instruction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Ok(statement) = self.statement {
+        if let Ok(Statement::Arm(ArmStatement::Bcond)) = self.statement
+            && let Some((Argument::Condition(condition), arguments)) = self.arguments.split_last()
+        {
+            write!(f, "b.{}", condition)?;
+            for arg in arguments.iter() {
+                write!(f, " {}", arg)?;
+            }
+            Ok(())
+        } else if let Ok(statement) = self.statement {
             write!(f, "{}", statement)?;
             for arg in self.arguments.iter() {
                 write!(f, " {}", arg)?;
@@ -193,6 +238,10 @@ impl std::fmt::Display for Argument {
             Argument::Register(register) => write!(f, "{}", register),
             Argument::Constant(constant) => write!(f, "0x{:X}", constant),
             Argument::Memory(memory) => write!(f, "{}", memory),
+            Argument::Condition(condition) => write!(f, "{}", condition),
+            Argument::ShiftedRegister(register, shift, amount) => {
+                write!(f, "{}, {} #{}", register, shift, amount)
+            }
         }
     }
 }
@@ -224,6 +273,9 @@ impl std::fmt::Display for RelativeAddressingArgument {
             RelativeAddressingArgument::Register(register) => write!(f, "{}", register),
             RelativeAddressingArgument::Constant(constant) => write!(f, "0x{:X}", constant),
             RelativeAddressingArgument::Operator(operator) => write!(f, "{}", operator),
+            RelativeAddressingArgument::ExtendedRegister(register, shift) => {
+                write!(f, "{} {}", register, shift)
+            }
         }
     }
 }